@@ -1,9 +1,10 @@
 #!/usr/bin/env bash
-# Installs Geth binary
+# Installs Geth and Reth binaries
 # Note: intended for use only with CI (x86_64 Ubuntu, MacOS or Windows)
 set -e
 
 GETH_BUILD=${GETH_BUILD:-"1.14.0-87246f3c"}
+RETH_BUILD=${RETH_BUILD:-"v0.2.0-beta.6"}
 
 BIN_DIR=${BIN_DIR:-"$HOME/bin"}
 
@@ -18,10 +19,14 @@ main() {
     fi
 
     install_geth
+    install_reth
 
     echo ""
     echo "Installed Geth:"
     geth version
+    echo ""
+    echo "Installed Reth:"
+    reth --version
 }
 
 # Installs geth from https://geth.ethereum.org/downloads
@@ -45,4 +50,15 @@ install_geth() {
     esac
 }
 
+# Installs reth from https://github.com/paradigmxyz/reth/releases
+install_reth() {
+    case "$PLATFORM" in
+        linux) target="x86_64-unknown-linux-gnu" ;;
+        darwin) target="x86_64-apple-darwin" ;;
+        *) target="x86_64-pc-windows-gnu" ;;
+    esac
+    name="reth-$RETH_BUILD-$target.tar.gz"
+    curl -sL "https://github.com/paradigmxyz/reth/releases/download/$RETH_BUILD/$name" | tar -xzf -
+}
+
 main
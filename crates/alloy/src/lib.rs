@@ -129,6 +129,10 @@ pub mod rpc {
     #[doc(inline)]
     pub use alloy_rpc_client as client;
 
+    #[cfg(feature = "rpc-client-beacon")]
+    #[doc(inline)]
+    pub use alloy_beacon_client as beacon_client;
+
     #[cfg(feature = "json-rpc")]
     #[doc(inline)]
     pub use alloy_json_rpc as json_rpc;
@@ -185,6 +189,10 @@ pub mod signers {
     #[cfg(feature = "signer-wallet")]
     #[doc(inline)]
     pub use alloy_signer_wallet as wallet;
+
+    #[cfg(feature = "signer-web3signer")]
+    #[doc(inline)]
+    pub use alloy_signer_web3signer as web3signer;
 }
 
 /// Low-level Ethereum JSON-RPC transport abstraction and implementations.
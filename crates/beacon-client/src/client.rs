@@ -0,0 +1,229 @@
+use alloy_eips::eip4844::BlobTransactionSidecar;
+use alloy_primitives::B256;
+use alloy_rpc_types_beacon::{
+    genesis::{GenesisData, GenesisResponse},
+    header::HeaderResponse,
+    sidecar::BeaconBlobBundle,
+    BlockId,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{fmt, sync::OnceLock};
+use thiserror::Error;
+use url::Url;
+
+/// Client of the [Beacon API] of a beacon node.
+///
+/// Beacon nodes keep the blobs of [EIP-4844] transactions for about 18 days, after execution
+/// clients have pruned them from their mempool. The sidecar of a transaction can be fetched with
+/// [`transaction_sidecar`](Self::transaction_sidecar), from the timestamp of its block and its
+/// blob versioned hashes.
+///
+/// TLS and timeouts are configured on the [`reqwest::Client`] used by the client.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy_beacon_client::{reqwest, BeaconClient};
+/// use alloy_primitives::b256;
+///
+/// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = BeaconClient::new(reqwest::Client::new(), "http://localhost:5052".parse()?);
+///
+/// let versioned_hash = b256!("01b0a4cdd5f55589f5c5b4d46c76704bb6ce95c0a8c09f77f197a57808dded28");
+/// let sidecar = client.transaction_sidecar(1_710_338_135, &[versioned_hash]).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [Beacon API]: https://ethereum.github.io/beacon-APIs
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+#[derive(Clone)]
+pub struct BeaconClient {
+    client: reqwest::Client,
+    url: Url,
+    genesis: OnceLock<GenesisData>,
+}
+
+impl fmt::Debug for BeaconClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BeaconClient")
+            .field("url", &self.url.as_str())
+            .field("genesis", &self.genesis.get())
+            .finish()
+    }
+}
+
+/// Errors thrown by [`BeaconClient`].
+#[derive(Debug, Error)]
+pub enum BeaconClientError {
+    /// Thrown on a request error.
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    /// Thrown when the URL of a request cannot be built.
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+    /// Thrown on an error response of the beacon node, such as a 404 for a missed slot.
+    #[error("beacon node error {code}: {message}")]
+    Api {
+        /// The HTTP status code.
+        code: u16,
+        /// The error message.
+        message: String,
+    },
+    /// Thrown when a timestamp is not the start of a slot of the beacon chain.
+    #[error("timestamp {0} is not the start of a slot")]
+    InvalidTimestamp(u64),
+}
+
+/// The body of error responses of the Beacon API.
+#[derive(Deserialize)]
+struct ApiError {
+    code: u16,
+    message: String,
+}
+
+impl BeaconClient {
+    /// Instantiate a new client for the beacon node at `url`.
+    pub const fn new(client: reqwest::Client, url: Url) -> Self {
+        Self { client, url, genesis: OnceLock::new() }
+    }
+
+    /// Returns the URL of the beacon node.
+    pub const fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the genesis of the beacon chain.
+    ///
+    /// The genesis is fetched from the beacon node on the first call, and cached afterwards.
+    #[instrument(skip(self), err)]
+    pub async fn genesis(&self) -> Result<GenesisData, BeaconClientError> {
+        if let Some(genesis) = self.genesis.get() {
+            return Ok(*genesis);
+        }
+        let response: GenesisResponse = self.get(endpoint(&self.url, "genesis", &[])?).await?;
+        Ok(*self.genesis.get_or_init(|| response.data))
+    }
+
+    /// Returns the header of the given block.
+    #[instrument(skip(self), err)]
+    pub async fn header(&self, block: BlockId) -> Result<HeaderResponse, BeaconClientError> {
+        self.get(endpoint(&self.url, &format!("headers/{block}"), &[])?).await
+    }
+
+    /// Returns the blob sidecars of the given block, or only those of the given indices if any.
+    #[instrument(skip(self), err)]
+    pub async fn blob_sidecars(
+        &self,
+        block: BlockId,
+        indices: &[u64],
+    ) -> Result<BeaconBlobBundle, BeaconClientError> {
+        self.get(endpoint(&self.url, &format!("blob_sidecars/{block}"), indices)?).await
+    }
+
+    /// Returns the sidecar of a blob transaction, from the timestamp of its block and its blob
+    /// versioned hashes.
+    ///
+    /// Returns `None` if the block does not hold all the blobs, for instance because the beacon
+    /// node has pruned them.
+    #[instrument(skip(self), err)]
+    pub async fn transaction_sidecar(
+        &self,
+        block_timestamp: u64,
+        versioned_hashes: &[B256],
+    ) -> Result<Option<BlobTransactionSidecar>, BeaconClientError> {
+        let genesis = self.genesis().await?;
+        let slot = genesis
+            .slot_at(block_timestamp)
+            .ok_or(BeaconClientError::InvalidTimestamp(block_timestamp))?;
+        let bundle = self.blob_sidecars(BlockId::Slot(slot), &[]).await?;
+        Ok(bundle.sidecar_for(versioned_hashes))
+    }
+
+    async fn get<T: DeserializeOwned>(&self, url: Url) -> Result<T, BeaconClientError> {
+        let response = self.client.get(url).send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.json().await?);
+        }
+
+        let body = response.text().await?;
+        let message = match serde_json::from_str::<ApiError>(&body) {
+            Ok(error) if error.code == status.as_u16() => error.message,
+            _ => body,
+        };
+        Err(BeaconClientError::Api { code: status.as_u16(), message })
+    }
+}
+
+/// Returns the URL of an endpoint of the `beacon` namespace of the beacon node at `url`.
+fn endpoint(url: &Url, path: &str, indices: &[u64]) -> Result<Url, url::ParseError> {
+    let base = url.as_str().trim_end_matches('/');
+    let mut url = Url::parse(&format!("{base}/eth/v1/beacon/{path}"))?;
+    if !indices.is_empty() {
+        let indices = indices.iter().map(u64::to_string).collect::<Vec<_>>().join(",");
+        url.query_pairs_mut().append_pair("indices", &indices);
+    }
+    Ok(url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    /// Serves the given responses, one per connection, and returns the URL of the server.
+    fn serve(responses: Vec<(u16, &'static str)>) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let _ = stream.read(&mut [0; 4096]).unwrap();
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn endpoints() {
+        let url = "http://beacon:5052/".parse().unwrap();
+        assert_eq!(
+            endpoint(&url, "genesis", &[]).unwrap().as_str(),
+            "http://beacon:5052/eth/v1/beacon/genesis"
+        );
+        assert_eq!(
+            endpoint(&url, &format!("blob_sidecars/{}", BlockId::Slot(7)), &[1, 3])
+                .unwrap()
+                .as_str(),
+            "http://beacon:5052/eth/v1/beacon/blob_sidecars/7?indices=1%2C3"
+        );
+    }
+
+    #[tokio::test]
+    async fn responses() {
+        let genesis = r#"{"data":{"genesis_time":"1606824023","genesis_validators_root":"0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95","genesis_fork_version":"0x00000000"}}"#;
+        let not_found = r#"{"code":404,"message":"NOT_FOUND: beacon block at slot 2"}"#;
+        let url = serve(vec![(200, genesis), (404, not_found)]);
+        let client = BeaconClient::new(reqwest::Client::new(), url);
+
+        assert_eq!(client.genesis().await.unwrap().genesis_time, 1606824023);
+        // The genesis is cached, so that the second request fetches the sidecars.
+        let err = client.transaction_sidecar(1606824023 + 24, &[B256::ZERO]).await.unwrap_err();
+        assert!(
+            matches!(&err, BeaconClientError::Api { code: 404, message } if message.contains("slot 2")),
+            "{err}"
+        );
+
+        let err = client.transaction_sidecar(1606824023 + 1, &[]).await.unwrap_err();
+        assert!(matches!(err, BeaconClientError::InvalidTimestamp(_)), "{err}");
+    }
+}
@@ -0,0 +1,212 @@
+//! Ethereum blocks, as encoded on the network and committed to by their header.
+
+use crate::{trie::ordered_trie_root, Header, Request, Sealable, TxEnvelope};
+use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawal, eip7685::Encodable7685};
+use alloy_primitives::{keccak256, B256};
+use alloy_rlp::{Decodable, Encodable};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// An Ethereum block: its header, and the body committed to by the header.
+///
+/// Blob transactions are expected without their sidecar, as they are included in blocks.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Block {
+    /// The header of the block.
+    pub header: Header,
+    /// The transactions of the block.
+    pub transactions: Vec<TxEnvelope>,
+    /// The headers of the ommers of the block.
+    pub ommers: Vec<Header>,
+    /// The withdrawals of the block, since Shanghai.
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// The [EIP-7685] requests of the block, since Prague.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    pub requests: Option<Vec<Request>>,
+}
+
+impl Block {
+    /// Computes the hash of the block, i.e. the hash of its header.
+    pub fn hash_slow(&self) -> B256 {
+        self.header.hash_slow()
+    }
+
+    /// Computes the transactions root of the block body, to compare with the one of the header.
+    pub fn calculate_transactions_root(&self) -> B256 {
+        let encoded: Vec<_> = self.transactions.iter().map(Encodable2718::encoded_2718).collect();
+        ordered_trie_root(&encoded)
+    }
+
+    /// Computes the ommers hash of the block body, to compare with the one of the header.
+    pub fn calculate_ommers_hash(&self) -> B256 {
+        keccak256(alloy_rlp::encode(&self.ommers))
+    }
+
+    /// Computes the withdrawals root of the block body, to compare with the one of the header.
+    ///
+    /// Returns `None` if the block has no withdrawals, i.e. is pre-Shanghai.
+    pub fn calculate_withdrawals_root(&self) -> Option<B256> {
+        let withdrawals = self.withdrawals.as_ref()?;
+        let encoded: Vec<_> = withdrawals.iter().map(alloy_rlp::encode).collect();
+        Some(ordered_trie_root(&encoded))
+    }
+
+    /// Computes the [EIP-7685] requests root of the block body, to compare with the one of the
+    /// header.
+    ///
+    /// Returns `None` if the block has no requests, i.e. is pre-Prague.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    pub fn calculate_requests_root(&self) -> Option<B256> {
+        let requests = self.requests.as_ref()?;
+        let encoded: Vec<_> = requests.iter().map(Encodable7685::encoded_7685).collect();
+        Some(ordered_trie_root(&encoded))
+    }
+
+    /// Returns whether the transactions root, ommers hash, withdrawals root and requests root of
+    /// the header match the body of the block.
+    pub fn body_matches_header(&self) -> bool {
+        self.calculate_transactions_root() == self.header.transactions_root
+            && self.calculate_ommers_hash() == self.header.ommers_hash
+            && self.calculate_withdrawals_root() == self.header.withdrawals_root
+            && self.calculate_requests_root() == self.header.requests_root
+    }
+
+    fn payload_length(&self) -> usize {
+        let mut length = self.header.length() + self.transactions.length() + self.ommers.length();
+        if let Some(withdrawals) = &self.withdrawals {
+            length += withdrawals.length();
+        }
+        if let Some(requests) = &self.requests {
+            length += requests.length();
+        }
+        length
+    }
+}
+
+impl Sealable for Block {
+    fn hash(&self) -> B256 {
+        self.hash_slow()
+    }
+}
+
+impl Encodable for Block {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        alloy_rlp::Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.header.encode(out);
+        self.transactions.encode(out);
+        self.ommers.encode(out);
+        if let Some(withdrawals) = &self.withdrawals {
+            withdrawals.encode(out);
+        }
+        if let Some(requests) = &self.requests {
+            requests.encode(out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + alloy_rlp::length_of_length(payload_length)
+    }
+}
+
+impl Decodable for Block {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let rlp_head = alloy_rlp::Header::decode(buf)?;
+        if !rlp_head.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let started_len = buf.len();
+        let mut this = Self {
+            header: Decodable::decode(buf)?,
+            transactions: Decodable::decode(buf)?,
+            ommers: Decodable::decode(buf)?,
+            withdrawals: None,
+            requests: None,
+        };
+        if started_len - buf.len() < rlp_head.payload_length {
+            this.withdrawals = Some(Decodable::decode(buf)?);
+        }
+        if started_len - buf.len() < rlp_head.payload_length {
+            this.requests = Some(Decodable::decode(buf)?);
+        }
+
+        let consumed = started_len - buf.len();
+        if consumed != rlp_head.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: rlp_head.payload_length,
+                got: consumed,
+            });
+        }
+        Ok(this)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_primitives::{address, hex};
+
+    #[test]
+    fn block_rlp_roundtrip() {
+        let transactions = [
+            // https://etherscan.io/tx/0x280cde7cdefe4b188750e76c888f13bd05ce9a4d7767730feefe8a0e50ca6fc4
+            hex!("f9015482078b8505d21dba0083022ef1947a250d5630b4cf539739df2c5dacb4c659f2488d880c46549a521b13d8b8e47ff36ab50000000000000000000000000000000000000000000066ab5a608bd00a23f2fe000000000000000000000000000000000000000000000000000000000000008000000000000000000000000048c04ed5691981c42154c6167398f95e8f38a7ff00000000000000000000000000000000000000000000000000000000632ceac70000000000000000000000000000000000000000000000000000000000000002000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc20000000000000000000000006c6ee5e31d828de241282b9606c8e98ea48526e225a0c9077369501641a92ef7399ff81c21639ed4fd8fc69cb793cfa1dbfab342e10aa0615facb2f1bcf3274a354cfe384a38d0cc008a11c2dd23a69111bc6930ba27a8").as_slice(),
+            // https://etherscan.io/tx/0xce4dc6d7a7549a98ee3b071b67e970879ff51b5b95d1c340bacd80fa1e1aab31
+            hex!("02f86f0102843b9aca0085029e7822d68298f094d9e1459a7a482635700cbc20bbaf52d495ab9c9680841b55ba3ac080a0c199674fcb29f353693dd779c017823b954b3c69dffa3cd6b2a6ff7888798039a028ca912de909e7e6cdef9cdcaf24c54dd8c1032946dfa1d85c206b32a9064fe8").as_slice(),
+        ]
+        .into_iter()
+        .map(|raw| TxEnvelope::decode_2718(&mut &raw[..]).unwrap())
+        .collect();
+        let withdrawal = Withdrawal {
+            index: 1,
+            validator_index: 2,
+            address: address!("7cd1122e8e118b12ece8d25480dfeef230da17ff"),
+            amount: 3,
+        };
+        let mut block = Block {
+            header: Header { number: 1, ..Default::default() },
+            transactions,
+            ommers: Vec::new(),
+            withdrawals: Some(vec![withdrawal]),
+            requests: None,
+        };
+        assert!(!block.body_matches_header());
+        block.header.transactions_root = block.calculate_transactions_root();
+        block.header.ommers_hash = block.calculate_ommers_hash();
+        block.header.withdrawals_root = block.calculate_withdrawals_root();
+        assert!(block.body_matches_header());
+        assert_eq!(block.header.ommers_hash, crate::EMPTY_OMMER_ROOT_HASH);
+
+        let encoded = alloy_rlp::encode(&block);
+        assert_eq!(encoded.len(), block.length());
+        let decoded = Block::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, block);
+        assert_eq!(decoded.seal_slow().hash(), block.header.hash_slow());
+
+        let request = alloy_eips::eip7002::WithdrawalRequest {
+            source_address: withdrawal.address,
+            validator_public_key: Default::default(),
+            amount: 3,
+        };
+        block.requests = Some(vec![Request::WithdrawalRequest(request)]);
+        block.header.blob_gas_used = Some(0);
+        block.header.excess_blob_gas = Some(0);
+        block.header.parent_beacon_block_root = Some(B256::ZERO);
+        assert!(!block.body_matches_header());
+        block.header.requests_root = block.calculate_requests_root();
+        assert!(block.body_matches_header());
+        block.header.requests_root = Some(crate::EMPTY_ROOT_HASH);
+        assert!(!block.body_matches_header());
+        let encoded = alloy_rlp::encode(&block);
+        assert_eq!(Block::decode(&mut encoded.as_slice()).unwrap(), block);
+
+        let pre_shanghai = Block { withdrawals: None, requests: None, ..block };
+        let encoded = alloy_rlp::encode(&pre_shanghai);
+        assert_eq!(Block::decode(&mut encoded.as_slice()).unwrap(), pre_shanghai);
+        assert!(!pre_shanghai.body_matches_header());
+    }
+}
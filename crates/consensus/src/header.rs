@@ -92,6 +92,9 @@ pub struct Header {
     /// The Keccak 256-bit hash of the root node of the trie structure populated with each
     /// [EIP-7685] request in the block body.
     ///
+    /// Later revisions of EIP-7685 replace this root with a flat SHA-256 hash of the requests, as
+    /// computed by [`requests_hash`](alloy_eips::eip7685::requests_hash), in the same field.
+    ///
     /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
     pub requests_root: Option<B256>,
     /// An arbitrary byte array containing data relevant to this block. This must be 32 bytes or
@@ -481,7 +484,7 @@ impl Decodable for Header {
         //    post-London, so this is technically not valid. However, a tool like proptest would
         //    generate a block like this.
         if started_len - buf.len() < rlp_head.payload_length {
-            this.parent_beacon_block_root = Some(B256::decode(buf)?);
+            this.requests_root = Some(B256::decode(buf)?);
         }
 
         let consumed = started_len - buf.len();
@@ -19,13 +19,18 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+mod block;
+pub use block::Block;
+
 pub mod constants;
 
 mod header;
 pub use header::{Header, EMPTY_OMMER_ROOT_HASH, EMPTY_ROOT_HASH};
 
 mod receipt;
-pub use receipt::{AnyReceiptEnvelope, Receipt, ReceiptEnvelope, ReceiptWithBloom, TxReceipt};
+pub use receipt::{
+    AnyReceiptEnvelope, Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom, TxReceipt,
+};
 
 /// Transaction types
 pub mod transaction;
@@ -46,8 +51,20 @@ pub use transaction::BlobTransactionValidationError;
 #[cfg(feature = "kzg")]
 pub use alloy_eips::eip4844::env_settings::EnvKzgSettings;
 
+/// OP-stack types
+#[cfg(feature = "optimism")]
+pub mod optimism;
+
+#[cfg(feature = "serde-bincode-compat")]
+pub mod serde_bincode_compat;
+
+mod request;
+pub use request::Request;
+
 mod sealed;
 pub use sealed::{Sealable, Sealed};
 
 mod signed;
 pub use signed::Signed;
+
+pub mod trie;
@@ -0,0 +1,238 @@
+use super::DEPOSIT_TX_TYPE_ID;
+use crate::Transaction;
+use alloy_primitives::{keccak256, Address, Bytes, ChainId, TxKind, B256, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable, Header, EMPTY_STRING_CODE};
+use core::mem;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// An OP-stack deposit transaction, with type `0x7e`.
+///
+/// Deposits are derived from L1 by the rollup node, and are not signed: the sender is
+/// authenticated by the L1 transaction or event that caused the deposit. See the
+/// [specification](https://specs.optimism.io/protocol/deposits.html#the-deposited-transaction-type).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TxDeposit {
+    /// Hash that uniquely identifies the source of the deposit.
+    pub source_hash: B256,
+    /// The address of the sender account.
+    pub from: Address,
+    /// The address of the recipient account, or the null (zero-length) address if the deposited
+    /// transaction is a contract creation.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "TxKind::is_create"))]
+    pub to: TxKind,
+    /// The ETH value to mint on L2.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::u128_opt_via_ruint"
+        )
+    )]
+    pub mint: Option<u128>,
+    /// The ETH value to send to the recipient account.
+    pub value: U256,
+    /// The gas limit for the L2 transaction.
+    #[cfg_attr(feature = "serde", serde(rename = "gas", with = "alloy_serde::u128_via_ruint"))]
+    pub gas_limit: u128,
+    /// Whether the transaction is a system transaction, exempt from the L2 gas limit.
+    #[cfg_attr(feature = "serde", serde(rename = "isSystemTx"))]
+    pub is_system_transaction: bool,
+    /// The calldata of the transaction.
+    pub input: Bytes,
+}
+
+impl TxDeposit {
+    /// Decodes the inner [`TxDeposit`] fields from RLP bytes.
+    ///
+    /// NOTE: This assumes a RLP header has already been decoded, and _just_ decodes the following
+    /// RLP fields in the following order:
+    ///
+    /// - `source_hash`
+    /// - `from`
+    /// - `to`
+    /// - `mint`
+    /// - `value`
+    /// - `gas_limit`
+    /// - `is_system_transaction`
+    /// - `input`
+    pub(crate) fn decode_fields(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self {
+            source_hash: Decodable::decode(buf)?,
+            from: Decodable::decode(buf)?,
+            to: Decodable::decode(buf)?,
+            mint: match buf.first() {
+                Some(&EMPTY_STRING_CODE) => {
+                    *buf = &buf[1..];
+                    None
+                }
+                _ => Some(Decodable::decode(buf)?),
+            },
+            value: Decodable::decode(buf)?,
+            gas_limit: Decodable::decode(buf)?,
+            is_system_transaction: Decodable::decode(buf)?,
+            input: Decodable::decode(buf)?,
+        })
+    }
+
+    /// Outputs the length of the transaction's fields, without a RLP header.
+    #[doc(hidden)]
+    pub fn fields_len(&self) -> usize {
+        self.source_hash.length()
+            + self.from.length()
+            + self.to.length()
+            + self.mint.map_or(1, |mint| mint.length())
+            + self.value.length()
+            + self.gas_limit.length()
+            + self.is_system_transaction.length()
+            + self.input.0.length()
+    }
+
+    /// Encodes only the transaction's fields into the desired buffer, without a RLP header.
+    pub(crate) fn encode_fields(&self, out: &mut dyn BufMut) {
+        self.source_hash.encode(out);
+        self.from.encode(out);
+        self.to.encode(out);
+        match self.mint {
+            Some(mint) => mint.encode(out),
+            None => out.put_u8(EMPTY_STRING_CODE),
+        }
+        self.value.encode(out);
+        self.gas_limit.encode(out);
+        self.is_system_transaction.encode(out);
+        self.input.0.encode(out);
+    }
+
+    /// Returns the length of the RLP list of the transaction's fields, including its header.
+    pub(crate) fn rlp_encoded_length(&self) -> usize {
+        let payload_length = self.fields_len();
+        Header { list: true, payload_length }.length() + payload_length
+    }
+
+    /// Decodes the transaction from RLP bytes, not including the transaction type byte.
+    #[doc(hidden)]
+    pub fn decode_inner(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+
+        // record original length so we can check encoding
+        let original_len = buf.len();
+        let tx = Self::decode_fields(buf)?;
+        if buf.len() + header.payload_length != original_len {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: original_len - buf.len(),
+            });
+        }
+
+        Ok(tx)
+    }
+
+    /// Calculates the hash of the transaction, the Keccak-256 hash of its [EIP-2718] encoding.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    pub fn tx_hash(&self) -> B256 {
+        let mut buf = Vec::with_capacity(1 + self.rlp_encoded_length());
+        buf.put_u8(DEPOSIT_TX_TYPE_ID);
+        self.encode(&mut buf);
+        keccak256(&buf)
+    }
+
+    /// Calculates a heuristic for the in-memory size of the [`TxDeposit`] transaction.
+    #[inline]
+    pub fn size(&self) -> usize {
+        mem::size_of::<B256>() + // source_hash
+        mem::size_of::<Address>() + // from
+        self.to.size() + // to
+        mem::size_of::<Option<u128>>() + // mint
+        mem::size_of::<U256>() + // value
+        mem::size_of::<u128>() + // gas_limit
+        mem::size_of::<bool>() + // is_system_transaction
+        self.input.len() // input
+    }
+}
+
+impl Transaction for TxDeposit {
+    fn input(&self) -> &[u8] {
+        &self.input
+    }
+
+    fn to(&self) -> TxKind {
+        self.to
+    }
+
+    fn value(&self) -> U256 {
+        self.value
+    }
+
+    fn chain_id(&self) -> Option<ChainId> {
+        None
+    }
+
+    fn nonce(&self) -> u64 {
+        0
+    }
+
+    fn gas_limit(&self) -> u128 {
+        self.gas_limit
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        None
+    }
+}
+
+impl Encodable for TxDeposit {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.fields_len() }.encode(out);
+        self.encode_fields(out);
+    }
+
+    fn length(&self) -> usize {
+        self.rlp_encoded_length()
+    }
+}
+
+impl Decodable for TxDeposit {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Self::decode_inner(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, hex};
+
+    #[test]
+    fn encode_decode_deposit() {
+        let tx = TxDeposit {
+            source_hash: b256!("0000000000000000000000000000000000000000000000000000000000000001"),
+            from: address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001"),
+            to: TxKind::Call(address!("4200000000000000000000000000000000000015")),
+            mint: None,
+            value: U256::ZERO,
+            gas_limit: 1_000_000,
+            is_system_transaction: false,
+            input: Bytes::from_static(&hex!("015d8eb9")),
+        };
+
+        let mut buf = Vec::new();
+        tx.encode(&mut buf);
+        assert_eq!(buf.len(), tx.length());
+        assert_eq!(TxDeposit::decode(&mut buf.as_slice()).unwrap(), tx);
+
+        let minted = TxDeposit { mint: Some(1), to: TxKind::Create, ..tx.clone() };
+        let mut buf = Vec::new();
+        minted.encode(&mut buf);
+        assert_eq!(buf.len(), minted.length());
+        assert_eq!(TxDeposit::decode(&mut buf.as_slice()).unwrap(), minted);
+        assert_ne!(minted.tx_hash(), tx.tx_hash());
+    }
+}
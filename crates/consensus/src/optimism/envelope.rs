@@ -0,0 +1,316 @@
+use super::{TxDeposit, DEPOSIT_TX_TYPE_ID};
+use crate::{Signed, TxEip1559, TxEip2930, TxEnvelope, TxLegacy};
+use alloy_eips::eip2718::{Decodable2718, Eip2718Error, Encodable2718};
+use alloy_primitives::B256;
+use alloy_rlp::{Decodable, Encodable, Header};
+use core::fmt;
+
+/// OP-stack `TransactionType` flags: the Ethereum types supported by the OP stack, and the
+/// [deposit](TxDeposit) type.
+///
+/// EIP-4844 blob transactions are not supported on L2.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpTxType {
+    /// Legacy transaction type.
+    Legacy = 0,
+    /// EIP-2930 transaction type.
+    Eip2930 = 1,
+    /// EIP-1559 transaction type.
+    Eip1559 = 2,
+    /// Deposit transaction type.
+    Deposit = DEPOSIT_TX_TYPE_ID,
+}
+
+impl From<OpTxType> for u8 {
+    fn from(value: OpTxType) -> Self {
+        value as u8
+    }
+}
+
+impl fmt::Display for OpTxType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Legacy => write!(f, "Legacy"),
+            Self::Eip2930 => write!(f, "EIP-2930"),
+            Self::Eip1559 => write!(f, "EIP-1559"),
+            Self::Deposit => write!(f, "Deposit"),
+        }
+    }
+}
+
+impl TryFrom<u8> for OpTxType {
+    type Error = Eip2718Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Legacy,
+            1 => Self::Eip2930,
+            2 => Self::Eip1559,
+            DEPOSIT_TX_TYPE_ID => Self::Deposit,
+            _ => return Err(Eip2718Error::UnexpectedType(value)),
+        })
+    }
+}
+
+/// The OP-stack [EIP-2718] Transaction Envelope.
+///
+/// Like [`TxEnvelope`], this distinguishes between tagged and untagged legacy transactions.
+/// [Deposits](TxDeposit) are not signed, so they are stored without a signature.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[non_exhaustive]
+pub enum OpTxEnvelope {
+    /// An untagged [`TxLegacy`].
+    #[cfg_attr(feature = "serde", serde(rename = "0x0", alias = "0x00"))]
+    Legacy(Signed<TxLegacy>),
+    /// A [`TxEip2930`] tagged with type 1.
+    #[cfg_attr(feature = "serde", serde(rename = "0x1", alias = "0x01"))]
+    Eip2930(Signed<TxEip2930>),
+    /// A [`TxEip1559`] tagged with type 2.
+    #[cfg_attr(feature = "serde", serde(rename = "0x2", alias = "0x02"))]
+    Eip1559(Signed<TxEip1559>),
+    /// A [`TxDeposit`] tagged with type `0x7e`.
+    #[cfg_attr(feature = "serde", serde(rename = "0x7e", alias = "0x7E"))]
+    Deposit(TxDeposit),
+}
+
+impl From<Signed<TxLegacy>> for OpTxEnvelope {
+    fn from(v: Signed<TxLegacy>) -> Self {
+        Self::Legacy(v)
+    }
+}
+
+impl From<Signed<TxEip2930>> for OpTxEnvelope {
+    fn from(v: Signed<TxEip2930>) -> Self {
+        Self::Eip2930(v)
+    }
+}
+
+impl From<Signed<TxEip1559>> for OpTxEnvelope {
+    fn from(v: Signed<TxEip1559>) -> Self {
+        Self::Eip1559(v)
+    }
+}
+
+impl From<TxDeposit> for OpTxEnvelope {
+    fn from(v: TxDeposit) -> Self {
+        Self::Deposit(v)
+    }
+}
+
+impl TryFrom<TxEnvelope> for OpTxEnvelope {
+    type Error = Eip2718Error;
+
+    /// Converts an Ethereum envelope, failing for transaction types not supported on L2.
+    fn try_from(envelope: TxEnvelope) -> Result<Self, Self::Error> {
+        match envelope {
+            TxEnvelope::Legacy(tx) => Ok(Self::Legacy(tx)),
+            TxEnvelope::Eip2930(tx) => Ok(Self::Eip2930(tx)),
+            TxEnvelope::Eip1559(tx) => Ok(Self::Eip1559(tx)),
+            envelope => Err(Eip2718Error::UnexpectedType(envelope.tx_type().into())),
+        }
+    }
+}
+
+impl OpTxEnvelope {
+    /// Recover the signer of the transaction. For deposits, this is the sender.
+    #[cfg(feature = "k256")]
+    pub fn recover_signer(
+        &self,
+    ) -> Result<alloy_primitives::Address, alloy_primitives::SignatureError> {
+        match self {
+            Self::Legacy(tx) => tx.recover_signer(),
+            Self::Eip2930(tx) => tx.recover_signer(),
+            Self::Eip1559(tx) => tx.recover_signer(),
+            Self::Deposit(tx) => Ok(tx.from),
+        }
+    }
+
+    /// Return the hash of the transaction.
+    pub fn tx_hash(&self) -> B256 {
+        match self {
+            Self::Legacy(tx) => *tx.hash(),
+            Self::Eip2930(tx) => *tx.hash(),
+            Self::Eip1559(tx) => *tx.hash(),
+            Self::Deposit(tx) => tx.tx_hash(),
+        }
+    }
+
+    /// Return the [`OpTxType`] of the inner txn.
+    pub const fn tx_type(&self) -> OpTxType {
+        match self {
+            Self::Legacy(_) => OpTxType::Legacy,
+            Self::Eip2930(_) => OpTxType::Eip2930,
+            Self::Eip1559(_) => OpTxType::Eip1559,
+            Self::Deposit(_) => OpTxType::Deposit,
+        }
+    }
+
+    /// Returns `true` if the transaction is a deposit.
+    pub const fn is_deposit(&self) -> bool {
+        matches!(self, Self::Deposit(_))
+    }
+
+    /// Return the length of the inner txn, __without a type byte__.
+    pub fn inner_length(&self) -> usize {
+        match self {
+            Self::Legacy(t) => {
+                let payload_length = t.tx().fields_len() + t.signature().rlp_vrs_len();
+                Header { list: true, payload_length }.length() + payload_length
+            }
+            Self::Eip2930(t) => {
+                let payload_length = t.tx().fields_len() + t.signature().rlp_vrs_len();
+                Header { list: true, payload_length }.length() + payload_length
+            }
+            Self::Eip1559(t) => {
+                let payload_length = t.tx().fields_len() + t.signature().rlp_vrs_len();
+                Header { list: true, payload_length }.length() + payload_length
+            }
+            Self::Deposit(t) => t.rlp_encoded_length(),
+        }
+    }
+}
+
+impl Encodable for OpTxEnvelope {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        self.network_encode(out)
+    }
+
+    fn length(&self) -> usize {
+        let mut payload_length = self.encode_2718_len();
+        if !self.is_legacy() {
+            payload_length += Header { list: false, payload_length }.length();
+        }
+
+        payload_length
+    }
+}
+
+impl Decodable for OpTxEnvelope {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Self::network_decode(buf)
+    }
+}
+
+impl Decodable2718 for OpTxEnvelope {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match ty.try_into().map_err(|_| alloy_rlp::Error::Custom("unexpected tx type"))? {
+            OpTxType::Eip2930 => Ok(TxEip2930::decode_signed_fields(buf)?.into()),
+            OpTxType::Eip1559 => Ok(TxEip1559::decode_signed_fields(buf)?.into()),
+            OpTxType::Deposit => Ok(TxDeposit::decode_inner(buf)?.into()),
+            OpTxType::Legacy => {
+                Err(alloy_rlp::Error::Custom("type-0 eip2718 transactions are not supported"))
+            }
+        }
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(TxLegacy::decode_signed_fields(buf)?.into())
+    }
+}
+
+impl Encodable2718 for OpTxEnvelope {
+    fn type_flag(&self) -> Option<u8> {
+        match self {
+            Self::Legacy(_) => None,
+            ty => Some(ty.tx_type().into()),
+        }
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        self.inner_length() + !self.is_legacy() as usize
+    }
+
+    fn encode_2718(&self, out: &mut dyn alloy_rlp::BufMut) {
+        match self {
+            // Legacy transactions have no difference between network and 2718
+            Self::Legacy(tx) => tx.tx().encode_with_signature_fields(tx.signature(), out),
+            Self::Eip2930(tx) => {
+                tx.tx().encode_with_signature(tx.signature(), out, false);
+            }
+            Self::Eip1559(tx) => {
+                tx.tx().encode_with_signature(tx.signature(), out, false);
+            }
+            Self::Deposit(tx) => {
+                out.put_u8(DEPOSIT_TX_TYPE_ID);
+                tx.encode(out);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SignableTransaction;
+    use alloy_primitives::{address, b256, Address, Bytes, Signature, TxKind, U256};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    fn roundtrip(envelope: OpTxEnvelope) {
+        let encoded = envelope.encoded_2718();
+        assert_eq!(encoded.len(), envelope.encode_2718_len());
+        assert_eq!(OpTxEnvelope::decode_2718(&mut encoded.as_slice()).unwrap(), envelope);
+
+        let mut network = Vec::new();
+        envelope.encode(&mut network);
+        assert_eq!(network.len(), envelope.length());
+        assert_eq!(OpTxEnvelope::decode(&mut network.as_slice()).unwrap(), envelope);
+    }
+
+    #[test]
+    fn encode_decode_envelopes() {
+        let deposit = TxDeposit {
+            source_hash: b256!("0000000000000000000000000000000000000000000000000000000000000001"),
+            from: address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001"),
+            to: TxKind::Call(address!("4200000000000000000000000000000000000015")),
+            mint: Some(1_000),
+            value: U256::from(1_000),
+            gas_limit: 1_000_000,
+            is_system_transaction: true,
+            input: Bytes::from_static(&[0x01, 0x02]),
+        };
+        let envelope = OpTxEnvelope::from(deposit.clone());
+        assert_eq!(envelope.tx_type(), OpTxType::Deposit);
+        assert_eq!(envelope.encoded_2718()[0], DEPOSIT_TX_TYPE_ID);
+        assert_eq!(envelope.tx_hash(), deposit.tx_hash());
+        roundtrip(envelope);
+
+        let tx = TxEip1559 {
+            chain_id: 10,
+            nonce: 1,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::ZERO),
+            max_fee_per_gas: 2,
+            max_priority_fee_per_gas: 1,
+            ..Default::default()
+        };
+        roundtrip(tx.into_signed(Signature::test_signature()).into());
+
+        let legacy = TxLegacy { gas_limit: 21_000, ..Default::default() };
+        roundtrip(legacy.into_signed(Signature::test_signature()).into());
+    }
+
+    #[test]
+    fn tx_type() {
+        assert_eq!(OpTxType::try_from(0x7e).unwrap(), OpTxType::Deposit);
+        assert!(OpTxType::try_from(3).is_err());
+        assert_eq!(u8::from(OpTxType::Deposit), DEPOSIT_TX_TYPE_ID);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_deposit() {
+        let json = r#"{"type":"0x7e","sourceHash":"0x0000000000000000000000000000000000000000000000000000000000000001","from":"0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001","to":"0x4200000000000000000000000000000000000015","mint":"0x3e8","value":"0x0","gas":"0xf4240","isSystemTx":false,"input":"0x"}"#;
+        let envelope: OpTxEnvelope = serde_json::from_str(json).unwrap();
+        let OpTxEnvelope::Deposit(deposit) = &envelope else { panic!("not a deposit") };
+        assert_eq!(deposit.mint, Some(1_000));
+        assert_eq!(deposit.gas_limit, 1_000_000);
+        assert_eq!(serde_json::to_string(&envelope).unwrap(), json);
+    }
+}
@@ -0,0 +1,20 @@
+//! OP-stack consensus types.
+//!
+//! The OP stack supports the Ethereum transaction types up to EIP-1559, and adds the
+//! [deposit](TxDeposit) transaction type, used to execute on L2 the transactions and events
+//! initiated on L1.
+
+mod deposit;
+pub use deposit::TxDeposit;
+
+mod envelope;
+pub use envelope::{OpTxEnvelope, OpTxType};
+
+mod receipt;
+pub use receipt::{OpDepositReceipt, OpDepositReceiptWithBloom, OpReceiptEnvelope};
+
+mod typed;
+pub use typed::OpTypedTransaction;
+
+/// Identifier for the deposit transaction type.
+pub const DEPOSIT_TX_TYPE_ID: u8 = 0x7e;
@@ -0,0 +1,354 @@
+use super::OpTxType;
+use crate::{Eip658Value, Receipt, ReceiptWithBloom, TxReceipt};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_primitives::{Bloom, Log};
+use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable, Header};
+
+/// Receipt of a [deposit](super::TxDeposit) transaction.
+///
+/// The deposit nonce was added by the Regolith upgrade, and the receipt version by Canyon.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OpDepositReceipt<T = Log> {
+    /// The Ethereum receipt.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub inner: Receipt<T>,
+    /// The nonce of the sender before the deposit.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::u64_opt_via_ruint"
+        )
+    )]
+    pub deposit_nonce: Option<u64>,
+    /// The version of the receipt encoding, set since Canyon.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            with = "alloy_serde::u64_opt_via_ruint"
+        )
+    )]
+    pub deposit_receipt_version: Option<u64>,
+}
+
+/// [`OpDepositReceipt`] with calculated bloom filter.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct OpDepositReceiptWithBloom<T = Log> {
+    /// The receipt.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub receipt: OpDepositReceipt<T>,
+    /// The bloom filter.
+    pub logs_bloom: Bloom,
+}
+
+impl<T: Encodable> OpDepositReceiptWithBloom<T> {
+    fn payload_len(&self) -> usize {
+        self.receipt.inner.status.length()
+            + self.receipt.inner.cumulative_gas_used.length()
+            + self.logs_bloom.length()
+            + self.receipt.inner.logs.length()
+            + self.receipt.deposit_nonce.map_or(0, |nonce| nonce.length())
+            + self.receipt.deposit_receipt_version.map_or(0, |version| version.length())
+    }
+}
+
+impl<T: Encodable> Encodable for OpDepositReceiptWithBloom<T> {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_len() }.encode(out);
+        self.receipt.inner.status.encode(out);
+        self.receipt.inner.cumulative_gas_used.encode(out);
+        self.logs_bloom.encode(out);
+        self.receipt.inner.logs.encode(out);
+        if let Some(nonce) = self.receipt.deposit_nonce {
+            nonce.encode(out);
+        }
+        if let Some(version) = self.receipt.deposit_receipt_version {
+            version.encode(out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_len();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl<T: Decodable> Decodable for OpDepositReceiptWithBloom<T> {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString);
+        }
+        let started_len = buf.len();
+        let remaining = |buf: &[u8]| {
+            header
+                .payload_length
+                .checked_sub(started_len - buf.len())
+                .ok_or(alloy_rlp::Error::UnexpectedLength)
+        };
+
+        let status = Decodable::decode(buf)?;
+        let cumulative_gas_used = Decodable::decode(buf)?;
+        let logs_bloom = Decodable::decode(buf)?;
+        let logs = Decodable::decode(buf)?;
+        let deposit_nonce = if remaining(buf)? > 0 { Some(Decodable::decode(buf)?) } else { None };
+        let deposit_receipt_version =
+            if remaining(buf)? > 0 { Some(Decodable::decode(buf)?) } else { None };
+
+        let consumed = started_len - buf.len();
+        if consumed != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: consumed,
+            });
+        }
+
+        Ok(Self {
+            receipt: OpDepositReceipt {
+                inner: Receipt { status, cumulative_gas_used, logs },
+                deposit_nonce,
+                deposit_receipt_version,
+            },
+            logs_bloom,
+        })
+    }
+}
+
+/// OP-stack receipt envelope, as defined in [EIP-2718].
+///
+/// Like [`ReceiptEnvelope`](crate::ReceiptEnvelope), this distinguishes between tagged and
+/// untagged legacy receipts.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+#[non_exhaustive]
+pub enum OpReceiptEnvelope<T = Log> {
+    /// Receipt envelope with no type flag.
+    #[cfg_attr(feature = "serde", serde(rename = "0x0", alias = "0x00"))]
+    Legacy(ReceiptWithBloom<T>),
+    /// Receipt envelope with type flag 1, containing a [EIP-2930] receipt.
+    ///
+    /// [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
+    #[cfg_attr(feature = "serde", serde(rename = "0x1", alias = "0x01"))]
+    Eip2930(ReceiptWithBloom<T>),
+    /// Receipt envelope with type flag 2, containing a [EIP-1559] receipt.
+    ///
+    /// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+    #[cfg_attr(feature = "serde", serde(rename = "0x2", alias = "0x02"))]
+    Eip1559(ReceiptWithBloom<T>),
+    /// Receipt envelope with type flag `0x7e`, containing a deposit receipt.
+    #[cfg_attr(feature = "serde", serde(rename = "0x7e", alias = "0x7E"))]
+    Deposit(OpDepositReceiptWithBloom<T>),
+}
+
+impl<T> OpReceiptEnvelope<T> {
+    /// Return the [`OpTxType`] of the inner receipt.
+    pub const fn tx_type(&self) -> OpTxType {
+        match self {
+            Self::Legacy(_) => OpTxType::Legacy,
+            Self::Eip2930(_) => OpTxType::Eip2930,
+            Self::Eip1559(_) => OpTxType::Eip1559,
+            Self::Deposit(_) => OpTxType::Deposit,
+        }
+    }
+
+    /// Return true if the transaction was successful.
+    pub const fn is_success(&self) -> bool {
+        self.as_receipt().status.coerce_status()
+    }
+
+    /// Return the inner receipt.
+    pub const fn as_receipt(&self) -> &Receipt<T> {
+        match self {
+            Self::Legacy(t) | Self::Eip2930(t) | Self::Eip1559(t) => &t.receipt,
+            Self::Deposit(t) => &t.receipt.inner,
+        }
+    }
+
+    /// Return the deposit receipt, if this is the receipt of a deposit.
+    pub const fn as_deposit_receipt(&self) -> Option<&OpDepositReceipt<T>> {
+        match self {
+            Self::Deposit(t) => Some(&t.receipt),
+            _ => None,
+        }
+    }
+
+    /// Return the receipt's bloom.
+    pub const fn logs_bloom(&self) -> &Bloom {
+        match self {
+            Self::Legacy(t) | Self::Eip2930(t) | Self::Eip1559(t) => &t.logs_bloom,
+            Self::Deposit(t) => &t.logs_bloom,
+        }
+    }
+}
+
+impl<T> TxReceipt<T> for OpReceiptEnvelope<T> {
+    fn status_or_post_state(&self) -> Eip658Value {
+        self.as_receipt().status
+    }
+
+    fn bloom(&self) -> Bloom {
+        *self.logs_bloom()
+    }
+
+    fn bloom_cheap(&self) -> Option<Bloom> {
+        Some(self.bloom())
+    }
+
+    fn cumulative_gas_used(&self) -> u128 {
+        self.as_receipt().cumulative_gas_used
+    }
+
+    fn logs(&self) -> &[T] {
+        &self.as_receipt().logs
+    }
+}
+
+impl<T: Encodable> OpReceiptEnvelope<T> {
+    /// Get the length of the inner receipt in the 2718 encoding.
+    pub fn inner_length(&self) -> usize {
+        match self {
+            Self::Legacy(t) | Self::Eip2930(t) | Self::Eip1559(t) => t.length(),
+            Self::Deposit(t) => t.length(),
+        }
+    }
+}
+
+impl Encodable for OpReceiptEnvelope {
+    fn encode(&self, out: &mut dyn BufMut) {
+        self.network_encode(out)
+    }
+
+    fn length(&self) -> usize {
+        let mut payload_length = self.encode_2718_len();
+        if !self.is_legacy() {
+            payload_length += length_of_length(payload_length);
+        }
+        payload_length
+    }
+}
+
+impl Decodable for OpReceiptEnvelope {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match Self::network_decode(buf) {
+            Ok(t) => Ok(t),
+            Err(_) => Err(alloy_rlp::Error::Custom("Unexpected type")),
+        }
+    }
+}
+
+impl<T: Encodable + Send + Sync + 'static> Encodable2718 for OpReceiptEnvelope<T> {
+    fn type_flag(&self) -> Option<u8> {
+        match self {
+            Self::Legacy(_) => None,
+            receipt => Some(receipt.tx_type().into()),
+        }
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        self.inner_length() + !self.is_legacy() as usize
+    }
+
+    fn encode_2718(&self, out: &mut dyn BufMut) {
+        match self.type_flag() {
+            None => {}
+            Some(ty) => out.put_u8(ty),
+        }
+        match self {
+            Self::Legacy(t) | Self::Eip2930(t) | Self::Eip1559(t) => t.encode(out),
+            Self::Deposit(t) => t.encode(out),
+        }
+    }
+}
+
+impl Decodable2718 for OpReceiptEnvelope {
+    fn typed_decode(ty: u8, buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        match ty.try_into().map_err(|_| alloy_rlp::Error::Custom("Unexpected type"))? {
+            OpTxType::Eip2930 => Ok(Self::Eip2930(Decodable::decode(buf)?)),
+            OpTxType::Eip1559 => Ok(Self::Eip1559(Decodable::decode(buf)?)),
+            OpTxType::Deposit => Ok(Self::Deposit(Decodable::decode(buf)?)),
+            OpTxType::Legacy => {
+                Err(alloy_rlp::Error::Custom("type-0 eip2718 transactions are not supported"))
+            }
+        }
+    }
+
+    fn fallback_decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Ok(Self::Legacy(Decodable::decode(buf)?))
+    }
+}
+
+impl From<OpDepositReceiptWithBloom> for OpReceiptEnvelope {
+    fn from(receipt: OpDepositReceiptWithBloom) -> Self {
+        Self::Deposit(receipt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimism::DEPOSIT_TX_TYPE_ID;
+    use alloy_primitives::{address, bytes, LogData};
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn encode_decode_deposit_receipt() {
+        let log = Log {
+            address: address!("4200000000000000000000000000000000000015"),
+            data: LogData::new_unchecked(vec![], bytes!("0100ff")),
+        };
+        let mut receipt = OpDepositReceiptWithBloom {
+            receipt: OpDepositReceipt {
+                inner: Receipt {
+                    status: true.into(),
+                    cumulative_gas_used: 46_913,
+                    logs: vec![log],
+                },
+                deposit_nonce: Some(4_012_991),
+                deposit_receipt_version: Some(1),
+            },
+            logs_bloom: Bloom::ZERO,
+        };
+
+        for _ in 0..3 {
+            let envelope = OpReceiptEnvelope::from(receipt.clone());
+            let encoded = envelope.encoded_2718();
+            assert_eq!(encoded[0], DEPOSIT_TX_TYPE_ID);
+            assert_eq!(encoded.len(), envelope.encode_2718_len());
+            assert_eq!(OpReceiptEnvelope::decode_2718(&mut encoded.as_slice()).unwrap(), envelope);
+
+            let mut network = Vec::new();
+            envelope.encode(&mut network);
+            assert_eq!(network.len(), envelope.length());
+            assert_eq!(OpReceiptEnvelope::decode(&mut network.as_slice()).unwrap(), envelope);
+
+            // pre-Canyon, then pre-Regolith receipts
+            if receipt.receipt.deposit_receipt_version.take().is_none() {
+                receipt.receipt.deposit_nonce = None;
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_fields_beyond_list_length() {
+        // A list of one byte, followed by the status, gas used, bloom and logs of a receipt.
+        let mut encoded = vec![DEPOSIT_TX_TYPE_ID, 0xc1, 0x01, 0x01, 0xb9, 0x01, 0x00];
+        encoded.extend_from_slice(&[0; 256]);
+        encoded.push(0xc0);
+        assert_eq!(
+            OpReceiptEnvelope::<Log>::decode_2718(&mut encoded.as_slice()),
+            Err(alloy_rlp::Error::UnexpectedLength)
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use super::{OpTxEnvelope, OpTxType, TxDeposit};
+use crate::{Transaction, TxEip1559, TxEip2930, TxLegacy, TypedTransaction};
+use alloy_eips::eip2718::Eip2718Error;
+use alloy_primitives::TxKind;
+
+/// The OpTypedTransaction enum represents all OP-stack transaction request types.
+///
+/// Its variants correspond to specific allowed transactions:
+/// 1. Legacy (pre-EIP2718) [`TxLegacy`]
+/// 2. EIP2930 (state access lists) [`TxEip2930`]
+/// 3. EIP1559 [`TxEip1559`]
+/// 4. Deposit [`TxDeposit`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum OpTypedTransaction {
+    /// Legacy transaction
+    #[cfg_attr(feature = "serde", serde(rename = "0x00", alias = "0x0"))]
+    Legacy(TxLegacy),
+    /// EIP-2930 transaction
+    #[cfg_attr(feature = "serde", serde(rename = "0x01", alias = "0x1"))]
+    Eip2930(TxEip2930),
+    /// EIP-1559 transaction
+    #[cfg_attr(feature = "serde", serde(rename = "0x02", alias = "0x2"))]
+    Eip1559(TxEip1559),
+    /// Deposit transaction
+    #[cfg_attr(feature = "serde", serde(rename = "0x7e", alias = "0x7E"))]
+    Deposit(TxDeposit),
+}
+
+impl From<TxLegacy> for OpTypedTransaction {
+    fn from(tx: TxLegacy) -> Self {
+        Self::Legacy(tx)
+    }
+}
+
+impl From<TxEip2930> for OpTypedTransaction {
+    fn from(tx: TxEip2930) -> Self {
+        Self::Eip2930(tx)
+    }
+}
+
+impl From<TxEip1559> for OpTypedTransaction {
+    fn from(tx: TxEip1559) -> Self {
+        Self::Eip1559(tx)
+    }
+}
+
+impl From<TxDeposit> for OpTypedTransaction {
+    fn from(tx: TxDeposit) -> Self {
+        Self::Deposit(tx)
+    }
+}
+
+impl From<OpTxEnvelope> for OpTypedTransaction {
+    fn from(envelope: OpTxEnvelope) -> Self {
+        match envelope {
+            OpTxEnvelope::Legacy(tx) => Self::Legacy(tx.strip_signature()),
+            OpTxEnvelope::Eip2930(tx) => Self::Eip2930(tx.strip_signature()),
+            OpTxEnvelope::Eip1559(tx) => Self::Eip1559(tx.strip_signature()),
+            OpTxEnvelope::Deposit(tx) => Self::Deposit(tx),
+        }
+    }
+}
+
+impl TryFrom<TypedTransaction> for OpTypedTransaction {
+    type Error = Eip2718Error;
+
+    /// Converts an Ethereum transaction, failing for transaction types not supported on L2.
+    fn try_from(tx: TypedTransaction) -> Result<Self, Self::Error> {
+        match tx {
+            TypedTransaction::Legacy(tx) => Ok(Self::Legacy(tx)),
+            TypedTransaction::Eip2930(tx) => Ok(Self::Eip2930(tx)),
+            TypedTransaction::Eip1559(tx) => Ok(Self::Eip1559(tx)),
+            tx => Err(Eip2718Error::UnexpectedType(tx.tx_type().into())),
+        }
+    }
+}
+
+impl OpTypedTransaction {
+    /// Return the [`OpTxType`] of the inner txn.
+    pub const fn tx_type(&self) -> OpTxType {
+        match self {
+            Self::Legacy(_) => OpTxType::Legacy,
+            Self::Eip2930(_) => OpTxType::Eip2930,
+            Self::Eip1559(_) => OpTxType::Eip1559,
+            Self::Deposit(_) => OpTxType::Deposit,
+        }
+    }
+
+    /// Return the inner deposit transaction if it exists.
+    pub const fn deposit(&self) -> Option<&TxDeposit> {
+        match self {
+            Self::Deposit(tx) => Some(tx),
+            _ => None,
+        }
+    }
+}
+
+impl Transaction for OpTypedTransaction {
+    fn chain_id(&self) -> Option<alloy_primitives::ChainId> {
+        match self {
+            Self::Legacy(tx) => tx.chain_id(),
+            Self::Eip2930(tx) => tx.chain_id(),
+            Self::Eip1559(tx) => tx.chain_id(),
+            Self::Deposit(tx) => tx.chain_id(),
+        }
+    }
+
+    fn gas_limit(&self) -> u128 {
+        match self {
+            Self::Legacy(tx) => tx.gas_limit(),
+            Self::Eip2930(tx) => tx.gas_limit(),
+            Self::Eip1559(tx) => tx.gas_limit(),
+            Self::Deposit(tx) => tx.gas_limit(),
+        }
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        match self {
+            Self::Legacy(tx) => tx.gas_price(),
+            Self::Eip2930(tx) => tx.gas_price(),
+            Self::Eip1559(tx) => tx.gas_price(),
+            Self::Deposit(tx) => tx.gas_price(),
+        }
+    }
+
+    fn input(&self) -> &[u8] {
+        match self {
+            Self::Legacy(tx) => tx.input(),
+            Self::Eip2930(tx) => tx.input(),
+            Self::Eip1559(tx) => tx.input(),
+            Self::Deposit(tx) => tx.input(),
+        }
+    }
+
+    fn nonce(&self) -> u64 {
+        match self {
+            Self::Legacy(tx) => tx.nonce(),
+            Self::Eip2930(tx) => tx.nonce(),
+            Self::Eip1559(tx) => tx.nonce(),
+            Self::Deposit(tx) => tx.nonce(),
+        }
+    }
+
+    fn to(&self) -> TxKind {
+        match self {
+            Self::Legacy(tx) => tx.to(),
+            Self::Eip2930(tx) => tx.to(),
+            Self::Eip1559(tx) => tx.to(),
+            Self::Deposit(tx) => tx.to(),
+        }
+    }
+
+    fn value(&self) -> alloy_primitives::U256 {
+        match self {
+            Self::Legacy(tx) => tx.value(),
+            Self::Eip2930(tx) => tx.value(),
+            Self::Eip1559(tx) => tx.value(),
+            Self::Deposit(tx) => tx.value(),
+        }
+    }
+}
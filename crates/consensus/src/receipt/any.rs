@@ -1,4 +1,4 @@
-use crate::{ReceiptWithBloom, TxReceipt};
+use crate::{Eip658Value, ReceiptWithBloom, TxReceipt};
 use alloy_eips::eip2718::{Decodable2718, Encodable2718};
 use alloy_primitives::{bytes::BufMut, Bloom, Log};
 use alloy_rlp::{Decodable, Encodable};
@@ -20,7 +20,9 @@ pub struct AnyReceiptEnvelope<T = Log> {
     #[cfg_attr(feature = "serde", serde(flatten))]
     pub inner: ReceiptWithBloom<T>,
     /// The transaction type.
-    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::num::u8_via_ruint"))]
+    ///
+    /// Some nonstandard chains omit this field for legacy receipts, so it defaults to `0`.
+    #[cfg_attr(feature = "serde", serde(default, with = "alloy_serde::num::u8_via_ruint"))]
     pub r#type: u8,
 }
 
@@ -51,7 +53,16 @@ impl<T> AnyReceiptEnvelope<T> {
     }
 
     /// Returns the success status of the receipt's transaction.
+    ///
+    /// Pre-Byzantium receipts are assumed to be successful, see
+    /// [`Eip658Value::coerce_status`].
     pub const fn status(&self) -> bool {
+        self.inner.receipt.status.coerce_status()
+    }
+
+    /// Returns the success status of the receipt's transaction, or its post state before the
+    /// Byzantium hardfork.
+    pub const fn status_or_post_state(&self) -> Eip658Value {
         self.inner.receipt.status
     }
 
@@ -72,8 +83,8 @@ impl<T> AnyReceiptEnvelope<T> {
 }
 
 impl<T> TxReceipt<T> for AnyReceiptEnvelope<T> {
-    /// Returns the success status of the receipt's transaction.
-    fn status(&self) -> bool {
+    /// Returns the success status of the receipt's transaction, or its post state.
+    fn status_or_post_state(&self) -> Eip658Value {
         self.inner.receipt.status
     }
 
@@ -93,7 +104,7 @@ impl<T> TxReceipt<T> for AnyReceiptEnvelope<T> {
     }
 }
 
-impl Encodable2718 for AnyReceiptEnvelope {
+impl<T: Encodable + Send + Sync + 'static> Encodable2718 for AnyReceiptEnvelope<T> {
     fn type_flag(&self) -> Option<u8> {
         match self.r#type {
             0 => None,
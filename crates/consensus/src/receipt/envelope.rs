@@ -1,4 +1,4 @@
-use crate::{Receipt, ReceiptWithBloom, TxReceipt, TxType};
+use crate::{Eip658Value, Receipt, ReceiptWithBloom, TxReceipt, TxType};
 use alloy_eips::eip2718::{Decodable2718, Encodable2718};
 use alloy_primitives::{Bloom, Log};
 use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable};
@@ -55,7 +55,16 @@ impl<T> ReceiptEnvelope<T> {
     }
 
     /// Returns the success status of the receipt's transaction.
+    ///
+    /// Pre-Byzantium receipts are assumed to be successful, see
+    /// [`Eip658Value::coerce_status`].
     pub fn status(&self) -> bool {
+        self.as_receipt().unwrap().status.coerce_status()
+    }
+
+    /// Returns the success status of the receipt's transaction, or its post state before the
+    /// Byzantium hardfork.
+    pub fn status_or_post_state(&self) -> Eip658Value {
         self.as_receipt().unwrap().status
     }
 
@@ -94,7 +103,7 @@ impl<T> ReceiptEnvelope<T> {
 }
 
 impl<T> TxReceipt<T> for ReceiptEnvelope<T> {
-    fn status(&self) -> bool {
+    fn status_or_post_state(&self) -> Eip658Value {
         self.as_receipt().unwrap().status
     }
 
@@ -118,7 +127,7 @@ impl<T> TxReceipt<T> for ReceiptEnvelope<T> {
     }
 }
 
-impl ReceiptEnvelope {
+impl<T: Encodable> ReceiptEnvelope<T> {
     /// Get the length of the inner receipt in the 2718 encoding.
     pub fn inner_length(&self) -> usize {
         self.as_receipt_with_bloom().unwrap().length()
@@ -157,7 +166,7 @@ impl Decodable for ReceiptEnvelope {
     }
 }
 
-impl Encodable2718 for ReceiptEnvelope {
+impl<T: Encodable + Send + Sync + 'static> Encodable2718 for ReceiptEnvelope<T> {
     fn type_flag(&self) -> Option<u8> {
         match self {
             Self::Legacy(_) => None,
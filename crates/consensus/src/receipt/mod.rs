@@ -9,10 +9,22 @@ pub use envelope::ReceiptEnvelope;
 mod receipts;
 pub use receipts::{Receipt, ReceiptWithBloom};
 
+mod status;
+pub use status::Eip658Value;
+
 /// Receipt is the result of a transaction execution.
 pub trait TxReceipt<T = Log> {
+    /// Returns the status of the transaction, or the state root after it before the Byzantium
+    /// hardfork.
+    fn status_or_post_state(&self) -> Eip658Value;
+
     /// Returns true if the transaction was successful.
-    fn status(&self) -> bool;
+    ///
+    /// Pre-Byzantium receipts do not commit to the outcome of the transaction, and are assumed
+    /// to be successful. See [`Eip658Value::coerce_status`].
+    fn status(&self) -> bool {
+        self.status_or_post_state().coerce_status()
+    }
 
     /// Returns the bloom filter for the logs in the receipt. This operation
     /// may be expensive.
@@ -58,7 +70,7 @@ mod tests {
                             bytes!("0100ff"),
                         ),
                     }],
-                    status: false,
+                    status: false.into(),
                 },
                 logs_bloom: [0; 256].into(),
             });
@@ -90,7 +102,7 @@ mod tests {
                             bytes!("0100ff"),
                         ),
                     }],
-                    status: false,
+                    status: false.into(),
                 },
                 logs_bloom: [0; 256].into(),
             };
@@ -103,7 +115,7 @@ mod tests {
     fn gigantic_receipt() {
         let receipt = Receipt {
             cumulative_gas_used: 16747627,
-            status: true,
+            status: true.into(),
             logs: vec![
                 Log {
                     address: address!("4bf56695415f725e43c3e04354b604bcfb6dfb6e"),
@@ -136,4 +148,29 @@ mod tests {
         // let (decoded, _) = Receipt::from_compact(&data[..], data.len());
         assert_eq!(decoded, receipt);
     }
+
+    #[test]
+    fn pre_byzantium_receipt() {
+        let root = b256!("d8df8d52e8a8dd4abfa49dbb7f8d5fded5d9e326fb3a7ab4c7a4487f1bb1bcd8");
+        let receipt =
+            Receipt { status: root.into(), cumulative_gas_used: 21_000, logs: Vec::<Log>::new() }
+                .with_bloom();
+
+        let mut data = vec![];
+        receipt.encode(&mut data);
+        // The state root is encoded as a 32 bytes string in place of the status flag.
+        assert_eq!(data[3], 0xa0);
+        assert_eq!(data[4..36], root[..]);
+        assert_eq!(receipt.length(), data.len());
+
+        let decoded = ReceiptWithBloom::decode(&mut &data[..]).unwrap();
+        assert_eq!(decoded, receipt);
+        assert_eq!(decoded.status_or_post_state().as_post_state(), Some(root));
+        assert!(decoded.status());
+
+        let failed = Receipt { status: false.into(), ..receipt.receipt }.with_bloom();
+        let decoded: ReceiptWithBloom =
+            Decodable::decode(&mut &alloy_rlp::encode(&failed)[..]).unwrap();
+        assert_eq!(decoded.status_or_post_state(), Eip658Value::Eip658(false));
+    }
 }
@@ -1,6 +1,6 @@
 use core::borrow::Borrow;
 
-use super::TxReceipt;
+use super::{Eip658Value, TxReceipt};
 use alloy_primitives::{Bloom, Log};
 use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable};
 
@@ -13,15 +13,23 @@ use alloc::vec::Vec;
 #[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Receipt<T = Log> {
-    /// If transaction is executed successfully.
+    /// If transaction is executed successfully, or the state root after it before the Byzantium
+    /// hardfork.
     ///
-    /// This is the `statusCode`
-    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity_bool"))]
-    pub status: bool,
+    /// This is the `statusCode`, or the `root`.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub status: Eip658Value,
     /// Gas used
     #[cfg_attr(feature = "serde", serde(with = "alloy_serde::u128_via_ruint"))]
     pub cumulative_gas_used: u128,
     /// Log send from contracts.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            deserialize_with = "alloy_serde::lenient::null_as_default",
+            bound(deserialize = "T: serde::Deserialize<'de>")
+        )
+    )]
     pub logs: Vec<T>,
 }
 
@@ -46,7 +54,7 @@ impl<T> TxReceipt<T> for Receipt<T>
 where
     T: Borrow<Log>,
 {
-    fn status(&self) -> bool {
+    fn status_or_post_state(&self) -> Eip658Value {
         self.status
     }
 
@@ -88,7 +96,7 @@ pub struct ReceiptWithBloom<T = Log> {
 }
 
 impl<T> TxReceipt<T> for ReceiptWithBloom<T> {
-    fn status(&self) -> bool {
+    fn status_or_post_state(&self) -> Eip658Value {
         self.receipt.status
     }
 
@@ -166,12 +174,12 @@ impl<T> ReceiptWithBloom<T> {
         }
         let started_len = b.len();
 
-        let success = Decodable::decode(b)?;
+        let status = Decodable::decode(b)?;
         let cumulative_gas_used = Decodable::decode(b)?;
         let bloom = Decodable::decode(b)?;
         let logs = Decodable::decode(b)?;
 
-        let receipt = Receipt { status: success, cumulative_gas_used, logs };
+        let receipt = Receipt { status, cumulative_gas_used, logs };
 
         let this = Self { receipt, logs_bloom: bloom };
         let consumed = started_len - b.len();
@@ -0,0 +1,145 @@
+use alloy_primitives::B256;
+use alloy_rlp::{Decodable, Encodable, Header};
+
+/// The outcome of a transaction committed to by its receipt.
+///
+/// Since [EIP-658], receipts contain a status flag. Receipts of the transactions of the blocks
+/// before the Byzantium hardfork contain the intermediate state root after the transaction
+/// instead.
+///
+/// [EIP-658]: https://eips.ethereum.org/EIPS/eip-658
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub enum Eip658Value {
+    /// The status flag of the transaction, `true` if it succeeded.
+    Eip658(bool),
+    /// The state root after the transaction, before the Byzantium hardfork.
+    PostState(B256),
+}
+
+impl Eip658Value {
+    /// Returns the status of a successful transaction.
+    pub const fn success() -> Self {
+        Self::Eip658(true)
+    }
+
+    /// Returns true if this is a status flag.
+    pub const fn is_eip658(&self) -> bool {
+        matches!(self, Self::Eip658(_))
+    }
+
+    /// Returns true if this is a pre-Byzantium state root.
+    pub const fn is_post_state(&self) -> bool {
+        matches!(self, Self::PostState(_))
+    }
+
+    /// Returns the status flag, if any.
+    pub const fn as_eip658(&self) -> Option<bool> {
+        match self {
+            Self::Eip658(status) => Some(*status),
+            Self::PostState(_) => None,
+        }
+    }
+
+    /// Returns the state root, if any.
+    pub const fn as_post_state(&self) -> Option<B256> {
+        match self {
+            Self::Eip658(_) => None,
+            Self::PostState(root) => Some(*root),
+        }
+    }
+
+    /// Returns the status flag, assuming pre-Byzantium transactions succeeded.
+    ///
+    /// Failed transactions did not commit to a different value before the Byzantium hardfork, so
+    /// this is only accurate for later receipts.
+    pub const fn coerce_status(&self) -> bool {
+        match self {
+            Self::Eip658(status) => *status,
+            Self::PostState(_) => true,
+        }
+    }
+}
+
+impl Default for Eip658Value {
+    fn default() -> Self {
+        Self::Eip658(false)
+    }
+}
+
+impl From<bool> for Eip658Value {
+    fn from(status: bool) -> Self {
+        Self::Eip658(status)
+    }
+}
+
+impl From<B256> for Eip658Value {
+    fn from(root: B256) -> Self {
+        Self::PostState(root)
+    }
+}
+
+impl Encodable for Eip658Value {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        match self {
+            Self::Eip658(status) => status.encode(out),
+            Self::PostState(root) => root.encode(out),
+        }
+    }
+
+    fn length(&self) -> usize {
+        match self {
+            Self::Eip658(status) => status.length(),
+            Self::PostState(root) => root.length(),
+        }
+    }
+}
+
+impl Decodable for Eip658Value {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(&mut &**buf)?;
+        if !header.list && header.payload_length == B256::len_bytes() {
+            return B256::decode(buf).map(Self::PostState);
+        }
+        bool::decode(buf).map(Self::Eip658)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::Eip658Value;
+    use alloy_primitives::B256;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// The JSON-RPC representation, with either a `status` or a `root` field.
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Eip658 {
+            #[serde(with = "alloy_serde::quantity_bool")]
+            status: bool,
+        },
+        PostState {
+            root: B256,
+        },
+    }
+
+    impl Serialize for Eip658Value {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match *self {
+                Self::Eip658(status) => Repr::Eip658 { status },
+                Self::PostState(root) => Repr::PostState { root },
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Eip658Value {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            Ok(match Repr::deserialize(deserializer)? {
+                Repr::Eip658 { status } => Self::Eip658(status),
+                Repr::PostState { root } => Self::PostState(root),
+            })
+        }
+    }
+}
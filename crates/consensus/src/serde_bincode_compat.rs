@@ -0,0 +1,187 @@
+//! Binary serde representations of consensus types.
+//!
+//! The serde implementations of headers, transactions and receipts follow the JSON-RPC format,
+//! which relies on untagged enums, flattened and optional fields. Formats that are not
+//! self-describing, such as bincode, cannot deserialize them. The wrappers of this module instead
+//! serialize a value as a single byte string holding its consensus encoding, which is also much
+//! more compact than JSON:
+//!
+//! - [`Rlp`] for RLP encodable types, such as [`Header`](crate::Header) and
+//!   [`ReceiptWithBloom`](crate::ReceiptWithBloom).
+//! - [`Eip2718`] for EIP-2718 envelopes, such as [`TxEnvelope`](crate::TxEnvelope) and
+//!   [`ReceiptEnvelope`](crate::ReceiptEnvelope).
+//!
+//! The [`rlp`] and [`eip2718`] modules provide the same representations for use with
+//! `#[serde(with = "...")]`:
+//!
+//! ```
+//! use alloy_consensus::{
+//!     serde_bincode_compat::{self, Eip2718},
+//!     Header, TxEnvelope,
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct CachedBlock {
+//!     #[serde(with = "serde_bincode_compat::rlp")]
+//!     header: Header,
+//!     transactions: Vec<Eip2718<TxEnvelope>>,
+//! }
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_rlp::{Decodable, Encodable};
+use core::fmt;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A value serialized as its RLP encoding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rlp<T>(pub T);
+
+impl<T: Encodable> Serialize for Rlp<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        rlp::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Decodable> Deserialize<'de> for Rlp<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        rlp::deserialize(deserializer).map(Self)
+    }
+}
+
+/// An EIP-2718 envelope serialized as its EIP-2718 encoding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Eip2718<T>(pub T);
+
+impl<T: Encodable2718> Serialize for Eip2718<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        eip2718::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: Decodable2718> Deserialize<'de> for Eip2718<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        eip2718::deserialize(deserializer).map(Self)
+    }
+}
+
+/// (De)serializes RLP encodable values as their RLP encoding.
+pub mod rlp {
+    use super::*;
+
+    /// Serializes the RLP encoding of `value` as bytes.
+    pub fn serialize<T: Encodable, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&alloy_rlp::encode(value))
+    }
+
+    /// Deserializes a value from its RLP encoding.
+    pub fn deserialize<'de, T: Decodable, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let bytes = deserializer.deserialize_byte_buf(BytesVisitor)?;
+        let mut buf = bytes.as_slice();
+        let value = T::decode(&mut buf).map_err(de::Error::custom)?;
+        if !buf.is_empty() {
+            return Err(de::Error::custom(alloy_rlp::Error::UnexpectedLength));
+        }
+        Ok(value)
+    }
+}
+
+/// (De)serializes EIP-2718 envelopes as their EIP-2718 encoding.
+pub mod eip2718 {
+    use super::*;
+
+    /// Serializes the EIP-2718 encoding of `value` as bytes.
+    pub fn serialize<T: Encodable2718, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&value.encoded_2718())
+    }
+
+    /// Deserializes an envelope from its EIP-2718 encoding.
+    pub fn deserialize<'de, T: Decodable2718, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let bytes = deserializer.deserialize_byte_buf(BytesVisitor)?;
+        T::decode_2718_exact(&bytes).map_err(de::Error::custom)
+    }
+}
+
+/// Visits a byte string, or a sequence of bytes for formats without byte strings.
+struct BytesVisitor;
+
+impl<'de> de::Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("a byte string")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Header, Receipt, ReceiptEnvelope, ReceiptWithBloom, SignableTransaction, TxEip1559,
+        TxEnvelope,
+    };
+    use alloy_primitives::{Address, Log, Signature, U256};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Cached {
+        #[serde(with = "rlp")]
+        header: Header,
+        transactions: Vec<Eip2718<TxEnvelope>>,
+        receipt: Eip2718<ReceiptEnvelope>,
+    }
+
+    #[test]
+    fn roundtrip() {
+        let header = Header { number: 1, base_fee_per_gas: Some(7), ..Default::default() };
+        let tx = TxEip1559 { chain_id: 1, value: U256::from(1), ..Default::default() };
+        let tx: TxEnvelope = tx.into_signed(Signature::test_signature()).into();
+        let receipt = Receipt {
+            status: true.into(),
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(Address::ZERO, vec![], Default::default())],
+        };
+        let receipt = ReceiptEnvelope::Eip1559(ReceiptWithBloom::from(receipt));
+
+        let cached = Cached { header, transactions: vec![Eip2718(tx)], receipt: Eip2718(receipt) };
+        let serialized = serde_json::to_vec(&cached).unwrap();
+        assert_eq!(serde_json::from_slice::<Cached>(&serialized).unwrap(), cached);
+    }
+
+    #[test]
+    fn rejects_trailing_bytes() {
+        let mut encoded = alloy_rlp::encode(Header::default());
+        encoded.push(0);
+        let json = serde_json::to_string(&encoded).unwrap();
+        assert!(serde_json::from_str::<Rlp<Header>>(&json).is_err());
+    }
+}
@@ -440,6 +440,96 @@ mod tests {
         test_encode_decode_roundtrip(tx);
     }
 
+    #[test]
+    fn decode_2718_exact_rejects_trailing_bytes() {
+        let tx = TxEip1559 { chain_id: 1, gas_limit: 21_000, ..Default::default() };
+        let envelope: TxEnvelope = tx.into_signed(Signature::test_signature()).into();
+        let mut encoded = envelope.encoded_2718();
+        assert_eq!(TxEnvelope::decode_2718_exact(&encoded).unwrap(), envelope);
+
+        encoded.push(0);
+        assert_eq!(
+            TxEnvelope::decode_2718_exact(&encoded).unwrap_err(),
+            alloy_rlp::Error::UnexpectedLength
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "k256")]
+    fn decode_raw_and_recover_signer() {
+        let key = k256::ecdsa::SigningKey::from_slice(&[0x11; 32]).unwrap();
+        let signer = Address::from_private_key(&key);
+
+        // Signs `tx`, encoding the parity of legacy transactions as in EIP-155.
+        fn sign<T: SignableTransaction<Signature>>(tx: T, key: &k256::ecdsa::SigningKey) -> Vec<u8>
+        where
+            Signed<T>: Into<TxEnvelope>,
+        {
+            let (sig, recid) =
+                key.sign_prehash_recoverable(tx.signature_hash().as_slice()).unwrap();
+            let mut signature = Signature::from((sig, recid));
+            if tx.use_eip155() {
+                signature = signature.with_chain_id(tx.chain_id().unwrap());
+            }
+            let envelope: TxEnvelope = tx.into_signed(signature).into();
+            envelope.encoded_2718()
+        }
+
+        let to = Address::left_padding_from(&[6]);
+        let raw_txs = [
+            sign(
+                TxLegacy {
+                    chain_id: Some(1),
+                    gas_price: 1,
+                    gas_limit: 21_000,
+                    to: to.into(),
+                    ..Default::default()
+                },
+                &key,
+            ),
+            sign(
+                TxEip2930 {
+                    chain_id: 1,
+                    gas_price: 1,
+                    gas_limit: 21_000,
+                    to: to.into(),
+                    ..Default::default()
+                },
+                &key,
+            ),
+            sign(
+                TxEip1559 {
+                    chain_id: 1,
+                    max_fee_per_gas: 2,
+                    gas_limit: 21_000,
+                    to: to.into(),
+                    ..Default::default()
+                },
+                &key,
+            ),
+            sign(
+                TxEip4844 {
+                    chain_id: 1,
+                    max_fee_per_gas: 2,
+                    max_fee_per_blob_gas: 3,
+                    gas_limit: 21_000,
+                    to,
+                    blob_versioned_hashes: vec![B256::with_last_byte(1)],
+                    ..Default::default()
+                },
+                &key,
+            ),
+        ];
+
+        for (raw_tx, ty) in
+            raw_txs.iter().zip([TxType::Legacy, TxType::Eip2930, TxType::Eip1559, TxType::Eip4844])
+        {
+            let tx = TxEnvelope::decode_2718_exact(raw_tx).unwrap();
+            assert_eq!(tx.tx_type(), ty);
+            assert_eq!(tx.recover_signer().unwrap(), signer);
+        }
+    }
+
     #[test]
     fn test_encode_decode_transaction_list() {
         let signature = Signature::test_signature();
@@ -491,6 +581,24 @@ mod tests {
         assert_eq!(tx_envelope, deserialized);
     }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_raw_and_decoded() {
+        use alloy_eips::eip2718::RawAndDecoded;
+
+        let tx = TxEip1559 { chain_id: 1, nonce: 7, gas_limit: 21_000, ..Default::default() };
+        let envelope: TxEnvelope = tx.into_signed(Signature::test_signature()).into();
+        let raw_and_decoded = RawAndDecoded::new(envelope.clone());
+        assert_eq!(raw_and_decoded.hash(), *envelope.tx_hash());
+
+        let serialized = serde_json::to_string(&raw_and_decoded).unwrap();
+        assert_eq!(serialized, format!("\"0x{}\"", hex::encode(envelope.encoded_2718())));
+
+        let deserialized: RawAndDecoded<TxEnvelope> = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized.decoded(), &envelope);
+        assert!(serde_json::from_str::<RawAndDecoded<TxEnvelope>>("\"0x02c0\"").is_err());
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_serde_roundtrip_legacy() {
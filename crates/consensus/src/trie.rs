@@ -0,0 +1,293 @@
+//! A minimal Merkle Patricia Trie, for the ordered lists committed to in block headers, such as
+//! the transactions and receipts of a block.
+//!
+//! The key of each value is its RLP-encoded index in the list. Proofs list the nodes on the path
+//! from the root to a value, starting with the root; nodes shorter than 32 bytes are embedded in
+//! their parent, and not listed.
+
+use crate::EMPTY_ROOT_HASH;
+use alloy_primitives::{keccak256, Bytes, B256};
+use alloy_rlp::{Encodable, Header, EMPTY_STRING_CODE};
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// Computes the root of a trie mapping the RLP-encoded index of each value to the value, as used
+/// for the transactions, receipts, withdrawals and requests roots.
+pub fn ordered_trie_root<T: AsRef<[u8]>>(values: &[T]) -> B256 {
+    if values.is_empty() {
+        return EMPTY_ROOT_HASH;
+    }
+    keccak256(encode_node(&entries(values), 0, None))
+}
+
+/// Returns the proof of the value at `index` in the trie of
+/// [`ordered_trie_root`], or `None` if `index` is out of bounds.
+pub fn ordered_trie_proof<T: AsRef<[u8]>>(values: &[T], index: usize) -> Option<Vec<Bytes>> {
+    if index >= values.len() {
+        return None;
+    }
+    let key = index_key(index);
+    let mut proof = Proof { key: &key, nodes: Vec::new() };
+    let root = encode_node(&entries(values), 0, Some(&mut proof));
+    // Nodes are recorded from the leaf up, and the root is hashed even if it is short.
+    proof.nodes.push(root.into());
+    proof.nodes.reverse();
+    Some(proof.nodes)
+}
+
+/// Verifies that `proof` proves `value` to be at `index` in the trie of
+/// [`ordered_trie_root`] with the given `root`.
+pub fn verify_ordered_trie_proof(root: B256, index: usize, value: &[u8], proof: &[Bytes]) -> bool {
+    verify(root, &index_key(index), value, proof).unwrap_or(false)
+}
+
+fn index_key(index: usize) -> Vec<u8> {
+    nibbles(&alloy_rlp::encode(index))
+}
+
+fn entries<T: AsRef<[u8]>>(values: &[T]) -> Vec<(Vec<u8>, &[u8])> {
+    let mut entries: Vec<(Vec<u8>, &[u8])> = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (index_key(index), value.as_ref()))
+        .collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+fn nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]).collect()
+}
+
+/// The nodes on the path to a key, recorded while encoding the trie.
+struct Proof<'a> {
+    key: &'a [u8],
+    nodes: Vec<Bytes>,
+}
+
+impl Proof<'_> {
+    /// Returns the proof to record the nodes of `entries` in, if they hold the key.
+    fn within<'p>(
+        proof: &'p mut Option<&mut Self>,
+        entries: &[(Vec<u8>, &[u8])],
+    ) -> Option<&'p mut Self> {
+        proof.as_deref_mut().filter(|proof| entries.iter().any(|(key, _)| key == proof.key))
+    }
+}
+
+/// Encodes the node holding `entries`, which are sorted and share their first `depth` nibbles,
+/// recording the nodes on the path to the key of `proof`, if any.
+fn encode_node(
+    entries: &[(Vec<u8>, &[u8])],
+    depth: usize,
+    mut proof: Option<&mut Proof<'_>>,
+) -> Vec<u8> {
+    if let [(key, value)] = entries {
+        return encode_list(&[encode_bytes(&hex_prefix(&key[depth..], true)), encode_bytes(value)]);
+    }
+
+    let (first, last) = (&entries[0].0[depth..], &entries[entries.len() - 1].0[depth..]);
+    let prefix = first.iter().zip(last).take_while(|(a, b)| a == b).count();
+    if prefix > 0 {
+        let child = encode_node(entries, depth + prefix, proof.as_deref_mut());
+        return encode_list(&[
+            encode_bytes(&hex_prefix(&first[..prefix], false)),
+            node_ref(child, proof),
+        ]);
+    }
+
+    // Keys are sorted, so a key ending at this node comes first.
+    let (value, mut rest) = match entries.split_first() {
+        Some(((key, value), rest)) if key.len() == depth => (Some(*value), rest),
+        _ => (None, entries),
+    };
+    let mut items = Vec::with_capacity(17);
+    for nibble in 0..16 {
+        let len = rest.iter().take_while(|(key, _)| key[depth] == nibble).count();
+        let (children, tail) = rest.split_at(len);
+        items.push(if children.is_empty() {
+            vec![EMPTY_STRING_CODE]
+        } else {
+            let mut proof = Proof::within(&mut proof, children);
+            let child = encode_node(children, depth + 1, proof.as_deref_mut());
+            node_ref(child, proof)
+        });
+        rest = tail;
+    }
+    items.push(value.map_or_else(|| vec![EMPTY_STRING_CODE], encode_bytes));
+    encode_list(&items)
+}
+
+/// Returns the reference to a child node: the node itself if short enough, otherwise its hash.
+///
+/// Children referenced by hash are recorded in `proof`, if they are on its path.
+fn node_ref(encoded: Vec<u8>, proof: Option<&mut Proof<'_>>) -> Vec<u8> {
+    if encoded.len() < 32 {
+        return encoded;
+    }
+    let hash = encode_bytes(keccak256(&encoded).as_slice());
+    if let Some(proof) = proof {
+        proof.nodes.push(encoded.into());
+    }
+    hash
+}
+
+/// Packs nibbles into bytes, with the hex-prefix flags in the first nibble.
+fn hex_prefix(nibbles: &[u8], leaf: bool) -> Vec<u8> {
+    let flag = if leaf { 2 } else { 0 };
+    let mut out = Vec::with_capacity(nibbles.len() / 2 + 1);
+    let rest = if nibbles.len() % 2 == 1 {
+        out.push(((flag + 1) << 4) | nibbles[0]);
+        &nibbles[1..]
+    } else {
+        out.push(flag << 4);
+        nibbles
+    };
+    out.extend(rest.chunks_exact(2).map(|pair| (pair[0] << 4) | pair[1]));
+    out
+}
+
+/// Unpacks hex-prefixed nibbles, returning them along with whether they are the path of a leaf.
+fn decode_hex_prefix(bytes: &[u8]) -> Option<(Vec<u8>, bool)> {
+    let (&first, rest) = bytes.split_first()?;
+    let flag = first >> 4;
+    let mut path = Vec::with_capacity(rest.len() * 2 + 1);
+    match flag {
+        0 | 2 => {}
+        1 | 3 => path.push(first & 0x0f),
+        _ => return None,
+    }
+    path.extend(nibbles(rest));
+    Some((path, flag >= 2))
+}
+
+fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() + 5);
+    bytes.encode(&mut out);
+    out
+}
+
+fn encode_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload_length = items.iter().map(Vec::len).sum();
+    let mut out = Vec::with_capacity(payload_length + 5);
+    Header { list: true, payload_length }.encode(&mut out);
+    for item in items {
+        out.extend_from_slice(item);
+    }
+    out
+}
+
+/// Splits an RLP item into its header and payload, checking that it spans all of `item`.
+fn decode_item(mut item: &[u8]) -> Option<(Header, &[u8])> {
+    let header = Header::decode(&mut item).ok()?;
+    (item.len() == header.payload_length).then_some((header, item))
+}
+
+/// Returns the payload of an RLP string.
+fn decode_string(item: &[u8]) -> Option<&[u8]> {
+    decode_item(item).filter(|(header, _)| !header.list).map(|(_, payload)| payload)
+}
+
+/// Splits an RLP list into its raw items.
+fn decode_list(item: &[u8]) -> Option<Vec<&[u8]>> {
+    let (_, mut payload) = decode_item(item).filter(|(header, _)| header.list)?;
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let mut rest = payload;
+        let header = Header::decode(&mut rest).ok()?;
+        let len = payload.len() - rest.len() + header.payload_length;
+        items.push(payload.get(..len)?);
+        payload = &payload[len..];
+    }
+    Some(items)
+}
+
+/// Walks `proof` from `root` along `key`, returning whether it ends at `value`, or `None` if the
+/// proof is malformed.
+fn verify(root: B256, key: &[u8], value: &[u8], proof: &[Bytes]) -> Option<bool> {
+    let mut nodes = proof.iter();
+    let mut node: &[u8] = nodes.next()?;
+    if keccak256(node) != root {
+        return Some(false);
+    }
+    let mut depth = 0;
+    loop {
+        let items = decode_list(node)?;
+        let child = match items.as_slice() {
+            [path, item] => {
+                let (path, leaf) = decode_hex_prefix(decode_string(path)?)?;
+                if !key[depth..].starts_with(&path) {
+                    return Some(false);
+                }
+                depth += path.len();
+                if leaf {
+                    return Some(depth == key.len() && decode_string(item)? == value);
+                }
+                *item
+            }
+            [children @ .., item] if children.len() == 16 => {
+                let Some(&nibble) = key.get(depth) else {
+                    return Some(decode_string(item)? == value);
+                };
+                depth += 1;
+                children[nibble as usize]
+            }
+            _ => return None,
+        };
+
+        // Children are embedded if shorter than 32 bytes, and referenced by hash otherwise.
+        let (header, payload) = decode_item(child)?;
+        node = if header.list {
+            child
+        } else if payload.len() == 32 {
+            let next: &[u8] = nodes.next()?;
+            if keccak256(next) != payload {
+                return Some(false);
+            }
+            next
+        } else {
+            return Some(false);
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::b256;
+
+    #[test]
+    fn trie_root() {
+        let mut entries: Vec<(Vec<u8>, &[u8])> = vec![
+            (nibbles(b"do"), b"verb"),
+            (nibbles(b"dog"), b"puppy"),
+            (nibbles(b"doge"), b"coin"),
+            (nibbles(b"horse"), b"stallion"),
+        ];
+        entries.sort();
+        assert_eq!(
+            keccak256(encode_node(&entries, 0, None)),
+            b256!("5991bb8c6514148a29db676a14ac506cd2cd5775ace63c30a4fe457715e9ac84")
+        );
+        assert_eq!(ordered_trie_root::<&[u8]>(&[]), EMPTY_ROOT_HASH);
+    }
+
+    #[test]
+    fn trie_proofs() {
+        for len in [1, 2, 16, 17, 130, 300] {
+            // Short values are embedded in their parent node, long ones are not.
+            let values: Vec<Vec<u8>> = (0..len).map(|i| vec![i as u8; 1 + i % 40]).collect();
+            let root = ordered_trie_root(&values);
+            for (index, value) in values.iter().enumerate() {
+                let proof = ordered_trie_proof(&values, index).unwrap();
+                assert!(verify_ordered_trie_proof(root, index, value, &proof), "{len} {index}");
+                assert!(!verify_ordered_trie_proof(root, index, b"other", &proof));
+                assert!(!verify_ordered_trie_proof(root, index + 1, value, &proof));
+                assert!(!verify_ordered_trie_proof(B256::ZERO, index, value, &proof));
+            }
+            assert_eq!(ordered_trie_proof(&values, len), None);
+        }
+        assert!(!verify_ordered_trie_proof(EMPTY_ROOT_HASH, 0, b"", &[]));
+    }
+}
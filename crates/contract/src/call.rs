@@ -2,9 +2,11 @@ use crate::{CallDecoder, Error, EthCall, Result};
 use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
 use alloy_json_abi::Function;
 use alloy_network::{Ethereum, Network, ReceiptResponse, TransactionBuilder};
-use alloy_primitives::{Address, Bytes, ChainId, TxKind, U256};
+use alloy_primitives::{address, Address, Bytes, ChainId, TxKind, B256, U256};
 use alloy_provider::{PendingTransactionBuilder, Provider};
-use alloy_rpc_types::{state::StateOverride, AccessList, BlobTransactionSidecar, BlockId};
+use alloy_rpc_types::{
+    state::StateOverride, AccessList, BlobTransactionSidecar, BlockId, BlockOverrides,
+};
 use alloy_sol_types::SolCall;
 use alloy_transport::Transport;
 use std::{
@@ -13,6 +15,12 @@ use std::{
     pin::Pin,
 };
 
+/// The address of the deterministic deployment proxy, deployed at the same address on most chains.
+///
+/// The proxy deploys the init code following a 32-byte salt in its call data with `CREATE2`.
+/// See [Arachnid/deterministic-deployment-proxy](https://github.com/Arachnid/deterministic-deployment-proxy).
+pub const DETERMINISTIC_DEPLOYER: Address = address!("4e59b44847b379578588920cA78FbF26c0B4956C");
+
 /// [`CallBuilder`] using a [`SolCall`] type as the call decoder.
 // NOTE: please avoid changing this type due to its use in the `sol!` macro.
 pub type SolCallBuilder<T, P, C, N = Ethereum> = CallBuilder<T, P, PhantomData<C>, N>;
@@ -124,6 +132,7 @@ pub struct CallBuilder<T, P, D, N: Network = Ethereum> {
     request: N::TransactionRequest,
     block: BlockId,
     state: Option<StateOverride>,
+    block_overrides: Option<BlockOverrides>,
     /// The provider.
     // NOTE: This is public due to usage in `sol!`, please avoid changing it.
     pub provider: P,
@@ -141,6 +150,10 @@ impl<T: Transport + Clone, P: Provider<T, N>, N: Network> DynCallBuilder<T, P, N
         ))
     }
 
+    pub(crate) fn new_dyn_encoded(provider: P, function: &Function, input: Bytes) -> Self {
+        Self::new_inner_call(provider, input, function.clone())
+    }
+
     /// Clears the decoder, returning a raw call builder.
     #[inline]
     pub fn clear_decoder(self) -> RawCallBuilder<T, P, N> {
@@ -148,6 +161,7 @@ impl<T: Transport + Clone, P: Provider<T, N>, N: Network> DynCallBuilder<T, P, N
             request: self.request,
             block: self.block,
             state: self.state,
+            block_overrides: self.block_overrides,
             provider: self.provider,
             decoder: (),
             transport: PhantomData,
@@ -174,6 +188,7 @@ impl<T: Transport + Clone, P: Provider<T, N>, C: SolCall, N: Network> SolCallBui
             request: self.request,
             block: self.block,
             state: self.state,
+            block_overrides: self.block_overrides,
             provider: self.provider,
             decoder: (),
             transport: PhantomData,
@@ -239,6 +254,7 @@ impl<T: Transport + Clone, P: Provider<T, N>, N: Network> RawCallBuilder<T, P, N
             request: self.request,
             block: self.block,
             state: self.state,
+            block_overrides: self.block_overrides,
             provider: self.provider,
             decoder: PhantomData::<C>,
             transport: PhantomData,
@@ -274,6 +290,7 @@ impl<T: Transport + Clone, P: Provider<T, N>, D: CallDecoder, N: Network> CallBu
             provider,
             block: BlockId::default(),
             state: None,
+            block_overrides: None,
             transport: PhantomData,
         }
     }
@@ -285,6 +302,7 @@ impl<T: Transport + Clone, P: Provider<T, N>, D: CallDecoder, N: Network> CallBu
             provider,
             block: BlockId::default(),
             state: None,
+            block_overrides: None,
             transport: PhantomData,
         }
     }
@@ -399,18 +417,47 @@ impl<T: Transport + Clone, P: Provider<T, N>, D: CallDecoder, N: Network> CallBu
         self
     }
 
+    /// Sets the block overrides, e.g. the block number, timestamp or base fee.
+    ///
+    /// # Note
+    ///
+    /// Not all client implementations will support this as a parameter to `eth_call`.
+    pub fn block_overrides(mut self, block_overrides: BlockOverrides) -> Self {
+        self.block_overrides = Some(block_overrides);
+        self
+    }
+
     /// Returns the underlying transaction's ABI-encoded data.
     pub fn calldata(&self) -> &Bytes {
         self.request.input().expect("set in the constructor")
     }
 
-    /// Returns the estimated gas cost for the underlying transaction to be executed
+    /// Returns the estimated gas cost for the underlying transaction to be executed.
+    /// If [`state overrides`](Self::state) or [`block overrides`](Self::block_overrides) are set,
+    /// they will be applied to the estimation.
     pub async fn estimate_gas(&self) -> Result<u128> {
-        self.provider.estimate_gas(&self.request).block_id(self.block).await.map_err(Into::into)
+        if self.state.is_none() && self.block_overrides.is_none() {
+            return self
+                .provider
+                .estimate_gas(&self.request)
+                .block_id(self.block)
+                .await
+                .map_err(Into::into);
+        }
+        self.provider
+            .estimate_gas_with_overrides(
+                &self.request,
+                self.block,
+                self.state.as_ref(),
+                self.block_overrides.as_ref(),
+            )
+            .await
+            .map_err(Into::into)
     }
 
     /// Queries the blockchain via an `eth_call` without submitting a transaction to the network.
-    /// If [`state overrides`](Self::state) are set, they will be applied to the call.
+    /// If [`state overrides`](Self::state) or [`block overrides`](Self::block_overrides) are set,
+    /// they will be applied to the call.
     ///
     /// Returns the decoded the output by using the provided decoder.
     /// If this is not desired, use [`call_raw`](Self::call_raw) to get the raw output data.
@@ -421,7 +468,8 @@ impl<T: Transport + Clone, P: Provider<T, N>, D: CallDecoder, N: Network> CallBu
     }
 
     /// Queries the blockchain via an `eth_call` without submitting a transaction to the network.
-    /// If [`state overrides`](Self::state) are set, they will be applied to the call.
+    /// If [`state overrides`](Self::state) or [`block overrides`](Self::block_overrides) are set,
+    /// they will be applied to the call.
     ///
     /// Does not decode the output of the call, returning the raw output data instead.
     ///
@@ -432,6 +480,10 @@ impl<T: Transport + Clone, P: Provider<T, N>, D: CallDecoder, N: Network> CallBu
             Some(state) => call.overrides(state),
             None => call,
         };
+        let call = match &self.block_overrides {
+            Some(block_overrides) => call.block_overrides(block_overrides),
+            None => call,
+        };
         call.into()
     }
 
@@ -460,6 +512,37 @@ impl<T: Transport + Clone, P: Provider<T, N>, D: CallDecoder, N: Network> CallBu
         receipt.contract_address().ok_or(Error::ContractNotDeployed)
     }
 
+    /// Broadcasts the underlying deployment transaction to the network as a call to the
+    /// [deterministic deployment proxy](DETERMINISTIC_DEPLOYER), returning the address of the
+    /// deployed contract after the transaction has been confirmed.
+    ///
+    /// The address only depends on `salt` and the init code, and can be predicted before sending
+    /// with [`calculate_create2_address`](Self::calculate_create2_address).
+    ///
+    /// Returns an error if the transaction is not a deployment transaction, if it reverts, e.g.
+    /// because a contract was already deployed with the same salt, or if no code is found at the
+    /// predicted address once the transaction is confirmed, e.g. because the proxy is not deployed
+    /// on the chain.
+    pub async fn deploy_create2(&self, salt: B256) -> Result<Address> {
+        let address =
+            self.calculate_create2_address(salt).ok_or(Error::NotADeploymentTransaction)?;
+        let init_code = self.request.input().cloned().unwrap_or_default();
+        let mut input = Vec::with_capacity(32 + init_code.len());
+        input.extend_from_slice(salt.as_slice());
+        input.extend_from_slice(&init_code);
+        let request = self.request.clone().with_to(DETERMINISTIC_DEPLOYER).with_input(input);
+
+        let pending_tx = self.provider.send_transaction(request).await?;
+        let tx_hash = *pending_tx.tx_hash();
+        if !pending_tx.get_receipt().await?.status() {
+            return Err(Error::TransactionReverted(tx_hash));
+        }
+        if self.provider.get_code_at(address).await?.is_empty() {
+            return Err(Error::ContractNotDeployed);
+        }
+        Ok(address)
+    }
+
     /// Broadcasts the underlying transaction to the network.
     ///
     /// Returns a builder for configuring the pending transaction watcher.
@@ -475,6 +558,18 @@ impl<T: Transport + Clone, P: Provider<T, N>, D: CallDecoder, N: Network> CallBu
     pub fn calculate_create_address(&self) -> Option<Address> {
         self.request.calculate_create_address()
     }
+
+    /// Calculates the address that will be created by
+    /// [`deploy_create2`](Self::deploy_create2) with the given salt.
+    ///
+    /// Returns `None` if the transaction is not a contract creation.
+    pub fn calculate_create2_address(&self, salt: B256) -> Option<Address> {
+        if !self.request.kind().is_some_and(|to| to.is_create()) {
+            return None;
+        }
+        let init_code = self.request.input().map(|input| &input[..]).unwrap_or_default();
+        Some(DETERMINISTIC_DEPLOYER.create2_from_code(salt, init_code))
+    }
 }
 
 impl<T: Transport, P: Clone, D, N: Network> CallBuilder<T, &P, D, N> {
@@ -484,6 +579,7 @@ impl<T: Transport, P: Clone, D, N: Network> CallBuilder<T, &P, D, N> {
             request: self.request,
             block: self.block,
             state: self.state,
+            block_overrides: self.block_overrides,
             provider: self.provider.clone(),
             decoder: self.decoder,
             transport: PhantomData,
@@ -529,6 +625,7 @@ impl<T, P, D: CallDecoder, N: Network> std::fmt::Debug for CallBuilder<T, P, D,
             .field("request", &self.request)
             .field("block", &self.block)
             .field("state", &self.state)
+            .field("block_overrides", &self.block_overrides)
             .field("decoder", &self.decoder.as_debug_field())
             .finish()
     }
@@ -733,6 +830,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create2_address() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let call_builder = MyContract::deploy_builder(&provider, true);
+        let salt = B256::with_last_byte(1);
+        assert_eq!(
+            call_builder.calculate_create2_address(salt),
+            Some(DETERMINISTIC_DEPLOYER.create2_from_code(salt, call_builder.calldata()))
+        );
+        let call_builder = call_builder.to(Address::ZERO);
+        assert_eq!(call_builder.calculate_create2_address(salt), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn deploy_create2() {
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_signer();
+        let call_builder = MyContract::deploy_builder(&provider, true);
+        let salt = B256::with_last_byte(1);
+        let expected_address = call_builder.calculate_create2_address(salt).unwrap();
+
+        let address = call_builder.deploy_create2(salt).await.unwrap();
+        assert_eq!(address, expected_address);
+        let my_contract = MyContract::new(address, &provider);
+        assert!(my_contract.myState().call().await.unwrap().myState);
+
+        // The same salt and init code cannot be deployed twice: the deployment reverts, and fails
+        // the gas estimation unless the gas limit is set.
+        assert!(call_builder.deploy_create2(salt).await.is_err());
+        let err = call_builder.clone().gas(1_000_000).deploy_create2(salt).await.unwrap_err();
+        assert!(matches!(err, Error::TransactionReverted(_)), "{err}");
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn deploy_and_call_with_priority() {
         let provider = ProviderBuilder::new().on_anvil();
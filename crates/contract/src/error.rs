@@ -1,5 +1,6 @@
 use alloy_dyn_abi::Error as AbiError;
-use alloy_primitives::Selector;
+use alloy_primitives::{Bytes, Selector, TxHash, B256};
+use alloy_sol_types::{ContractError, SolInterface};
 use alloy_transport::TransportError;
 use thiserror::Error;
 
@@ -15,12 +16,21 @@ pub enum Error {
     /// Unknown function selector referenced.
     #[error("unknown function: function with selector {0} does not exist")]
     UnknownSelector(Selector),
+    /// Unknown event referenced.
+    #[error("unknown event: event {0} does not exist")]
+    UnknownEvent(String),
+    /// Unknown event signature referenced.
+    #[error("unknown event: event with signature {0} does not exist")]
+    UnknownEventSignature(B256),
     /// Called `deploy` with a transaction that is not a deployment transaction.
     #[error("transaction is not a deployment transaction")]
     NotADeploymentTransaction,
     /// `contractAddress` was not found in the deployment transaction’s receipt.
     #[error("missing `contractAddress` from deployment transaction receipt")]
     ContractNotDeployed,
+    /// The transaction was included in a block, but reverted.
+    #[error("transaction {0} reverted")]
+    TransactionReverted(TxHash),
     /// An error occurred ABI encoding or decoding.
     #[error(transparent)]
     AbiError(#[from] AbiError),
@@ -35,3 +45,101 @@ impl From<alloy_sol_types::Error> for Error {
         Self::AbiError(e.into())
     }
 }
+
+impl Error {
+    /// Returns the revert data of a reverted call or gas estimation, if any.
+    pub fn as_revert_data(&self) -> Option<Bytes> {
+        match self {
+            Self::TransportError(e) => e.as_revert_data(),
+            _ => None,
+        }
+    }
+
+    /// Decodes the revert data of a reverted call or gas estimation into an `Error(string)`, a
+    /// `Panic(uint256)`, or one of the custom errors `E` of the contract.
+    ///
+    /// `E` is usually the `<Contract>Errors` enum generated by `sol!` for the contract, or
+    /// [`Infallible`](std::convert::Infallible) to only decode the generic errors. Returns `None`
+    /// if there is no revert data, or if it is not one of these errors.
+    ///
+    /// ```no_run
+    /// # async fn test<P: alloy_contract::private::Provider>(provider: P) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::{Address, U256};
+    /// use alloy_sol_types::{sol, ContractError};
+    ///
+    /// sol! {
+    ///     #[sol(rpc)]
+    ///     contract Vault {
+    ///         error InsufficientBalance(uint256 available, uint256 required);
+    ///         function withdraw(uint256 amount);
+    ///     }
+    /// }
+    ///
+    /// let vault = Vault::new(Address::ZERO, &provider);
+    /// if let Err(e) = vault.withdraw(U256::from(100)).call().await {
+    ///     match e.as_decoded_error::<Vault::VaultErrors>() {
+    ///         Some(ContractError::CustomError(Vault::VaultErrors::InsufficientBalance(e))) => {
+    ///             println!("only {} available", e.available)
+    ///         }
+    ///         Some(ContractError::Panic(panic)) => println!("panicked: {:?}", panic.kind()),
+    ///         Some(ContractError::Revert(revert)) => println!("reverted: {}", revert.reason()),
+    ///         None => return Err(e.into()),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn as_decoded_error<E: SolInterface>(&self) -> Option<ContractError<E>> {
+        ContractError::abi_decode(&self.as_revert_data()?, true).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use alloy_sol_types::{sol, PanicKind, SolError};
+
+    sol! {
+        #[derive(Debug, PartialEq)]
+        interface Vault {
+            error InsufficientBalance(uint256 available, uint256 required);
+            error Paused();
+        }
+    }
+
+    fn reverted(data: &[u8]) -> Error {
+        let json = format!(
+            r#"{{"code":3,"message":"execution reverted","data":"{}"}}"#,
+            Bytes::copy_from_slice(data)
+        );
+        Error::TransportError(TransportError::ErrorResp(serde_json::from_str(&json).unwrap()))
+    }
+
+    #[test]
+    fn decodes_revert_data() {
+        let error = reverted(&alloy_sol_types::Revert::from("not owner").abi_encode());
+        let Some(ContractError::Revert(revert)) =
+            error.as_decoded_error::<std::convert::Infallible>()
+        else {
+            panic!("not a revert")
+        };
+        assert_eq!(revert.reason(), "not owner");
+
+        let panic = alloy_sol_types::Panic::from(U256::from(0x11));
+        let error = reverted(&panic.abi_encode());
+        let decoded = error.as_decoded_error::<Vault::VaultErrors>().unwrap();
+        assert_eq!(decoded.as_panic().unwrap().kind(), Some(PanicKind::UnderOverflow));
+
+        let custom =
+            Vault::InsufficientBalance { available: U256::from(1), required: U256::from(2) };
+        let decoded = reverted(&custom.abi_encode()).as_decoded_error::<Vault::VaultErrors>();
+        assert_eq!(
+            decoded,
+            Some(ContractError::CustomError(Vault::VaultErrors::InsufficientBalance(custom)))
+        );
+
+        assert!(reverted(&[1, 2, 3, 4]).as_decoded_error::<Vault::VaultErrors>().is_none());
+        assert!(Error::ContractNotDeployed.as_decoded_error::<Vault::VaultErrors>().is_none());
+    }
+}
@@ -4,7 +4,7 @@ use alloy_dyn_abi::{DynSolValue, FunctionExt};
 use alloy_json_abi::Function;
 use alloy_network::Network;
 use alloy_primitives::Bytes;
-use alloy_rpc_types::{state::StateOverride, BlockId};
+use alloy_rpc_types::{state::StateOverride, BlockId, BlockOverrides};
 use alloy_sol_types::SolCall;
 use alloy_transport::Transport;
 
@@ -84,6 +84,12 @@ where
         self
     }
 
+    /// Set the block overrides for this call.
+    pub fn block_overrides(mut self, block_overrides: &'state BlockOverrides) -> Self {
+        self.inner = self.inner.block_overrides(block_overrides);
+        self
+    }
+
     /// Set the block to use for this call.
     pub fn block(mut self, block: BlockId) -> Self {
         self.inner = self.inner.block(block);
@@ -0,0 +1,94 @@
+use crate::{DynCallBuilder, Result};
+use alloy_dyn_abi::Error as AbiError;
+use alloy_json_abi::Function;
+use alloy_network::Network;
+use alloy_primitives::Address;
+use alloy_provider::Provider;
+use alloy_sol_types::{abi::TokenSeq, SolType, SolValue};
+use alloy_transport::Transport;
+
+/// Contract call one-liners for providers, for scripts that do not have the ABI of a contract at
+/// hand.
+pub trait ProviderCallExt<T: Transport + Clone, N: Network>: Provider<T, N> + Sized {
+    /// Builds a call to the function with the given human-readable signature, such as
+    /// `balanceOf(address)(uint256)` or `function balanceOf(address) returns (uint256)`.
+    ///
+    /// The arguments are a tuple of Rust values whose Solidity types must match the parameters
+    /// of the function. The returned builder decodes the output of the function into
+    /// [`DynSolValue`](alloy_dyn_abi::DynSolValue)s, of which there are none if the signature
+    /// omits the return types. Like any [`CallBuilder`](crate::CallBuilder), it can
+    /// [`call`](crate::CallBuilder::call) the function, or [`send`](crate::CallBuilder::send) it as
+    /// a transaction.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider, token: alloy_primitives::Address, who: alloy_primitives::Address) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_contract::ProviderCallExt;
+    ///
+    /// let signature = "balanceOf(address)(uint256)";
+    /// let balance = provider.call_function(token, signature, (who,))?.call().await?;
+    /// let balance = balance[0].as_uint();
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn call_function<A>(
+        &self,
+        address: Address,
+        signature: &str,
+        args: A,
+    ) -> Result<DynCallBuilder<T, &Self, N>>
+    where
+        A: SolValue,
+        for<'a> <A::SolType as SolType>::Token<'a>: TokenSeq<'a>,
+    {
+        let function = Function::parse(signature).map_err(AbiError::from)?;
+        let signature = function.signature();
+        let expected = &signature[function.name.len()..];
+        let actual = args.sol_name();
+        if actual != expected {
+            return Err(AbiError::TypeMismatch {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            }
+            .into());
+        }
+
+        let input = [&function.selector()[..], &args.abi_encode_params()].concat();
+        Ok(DynCallBuilder::new_dyn_encoded(self, &function, input.into()).to(address))
+    }
+}
+
+impl<T, N, P> ProviderCallExt<T, N> for P
+where
+    T: Transport + Clone,
+    N: Network,
+    P: Provider<T, N>,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use alloy_primitives::{hex, U256};
+    use alloy_provider::ProviderBuilder;
+
+    #[test]
+    fn builds_function_call() {
+        let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse().unwrap());
+        let (token, who) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+
+        let call = provider.call_function(token, "balanceOf(address)(uint256)", (who,)).unwrap();
+        assert_eq!(
+            call.calldata()[..],
+            [&hex!("70a08231")[..], &[0; 12], who.as_slice()].concat()[..]
+        );
+        let output = call.decode_output(U256::from(7).abi_encode().into(), true).unwrap();
+        assert_eq!(output[0].as_uint(), Some((U256::from(7), 256)));
+
+        let err =
+            provider.call_function(token, "transfer(address,uint256)", (who, 1u64)).unwrap_err();
+        assert!(matches!(err, Error::AbiError(AbiError::TypeMismatch { .. })), "{err}");
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{CallBuilder, Event, Interface, Result};
-use alloy_dyn_abi::DynSolValue;
+use alloy_dyn_abi::{DecodedEvent, DynSolValue, EventExt};
 use alloy_json_abi::{Function, JsonAbi};
 use alloy_network::{Ethereum, Network};
 use alloy_primitives::{Address, Selector};
 use alloy_provider::Provider;
-use alloy_rpc_types::Filter;
+use alloy_rpc_types::{Filter, Log};
 use alloy_sol_types::SolEvent;
 use alloy_transport::Transport;
 use std::marker::PhantomData;
@@ -29,6 +29,16 @@ impl<T, P, N> ContractInstance<T, P, N> {
         Self { address, provider, interface, transport: PhantomData, network: PhantomData }
     }
 
+    /// Creates a new contract from the provided address, provider, and JSON ABI, loaded e.g. at
+    /// runtime.
+    ///
+    /// Functions can then be called by name with [`function`](ContractInstance::function), and
+    /// the logs of events decoded with [`decode_log`](Interface::decode_log).
+    #[inline]
+    pub fn from_abi(address: Address, provider: P, abi: JsonAbi) -> Self {
+        Self::new(address, provider, Interface::new(abi))
+    }
+
     /// Returns a reference to the contract's address.
     #[inline]
     pub const fn address(&self) -> &Address {
@@ -100,6 +110,36 @@ impl<T: Transport + Clone, P: Provider<T, N>, N: Network> ContractInstance<T, P,
         CallBuilder::new_dyn(&self.provider, function, args)
     }
 
+    /// Returns a filter for the logs of the provided event emitted by this contract.
+    ///
+    /// If there are multiple events with the same name due to overloading, this will use the
+    /// first match.
+    pub fn event_filter(&self, name: &str) -> Result<Filter> {
+        let event = self.interface.get_event_from_name(name)?;
+        Ok(Filter::new().address(self.address).event_signature(event.selector()))
+    }
+
+    /// Fetches the logs of the provided event emitted by this contract, matching `filter`, and
+    /// decodes them.
+    ///
+    /// The address and event signature of `filter` are overridden. See
+    /// [`event_filter`](Self::event_filter).
+    pub async fn query_event(
+        &self,
+        name: &str,
+        filter: Filter,
+    ) -> Result<Vec<(Log, DecodedEvent)>> {
+        let event = self.interface.get_event_from_name(name)?;
+        let filter = filter.address(self.address).event_signature(event.selector());
+        let logs = self.provider.get_logs(&filter).await?;
+        logs.into_iter()
+            .map(|log| {
+                let decoded = event.decode_log(&log.inner.data, true)?;
+                Ok((log, decoded))
+            })
+            .collect()
+    }
+
     /// Returns an [`Event`] builder with the provided filter.
     pub fn event<E: SolEvent>(&self, filter: Filter) -> Event<T, &P, E, N> {
         Event::new(&self.provider, filter)
@@ -1,7 +1,9 @@
 use crate::{ContractInstance, Error, Result};
-use alloy_dyn_abi::{DynSolValue, FunctionExt, JsonAbiExt};
-use alloy_json_abi::{Function, JsonAbi};
-use alloy_primitives::{Address, Selector};
+use alloy_dyn_abi::{
+    DecodedEvent, DynSolValue, Error as AbiError, EventExt, FunctionExt, JsonAbiExt,
+};
+use alloy_json_abi::{Event, Function, JsonAbi};
+use alloy_primitives::{Address, LogData, Selector, B256};
 use std::collections::{BTreeMap, HashMap};
 
 /// A smart contract interface.
@@ -9,14 +11,39 @@ use std::collections::{BTreeMap, HashMap};
 pub struct Interface {
     abi: JsonAbi,
     functions: HashMap<Selector, (String, usize)>,
+    events: HashMap<B256, (String, usize)>,
 }
 
-// TODO: events/errors
+// TODO: errors
 impl Interface {
     /// Creates a new contract interface from the provided ABI.
     pub fn new(abi: JsonAbi) -> Self {
         let functions = create_mapping(&abi.functions, Function::selector);
-        Self { abi, functions }
+        let events = create_mapping(&abi.events, Event::selector);
+        Self { abi, functions, events }
+    }
+
+    /// Creates a new contract interface from human-readable ABI fragments.
+    ///
+    /// Each fragment is a Solidity-like declaration of a function, event, error, constructor,
+    /// fallback or receive function, as accepted by [`JsonAbi::parse`]:
+    ///
+    /// ```
+    /// use alloy_contract::Interface;
+    ///
+    /// let interface = Interface::parse([
+    ///     "function transfer(address to, uint256 amount) returns (bool)",
+    ///     "event Transfer(address indexed from, address indexed to, uint256 value)",
+    /// ])?;
+    /// assert!(interface.abi().function("transfer").is_some());
+    /// # Ok::<(), alloy_contract::Error>(())
+    /// ```
+    ///
+    /// The [`sol!`](alloy_sol_types::sol) macro accepts the same declarations, ending with a
+    /// semicolon, inside an `interface` block.
+    pub fn parse<'a, I: IntoIterator<Item = &'a str>>(fragments: I) -> Result<Self> {
+        let abi = JsonAbi::parse(fragments).map_err(AbiError::from)?;
+        Ok(Self::new(abi))
     }
 
     /// Returns the ABI encoded data (including the selector) for the provided function and
@@ -91,6 +118,23 @@ impl Interface {
         self.get_from_selector(selector)?.abi_decode_output(data, validate).map_err(Into::into)
     }
 
+    /// Decodes a log emitted by one of the interface's events, returning the name of the event
+    /// and its decoded indexed and body values.
+    ///
+    /// The event is matched by the first topic of the log, so logs of anonymous events cannot be
+    /// decoded.
+    pub fn decode_log<'a>(
+        &'a self,
+        log: &LogData,
+        validate: bool,
+    ) -> Result<(&'a str, DecodedEvent)> {
+        let signature = log.topics().first().copied().unwrap_or_default();
+        let (name, index) =
+            self.events.get(&signature).ok_or(Error::UnknownEventSignature(signature))?;
+        let event = &self.abi.events[name][*index];
+        Ok((name, event.decode_log(log, validate)?))
+    }
+
     /// Returns a reference to the contract's ABI.
     pub const fn abi(&self) -> &JsonAbi {
         &self.abi
@@ -108,6 +152,13 @@ impl Interface {
             .ok_or_else(|| Error::UnknownFunction(name.to_string()))
     }
 
+    pub(crate) fn get_event_from_name(&self, name: &str) -> Result<&Event> {
+        self.abi
+            .event(name)
+            .and_then(|r| r.first())
+            .ok_or_else(|| Error::UnknownEvent(name.to_string()))
+    }
+
     pub(crate) fn get_from_selector(&self, selector: &Selector) -> Result<&Function> {
         self.functions
             .get(selector)
@@ -145,3 +196,54 @@ where
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+
+    #[test]
+    fn decode_log() {
+        let interface = Interface::parse([
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "function transfer(address to, uint256 value) returns (bool)",
+        ])
+        .unwrap();
+        let event = &interface.abi().events["Transfer"][0];
+
+        let (from, to) = (address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"), Address::ZERO);
+        let log = LogData::new_unchecked(
+            vec![event.selector(), from.into_word(), to.into_word()],
+            U256::from(42).to_be_bytes_vec().into(),
+        );
+        let (name, decoded) = interface.decode_log(&log, true).unwrap();
+        assert_eq!(name, "Transfer");
+        assert_eq!(decoded.indexed, [DynSolValue::Address(from), DynSolValue::Address(to)]);
+        assert_eq!(decoded.body, [DynSolValue::Uint(U256::from(42), 256)]);
+
+        let log = LogData::new_unchecked(vec![B256::ZERO], Default::default());
+        assert!(matches!(
+            interface.decode_log(&log, true),
+            Err(Error::UnknownEventSignature(B256::ZERO))
+        ));
+    }
+
+    #[test]
+    fn parse_human_readable() {
+        alloy_sol_types::sol! {
+            function transfer(address to, uint256 amount) returns (bool);
+        }
+
+        let interface =
+            Interface::parse(["function transfer(address to, uint256 amount) returns (bool)"])
+                .unwrap();
+        let to = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let args = [DynSolValue::Address(to), DynSolValue::Uint(U256::from(1), 256)];
+        assert_eq!(
+            interface.encode_input("transfer", &args).unwrap(),
+            alloy_sol_types::SolCall::abi_encode(&transferCall { to, amount: U256::from(1) })
+        );
+
+        assert!(matches!(Interface::parse(["function (uint256)"]), Err(Error::AbiError(_))));
+    }
+}
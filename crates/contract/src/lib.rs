@@ -39,6 +39,27 @@ pub use instance::*;
 mod call;
 pub use call::*;
 
+mod ext;
+pub use ext::ProviderCallExt;
+
+pub mod nft;
+
+#[cfg(feature = "math")]
+pub mod math;
+
+#[cfg(feature = "pool-math")]
+pub mod pool_math;
+
+pub mod price_feed;
+
+#[cfg(feature = "siwe")]
+pub mod siwe;
+
+#[cfg(feature = "tokens")]
+pub mod tokens;
+
+pub mod weth;
+
 // Not public API.
 // NOTE: please avoid changing the API of this module due to its use in the `sol!` macro.
 #[doc(hidden)]
@@ -0,0 +1,191 @@
+//! Overflow-checked integer math on [`U256`], as commonly needed to interact with DeFi contracts.
+//!
+//! Products are computed with a 512-bit intermediate, so that `a * b / denominator` only fails if
+//! the final result does not fit in 256 bits. WAD and RAY numbers are fixed-point numbers with 18
+//! and 27 decimals, as used by MakerDAO's `DSMath` and Aave's `WadRayMath`, whose rounding to the
+//! nearest value, with halves rounded up, is followed here.
+
+use alloy_primitives::{uint, U256, U512};
+
+/// `10^18`, the scaling factor of WAD numbers.
+pub const WAD: U256 = uint!(1_000_000_000_000_000_000_U256);
+
+/// `10^27`, the scaling factor of RAY numbers.
+pub const RAY: U256 = uint!(1_000_000_000_000_000_000_000_000_000_U256);
+
+/// `10^9`, the ratio between [`RAY`] and [`WAD`].
+const WAD_RAY_RATIO: U256 = uint!(1_000_000_000_U256);
+
+/// The number of basis points in a whole, i.e. 100%.
+pub const BPS: u64 = 10_000;
+
+/// Computes `a * b / denominator` with full precision, rounding down.
+///
+/// Returns `None` if the denominator is zero or the result overflows.
+pub fn mul_div(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let product: U512 = a.widening_mul(b);
+    truncate(product / U512::from(denominator))
+}
+
+/// Computes `a * b / denominator` with full precision, rounding up.
+///
+/// Returns `None` if the denominator is zero or the result overflows.
+pub fn mul_div_rounding_up(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let product: U512 = a.widening_mul(b);
+    let (quotient, remainder) = product.div_rem(U512::from(denominator));
+    let quotient = if remainder.is_zero() { quotient } else { quotient + U512::from(1) };
+    truncate(quotient)
+}
+
+/// Computes `a * b / denominator` with full precision, rounding to the nearest value, with halves
+/// rounded up.
+fn mul_div_rounding_half_up(a: U256, b: U256, denominator: U256) -> Option<U256> {
+    if denominator.is_zero() {
+        return None;
+    }
+    let product: U512 = a.widening_mul(b);
+    let denominator = U512::from(denominator);
+    truncate((product + (denominator >> 1)) / denominator)
+}
+
+fn truncate(x: U512) -> Option<U256> {
+    let (low, high) = x.as_limbs().split_at(4);
+    high.iter().all(|limb| *limb == 0).then(|| U256::from_limbs(low.try_into().unwrap()))
+}
+
+/// Returns `percent`% of `value`, rounding down.
+///
+/// Returns `None` if the result overflows, which is only possible above 100%.
+pub fn percent_of(value: U256, percent: u64) -> Option<U256> {
+    mul_div(value, U256::from(percent), U256::from(100))
+}
+
+/// Returns `percent`% of `value`, rounding down and saturating at [`U256::MAX`].
+pub fn saturating_percent_of(value: U256, percent: u64) -> U256 {
+    percent_of(value, percent).unwrap_or(U256::MAX)
+}
+
+/// Returns `bps` basis points of `value`, rounding down.
+///
+/// Returns `None` if the result overflows, which is only possible above [`BPS`].
+pub fn bps_of(value: U256, bps: u64) -> Option<U256> {
+    mul_div(value, U256::from(bps), U256::from(BPS))
+}
+
+/// Returns `bps` basis points of `value`, rounding down and saturating at [`U256::MAX`].
+pub fn saturating_bps_of(value: U256, bps: u64) -> U256 {
+    bps_of(value, bps).unwrap_or(U256::MAX)
+}
+
+/// Multiplies two WAD numbers.
+///
+/// Returns `None` if the result overflows.
+pub fn wad_mul(a: U256, b: U256) -> Option<U256> {
+    mul_div_rounding_half_up(a, b, WAD)
+}
+
+/// Divides two WAD numbers.
+///
+/// Returns `None` if `b` is zero or the result overflows.
+pub fn wad_div(a: U256, b: U256) -> Option<U256> {
+    mul_div_rounding_half_up(a, WAD, b)
+}
+
+/// Multiplies two RAY numbers.
+///
+/// Returns `None` if the result overflows.
+pub fn ray_mul(a: U256, b: U256) -> Option<U256> {
+    mul_div_rounding_half_up(a, b, RAY)
+}
+
+/// Divides two RAY numbers.
+///
+/// Returns `None` if `b` is zero or the result overflows.
+pub fn ray_div(a: U256, b: U256) -> Option<U256> {
+    mul_div_rounding_half_up(a, RAY, b)
+}
+
+/// Converts a WAD number to a RAY number.
+///
+/// Returns `None` if the result overflows.
+pub const fn wad_to_ray(wad: U256) -> Option<U256> {
+    wad.checked_mul(WAD_RAY_RATIO)
+}
+
+/// Converts a RAY number to a WAD number, rounding to the nearest value.
+pub fn ray_to_wad(ray: U256) -> U256 {
+    let (wad, remainder) = ray.div_rem(WAD_RAY_RATIO);
+    if remainder >= WAD_RAY_RATIO >> 1 {
+        wad + U256::from(1)
+    } else {
+        wad
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_precision() {
+        let max = U256::MAX;
+        assert_eq!(mul_div(max, max, max), Some(max));
+        assert_eq!(mul_div(max, U256::from(2), U256::from(4)), Some(max >> 1));
+        assert_eq!(mul_div(U256::from(1), U256::from(1), U256::ZERO), None);
+        assert_eq!(mul_div(max, max, U256::from(1)), None);
+        assert_eq!(mul_div(U256::from(5), U256::from(1), U256::from(2)), Some(U256::from(2)));
+        assert_eq!(
+            mul_div_rounding_up(U256::from(5), U256::from(1), U256::from(2)),
+            Some(U256::from(3))
+        );
+        assert_eq!(
+            mul_div_rounding_up(U256::from(4), U256::from(1), U256::from(2)),
+            Some(U256::from(2))
+        );
+        assert_eq!(mul_div_rounding_up(max, max, max), Some(max));
+    }
+
+    #[test]
+    fn percentages() {
+        let value = U256::from(12_345);
+        assert_eq!(percent_of(value, 10), Some(U256::from(1_234)));
+        assert_eq!(percent_of(value, 100), Some(value));
+        assert_eq!(bps_of(value, 30), Some(U256::from(37)));
+        assert_eq!(bps_of(value, BPS), Some(value));
+        // The product overflows 256 bits, but not the result.
+        assert_eq!(percent_of(U256::MAX, 50), Some(U256::MAX >> 1));
+        assert_eq!(percent_of(U256::MAX, 101), None);
+        assert_eq!(saturating_percent_of(U256::MAX, 101), U256::MAX);
+        assert_eq!(saturating_bps_of(U256::MAX, BPS + 1), U256::MAX);
+        assert_eq!(saturating_bps_of(value, 5_000), U256::from(6_172));
+    }
+
+    #[test]
+    fn fixed_point() {
+        let wad = |x: u64| U256::from(x) * WAD;
+        assert_eq!(wad_mul(wad(3), wad(2)), Some(wad(6)));
+        assert_eq!(wad_div(wad(3), wad(2)), Some(WAD + WAD / U256::from(2)));
+        assert_eq!(wad_div(wad(1), U256::ZERO), None);
+        // 2 / 3 * 10^-18 rounds up, 1 / 3 * 10^-18 rounds down.
+        assert_eq!(wad_mul(U256::from(2), WAD / U256::from(3)), Some(U256::from(1)));
+        assert_eq!(wad_mul(U256::from(1), WAD / U256::from(3)), Some(U256::ZERO));
+        assert_eq!(wad_mul(U256::MAX, wad(2)), None);
+
+        let ray = |x: u64| U256::from(x) * RAY;
+        assert_eq!(ray_mul(ray(3), ray(2)), Some(ray(6)));
+        assert_eq!(ray_div(ray(1), ray(4)), Some(RAY / U256::from(4)));
+        assert_eq!(ray_div(RAY, U256::ZERO), None);
+
+        assert_eq!(wad_to_ray(wad(5)), Some(ray(5)));
+        assert_eq!(wad_to_ray(U256::MAX), None);
+        assert_eq!(ray_to_wad(ray(5)), wad(5));
+        assert_eq!(ray_to_wad(U256::from(500_000_000)), U256::from(1));
+        assert_eq!(ray_to_wad(U256::from(499_999_999)), U256::ZERO);
+    }
+}
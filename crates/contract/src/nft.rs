@@ -0,0 +1,411 @@
+//! ERC-721 and ERC-1155 token URI and metadata resolution.
+//!
+//! Token URIs are read through the `tokenURI` ([ERC-721]) and `uri` ([ERC-1155]) metadata
+//! extensions, and can be normalized from `ipfs://` and `ar://` schemes to HTTP gateway URLs.
+//! Fetching and parsing the metadata JSON itself requires the `nft-metadata` feature.
+//!
+//! [ERC-721]: https://eips.ethereum.org/EIPS/eip-721
+//! [ERC-1155]: https://eips.ethereum.org/EIPS/eip-1155
+
+use crate::{Result, SolCallBuilder};
+use alloy_network::Network;
+use alloy_primitives::{Address, U256};
+use alloy_provider::Provider;
+use alloy_sol_types::sol;
+use alloy_transport::Transport;
+
+pub use interfaces::{IERC1155MetadataURI, IERC721Metadata};
+
+#[allow(missing_docs)]
+mod interfaces {
+    use super::sol;
+
+    sol! {
+        /// The ERC-721 metadata extension.
+        interface IERC721Metadata {
+            /// Returns the URI of the metadata JSON of the given token.
+            function tokenURI(uint256 tokenId) external view returns (string uri);
+        }
+
+        /// The ERC-1155 metadata URI extension.
+        interface IERC1155MetadataURI {
+            /// Returns the URI of the metadata JSON of the given token, possibly containing an
+            /// `{id}` placeholder.
+            function uri(uint256 id) external view returns (string uri);
+        }
+    }
+}
+
+/// Default IPFS HTTP gateway used by [`UriGateways`].
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io";
+
+/// Default Arweave HTTP gateway used by [`UriGateways`].
+pub const DEFAULT_ARWEAVE_GATEWAY: &str = "https://arweave.net";
+
+/// The token standard used to look up a token URI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NftStandard {
+    /// [ERC-721](https://eips.ethereum.org/EIPS/eip-721), using `tokenURI(uint256)`.
+    Erc721,
+    /// [ERC-1155](https://eips.ethereum.org/EIPS/eip-1155), using `uri(uint256)`.
+    Erc1155,
+}
+
+/// HTTP gateways used to resolve content-addressed URIs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UriGateways {
+    /// Base URL of the IPFS gateway, e.g. `https://ipfs.io`.
+    pub ipfs: String,
+    /// Base URL of the Arweave gateway, e.g. `https://arweave.net`.
+    pub arweave: String,
+}
+
+impl Default for UriGateways {
+    fn default() -> Self {
+        Self {
+            ipfs: DEFAULT_IPFS_GATEWAY.to_string(),
+            arweave: DEFAULT_ARWEAVE_GATEWAY.to_string(),
+        }
+    }
+}
+
+impl UriGateways {
+    /// Rewrites `ipfs://` and `ar://` URIs to URLs on the configured gateways.
+    ///
+    /// Both `ipfs://<cid>` and the non-standard `ipfs://ipfs/<cid>` forms are accepted. All other
+    /// URIs, including `http(s)://` and `data:` URIs, are returned unchanged.
+    pub fn normalize(&self, uri: &str) -> String {
+        let uri = uri.trim();
+        if let Some(path) = uri.strip_prefix("ipfs://") {
+            let path = path.strip_prefix("ipfs/").unwrap_or(path);
+            format!("{}/ipfs/{path}", self.ipfs.trim_end_matches('/'))
+        } else if let Some(path) = uri.strip_prefix("ar://") {
+            format!("{}/{path}", self.arweave.trim_end_matches('/'))
+        } else {
+            uri.to_string()
+        }
+    }
+}
+
+/// Substitutes the `{id}` placeholder of an ERC-1155 URI with the token ID, encoded as 64
+/// lowercase hex characters without a `0x` prefix, as mandated by the standard.
+pub fn expand_erc1155_uri(uri: &str, id: U256) -> String {
+    uri.replace("{id}", &format!("{id:064x}"))
+}
+
+/// Returns the metadata URI of the given token.
+///
+/// For ERC-1155 tokens, the `{id}` placeholder is substituted with the token ID.
+pub async fn token_uri<T, P, N>(
+    provider: &P,
+    token: Address,
+    token_id: U256,
+    standard: NftStandard,
+) -> Result<String>
+where
+    T: Transport + Clone,
+    P: Provider<T, N>,
+    N: Network,
+{
+    match standard {
+        NftStandard::Erc721 => {
+            let call = IERC721Metadata::tokenURICall { tokenId: token_id };
+            let uri = SolCallBuilder::new_sol(provider, &token, &call).call().await?.uri;
+            Ok(uri)
+        }
+        NftStandard::Erc1155 => {
+            let call = IERC1155MetadataURI::uriCall { id: token_id };
+            let uri = SolCallBuilder::new_sol(provider, &token, &call).call().await?.uri;
+            Ok(expand_erc1155_uri(&uri, token_id))
+        }
+    }
+}
+
+#[cfg(feature = "nft-metadata")]
+pub use metadata::*;
+
+#[cfg(feature = "nft-metadata")]
+mod metadata {
+    use super::{token_uri, NftStandard, UriGateways};
+    use alloy_network::Network;
+    use alloy_primitives::{Address, U256};
+    use alloy_provider::Provider;
+    use alloy_transport::Transport;
+    use base64::Engine;
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+    use thiserror::Error;
+
+    /// Default maximum size of a metadata document, in bytes.
+    pub const DEFAULT_MAX_METADATA_SIZE: usize = 1024 * 1024;
+
+    /// Error returned when resolving token metadata.
+    #[derive(Debug, Error)]
+    pub enum MetadataError {
+        /// Reading the token URI from the contract failed.
+        #[error(transparent)]
+        Contract(#[from] crate::Error),
+        /// The HTTP request failed.
+        #[error(transparent)]
+        Http(#[from] reqwest::Error),
+        /// The metadata document exceeds the configured size limit.
+        #[error("metadata exceeds the size limit of {0} bytes")]
+        TooLarge(usize),
+        /// The `data:` URI is malformed.
+        #[error("invalid data URI")]
+        InvalidDataUri,
+        /// The base64 payload of a `data:` URI is malformed.
+        #[error(transparent)]
+        Base64(#[from] base64::DecodeError),
+        /// The metadata is not valid JSON.
+        #[error(transparent)]
+        Json(#[from] serde_json::Error),
+    }
+
+    /// A single attribute of [`NftMetadata`].
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct NftAttribute {
+        /// The name of the trait.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub trait_type: Option<String>,
+        /// The value of the trait.
+        pub value: serde_json::Value,
+        /// How the trait should be displayed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub display_type: Option<String>,
+    }
+
+    /// Token metadata as defined by the ERC-721 and ERC-1155 metadata JSON schemas, along with the
+    /// widely used OpenSea extensions.
+    #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct NftMetadata {
+        /// Name of the asset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+        /// Description of the asset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+        /// URI of the image of the asset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub image: Option<String>,
+        /// Raw SVG image data of the asset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub image_data: Option<String>,
+        /// URL of an external page describing the asset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub external_url: Option<String>,
+        /// URI of a multimedia attachment of the asset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub animation_url: Option<String>,
+        /// Attributes of the asset.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub attributes: Vec<NftAttribute>,
+        /// Any other fields.
+        #[serde(flatten)]
+        pub other: BTreeMap<String, serde_json::Value>,
+    }
+
+    /// Decodes the payload of a `data:` URI, returning its media type and content.
+    ///
+    /// Both base64 and percent-encoded payloads are supported. The media type defaults to
+    /// `text/plain` when omitted.
+    pub fn decode_data_uri(uri: &str) -> Result<(String, Vec<u8>), MetadataError> {
+        let rest = uri.strip_prefix("data:").ok_or(MetadataError::InvalidDataUri)?;
+        let (header, payload) = rest.split_once(',').ok_or(MetadataError::InvalidDataUri)?;
+        let (media_type, base64) = match header.strip_suffix(";base64") {
+            Some(media_type) => (media_type, true),
+            None => (header, false),
+        };
+        let media_type = if media_type.is_empty() { "text/plain" } else { media_type };
+
+        let data = if base64 {
+            base64::engine::general_purpose::STANDARD.decode(payload)?
+        } else {
+            percent_decode(payload)?
+        };
+        Ok((media_type.to_string(), data))
+    }
+
+    fn percent_decode(s: &str) -> Result<Vec<u8>, MetadataError> {
+        let bytes = s.as_bytes();
+        let mut out = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'%' {
+                let hex = bytes.get(i + 1..i + 3).ok_or(MetadataError::InvalidDataUri)?;
+                let hex = std::str::from_utf8(hex).map_err(|_| MetadataError::InvalidDataUri)?;
+                out.push(u8::from_str_radix(hex, 16).map_err(|_| MetadataError::InvalidDataUri)?);
+                i += 3;
+            } else {
+                out.push(bytes[i]);
+                i += 1;
+            }
+        }
+        Ok(out)
+    }
+
+    /// Fetches and parses token metadata documents.
+    #[derive(Clone, Debug)]
+    pub struct MetadataFetcher {
+        client: reqwest::Client,
+        gateways: UriGateways,
+        max_size: usize,
+    }
+
+    impl Default for MetadataFetcher {
+        fn default() -> Self {
+            Self::new(reqwest::Client::new())
+        }
+    }
+
+    impl MetadataFetcher {
+        /// Creates a new fetcher using the given HTTP client, the default gateways and a size
+        /// limit of [`DEFAULT_MAX_METADATA_SIZE`].
+        pub fn new(client: reqwest::Client) -> Self {
+            Self { client, gateways: UriGateways::default(), max_size: DEFAULT_MAX_METADATA_SIZE }
+        }
+
+        /// Sets the gateways used to resolve `ipfs://` and `ar://` URIs.
+        pub fn with_gateways(mut self, gateways: UriGateways) -> Self {
+            self.gateways = gateways;
+            self
+        }
+
+        /// Sets the maximum size of a metadata document, in bytes.
+        pub const fn with_max_size(mut self, max_size: usize) -> Self {
+            self.max_size = max_size;
+            self
+        }
+
+        /// Fetches and parses the metadata document at the given URI.
+        pub async fn fetch(&self, uri: &str) -> Result<NftMetadata, MetadataError> {
+            let body = self.fetch_raw(uri).await?;
+            Ok(serde_json::from_slice(&body)?)
+        }
+
+        /// Fetches the raw metadata document at the given URI, enforcing the size limit.
+        pub async fn fetch_raw(&self, uri: &str) -> Result<Vec<u8>, MetadataError> {
+            if uri.starts_with("data:") {
+                let (_, data) = decode_data_uri(uri)?;
+                if data.len() > self.max_size {
+                    return Err(MetadataError::TooLarge(self.max_size));
+                }
+                return Ok(data);
+            }
+
+            let mut response =
+                self.client.get(self.gateways.normalize(uri)).send().await?.error_for_status()?;
+            if response.content_length().is_some_and(|len| len > self.max_size as u64) {
+                return Err(MetadataError::TooLarge(self.max_size));
+            }
+
+            let mut body = Vec::new();
+            while let Some(chunk) = response.chunk().await? {
+                if body.len() + chunk.len() > self.max_size {
+                    return Err(MetadataError::TooLarge(self.max_size));
+                }
+                body.extend_from_slice(&chunk);
+            }
+            Ok(body)
+        }
+
+        /// Reads the token URI from the contract, then fetches and parses its metadata.
+        pub async fn fetch_token<T, P, N>(
+            &self,
+            provider: &P,
+            token: Address,
+            token_id: U256,
+            standard: NftStandard,
+        ) -> Result<NftMetadata, MetadataError>
+        where
+            T: Transport + Clone,
+            P: Provider<T, N>,
+            N: Network,
+        {
+            let uri = token_uri(provider, token, token_id, standard).await?;
+            self.fetch(&uri).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_uris() {
+        let gateways = UriGateways::default();
+        assert_eq!(
+            gateways.normalize("ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG/1.json"),
+            "https://ipfs.io/ipfs/QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG/1.json"
+        );
+        assert_eq!(
+            gateways.normalize("ipfs://ipfs/QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+            "https://ipfs.io/ipfs/QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+        );
+        assert_eq!(
+            gateways.normalize("ar://bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"),
+            "https://arweave.net/bNbA3TEQVL60xlgCcqdz4ZPHFZ711cZ3hmkpGttDt_U"
+        );
+        assert_eq!(gateways.normalize("https://example.com/1"), "https://example.com/1");
+        assert_eq!(gateways.normalize("data:,{}"), "data:,{}");
+
+        let gateways = UriGateways { ipfs: "https://cf-ipfs.com/".into(), ..Default::default() };
+        assert_eq!(gateways.normalize("ipfs://cid"), "https://cf-ipfs.com/ipfs/cid");
+    }
+
+    #[test]
+    fn expands_erc1155_uri() {
+        assert_eq!(
+            expand_erc1155_uri("https://token-cdn-domain/{id}.json", U256::from(314592)),
+            "https://token-cdn-domain/000000000000000000000000000000000000000000000000000000000004cce0.json"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "nft-metadata")]
+    fn decodes_data_uris() {
+        let (media_type, data) =
+            decode_data_uri("data:application/json;base64,eyJuYW1lIjoiQSJ9").unwrap();
+        assert_eq!(media_type, "application/json");
+        assert_eq!(data, br#"{"name":"A"}"#);
+
+        let (media_type, data) = decode_data_uri("data:,%7B%22name%22%3A%22A%22%7D").unwrap();
+        assert_eq!(media_type, "text/plain");
+        assert_eq!(data, br#"{"name":"A"}"#);
+
+        assert!(decode_data_uri("data:application/json").is_err());
+        assert!(decode_data_uri("data:,%7").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "nft-metadata")]
+    fn parses_metadata() {
+        let json = r#"{
+            "name": "Dave Starbelly",
+            "description": "Friendly OpenSea Creature.",
+            "image": "ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            "attributes": [
+                { "trait_type": "Base", "value": "Starfish" },
+                { "display_type": "number", "trait_type": "Generation", "value": 2 }
+            ],
+            "background_color": "ffffff"
+        }"#;
+        let metadata: NftMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(metadata.name.as_deref(), Some("Dave Starbelly"));
+        assert_eq!(metadata.attributes.len(), 2);
+        assert_eq!(metadata.attributes[1].value, serde_json::json!(2));
+        assert_eq!(metadata.other["background_color"], "ffffff");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "nft-metadata")]
+    async fn fetches_data_uri_with_limit() {
+        let fetcher = MetadataFetcher::default();
+        let uri = "data:application/json;base64,eyJuYW1lIjoiQSJ9";
+        assert_eq!(fetcher.fetch(uri).await.unwrap().name.as_deref(), Some("A"));
+
+        let fetcher = fetcher.with_max_size(4);
+        assert!(matches!(fetcher.fetch(uri).await, Err(MetadataError::TooLarge(4))));
+    }
+}
@@ -0,0 +1,221 @@
+//! Fixed-point math for concentrated liquidity pools, following Uniswap v3's `TickMath` and
+//! `FullMath` libraries.
+//!
+//! Prices are represented as `sqrtPriceX96`, the square root of the price of token0 in terms of
+//! token1 as a Q64.96 fixed-point number. Ticks are the base-1.0001 logarithm of the price.
+
+pub use crate::math::{mul_div, mul_div_rounding_up};
+
+use alloy_primitives::U256;
+use thiserror::Error;
+
+/// `2^96`, the scaling factor of Q64.96 numbers.
+pub const Q96: U256 = U256::from_limbs([0, 1 << 32, 0, 0]);
+
+/// The minimum tick, i.e. `log_1.0001(2^-128)`.
+pub const MIN_TICK: i32 = -887272;
+
+/// The maximum tick, i.e. `log_1.0001(2^128)`.
+pub const MAX_TICK: i32 = -MIN_TICK;
+
+/// The minimum `sqrtPriceX96`, i.e. [`get_sqrt_ratio_at_tick`] of [`MIN_TICK`].
+pub const MIN_SQRT_RATIO: U256 = U256::from_limbs([4295128739, 0, 0, 0]);
+
+/// The maximum `sqrtPriceX96`, i.e. [`get_sqrt_ratio_at_tick`] of [`MAX_TICK`].
+pub const MAX_SQRT_RATIO: U256 =
+    U256::from_limbs([0x5d951d5263988d26, 0xefd1fc6a50648849, 0xfffd8963, 0]);
+
+/// Error returned by the pool math functions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum PoolMathError {
+    /// The tick is outside of `[MIN_TICK, MAX_TICK]`.
+    #[error("tick {0} is out of range")]
+    TickOutOfRange(i32),
+    /// The square root price is outside of `[MIN_SQRT_RATIO, MAX_SQRT_RATIO)`.
+    #[error("sqrt price {0} is out of range")]
+    SqrtPriceOutOfRange(U256),
+}
+
+/// Returns `sqrt(1.0001^tick) * 2^96`, rounded up.
+pub fn get_sqrt_ratio_at_tick(tick: i32) -> Result<U256, PoolMathError> {
+    let abs_tick = tick.unsigned_abs();
+    if abs_tick > MAX_TICK as u32 {
+        return Err(PoolMathError::TickOutOfRange(tick));
+    }
+
+    // `sqrt(1.0001^-2^i) * 2^128` for every bit `i` of the tick.
+    const FACTORS: [u128; 19] = [
+        0xfff97272373d413259a46990580e213a,
+        0xfff2e50f5f656932ef12357cf3c7fdcc,
+        0xffe5caca7e10e4e61c3624eaa0941cd0,
+        0xffcb9843d60f6159c9db58835c926644,
+        0xff973b41fa98c081472e6896dfb254c0,
+        0xff2ea16466c96a3843ec78b326b52861,
+        0xfe5dee046a99a2a811c461f1969c3053,
+        0xfcbe86c7900a88aedcffc83b479aa3a4,
+        0xf987a7253ac413176f2b074cf7815e54,
+        0xf3392b0822b70005940c7a398e4b70f3,
+        0xe7159475a2c29b7443b29c7fa6e889d9,
+        0xd097f3bdfd2022b8845ad8f792aa5825,
+        0xa9f746462d870fdf8a65dc1f90e061e5,
+        0x70d869a156d2a1b890bb3df62baf32f7,
+        0x31be135f97d08fd981231505542fcfa6,
+        0x9aa508b5b7a84e1c677de54f3e99bc9,
+        0x5d6af8dedb81196699c329225ee604,
+        0x2216e584f5fa1ea926041bedfe98,
+        0x48a170391f7dc42444e8fa2,
+    ];
+
+    let mut ratio = if abs_tick & 1 != 0 {
+        U256::from(0xfffcb933bd6fad37aa2d162d1a594001u128)
+    } else {
+        U256::from(1) << 128
+    };
+    for (i, factor) in FACTORS.iter().enumerate() {
+        if abs_tick & (2 << i) != 0 {
+            ratio = (ratio * U256::from(*factor)) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Downcast from Q128.128 to Q64.96, rounding up.
+    let round_up = !(ratio & U256::from(u32::MAX)).is_zero();
+    Ok((ratio >> 32) + U256::from(round_up as u8))
+}
+
+/// Returns the greatest tick whose square root ratio is less than or equal to `sqrt_price_x96`.
+pub fn get_tick_at_sqrt_ratio(sqrt_price_x96: U256) -> Result<i32, PoolMathError> {
+    if sqrt_price_x96 < MIN_SQRT_RATIO || sqrt_price_x96 >= MAX_SQRT_RATIO {
+        return Err(PoolMathError::SqrtPriceOutOfRange(sqrt_price_x96));
+    }
+
+    // Invariant: `ratio(low) <= sqrt_price_x96 < ratio(high)`.
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if get_sqrt_ratio_at_tick(mid)? <= sqrt_price_x96 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+/// Converts a `sqrtPriceX96` to the price of token0 in terms of token1, in raw token units.
+///
+/// Use [`adjust_for_decimals`] to get the price in whole tokens.
+pub fn sqrt_price_x96_to_price(sqrt_price_x96: U256) -> f64 {
+    let sqrt_price = f64::from(sqrt_price_x96) / f64::from(Q96);
+    sqrt_price * sqrt_price
+}
+
+/// Converts the price of token0 in terms of token1, in raw token units, to a `sqrtPriceX96`.
+///
+/// Returns `None` if the price is negative, not finite, or too large to be represented.
+pub fn price_to_sqrt_price_x96(price: f64) -> Option<U256> {
+    if !price.is_finite() || price < 0.0 {
+        return None;
+    }
+    U256::try_from(price.sqrt() * f64::from(Q96)).ok()
+}
+
+/// Converts a price in raw token units to a price in whole tokens, given the decimals of both
+/// tokens.
+pub fn adjust_for_decimals(price: f64, decimals0: u8, decimals1: u8) -> f64 {
+    price * 10f64.powi(decimals0 as i32 - decimals1 as i32)
+}
+
+/// Returns the price at the given tick, i.e. `1.0001^tick`.
+pub fn tick_to_price(tick: i32) -> f64 {
+    1.0001f64.powi(tick)
+}
+
+/// Returns the greatest tick whose price is less than or equal to `price`, clamped to
+/// `[MIN_TICK, MAX_TICK]`.
+///
+/// This uses floating-point math and may be off by one tick close to tick boundaries. Use
+/// [`get_tick_at_sqrt_ratio`] for exact results.
+pub fn price_to_tick(price: f64) -> i32 {
+    let tick = (price.ln() / 1.0001f64.ln()).floor();
+    tick.clamp(MIN_TICK as f64, MAX_TICK as f64) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::uint;
+
+    #[test]
+    fn sqrt_ratio_bounds() {
+        assert_eq!(get_sqrt_ratio_at_tick(0).unwrap(), Q96);
+        assert_eq!(get_sqrt_ratio_at_tick(MIN_TICK).unwrap(), MIN_SQRT_RATIO);
+        assert_eq!(get_sqrt_ratio_at_tick(MAX_TICK).unwrap(), MAX_SQRT_RATIO);
+        assert_eq!(MAX_SQRT_RATIO, uint!(1461446703485210103287273052203988822378723970342_U256));
+        assert_eq!(
+            get_sqrt_ratio_at_tick(MAX_TICK + 1),
+            Err(PoolMathError::TickOutOfRange(MAX_TICK + 1))
+        );
+        assert!(get_sqrt_ratio_at_tick(MIN_TICK - 1).is_err());
+    }
+
+    #[test]
+    fn sqrt_ratio_values() {
+        // Matches `sqrt(1.0001^tick) * 2^96`, rounded up.
+        assert_eq!(get_sqrt_ratio_at_tick(50).unwrap(), uint!(79426470787362580746886972461_U256));
+        assert_eq!(get_sqrt_ratio_at_tick(-50).unwrap(), uint!(79030349367926598376800521322_U256));
+        assert_eq!(get_sqrt_ratio_at_tick(100).unwrap(), uint!(79625275426524748796330556128_U256));
+    }
+
+    #[test]
+    fn tick_at_sqrt_ratio() {
+        assert_eq!(get_tick_at_sqrt_ratio(MIN_SQRT_RATIO).unwrap(), MIN_TICK);
+        assert_eq!(get_tick_at_sqrt_ratio(MAX_SQRT_RATIO - U256::from(1)).unwrap(), MAX_TICK - 1);
+        assert_eq!(get_tick_at_sqrt_ratio(Q96).unwrap(), 0);
+        for tick in [-887271, -50000, -1, 1, 12345, 500000] {
+            let ratio = get_sqrt_ratio_at_tick(tick).unwrap();
+            assert_eq!(get_tick_at_sqrt_ratio(ratio).unwrap(), tick);
+            assert_eq!(get_tick_at_sqrt_ratio(ratio - U256::from(1)).unwrap(), tick - 1);
+        }
+        assert!(get_tick_at_sqrt_ratio(MAX_SQRT_RATIO).is_err());
+        assert!(get_tick_at_sqrt_ratio(MIN_SQRT_RATIO - U256::from(1)).is_err());
+    }
+
+    #[test]
+    fn full_math() {
+        let max = U256::MAX;
+        assert_eq!(mul_div(max, max, max), Some(max));
+        assert_eq!(
+            mul_div(Q96, U256::from(3), U256::from(2)),
+            Some(Q96 * U256::from(3) / U256::from(2))
+        );
+        assert_eq!(mul_div(U256::from(1), U256::from(1), U256::ZERO), None);
+        assert_eq!(mul_div(max, max, U256::from(1)), None);
+        assert_eq!(
+            mul_div_rounding_up(U256::from(5), U256::from(1), U256::from(2)),
+            Some(U256::from(3))
+        );
+        assert_eq!(
+            mul_div_rounding_up(U256::from(4), U256::from(1), U256::from(2)),
+            Some(U256::from(2))
+        );
+    }
+
+    #[test]
+    fn prices() {
+        assert_eq!(sqrt_price_x96_to_price(Q96), 1.0);
+        assert_eq!(price_to_sqrt_price_x96(4.0), Some(Q96 * U256::from(2)));
+        assert_eq!(price_to_sqrt_price_x96(-1.0), None);
+
+        // USDC/WETH: 10^12 raw units of USDC per raw unit of WETH is one USDC per ether.
+        let price = adjust_for_decimals(1e-9, 18, 6);
+        assert!((price - 1000.0).abs() < 1e-9);
+
+        assert_eq!(tick_to_price(0), 1.0);
+        assert_eq!(price_to_tick(1.0), 0);
+        assert_eq!(price_to_tick(tick_to_price(1000) * 1.00001), 1000);
+        assert_eq!(price_to_tick(0.0), MIN_TICK);
+    }
+}
@@ -0,0 +1,357 @@
+//! Readers for [Chainlink] `AggregatorV3Interface` price feeds.
+//!
+//! [Chainlink]: https://docs.chain.link/data-feeds/api-reference
+
+use crate::SolCallBuilder;
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{Address, I256, U256};
+use alloy_provider::Provider;
+use alloy_sol_types::sol;
+use alloy_transport::Transport;
+use std::{
+    marker::PhantomData,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+pub use interfaces::AggregatorV3Interface;
+
+#[allow(missing_docs)]
+mod interfaces {
+    use super::sol;
+
+    sol! {
+        /// The Chainlink data feed interface.
+        interface AggregatorV3Interface {
+            function decimals() external view returns (uint8 decimals);
+
+            function description() external view returns (string description);
+
+            function version() external view returns (uint256 version);
+
+            function getRoundData(uint80 _roundId)
+                external
+                view
+                returns (
+                    uint80 roundId,
+                    int256 answer,
+                    uint256 startedAt,
+                    uint256 updatedAt,
+                    uint80 answeredInRound
+                );
+
+            function latestRoundData()
+                external
+                view
+                returns (
+                    uint80 roundId,
+                    int256 answer,
+                    uint256 startedAt,
+                    uint256 updatedAt,
+                    uint80 answeredInRound
+                );
+        }
+    }
+}
+
+/// Error returned when reading a [`PriceFeed`].
+#[derive(Debug, Error)]
+pub enum PriceFeedError {
+    /// The call to the aggregator failed.
+    #[error(transparent)]
+    Contract(#[from] crate::Error),
+    /// The round has not been updated within the allowed age.
+    #[error("round {round_id} is stale: last updated at {updated_at}, max age is {max_age:?}")]
+    Stale {
+        /// The stale round.
+        round_id: u128,
+        /// The UNIX timestamp of the last update.
+        updated_at: u64,
+        /// The maximum allowed age.
+        max_age: Duration,
+    },
+    /// The round has not been completed.
+    #[error("round {0} is incomplete")]
+    IncompleteRound(u128),
+    /// The answer is zero or negative.
+    #[error("invalid answer {0}")]
+    InvalidAnswer(I256),
+    /// The answer does not fit in an `int256` once rescaled.
+    #[error("answer {value} overflows when rescaled from {from} to {to} decimals")]
+    Overflow {
+        /// The answer.
+        value: I256,
+        /// The decimals of the answer.
+        from: u8,
+        /// The requested decimals.
+        to: u8,
+    },
+}
+
+/// A round of a price feed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RoundData {
+    /// The round ID, composed of the phase ID in the upper 16 bits and the aggregator round ID in
+    /// the lower 64 bits.
+    pub round_id: u128,
+    /// The answer of the round, scaled by [`PriceFeed::decimals`].
+    pub answer: I256,
+    /// The UNIX timestamp at which the round started.
+    pub started_at: u64,
+    /// The UNIX timestamp at which the round was last updated.
+    pub updated_at: u64,
+    /// Deprecated, kept for compatibility with the aggregator interface.
+    pub answered_in_round: u128,
+}
+
+impl RoundData {
+    /// Returns the phase ID of the round.
+    pub const fn phase_id(&self) -> u16 {
+        (self.round_id >> 64) as u16
+    }
+
+    /// Returns the aggregator round ID of the round, i.e. its index within the phase.
+    pub const fn aggregator_round_id(&self) -> u64 {
+        self.round_id as u64
+    }
+
+    /// Returns whether the round has been completed.
+    pub const fn is_complete(&self) -> bool {
+        self.updated_at != 0
+    }
+
+    /// Checks that the round is complete, has a positive answer, and was updated no longer than
+    /// `max_age` before `now`.
+    pub fn validate(&self, max_age: Duration, now: SystemTime) -> Result<(), PriceFeedError> {
+        if !self.is_complete() {
+            return Err(PriceFeedError::IncompleteRound(self.round_id));
+        }
+        if self.answer <= I256::ZERO {
+            return Err(PriceFeedError::InvalidAnswer(self.answer));
+        }
+        let updated_at = UNIX_EPOCH + Duration::from_secs(self.updated_at);
+        if now.duration_since(updated_at).is_ok_and(|age| age > max_age) {
+            return Err(PriceFeedError::Stale {
+                round_id: self.round_id,
+                updated_at: self.updated_at,
+                max_age,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl From<AggregatorV3Interface::getRoundDataReturn> for RoundData {
+    fn from(ret: AggregatorV3Interface::getRoundDataReturn) -> Self {
+        Self {
+            round_id: ret.roundId,
+            answer: ret.answer,
+            started_at: ret.startedAt.saturating_to(),
+            updated_at: ret.updatedAt.saturating_to(),
+            answered_in_round: ret.answeredInRound,
+        }
+    }
+}
+
+impl From<AggregatorV3Interface::latestRoundDataReturn> for RoundData {
+    fn from(ret: AggregatorV3Interface::latestRoundDataReturn) -> Self {
+        Self {
+            round_id: ret.roundId,
+            answer: ret.answer,
+            started_at: ret.startedAt.saturating_to(),
+            updated_at: ret.updatedAt.saturating_to(),
+            answered_in_round: ret.answeredInRound,
+        }
+    }
+}
+
+/// Rescales a fixed-point `value` with `from` decimals to `to` decimals, truncating towards zero
+/// when decreasing the precision.
+///
+/// Returns [`PriceFeedError::Overflow`] if the rescaled value does not fit in an `int256`.
+pub fn normalize_decimals(value: I256, from: u8, to: u8) -> Result<I256, PriceFeedError> {
+    // `None` if the scale does not fit in an `int256`.
+    let scale = |decimals: u8| {
+        U256::from(10)
+            .checked_pow(U256::from(decimals))
+            .and_then(|scale| I256::try_from(scale).ok())
+    };
+    match from.cmp(&to) {
+        std::cmp::Ordering::Less => scale(to - from)
+            .and_then(|scale| value.checked_mul(scale))
+            .ok_or(PriceFeedError::Overflow { value, from, to }),
+        std::cmp::Ordering::Equal => Ok(value),
+        // Every `int256` is smaller than a scale that does not fit in one.
+        std::cmp::Ordering::Greater => {
+            Ok(scale(from - to).map_or(I256::ZERO, |scale| value / scale))
+        }
+    }
+}
+
+/// A handle to a Chainlink price feed at a specific address.
+#[derive(Clone, Debug)]
+pub struct PriceFeed<T, P, N = Ethereum> {
+    address: Address,
+    provider: P,
+    transport: PhantomData<T>,
+    network: PhantomData<N>,
+}
+
+impl<T, P, N> PriceFeed<T, P, N> {
+    /// Creates a new price feed reader for the aggregator (or proxy) at `address`.
+    #[inline]
+    pub const fn new(address: Address, provider: P) -> Self {
+        Self { address, provider, transport: PhantomData, network: PhantomData }
+    }
+
+    /// Returns a reference to the aggregator's address.
+    #[inline]
+    pub const fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// Returns a reference to the provider.
+    #[inline]
+    pub const fn provider(&self) -> &P {
+        &self.provider
+    }
+}
+
+impl<T: Transport + Clone, P: Provider<T, N>, N: Network> PriceFeed<T, P, N> {
+    /// Returns the number of decimals of the answers.
+    pub async fn decimals(&self) -> crate::Result<u8> {
+        let call = AggregatorV3Interface::decimalsCall {};
+        Ok(SolCallBuilder::new_sol(&self.provider, &self.address, &call).call().await?.decimals)
+    }
+
+    /// Returns the description of the feed, e.g. `ETH / USD`.
+    pub async fn description(&self) -> crate::Result<String> {
+        let call = AggregatorV3Interface::descriptionCall {};
+        Ok(SolCallBuilder::new_sol(&self.provider, &self.address, &call).call().await?.description)
+    }
+
+    /// Returns the latest round.
+    pub async fn latest_round(&self) -> crate::Result<RoundData> {
+        let call = AggregatorV3Interface::latestRoundDataCall {};
+        Ok(SolCallBuilder::new_sol(&self.provider, &self.address, &call).call().await?.into())
+    }
+
+    /// Returns the round with the given ID.
+    pub async fn round(&self, round_id: u128) -> crate::Result<RoundData> {
+        let call = AggregatorV3Interface::getRoundDataCall { _roundId: round_id };
+        Ok(SolCallBuilder::new_sol(&self.provider, &self.address, &call).call().await?.into())
+    }
+
+    /// Returns the latest answer, after checking that it is positive and no older than
+    /// `max_age`.
+    pub async fn latest_answer(&self, max_age: Duration) -> Result<I256, PriceFeedError> {
+        let round = self.latest_round().await?;
+        round.validate(max_age, SystemTime::now())?;
+        Ok(round.answer)
+    }
+
+    /// Returns the latest answer rescaled to `decimals`, after checking that it is positive and
+    /// no older than `max_age`.
+    pub async fn latest_answer_scaled(
+        &self,
+        max_age: Duration,
+        decimals: u8,
+    ) -> Result<I256, PriceFeedError> {
+        let (answer, feed_decimals) = futures::try_join!(self.latest_answer(max_age), async {
+            self.decimals().await.map_err(PriceFeedError::from)
+        })?;
+        normalize_decimals(answer, feed_decimals, decimals)
+    }
+
+    /// Returns up to `count` rounds preceding and including the latest one, newest first.
+    ///
+    /// Iteration stops at the first round of the current phase, since round IDs are not
+    /// contiguous across phases.
+    pub async fn previous_rounds(&self, count: usize) -> crate::Result<Vec<RoundData>> {
+        let latest = self.latest_round().await?;
+        let mut rounds = Vec::with_capacity(count);
+        if count == 0 {
+            return Ok(rounds);
+        }
+
+        let phase = (latest.phase_id() as u128) << 64;
+        let mut aggregator_round_id = latest.aggregator_round_id();
+        rounds.push(latest);
+        while rounds.len() < count && aggregator_round_id > 1 {
+            aggregator_round_id -= 1;
+            rounds.push(self.round(phase | aggregator_round_id as u128).await?);
+        }
+        Ok(rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(answer: i64, updated_at: u64) -> RoundData {
+        RoundData {
+            round_id: (2 << 64) | 15,
+            answer: I256::try_from(answer).unwrap(),
+            started_at: updated_at,
+            updated_at,
+            answered_in_round: (2 << 64) | 15,
+        }
+    }
+
+    #[test]
+    fn round_ids() {
+        let round = round(1, 1);
+        assert_eq!(round.phase_id(), 2);
+        assert_eq!(round.aggregator_round_id(), 15);
+    }
+
+    #[test]
+    fn validates_rounds() {
+        let now = UNIX_EPOCH + Duration::from_secs(10_000);
+        let max_age = Duration::from_secs(3600);
+
+        assert!(round(300_000_000_000, 9_000).validate(max_age, now).is_ok());
+        // Rounds updated after `now` are not considered stale.
+        assert!(round(300_000_000_000, 10_001).validate(max_age, now).is_ok());
+        assert!(matches!(
+            round(300_000_000_000, 1_000).validate(max_age, now),
+            Err(PriceFeedError::Stale { updated_at: 1_000, .. })
+        ));
+        assert!(matches!(
+            round(0, 9_000).validate(max_age, now),
+            Err(PriceFeedError::InvalidAnswer(_))
+        ));
+        assert!(matches!(
+            round(1, 0).validate(max_age, now),
+            Err(PriceFeedError::IncompleteRound(_))
+        ));
+    }
+
+    #[test]
+    fn normalizes_decimals() {
+        let answer = I256::try_from(312_345_678_900i64).unwrap();
+        assert_eq!(normalize_decimals(answer, 8, 8).unwrap(), answer);
+        assert_eq!(
+            normalize_decimals(answer, 8, 18).unwrap(),
+            I256::try_from(3_123_456_789_000_000_000_000i128).unwrap()
+        );
+        assert_eq!(normalize_decimals(answer, 8, 2).unwrap(), I256::try_from(312_345i64).unwrap());
+        assert_eq!(
+            normalize_decimals(-answer, 8, 2).unwrap(),
+            I256::try_from(-312_345i64).unwrap()
+        );
+    }
+
+    #[test]
+    fn normalizes_decimals_without_overflow() {
+        let answer = I256::try_from(312_345_678_900i64).unwrap();
+        assert!(matches!(
+            normalize_decimals(answer, 0, 70),
+            Err(PriceFeedError::Overflow { from: 0, to: 70, .. })
+        ));
+        assert!(matches!(normalize_decimals(answer, 0, 255), Err(PriceFeedError::Overflow { .. })));
+        assert_eq!(normalize_decimals(I256::MAX, 77, 0).unwrap(), I256::ZERO);
+        assert_eq!(normalize_decimals(I256::MAX, 255, 0).unwrap(), I256::ZERO);
+    }
+}
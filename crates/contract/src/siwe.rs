@@ -0,0 +1,548 @@
+//! Sign-In with Ethereum ([EIP-4361]) messages.
+//!
+//! [`SiweMessage`] builds, formats and parses the canonical message that wallets sign, and
+//! verifies signatures from both externally owned accounts and [EIP-1271] contract wallets,
+//! with [`alloy_provider::verify`].
+//!
+//! [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+//! [EIP-1271]: https://eips.ethereum.org/EIPS/eip-1271
+
+use alloy_network::Network;
+use alloy_primitives::{eip191_hash_message, Address, FixedBytes, Signature};
+use alloy_provider::{verify::verify_signature, Provider};
+use alloy_transport::{Transport, TransportError};
+use chrono::{DateTime, FixedOffset};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// The only supported message version.
+pub const SIWE_VERSION: &str = "1";
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+const URI_TAG: &str = "URI: ";
+const VERSION_TAG: &str = "Version: ";
+const CHAIN_ID_TAG: &str = "Chain ID: ";
+const NONCE_TAG: &str = "Nonce: ";
+const ISSUED_AT_TAG: &str = "Issued At: ";
+const EXPIRATION_TIME_TAG: &str = "Expiration Time: ";
+const NOT_BEFORE_TAG: &str = "Not Before: ";
+const REQUEST_ID_TAG: &str = "Request ID: ";
+const RESOURCES_TAG: &str = "Resources:";
+
+/// Error returned when parsing or verifying a [`SiweMessage`].
+#[derive(Debug, Error)]
+pub enum SiweError {
+    /// The message does not follow the EIP-4361 format.
+    #[error("invalid SIWE message: {0}")]
+    Format(&'static str),
+    /// The address is not a valid EIP-55 checksummed address.
+    #[error("invalid address: {0}")]
+    Address(String),
+    /// A timestamp is not a valid RFC 3339 date-time.
+    #[error("invalid timestamp {0:?}")]
+    Timestamp(String),
+    /// The message version is not supported.
+    #[error("unsupported version {0:?}")]
+    Version(String),
+    /// The nonce is not at least 8 alphanumeric characters.
+    #[error("invalid nonce {0:?}")]
+    Nonce(String),
+    /// The domain does not match the expected one.
+    #[error("domain mismatch: expected {expected}, got {got}")]
+    DomainMismatch {
+        /// The expected domain.
+        expected: String,
+        /// The domain of the message.
+        got: String,
+    },
+    /// The nonce does not match the expected one.
+    #[error("nonce mismatch: expected {expected}, got {got}")]
+    NonceMismatch {
+        /// The expected nonce.
+        expected: String,
+        /// The nonce of the message.
+        got: String,
+    },
+    /// The message has expired.
+    #[error("message has expired")]
+    Expired,
+    /// The message is not yet valid.
+    #[error("message is not yet valid")]
+    NotYetValid,
+    /// The signature was not produced by the message address.
+    #[error("invalid signature")]
+    InvalidSignature,
+    /// The requests verifying a contract wallet signature failed.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+}
+
+/// A Sign-In with Ethereum message, as defined by [EIP-4361].
+///
+/// The [`Display`](fmt::Display) implementation produces the canonical message to be signed, and
+/// the [`FromStr`] implementation parses it back. Timestamps are kept verbatim so that a parsed
+/// message formats to the exact string that was signed.
+///
+/// [EIP-4361]: https://eips.ethereum.org/EIPS/eip-4361
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SiweMessage {
+    /// The URI scheme of the origin of the request, e.g. `https`.
+    pub scheme: Option<String>,
+    /// The RFC 3986 authority requesting the signing, e.g. `example.com`.
+    pub domain: String,
+    /// The address performing the signing.
+    pub address: Address,
+    /// A human-readable assertion the user signs. Must not contain newlines.
+    pub statement: Option<String>,
+    /// The RFC 3986 URI referring to the subject of the signing.
+    pub uri: String,
+    /// The message version. Must be [`SIWE_VERSION`].
+    pub version: String,
+    /// The EIP-155 chain ID to which the session is bound.
+    pub chain_id: u64,
+    /// A randomized token used to prevent replay attacks, at least 8 alphanumeric characters.
+    pub nonce: String,
+    /// The RFC 3339 date-time at which the message was generated.
+    pub issued_at: String,
+    /// The RFC 3339 date-time after which the message is no longer valid.
+    pub expiration_time: Option<String>,
+    /// The RFC 3339 date-time before which the message is not yet valid.
+    pub not_before: Option<String>,
+    /// A system-specific identifier that may be used to uniquely refer to the sign-in request.
+    pub request_id: Option<String>,
+    /// RFC 3986 URIs the user wishes to have resolved as part of authentication.
+    pub resources: Vec<String>,
+}
+
+/// Options for [`SiweMessage::verify`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VerificationOpts {
+    /// The domain the message must be bound to.
+    pub domain: Option<String>,
+    /// The nonce the message must contain.
+    pub nonce: Option<String>,
+    /// The time at which to check the validity window. Defaults to the current system time.
+    pub timestamp: Option<DateTime<FixedOffset>>,
+}
+
+impl SiweMessage {
+    /// Creates a new message with the required fields.
+    pub fn new(
+        domain: impl Into<String>,
+        address: Address,
+        uri: impl Into<String>,
+        chain_id: u64,
+        nonce: impl Into<String>,
+        issued_at: impl Into<String>,
+    ) -> Self {
+        Self {
+            scheme: None,
+            domain: domain.into(),
+            address,
+            statement: None,
+            uri: uri.into(),
+            version: SIWE_VERSION.to_string(),
+            chain_id,
+            nonce: nonce.into(),
+            issued_at: issued_at.into(),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    /// Sets the URI scheme of the origin.
+    pub fn with_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.scheme = Some(scheme.into());
+        self
+    }
+
+    /// Sets the statement.
+    pub fn with_statement(mut self, statement: impl Into<String>) -> Self {
+        self.statement = Some(statement.into());
+        self
+    }
+
+    /// Sets the expiration time.
+    pub fn with_expiration_time(mut self, expiration_time: impl Into<String>) -> Self {
+        self.expiration_time = Some(expiration_time.into());
+        self
+    }
+
+    /// Sets the not-before time.
+    pub fn with_not_before(mut self, not_before: impl Into<String>) -> Self {
+        self.not_before = Some(not_before.into());
+        self
+    }
+
+    /// Sets the request ID.
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// Appends a resource.
+    pub fn with_resource(mut self, resource: impl Into<String>) -> Self {
+        self.resources.push(resource.into());
+        self
+    }
+
+    /// Checks that the fields satisfy the constraints of EIP-4361.
+    pub fn validate(&self) -> Result<(), SiweError> {
+        if self.version != SIWE_VERSION {
+            return Err(SiweError::Version(self.version.clone()));
+        }
+        if self.nonce.len() < 8 || !self.nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(SiweError::Nonce(self.nonce.clone()));
+        }
+        if self.domain.is_empty() || self.uri.is_empty() {
+            return Err(SiweError::Format("empty domain or URI"));
+        }
+        if self.statement.as_deref().is_some_and(|s| s.contains('\n')) {
+            return Err(SiweError::Format("statement contains a newline"));
+        }
+        parse_timestamp(&self.issued_at)?;
+        self.expiration_time.as_deref().map(parse_timestamp).transpose()?;
+        self.not_before.as_deref().map(parse_timestamp).transpose()?;
+        Ok(())
+    }
+
+    /// Returns whether the message is within its validity window at the given time.
+    pub fn valid_at(&self, time: &DateTime<FixedOffset>) -> Result<(), SiweError> {
+        if let Some(expiration_time) = &self.expiration_time {
+            if *time >= parse_timestamp(expiration_time)? {
+                return Err(SiweError::Expired);
+            }
+        }
+        if let Some(not_before) = &self.not_before {
+            if *time < parse_timestamp(not_before)? {
+                return Err(SiweError::NotYetValid);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the EIP-191 hash of the message, i.e. the hash signed by the wallet.
+    pub fn eip191_hash(&self) -> FixedBytes<32> {
+        eip191_hash_message(self.to_string())
+    }
+
+    /// Verifies that the signature was produced by the externally owned account of the message.
+    pub fn verify_eoa(&self, signature: &Signature) -> Result<(), SiweError> {
+        match signature.recover_address_from_msg(self.to_string()) {
+            Ok(address) if address == self.address => Ok(()),
+            _ => Err(SiweError::InvalidSignature),
+        }
+    }
+
+    /// Verifies the message fields against `opts` and the signature against the message address.
+    ///
+    /// The signature is first checked as an ECDSA signature of the message address. If that fails,
+    /// it is verified with [`verify_signature`] using the given provider, e.g. through the
+    /// EIP-1271 `isValidSignature` method of a contract wallet. Signatures of addresses without
+    /// code that are not ECDSA signatures of the address are invalid.
+    pub async fn verify<T, P, N>(
+        &self,
+        signature: &[u8],
+        provider: &P,
+        opts: &VerificationOpts,
+    ) -> Result<(), SiweError>
+    where
+        T: Transport + Clone,
+        P: Provider<T, N>,
+        N: Network,
+    {
+        self.validate()?;
+        if let Some(domain) = &opts.domain {
+            if *domain != self.domain {
+                return Err(SiweError::DomainMismatch {
+                    expected: domain.clone(),
+                    got: self.domain.clone(),
+                });
+            }
+        }
+        if let Some(nonce) = &opts.nonce {
+            if *nonce != self.nonce {
+                return Err(SiweError::NonceMismatch {
+                    expected: nonce.clone(),
+                    got: self.nonce.clone(),
+                });
+            }
+        }
+        let now = opts.timestamp.unwrap_or_else(|| chrono::Utc::now().fixed_offset());
+        self.valid_at(&now)?;
+
+        if let Ok(signature) = Signature::try_from(signature) {
+            if self.verify_eoa(&signature).is_ok() {
+                return Ok(());
+            }
+        }
+
+        if verify_signature(provider, self.address, self.eip191_hash(), signature).await? {
+            Ok(())
+        } else {
+            Err(SiweError::InvalidSignature)
+        }
+    }
+}
+
+fn parse_timestamp(s: &str) -> Result<DateTime<FixedOffset>, SiweError> {
+    DateTime::parse_from_rfc3339(s).map_err(|_| SiweError::Timestamp(s.to_string()))
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{scheme}://")?;
+        }
+        writeln!(f, "{}{PREAMBLE}", self.domain)?;
+        writeln!(f, "{}", self.address.to_checksum(None))?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{statement}")?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{URI_TAG}{}", self.uri)?;
+        writeln!(f, "{VERSION_TAG}{}", self.version)?;
+        writeln!(f, "{CHAIN_ID_TAG}{}", self.chain_id)?;
+        writeln!(f, "{NONCE_TAG}{}", self.nonce)?;
+        write!(f, "{ISSUED_AT_TAG}{}", self.issued_at)?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\n{EXPIRATION_TIME_TAG}{expiration_time}")?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\n{NOT_BEFORE_TAG}{not_before}")?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\n{REQUEST_ID_TAG}{request_id}")?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\n{RESOURCES_TAG}")?;
+            for resource in &self.resources {
+                write!(f, "\n- {resource}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = SiweError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.split('\n').peekable();
+        let mut next = |what| lines.next().ok_or(SiweError::Format(what));
+
+        let origin = next("missing preamble")?
+            .strip_suffix(PREAMBLE)
+            .ok_or(SiweError::Format("missing preamble"))?;
+        let (scheme, domain) = match origin.split_once("://") {
+            Some((scheme, domain)) => (Some(scheme.to_string()), domain),
+            None => (None, origin),
+        };
+
+        let address = next("missing address")?;
+        let address = Address::parse_checksummed(address, None)
+            .map_err(|_| SiweError::Address(address.to_string()))?;
+
+        if !next("missing blank line")?.is_empty() {
+            return Err(SiweError::Format("expected blank line after address"));
+        }
+        let statement = match next("missing statement")? {
+            "" => None,
+            statement => {
+                if !next("missing blank line")?.is_empty() {
+                    return Err(SiweError::Format("expected blank line after statement"));
+                }
+                Some(statement.to_string())
+            }
+        };
+
+        let mut tagged = |tag: &'static str| -> Result<String, SiweError> {
+            next(tag)?.strip_prefix(tag).map(str::to_string).ok_or(SiweError::Format(tag))
+        };
+        let uri = tagged(URI_TAG)?;
+        let version = tagged(VERSION_TAG)?;
+        let chain_id =
+            tagged(CHAIN_ID_TAG)?.parse().map_err(|_| SiweError::Format(CHAIN_ID_TAG))?;
+        let nonce = tagged(NONCE_TAG)?;
+        let issued_at = tagged(ISSUED_AT_TAG)?;
+
+        let mut optional = |tag: &str| {
+            let value = lines.peek()?.strip_prefix(tag)?.to_string();
+            lines.next();
+            Some(value)
+        };
+        let expiration_time = optional(EXPIRATION_TIME_TAG);
+        let not_before = optional(NOT_BEFORE_TAG);
+        let request_id = optional(REQUEST_ID_TAG);
+
+        let mut resources = Vec::new();
+        if let Some(line) = lines.next() {
+            if line != RESOURCES_TAG {
+                return Err(SiweError::Format("unexpected trailing line"));
+            }
+            for line in lines {
+                let resource =
+                    line.strip_prefix("- ").ok_or(SiweError::Format("invalid resource"))?;
+                resources.push(resource.to_string());
+            }
+        }
+
+        let message = Self {
+            scheme,
+            domain: domain.to_string(),
+            address,
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        };
+        message.validate()?;
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use alloy_provider::ProviderBuilder;
+    use alloy_signer::SignerSync;
+    use alloy_signer_wallet::LocalWallet;
+
+    const MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    fn message() -> SiweMessage {
+        SiweMessage::new(
+            "service.org",
+            address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+            "https://service.org/login",
+            1,
+            "32891756",
+            "2021-09-30T16:25:24Z",
+        )
+        .with_statement("I accept the ServiceOrg Terms of Service: https://service.org/tos")
+        .with_resource("ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/")
+        .with_resource("https://example.com/my-web2-claim.json")
+    }
+
+    #[test]
+    fn format_and_parse() {
+        assert_eq!(message().to_string(), MESSAGE);
+        assert_eq!(MESSAGE.parse::<SiweMessage>().unwrap(), message());
+
+        let mut message = message()
+            .with_scheme("https")
+            .with_expiration_time("2021-10-01T16:25:24+02:00")
+            .with_not_before("2021-09-30T16:25:24Z")
+            .with_request_id("some-id");
+        message.statement = None;
+        message.resources.clear();
+        let formatted = message.to_string();
+        assert!(formatted.starts_with("https://service.org wants you to sign in"));
+        assert!(formatted.contains("Cc2\n\n\nURI: "));
+        assert_eq!(formatted.parse::<SiweMessage>().unwrap(), message);
+    }
+
+    #[test]
+    fn rejects_invalid_messages() {
+        let lowercase = MESSAGE.replace(
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2",
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+        );
+        assert!(matches!(lowercase.parse::<SiweMessage>(), Err(SiweError::Address(_))));
+
+        let short_nonce = MESSAGE.replace("Nonce: 32891756", "Nonce: 1234");
+        assert!(matches!(short_nonce.parse::<SiweMessage>(), Err(SiweError::Nonce(_))));
+
+        let version = MESSAGE.replace("Version: 1", "Version: 2");
+        assert!(matches!(version.parse::<SiweMessage>(), Err(SiweError::Version(_))));
+
+        let timestamp = MESSAGE.replace("2021-09-30T16:25:24Z", "yesterday");
+        assert!(matches!(timestamp.parse::<SiweMessage>(), Err(SiweError::Timestamp(_))));
+
+        assert!(matches!(
+            MESSAGE.replace("Chain ID", "Chain").parse::<SiweMessage>(),
+            Err(SiweError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn validity_window() {
+        let message = message()
+            .with_not_before("2021-09-30T16:25:24Z")
+            .with_expiration_time("2021-10-01T16:25:24Z");
+        let at = |s| DateTime::parse_from_rfc3339(s).unwrap();
+        assert!(matches!(
+            message.valid_at(&at("2021-09-30T16:00:00Z")),
+            Err(SiweError::NotYetValid)
+        ));
+        assert!(message.valid_at(&at("2021-09-30T18:25:24+02:00")).is_ok());
+        assert!(matches!(message.valid_at(&at("2021-10-01T16:25:24Z")), Err(SiweError::Expired)));
+    }
+
+    #[tokio::test]
+    async fn verify_eoa_signature() {
+        let wallet = LocalWallet::random();
+        let mut message = message();
+        message.address = wallet.address();
+        let signature = wallet.sign_message_sync(message.to_string().as_bytes()).unwrap();
+        message.verify_eoa(&signature).unwrap();
+
+        // The EOA path never reaches the provider.
+        let provider = ProviderBuilder::new().on_http("http://localhost:1".parse().unwrap());
+        let opts = VerificationOpts {
+            domain: Some("service.org".into()),
+            nonce: Some("32891756".into()),
+            timestamp: Some(DateTime::parse_from_rfc3339("2021-09-30T16:25:24Z").unwrap()),
+        };
+        message.verify(&signature.as_bytes(), &provider, &opts).await.unwrap();
+
+        let opts = VerificationOpts { nonce: Some("00000000".into()), ..opts };
+        assert!(matches!(
+            message.verify(&signature.as_bytes(), &provider, &opts).await,
+            Err(SiweError::NonceMismatch { .. })
+        ));
+
+        let other =
+            LocalWallet::random().sign_message_sync(message.to_string().as_bytes()).unwrap();
+        assert!(matches!(message.verify_eoa(&other), Err(SiweError::InvalidSignature)));
+    }
+
+    #[tokio::test]
+    async fn verify_signature_of_address_without_code() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let mut message = message();
+        message.address = LocalWallet::random().address();
+        let signature =
+            LocalWallet::random().sign_message_sync(message.to_string().as_bytes()).unwrap();
+        let opts = VerificationOpts {
+            timestamp: Some(DateTime::parse_from_rfc3339("2021-09-30T16:25:24Z").unwrap()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            message.verify(&signature.as_bytes(), &provider, &opts).await,
+            Err(SiweError::InvalidSignature)
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+//! Bindings for the common token standards.
+//!
+//! The interfaces are generated with [`sol!`](alloy_sol_types::sol) and the `rpc` attribute, so
+//! each of them comes with an instance type building typed calls and event filters:
+//!
+//! ```no_run
+//! use alloy_contract::tokens::IERC20;
+//! use alloy_primitives::address;
+//! use alloy_provider::ProviderBuilder;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse()?);
+//! let token = IERC20::new(address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"), provider);
+//! let owner = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+//! let balance = token.balanceOf(owner).call().await?.balance;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! - [ERC-20](https://eips.ethereum.org/EIPS/eip-20): [`IERC20`] and [`IERC20Metadata`].
+//! - [ERC-721](https://eips.ethereum.org/EIPS/eip-721): [`IERC721`], [`IERC721Metadata`] and
+//!   [`IERC721Enumerable`].
+//! - [ERC-1155](https://eips.ethereum.org/EIPS/eip-1155): [`IERC1155`] and
+//!   [`IERC1155MetadataURI`].
+//! - [ERC-165](https://eips.ethereum.org/EIPS/eip-165): [`IERC165`], implemented by ERC-721 and
+//!   ERC-1155 tokens.
+
+pub use interfaces::*;
+
+#[allow(missing_docs)]
+mod interfaces {
+    // Interfaces of different standards share item names, so each is declared separately.
+    alloy_sol_types::sol! {
+        #![sol(alloy_contract = crate)]
+
+        /// The ERC-165 interface detection standard.
+        #[sol(rpc)]
+        interface IERC165 {
+            function supportsInterface(bytes4 interfaceId) external view returns (bool supported);
+        }
+    }
+
+    alloy_sol_types::sol! {
+        #![sol(alloy_contract = crate)]
+
+        /// The ERC-20 fungible token standard.
+        #[sol(rpc)]
+        interface IERC20 {
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            event Approval(address indexed owner, address indexed spender, uint256 value);
+
+            function totalSupply() external view returns (uint256 supply);
+            function balanceOf(address owner) external view returns (uint256 balance);
+            function allowance(address owner, address spender) external view returns (uint256 remaining);
+            function transfer(address to, uint256 value) external returns (bool success);
+            function approve(address spender, uint256 value) external returns (bool success);
+            function transferFrom(address from, address to, uint256 value) external returns (bool success);
+        }
+    }
+
+    alloy_sol_types::sol! {
+        #![sol(alloy_contract = crate)]
+
+        /// The optional metadata extension of ERC-20.
+        #[sol(rpc)]
+        interface IERC20Metadata {
+            function name() external view returns (string name);
+            function symbol() external view returns (string symbol);
+            function decimals() external view returns (uint8 decimals);
+        }
+    }
+
+    alloy_sol_types::sol! {
+        #![sol(alloy_contract = crate)]
+
+        /// The ERC-721 non-fungible token standard.
+        #[sol(rpc)]
+        interface IERC721 {
+            event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+            event Approval(address indexed owner, address indexed approved, uint256 indexed tokenId);
+            event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+
+            function balanceOf(address owner) external view returns (uint256 balance);
+            function ownerOf(uint256 tokenId) external view returns (address owner);
+            function safeTransferFrom(address from, address to, uint256 tokenId, bytes data) external payable;
+            function safeTransferFrom(address from, address to, uint256 tokenId) external payable;
+            function transferFrom(address from, address to, uint256 tokenId) external payable;
+            function approve(address approved, uint256 tokenId) external payable;
+            function setApprovalForAll(address operator, bool approved) external;
+            function getApproved(uint256 tokenId) external view returns (address approved);
+            function isApprovedForAll(address owner, address operator) external view returns (bool approved);
+        }
+    }
+
+    alloy_sol_types::sol! {
+        #![sol(alloy_contract = crate)]
+
+        /// The optional metadata extension of ERC-721.
+        #[sol(rpc)]
+        interface IERC721Metadata {
+            function name() external view returns (string name);
+            function symbol() external view returns (string symbol);
+            function tokenURI(uint256 tokenId) external view returns (string uri);
+        }
+    }
+
+    alloy_sol_types::sol! {
+        #![sol(alloy_contract = crate)]
+
+        /// The optional enumeration extension of ERC-721.
+        #[sol(rpc)]
+        interface IERC721Enumerable {
+            function totalSupply() external view returns (uint256 supply);
+            function tokenOfOwnerByIndex(address owner, uint256 index) external view returns (uint256 tokenId);
+            function tokenByIndex(uint256 index) external view returns (uint256 tokenId);
+        }
+    }
+
+    alloy_sol_types::sol! {
+        #![sol(alloy_contract = crate)]
+
+        /// The ERC-1155 multi token standard.
+        #[sol(rpc)]
+        interface IERC1155 {
+            event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+            event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+            event ApprovalForAll(address indexed owner, address indexed operator, bool approved);
+            event URI(string value, uint256 indexed id);
+
+            function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes data) external;
+            function safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] values, bytes data) external;
+            function balanceOf(address owner, uint256 id) external view returns (uint256 balance);
+            function balanceOfBatch(address[] owners, uint256[] ids) external view returns (uint256[] balances);
+            function setApprovalForAll(address operator, bool approved) external;
+            function isApprovedForAll(address owner, address operator) external view returns (bool approved);
+        }
+    }
+
+    alloy_sol_types::sol! {
+        #![sol(alloy_contract = crate)]
+
+        /// The optional metadata URI extension of ERC-1155.
+        #[sol(rpc)]
+        interface IERC1155MetadataURI {
+            function uri(uint256 id) external view returns (string uri);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+    use alloy_sol_types::{SolCall, SolEvent};
+
+    #[test]
+    fn selectors() {
+        assert_eq!(IERC20::transferCall::SELECTOR, hex!("a9059cbb"));
+        assert_eq!(IERC20::balanceOfCall::SELECTOR, hex!("70a08231"));
+        assert_eq!(IERC20Metadata::decimalsCall::SELECTOR, hex!("313ce567"));
+        assert_eq!(IERC721::safeTransferFrom_0Call::SELECTOR, hex!("b88d4fde"));
+        assert_eq!(IERC721::safeTransferFrom_1Call::SELECTOR, hex!("42842e0e"));
+        assert_eq!(IERC1155::balanceOfBatchCall::SELECTOR, hex!("4e1273f4"));
+        assert_eq!(IERC165::supportsInterfaceCall::SELECTOR, hex!("01ffc9a7"));
+        // ERC-20 and ERC-721 transfers share their signature, but not their indexed topics.
+        assert_eq!(IERC20::Transfer::SIGNATURE_HASH, IERC721::Transfer::SIGNATURE_HASH);
+    }
+}
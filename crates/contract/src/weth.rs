@@ -0,0 +1,156 @@
+//! Wrapped native token (WETH9) helpers.
+//!
+//! [`WrappedNative`] builds typed calls to wrap and unwrap the native currency of a chain through
+//! its canonical wrapped native token contract, as returned by [`wrapped_native_address`].
+
+use crate::SolCallBuilder;
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{address, Address, ChainId, U256};
+use alloy_provider::Provider;
+use alloy_sol_types::sol;
+use alloy_transport::Transport;
+use std::marker::PhantomData;
+
+pub use interfaces::IWETH9;
+
+#[allow(missing_docs)]
+mod interfaces {
+    use super::sol;
+
+    sol! {
+        /// The WETH9 wrapped native token interface.
+        interface IWETH9 {
+            function deposit() external payable;
+
+            function withdraw(uint256 wad) external;
+
+            function balanceOf(address owner) external view returns (uint256 balance);
+        }
+    }
+}
+
+/// Returns the canonical wrapped native token of the given chain, if known.
+///
+/// | Chain | ID | Token |
+/// |---|---|---|
+/// | Ethereum | 1 | WETH |
+/// | Optimism | 10 | WETH |
+/// | BNB Smart Chain | 56 | WBNB |
+/// | Gnosis | 100 | WXDAI |
+/// | Polygon | 137 | WMATIC |
+/// | Base | 8453 | WETH |
+/// | Arbitrum One | 42161 | WETH |
+/// | Avalanche C-Chain | 43114 | WAVAX |
+/// | Sepolia | 11155111 | WETH |
+pub const fn wrapped_native_address(chain_id: ChainId) -> Option<Address> {
+    Some(match chain_id {
+        1 => address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+        10 | 8453 => address!("4200000000000000000000000000000000000006"),
+        56 => address!("bb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c"),
+        100 => address!("e91D153E0b41518A2Ce8Dd3D7944Fa863463a97d"),
+        137 => address!("0d500B1d8E8eF31E21C99d1Db9A6444d3ADf1270"),
+        42161 => address!("82aF49447D8a07e3bd95BD0d56f35241523fBab1"),
+        43114 => address!("B31f66AA3C1e785363F0875A1B74E27b85FD66c7"),
+        11155111 => address!("fFf9976782d46CC05630D1f6eBAb18b2324d6B14"),
+        _ => return None,
+    })
+}
+
+/// A handle to a wrapped native token contract at a specific address.
+#[derive(Clone, Debug)]
+pub struct WrappedNative<T, P, N = Ethereum> {
+    address: Address,
+    provider: P,
+    transport: PhantomData<T>,
+    network: PhantomData<N>,
+}
+
+impl<T, P, N> WrappedNative<T, P, N> {
+    /// Creates a new handle to the wrapped native token at `address`.
+    #[inline]
+    pub const fn new(address: Address, provider: P) -> Self {
+        Self { address, provider, transport: PhantomData, network: PhantomData }
+    }
+
+    /// Creates a new handle to the canonical wrapped native token of the given chain, if known.
+    ///
+    /// See [`wrapped_native_address`] for the supported chains.
+    #[inline]
+    pub fn for_chain(chain_id: ChainId, provider: P) -> Option<Self> {
+        wrapped_native_address(chain_id).map(|address| Self::new(address, provider))
+    }
+
+    /// Returns a reference to the token's address.
+    #[inline]
+    pub const fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// Returns a reference to the provider.
+    #[inline]
+    pub const fn provider(&self) -> &P {
+        &self.provider
+    }
+}
+
+impl<T: Transport + Clone, P: Provider<T, N>, N: Network> WrappedNative<T, P, N> {
+    /// Creates a new handle to the canonical wrapped native token of the provider's chain.
+    ///
+    /// Returns `Ok(None)` if the chain has no known wrapped native token.
+    pub async fn for_provider_chain(provider: P) -> crate::Result<Option<Self>> {
+        let chain_id = provider.get_chain_id().await?;
+        Ok(Self::for_chain(chain_id, provider))
+    }
+
+    /// Returns a call that wraps `amount` of the native currency.
+    pub fn deposit(&self, amount: U256) -> SolCallBuilder<T, &P, IWETH9::depositCall, N> {
+        SolCallBuilder::new_sol(&self.provider, &self.address, &IWETH9::depositCall {})
+            .value(amount)
+    }
+
+    /// Returns a call that unwraps `amount` of the token back into the native currency.
+    pub fn withdraw(&self, amount: U256) -> SolCallBuilder<T, &P, IWETH9::withdrawCall, N> {
+        SolCallBuilder::new_sol(
+            &self.provider,
+            &self.address,
+            &IWETH9::withdrawCall { wad: amount },
+        )
+    }
+
+    /// Returns a call that reads the token balance of `owner`.
+    pub fn balance_of(&self, owner: Address) -> SolCallBuilder<T, &P, IWETH9::balanceOfCall, N> {
+        SolCallBuilder::new_sol(&self.provider, &self.address, &IWETH9::balanceOfCall { owner })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+    use alloy_provider::ProviderBuilder;
+
+    #[test]
+    fn known_chains() {
+        assert_eq!(
+            wrapped_native_address(1),
+            Some(address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"))
+        );
+        assert_eq!(wrapped_native_address(10), wrapped_native_address(8453));
+        assert_eq!(wrapped_native_address(31337), None);
+    }
+
+    #[test]
+    fn builds_calls() {
+        let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse().unwrap());
+        let weth = WrappedNative::<_, _>::for_chain(1, &provider).unwrap();
+
+        let deposit = weth.deposit(U256::from(1000));
+        assert_eq!(deposit.calldata()[..], hex!("d0e30db0"));
+
+        let withdraw = weth.withdraw(U256::from(1000));
+        assert_eq!(withdraw.calldata()[..4], hex!("2e1a7d4d"));
+        assert_eq!(U256::from_be_slice(&withdraw.calldata()[4..]), U256::from(1000));
+
+        assert!(WrappedNative::<(), _>::for_chain(31337, &provider).is_none());
+    }
+}
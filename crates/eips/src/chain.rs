@@ -0,0 +1,401 @@
+//! Metadata of known chains.
+//!
+//! [`NamedChain`] maps the IDs of well-known chains to their names and properties, such as their
+//! average block time and the transaction types they accept. [`Chain`] is either a named chain or
+//! any other chain ID.
+//!
+//! ```
+//! use alloy_eips::chain::{Chain, NamedChain};
+//!
+//! let chain: Chain = "base".parse().unwrap();
+//! assert_eq!(chain.id(), 8453);
+//! assert_eq!(chain.named(), Some(NamedChain::Base));
+//! assert_eq!(Chain::from_id(8453), chain);
+//! assert!(!NamedChain::Base.supports_eip4844());
+//! ```
+
+use alloy_primitives::ChainId;
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
+
+#[cfg(feature = "serde")]
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A well-known chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u64)]
+pub enum NamedChain {
+    /// Ethereum mainnet.
+    Mainnet = 1,
+    /// OP Mainnet.
+    Optimism = 10,
+    /// BNB Smart Chain.
+    BinanceSmartChain = 56,
+    /// Gnosis Chain.
+    Gnosis = 100,
+    /// Polygon PoS.
+    Polygon = 137,
+    /// Base.
+    Base = 8453,
+    /// The Holesky testnet.
+    Holesky = 17_000,
+    /// The default chain of Anvil.
+    Anvil = 31_337,
+    /// Arbitrum One.
+    Arbitrum = 42_161,
+    /// Avalanche C-Chain.
+    Avalanche = 43_114,
+    /// The Base Sepolia testnet.
+    BaseSepolia = 84_532,
+    /// The Arbitrum Sepolia testnet.
+    ArbitrumSepolia = 421_614,
+    /// The Sepolia testnet.
+    Sepolia = 11_155_111,
+    /// The OP Sepolia testnet.
+    OptimismSepolia = 11_155_420,
+}
+
+impl NamedChain {
+    /// All named chains, by chain ID.
+    pub const ALL: [Self; 14] = [
+        Self::Mainnet,
+        Self::Optimism,
+        Self::BinanceSmartChain,
+        Self::Gnosis,
+        Self::Polygon,
+        Self::Base,
+        Self::Holesky,
+        Self::Anvil,
+        Self::Arbitrum,
+        Self::Avalanche,
+        Self::BaseSepolia,
+        Self::ArbitrumSepolia,
+        Self::Sepolia,
+        Self::OptimismSepolia,
+    ];
+
+    /// Returns the named chain with the given ID, if any.
+    pub const fn from_id(id: ChainId) -> Option<Self> {
+        Some(match id {
+            1 => Self::Mainnet,
+            10 => Self::Optimism,
+            56 => Self::BinanceSmartChain,
+            100 => Self::Gnosis,
+            137 => Self::Polygon,
+            8453 => Self::Base,
+            17_000 => Self::Holesky,
+            31_337 => Self::Anvil,
+            42_161 => Self::Arbitrum,
+            43_114 => Self::Avalanche,
+            84_532 => Self::BaseSepolia,
+            421_614 => Self::ArbitrumSepolia,
+            11_155_111 => Self::Sepolia,
+            11_155_420 => Self::OptimismSepolia,
+            _ => return None,
+        })
+    }
+
+    /// Returns the ID of the chain.
+    pub const fn id(self) -> ChainId {
+        self as ChainId
+    }
+
+    /// Returns the name of the chain, as parsed by [`FromStr`].
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Optimism => "optimism",
+            Self::BinanceSmartChain => "bsc",
+            Self::Gnosis => "gnosis",
+            Self::Polygon => "polygon",
+            Self::Base => "base",
+            Self::Holesky => "holesky",
+            Self::Anvil => "anvil",
+            Self::Arbitrum => "arbitrum",
+            Self::Avalanche => "avalanche",
+            Self::BaseSepolia => "base-sepolia",
+            Self::ArbitrumSepolia => "arbitrum-sepolia",
+            Self::Sepolia => "sepolia",
+            Self::OptimismSepolia => "optimism-sepolia",
+        }
+    }
+
+    /// Returns the number of decimals of the native currency of the chain.
+    pub const fn native_currency_decimals(self) -> u8 {
+        18
+    }
+
+    /// Returns the average time between two blocks of the chain, or `None` if blocks are not
+    /// produced at a regular interval.
+    ///
+    /// Anvil mines a block for every transaction by default.
+    pub const fn average_blocktime(self) -> Option<Duration> {
+        Some(match self {
+            Self::Mainnet | Self::Holesky | Self::Sepolia => Duration::from_secs(12),
+            Self::Optimism
+            | Self::OptimismSepolia
+            | Self::Base
+            | Self::BaseSepolia
+            | Self::Polygon
+            | Self::Avalanche => Duration::from_secs(2),
+            Self::BinanceSmartChain => Duration::from_millis(750),
+            Self::Gnosis => Duration::from_secs(5),
+            Self::Arbitrum | Self::ArbitrumSepolia => Duration::from_millis(250),
+            Self::Anvil => return None,
+        })
+    }
+
+    /// Returns `true` if the chain has an [EIP-1559] fee market.
+    ///
+    /// BNB Smart Chain accepts EIP-1559 transactions, but its base fee is always zero.
+    ///
+    /// [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+    pub const fn supports_eip1559(self) -> bool {
+        !matches!(self, Self::BinanceSmartChain)
+    }
+
+    /// Returns `true` if the chain accepts [EIP-4844] blob transactions.
+    ///
+    /// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+    pub const fn supports_eip4844(self) -> bool {
+        matches!(
+            self,
+            Self::Mainnet
+                | Self::Holesky
+                | Self::Sepolia
+                | Self::Gnosis
+                | Self::BinanceSmartChain
+                | Self::Anvil
+        )
+    }
+
+    /// Returns `true` if the chain is a testnet or a development chain.
+    pub const fn is_testnet(self) -> bool {
+        matches!(
+            self,
+            Self::Holesky
+                | Self::Sepolia
+                | Self::Anvil
+                | Self::BaseSepolia
+                | Self::ArbitrumSepolia
+                | Self::OptimismSepolia
+        )
+    }
+}
+
+impl From<NamedChain> for ChainId {
+    fn from(chain: NamedChain) -> Self {
+        chain.id()
+    }
+}
+
+impl TryFrom<ChainId> for NamedChain {
+    type Error = ChainId;
+
+    fn try_from(id: ChainId) -> Result<Self, Self::Error> {
+        Self::from_id(id).ok_or(id)
+    }
+}
+
+impl Display for NamedChain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NamedChain {
+    type Err = ParseChainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = match s {
+            "ethereum" => "mainnet",
+            "binance-smart-chain" | "bnb" => "bsc",
+            "optimism" | "op" => "optimism",
+            "arbitrum-one" => "arbitrum",
+            name => name,
+        };
+        Self::ALL.into_iter().find(|chain| chain.as_str() == name).ok_or(ParseChainError)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for NamedChain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for NamedChain {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Chain::deserialize(deserializer)? {
+            Chain::Named(chain) => Ok(chain),
+            Chain::Id(id) => Err(serde::de::Error::custom(format!("unknown chain ID {id}"))),
+        }
+    }
+}
+
+/// A chain, either a [`NamedChain`] or any other chain ID.
+///
+/// Chains are parsed from, and serialized as, the name of named chains and the decimal ID of
+/// other chains. Named chains are also parsed from their ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Chain {
+    /// A well-known chain.
+    Named(NamedChain),
+    /// Any other chain.
+    Id(ChainId),
+}
+
+impl Chain {
+    /// Returns the chain with the given ID, which is named if the chain is known.
+    pub const fn from_id(id: ChainId) -> Self {
+        match NamedChain::from_id(id) {
+            Some(chain) => Self::Named(chain),
+            None => Self::Id(id),
+        }
+    }
+
+    /// Returns the ID of the chain.
+    pub const fn id(&self) -> ChainId {
+        match *self {
+            Self::Named(chain) => chain.id(),
+            Self::Id(id) => id,
+        }
+    }
+
+    /// Returns the named chain, if the chain is known.
+    pub const fn named(&self) -> Option<NamedChain> {
+        match *self {
+            Self::Named(chain) => Some(chain),
+            Self::Id(_) => None,
+        }
+    }
+}
+
+impl From<NamedChain> for Chain {
+    fn from(chain: NamedChain) -> Self {
+        Self::Named(chain)
+    }
+}
+
+impl From<ChainId> for Chain {
+    fn from(id: ChainId) -> Self {
+        Self::from_id(id)
+    }
+}
+
+impl From<Chain> for ChainId {
+    fn from(chain: Chain) -> Self {
+        chain.id()
+    }
+}
+
+impl Display for Chain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Named(chain) => chain.fmt(f),
+            Self::Id(id) => id.fmt(f),
+        }
+    }
+}
+
+impl FromStr for Chain {
+    type Err = ParseChainError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<ChainId>() {
+            Ok(id) => Ok(Self::from_id(id)),
+            Err(_) => s.parse().map(Self::Named),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Chain {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Named(chain) => chain.serialize(serializer),
+            Self::Id(id) => serializer.serialize_u64(*id),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Chain {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ChainVisitor;
+
+        impl<'de> Visitor<'de> for ChainVisitor {
+            type Value = Chain;
+
+            fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a chain name or ID")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, id: u64) -> Result<Self::Value, E> {
+                Ok(Chain::from_id(id))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Self::Value, E> {
+                s.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(ChainVisitor)
+    }
+}
+
+/// Error thrown when parsing an unknown chain name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseChainError;
+
+impl Display for ParseChainError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown chain name")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseChainError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_chains() {
+        for chain in NamedChain::ALL {
+            assert_eq!(NamedChain::from_id(chain.id()), Some(chain));
+            assert_eq!(chain.as_str().parse(), Ok(chain));
+            assert_eq!(Chain::from_id(chain.id()), Chain::Named(chain));
+        }
+        assert!(NamedChain::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!("ethereum".parse(), Ok(NamedChain::Mainnet));
+        assert_eq!("goerli".parse::<NamedChain>(), Err(ParseChainError));
+    }
+
+    #[test]
+    fn parse_chains() {
+        assert_eq!("1".parse(), Ok(Chain::Named(NamedChain::Mainnet)));
+        assert_eq!("7777777".parse(), Ok(Chain::Id(7_777_777)));
+        assert_eq!("sepolia".parse(), Ok(Chain::Named(NamedChain::Sepolia)));
+        assert_eq!(Chain::Id(7_777_777).to_string(), "7777777");
+        assert_eq!(Chain::from(NamedChain::Anvil).to_string(), "anvil");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_chains() {
+        let chains = [Chain::Named(NamedChain::Base), Chain::Id(7_777_777)];
+        let json = serde_json::to_string(&chains).unwrap();
+        assert_eq!(json, r#"["base",7777777]"#);
+        assert_eq!(serde_json::from_str::<[Chain; 2]>(&json).unwrap(), chains);
+        assert_eq!(serde_json::from_str::<Chain>("8453").unwrap(), Chain::Named(NamedChain::Base));
+        assert!(serde_json::from_str::<NamedChain>("7777777").is_err());
+        assert_eq!(serde_json::from_str::<NamedChain>("10").unwrap(), NamedChain::Optimism);
+    }
+}
@@ -5,11 +5,12 @@
 #[cfg(not(feature = "std"))]
 use crate::alloc::{vec, vec::Vec};
 
-use alloy_primitives::{keccak256, Sealed, B256};
+use alloy_primitives::{keccak256, Bytes, Sealed, B256};
 use alloy_rlp::{Buf, BufMut, Header, EMPTY_STRING_CODE};
 use core::{
     fmt,
     fmt::{Display, Formatter},
+    ops::Deref,
 };
 
 // https://eips.ethereum.org/EIPS/eip-2718#transactiontype-only-goes-up-to-0x7f
@@ -77,6 +78,26 @@ pub trait Decodable2718: Sized {
             .unwrap_or_else(|| Self::fallback_decode(buf))
     }
 
+    /// Decode a complete EIP-2718 encoded item, such as a raw transaction returned by
+    /// `eth_getRawTransactionByHash`.
+    ///
+    /// Unlike [`Self::decode_2718`], this fails with [`alloy_rlp::Error::UnexpectedLength`] if
+    /// `bytes` contains anything after the encoded item.
+    fn decode_2718_exact(bytes: &[u8]) -> alloy_rlp::Result<Self> {
+        let mut buf = bytes;
+        let item = match Self::extract_type_byte(&mut buf) {
+            Some(ty) => {
+                buf = &buf[1..];
+                Self::typed_decode(ty, &mut buf)?
+            }
+            None => Self::fallback_decode(&mut buf)?,
+        };
+        if !buf.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength);
+        }
+        Ok(item)
+    }
+
     /// Decode an EIP-2718 transaction in the network format.
     ///
     /// The network format is the RLP encoded string consisting of the
@@ -188,3 +209,112 @@ pub trait Encodable2718: Sized + Send + Sync + 'static {
 /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
 pub trait Eip2718Envelope: Decodable2718 + Encodable2718 {}
 impl<T> Eip2718Envelope for T where T: Decodable2718 + Encodable2718 {}
+
+/// An [EIP-2718] item kept along with its encoding.
+///
+/// Serializes as the hex string of the encoding, as sent with `eth_sendRawTransaction`, while the
+/// decoded item stays available in memory. The item is encoded, or decoded, only once when the
+/// wrapper is created.
+///
+/// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RawAndDecoded<T> {
+    raw: Bytes,
+    decoded: T,
+}
+
+impl<T: Encodable2718> RawAndDecoded<T> {
+    /// Encodes the given item.
+    pub fn new(decoded: T) -> Self {
+        Self { raw: decoded.encoded_2718().into(), decoded }
+    }
+}
+
+impl<T: Decodable2718> RawAndDecoded<T> {
+    /// Decodes a complete EIP-2718 encoded item.
+    pub fn decode(raw: impl Into<Bytes>) -> alloy_rlp::Result<Self> {
+        let raw = raw.into();
+        let decoded = T::decode_2718_exact(&raw)?;
+        Ok(Self { raw, decoded })
+    }
+}
+
+impl<T> RawAndDecoded<T> {
+    /// Returns the EIP-2718 encoding of the item.
+    pub const fn raw(&self) -> &Bytes {
+        &self.raw
+    }
+
+    /// Returns the decoded item.
+    pub const fn decoded(&self) -> &T {
+        &self.decoded
+    }
+
+    /// Returns the hash of the encoding, which is the transaction hash for transactions.
+    pub fn hash(&self) -> B256 {
+        keccak256(&self.raw)
+    }
+
+    /// Consumes the wrapper, returning the decoded item.
+    pub fn into_decoded(self) -> T {
+        self.decoded
+    }
+
+    /// Consumes the wrapper, returning the encoding and the decoded item.
+    pub fn into_parts(self) -> (Bytes, T) {
+        (self.raw, self.decoded)
+    }
+}
+
+impl<T: Encodable2718> From<T> for RawAndDecoded<T> {
+    fn from(decoded: T) -> Self {
+        Self::new(decoded)
+    }
+}
+
+impl<T> Deref for RawAndDecoded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.decoded
+    }
+}
+
+impl<T> AsRef<[u8]> for RawAndDecoded<T> {
+    fn as_ref(&self) -> &[u8] {
+        &self.raw
+    }
+}
+
+impl<T: Encodable2718> Encodable2718 for RawAndDecoded<T> {
+    fn type_flag(&self) -> Option<u8> {
+        self.decoded.type_flag()
+    }
+
+    fn encode_2718_len(&self) -> usize {
+        self.raw.len()
+    }
+
+    fn encode_2718(&self, out: &mut dyn BufMut) {
+        out.put_slice(&self.raw)
+    }
+
+    fn encoded_2718(&self) -> Vec<u8> {
+        self.raw.to_vec()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for RawAndDecoded<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.raw.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Decodable2718> serde::Deserialize<'de> for RawAndDecoded<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Bytes::deserialize(deserializer)?;
+        Self::decode(raw).map_err(serde::de::Error::custom)
+    }
+}
@@ -5,7 +5,7 @@
 #![allow(unknown_lints, non_local_definitions)]
 
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
 
 use alloy_primitives::{Address, B256, U256};
 use alloy_rlp::{RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper};
@@ -144,6 +144,32 @@ impl AccessList {
         }
     }
 
+    /// Merges the items of the same address, and sorts and deduplicates the addresses and their
+    /// storage keys.
+    ///
+    /// This does not change the cost of the list for a transaction, but makes lists returned by
+    /// different nodes, or built from different traces, comparable.
+    pub fn normalize(&mut self) {
+        let mut accessed = alloc::collections::BTreeMap::<Address, Vec<B256>>::new();
+        for item in mem::take(&mut self.0) {
+            accessed.entry(item.address).or_default().extend(item.storage_keys);
+        }
+        self.0 = accessed
+            .into_iter()
+            .map(|(address, mut storage_keys)| {
+                storage_keys.sort_unstable();
+                storage_keys.dedup();
+                AccessListItem { address, storage_keys }
+            })
+            .collect();
+    }
+
+    /// Returns the list, normalized with [`normalize`](Self::normalize).
+    pub fn normalized(mut self) -> Self {
+        self.normalize();
+        self
+    }
+
     /// Calculates a heuristic for the in-memory size of the [AccessList].
     #[inline]
     pub fn size(&self) -> usize {
@@ -164,6 +190,61 @@ pub struct AccessListWithGasUsed {
     pub gas_used: U256,
 }
 
+/// The result of `eth_createAccessList`.
+///
+/// Nodes return the access list generated so far, along with an error, when the transaction
+/// fails, e.g. if it reverts.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AccessListResult {
+    /// List with accounts accessed during transaction.
+    pub access_list: AccessList,
+    /// Estimated gas used with access list.
+    pub gas_used: U256,
+    /// The error of the transaction, if it failed.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub error: Option<String>,
+}
+
+impl AccessListResult {
+    /// Returns the error of the transaction, if it failed.
+    pub fn ensure_ok(self) -> Result<Self, String> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self),
+        }
+    }
+
+    /// Returns true if the transaction failed.
+    pub const fn is_err(&self) -> bool {
+        self.error.is_some()
+    }
+
+    /// Returns the estimated gas used with the access list, saturating at [`u128::MAX`].
+    pub fn saturating_gas_with_list(&self) -> u128 {
+        self.gas_used.saturating_to()
+    }
+
+    /// Consumes the result, returning its access list, normalized with
+    /// [`AccessList::normalize`].
+    pub fn into_access_list(self) -> AccessList {
+        self.access_list.normalized()
+    }
+}
+
+impl From<AccessListResult> for AccessList {
+    fn from(result: AccessListResult) -> Self {
+        result.into_access_list()
+    }
+}
+
+impl From<AccessListResult> for AccessListWithGasUsed {
+    fn from(result: AccessListResult) -> Self {
+        Self { access_list: result.access_list, gas_used: result.gas_used }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,4 +273,43 @@ mod tests {
         let list2 = serde_json::from_str::<AccessListWithGasUsed>(&json).unwrap();
         assert_eq!(list, list2);
     }
+
+    #[test]
+    fn normalize_access_list() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (k1, k2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let list = AccessList(vec![
+            AccessListItem { address: b, storage_keys: vec![k2, k1, k2] },
+            AccessListItem { address: a, storage_keys: vec![] },
+            AccessListItem { address: b, storage_keys: vec![k1] },
+        ]);
+        assert_eq!(
+            list.normalized().0,
+            [
+                AccessListItem { address: a, storage_keys: vec![] },
+                AccessListItem { address: b, storage_keys: vec![k1, k2] },
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn access_list_result() {
+        let json = r#"{"accessList":[{"address":"0x0000000000000000000000000000000000000001","storageKeys":["0x0000000000000000000000000000000000000000000000000000000000000001","0x0000000000000000000000000000000000000000000000000000000000000001"]}],"gasUsed":"0x5208","error":"execution reverted"}"#;
+        let result = serde_json::from_str::<AccessListResult>(json).unwrap();
+        assert!(result.is_err());
+        assert_eq!(result.saturating_gas_with_list(), 21_000);
+        assert_eq!(result.clone().ensure_ok(), Err("execution reverted".to_string()));
+        assert_eq!(
+            AccessList::from(result).0,
+            [AccessListItem {
+                address: Address::with_last_byte(1),
+                storage_keys: vec![B256::with_last_byte(1)],
+            }]
+        );
+
+        let result = AccessListResult { gas_used: U256::MAX, ..Default::default() };
+        assert_eq!(result.saturating_gas_with_list(), u128::MAX);
+        assert!(result.ensure_ok().is_ok());
+    }
 }
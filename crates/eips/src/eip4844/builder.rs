@@ -193,10 +193,12 @@ impl SimpleCoder {
     /// Decode an some bytes from an iterator of valid FEs.
     ///
     /// Returns `Ok(Some(data))` if there is some data.
-    /// Returns `Ok(None)` if there is no data (length prefix is 0).
+    /// Returns `Ok(None)` if there is no data (length prefix is 0, or no FEs are left).
     /// Returns `Err(())` if there is an error.
     fn decode_one<'a>(mut fes: impl Iterator<Item = WholeFe<'a>>) -> Result<Option<Vec<u8>>, ()> {
-        let first = fes.next().ok_or(())?;
+        let Some(first) = fes.next() else {
+            return Ok(None);
+        };
         let mut num_bytes = u64::from_be_bytes(first.as_ref()[1..9].try_into().unwrap()) as usize;
 
         // if no more bytes is 0, we're done
@@ -245,8 +247,11 @@ impl SidecarCoder for SimpleCoder {
     fn finish(self, _builder: &mut PartialSidecar) {}
 
     fn decode_all(&mut self, blobs: &[Blob]) -> Option<Vec<Vec<u8>>> {
-        let mut fes =
-            blobs.iter().flat_map(|blob| blob.chunks(32).map(WholeFe::new)).map(Option::unwrap);
+        let fes = blobs
+            .iter()
+            .flat_map(|blob| blob.chunks(32).map(WholeFe::new))
+            .collect::<Option<Vec<_>>>()?;
+        let mut fes = fes.into_iter();
 
         let mut res = Vec::new();
         loop {
@@ -266,7 +271,19 @@ impl SidecarCoder for SimpleCoder {
 /// which is then split into blobs. It delays KZG commitments and proofs
 /// until all data is ready.
 ///
+/// The data is decoded back with the same coder, from the blobs of the builder or from those of
+/// the built sidecar with [`BlobTransactionSidecar::decode_data_with`].
+///
+/// ```
+/// use alloy_eips::eip4844::builder::{SidecarBuilder, SidecarCoder, SimpleCoder};
+///
+/// let data = [b"hello".to_vec(), vec![0xff; 200]];
+/// let blobs = data.iter().collect::<SidecarBuilder<SimpleCoder>>().take();
+/// assert_eq!(SimpleCoder::default().decode_all(&blobs).unwrap(), data);
+/// ```
+///
 /// [`BlobTransactionSidecar`]: crate::eip4844::BlobTransactionSidecar
+/// [`BlobTransactionSidecar::decode_data_with`]: crate::eip4844::BlobTransactionSidecar::decode_data_with
 #[derive(Clone, Debug)]
 pub struct SidecarBuilder<T = SimpleCoder> {
     /// The blob array we will code data into
@@ -455,4 +472,20 @@ mod tests {
         builder.ingest("hello".as_bytes());
         assert_eq!(builder.len(), expected_fe * 32 + 64);
     }
+
+    #[test]
+    fn decodes_full_blobs() {
+        // The data fills the blob, leaving no room for a terminating empty length prefix.
+        let data = vec![7u8; (FIELD_ELEMENTS_PER_BLOB as usize - 1) * 31];
+        let blobs = SidecarBuilder::<SimpleCoder>::from_slice(&data).take();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(SimpleCoder.decode_all(&blobs).unwrap(), [data]);
+    }
+
+    #[test]
+    fn rejects_invalid_field_elements() {
+        let mut blobs = SidecarBuilder::<SimpleCoder>::from_slice(b"hello").take();
+        blobs[0][32] = 0xff;
+        assert_eq!(SimpleCoder.decode_all(&blobs), None);
+    }
 }
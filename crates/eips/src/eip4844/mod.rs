@@ -17,6 +17,9 @@ pub mod utils;
 mod sidecar;
 pub use sidecar::*;
 
+mod params;
+pub use params::BlobParams;
+
 use alloy_primitives::{b256, FixedBytes, B256, U256};
 
 /// The modulus of the BLS group used in the KZG commitment scheme. All field
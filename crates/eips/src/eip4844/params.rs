@@ -0,0 +1,96 @@
+use crate::eip4844::{fake_exponential, BLOB_TX_MIN_BLOB_GASPRICE, DATA_GAS_PER_BLOB};
+
+/// The blob parameters of a fork: how many blobs fit in a block, and how fast the blob gas price
+/// reacts to demand.
+///
+/// [EIP-4844] introduced blobs in Cancun, and [EIP-7691] raised their throughput in Prague.
+///
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+/// [EIP-7691]: https://eips.ethereum.org/EIPS/eip-7691
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BlobParams {
+    /// The target number of blobs per block.
+    pub target_blobs_per_block: u64,
+    /// The maximum number of blobs per block.
+    pub max_blobs_per_block: u64,
+    /// The denominator controlling the rate of change of the blob gas price.
+    pub update_fraction: u128,
+}
+
+impl BlobParams {
+    /// Returns the blob parameters introduced in Cancun.
+    pub const fn cancun() -> Self {
+        Self { target_blobs_per_block: 3, max_blobs_per_block: 6, update_fraction: 3_338_477 }
+    }
+
+    /// Returns the blob parameters introduced in Prague.
+    pub const fn prague() -> Self {
+        Self { target_blobs_per_block: 6, max_blobs_per_block: 9, update_fraction: 5_007_716 }
+    }
+
+    /// Returns the target blob gas per block.
+    pub const fn target_blob_gas_per_block(&self) -> u64 {
+        self.target_blobs_per_block * DATA_GAS_PER_BLOB
+    }
+
+    /// Returns the maximum blob gas per block.
+    pub const fn max_blob_gas_per_block(&self) -> u64 {
+        self.max_blobs_per_block * DATA_GAS_PER_BLOB
+    }
+
+    /// Calculates the `excess_blob_gas` from the parent header's `blob_gas_used` and
+    /// `excess_blob_gas`.
+    ///
+    /// See also [`calc_excess_blob_gas`](crate::eip4844::calc_excess_blob_gas).
+    #[inline]
+    pub const fn next_block_excess_blob_gas(
+        &self,
+        parent_excess_blob_gas: u128,
+        parent_blob_gas_used: u128,
+    ) -> u128 {
+        (parent_excess_blob_gas + parent_blob_gas_used)
+            .saturating_sub(self.target_blob_gas_per_block() as u128)
+    }
+
+    /// Calculates the blob gas price from the header's excess blob gas field.
+    ///
+    /// See also [`calc_blob_gasprice`](crate::eip4844::calc_blob_gasprice).
+    #[inline]
+    pub fn calc_blob_gasprice(&self, excess_blob_gas: u128) -> u128 {
+        fake_exponential(BLOB_TX_MIN_BLOB_GASPRICE, excess_blob_gas, self.update_fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip4844::{
+        calc_blob_gasprice, calc_excess_blob_gas, MAX_DATA_GAS_PER_BLOCK, TARGET_DATA_GAS_PER_BLOCK,
+    };
+
+    #[test]
+    fn cancun_matches_constants() {
+        let params = BlobParams::cancun();
+        assert_eq!(params.target_blob_gas_per_block(), TARGET_DATA_GAS_PER_BLOCK);
+        assert_eq!(params.max_blob_gas_per_block(), MAX_DATA_GAS_PER_BLOCK);
+        for (excess, used) in [(0, 0), (1_000_000, 786_432), (10_000_000, 0)] {
+            assert_eq!(
+                params.next_block_excess_blob_gas(excess, used),
+                calc_excess_blob_gas(excess, used)
+            );
+            assert_eq!(params.calc_blob_gasprice(excess), calc_blob_gasprice(excess));
+        }
+    }
+
+    #[test]
+    fn prague_params() {
+        let params = BlobParams::prague();
+        // Six blobs are on target in Prague, but raise the excess blob gas in Cancun.
+        let used = 6 * DATA_GAS_PER_BLOB as u128;
+        assert_eq!(params.next_block_excess_blob_gas(0, used), 0);
+        assert_eq!(BlobParams::cancun().next_block_excess_blob_gas(0, used), used / 2);
+        assert!(params.calc_blob_gasprice(10_000_000) < calc_blob_gasprice(10_000_000));
+    }
+}
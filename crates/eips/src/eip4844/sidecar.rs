@@ -1,6 +1,7 @@
 //! EIP-4844 sidecar type
 
 use crate::eip4844::{
+    builder::{SidecarCoder, SimpleCoder},
     kzg_to_versioned_hash, Blob, Bytes48, BYTES_PER_BLOB, BYTES_PER_COMMITMENT, BYTES_PER_PROOF,
 };
 use alloy_primitives::{bytes::BufMut, B256};
@@ -156,6 +157,21 @@ impl BlobTransactionSidecar {
         self.commitments.get(blob_index).map(|c| kzg_to_versioned_hash(c.as_slice()))
     }
 
+    /// Decodes the data coded into the blobs by the given coder.
+    ///
+    /// Returns `None` if the blobs do not hold data coded by the coder.
+    pub fn decode_data_with<C: SidecarCoder>(&self, coder: &mut C) -> Option<Vec<Vec<u8>>> {
+        coder.decode_all(&self.blobs)
+    }
+
+    /// Decodes the data coded into the blobs by [`SimpleCoder`], the default coder of
+    /// [`SidecarBuilder`](crate::eip4844::builder::SidecarBuilder).
+    ///
+    /// Returns `None` if the blobs do not hold data coded by [`SimpleCoder`].
+    pub fn decode_data(&self) -> Option<Vec<Vec<u8>>> {
+        self.decode_data_with(&mut SimpleCoder)
+    }
+
     /// Encodes the inner [BlobTransactionSidecar] fields as RLP bytes, __without__ a RLP header.
     ///
     /// This encodes the fields in the following order:
@@ -311,4 +327,12 @@ mod tests {
         let deserialized: BlobTransactionSidecar = serde_json::from_str(&s).unwrap();
         assert_eq!(blob, deserialized);
     }
+
+    #[test]
+    fn decodes_data() {
+        let data = [b"blob".to_vec(), vec![1; 1000]];
+        let blobs = data.iter().collect::<crate::eip4844::builder::SidecarBuilder>().take();
+        let sidecar = BlobTransactionSidecar::new(blobs, vec![], vec![]);
+        assert_eq!(sidecar.decode_data().unwrap(), data);
+    }
 }
@@ -6,13 +6,64 @@
 
 #![allow(unknown_lints, non_local_definitions)]
 
-use alloy_primitives::{address, Address, FixedBytes, B256};
+use alloy_primitives::{address, b256, Address, FixedBytes, Log, B256};
 use alloy_rlp::{RlpDecodable, RlpEncodable};
+use core::fmt;
+use sha2::{Digest, Sha256};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
 
 /// Mainnet deposit contract address.
 pub const MAINNET_DEPOSIT_CONTRACT_ADDRESS: Address =
     address!("00000000219ab540356cbb839cbe05303d7705fa");
 
+/// The topic of the `DepositEvent(bytes,bytes,bytes,bytes,bytes)` event of the deposit contract.
+pub const DEPOSIT_EVENT_SIGNATURE: B256 =
+    b256!("649bbc62d0e31342afea4e5cd82d4049e7e1ee912fc0889aa790803be39038c5");
+
+/// The lengths of the fields of a `DepositEvent`, in order.
+const DEPOSIT_EVENT_FIELDS: [(&str, usize); 5] = [
+    ("pubkey", 48),
+    ("withdrawal_credentials", 32),
+    ("amount", 8),
+    ("signature", 96),
+    ("index", 8),
+];
+
+/// Error returned when parsing a deposit contract log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepositLogError {
+    /// The log is not a `DepositEvent`.
+    UnexpectedEvent,
+    /// The ABI encoding of the log data is malformed.
+    Malformed,
+    /// A field of the event does not have the expected length.
+    InvalidLength {
+        /// The name of the field.
+        field: &'static str,
+        /// The expected length of the field.
+        expected: usize,
+        /// The length of the field in the log.
+        got: usize,
+    },
+}
+
+impl fmt::Display for DepositLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEvent => f.write_str("log is not a deposit event"),
+            Self::Malformed => f.write_str("malformed deposit event data"),
+            Self::InvalidLength { field, expected, got } => {
+                write!(f, "deposit event field {field} has length {got}, expected {expected}")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DepositLogError {}
+
 /// This structure maps onto the deposit object from [EIP-6110](https://eips.ethereum.org/EIPS/eip-6110).
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -36,3 +87,183 @@ pub struct DepositRequest {
     #[cfg_attr(feature = "serde", serde(with = "alloy_serde::u64_via_ruint"))]
     pub index: u64,
 }
+
+impl DepositRequest {
+    /// Parses a `DepositEvent` log of the deposit contract.
+    ///
+    /// The address of the log is not checked, as the deposit contract address depends on the
+    /// chain.
+    pub fn from_log(log: &Log) -> Result<Self, DepositLogError> {
+        if log.topics().first() != Some(&DEPOSIT_EVENT_SIGNATURE) {
+            return Err(DepositLogError::UnexpectedEvent);
+        }
+        Self::decode_log_data(&log.data.data)
+    }
+
+    /// Decodes the ABI-encoded data of a `DepositEvent` log, whose fields are all `bytes`, with
+    /// the amount and index encoded as little-endian integers.
+    pub fn decode_log_data(data: &[u8]) -> Result<Self, DepositLogError> {
+        let mut fields = [&[][..]; 5];
+        for (i, (field, (name, expected))) in
+            fields.iter_mut().zip(DEPOSIT_EVENT_FIELDS).enumerate()
+        {
+            let offset = read_usize(data, i * 32)?;
+            let got = read_usize(data, offset)?;
+            if got != expected {
+                return Err(DepositLogError::InvalidLength { field: name, expected, got });
+            }
+            let start = offset.checked_add(32).ok_or(DepositLogError::Malformed)?;
+            *field = data.get(start..start + expected).ok_or(DepositLogError::Malformed)?;
+        }
+        let [pubkey, withdrawal_credentials, amount, signature, index] = fields;
+        Ok(Self {
+            pubkey: FixedBytes::from_slice(pubkey),
+            withdrawal_credentials: B256::from_slice(withdrawal_credentials),
+            amount: u64::from_le_bytes(amount.try_into().unwrap()),
+            signature: FixedBytes::from_slice(signature),
+            index: u64::from_le_bytes(index.try_into().unwrap()),
+        })
+    }
+
+    /// Computes the root of the `DepositData` of the deposit, i.e. the deposit without its index,
+    /// as checked by the deposit contract against the root passed to `deposit`.
+    pub fn deposit_data_root(&self) -> B256 {
+        let pubkey_root = sha256(&[self.pubkey.as_slice(), &[0; 16]]);
+        let signature_root = sha256(&[
+            sha256(&[&self.signature[..64]]).as_slice(),
+            sha256(&[&self.signature[64..], &[0; 32]]).as_slice(),
+        ]);
+        sha256(&[
+            sha256(&[pubkey_root.as_slice(), self.withdrawal_credentials.as_slice()]).as_slice(),
+            sha256(&[&self.amount.to_le_bytes(), &[0; 24], signature_root.as_slice()]).as_slice(),
+        ])
+    }
+
+    /// Returns whether `root` is the root of the `DepositData` of the deposit.
+    pub fn verify_deposit_data_root(&self, root: B256) -> bool {
+        self.deposit_data_root() == root
+    }
+}
+
+/// Parses the deposits of the `DepositEvent` logs emitted by the deposit contract at `contract`,
+/// ignoring other logs, as done to build the deposit requests of a block.
+pub fn parse_deposit_requests<'a>(
+    contract: Address,
+    logs: impl IntoIterator<Item = &'a Log>,
+) -> Result<Vec<DepositRequest>, DepositLogError> {
+    logs.into_iter()
+        .filter(|log| {
+            log.address == contract && log.topics().first() == Some(&DEPOSIT_EVENT_SIGNATURE)
+        })
+        .map(DepositRequest::from_log)
+        .collect()
+}
+
+/// Reads the ABI word at `offset` as a `usize`.
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, DepositLogError> {
+    let word = data.get(offset..offset.checked_add(32).ok_or(DepositLogError::Malformed)?);
+    let word = word.ok_or(DepositLogError::Malformed)?;
+    let (high, low) = word.split_at(24);
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(DepositLogError::Malformed);
+    }
+    usize::try_from(u64::from_be_bytes(low.try_into().unwrap()))
+        .map_err(|_| DepositLogError::Malformed)
+}
+
+fn sha256(parts: &[&[u8]]) -> B256 {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, LogData, U256};
+
+    /// ABI-encodes the fields of a `DepositEvent`, as emitted by the deposit contract.
+    fn encode_log_data(fields: &[&[u8]]) -> Vec<u8> {
+        let mut head = Vec::new();
+        let mut tail = Vec::new();
+        for field in fields {
+            head.extend_from_slice(&U256::from(fields.len() * 32 + tail.len()).to_be_bytes::<32>());
+            tail.extend_from_slice(&U256::from(field.len()).to_be_bytes::<32>());
+            tail.extend_from_slice(field);
+            tail.resize(tail.len().next_multiple_of(32), 0);
+        }
+        [head, tail].concat()
+    }
+
+    fn deposit() -> DepositRequest {
+        DepositRequest {
+            pubkey: FixedBytes::repeat_byte(0xaa),
+            withdrawal_credentials: B256::repeat_byte(0x01),
+            amount: 32_000_000_000,
+            signature: FixedBytes::repeat_byte(0xbb),
+            index: 42,
+        }
+    }
+
+    #[test]
+    fn parses_deposit_logs() {
+        assert_eq!(
+            DEPOSIT_EVENT_SIGNATURE,
+            keccak256("DepositEvent(bytes,bytes,bytes,bytes,bytes)")
+        );
+
+        let deposit = deposit();
+        let data = encode_log_data(&[
+            deposit.pubkey.as_slice(),
+            deposit.withdrawal_credentials.as_slice(),
+            &deposit.amount.to_le_bytes(),
+            deposit.signature.as_slice(),
+            &deposit.index.to_le_bytes(),
+        ]);
+        // The layout of the logs of the mainnet deposit contract.
+        assert_eq!(data.len(), 576);
+        assert_eq!(DepositRequest::decode_log_data(&data), Ok(deposit));
+
+        let log = |address, topic| Log {
+            address,
+            data: LogData::new_unchecked(vec![topic], data.clone().into()),
+        };
+        let logs = [
+            log(MAINNET_DEPOSIT_CONTRACT_ADDRESS, DEPOSIT_EVENT_SIGNATURE),
+            log(MAINNET_DEPOSIT_CONTRACT_ADDRESS, B256::ZERO),
+            log(Address::ZERO, DEPOSIT_EVENT_SIGNATURE),
+        ];
+        assert_eq!(DepositRequest::from_log(&logs[0]), Ok(deposit));
+        assert_eq!(DepositRequest::from_log(&logs[1]), Err(DepositLogError::UnexpectedEvent));
+        assert_eq!(
+            parse_deposit_requests(MAINNET_DEPOSIT_CONTRACT_ADDRESS, &logs),
+            Ok(vec![deposit])
+        );
+
+        assert_eq!(DepositRequest::decode_log_data(&data[..500]), Err(DepositLogError::Malformed));
+        let data = encode_log_data(&[&[0; 47], &[0; 32], &[0; 8], &[0; 96], &[0; 8]]);
+        assert_eq!(
+            DepositRequest::decode_log_data(&data),
+            Err(DepositLogError::InvalidLength { field: "pubkey", expected: 48, got: 47 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ssz")]
+    fn deposit_data_root() {
+        use crate::tree_hash::{container_root, HashTreeRoot};
+
+        let deposit = deposit();
+        let root = container_root(&[
+            deposit.pubkey.hash_tree_root(),
+            deposit.withdrawal_credentials.hash_tree_root(),
+            deposit.amount.hash_tree_root(),
+            deposit.signature.hash_tree_root(),
+        ]);
+        assert_eq!(deposit.deposit_data_root(), root);
+        assert!(deposit.verify_deposit_data_root(root));
+        assert!(!DepositRequest { amount: 1, ..deposit }.verify_deposit_data_root(root));
+    }
+}
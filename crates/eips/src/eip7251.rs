@@ -0,0 +1,38 @@
+//! Contains the system contract and [ConsolidationRequest] types, first introduced in the [Prague hardfork](https://github.com/ethereum/execution-apis/blob/main/src/engine/prague.md).
+//!
+//! See also [EIP-7251](https://eips.ethereum.org/EIPS/eip-7251): Increase the MAX_EFFECTIVE_BALANCE
+
+#![allow(unknown_lints, non_local_definitions)]
+
+use alloy_primitives::{address, Address, FixedBytes};
+use alloy_rlp::{RlpDecodable, RlpEncodable};
+
+/// The address for the EIP-7251 consolidation requests contract, as deployed on the Prague
+/// devnets.
+pub const CONSOLIDATION_REQUEST_PREDEPLOY_ADDRESS: Address =
+    address!("00b42dbF2194e931E80326D950320f7d9Dbeac02");
+
+/// The [EIP-7685](https://eips.ethereum.org/EIPS/eip-7685) request type for consolidation requests.
+pub const CONSOLIDATION_REQUEST_TYPE: u8 = 0x02;
+
+/// Represents an execution layer triggerable consolidation request, moving the balance of a
+/// source validator to a target validator.
+///
+/// See [EIP-7251](https://eips.ethereum.org/EIPS/eip-7251).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(feature = "ssz", derive(ssz_derive::Encode, ssz_derive::Decode))]
+#[cfg_attr(
+    any(test, feature = "arbitrary"),
+    derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
+)]
+pub struct ConsolidationRequest {
+    /// Address of the source of the consolidation, the withdrawal address of the source
+    /// validator.
+    pub source_address: Address,
+    /// The public key of the source validator.
+    pub source_pubkey: FixedBytes<48>,
+    /// The public key of the target validator.
+    pub target_pubkey: FixedBytes<48>,
+}
@@ -7,12 +7,37 @@
 #[cfg(not(feature = "std"))]
 use crate::alloc::{vec, vec::Vec};
 
+use alloy_primitives::{b256, B256};
 use alloy_rlp::BufMut;
 use core::{
     fmt,
     fmt::{Display, Formatter},
 };
 
+/// The [EIP-7685] requests hash of a block without requests, i.e. the SHA-256 hash of nothing.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+pub const EMPTY_REQUESTS_HASH: B256 =
+    b256!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+
+/// Computes the [EIP-7685] requests hash, which later Prague devnets commit to in the block header
+/// in place of the requests root.
+///
+/// Each item of `requests` is a request type followed by the concatenated data of the requests
+/// of that type, ordered by request type. Items without requests, i.e. with only a type, are
+/// skipped.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+pub fn requests_hash<T: AsRef<[u8]>>(requests: &[T]) -> B256 {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for request in requests.iter().map(AsRef::as_ref).filter(|request| request.len() > 1) {
+        hasher.update(Sha256::digest(request));
+    }
+    B256::new(hasher.finalize().into())
+}
+
 /// [EIP-7685] decoding errors.
 ///
 /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
@@ -136,3 +161,25 @@ pub trait Encodable7685: Sized + Send + Sync + 'static {
 /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
 pub trait Eip7685RequestEnvelope: Decodable7685 + Encodable7685 {}
 impl<T> Eip7685RequestEnvelope for T where T: Decodable7685 + Encodable7685 {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn hashes_requests() {
+        assert_eq!(requests_hash::<&[u8]>(&[]), EMPTY_REQUESTS_HASH);
+        assert_eq!(requests_hash(&[[0x00], [0x01], [0x02]]), EMPTY_REQUESTS_HASH);
+
+        let deposits = [0x00, 0xaa, 0xbb];
+        let consolidations = [0x02, 0xcc];
+        let mut expected = Sha256::new();
+        expected.update(Sha256::digest(deposits));
+        expected.update(Sha256::digest(consolidations));
+        assert_eq!(
+            requests_hash(&[&deposits[..], &[0x01], &consolidations]),
+            B256::new(expected.finalize().into())
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! [EIP-7702] set code authorizations.
+//!
+//! An authorization lets a transaction set the code of the signing account, the authority, to
+//! the code of another address. It is only bound to a chain if its chain ID is not zero.
+//!
+//! [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+
+use alloy_primitives::{keccak256, Address, ChainId, Signature, B256};
+use alloy_rlp::{BufMut, Encodable, RlpDecodable, RlpEncodable};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// The prefix of the signed payload of an [`Authorization`].
+pub const MAGIC: u8 = 0x05;
+
+/// An unsigned authorization to set the code of the signing account to the code of
+/// [`address`](Self::address).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, RlpEncodable, RlpDecodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    any(test, feature = "arbitrary"),
+    derive(proptest_derive::Arbitrary, arbitrary::Arbitrary)
+)]
+pub struct Authorization {
+    /// The chain the authorization is valid on, or zero for every chain.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::u64_via_ruint"))]
+    pub chain_id: ChainId,
+    /// The address whose code the authority delegates to.
+    pub address: Address,
+    /// The nonce the authority must have for the authorization to be valid.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::u64_via_ruint"))]
+    pub nonce: u64,
+}
+
+impl Authorization {
+    /// Returns `true` if the authorization is valid on every chain.
+    pub const fn is_chain_agnostic(&self) -> bool {
+        self.chain_id == 0
+    }
+
+    /// Returns `true` if the authorization is valid on the chain with the given ID.
+    pub const fn is_valid_on(&self, chain_id: ChainId) -> bool {
+        self.is_chain_agnostic() || self.chain_id == chain_id
+    }
+
+    /// Returns the hash signed by the authority, the keccak256 hash of [`MAGIC`] followed by the
+    /// RLP encoding of the authorization.
+    pub fn signature_hash(&self) -> B256 {
+        let mut buf = Vec::with_capacity(1 + self.length());
+        buf.put_u8(MAGIC);
+        self.encode(&mut buf);
+        keccak256(buf)
+    }
+
+    /// Attaches the signature of the authority to the authorization.
+    pub const fn into_signed(self, signature: Signature) -> SignedAuthorization {
+        SignedAuthorization { inner: self, signature }
+    }
+}
+
+/// An [`Authorization`] signed by its authority.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SignedAuthorization {
+    inner: Authorization,
+    signature: Signature,
+}
+
+impl SignedAuthorization {
+    /// Returns the signed authorization.
+    pub const fn authorization(&self) -> &Authorization {
+        &self.inner
+    }
+
+    /// Returns the signature of the authority.
+    pub const fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Splits the signed authorization into the authorization and its signature.
+    pub const fn into_parts(self) -> (Authorization, Signature) {
+        (self.inner, self.signature)
+    }
+
+    /// Recovers the address of the authority.
+    #[cfg(feature = "k256")]
+    pub fn recover_authority(&self) -> Result<Address, alloy_primitives::SignatureError> {
+        self.signature.recover_address_from_prehash(&self.inner.signature_hash())
+    }
+}
+
+impl core::ops::Deref for SignedAuthorization {
+    type Target = Authorization;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn signature_hash() {
+        let auth = Authorization {
+            chain_id: 1,
+            address: address!("00000000000000000000000000000000000000aa"),
+            nonce: 0,
+        };
+        let mut expected = vec![MAGIC];
+        expected.extend(alloy_rlp::encode(auth));
+        assert_eq!(auth.signature_hash(), keccak256(expected));
+        // [chain_id, address, nonce]
+        assert_eq!(alloy_rlp::encode(auth)[..3], [0xd7, 0x01, 0x94]);
+    }
+
+    #[test]
+    fn chain_binding() {
+        let auth = Authorization { chain_id: 0, ..Default::default() };
+        assert!(auth.is_chain_agnostic() && auth.is_valid_on(10));
+        let auth = Authorization { chain_id: 1, ..Default::default() };
+        assert!(auth.is_valid_on(1) && !auth.is_valid_on(10));
+    }
+}
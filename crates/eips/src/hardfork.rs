@@ -0,0 +1,287 @@
+//! Ethereum hardforks and their activation schedules.
+//!
+//! A [`ChainHardforks`] schedule tells when each [`Hardfork`] activates on a chain, and derives
+//! the fee parameters in effect at a given block: the [`BaseFeeParams`] once London is active,
+//! and the [`BlobParams`] of the latest fork that changed them.
+//!
+//! ```
+//! use alloy_eips::hardfork::{ChainHardforks, Hardfork};
+//!
+//! let mainnet = ChainHardforks::for_chain(1).unwrap();
+//! assert!(mainnet.is_active_at(Hardfork::Cancun, 19_426_587, 1_710_338_135));
+//! assert!(!mainnet.is_active_at_timestamp(Hardfork::Cancun, 1_710_338_134));
+//! ```
+
+use crate::{chain::NamedChain, eip1559::BaseFeeParams, eip4844::BlobParams};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// An Ethereum hardfork, in activation order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hardfork {
+    /// Frontier, the genesis of mainnet.
+    Frontier,
+    /// Homestead.
+    Homestead,
+    /// The DAO fork.
+    Dao,
+    /// Tangerine Whistle.
+    Tangerine,
+    /// Spurious Dragon.
+    SpuriousDragon,
+    /// Byzantium.
+    Byzantium,
+    /// Constantinople.
+    Constantinople,
+    /// Petersburg.
+    Petersburg,
+    /// Istanbul.
+    Istanbul,
+    /// Muir Glacier.
+    MuirGlacier,
+    /// Berlin.
+    Berlin,
+    /// London, which introduced [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559) base fees.
+    London,
+    /// Arrow Glacier.
+    ArrowGlacier,
+    /// Gray Glacier.
+    GrayGlacier,
+    /// Paris, the merge.
+    Paris,
+    /// Shanghai.
+    Shanghai,
+    /// Cancun, which introduced [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844) blobs.
+    Cancun,
+    /// Prague.
+    Prague,
+}
+
+impl Hardfork {
+    /// Returns the blob parameters set by the fork, or `None` if it did not change them.
+    pub const fn blob_params(self) -> Option<BlobParams> {
+        match self {
+            Self::Cancun => Some(BlobParams::cancun()),
+            Self::Prague => Some(BlobParams::prague()),
+            _ => None,
+        }
+    }
+}
+
+/// The condition activating a hardfork.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ForkCondition {
+    /// The fork activates at a block number.
+    Block(u64),
+    /// The fork activates at a block timestamp.
+    Timestamp(u64),
+}
+
+impl ForkCondition {
+    /// Returns `true` if the condition is met by the block with the given number and timestamp.
+    pub const fn is_met_at(&self, block: u64, timestamp: u64) -> bool {
+        match *self {
+            Self::Block(activation) => block >= activation,
+            Self::Timestamp(activation) => timestamp >= activation,
+        }
+    }
+}
+
+/// The hardfork schedule of a chain.
+///
+/// Forks missing from the schedule never activate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChainHardforks {
+    forks: Vec<(Hardfork, ForkCondition)>,
+    base_fee_params: BaseFeeParams,
+}
+
+impl ChainHardforks {
+    /// Creates a schedule from the activation conditions of its forks, using the Ethereum base
+    /// fee parameters.
+    pub fn new(forks: impl IntoIterator<Item = (Hardfork, ForkCondition)>) -> Self {
+        let mut forks: Vec<_> = forks.into_iter().collect();
+        forks.sort_unstable_by_key(|(fork, _)| *fork);
+        forks.dedup_by_key(|(fork, _)| *fork);
+        Self { forks, base_fee_params: BaseFeeParams::ethereum() }
+    }
+
+    /// Sets the base fee parameters of the chain.
+    pub const fn with_base_fee_params(mut self, base_fee_params: BaseFeeParams) -> Self {
+        self.base_fee_params = base_fee_params;
+        self
+    }
+
+    /// Returns the schedule of a known chain, or `None` if the chain is unknown.
+    ///
+    /// Known chains are Ethereum mainnet, Sepolia and Holesky.
+    pub fn for_chain(chain_id: u64) -> Option<Self> {
+        match NamedChain::from_id(chain_id)? {
+            NamedChain::Mainnet => Some(Self::mainnet()),
+            NamedChain::Sepolia => Some(Self::sepolia()),
+            NamedChain::Holesky => Some(Self::holesky()),
+            _ => None,
+        }
+    }
+
+    /// Returns the schedule of Ethereum mainnet.
+    pub fn mainnet() -> Self {
+        use ForkCondition::{Block, Timestamp};
+        Self::new([
+            (Hardfork::Frontier, Block(0)),
+            (Hardfork::Homestead, Block(1_150_000)),
+            (Hardfork::Dao, Block(1_920_000)),
+            (Hardfork::Tangerine, Block(2_463_000)),
+            (Hardfork::SpuriousDragon, Block(2_675_000)),
+            (Hardfork::Byzantium, Block(4_370_000)),
+            (Hardfork::Constantinople, Block(7_280_000)),
+            (Hardfork::Petersburg, Block(7_280_000)),
+            (Hardfork::Istanbul, Block(9_069_000)),
+            (Hardfork::MuirGlacier, Block(9_200_000)),
+            (Hardfork::Berlin, Block(12_244_000)),
+            (Hardfork::London, Block(12_965_000)),
+            (Hardfork::ArrowGlacier, Block(13_773_000)),
+            (Hardfork::GrayGlacier, Block(15_050_000)),
+            (Hardfork::Paris, Block(15_537_394)),
+            (Hardfork::Shanghai, Timestamp(1_681_338_455)),
+            (Hardfork::Cancun, Timestamp(1_710_338_135)),
+            (Hardfork::Prague, Timestamp(1_746_612_311)),
+        ])
+    }
+
+    /// Returns the schedule of the Sepolia testnet.
+    pub fn sepolia() -> Self {
+        use ForkCondition::{Block, Timestamp};
+        Self::new([
+            (Hardfork::Frontier, Block(0)),
+            (Hardfork::Homestead, Block(0)),
+            (Hardfork::Tangerine, Block(0)),
+            (Hardfork::SpuriousDragon, Block(0)),
+            (Hardfork::Byzantium, Block(0)),
+            (Hardfork::Constantinople, Block(0)),
+            (Hardfork::Petersburg, Block(0)),
+            (Hardfork::Istanbul, Block(0)),
+            (Hardfork::MuirGlacier, Block(0)),
+            (Hardfork::Berlin, Block(0)),
+            (Hardfork::London, Block(0)),
+            (Hardfork::Paris, Block(1_735_371)),
+            (Hardfork::Shanghai, Timestamp(1_677_557_088)),
+            (Hardfork::Cancun, Timestamp(1_706_655_072)),
+            (Hardfork::Prague, Timestamp(1_741_159_776)),
+        ])
+    }
+
+    /// Returns the schedule of the Holesky testnet.
+    pub fn holesky() -> Self {
+        use ForkCondition::{Block, Timestamp};
+        Self::new([
+            (Hardfork::Frontier, Block(0)),
+            (Hardfork::Homestead, Block(0)),
+            (Hardfork::Tangerine, Block(0)),
+            (Hardfork::SpuriousDragon, Block(0)),
+            (Hardfork::Byzantium, Block(0)),
+            (Hardfork::Constantinople, Block(0)),
+            (Hardfork::Petersburg, Block(0)),
+            (Hardfork::Istanbul, Block(0)),
+            (Hardfork::MuirGlacier, Block(0)),
+            (Hardfork::Berlin, Block(0)),
+            (Hardfork::London, Block(0)),
+            (Hardfork::Paris, Block(0)),
+            (Hardfork::Shanghai, Timestamp(1_696_000_704)),
+            (Hardfork::Cancun, Timestamp(1_707_305_664)),
+            (Hardfork::Prague, Timestamp(1_740_434_112)),
+        ])
+    }
+
+    /// Returns the activation condition of a fork, or `None` if it never activates.
+    pub fn fork(&self, fork: Hardfork) -> Option<ForkCondition> {
+        self.forks.iter().find(|(f, _)| *f == fork).map(|(_, condition)| *condition)
+    }
+
+    /// Returns the forks of the schedule and their activation conditions, in activation order.
+    pub fn forks(&self) -> impl Iterator<Item = (Hardfork, ForkCondition)> + '_ {
+        self.forks.iter().copied()
+    }
+
+    /// Returns `true` if the fork is active at the block with the given number and timestamp.
+    pub fn is_active_at(&self, fork: Hardfork, block: u64, timestamp: u64) -> bool {
+        self.fork(fork).is_some_and(|condition| condition.is_met_at(block, timestamp))
+    }
+
+    /// Returns `true` if the fork activates by block number and is active at `block`.
+    pub fn is_active_at_block(&self, fork: Hardfork, block: u64) -> bool {
+        matches!(self.fork(fork), Some(ForkCondition::Block(activation)) if block >= activation)
+    }
+
+    /// Returns `true` if the fork activates by timestamp and is active at `timestamp`.
+    pub fn is_active_at_timestamp(&self, fork: Hardfork, timestamp: u64) -> bool {
+        matches!(self.fork(fork), Some(ForkCondition::Timestamp(activation)) if timestamp >= activation)
+    }
+
+    /// Returns the latest fork active at the block with the given number and timestamp.
+    pub fn active_fork(&self, block: u64, timestamp: u64) -> Option<Hardfork> {
+        self.active_forks(block, timestamp).next()
+    }
+
+    /// Returns the base fee parameters in effect at the block with the given number and
+    /// timestamp, or `None` before London.
+    pub fn base_fee_params_at(&self, block: u64, timestamp: u64) -> Option<BaseFeeParams> {
+        self.is_active_at(Hardfork::London, block, timestamp).then_some(self.base_fee_params)
+    }
+
+    /// Returns the blob parameters in effect at the block with the given number and timestamp,
+    /// or `None` before Cancun.
+    pub fn blob_params_at(&self, block: u64, timestamp: u64) -> Option<BlobParams> {
+        self.active_forks(block, timestamp).find_map(Hardfork::blob_params)
+    }
+
+    /// Returns the forks active at the given block, latest first.
+    fn active_forks(&self, block: u64, timestamp: u64) -> impl Iterator<Item = Hardfork> + '_ {
+        self.forks
+            .iter()
+            .rev()
+            .filter(move |(_, condition)| condition.is_met_at(block, timestamp))
+            .map(|(fork, _)| *fork)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mainnet_schedule() {
+        let mainnet = ChainHardforks::mainnet();
+        assert!(mainnet.is_active_at_block(Hardfork::London, 12_965_000));
+        assert!(!mainnet.is_active_at_block(Hardfork::London, 12_964_999));
+        // Timestamp forks are not active at any block number, and block forks at any timestamp.
+        assert!(!mainnet.is_active_at_block(Hardfork::Shanghai, u64::MAX));
+        assert!(!mainnet.is_active_at_timestamp(Hardfork::London, u64::MAX));
+
+        let (block, timestamp) = (19_426_587, 1_710_338_135);
+        assert_eq!(mainnet.active_fork(block, timestamp), Some(Hardfork::Cancun));
+        assert_eq!(mainnet.active_fork(block, timestamp - 1), Some(Hardfork::Shanghai));
+        assert_eq!(mainnet.active_fork(0, 0), Some(Hardfork::Frontier));
+        assert_eq!(ChainHardforks::new([]).active_fork(block, timestamp), None);
+    }
+
+    #[test]
+    fn fee_params() {
+        let sepolia = ChainHardforks::for_chain(11_155_111).unwrap();
+        assert_eq!(sepolia.base_fee_params_at(0, 0), Some(BaseFeeParams::ethereum()));
+        assert_eq!(sepolia.blob_params_at(0, 0), None);
+        assert_eq!(sepolia.blob_params_at(5_000_000, 1_706_655_072), Some(BlobParams::cancun()));
+        assert_eq!(sepolia.blob_params_at(7_000_000, 1_741_159_776), Some(BlobParams::prague()));
+
+        let mainnet = ChainHardforks::mainnet();
+        assert_eq!(mainnet.base_fee_params_at(12_964_999, 0), None);
+
+        let custom = ChainHardforks::new([(Hardfork::London, ForkCondition::Block(5))])
+            .with_base_fee_params(BaseFeeParams::new(50, 6));
+        assert_eq!(custom.base_fee_params_at(5, 0), Some(BaseFeeParams::new(50, 6)));
+        assert!(ChainHardforks::for_chain(10).is_none());
+    }
+}
@@ -19,6 +19,9 @@
 #[macro_use]
 extern crate alloc;
 
+pub mod chain;
+pub use chain::{Chain, NamedChain};
+
 pub mod eip1559;
 pub use eip1559::calc_next_block_base_fee;
 
@@ -45,4 +48,16 @@ pub mod merge;
 
 pub mod eip7002;
 
+pub mod eip7251;
+
 pub mod eip7685;
+
+pub mod eip7702;
+
+pub mod hardfork;
+
+#[cfg(any(test, feature = "test-vectors"))]
+pub mod test_vectors;
+
+#[cfg(feature = "ssz")]
+pub mod tree_hash;
@@ -0,0 +1,273 @@
+//! Test vectors for the fee computations of [EIP-1559] and [EIP-4844].
+//!
+//! Networks embedding these computations, possibly with their own [`BaseFeeParams`], can check
+//! their parameters and implementations against the same inputs and expected outputs as this
+//! crate. Most vectors come from the test suites of go-ethereum and reth, the fee history vectors
+//! from a response of a public testnet, and the Prague vectors were computed with the reference
+//! implementation of [EIP-4844] and the parameters of [EIP-7691].
+//!
+//! [EIP-1559]: https://eips.ethereum.org/EIPS/eip-1559
+//! [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+//! [EIP-7691]: https://eips.ethereum.org/EIPS/eip-7691
+//! [`BaseFeeParams`]: crate::eip1559::BaseFeeParams
+
+use crate::eip4844::{DATA_GAS_PER_BLOB, TARGET_DATA_GAS_PER_BLOCK};
+
+/// The base fee of the block following a parent block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BaseFeeVector {
+    /// The gas used by the parent block.
+    pub gas_used: u128,
+    /// The gas limit of the parent block.
+    pub gas_limit: u128,
+    /// The base fee of the parent block.
+    pub base_fee: u128,
+    /// The expected base fee of the next block.
+    pub next_base_fee: u128,
+}
+
+impl BaseFeeVector {
+    const fn new(gas_used: u128, gas_limit: u128, base_fee: u128, next_base_fee: u128) -> Self {
+        Self { gas_used, gas_limit, base_fee, next_base_fee }
+    }
+}
+
+/// Base fee vectors for [`BaseFeeParams::ethereum`](crate::eip1559::BaseFeeParams::ethereum).
+pub const ETHEREUM_BASE_FEE_VECTORS: [BaseFeeVector; 10] = [
+    BaseFeeVector::new(10_000_000, 10_000_000, 1_000_000_000, 1_125_000_000),
+    BaseFeeVector::new(10_000_000, 12_000_000, 1_000_000_000, 1_083_333_333),
+    BaseFeeVector::new(10_000_000, 14_000_000, 1_000_000_000, 1_053_571_428),
+    BaseFeeVector::new(9_000_000, 10_000_000, 1_072_671_875, 1_179_939_062),
+    BaseFeeVector::new(10_001_000, 14_000_000, 1_059_263_476, 1_116_028_649),
+    BaseFeeVector::new(0, 2_000_000, 1_049_238_967, 918_084_097),
+    BaseFeeVector::new(10_000_000, 18_000_000, 1_049_238_967, 1_063_811_730),
+    BaseFeeVector::new(10_000_000, 18_000_000, 0, 1),
+    BaseFeeVector::new(10_000_000, 18_000_000, 1, 2),
+    BaseFeeVector::new(10_000_000, 18_000_000, 2, 3),
+];
+
+/// The excess blob gas of the block following a parent block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExcessBlobGasVector {
+    /// The excess blob gas of the parent block.
+    pub excess_blob_gas: u128,
+    /// The blob gas used by the parent block.
+    pub blob_gas_used: u128,
+    /// The expected excess blob gas of the next block.
+    pub next_excess_blob_gas: u128,
+}
+
+impl ExcessBlobGasVector {
+    const fn new(excess_blob_gas: u64, blobs: u64, next_excess_blob_gas: u64) -> Self {
+        Self {
+            excess_blob_gas: excess_blob_gas as u128,
+            blob_gas_used: (blobs * DATA_GAS_PER_BLOB) as u128,
+            next_excess_blob_gas: next_excess_blob_gas as u128,
+        }
+    }
+}
+
+const TARGET_BLOBS: u64 = TARGET_DATA_GAS_PER_BLOCK / DATA_GAS_PER_BLOB;
+
+/// Excess blob gas vectors for the parameters of Cancun.
+pub const EXCESS_BLOB_GAS_VECTORS: [ExcessBlobGasVector; 10] = [
+    // The excess blob gas does not increase from zero if the blob gas used is below, or equal to,
+    // the target.
+    ExcessBlobGasVector::new(0, 0, 0),
+    ExcessBlobGasVector::new(0, 1, 0),
+    ExcessBlobGasVector::new(0, TARGET_BLOBS, 0),
+    // Above the target, the excess blob gas increases by the blob gas used above the target.
+    ExcessBlobGasVector::new(0, TARGET_BLOBS + 1, DATA_GAS_PER_BLOB),
+    ExcessBlobGasVector::new(1, TARGET_BLOBS + 1, DATA_GAS_PER_BLOB + 1),
+    ExcessBlobGasVector::new(1, TARGET_BLOBS + 2, 2 * DATA_GAS_PER_BLOB + 1),
+    // Below the target, it decreases by the blob gas used below the target, down to zero.
+    ExcessBlobGasVector::new(TARGET_DATA_GAS_PER_BLOCK, TARGET_BLOBS, TARGET_DATA_GAS_PER_BLOCK),
+    ExcessBlobGasVector::new(
+        TARGET_DATA_GAS_PER_BLOCK,
+        TARGET_BLOBS - 1,
+        TARGET_DATA_GAS_PER_BLOCK - DATA_GAS_PER_BLOB,
+    ),
+    ExcessBlobGasVector::new(
+        TARGET_DATA_GAS_PER_BLOCK,
+        TARGET_BLOBS - 2,
+        TARGET_DATA_GAS_PER_BLOCK - (2 * DATA_GAS_PER_BLOB),
+    ),
+    ExcessBlobGasVector::new(DATA_GAS_PER_BLOB - 1, TARGET_BLOBS - 1, 0),
+];
+
+/// The blob gas price of a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlobGasPriceVector {
+    /// The excess blob gas of the block.
+    pub excess_blob_gas: u128,
+    /// The expected blob gas price of the block.
+    pub blob_gasprice: u128,
+}
+
+impl BlobGasPriceVector {
+    const fn new(excess_blob_gas: u128, blob_gasprice: u128) -> Self {
+        Self { excess_blob_gas, blob_gasprice }
+    }
+}
+
+/// Blob gas price vectors for the parameters of Cancun.
+///
+/// The blob gas price approximates `e ** (excess_blob_gas / BLOB_GASPRICE_UPDATE_FRACTION)` with
+/// a Taylor expansion, so the vectors include the boundaries where it crosses powers of two.
+pub const BLOB_GASPRICE_VECTORS: [BlobGasPriceVector; 7] = [
+    BlobGasPriceVector::new(0, 1),
+    BlobGasPriceVector::new(2_314_057, 1),
+    BlobGasPriceVector::new(2_314_058, 2),
+    BlobGasPriceVector::new(10 * 1024 * 1024, 23),
+    // just below 2**64
+    BlobGasPriceVector::new(148_099_578, 18_446_739_238_971_471_609),
+    // just above 2**64
+    BlobGasPriceVector::new(148_099_579, 18_446_744_762_204_311_910),
+    BlobGasPriceVector::new(161_087_488, 902_580_055_246_494_526_580),
+];
+
+/// An `eth_feeHistory` response of ten consecutive post-Cancun blocks of a public testnet, with a
+/// gas limit of 30M and a reward percentile of 50.
+pub const FEE_HISTORY_RESPONSE: &str = r#"{"baseFeePerBlobGas":["0xc0","0xb2","0xab","0x98","0x9e","0x92","0xa4","0xb9","0xd0","0xea","0xfd"],"baseFeePerGas":["0x4cb8cf181","0x53075988e","0x4fb92ee18","0x45c209055","0x4e790dca2","0x58462e84e","0x5b7659f4e","0x5d66ea3aa","0x6283c6e45","0x5ecf0e1e5","0x5da59cf89"],"blobGasUsedRatio":[0.16666666666666666,0.3333333333333333,0,0.6666666666666666,0.16666666666666666,1,1,1,1,0.8333333333333334],"gasUsedRatio":[0.8288135,0.3407616666666667,0,0.9997232,0.999601,0.6444664333333333,0.5848306333333333,0.7189564,0.34952733333333336,0.4509799666666667],"oldestBlock":"0x59f94f","reward":[["0x59682f00"],["0x59682f00"],["0x0"],["0x59682f00"],["0x59682f00"],["0x3b9aca00"],["0x59682f00"],["0x59682f00"],["0x3b9aca00"],["0x59682f00"]]}"#;
+
+/// Base fee vectors of the blocks of [`FEE_HISTORY_RESPONSE`], for
+/// [`BaseFeeParams::ethereum`](crate::eip1559::BaseFeeParams::ethereum).
+///
+/// The gas used is the gas used ratio of the response times the gas limit.
+pub const FEE_HISTORY_BASE_FEE_VECTORS: [BaseFeeVector; 10] = [
+    BaseFeeVector::new(24_864_405, 30_000_000, 20_594_880_897, 22_287_849_614),
+    BaseFeeVector::new(10_222_850, 30_000_000, 22_287_849_614, 21_400_579_608),
+    BaseFeeVector::new(0, 30_000_000, 21_400_579_608, 18_725_507_157),
+    BaseFeeVector::new(29_991_696, 30_000_000, 18_725_507_157, 21_064_899_746),
+    BaseFeeVector::new(29_988_030, 30_000_000, 21_064_899_746, 23_695_910_990),
+    BaseFeeVector::new(19_333_993, 30_000_000, 23_695_910_990, 24_551_726_926),
+    BaseFeeVector::new(17_544_919, 30_000_000, 24_551_726_926, 25_072_411_562),
+    BaseFeeVector::new(21_568_692, 30_000_000, 25_072_411_562, 26_444_852_805),
+    BaseFeeVector::new(10_485_820, 30_000_000, 26_444_852_805, 25_450_045_925),
+    BaseFeeVector::new(13_529_399, 30_000_000, 25_450_045_925, 25_138_155_401),
+];
+
+/// The blobs used by the blocks of [`FEE_HISTORY_RESPONSE`], from their blob gas used ratios.
+pub const FEE_HISTORY_BLOBS: [u64; 10] = [1, 2, 0, 4, 1, 6, 6, 6, 6, 5];
+
+/// An excess blob gas of the oldest block of [`FEE_HISTORY_RESPONSE`] consistent with its blob
+/// base fees. Any excess blob gas from 17,561,410 to 17,568,759 yields the same fees.
+pub const FEE_HISTORY_EXCESS_BLOB_GAS: u128 = 17_561_410;
+
+const PRAGUE_TARGET_BLOBS: u64 = 6;
+const PRAGUE_TARGET_BLOB_GAS: u64 = PRAGUE_TARGET_BLOBS * DATA_GAS_PER_BLOB;
+
+/// Excess blob gas vectors for [`BlobParams::prague`](crate::eip4844::BlobParams::prague).
+pub const PRAGUE_EXCESS_BLOB_GAS_VECTORS: [ExcessBlobGasVector; 8] = [
+    ExcessBlobGasVector::new(0, 0, 0),
+    ExcessBlobGasVector::new(0, PRAGUE_TARGET_BLOBS, 0),
+    ExcessBlobGasVector::new(0, PRAGUE_TARGET_BLOBS + 1, DATA_GAS_PER_BLOB),
+    // A full block of nine blobs.
+    ExcessBlobGasVector::new(1, PRAGUE_TARGET_BLOBS + 3, 3 * DATA_GAS_PER_BLOB + 1),
+    // Three blobs were the target of Cancun, but are below the target of Prague.
+    ExcessBlobGasVector::new(0, 3, 0),
+    ExcessBlobGasVector::new(PRAGUE_TARGET_BLOB_GAS, PRAGUE_TARGET_BLOBS, PRAGUE_TARGET_BLOB_GAS),
+    ExcessBlobGasVector::new(
+        PRAGUE_TARGET_BLOB_GAS,
+        PRAGUE_TARGET_BLOBS - 1,
+        PRAGUE_TARGET_BLOB_GAS - DATA_GAS_PER_BLOB,
+    ),
+    ExcessBlobGasVector::new(DATA_GAS_PER_BLOB - 1, PRAGUE_TARGET_BLOBS - 1, 0),
+];
+
+/// Blob gas price vectors for [`BlobParams::prague`](crate::eip4844::BlobParams::prague).
+pub const PRAGUE_BLOB_GASPRICE_VECTORS: [BlobGasPriceVector; 6] = [
+    BlobGasPriceVector::new(0, 1),
+    BlobGasPriceVector::new(3_471_086, 1),
+    BlobGasPriceVector::new(3_471_087, 2),
+    BlobGasPriceVector::new(10 * 1024 * 1024, 8),
+    // just below 2**64
+    BlobGasPriceVector::new(222_149_390, 18_446_742_259_463_423_153),
+    // just above 2**64
+    BlobGasPriceVector::new(222_149_391, 18_446_745_941_619_026_145),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eip1559::{calc_next_block_base_fee, BaseFeeParams},
+        eip4844::{calc_blob_gasprice, calc_excess_blob_gas, BlobParams},
+    };
+
+    #[test]
+    fn base_fee_vectors() {
+        for vector in ETHEREUM_BASE_FEE_VECTORS.iter().chain(&FEE_HISTORY_BASE_FEE_VECTORS) {
+            let next_base_fee = calc_next_block_base_fee(
+                vector.gas_used,
+                vector.gas_limit,
+                vector.base_fee,
+                BaseFeeParams::ethereum(),
+            );
+            assert_eq!(next_base_fee, vector.next_base_fee, "{vector:?}");
+        }
+    }
+
+    #[test]
+    fn blob_vectors() {
+        let (cancun, prague) = (BlobParams::cancun(), BlobParams::prague());
+        for vector in EXCESS_BLOB_GAS_VECTORS {
+            let next = calc_excess_blob_gas(vector.excess_blob_gas, vector.blob_gas_used);
+            assert_eq!(next, vector.next_excess_blob_gas, "{vector:?}");
+            let next =
+                cancun.next_block_excess_blob_gas(vector.excess_blob_gas, vector.blob_gas_used);
+            assert_eq!(next, vector.next_excess_blob_gas, "{vector:?}");
+        }
+        for vector in BLOB_GASPRICE_VECTORS {
+            assert_eq!(calc_blob_gasprice(vector.excess_blob_gas), vector.blob_gasprice);
+            assert_eq!(cancun.calc_blob_gasprice(vector.excess_blob_gas), vector.blob_gasprice);
+        }
+        for vector in PRAGUE_EXCESS_BLOB_GAS_VECTORS {
+            let next =
+                prague.next_block_excess_blob_gas(vector.excess_blob_gas, vector.blob_gas_used);
+            assert_eq!(next, vector.next_excess_blob_gas, "{vector:?}");
+        }
+        for vector in PRAGUE_BLOB_GASPRICE_VECTORS {
+            assert_eq!(prague.calc_blob_gasprice(vector.excess_blob_gas), vector.blob_gasprice);
+        }
+    }
+
+    #[test]
+    fn fee_history_vectors() {
+        let response: serde_json::Value = serde_json::from_str(FEE_HISTORY_RESPONSE).unwrap();
+        let quantities = |field: &str| -> Vec<u128> {
+            response[field]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|fee| u128::from_str_radix(&fee.as_str().unwrap()[2..], 16).unwrap())
+                .collect()
+        };
+        let ratios = |field: &str| -> Vec<f64> {
+            response[field].as_array().unwrap().iter().map(|r| r.as_f64().unwrap()).collect()
+        };
+
+        let base_fees = quantities("baseFeePerGas");
+        for ((vector, ratio), fees) in FEE_HISTORY_BASE_FEE_VECTORS
+            .iter()
+            .zip(ratios("gasUsedRatio"))
+            .zip(base_fees.windows(2))
+        {
+            assert_eq!(vector.gas_used as f64 / vector.gas_limit as f64, ratio);
+            assert_eq!([vector.base_fee, vector.next_base_fee], fees);
+        }
+
+        // The blob base fees follow from the excess blob gas and the blobs of each block.
+        let blob_fees = quantities("baseFeePerBlobGas");
+        let mut excess_blob_gas = FEE_HISTORY_EXCESS_BLOB_GAS;
+        for ((blobs, ratio), fees) in
+            FEE_HISTORY_BLOBS.iter().zip(ratios("blobGasUsedRatio")).zip(blob_fees.windows(2))
+        {
+            assert_eq!(*blobs as f64 / 6.0, ratio);
+            assert_eq!(calc_blob_gasprice(excess_blob_gas), fees[0]);
+            excess_blob_gas =
+                calc_excess_blob_gas(excess_blob_gas, (blobs * DATA_GAS_PER_BLOB) as u128);
+            assert_eq!(calc_blob_gasprice(excess_blob_gas), fees[1]);
+        }
+    }
+}
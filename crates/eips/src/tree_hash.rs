@@ -0,0 +1,206 @@
+//! SSZ [merkleization], to compute the hash tree roots of the types exchanged with the consensus
+//! layer.
+//!
+//! [merkleization]: https://github.com/ethereum/consensus-specs/blob/dev/ssz/simple-serialize.md#merkleization
+
+use crate::{eip4895::Withdrawal, eip6110::DepositRequest, eip7002::WithdrawalRequest};
+use alloy_primitives::{Address, Bloom, FixedBytes, B256, U256};
+use sha2::{Digest, Sha256};
+
+/// The maximum number of bytes of the extra data of an execution payload.
+pub const MAX_EXTRA_DATA_BYTES: usize = 32;
+
+/// The maximum number of bytes of a transaction of an execution payload.
+pub const MAX_BYTES_PER_TRANSACTION: usize = 1 << 30;
+
+/// The maximum number of transactions of an execution payload.
+pub const MAX_TRANSACTIONS_PER_PAYLOAD: usize = 1 << 20;
+
+/// The maximum number of withdrawals of an execution payload.
+pub const MAX_WITHDRAWALS_PER_PAYLOAD: usize = 16;
+
+/// The maximum number of deposit requests of an execution payload.
+pub const MAX_DEPOSIT_REQUESTS_PER_PAYLOAD: usize = 8192;
+
+/// The maximum number of withdrawal requests of an execution payload.
+pub const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize = 16;
+
+/// A type with an SSZ hash tree root.
+pub trait HashTreeRoot {
+    /// Returns the hash tree root of the value.
+    fn hash_tree_root(&self) -> B256;
+}
+
+/// Returns the SHA-256 hash of the concatenation of two nodes.
+fn hash_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+/// Returns the root of a tree of zero chunks of the given depth.
+pub fn zero_hash(depth: usize) -> B256 {
+    (0..depth).fold(B256::ZERO, |hash, _| hash_pair(&hash, &hash))
+}
+
+/// Packs bytes into chunks, padding the last chunk with zeros.
+pub fn pack(bytes: &[u8]) -> Vec<B256> {
+    bytes
+        .chunks(32)
+        .map(|chunk| {
+            let mut padded = B256::ZERO;
+            padded[..chunk.len()].copy_from_slice(chunk);
+            padded
+        })
+        .collect()
+}
+
+/// Returns the root of the tree of the given chunks, padded with zero chunks to the next power of
+/// two of `limit`.
+///
+/// # Panics
+///
+/// Panics if there are more chunks than `limit`.
+pub fn merkleize(chunks: &[B256], limit: usize) -> B256 {
+    assert!(chunks.len() <= limit.max(1), "{} chunks exceed the limit of {limit}", chunks.len());
+    let depth = limit.max(1).next_power_of_two().trailing_zeros() as usize;
+
+    let mut layer = chunks.to_vec();
+    let mut zero = B256::ZERO;
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect();
+        zero = hash_pair(&zero, &zero);
+    }
+    layer.first().copied().unwrap_or(zero)
+}
+
+/// Mixes the length of a list into the root of its elements.
+pub fn mix_in_length(root: B256, length: usize) -> B256 {
+    hash_pair(&root, &B256::from(U256::from(length).to_le_bytes::<32>()))
+}
+
+/// Returns the hash tree root of a container from the hash tree roots of its fields.
+pub fn container_root(fields: &[B256]) -> B256 {
+    merkleize(fields, fields.len())
+}
+
+/// Returns the hash tree root of a `ByteList[max_len]`.
+pub fn byte_list_root(bytes: &[u8], max_len: usize) -> B256 {
+    mix_in_length(merkleize(&pack(bytes), max_len.div_ceil(32)), bytes.len())
+}
+
+/// Returns the hash tree root of a `List[T, max_len]` of composite types, such as containers
+/// or byte lists.
+pub fn list_root<T: HashTreeRoot>(items: &[T], max_len: usize) -> B256 {
+    let roots = items.iter().map(HashTreeRoot::hash_tree_root).collect::<Vec<_>>();
+    mix_in_length(merkleize(&roots, max_len), items.len())
+}
+
+impl HashTreeRoot for u64 {
+    fn hash_tree_root(&self) -> B256 {
+        pack(&self.to_le_bytes())[0]
+    }
+}
+
+impl HashTreeRoot for U256 {
+    fn hash_tree_root(&self) -> B256 {
+        B256::from(self.to_le_bytes::<32>())
+    }
+}
+
+impl<const N: usize> HashTreeRoot for FixedBytes<N> {
+    fn hash_tree_root(&self) -> B256 {
+        merkleize(&pack(self.as_slice()), N.div_ceil(32))
+    }
+}
+
+impl HashTreeRoot for Address {
+    fn hash_tree_root(&self) -> B256 {
+        self.0.hash_tree_root()
+    }
+}
+
+impl HashTreeRoot for Bloom {
+    fn hash_tree_root(&self) -> B256 {
+        self.0.hash_tree_root()
+    }
+}
+
+impl HashTreeRoot for Withdrawal {
+    fn hash_tree_root(&self) -> B256 {
+        container_root(&[
+            self.index.hash_tree_root(),
+            self.validator_index.hash_tree_root(),
+            self.address.hash_tree_root(),
+            self.amount.hash_tree_root(),
+        ])
+    }
+}
+
+impl HashTreeRoot for DepositRequest {
+    fn hash_tree_root(&self) -> B256 {
+        container_root(&[
+            self.pubkey.hash_tree_root(),
+            self.withdrawal_credentials.hash_tree_root(),
+            self.amount.hash_tree_root(),
+            self.signature.hash_tree_root(),
+            self.index.hash_tree_root(),
+        ])
+    }
+}
+
+impl HashTreeRoot for WithdrawalRequest {
+    fn hash_tree_root(&self) -> B256 {
+        container_root(&[
+            self.source_address.hash_tree_root(),
+            self.validator_public_key.hash_tree_root(),
+            self.amount.hash_tree_root(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn merkleization() {
+        assert_eq!(
+            zero_hash(1),
+            b256!("f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b")
+        );
+        assert_eq!(merkleize(&[], 0), B256::ZERO);
+        assert_eq!(merkleize(&[], 5), zero_hash(3));
+
+        let (a, b) = (B256::repeat_byte(1), B256::repeat_byte(2));
+        assert_eq!(merkleize(&[a], 1), a);
+        assert_eq!(
+            merkleize(&[a, b, a], 3),
+            hash_pair(&hash_pair(&a, &b), &hash_pair(&a, &B256::ZERO))
+        );
+        assert_eq!(merkleize(&[a], 4), hash_pair(&hash_pair(&a, &B256::ZERO), &zero_hash(1)));
+
+        assert_eq!(5u64.hash_tree_root()[..], [&[5][..], &[0; 31]].concat()[..]);
+        let address = address!("00000000219ab540356cbb839cbe05303d7705fa");
+        assert_eq!(address.hash_tree_root()[..20], address[..]);
+        assert_eq!(byte_list_root(&[], 32), mix_in_length(B256::ZERO, 0));
+    }
+
+    #[test]
+    fn empty_list_roots() {
+        // The roots of the empty withdrawals and transactions of execution payload headers.
+        assert_eq!(
+            list_root::<Withdrawal>(&[], MAX_WITHDRAWALS_PER_PAYLOAD),
+            b256!("792930bbd5baac43bcc798ee49aa8185ef76bb3b44ba62b91d86ae569e4bb535")
+        );
+        assert_eq!(
+            mix_in_length(merkleize(&[], MAX_TRANSACTIONS_PER_PAYLOAD), 0),
+            b256!("7ffe241ea60187fdb0187bfa22de35d1f9bed7ab061d9401fd47e34a54fbede1")
+        );
+    }
+}
@@ -22,13 +22,13 @@ extern crate alloc;
 
 use alloc::{collections::BTreeMap, string::String};
 
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{hex, Address, Bytes, B256, U256};
 use alloy_serde::{
     num::{u128_opt_via_ruint, u128_via_ruint, u64_opt_via_ruint, u64_via_ruint},
     storage::deserialize_storage_map,
-    ttd::deserialize_json_ttd_opt,
+    ttd::{deserialize_json_ttd_opt, serialize_json_ttd_opt},
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 
 /// The genesis block specification.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -55,26 +55,36 @@ pub struct Genesis {
     /// The genesis header coinbase address.
     pub coinbase: Address,
     /// The initial state of accounts in the genesis block.
+    #[serde(serialize_with = "serialize_alloc")]
     pub alloc: BTreeMap<Address, GenesisAccount>,
     // NOTE: the following fields:
+    // * number
+    // * gas_used
+    // * parent_hash
     // * base_fee_per_gas
     // * excess_blob_gas
     // * blob_gas_used
-    // * number
     // should NOT be set in a real genesis file, but are included here for compatibility with
-    // consensus tests, which have genesis files with these fields populated.
-    /// The genesis header base fee
+    // consensus tests, which have genesis files with these fields populated. Like geth, the base
+    // fee and blob gas fields are serialized as `null` when unset.
+    /// The genesis block number
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "u64_opt_via_ruint")]
+    pub number: Option<u64>,
+    /// The genesis header gas used
     #[serde(default, skip_serializing_if = "Option::is_none", with = "u128_opt_via_ruint")]
+    pub gas_used: Option<u128>,
+    /// The genesis header parent hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_hash: Option<B256>,
+    /// The genesis header base fee
+    #[serde(default, with = "u128_opt_via_ruint")]
     pub base_fee_per_gas: Option<u128>,
     /// The genesis header excess blob gas
-    #[serde(default, skip_serializing_if = "Option::is_none", with = "u128_opt_via_ruint")]
+    #[serde(default, with = "u128_opt_via_ruint")]
     pub excess_blob_gas: Option<u128>,
     /// The genesis header blob gas used
-    #[serde(default, skip_serializing_if = "Option::is_none", with = "u128_opt_via_ruint")]
+    #[serde(default, with = "u128_opt_via_ruint")]
     pub blob_gas_used: Option<u128>,
-    /// The genesis block number
-    #[serde(default, skip_serializing_if = "Option::is_none", with = "u64_opt_via_ruint")]
-    pub number: Option<u64>,
 }
 
 impl Genesis {
@@ -214,11 +224,6 @@ impl Genesis {
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct GenesisAccount {
-    /// The nonce of the account at genesis.
-    #[serde(skip_serializing_if = "Option::is_none", with = "u64_opt_via_ruint", default)]
-    pub nonce: Option<u64>,
-    /// The balance of the account at genesis.
-    pub balance: U256,
     /// The account's bytecode at genesis.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub code: Option<Bytes>,
@@ -229,6 +234,11 @@ pub struct GenesisAccount {
         deserialize_with = "deserialize_storage_map"
     )]
     pub storage: Option<BTreeMap<B256, B256>>,
+    /// The balance of the account at genesis.
+    pub balance: U256,
+    /// The nonce of the account at genesis.
+    #[serde(skip_serializing_if = "Option::is_none", with = "u64_opt_via_ruint", default)]
+    pub nonce: Option<u64>,
     /// The account's private key. Should only be used for testing.
     #[serde(rename = "secretKey", default, skip_serializing_if = "Option::is_none")]
     pub private_key: Option<B256>,
@@ -293,6 +303,7 @@ pub struct ChainConfig {
     pub dao_fork_block: Option<u64>,
 
     /// Whether or not the node supports the DAO hard-fork.
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
     pub dao_fork_support: bool,
 
     /// The [EIP-150](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-150.md) hard fork block (None = no fork).
@@ -411,17 +422,30 @@ pub struct ChainConfig {
     )]
     pub prague_time: Option<u64>,
 
+    /// Verkle switch time (None = no fork, 0 = already on verkle).
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "u64_opt_via_ruint::deserialize"
+    )]
+    pub verkle_time: Option<u64>,
+
     /// Total difficulty reached that triggers the merge consensus upgrade.
     #[serde(
         skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_json_ttd_opt",
         deserialize_with = "deserialize_json_ttd_opt"
     )]
     pub terminal_total_difficulty: Option<U256>,
 
     /// A flag specifying that the network already passed the terminal total difficulty. Its
     /// purpose is to disable legacy sync without having seen the TTD locally.
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
     pub terminal_total_difficulty_passed: bool,
 
+    /// The deposit contract address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_contract_address: Option<Address>,
+
     /// Ethash parameters.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ethash: Option<EthashConfig>,
@@ -433,10 +457,6 @@ pub struct ChainConfig {
     /// Additional fields specific to each chain.
     #[serde(flatten, default)]
     pub extra_fields: BTreeMap<String, serde_json::Value>,
-
-    /// The deposit contract address
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub deposit_contract_address: Option<Address>,
 }
 
 impl ChainConfig {
@@ -519,6 +539,13 @@ impl ChainConfig {
             && self.is_active_at_timestamp(self.cancun_time, timestamp)
     }
 
+    /// Checks if the blockchain is active at or after the Prague fork block and the specified
+    /// timestamp.
+    pub fn is_prague_active_at_block_and_timestamp(&self, block: u64, timestamp: u64) -> bool {
+        self.is_london_active_at_block(block)
+            && self.is_active_at_timestamp(self.prague_time, timestamp)
+    }
+
     // Private function handling the comparison logic for block numbers
     fn is_active_at_block(&self, config_block: Option<u64>, block: u64) -> bool {
         config_block.map_or(false, |cb| cb <= block)
@@ -530,6 +557,14 @@ impl ChainConfig {
     }
 }
 
+/// Serializes the genesis alloc with unprefixed addresses as keys, like geth does.
+fn serialize_alloc<S: Serializer>(
+    alloc: &BTreeMap<Address, GenesisAccount>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(alloc.iter().map(|(address, account)| (hex::encode(address), account)))
+}
+
 // used only for serde
 #[inline]
 const fn mainnet_id() -> u64 {
@@ -556,7 +591,6 @@ pub struct CliqueConfig {
 mod tests {
     use super::*;
     use alloc::vec;
-    use alloy_primitives::hex;
     use core::str::FromStr;
 
     #[test]
@@ -1334,6 +1368,8 @@ mod tests {
                 excess_blob_gas: None,
                 blob_gas_used: None,
                 number: None,
+                gas_used: None,
+                parent_hash: Some(B256::ZERO),
                 alloc: BTreeMap::from_iter(vec![
                 (
                     Address::from_str("0xdbdbdb2cbd23b783741e8d7fcf51e459b497e4a6").unwrap(),
@@ -1455,6 +1491,7 @@ mod tests {
         let s = serde_json::to_string_pretty(&gen).unwrap();
         let gen2 = serde_json::from_str::<Genesis>(&s).unwrap();
         assert_eq!(gen, gen2);
+        assert_eq!(serde_json::to_string(&gen).unwrap(), mainnet.trim_end());
     }
 
     #[test]
@@ -1464,6 +1501,7 @@ mod tests {
         let s = serde_json::to_string_pretty(&gen).unwrap();
         let gen2 = serde_json::from_str::<Genesis>(&s).unwrap();
         assert_eq!(gen, gen2);
+        assert_eq!(serde_json::to_string(&gen).unwrap(), sepolia.trim_end());
     }
 
     #[test]
@@ -1473,6 +1511,7 @@ mod tests {
         let s = serde_json::to_string_pretty(&gen).unwrap();
         let gen2 = serde_json::from_str::<Genesis>(&s).unwrap();
         assert_eq!(gen, gen2);
+        assert_eq!(serde_json::to_string(&gen).unwrap(), holesky.trim_end());
     }
 
     #[test]
@@ -1501,4 +1540,15 @@ mod tests {
         let actual_object_value = genesis.config.extra_fields.get("object_field").unwrap();
         assert_eq!(actual_object_value, &serde_json::json!({"sub_field": "sub_value"}));
     }
+
+    #[test]
+    fn roundtrip_geth_chain_config() {
+        let geth_genesis = r#"{"config":{"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"shanghaiTime":0,"cancunTime":0,"pragueTime":10,"verkleTime":20,"terminalTotalDifficulty":0,"depositContractAddress":"0x00000000219ab540356cbb839cbe05303d7705fa","clique":{"period":5,"epoch":30000}},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x1c9c380","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{"00000000219ab540356cbb839cbe05303d7705fa":{"code":"0x60","storage":{"0x0000000000000000000000000000000000000000000000000000000000000001":"0x0000000000000000000000000000000000000000000000000000000000000002"},"balance":"0x0","nonce":"0x1"}},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00","excessBlobGas":null,"blobGasUsed":null}"#;
+        let genesis: Genesis = serde_json::from_str(geth_genesis).unwrap();
+        assert_eq!(genesis.config.verkle_time, Some(20));
+        assert!(genesis.config.is_prague_active_at_block_and_timestamp(0, 10));
+        assert!(!genesis.config.is_prague_active_at_block_and_timestamp(0, 9));
+        assert!(genesis.config.extra_fields.is_empty());
+        assert_eq!(serde_json::to_string(&genesis).unwrap(), geth_genesis);
+    }
 }
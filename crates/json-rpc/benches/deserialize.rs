@@ -0,0 +1,76 @@
+//! Benchmarks of the deserialization of large results.
+//!
+//! Run with `--features simd-json` to compare `simd-json` with `serde_json`.
+
+use alloy_json_rpc::{try_deserialize_ok, RpcResult, RpcReturn};
+use alloy_primitives::{Address, Bytes, LogData, B256, U256};
+use alloy_rpc_types::{Block, BlockTransactions, Log, Transaction};
+use criterion::{criterion_group, criterion_main, Criterion};
+use serde_json::value::RawValue;
+use std::hint::black_box;
+
+fn logs() -> Vec<Log> {
+    (0..1_000u64)
+        .map(|i| Log {
+            inner: alloy_primitives::Log {
+                address: Address::with_last_byte(i as u8),
+                data: LogData::new_unchecked(
+                    vec![B256::with_last_byte(1), B256::from(U256::from(i)), B256::ZERO],
+                    Bytes::from(vec![i as u8; 64]),
+                ),
+            },
+            block_hash: Some(B256::with_last_byte(2)),
+            block_number: Some(19_000_000 + i / 100),
+            block_timestamp: None,
+            transaction_hash: Some(B256::from(U256::from(i))),
+            transaction_index: Some(i % 100),
+            log_index: Some(i),
+            removed: false,
+        })
+        .collect()
+}
+
+fn block() -> Block {
+    let transactions = (0..200u64)
+        .map(|i| Transaction {
+            hash: B256::from(U256::from(i)),
+            nonce: i,
+            block_hash: Some(B256::with_last_byte(2)),
+            block_number: Some(19_000_000),
+            transaction_index: Some(i),
+            from: Address::with_last_byte(i as u8),
+            to: Some(Address::with_last_byte(3)),
+            value: U256::from(i) * U256::from(10).pow(U256::from(18)),
+            gas: 21_000,
+            input: Bytes::from(vec![i as u8; 132]),
+            chain_id: Some(1),
+            ..Default::default()
+        })
+        .collect();
+    Block { transactions: BlockTransactions::Full(transactions), ..Default::default() }
+}
+
+fn bench<T: RpcReturn + serde::Serialize>(c: &mut Criterion, name: &str, value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    let raw = RawValue::from_string(json.clone()).unwrap();
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("serde_json", |b| {
+        b.iter(|| serde_json::from_str::<T>(black_box(&json)).unwrap())
+    });
+    group.bench_function("try_deserialize_ok", |b| {
+        b.iter(|| {
+            let result: RpcResult<&RawValue, (), Box<RawValue>> = Ok(black_box(&*raw));
+            try_deserialize_ok::<_, T, _, _>(result).unwrap()
+        })
+    });
+    group.finish();
+}
+
+fn deserialize(c: &mut Criterion) {
+    bench(c, "eth_getLogs", &logs());
+    bench(c, "eth_getBlockByNumber", &block());
+}
+
+criterion_group!(benches, deserialize);
+criterion_main!(benches);
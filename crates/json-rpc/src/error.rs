@@ -1,5 +1,11 @@
 use crate::{ErrorPayload, RpcReturn};
+use alloy_primitives::Bytes;
 use serde_json::value::RawValue;
+use std::{
+    borrow::{Borrow, Cow},
+    fmt,
+    time::Duration,
+};
 
 /// An RPC error.
 #[derive(Debug, thiserror::Error)]
@@ -51,6 +57,49 @@ pub enum RpcError<E, ErrResp = Box<RawValue>> {
         #[from]
         E,
     ),
+
+    /// Any of the other errors, with the context of the request that caused it.
+    ///
+    /// This is only returned by clients that opt in to it, as the context may include the
+    /// request parameters. The `is_*` and `as_*` methods look through it.
+    // The error is formatted through a reference and not marked as the source, as `thiserror`
+    // would otherwise bound the `Display` and `Error` impls on the recursive type.
+    #[error("{} ({context})", &**.error)]
+    WithContext {
+        /// The underlying error.
+        error: Box<RpcError<E, ErrResp>>,
+        /// The context of the request.
+        context: Box<RequestContext>,
+    },
+}
+
+/// The context of a failed request, attached to an [`RpcError::WithContext`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RequestContext {
+    /// The method of the request.
+    pub method: Cow<'static, str>,
+    /// The label of the endpoint the request was sent to, if any.
+    pub endpoint: Option<String>,
+    /// The serialized parameters of the request, possibly truncated, if echoing them is enabled.
+    pub params: Option<String>,
+    /// The number of times the request was sent, including the failed attempt.
+    pub attempt: u32,
+    /// The time elapsed between sending the request and the failure.
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "method `{}`", self.method)?;
+        if let Some(endpoint) = &self.endpoint {
+            write!(f, ", endpoint `{endpoint}`")?;
+        }
+        write!(f, ", attempt {}, elapsed {:?}", self.attempt, self.elapsed)?;
+        if let Some(params) = &self.params {
+            write!(f, ", params {params}")?;
+        }
+        Ok(())
+    }
 }
 
 impl<E, ErrResp> RpcError<E, ErrResp>
@@ -97,46 +146,96 @@ impl<E, ErrResp> RpcError<E, ErrResp> {
         Self::SerError(err)
     }
 
+    /// Attach the context of the request that caused the error. If the error already has a
+    /// context, it is replaced.
+    pub fn with_context(self, context: RequestContext) -> Self {
+        Self::WithContext { error: Box::new(self.without_context()), context: Box::new(context) }
+    }
+
+    /// Returns the context of the request that caused the error, if attached.
+    pub const fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Self::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the error without its request context.
+    pub fn without_context(self) -> Self {
+        match self {
+            Self::WithContext { error, .. } => *error,
+            err => err,
+        }
+    }
+
+    /// Returns the error, looking through its request context.
+    const fn inner(&self) -> &Self {
+        match self {
+            Self::WithContext { error, .. } => error,
+            err => err,
+        }
+    }
+
     /// Check if the error is a serialization error.
     pub const fn is_ser_error(&self) -> bool {
-        matches!(self, Self::SerError(_))
+        matches!(self.inner(), Self::SerError(_))
     }
 
     /// Check if the error is a deserialization error.
     pub const fn is_deser_error(&self) -> bool {
-        matches!(self, Self::DeserError { .. })
+        matches!(self.inner(), Self::DeserError { .. })
     }
 
     /// Check if the error is a transport error.
     pub const fn is_transport_error(&self) -> bool {
-        matches!(self, Self::Transport(_))
+        matches!(self.inner(), Self::Transport(_))
     }
 
     /// Check if the error is an error response.
     pub const fn is_error_resp(&self) -> bool {
-        matches!(self, Self::ErrorResp(_))
+        matches!(self.inner(), Self::ErrorResp(_))
     }
 
     /// Check if the error is a null response.
     pub const fn is_null_resp(&self) -> bool {
-        matches!(self, Self::NullResp)
+        matches!(self.inner(), Self::NullResp)
     }
 
     /// Check if the error is an unsupported feature error.
     pub const fn is_unsupported_feature(&self) -> bool {
-        matches!(self, Self::UnsupportedFeature(_))
+        matches!(self.inner(), Self::UnsupportedFeature(_))
     }
 
     /// Check if the error is a local usage error.
     pub const fn is_local_usage_error(&self) -> bool {
-        matches!(self, Self::LocalUsageError(_))
+        matches!(self.inner(), Self::LocalUsageError(_))
+    }
+
+    /// Fallible conversion to a transport error.
+    pub const fn as_transport_err(&self) -> Option<&E> {
+        match self.inner() {
+            Self::Transport(err) => Some(err),
+            _ => None,
+        }
     }
 
     /// Fallible conversion to an error response.
     pub const fn as_error_resp(&self) -> Option<&ErrorPayload<ErrResp>> {
-        match self {
+        match self.inner() {
             Self::ErrorResp(err) => Some(err),
             _ => None,
         }
     }
 }
+
+impl<E, ErrResp> RpcError<E, ErrResp>
+where
+    ErrResp: Borrow<RawValue>,
+{
+    /// Returns the revert data of the error response, if any.
+    ///
+    /// See [`ErrorPayload::as_revert_data`] for the shapes of revert data this accepts.
+    pub fn as_revert_data(&self) -> Option<Bytes> {
+        self.as_error_resp().and_then(ErrorPayload::as_revert_data)
+    }
+}
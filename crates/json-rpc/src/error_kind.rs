@@ -0,0 +1,373 @@
+use crate::{ErrorPayload, RpcError};
+use alloy_primitives::Bytes;
+use serde_json::value::RawValue;
+use std::borrow::Borrow;
+
+/// The kind of an [`ErrorPayload`], classified from its code and message.
+///
+/// Standard codes are those of [JSON-RPC 2.0] and [EIP-1474]. As nodes report many errors with
+/// the generic `-32000` code, or with codes of their own, errors are first classified by the
+/// message patterns of geth, Erigon, Nethermind, Besu and Reth, and by the code otherwise.
+///
+/// [JSON-RPC 2.0]: https://www.jsonrpc.org/specification#error_object
+/// [EIP-1474]: https://eips.ethereum.org/EIPS/eip-1474#error-codes
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Invalid JSON was received by the server (`-32700`).
+    ParseError,
+    /// The JSON sent is not a valid request object (`-32600`).
+    InvalidRequest,
+    /// The method does not exist or is not available (`-32601`).
+    MethodNotFound,
+    /// Invalid method parameters (`-32602`).
+    InvalidParams,
+    /// Internal JSON-RPC error (`-32603`).
+    InternalError,
+    /// Missing or invalid parameters (`-32000`), for errors not classified otherwise.
+    InvalidInput,
+    /// The requested resource cannot be found (`-32001`).
+    ResourceNotFound,
+    /// The requested resource is not available (`-32002`).
+    ResourceUnavailable,
+    /// The transaction creation failed (`-32003`).
+    TransactionRejected,
+    /// The method is not implemented (`-32004`).
+    MethodNotSupported,
+    /// The request exceeds a defined limit (`-32005`), such as the block range or the number of
+    /// results of a log query.
+    ///
+    /// Sending the same request again fails the same way, unlike [`RateLimited`](Self::RateLimited)
+    /// requests: the request must be made smaller, e.g. by splitting its block range.
+    LimitExceeded,
+    /// The version of the JSON-RPC protocol is not supported (`-32006`).
+    VersionNotSupported,
+    /// The call or gas estimation reverted, with the revert data if the node returned it.
+    ///
+    /// See [`ErrorPayload::as_revert_data`] for the shapes of revert data this accepts.
+    ExecutionReverted {
+        /// The data returned by the reverted execution.
+        data: Option<Bytes>,
+    },
+    /// The request was rejected because of rate limiting.
+    RateLimited,
+    /// The block or header of the request is not known by the node yet.
+    HeaderNotFound,
+    /// The filter of the request is not installed on the node, as it expired or was installed on
+    /// another node behind a load balancer.
+    FilterNotFound,
+    /// The nonce of the transaction is lower than the nonce of its sender.
+    NonceTooLow,
+    /// The nonce of the transaction is higher than the next nonce of its sender.
+    NonceTooHigh,
+    /// The sender cannot pay for the gas and value of the transaction.
+    InsufficientFunds,
+    /// The transaction is already in the transaction pool.
+    AlreadyKnown,
+    /// The type of the transaction is not supported by the node, such as EIP-1559 transactions on
+    /// chains without the London upgrade.
+    TransactionTypeNotSupported,
+    /// The fees of the transaction are too low to replace the pending transaction with the same
+    /// nonce.
+    ReplacementUnderpriced,
+    /// The fees of the transaction are below the minimum accepted by the node.
+    Underpriced,
+    /// The gas limit of the transaction is below its intrinsic gas.
+    IntrinsicGasTooLow,
+    /// The gas limit of the transaction exceeds the gas limit of the block.
+    ExceedsBlockGasLimit,
+    /// Any other error, with its code.
+    Other(i64),
+}
+
+/// Message patterns of node-specific errors, matched case-insensitively. Nethermind reports
+/// transaction pool errors with the names of their variants, such as `OldNonce`.
+const MESSAGE_PATTERNS: &[(&[&str], ErrorKind)] = &[
+    (
+        &["rate limit", "too many requests", "request limit", "compute units per second"],
+        ErrorKind::RateLimited,
+    ),
+    (
+        &[
+            "query returned more than",
+            "query exceeds max results",
+            "response size",
+            "response too large",
+            "block range",
+            "range too large",
+            "range is too large",
+        ],
+        ErrorKind::LimitExceeded,
+    ),
+    (&["method not found"], ErrorKind::MethodNotFound),
+    (&["header not found", "unknown block"], ErrorKind::HeaderNotFound),
+    (&["filter not found", "filter with id"], ErrorKind::FilterNotFound),
+    (&["nonce too low", "oldnonce"], ErrorKind::NonceTooLow),
+    (&["nonce too high", "noncegap"], ErrorKind::NonceTooHigh),
+    (&["insufficient funds", "insufficientfunds"], ErrorKind::InsufficientFunds),
+    (&["already known", "alreadyknown", "already imported"], ErrorKind::AlreadyKnown),
+    (
+        &[
+            "transaction type not supported",
+            "unsupported transaction type",
+            "invalid transaction type",
+        ],
+        ErrorKind::TransactionTypeNotSupported,
+    ),
+    (
+        &["replacement transaction underpriced", "replacementnotallowed"],
+        ErrorKind::ReplacementUnderpriced,
+    ),
+    (&["transaction underpriced", "feetoolow"], ErrorKind::Underpriced),
+    (&["intrinsic gas too low", "gaslimitbelowintrinsicgas"], ErrorKind::IntrinsicGasTooLow),
+    (&["exceeds block gas limit", "gaslimitexceeded"], ErrorKind::ExceedsBlockGasLimit),
+];
+
+impl ErrorKind {
+    /// Classifies an error from its code, message and revert data.
+    pub fn classify(code: i64, message: &str, revert_data: Option<Bytes>) -> Self {
+        let message = message.to_lowercase();
+        // geth, Erigon, Besu and Reth revert with code 3, Nethermind with `-32015`.
+        if code == 3
+            || code == -32015
+            || message.starts_with("execution reverted")
+            || message.contains("vm exception while processing transaction")
+        {
+            return Self::ExecutionReverted { data: revert_data };
+        }
+        if message.contains("eip-1559") && message.contains("not supported") {
+            return Self::TransactionTypeNotSupported;
+        }
+        if let Some((_, kind)) = MESSAGE_PATTERNS
+            .iter()
+            .find(|(patterns, _)| patterns.iter().any(|pattern| message.contains(pattern)))
+        {
+            return kind.clone();
+        }
+        match code {
+            -32700 => Self::ParseError,
+            -32600 => Self::InvalidRequest,
+            -32601 => Self::MethodNotFound,
+            -32602 => Self::InvalidParams,
+            -32603 => Self::InternalError,
+            -32000 => Self::InvalidInput,
+            -32001 => Self::ResourceNotFound,
+            -32002 => Self::ResourceUnavailable,
+            -32003 => Self::TransactionRejected,
+            -32004 => Self::MethodNotSupported,
+            -32005 => Self::LimitExceeded,
+            -32006 => Self::VersionNotSupported,
+            // HTTP status codes reported as error codes by some providers.
+            429 | -32016 => Self::RateLimited,
+            code => Self::Other(code),
+        }
+    }
+
+    /// Returns `true` if the request may succeed if sent again later, as it failed because of
+    /// rate limiting, or a node lagging behind.
+    ///
+    /// Requests exceeding a [limit](Self::LimitExceeded) of the node are not retryable as is.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::RateLimited | Self::ResourceUnavailable | Self::HeaderNotFound)
+    }
+
+    /// Returns `true` if the error is an execution revert.
+    pub const fn is_revert(&self) -> bool {
+        matches!(self, Self::ExecutionReverted { .. })
+    }
+
+    /// Returns the revert data, if the error is an execution revert with data.
+    pub const fn revert_data(&self) -> Option<&Bytes> {
+        match self {
+            Self::ExecutionReverted { data } => data.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl<Data: Borrow<RawValue>> ErrorPayload<Data> {
+    /// Classifies the error, see [`ErrorKind`].
+    pub fn kind(&self) -> ErrorKind {
+        ErrorKind::classify(self.code, &self.message, self.as_revert_data())
+    }
+}
+
+/// An error that may be resolved by sending the request again.
+///
+/// This is implemented by [`RpcError`], and by the transport errors it wraps, so that layers
+/// retrying requests can tell transient failures, such as rate limiting, from permanent ones.
+pub trait RetryableError {
+    /// Returns `true` if sending the request again may succeed.
+    fn is_retryable(&self) -> bool;
+}
+
+impl<Data: Borrow<RawValue>> RetryableError for ErrorPayload<Data> {
+    fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
+
+impl<E: RetryableError, ErrResp: Borrow<RawValue>> RetryableError for RpcError<E, ErrResp> {
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::ErrorResp(err) => err.is_retryable(),
+            Self::Transport(err) => err.is_retryable(),
+            Self::WithContext { error, .. } => error.is_retryable(),
+            _ => false,
+        }
+    }
+}
+
+impl<E, ErrResp: Borrow<RawValue>> RpcError<E, ErrResp> {
+    /// Classifies the error response, or returns `None` if the error is not an error response.
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        self.as_error_resp().map(ErrorPayload::kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::bytes;
+
+    fn kind(json: &str) -> ErrorKind {
+        serde_json::from_str::<ErrorPayload>(json).unwrap().kind()
+    }
+
+    #[test]
+    fn standard_codes() {
+        assert_eq!(kind(r#"{"code":-32700,"message":"parse error"}"#), ErrorKind::ParseError);
+        assert_eq!(
+            kind(
+                r#"{"code":-32601,"message":"the method eth_foo does not exist/is not available"}"#
+            ),
+            ErrorKind::MethodNotFound
+        );
+        assert_eq!(
+            kind(r#"{"code":-32602,"message":"invalid argument 0"}"#),
+            ErrorKind::InvalidParams
+        );
+        assert_eq!(
+            kind(r#"{"code":-32000,"message":"missing value for required argument 0"}"#),
+            ErrorKind::InvalidInput
+        );
+        assert_eq!(
+            kind(r#"{"code":-32005,"message":"query returned more than 10000 results"}"#),
+            ErrorKind::LimitExceeded
+        );
+        assert_eq!(kind(r#"{"code":-39999,"message":"b"}"#), ErrorKind::Other(-39999));
+    }
+
+    #[test]
+    fn reverts() {
+        let revert = bytes!("08c379a0");
+        // geth, Erigon, Besu, Reth
+        let err = kind(r#"{"code":3,"message":"execution reverted: abcd","data":"0x08c379a0"}"#);
+        assert_eq!(err, ErrorKind::ExecutionReverted { data: Some(revert.clone()) });
+        assert_eq!(err.revert_data(), Some(&revert));
+        // Nethermind
+        let err =
+            kind(r#"{"code":-32015,"message":"VM execution error.","data":"Reverted 0x08c379a0"}"#);
+        assert_eq!(err, ErrorKind::ExecutionReverted { data: Some(revert) });
+        // Without data.
+        let err = kind(r#"{"code":-32000,"message":"execution reverted"}"#);
+        assert_eq!(err, ErrorKind::ExecutionReverted { data: None });
+        assert!(err.is_revert());
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn node_messages() {
+        for (json, expected) in [
+            (
+                r#"{"code":-32000,"message":"nonce too low: next nonce 5, tx nonce 4"}"#,
+                ErrorKind::NonceTooLow,
+            ),
+            (r#"{"code":-32010,"message":"OldNonce"}"#, ErrorKind::NonceTooLow),
+            (
+                r#"{"code":-32000,"message":"insufficient funds for gas * price + value"}"#,
+                ErrorKind::InsufficientFunds,
+            ),
+            (r#"{"code":-32000,"message":"already known"}"#, ErrorKind::AlreadyKnown),
+            (r#"{"code":-32010,"message":"AlreadyKnown"}"#, ErrorKind::AlreadyKnown),
+            (
+                r#"{"code":-32000,"message":"replacement transaction underpriced"}"#,
+                ErrorKind::ReplacementUnderpriced,
+            ),
+            (
+                r#"{"code":-32000,"message":"transaction underpriced: tip needed 1, tip permitted 0"}"#,
+                ErrorKind::Underpriced,
+            ),
+            (r#"{"code":-32000,"message":"intrinsic gas too low"}"#, ErrorKind::IntrinsicGasTooLow),
+            (
+                r#"{"code":-32000,"message":"exceeds block gas limit"}"#,
+                ErrorKind::ExceedsBlockGasLimit,
+            ),
+            (r#"{"code":-32000,"message":"header not found"}"#, ErrorKind::HeaderNotFound),
+            (r#"{"code":-32000,"message":"method not found"}"#, ErrorKind::MethodNotFound),
+            (
+                r#"{"code":-32000,"message":"transaction type not supported"}"#,
+                ErrorKind::TransactionTypeNotSupported,
+            ),
+            (
+                r#"{"code":-32000,"message":"eip-1559 transactions are not supported"}"#,
+                ErrorKind::TransactionTypeNotSupported,
+            ),
+            (
+                r#"{"code":-32000,"message":"query returned more than 10000 results"}"#,
+                ErrorKind::LimitExceeded,
+            ),
+            (
+                r#"{"code":-32602,"message":"Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range"}"#,
+                ErrorKind::LimitExceeded,
+            ),
+            (r#"{"code":-32000,"message":"block range is too wide"}"#, ErrorKind::LimitExceeded),
+            (r#"{"code":-32000,"message":"filter not found"}"#, ErrorKind::FilterNotFound),
+            // Nethermind
+            (
+                r#"{"code":-32000,"message":"Filter with id: '0x1' does not exist."}"#,
+                ErrorKind::FilterNotFound,
+            ),
+            (
+                r#"{"code":-32007,"message":"20/second request limit reached - reduce calls per second or upgrade your account at quicknode.com"}"#,
+                ErrorKind::RateLimited,
+            ),
+            (
+                r#"{"code":429,"message":"Your app has exceeded its compute units per second capacity."}"#,
+                ErrorKind::RateLimited,
+            ),
+            (
+                r#"{"code":-32005,"message":"daily request count exceeded, request rate limited"}"#,
+                ErrorKind::RateLimited,
+            ),
+        ] {
+            assert_eq!(kind(json), expected, "{json}");
+        }
+    }
+
+    #[test]
+    fn retryable() {
+        #[derive(Debug)]
+        struct Transient;
+
+        impl RetryableError for Transient {
+            fn is_retryable(&self) -> bool {
+                true
+            }
+        }
+
+        let resp =
+            |json: &str| RpcError::<Transient>::ErrorResp(serde_json::from_str(json).unwrap());
+        assert!(!resp(r#"{"code":-32005,"message":"limit exceeded"}"#).is_retryable());
+        assert!(resp(r#"{"code":-32005,"message":"request rate limited"}"#).is_retryable());
+        assert!(resp(r#"{"code":-32000,"message":"header not found"}"#).is_retryable());
+        assert!(!resp(r#"{"code":-32000,"message":"nonce too low"}"#).is_retryable());
+        assert_eq!(
+            resp(r#"{"code":-32000,"message":"nonce too low"}"#).error_kind(),
+            Some(ErrorKind::NonceTooLow)
+        );
+        assert!(RpcError::<_>::Transport(Transient).is_retryable());
+        assert!(!RpcError::<Transient>::NullResp.is_retryable());
+        assert_eq!(RpcError::<Transient>::NullResp.error_kind(), None);
+    }
+}
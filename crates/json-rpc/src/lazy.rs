@@ -0,0 +1,102 @@
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::RawValue;
+use std::{fmt, marker::PhantomData};
+
+/// A JSON-RPC return value kept as raw JSON, to be deserialized into `T` later.
+///
+/// Deserializing large responses, such as full blocks, traces or logs, can take longer than
+/// receiving them. Requesting a `LazyValue<T>` instead of a `T` returns as soon as the response
+/// is received, and leaves the deserialization to the caller, which can then defer it, or run it
+/// on another thread.
+///
+/// ```
+/// use alloy_json_rpc::LazyValue;
+///
+/// let lazy: LazyValue<Vec<u64>> = serde_json::from_str("[1, 2, 3]").unwrap();
+/// assert_eq!(lazy.get(), "[1, 2, 3]");
+/// assert_eq!(lazy.decode().unwrap(), vec![1, 2, 3]);
+/// ```
+pub struct LazyValue<T> {
+    raw: Box<RawValue>,
+    _pd: PhantomData<fn() -> T>,
+}
+
+impl<T> LazyValue<T> {
+    /// Wraps a raw JSON value.
+    pub const fn new(raw: Box<RawValue>) -> Self {
+        Self { raw, _pd: PhantomData }
+    }
+
+    /// Returns the raw JSON text of the value.
+    pub fn get(&self) -> &str {
+        self.raw.get()
+    }
+
+    /// Returns the raw JSON value.
+    pub const fn as_raw(&self) -> &RawValue {
+        &self.raw
+    }
+
+    /// Consumes the wrapper, returning the raw JSON value.
+    pub fn into_raw(self) -> Box<RawValue> {
+        self.raw
+    }
+}
+
+impl<T: DeserializeOwned> LazyValue<T> {
+    /// Deserializes the value.
+    pub fn decode(&self) -> serde_json::Result<T> {
+        serde_json::from_str(self.raw.get())
+    }
+}
+
+impl<T> From<Box<RawValue>> for LazyValue<T> {
+    fn from(raw: Box<RawValue>) -> Self {
+        Self::new(raw)
+    }
+}
+
+impl<T> Clone for LazyValue<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.raw.clone())
+    }
+}
+
+impl<T> fmt::Debug for LazyValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyValue").field("len", &self.raw.get().len()).finish_non_exhaustive()
+    }
+}
+
+impl<T> Serialize for LazyValue<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.raw.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for LazyValue<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Box::<RawValue>::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Response;
+
+    #[test]
+    fn deferred_decoding() {
+        let response = r#"{"jsonrpc":"2.0","id":1,"result":{"number":"0x1","transactions":[]}}"#;
+        let response: Response = serde_json::from_str(response).unwrap();
+        let lazy: LazyValue<serde_json::Value> =
+            response.payload.try_success_as().unwrap().unwrap();
+        assert_eq!(lazy.get(), r#"{"number":"0x1","transactions":[]}"#);
+        assert_eq!(lazy.decode().unwrap()["number"], "0x1");
+
+        // Invalid values only fail when decoded.
+        let lazy = LazyValue::<u64>::new(RawValue::from_string("\"0x1\"".into()).unwrap());
+        assert!(lazy.decode().is_err());
+        assert_eq!(serde_json::to_string(&lazy).unwrap(), "\"0x1\"");
+    }
+}
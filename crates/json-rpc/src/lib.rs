@@ -92,7 +92,13 @@ mod common;
 pub use common::Id;
 
 mod error;
-pub use error::RpcError;
+pub use error::{RequestContext, RpcError};
+
+mod error_kind;
+pub use error_kind::{ErrorKind, RetryableError};
+
+mod lazy;
+pub use lazy::LazyValue;
 
 mod notification;
 pub use notification::{EthNotification, PubSubItem};
@@ -109,6 +115,11 @@ pub use response::{
     ResponsePayload,
 };
 
+mod trace_context;
+pub use trace_context::{
+    ParseTraceContextError, TraceContext, TRACEPARENT_HEADER, TRACESTATE_HEADER,
+};
+
 mod result;
 pub use result::{
     transform_response, transform_result, try_deserialize_ok, BorrowedRpcResult, RpcResult,
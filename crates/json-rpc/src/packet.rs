@@ -1,10 +1,15 @@
-use crate::{ErrorPayload, Id, Response, SerializedRequest};
+use crate::{ErrorPayload, Id, Response, SerializedRequest, TraceContext};
 use serde::{
     de::{self, Deserializer, MapAccess, SeqAccess, Visitor},
     Deserialize, Serialize,
 };
 use serde_json::value::RawValue;
-use std::{collections::HashSet, fmt, marker::PhantomData};
+use std::{collections::HashSet, fmt, marker::PhantomData, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
 
 /// A [`RequestPacket`] is a [`SerializedRequest`] or a batch of serialized
 /// request.
@@ -72,6 +77,53 @@ impl RequestPacket {
         }
     }
 
+    /// Get the trace context of the packet, i.e. that of its first request carrying one.
+    pub fn trace_context(&self) -> Option<&TraceContext> {
+        match self {
+            Self::Single(single) => single.trace_context(),
+            Self::Batch(batch) => batch.iter().find_map(SerializedRequest::trace_context),
+        }
+    }
+
+    /// Get the `Authorization` header value of the packet, i.e. that of its first request carrying
+    /// one.
+    pub fn authorization(&self) -> Option<&str> {
+        match self {
+            Self::Single(single) => single.authorization(),
+            Self::Batch(batch) => batch.iter().find_map(SerializedRequest::authorization),
+        }
+    }
+
+    /// Get the timeout of the packet, i.e. the longest of those of its requests, if any is set.
+    pub fn timeout(&self) -> Option<Duration> {
+        match self {
+            Self::Single(single) => single.timeout(),
+            Self::Batch(batch) => batch.iter().filter_map(SerializedRequest::timeout).max(),
+        }
+    }
+
+    /// Get the deadline of the packet, i.e. the latest of those of its requests, if any is set.
+    ///
+    /// Layers retrying or failing over requests should stop once it passed.
+    pub fn deadline(&self) -> Option<Instant> {
+        match self {
+            Self::Single(single) => single.deadline(),
+            Self::Batch(batch) => batch.iter().filter_map(SerializedRequest::deadline).max(),
+        }
+    }
+
+    /// Set the `Authorization` header value of all requests in the packet.
+    pub fn set_authorization(&mut self, authorization: Option<String>) {
+        match self {
+            Self::Single(single) => single.set_authorization(authorization),
+            Self::Batch(batch) => {
+                for req in batch {
+                    req.set_authorization(authorization.clone());
+                }
+            }
+        }
+    }
+
     /// Get the number of requests in the packet.
     pub fn len(&self) -> usize {
         match self {
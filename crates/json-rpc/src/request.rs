@@ -1,11 +1,23 @@
-use crate::{common::Id, RpcParam};
+use crate::{common::Id, RpcParam, TraceContext};
 use alloy_primitives::{keccak256, B256};
-use serde::{de::DeserializeOwned, ser::SerializeMap, Deserialize, Serialize};
+use serde::{
+    de::DeserializeOwned,
+    ser::{Error, SerializeMap},
+    Deserialize, Serialize,
+};
 use serde_json::value::RawValue;
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// Timers of `std` are not available in the browser.
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
 
 /// `RequestMeta` contains the [`Id`] and method name of a request.
-#[derive(Clone, Debug)]
+///
+/// The [`Debug`](fmt::Debug) output redacts the `Authorization` header value.
+#[derive(Clone)]
 pub struct RequestMeta {
     /// The method name.
     pub method: Cow<'static, str>,
@@ -13,12 +25,109 @@ pub struct RequestMeta {
     pub id: Id,
     /// Whether the request is a subscription, other than `eth_subscribe`.
     is_subscription: bool,
+    /// The trace context of the caller, propagated by transports that support it.
+    trace_context: Option<TraceContext>,
+    /// The `Authorization` header value to send, for transports that support it.
+    authorization: Option<String>,
+    /// Whether trailing `null` params are omitted from the serialized params.
+    strict: bool,
+    /// The time after which the request is abandoned, overriding the default of the transport.
+    timeout: Option<Duration>,
+    /// The instant after which the request, and any retry of it, is abandoned.
+    deadline: Option<Instant>,
+}
+
+impl fmt::Debug for RequestMeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestMeta")
+            .field("method", &self.method)
+            .field("id", &self.id)
+            .field("is_subscription", &self.is_subscription)
+            .field("trace_context", &self.trace_context)
+            .field("authorization", &self.authorization.as_ref().map(|_| "<redacted>"))
+            .field("strict", &self.strict)
+            .field("timeout", &self.timeout)
+            .field("deadline", &self.deadline)
+            .finish()
+    }
 }
 
 impl RequestMeta {
     /// Create a new `RequestMeta`.
     pub const fn new(method: Cow<'static, str>, id: Id) -> Self {
-        Self { method, id, is_subscription: false }
+        Self {
+            method,
+            id,
+            is_subscription: false,
+            trace_context: None,
+            authorization: None,
+            strict: false,
+            timeout: None,
+            deadline: None,
+        }
+    }
+
+    /// Returns the trace context of the caller, if any.
+    pub const fn trace_context(&self) -> Option<&TraceContext> {
+        self.trace_context.as_ref()
+    }
+
+    /// Set the trace context of the caller. Transports that support it, like HTTP, propagate it
+    /// to the server.
+    pub fn set_trace_context(&mut self, trace_context: Option<TraceContext>) {
+        self.trace_context = trace_context;
+    }
+
+    /// Returns the `Authorization` header value to send with the request, if any.
+    pub fn authorization(&self) -> Option<&str> {
+        self.authorization.as_deref()
+    }
+
+    /// Set the `Authorization` header value to send with the request. Transports without
+    /// per-request headers ignore it.
+    pub fn set_authorization(&mut self, authorization: Option<String>) {
+        self.authorization = authorization;
+    }
+
+    /// Returns `true` if trailing `null` params are omitted from the serialized params.
+    pub const fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Set whether trailing `null` params are omitted from the serialized params, for servers
+    /// rejecting them in place of omitted optional params.
+    ///
+    /// In strict mode, the `null`s at the end of the params array are removed, so that
+    /// `[{"to":"0x01"},null]` is sent as `[{"to":"0x01"}]`. Other `null`s, such as wildcard
+    /// topics in filters or members of objects, are kept.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Returns the time after which the request is abandoned, if set.
+    pub const fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set the time after which the request is abandoned. It overrides the default timeout of
+    /// the transport, such as the one of the `TimeoutLayer` of `alloy-transport`, so that slow
+    /// methods can be given more time than others.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the instant after which the request is abandoned, if set.
+    pub const fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Set the instant after which the request is abandoned.
+    ///
+    /// Unlike the timeout, which applies to each attempt, the deadline bounds the total time
+    /// spent on the request, including waiting for the transport to be ready, and the retries
+    /// and failovers of the layers honoring it.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
     }
 
     /// Returns `true` if the request is a subscription.
@@ -78,6 +187,12 @@ impl<Params> Request<Params> {
     pub fn set_subscription_status(&mut self, sub: bool) {
         self.meta.set_subscription_status(sub);
     }
+
+    /// Set the trace context of the caller.
+    pub fn with_trace_context(mut self, trace_context: Option<TraceContext>) -> Self {
+        self.meta.set_trace_context(trace_context);
+        self
+    }
 }
 
 /// A [`Request`] that has been partially serialized. The request parameters
@@ -163,7 +278,13 @@ where
 
         // Params may be omitted if it is 0-sized
         if sized_params {
-            map.serialize_entry("params", &self.params)?;
+            if self.meta.strict {
+                let mut params = serde_json::to_value(&self.params).map_err(S::Error::custom)?;
+                strip_trailing_nulls(&mut params);
+                map.serialize_entry("params", &params)?;
+            } else {
+                map.serialize_entry("params", &self.params)?;
+            }
         }
 
         map.serialize_entry("id", &self.meta.id)?;
@@ -172,6 +293,15 @@ where
     }
 }
 
+/// Removes the trailing `null`s of the `params` array.
+fn strip_trailing_nulls(params: &mut serde_json::Value) {
+    if let serde_json::Value::Array(list) = params {
+        while list.last().is_some_and(serde_json::Value::is_null) {
+            list.pop();
+        }
+    }
+}
+
 /// A JSON-RPC 2.0 request object that has been serialized, with its [`Id`] and
 /// method preserved.
 ///
@@ -222,6 +352,31 @@ impl SerializedRequest {
         self.meta.is_subscription()
     }
 
+    /// Returns the trace context of the caller, if any.
+    pub const fn trace_context(&self) -> Option<&TraceContext> {
+        self.meta.trace_context()
+    }
+
+    /// Returns the `Authorization` header value to send with the request, if any.
+    pub fn authorization(&self) -> Option<&str> {
+        self.meta.authorization()
+    }
+
+    /// Returns the time after which the request is abandoned, if set.
+    pub const fn timeout(&self) -> Option<Duration> {
+        self.meta.timeout()
+    }
+
+    /// Returns the instant after which the request is abandoned, if set.
+    pub const fn deadline(&self) -> Option<Instant> {
+        self.meta.deadline()
+    }
+
+    /// Set the `Authorization` header value to send with the request.
+    pub fn set_authorization(&mut self, authorization: Option<String>) {
+        self.meta.set_authorization(authorization);
+    }
+
     /// Returns the serialized request.
     pub const fn serialized(&self) -> &RawValue {
         &self.request
@@ -261,6 +416,14 @@ impl SerializedRequest {
         req.params
     }
 
+    /// Replace the request's params, re-serializing the request with the same
+    /// metadata.
+    pub fn set_params<Params: RpcParam>(&mut self, params: Params) -> serde_json::Result<()> {
+        let request = Request { meta: self.meta.clone(), params };
+        self.request = serde_json::value::to_raw_value(&request)?;
+        Ok(())
+    }
+
     /// Get the hash of the serialized request's params.
     ///
     /// This partially deserializes the request, and should be avoided if
@@ -282,3 +445,29 @@ impl Serialize for SerializedRequest {
         self.request.serialize(serializer)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_params() {
+        let params = serde_json::json!([
+            {"from": null, "to": "0x01", "accessList": []},
+            {"topics": [null, "0x02"], "address": null},
+            null,
+            null
+        ]);
+        let mut request = Request::new("eth_call", Id::Number(1), params);
+        assert_eq!(
+            request.clone().serialize().unwrap().serialized().get(),
+            r#"{"method":"eth_call","params":[{"accessList":[],"from":null,"to":"0x01"},{"address":null,"topics":[null,"0x02"]},null,null],"id":1,"jsonrpc":"2.0"}"#
+        );
+
+        request.meta.set_strict(true);
+        assert_eq!(
+            request.box_params().serialize().unwrap().serialized().get(),
+            r#"{"method":"eth_call","params":[{"accessList":[],"from":null,"to":"0x01"},{"address":null,"topics":[null,"0x02"]}],"id":1,"jsonrpc":"2.0"}"#
+        );
+    }
+}
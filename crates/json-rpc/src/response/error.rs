@@ -1,3 +1,4 @@
+use alloy_primitives::{hex, Bytes};
 use serde::{
     de::{DeserializeOwned, MapAccess, Visitor},
     Deserialize, Deserializer, Serialize,
@@ -177,12 +178,45 @@ where
             _ => Err(self),
         }
     }
+
+    /// Extract the revert data of a failed call or gas estimation from the `data` field.
+    ///
+    /// Clients disagree on where they put it, so this accepts all shapes known to be in use:
+    /// - a hex string, with or without the `0x` prefix (geth, Erigon, Besu, Reth);
+    /// - a hex string prefixed by `Reverted ` (Nethermind);
+    /// - an object nesting any of these under `data` or `originalError` (Hardhat), or under
+    ///   `return` in an object keyed by transaction hash (Ganache).
+    ///
+    /// Returns `None` if there is no `data` field, or if it does not contain revert data.
+    pub fn as_revert_data(&self) -> Option<Bytes> {
+        let data = serde_json::from_str(self.data.as_ref()?.borrow().get()).ok()?;
+        revert_data(&data, 0)
+    }
+}
+
+/// Search `value` for revert data, recursing into objects up to a fixed depth.
+fn revert_data(value: &serde_json::Value, depth: usize) -> Option<Bytes> {
+    const MAX_DEPTH: usize = 4;
+
+    match value {
+        serde_json::Value::String(s) => {
+            let s = s.strip_prefix("Reverted ").unwrap_or(s).trim();
+            hex::decode(s).ok().map(Into::into)
+        }
+        serde_json::Value::Object(map) if depth < MAX_DEPTH => ["data", "originalError", "return"]
+            .iter()
+            .filter_map(|key| map.get(*key))
+            .chain(map.iter().filter(|(key, _)| key.starts_with("0x")).map(|(_, value)| value))
+            .find_map(|value| revert_data(value, depth + 1)),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::BorrowedErrorPayload;
     use crate::ErrorPayload;
+    use alloy_primitives::{bytes, Bytes};
 
     #[test]
     fn smooth_borrowing() {
@@ -218,4 +252,51 @@ mod test {
         assert_eq!(payload.message, "20/second request limit reached - reduce calls per second or upgrade your account at quicknode.com");
         assert!(payload.data.is_none());
     }
+
+    #[test]
+    fn revert_data() {
+        let revert = bytes!("08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000461626364");
+        let parse =
+            |json: &str| serde_json::from_str::<ErrorPayload>(json).unwrap().as_revert_data();
+
+        // geth, Erigon, Besu, Reth
+        let geth =
+            format!(r#"{{"code":3,"message":"execution reverted: abcd","data":"{revert}"}}"#);
+        assert_eq!(parse(&geth), Some(revert.clone()));
+        // Nethermind
+        let nethermind = format!(
+            r#"{{"code":-32015,"message":"VM execution error.","data":"Reverted {revert}"}}"#
+        );
+        assert_eq!(parse(&nethermind), Some(revert.clone()));
+        // Prefixless hex.
+        let prefixless = format!(
+            r#"{{"code":-32000,"message":"execution reverted","data":"{}"}}"#,
+            &revert.to_string()[2..]
+        );
+        assert_eq!(parse(&prefixless), Some(revert.clone()));
+        // Hardhat
+        let hardhat = format!(
+            r#"{{"code":-32603,"message":"Error: VM Exception while processing transaction","data":{{"message":"Error: VM Exception","data":"{revert}"}}}}"#
+        );
+        assert_eq!(parse(&hardhat), Some(revert.clone()));
+        let hardhat = format!(
+            r#"{{"code":-32603,"message":"Internal error","data":{{"originalError":{{"code":3,"data":"{revert}","message":"execution reverted: abcd"}}}}}}"#
+        );
+        assert_eq!(parse(&hardhat), Some(revert.clone()));
+        // Ganache
+        let ganache = format!(
+            r#"{{"code":-32000,"message":"VM Exception while processing transaction: revert abcd","data":{{"0x3ff568f1b3a7ab9f4b2fb46e2d9aa1b6d5b1dbcc4e41a3a5b138e293accb9a9f":{{"error":"revert","program_counter":130,"return":"{revert}","reason":"abcd"}},"stack":"RuntimeError: VM Exception","name":"RuntimeError"}}}}"#
+        );
+        assert_eq!(parse(&ganache), Some(revert));
+
+        // A revert without data.
+        assert_eq!(
+            parse(r#"{"code":3,"message":"execution reverted","data":"0x"}"#),
+            Some(Bytes::new())
+        );
+        // Not revert data.
+        assert_eq!(parse(r#"{"code":-32000,"message":"nonce too low"}"#), None);
+        assert_eq!(parse(r#"{"code":-32000,"message":"b","data":"insufficient funds"}"#), None);
+        assert_eq!(parse(r#"{"code":-32000,"message":"b","data":{"a":5}}"#), None);
+    }
 }
@@ -99,7 +99,7 @@ where
     ) -> Result<ResponsePayload<T, ErrData>, Self> {
         match self {
             ResponsePayload::Success(ref payload) => {
-                match serde_json::from_str(payload.as_ref().get()) {
+                match crate::result::from_json(payload.as_ref().get()) {
                     Ok(payload) => Ok(ResponsePayload::Success(payload)),
                     Err(_) => Err(self),
                 }
@@ -1,7 +1,13 @@
 use crate::{Response, ResponsePayload, RpcError, RpcReturn};
+use serde::de::DeserializeOwned;
 use serde_json::value::RawValue;
 use std::borrow::Borrow;
 
+/// Results shorter than this are always deserialized with `serde_json`, as copying them into the
+/// mutable buffer needed by `simd-json` costs more than it saves.
+#[cfg(feature = "simd-json")]
+const SIMD_JSON_MIN_LEN: usize = 4 * 1024;
+
 /// The result of a JSON-RPC request.
 ///
 /// Either a success response, an error response, or a non-response error. The
@@ -64,8 +70,52 @@ where
     let json = result?;
     let json = json.borrow().get();
     trace!(ty=%std::any::type_name::<T>(), json, "deserializing response");
-    serde_json::from_str(json)
+    from_json(json)
         .inspect(|response| trace!(?response, "deserialized response"))
         .inspect_err(|err| trace!(?err, "failed to deserialize response"))
         .map_err(|err| RpcError::deser_err(err, json))
 }
+
+/// Deserialize a JSON-RPC result.
+///
+/// With the `simd-json` feature, large results are deserialized with `simd-json`. On failure, they
+/// are deserialized again with `serde_json`, which returns the error: `simd-json` can't produce
+/// some types, such as [`RawValue`]s.
+pub(crate) fn from_json<T: DeserializeOwned>(json: &str) -> serde_json::Result<T> {
+    #[cfg(feature = "simd-json")]
+    if json.len() >= SIMD_JSON_MIN_LEN {
+        if let Ok(value) = simd_json::serde::from_slice(&mut json.as_bytes().to_vec()) {
+            return Ok(value);
+        }
+    }
+    serde_json::from_str(json)
+}
+
+#[cfg(all(test, feature = "simd-json"))]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn simd_json_results() {
+        let entries: BTreeMap<String, Vec<u64>> =
+            (0..512).map(|i| (format!("key{i}"), vec![i; 4])).collect();
+        let json = serde_json::to_string(&entries).unwrap();
+        assert!(json.len() >= SIMD_JSON_MIN_LEN);
+        assert_eq!(from_json::<BTreeMap<String, Vec<u64>>>(&json).unwrap(), entries);
+
+        // `simd-json` can't produce a `RawValue`, so `serde_json` is used instead.
+        let raw: BTreeMap<String, Box<RawValue>> = from_json(&json).unwrap();
+        assert_eq!(raw["key1"].get(), "[1,1,1,1]");
+
+        // Nor can it produce the `RawValue` fields of a struct.
+        #[derive(serde::Deserialize)]
+        struct Lazy {
+            key1: Box<RawValue>,
+        }
+        let lazy: Lazy = from_json(&json).unwrap();
+        assert_eq!(lazy.key1.get(), "[1,1,1,1]");
+
+        assert!(from_json::<BTreeMap<String, Vec<String>>>(&json).is_err());
+    }
+}
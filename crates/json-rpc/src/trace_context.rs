@@ -0,0 +1,134 @@
+use alloy_primitives::{hex, B128, B64};
+use std::{fmt, str::FromStr};
+
+/// The HTTP header carrying the [`TraceContext`] of a request.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// The HTTP header carrying vendor-specific trace state, see [`TraceContext::state`].
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+/// A [W3C Trace Context], identifying the distributed trace and the span that a request was made
+/// from.
+///
+/// Its [`Display`](fmt::Display) and [`FromStr`] implementations use the `traceparent` header
+/// format, e.g. `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`.
+///
+/// [W3C Trace Context]: https://www.w3.org/TR/trace-context/
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+    /// The ID of the whole trace.
+    pub trace_id: B128,
+    /// The ID of the calling span.
+    pub parent_id: B64,
+    /// The trace flags. Only the lowest bit, `sampled`, is defined.
+    pub flags: u8,
+    /// The `tracestate` header value, if any.
+    pub state: Option<String>,
+}
+
+impl TraceContext {
+    /// The `sampled` trace flag.
+    pub const SAMPLED: u8 = 0x01;
+
+    /// Create a new sampled trace context.
+    pub const fn new(trace_id: B128, parent_id: B64) -> Self {
+        Self { trace_id, parent_id, flags: Self::SAMPLED, state: None }
+    }
+
+    /// Set the trace flags.
+    pub const fn with_flags(mut self, flags: u8) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Set the `tracestate` header value.
+    pub fn with_state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Returns `true` if the caller may have recorded the trace.
+    pub const fn is_sampled(&self) -> bool {
+        self.flags & Self::SAMPLED != 0
+    }
+
+    /// Returns `true` if neither ID is all zeroes, as required by the specification.
+    pub fn is_valid(&self) -> bool {
+        !self.trace_id.is_zero() && !self.parent_id.is_zero()
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "00-{:x}-{:x}-{:02x}", self.trace_id, self.parent_id, self.flags)
+    }
+}
+
+/// Error returned when parsing an invalid `traceparent` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("invalid traceparent header")]
+pub struct ParseTraceContextError;
+
+impl FromStr for TraceContext {
+    type Err = ParseTraceContextError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('-');
+        let (Some(version), Some(trace_id), Some(parent_id), Some(flags)) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ParseTraceContextError);
+        };
+        // Future versions may append fields, but version `00` has exactly four.
+        if version.len() != 2 || version == "ff" || (version == "00" && parts.next().is_some()) {
+            return Err(ParseTraceContextError);
+        }
+        if flags.len() != 2 || trace_id.len() != 32 || parent_id.len() != 16 {
+            return Err(ParseTraceContextError);
+        }
+        hex::decode(version).map_err(|_| ParseTraceContextError)?;
+
+        let context = Self {
+            trace_id: trace_id.parse().map_err(|_| ParseTraceContextError)?,
+            parent_id: parent_id.parse().map_err(|_| ParseTraceContextError)?,
+            flags: u8::from_str_radix(flags, 16).map_err(|_| ParseTraceContextError)?,
+            state: None,
+        };
+        if !context.is_valid() {
+            return Err(ParseTraceContextError);
+        }
+        Ok(context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b128, b64};
+
+    #[test]
+    fn traceparent_roundtrip() {
+        let s = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context: TraceContext = s.parse().unwrap();
+        assert_eq!(
+            context,
+            TraceContext::new(b128!("4bf92f3577b34da6a3ce929d0e0e4736"), b64!("00f067aa0ba902b7"))
+        );
+        assert!(context.is_sampled());
+        assert_eq!(context.to_string(), s);
+        assert!(!context.with_flags(0).is_sampled());
+
+        for invalid in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-00",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e473-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-zz",
+        ] {
+            assert_eq!(invalid.parse::<TraceContext>(), Err(ParseTraceContextError), "{invalid}");
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use core::fmt;
 
-use crate::{Network, ReceiptResponse};
+use crate::{FinalityPolicy, Network, ReceiptResponse, DEFAULT_FINALITY_CONFIRMATIONS};
 use alloy_consensus::TxType;
 use alloy_eips::eip2718::Eip2718Error;
 use alloy_rpc_types::{
@@ -51,6 +51,12 @@ impl From<TxType> for AnyTxType {
 /// Essentially just returns the regular Ethereum types + a catch all field.
 /// This [`Network`] should be used only when the network is not known at
 /// compile time.
+///
+/// Fields that are not part of the Ethereum types, such as Celo's `feeCurrency`
+/// or BSC's system transaction markers, end up in the
+/// [`OtherFields`](alloy_rpc_types::other::OtherFields) of the response instead
+/// of failing deserialization. Unknown transaction and receipt types are kept
+/// as-is.
 #[derive(Clone, Copy, Debug)]
 pub struct AnyNetwork {
     _private: (),
@@ -74,10 +80,240 @@ impl Network for AnyNetwork {
     type ReceiptResponse = AnyTransactionReceipt;
 
     type HeaderResponse = WithOtherFields<Header>;
+
+    fn finality_policy() -> FinalityPolicy {
+        // nodes of arbitrary networks may not track finality
+        FinalityPolicy::Confirmations(DEFAULT_FINALITY_CONFIRMATIONS)
+    }
 }
 
 impl ReceiptResponse for AnyTransactionReceipt {
     fn contract_address(&self) -> Option<alloy_primitives::Address> {
         self.contract_address
     }
+
+    fn block_number(&self) -> Option<alloy_primitives::BlockNumber> {
+        self.block_number
+    }
+
+    fn block_hash(&self) -> Option<alloy_primitives::BlockHash> {
+        self.block_hash
+    }
+
+    fn transaction_index(&self) -> Option<u64> {
+        self.transaction_index
+    }
+
+    fn status(&self) -> bool {
+        self.inner.inner.status()
+    }
+
+    fn gas_used(&self) -> u128 {
+        self.gas_used
+    }
+
+    fn effective_gas_price(&self) -> u128 {
+        self.effective_gas_price
+    }
+
+    fn encoded_2718(&self) -> Option<Vec<u8>> {
+        Some(alloy_eips::eip2718::Encodable2718::encoded_2718(&self.inner.inner))
+    }
+
+    fn logs(&self) -> &[alloy_rpc_types::Log] {
+        self.inner.inner.logs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types::{Block, BlockTransactions};
+
+    type AnyBlock = Block<<AnyNetwork as Network>::TransactionResponse>;
+
+    #[test]
+    fn polygon_state_sync_block() {
+        // Bor appends a state-sync transaction with a zero sender and signature to some blocks.
+        let block = r#"{
+            "baseFeePerGas": "0x1b",
+            "difficulty": "0x14",
+            "extraData": "0xd78301000683626f7288676f312e32312e36856c696e75780000000000000000",
+            "gasLimit": "0x1c9c380",
+            "gasUsed": "0x0",
+            "hash": "0x8b2a3c5a1f1e85a0a3b1c9d3e4f5061728394a5b6c7d8e9fa0b1c2d3e4f50617",
+            "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "miner": "0x0000000000000000000000000000000000000000",
+            "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "nonce": "0x0000000000000000",
+            "number": "0x3b9aca0",
+            "parentHash": "0x1d4b7c8e9f0a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4",
+            "receiptsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "sha3Uncles": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "size": "0x26a",
+            "stateRoot": "0xa5e2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708192a3b4c5d6e7f80",
+            "timestamp": "0x65f0c1a4",
+            "totalDifficulty": "0x24a7b0c",
+            "transactions": [{
+                "blockHash": "0x8b2a3c5a1f1e85a0a3b1c9d3e4f5061728394a5b6c7d8e9fa0b1c2d3e4f50617",
+                "blockNumber": "0x3b9aca0",
+                "from": "0x0000000000000000000000000000000000000000",
+                "gas": "0x0",
+                "gasPrice": "0x0",
+                "hash": "0x3f2a9b8c7d6e5f4031a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708",
+                "input": "0x",
+                "nonce": "0x0",
+                "to": "0x0000000000000000000000000000000000000000",
+                "transactionIndex": "0x0",
+                "value": "0x0",
+                "type": "0x0",
+                "v": "0x0",
+                "r": "0x0",
+                "s": "0x0"
+            }],
+            "transactionsRoot": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "uncles": []
+        }"#;
+        let block: AnyBlock = serde_json::from_str(block).unwrap();
+        let BlockTransactions::Full(txs) = &block.transactions else { panic!("expected full txs") };
+        assert_eq!(txs.len(), 1);
+        assert_eq!(block.header.number, Some(0x3b9aca0));
+    }
+
+    #[test]
+    fn celo_fee_currency_transaction() {
+        let tx = r#"{
+            "blockHash": "0x1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d",
+            "blockNumber": "0x1a2b3c",
+            "from": "0x7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b",
+            "gas": "0x5208",
+            "gasPrice": "0x2540be400",
+            "hash": "0x9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c2b1a0f9e8d",
+            "input": "0x",
+            "nonce": "0x7",
+            "to": "0x1f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c",
+            "transactionIndex": "0x2",
+            "value": "0xde0b6b3a7640000",
+            "type": "0x0",
+            "v": "0x149fb",
+            "r": "0x5d2e4f6a8b0c1d3e5f7a9b0c2d4e6f8a0b1c3d5e7f9a1b2c4d6e8f0a1b3c5d7e",
+            "s": "0x2b4c6d8e0f1a3b5c7d9e1f2a4b6c8d0e2f3a5b7c9d1e3f4a6b8c0d2e4f5a7b9c",
+            "feeCurrency": "0x765de816845861e75a25fca122bb6898b8b1282a",
+            "gatewayFeeRecipient": null,
+            "gatewayFee": "0x0"
+        }"#;
+        let tx: <AnyNetwork as Network>::TransactionResponse = serde_json::from_str(tx).unwrap();
+        assert_eq!(
+            tx.inner
+                .other
+                .get_deserialized::<alloy_primitives::Address>("feeCurrency")
+                .unwrap()
+                .unwrap(),
+            alloy_primitives::address!("765de816845861e75a25fca122bb6898b8b1282a")
+        );
+        assert!(tx.inner.other.contains_key("gatewayFee"));
+    }
+
+    #[test]
+    fn untyped_receipt_with_extra_fields() {
+        // Receipt without a `type` field, as returned by some older BSC and Celo nodes.
+        let receipt = r#"{
+            "blockHash": "0x1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d",
+            "blockNumber": "0x1a2b3c",
+            "contractAddress": null,
+            "cumulativeGasUsed": "0x5208",
+            "effectiveGasPrice": "0x2540be400",
+            "from": "0x7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b",
+            "gasUsed": "0x5208",
+            "logs": [],
+            "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "status": "0x1",
+            "to": "0x1f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c",
+            "transactionHash": "0x9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c2b1a0f9e8d",
+            "transactionIndex": "0x2",
+            "l1Fee": "0x0"
+        }"#;
+        let receipt: <AnyNetwork as Network>::ReceiptResponse =
+            serde_json::from_str(receipt).unwrap();
+        assert!(receipt.inner.inner.is_legacy());
+        assert!(receipt.inner.inner.status());
+        assert!(receipt.other.contains_key("l1Fee"));
+    }
+
+    #[test]
+    fn receipt_response_accessors() {
+        alloy_sol_types::sol! {
+            #[derive(Debug, PartialEq)]
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            #[derive(Debug, PartialEq)]
+            event Approval(address indexed owner, address indexed spender, uint256 value);
+        }
+
+        let receipt = r#"{
+            "blockHash": "0x1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d",
+            "blockNumber": "0x1a2b3c",
+            "contractAddress": null,
+            "cumulativeGasUsed": "0xc350",
+            "effectiveGasPrice": "0x2540be400",
+            "from": "0x7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b",
+            "gasUsed": "0xc350",
+            "logs": [{
+                "address": "0x1f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c",
+                "topics": [
+                    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                    "0x0000000000000000000000007a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b",
+                    "0x0000000000000000000000001f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c"
+                ],
+                "data": "0x00000000000000000000000000000000000000000000000000000000000003e8",
+                "blockHash": "0x1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d",
+                "blockNumber": "0x1a2b3c",
+                "transactionHash": "0x9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c2b1a0f9e8d",
+                "transactionIndex": "0x2",
+                "logIndex": "0x0",
+                "removed": false
+            }, {
+                "address": "0x1f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c",
+                "topics": [
+                    "0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925",
+                    "0x0000000000000000000000007a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b",
+                    "0x0000000000000000000000001f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c"
+                ],
+                "data": "0x00000000000000000000000000000000000000000000000000000000000007d0",
+                "blockHash": "0x1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d",
+                "blockNumber": "0x1a2b3c",
+                "transactionHash": "0x9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c2b1a0f9e8d",
+                "transactionIndex": "0x2",
+                "logIndex": "0x1",
+                "removed": false
+            }],
+            "logsBloom": "0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "status": "0x0",
+            "to": "0x1f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c",
+            "transactionHash": "0x9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c2b1a0f9e8d",
+            "transactionIndex": "0x2",
+            "type": "0x2"
+        }"#;
+        let receipt: <AnyNetwork as Network>::ReceiptResponse =
+            serde_json::from_str(receipt).unwrap();
+        assert!(!ReceiptResponse::status(&receipt));
+        assert_eq!(receipt.gas_cost(), alloy_primitives::U256::from(50_000u128 * 10_000_000_000));
+        assert_eq!(receipt.logs().len(), 2);
+        assert_eq!(
+            receipt.decoded_logs::<Transfer>(),
+            [Transfer {
+                from: alloy_primitives::address!("7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b"),
+                to: alloy_primitives::address!("1f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c"),
+                value: alloy_primitives::U256::from(1000),
+            }]
+        );
+        // The approval has the same parameters, but not the signature of a transfer.
+        assert_eq!(
+            receipt.decoded_logs::<Approval>(),
+            [Approval {
+                owner: alloy_primitives::address!("7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d3e4f5a6b"),
+                spender: alloy_primitives::address!("1f2e3d4c5b6a79880f1e2d3c4b5a69788f9e0d1c"),
+                value: alloy_primitives::U256::from(2000),
+            }]
+        );
+    }
 }
@@ -0,0 +1,73 @@
+use crate::{Network, ReceiptResponse};
+use alloy_rpc_types::arbitrum::ArbitrumTransactionReceipt;
+
+mod builder;
+
+/// Types for an Arbitrum Nitro network, such as Arbitrum One or Nova.
+///
+/// Users sign the Ethereum transaction types, so [`EthereumSigner`](crate::EthereumSigner) can
+/// be used with this network. Priority fees are not paid on Arbitrum: to not pay them, set the
+/// provider's `eip1559_arbitrum_estimator` on its gas filler with
+/// `GasFiller::with_eip1559_estimator`.
+#[derive(Clone, Copy, Debug)]
+pub struct Arbitrum {
+    _private: (),
+}
+
+impl Network for Arbitrum {
+    type TxType = alloy_rpc_types::arbitrum::ArbitrumTxType;
+
+    type TxEnvelope = alloy_consensus::TxEnvelope;
+
+    type UnsignedTx = alloy_consensus::TypedTransaction;
+
+    type ReceiptEnvelope = alloy_consensus::AnyReceiptEnvelope;
+
+    type Header = alloy_consensus::Header;
+
+    type TransactionRequest = alloy_rpc_types::arbitrum::ArbitrumTransactionRequest;
+
+    type TransactionResponse = alloy_rpc_types::arbitrum::ArbitrumTransaction;
+
+    type ReceiptResponse = ArbitrumTransactionReceipt;
+
+    type HeaderResponse = alloy_rpc_types::arbitrum::ArbitrumHeader;
+}
+
+impl ReceiptResponse for ArbitrumTransactionReceipt {
+    fn contract_address(&self) -> Option<alloy_primitives::Address> {
+        self.inner.contract_address
+    }
+
+    fn block_number(&self) -> Option<alloy_primitives::BlockNumber> {
+        self.inner.block_number
+    }
+
+    fn block_hash(&self) -> Option<alloy_primitives::BlockHash> {
+        self.inner.block_hash
+    }
+
+    fn transaction_index(&self) -> Option<u64> {
+        self.inner.transaction_index
+    }
+
+    fn status(&self) -> bool {
+        self.inner.inner.status()
+    }
+
+    fn gas_used(&self) -> u128 {
+        self.inner.gas_used
+    }
+
+    fn effective_gas_price(&self) -> u128 {
+        self.inner.effective_gas_price
+    }
+
+    fn encoded_2718(&self) -> Option<Vec<u8>> {
+        Some(alloy_eips::eip2718::Encodable2718::encoded_2718(&self.inner.inner))
+    }
+
+    fn logs(&self) -> &[alloy_rpc_types::Log] {
+        self.inner.inner.logs()
+    }
+}
@@ -0,0 +1,44 @@
+use alloy_rpc_types::bsc::BscTransactionRequest;
+
+use crate::bsc::Bsc;
+
+impl_tx_builder!(Bsc, BscTransactionRequest);
+
+#[cfg(test)]
+mod tests {
+    use crate::{TransactionBuilder, TransactionBuilderError};
+    use alloy_consensus::{BlobTransactionSidecar, TxType, TypedTransaction};
+    use alloy_primitives::Address;
+    use alloy_rpc_types::bsc::BscTransactionRequest;
+
+    #[test]
+    fn test_4844_when_sidecar() {
+        let request = BscTransactionRequest::default()
+            .with_nonce(1)
+            .with_gas_limit(0)
+            .with_max_fee_per_gas(0)
+            .with_max_priority_fee_per_gas(0)
+            .with_to(Address::ZERO)
+            .with_blob_sidecar(BlobTransactionSidecar::default())
+            .with_max_fee_per_blob_gas(0);
+
+        assert_eq!(request.output_tx_type(), TxType::Eip4844);
+        let tx = request.build_unsigned().unwrap();
+
+        assert!(matches!(tx, TypedTransaction::Eip4844(_)));
+    }
+
+    #[test]
+    fn test_invalid_1559_fields() {
+        let request = BscTransactionRequest::default();
+
+        let error = request.build_unsigned().unwrap_err();
+
+        let (_, TransactionBuilderError::InvalidTransactionRequest(tx_type, errors)) = error else {
+            panic!("wrong variant")
+        };
+
+        assert_eq!(tx_type, TxType::Eip1559);
+        assert_eq!(errors.len(), 5);
+    }
+}
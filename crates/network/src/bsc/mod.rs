@@ -0,0 +1,35 @@
+use crate::Network;
+
+mod builder;
+
+/// Types for BNB Smart Chain, or its testnet.
+///
+/// BNB Smart Chain has the Ethereum transaction and receipt types, so
+/// [`EthereumSigner`](crate::EthereumSigner) can be used with this network. Blocks include the
+/// system transactions of their validator, see [`BscTransaction::is_system`].
+///
+/// [`BscTransaction::is_system`]: alloy_rpc_types::bsc::BscTransaction::is_system
+#[derive(Clone, Copy, Debug)]
+pub struct Bsc {
+    _private: (),
+}
+
+impl Network for Bsc {
+    type TxType = alloy_consensus::TxType;
+
+    type TxEnvelope = alloy_consensus::TxEnvelope;
+
+    type UnsignedTx = alloy_consensus::TypedTransaction;
+
+    type ReceiptEnvelope = alloy_consensus::ReceiptEnvelope;
+
+    type Header = alloy_consensus::Header;
+
+    type TransactionRequest = alloy_rpc_types::bsc::BscTransactionRequest;
+
+    type TransactionResponse = alloy_rpc_types::bsc::BscTransaction;
+
+    type ReceiptResponse = alloy_rpc_types::TransactionReceipt;
+
+    type HeaderResponse = alloy_rpc_types::Header;
+}
@@ -116,12 +116,7 @@ impl TransactionBuilder<Ethereum> for TransactionRequest {
     }
 
     fn complete_type(&self, ty: TxType) -> Result<(), Vec<&'static str>> {
-        match ty {
-            TxType::Legacy => self.complete_legacy(),
-            TxType::Eip2930 => self.complete_2930(),
-            TxType::Eip1559 => self.complete_1559(),
-            TxType::Eip4844 => self.complete_4844(),
-        }
+        self.complete_type(ty)
     }
 
     fn can_submit(&self) -> bool {
@@ -333,13 +328,12 @@ mod tests {
         };
 
         assert_eq!(tx_type, TxType::Eip4844);
-        assert_eq!(errors.len(), 7);
+        assert_eq!(errors.len(), 6);
         assert!(errors.contains(&"to"));
         assert!(errors.contains(&"nonce"));
         assert!(errors.contains(&"gas_limit"));
         assert!(errors.contains(&"max_priority_fee_per_gas"));
         assert!(errors.contains(&"max_fee_per_gas"));
-        assert!(errors.contains(&"to"));
         assert!(errors.contains(&"max_fee_per_blob_gas"));
     }
 }
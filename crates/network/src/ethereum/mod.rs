@@ -35,4 +35,36 @@ impl ReceiptResponse for alloy_rpc_types::TransactionReceipt {
     fn contract_address(&self) -> Option<alloy_primitives::Address> {
         self.contract_address
     }
+
+    fn block_number(&self) -> Option<alloy_primitives::BlockNumber> {
+        self.block_number
+    }
+
+    fn block_hash(&self) -> Option<alloy_primitives::BlockHash> {
+        self.block_hash
+    }
+
+    fn transaction_index(&self) -> Option<u64> {
+        self.transaction_index
+    }
+
+    fn status(&self) -> bool {
+        self.status()
+    }
+
+    fn gas_used(&self) -> u128 {
+        self.gas_used
+    }
+
+    fn effective_gas_price(&self) -> u128 {
+        self.effective_gas_price
+    }
+
+    fn encoded_2718(&self) -> Option<Vec<u8>> {
+        Some(alloy_eips::eip2718::Encodable2718::encoded_2718(&self.inner))
+    }
+
+    fn logs(&self) -> &[alloy_rpc_types::Log] {
+        self.inner.logs()
+    }
 }
@@ -67,6 +67,21 @@ impl EthereumSigner {
         self.register_signer(signer);
     }
 
+    /// Deregister the signer for the given address, returning it if it was
+    /// registered.
+    ///
+    /// The default signer cannot be deregistered, set another default signer
+    /// with [`EthereumSigner::register_default_signer`] first.
+    pub fn deregister_signer(
+        &mut self,
+        address: Address,
+    ) -> Option<Arc<dyn TxSigner<Signature> + Send + Sync + 'static>> {
+        if address == self.default {
+            return None;
+        }
+        self.secp_signers.remove(&address)
+    }
+
     /// Get the default signer.
     pub fn default_signer(&self) -> Arc<dyn TxSigner<Signature> + Send + Sync + 'static> {
         self.secp_signers.get(&self.default).cloned().expect("invalid signer")
@@ -80,7 +95,7 @@ impl EthereumSigner {
         self.secp_signers.get(&address).cloned()
     }
 
-    async fn sign_transaction_inner(
+    pub(crate) async fn sign_transaction_inner(
         &self,
         sender: Address,
         tx: &mut dyn SignableTransaction<Signature>,
@@ -0,0 +1,89 @@
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::BlockNumber;
+use std::{fmt, sync::Arc};
+
+/// The default number of confirmations after which a block is final, for networks whose nodes
+/// do not track finality.
+pub const DEFAULT_FINALITY_CONFIRMATIONS: u64 = 64;
+
+/// What counts as final: the blocks, and the transactions included in them, that are not
+/// expected to be reorged anymore.
+///
+/// Each [`Network`](crate::Network) has a default policy, returned by
+/// [`Network::finality_policy`](crate::Network::finality_policy), which is used by the
+/// components of the provider dealing with finality unless they are given another one.
+#[derive(Clone)]
+pub enum FinalityPolicy {
+    /// A block is final once this many blocks were built on top of it.
+    Confirmations(u64),
+    /// A block is final once it is at or below the `safe` block of the node.
+    Safe,
+    /// A block is final once it is at or below the `finalized` block of the node.
+    Finalized,
+    /// A block is final once it is at or below the block number returned for the head of the
+    /// chain.
+    Custom(Arc<dyn Fn(BlockNumber) -> BlockNumber + Send + Sync>),
+}
+
+impl fmt::Debug for FinalityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Confirmations(confirmations) => {
+                f.debug_tuple("Confirmations").field(confirmations).finish()
+            }
+            Self::Safe => f.write_str("Safe"),
+            Self::Finalized => f.write_str("Finalized"),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+impl Default for FinalityPolicy {
+    fn default() -> Self {
+        Self::Confirmations(DEFAULT_FINALITY_CONFIRMATIONS)
+    }
+}
+
+impl FinalityPolicy {
+    /// Creates a custom policy from a function returning the latest final block for the head of
+    /// the chain.
+    pub fn custom<F>(f: F) -> Self
+    where
+        F: Fn(BlockNumber) -> BlockNumber + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(f))
+    }
+
+    /// Returns the tag of the latest final block, for policies relying on the node.
+    pub const fn block_tag(&self) -> Option<BlockNumberOrTag> {
+        match self {
+            Self::Safe => Some(BlockNumberOrTag::Safe),
+            Self::Finalized => Some(BlockNumberOrTag::Finalized),
+            Self::Confirmations(_) | Self::Custom(_) => None,
+        }
+    }
+
+    /// Returns the latest final block when `head` is the head of the chain, for policies not
+    /// relying on the node.
+    pub fn final_block_at(&self, head: BlockNumber) -> Option<BlockNumber> {
+        match self {
+            Self::Confirmations(confirmations) => Some(head.saturating_sub(*confirmations)),
+            Self::Custom(f) => Some(f(head)),
+            Self::Safe | Self::Finalized => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn final_blocks() {
+        assert_eq!(FinalityPolicy::Confirmations(2).final_block_at(10), Some(8));
+        assert_eq!(FinalityPolicy::Confirmations(20).final_block_at(10), Some(0));
+        assert_eq!(FinalityPolicy::custom(|head| head / 2).final_block_at(10), Some(5));
+        assert_eq!(FinalityPolicy::Finalized.final_block_at(10), None);
+        assert_eq!(FinalityPolicy::Safe.block_tag(), Some(BlockNumberOrTag::Safe));
+    }
+}
@@ -18,9 +18,18 @@
 use alloy_consensus::TxReceipt;
 use alloy_eips::eip2718::{Eip2718Envelope, Eip2718Error};
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, BlockHash, BlockNumber, U256};
+use alloy_rpc_types::{Log, LogDecodePolicy};
+use alloy_sol_types::SolEvent;
 use core::fmt::{Debug, Display};
 
+#[cfg(any(feature = "arbitrum", feature = "bsc", feature = "polygon"))]
+#[macro_use]
+mod macros;
+
+mod finality;
+pub use finality::{FinalityPolicy, DEFAULT_FINALITY_CONFIRMATIONS};
+
 mod transaction;
 pub use transaction::{
     BuildResult, NetworkSigner, TransactionBuilder, TransactionBuilderError, TxSigner,
@@ -33,6 +42,26 @@ pub use ethereum::{Ethereum, EthereumSigner};
 mod any;
 pub use any::AnyNetwork;
 
+#[cfg(feature = "arbitrum")]
+mod arbitrum;
+#[cfg(feature = "arbitrum")]
+pub use arbitrum::Arbitrum;
+
+#[cfg(feature = "bsc")]
+mod bsc;
+#[cfg(feature = "bsc")]
+pub use bsc::Bsc;
+
+#[cfg(feature = "optimism")]
+mod optimism;
+#[cfg(feature = "optimism")]
+pub use optimism::Optimism;
+
+#[cfg(feature = "polygon")]
+mod polygon;
+#[cfg(feature = "polygon")]
+pub use polygon::Polygon;
+
 pub use alloy_eips::eip2718;
 
 /// A receipt response.
@@ -43,13 +72,80 @@ pub use alloy_eips::eip2718;
 pub trait ReceiptResponse {
     /// Address of the created contract, or `None` if the transaction was not a deployment.
     fn contract_address(&self) -> Option<Address>;
+
+    /// Number of the block the transaction was included in, or `None` if it is pending.
+    ///
+    /// Returns `None` by default, for receipts that do not expose their block.
+    fn block_number(&self) -> Option<BlockNumber> {
+        None
+    }
+
+    /// Hash of the block the transaction was included in, or `None` if it is pending.
+    ///
+    /// Returns `None` by default, for receipts that do not expose their block.
+    fn block_hash(&self) -> Option<BlockHash> {
+        None
+    }
+
+    /// Index of the transaction within its block, or `None` if it is pending.
+    ///
+    /// Returns `None` by default, for receipts that do not expose their index.
+    fn transaction_index(&self) -> Option<u64> {
+        None
+    }
+
+    /// Whether the transaction succeeded.
+    ///
+    /// Pre-Byzantium receipts, which have a state root instead of a status, are assumed to be
+    /// successful.
+    fn status(&self) -> bool;
+
+    /// Gas used by the transaction alone.
+    fn gas_used(&self) -> u128;
+
+    /// Price paid per unit of gas, including the priority fee.
+    fn effective_gas_price(&self) -> u128;
+
+    /// Logs emitted by the transaction.
+    fn logs(&self) -> &[Log];
+
+    /// Encodes the consensus receipt of the transaction in its [EIP-2718] form, as committed to
+    /// in the receipts root of its block.
+    ///
+    /// Returns `None` by default, for receipts that cannot be encoded, which then cannot be
+    /// proven.
+    ///
+    /// [EIP-2718]: https://eips.ethereum.org/EIPS/eip-2718
+    fn encoded_2718(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Fee paid for the gas used by the transaction, i.e. the
+    /// [effective gas price](Self::effective_gas_price) times the [gas used](Self::gas_used).
+    ///
+    /// This does not include the fee paid for blobs, nor the fees specific to some networks, such
+    /// as the L1 fee of OP-stack networks.
+    fn gas_cost(&self) -> U256 {
+        U256::from(self.effective_gas_price()) * U256::from(self.gas_used())
+    }
+
+    /// Decode the logs of the transaction emitted as the event `E`, skipping the other logs.
+    ///
+    /// Logs are matched by the signature hash of `E` in their first topic, unless `E` is
+    /// anonymous. Their address is not checked, so logs of other contracts emitting the same event
+    /// are also decoded.
+    fn decoded_logs<E: SolEvent>(&self) -> Vec<E> {
+        Log::decode_logs(self.logs(), LogDecodePolicy::Skip).unwrap_or_default()
+    }
 }
 
 /// Captures type info for network-specific RPC requests/responses.
 ///
 /// Networks are only containers for types, so it is recommended to use ZSTs for their definition.
-// todo: block responses are ethereum only, so we need to include this in here too, or make `Block`
-// generic over tx/header type
+///
+/// Blocks are returned as [`Block`](alloy_rpc_types::Block)s of the network's
+/// [`TransactionResponse`](Network::TransactionResponse).
+// todo: block headers are ethereum only, so we need to make `Block` generic over the header type
 pub trait Network: Debug + Clone + Copy + Sized + Send + Sync + 'static {
     // -- Consensus types --
 
@@ -98,4 +194,14 @@ pub trait Network: Debug + Clone + Copy + Sized + Send + Sync + 'static {
 
     /// The JSON body of a header response.
     type HeaderResponse: RpcObject;
+
+    // -- Finality --
+
+    /// The default [`FinalityPolicy`] of the network.
+    ///
+    /// Defaults to [`FinalityPolicy::Finalized`], as nodes of post-merge Ethereum and its rollups
+    /// track finality.
+    fn finality_policy() -> FinalityPolicy {
+        FinalityPolicy::Finalized
+    }
 }
@@ -0,0 +1,285 @@
+/// Implements [`TransactionBuilder`](crate::TransactionBuilder) for the transaction request of a
+/// network, by delegating to the [`TransactionRequest`](alloy_rpc_types::TransactionRequest) it
+/// dereferences to.
+///
+/// `impl_tx_builder!(Network, Request)` delegates everything, for networks with the Ethereum
+/// transaction types.
+///
+/// `impl_tx_builder!(Network, Request, TxType, "Name")` is for networks without blob
+/// transactions, whose transaction type has `Legacy`, `Eip2930` and `Eip1559` variants: requests
+/// with blob fields fail to build with
+/// [`TransactionBuilderError::UnsupportedTxType`](crate::TransactionBuilderError::UnsupportedTxType),
+/// and the other transaction types of the network can't be built.
+macro_rules! impl_tx_builder {
+    ($network:ident, $request:ty) => {
+        const _: () = {
+            use crate::{BuildResult, Network, TransactionBuilder, TransactionBuilderError};
+            use alloy_consensus::BlobTransactionSidecar;
+            use core::ops::{Deref, DerefMut};
+
+            impl TransactionBuilder<$network> for $request {
+                impl_tx_builder!(@fields);
+
+                fn max_fee_per_blob_gas(&self) -> Option<u128> {
+                    self.deref().max_fee_per_blob_gas()
+                }
+
+                fn set_max_fee_per_blob_gas(&mut self, max_fee_per_blob_gas: u128) {
+                    self.deref_mut().set_max_fee_per_blob_gas(max_fee_per_blob_gas)
+                }
+
+                fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar> {
+                    self.deref().blob_sidecar()
+                }
+
+                fn set_blob_sidecar(&mut self, sidecar: BlobTransactionSidecar) {
+                    self.deref_mut().set_blob_sidecar(sidecar)
+                }
+
+                fn complete_type(
+                    &self,
+                    ty: <$network as Network>::TxType,
+                ) -> Result<(), Vec<&'static str>> {
+                    self.deref().complete_type(ty)
+                }
+
+                fn can_build(&self) -> bool {
+                    self.deref().can_build()
+                }
+
+                fn output_tx_type(&self) -> <$network as Network>::TxType {
+                    self.deref().output_tx_type()
+                }
+
+                fn output_tx_type_checked(&self) -> Option<<$network as Network>::TxType> {
+                    self.deref().output_tx_type_checked()
+                }
+
+                fn prep_for_submission(&mut self) {
+                    self.deref_mut().prep_for_submission()
+                }
+
+                fn build_unsigned(
+                    self,
+                ) -> BuildResult<<$network as Network>::UnsignedTx, $network> {
+                    if let Err((tx_type, missing)) = self.missing_keys() {
+                        return Err((
+                            self,
+                            TransactionBuilderError::InvalidTransactionRequest(tx_type, missing),
+                        ));
+                    }
+                    Ok(self.inner.build_typed_tx().expect("checked by missing_keys"))
+                }
+
+                impl_tx_builder!(@build $network);
+            }
+        };
+    };
+
+    ($network:ident, $request:ty, $tx_type:ident, $name:literal) => {
+        const _: () = {
+            use crate::{BuildResult, Network, TransactionBuilder, TransactionBuilderError};
+            use alloy_consensus::{BlobTransactionSidecar, TxType};
+            use core::ops::{Deref, DerefMut};
+
+            /// Blob transactions are not supported, so requests with blob fields map to EIP-1559,
+            /// and are rejected by `can_build` and `build_unsigned`.
+            const fn network_type(ty: TxType) -> $tx_type {
+                match ty {
+                    TxType::Legacy => $tx_type::Legacy,
+                    TxType::Eip2930 => $tx_type::Eip2930,
+                    TxType::Eip1559 | TxType::Eip4844 => $tx_type::Eip1559,
+                }
+            }
+
+            /// Whether the request has fields only blob transactions have.
+            fn has_blob_fields(request: &$request) -> bool {
+                request.sidecar.is_some() || request.max_fee_per_blob_gas.is_some()
+            }
+
+            impl TransactionBuilder<$network> for $request {
+                impl_tx_builder!(@fields);
+
+                fn max_fee_per_blob_gas(&self) -> Option<u128> {
+                    self.deref().max_fee_per_blob_gas()
+                }
+
+                #[doc = concat!(
+                            "EIP-4844 transactions are not supported on ", $name,
+                            ", so the request will fail to build."
+                        )]
+                fn set_max_fee_per_blob_gas(&mut self, max_fee_per_blob_gas: u128) {
+                    self.deref_mut().set_max_fee_per_blob_gas(max_fee_per_blob_gas)
+                }
+
+                fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar> {
+                    self.deref().blob_sidecar()
+                }
+
+                #[doc = concat!(
+                            "EIP-4844 transactions are not supported on ", $name,
+                            ", so the request will fail to build."
+                        )]
+                fn set_blob_sidecar(&mut self, sidecar: BlobTransactionSidecar) {
+                    self.deref_mut().set_blob_sidecar(sidecar)
+                }
+
+                fn complete_type(
+                    &self,
+                    ty: <$network as Network>::TxType,
+                ) -> Result<(), Vec<&'static str>> {
+                    match ty {
+                        $tx_type::Legacy => self.complete_legacy(),
+                        $tx_type::Eip2930 => self.complete_2930(),
+                        $tx_type::Eip1559 => self.complete_1559(),
+                        _ => Err(vec!["supported tx type"]),
+                    }
+                }
+
+                fn can_build(&self) -> bool {
+                    !has_blob_fields(self) && self.output_tx_type_checked().is_some()
+                }
+
+                fn output_tx_type(&self) -> <$network as Network>::TxType {
+                    network_type(self.preferred_type())
+                }
+
+                fn output_tx_type_checked(&self) -> Option<<$network as Network>::TxType> {
+                    let ty = network_type(self.preferred_type());
+                    self.complete_type(ty).ok().map(|_| ty)
+                }
+
+                fn prep_for_submission(&mut self) {
+                    self.deref_mut().prep_for_submission()
+                }
+
+                fn build_unsigned(
+                    self,
+                ) -> BuildResult<<$network as Network>::UnsignedTx, $network> {
+                    if has_blob_fields(&self) {
+                        return Err((self, TransactionBuilderError::UnsupportedTxType("EIP-4844")));
+                    }
+                    if let Err((tx_type, missing)) = self.missing_keys() {
+                        return Err((
+                            self,
+                            TransactionBuilderError::InvalidTransactionRequest(
+                                network_type(tx_type),
+                                missing,
+                            ),
+                        ));
+                    }
+                    Ok(self.inner.build_typed_tx().expect("checked by missing_keys"))
+                }
+
+                impl_tx_builder!(@build $network);
+            }
+        };
+    };
+
+    (@fields) => {
+        fn chain_id(&self) -> Option<alloy_primitives::ChainId> {
+            self.deref().chain_id()
+        }
+
+        fn set_chain_id(&mut self, chain_id: alloy_primitives::ChainId) {
+            self.deref_mut().set_chain_id(chain_id)
+        }
+
+        fn nonce(&self) -> Option<u64> {
+            self.deref().nonce()
+        }
+
+        fn set_nonce(&mut self, nonce: u64) {
+            self.deref_mut().set_nonce(nonce)
+        }
+
+        fn input(&self) -> Option<&alloy_primitives::Bytes> {
+            self.deref().input()
+        }
+
+        fn set_input<T: Into<alloy_primitives::Bytes>>(&mut self, input: T) {
+            self.deref_mut().set_input(input);
+        }
+
+        fn from(&self) -> Option<alloy_primitives::Address> {
+            self.deref().from()
+        }
+
+        fn set_from(&mut self, from: alloy_primitives::Address) {
+            self.deref_mut().set_from(from);
+        }
+
+        fn kind(&self) -> Option<alloy_primitives::TxKind> {
+            self.deref().kind()
+        }
+
+        fn clear_kind(&mut self) {
+            self.deref_mut().clear_kind()
+        }
+
+        fn set_kind(&mut self, kind: alloy_primitives::TxKind) {
+            self.deref_mut().set_kind(kind)
+        }
+
+        fn value(&self) -> Option<alloy_primitives::U256> {
+            self.deref().value()
+        }
+
+        fn set_value(&mut self, value: alloy_primitives::U256) {
+            self.deref_mut().set_value(value)
+        }
+
+        fn gas_price(&self) -> Option<u128> {
+            self.deref().gas_price()
+        }
+
+        fn set_gas_price(&mut self, gas_price: u128) {
+            self.deref_mut().set_gas_price(gas_price);
+        }
+
+        fn max_fee_per_gas(&self) -> Option<u128> {
+            self.deref().max_fee_per_gas()
+        }
+
+        fn set_max_fee_per_gas(&mut self, max_fee_per_gas: u128) {
+            self.deref_mut().set_max_fee_per_gas(max_fee_per_gas);
+        }
+
+        fn max_priority_fee_per_gas(&self) -> Option<u128> {
+            self.deref().max_priority_fee_per_gas()
+        }
+
+        fn set_max_priority_fee_per_gas(&mut self, max_priority_fee_per_gas: u128) {
+            self.deref_mut().set_max_priority_fee_per_gas(max_priority_fee_per_gas);
+        }
+
+        fn gas_limit(&self) -> Option<u128> {
+            self.deref().gas_limit()
+        }
+
+        fn set_gas_limit(&mut self, gas_limit: u128) {
+            self.deref_mut().set_gas_limit(gas_limit);
+        }
+
+        fn access_list(&self) -> Option<&alloy_rpc_types::AccessList> {
+            self.deref().access_list()
+        }
+
+        fn set_access_list(&mut self, access_list: alloy_rpc_types::AccessList) {
+            self.deref_mut().set_access_list(access_list)
+        }
+
+        fn can_submit(&self) -> bool {
+            self.deref().can_submit()
+        }
+    };
+
+    (@build $network:ident) => {
+        async fn build<S: crate::NetworkSigner<$network>>(
+            self,
+            signer: &S,
+        ) -> Result<<$network as Network>::TxEnvelope, TransactionBuilderError<$network>> {
+            Ok(signer.sign_request(self).await?)
+        }
+    };
+}
@@ -0,0 +1,255 @@
+use crate::{
+    BuildResult, Network, NetworkSigner, Optimism, TransactionBuilder, TransactionBuilderError,
+};
+use alloy_consensus::{
+    optimism::{OpTxType, OpTypedTransaction},
+    BlobTransactionSidecar,
+};
+use alloy_primitives::{Address, Bytes, ChainId, TxKind, U256};
+use alloy_rpc_types::{optimism::OptimismTransactionRequest, AccessList};
+
+impl TransactionBuilder<Optimism> for OptimismTransactionRequest {
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    fn set_chain_id(&mut self, chain_id: ChainId) {
+        self.chain_id = Some(chain_id);
+    }
+
+    fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    fn set_nonce(&mut self, nonce: u64) {
+        self.nonce = Some(nonce);
+    }
+
+    fn input(&self) -> Option<&Bytes> {
+        self.input.input()
+    }
+
+    fn set_input<T: Into<Bytes>>(&mut self, input: T) {
+        self.input.input = Some(input.into());
+    }
+
+    fn from(&self) -> Option<Address> {
+        self.inner.from
+    }
+
+    fn set_from(&mut self, from: Address) {
+        self.inner.from = Some(from);
+    }
+
+    fn kind(&self) -> Option<TxKind> {
+        self.to
+    }
+
+    fn set_kind(&mut self, kind: TxKind) {
+        self.to = Some(kind);
+    }
+
+    fn clear_kind(&mut self) {
+        self.to = None;
+    }
+
+    fn value(&self) -> Option<U256> {
+        self.value
+    }
+
+    fn set_value(&mut self, value: U256) {
+        self.value = Some(value)
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        self.gas_price
+    }
+
+    fn set_gas_price(&mut self, gas_price: u128) {
+        self.gas_price = Some(gas_price);
+    }
+
+    fn max_fee_per_gas(&self) -> Option<u128> {
+        self.max_fee_per_gas
+    }
+
+    fn set_max_fee_per_gas(&mut self, max_fee_per_gas: u128) {
+        self.max_fee_per_gas = Some(max_fee_per_gas);
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        self.max_priority_fee_per_gas
+    }
+
+    fn set_max_priority_fee_per_gas(&mut self, max_priority_fee_per_gas: u128) {
+        self.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        None
+    }
+
+    /// EIP-4844 transactions are not supported on L2, so this is a no-op.
+    fn set_max_fee_per_blob_gas(&mut self, _max_fee_per_blob_gas: u128) {}
+
+    fn gas_limit(&self) -> Option<u128> {
+        self.gas
+    }
+
+    fn set_gas_limit(&mut self, gas_limit: u128) {
+        self.gas = Some(gas_limit);
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        self.access_list.as_ref()
+    }
+
+    fn set_access_list(&mut self, access_list: AccessList) {
+        self.access_list = Some(access_list);
+    }
+
+    fn blob_sidecar(&self) -> Option<&BlobTransactionSidecar> {
+        None
+    }
+
+    /// EIP-4844 transactions are not supported on L2, so this is a no-op.
+    fn set_blob_sidecar(&mut self, _sidecar: BlobTransactionSidecar) {}
+
+    fn complete_type(&self, ty: OpTxType) -> Result<(), Vec<&'static str>> {
+        self.complete_type(ty)
+    }
+
+    fn can_submit(&self) -> bool {
+        // deposits are derived from L1, and can't be submitted to the L2 node
+        self.inner.from.is_some() && self.source_hash.is_none()
+    }
+
+    fn can_build(&self) -> bool {
+        self.buildable_type().is_some()
+    }
+
+    fn output_tx_type(&self) -> OpTxType {
+        self.preferred_type()
+    }
+
+    fn output_tx_type_checked(&self) -> Option<OpTxType> {
+        self.buildable_type()
+    }
+
+    fn prep_for_submission(&mut self) {
+        self.transaction_type = Some(self.preferred_type().into());
+        self.trim_conflicting_keys();
+    }
+
+    fn build_unsigned(self) -> BuildResult<OpTypedTransaction, Optimism> {
+        if let Err((tx_type, missing)) = self.missing_keys() {
+            return Err((
+                self,
+                TransactionBuilderError::InvalidTransactionRequest(tx_type, missing),
+            ));
+        }
+        Ok(self.build_typed_tx().expect("checked by missing_keys"))
+    }
+
+    async fn build<S: NetworkSigner<Optimism>>(
+        self,
+        signer: &S,
+    ) -> Result<<Optimism as Network>::TxEnvelope, TransactionBuilderError<Optimism>> {
+        Ok(signer.sign_request(self).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TransactionBuilder, TransactionBuilderError};
+    use alloy_consensus::{
+        optimism::{OpTxType, OpTypedTransaction, TxDeposit},
+        BlobTransactionSidecar, TxEip1559,
+    };
+    use alloy_primitives::{Address, B256};
+    use alloy_rpc_types::{optimism::OptimismTransactionRequest, AccessList};
+
+    #[test]
+    fn from_eip1559_to_tx_req() {
+        let tx = TxEip1559 {
+            chain_id: 10,
+            nonce: 0,
+            gas_limit: 21_000,
+            to: Address::ZERO.into(),
+            max_priority_fee_per_gas: 20e9 as u128,
+            max_fee_per_gas: 20e9 as u128,
+            ..Default::default()
+        };
+        let tx_req: OptimismTransactionRequest = OpTypedTransaction::from(tx).into();
+        tx_req.build_unsigned().unwrap();
+    }
+
+    #[test]
+    fn from_deposit_to_tx_req() {
+        let tx =
+            TxDeposit { from: Address::with_last_byte(1), gas_limit: 21_000, ..Default::default() };
+        let tx_req: OptimismTransactionRequest = OpTypedTransaction::from(tx.clone()).into();
+        assert!(!tx_req.can_submit());
+        assert_eq!(tx_req.build_unsigned().unwrap(), OpTypedTransaction::Deposit(tx));
+    }
+
+    #[test]
+    fn test_1559_when_sidecar() {
+        let request = OptimismTransactionRequest::default()
+            .with_nonce(1)
+            .with_gas_limit(0)
+            .with_max_fee_per_gas(0)
+            .with_max_priority_fee_per_gas(0)
+            .with_to(Address::ZERO)
+            .with_blob_sidecar(BlobTransactionSidecar::default())
+            .with_max_fee_per_blob_gas(0);
+
+        let tx = request.build_unsigned().unwrap();
+
+        assert!(matches!(tx, OpTypedTransaction::Eip1559(_)));
+    }
+
+    #[test]
+    fn test_2930_when_access_list() {
+        let request = OptimismTransactionRequest::default()
+            .with_nonce(1)
+            .with_gas_limit(0)
+            .with_to(Address::ZERO)
+            .with_gas_price(0)
+            .with_access_list(AccessList::default());
+
+        let tx = request.build_unsigned().unwrap();
+
+        assert!(matches!(tx, OpTypedTransaction::Eip2930(_)));
+    }
+
+    #[test]
+    fn test_invalid_deposit_fields() {
+        let request = OptimismTransactionRequest::default().source_hash(B256::ZERO);
+
+        let error = request.build_unsigned().unwrap_err();
+
+        let (_, TransactionBuilderError::InvalidTransactionRequest(tx_type, errors)) = error else {
+            panic!("wrong variant")
+        };
+
+        assert_eq!(tx_type, OpTxType::Deposit);
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains(&"from"));
+        assert!(errors.contains(&"gas_limit"));
+    }
+
+    #[test]
+    fn test_invalid_1559_fields() {
+        let request = OptimismTransactionRequest::default();
+
+        let error = request.build_unsigned().unwrap_err();
+
+        let (_, TransactionBuilderError::InvalidTransactionRequest(tx_type, errors)) = error else {
+            panic!("wrong variant")
+        };
+
+        assert_eq!(tx_type, OpTxType::Eip1559);
+        assert_eq!(errors.len(), 5);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{Network, ReceiptResponse};
+use alloy_consensus::TxReceipt;
+use alloy_rpc_types::optimism::OptimismTransactionReceipt;
+
+mod builder;
+
+mod signer;
+
+/// Types for an OP-stack network, such as OP Mainnet or Base.
+#[derive(Clone, Copy, Debug)]
+pub struct Optimism {
+    _private: (),
+}
+
+impl Network for Optimism {
+    type TxType = alloy_consensus::optimism::OpTxType;
+
+    type TxEnvelope = alloy_consensus::optimism::OpTxEnvelope;
+
+    type UnsignedTx = alloy_consensus::optimism::OpTypedTransaction;
+
+    type ReceiptEnvelope = alloy_consensus::optimism::OpReceiptEnvelope;
+
+    type Header = alloy_consensus::Header;
+
+    type TransactionRequest = alloy_rpc_types::optimism::OptimismTransactionRequest;
+
+    type TransactionResponse = alloy_rpc_types::optimism::OptimismTransaction;
+
+    type ReceiptResponse = OptimismTransactionReceipt;
+
+    type HeaderResponse = alloy_rpc_types::Header;
+}
+
+impl ReceiptResponse for OptimismTransactionReceipt {
+    fn contract_address(&self) -> Option<alloy_primitives::Address> {
+        self.inner.contract_address
+    }
+
+    fn block_number(&self) -> Option<alloy_primitives::BlockNumber> {
+        self.inner.block_number
+    }
+
+    fn block_hash(&self) -> Option<alloy_primitives::BlockHash> {
+        self.inner.block_hash
+    }
+
+    fn transaction_index(&self) -> Option<u64> {
+        self.inner.transaction_index
+    }
+
+    fn status(&self) -> bool {
+        self.inner.inner.status()
+    }
+
+    fn gas_used(&self) -> u128 {
+        self.inner.gas_used
+    }
+
+    fn effective_gas_price(&self) -> u128 {
+        self.inner.effective_gas_price
+    }
+
+    fn encoded_2718(&self) -> Option<Vec<u8>> {
+        Some(alloy_eips::eip2718::Encodable2718::encoded_2718(&self.inner.inner))
+    }
+
+    fn logs(&self) -> &[alloy_rpc_types::Log] {
+        self.inner.inner.logs()
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{Ethereum, EthereumSigner, NetworkSigner, Optimism};
+use alloy_consensus::{
+    optimism::{OpTxEnvelope, OpTypedTransaction},
+    SignableTransaction,
+};
+use alloy_primitives::Address;
+use async_trait::async_trait;
+
+/// Deposits are not signed, and are returned as is.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl NetworkSigner<Optimism> for EthereumSigner {
+    fn default_signer_address(&self) -> Address {
+        <Self as NetworkSigner<Ethereum>>::default_signer_address(self)
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        <Self as NetworkSigner<Ethereum>>::has_signer_for(self, address)
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        <Self as NetworkSigner<Ethereum>>::signer_addresses(self)
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: Address,
+        tx: OpTypedTransaction,
+    ) -> alloy_signer::Result<OpTxEnvelope> {
+        match tx {
+            OpTypedTransaction::Legacy(mut t) => {
+                let sig = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+            OpTypedTransaction::Eip2930(mut t) => {
+                let sig = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+            OpTypedTransaction::Eip1559(mut t) => {
+                let sig = self.sign_transaction_inner(sender, &mut t).await?;
+                Ok(t.into_signed(sig).into())
+            }
+            OpTypedTransaction::Deposit(t) => Ok(t.into()),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use alloy_rpc_types::polygon::{PolygonTransactionRequest, PolygonTxType};
+
+use crate::polygon::Polygon;
+
+impl_tx_builder!(Polygon, PolygonTransactionRequest, PolygonTxType, "Polygon");
+
+#[cfg(test)]
+mod tests {
+    use crate::{TransactionBuilder, TransactionBuilderError};
+    use alloy_consensus::{BlobTransactionSidecar, TypedTransaction};
+    use alloy_primitives::Address;
+    use alloy_rpc_types::polygon::{PolygonTransactionRequest, PolygonTxType};
+
+    #[test]
+    fn test_sidecar_unsupported() {
+        let request = PolygonTransactionRequest::default()
+            .with_nonce(1)
+            .with_gas_limit(0)
+            .with_max_fee_per_gas(0)
+            .with_max_priority_fee_per_gas(0)
+            .with_to(Address::ZERO);
+        assert!(request.can_build());
+        assert!(matches!(request.clone().build_unsigned(), Ok(TypedTransaction::Eip1559(_))));
+
+        let request = request.with_blob_sidecar(BlobTransactionSidecar::default());
+        assert!(request.blob_sidecar().is_some());
+        assert!(!request.can_build());
+
+        let (_, error) = request.build_unsigned().unwrap_err();
+        assert!(matches!(error, TransactionBuilderError::UnsupportedTxType("EIP-4844")));
+    }
+
+    #[test]
+    fn test_invalid_1559_fields() {
+        let request = PolygonTransactionRequest::default();
+
+        let error = request.build_unsigned().unwrap_err();
+
+        let (_, TransactionBuilderError::InvalidTransactionRequest(tx_type, errors)) = error else {
+            panic!("wrong variant")
+        };
+
+        assert_eq!(tx_type, PolygonTxType::Eip1559);
+        assert_eq!(errors.len(), 5);
+    }
+
+    #[test]
+    fn test_system_tx_types() {
+        let request = PolygonTransactionRequest::default();
+        assert!(request.complete_type(PolygonTxType::StateSync).is_err());
+    }
+}
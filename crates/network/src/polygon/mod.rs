@@ -0,0 +1,74 @@
+use crate::{Network, ReceiptResponse};
+use alloy_rpc_types::polygon::PolygonTransactionReceipt;
+
+mod builder;
+
+/// Types for a Polygon PoS network, such as Polygon mainnet or Amoy.
+///
+/// Users sign the Ethereum transaction types, so [`EthereumSigner`](crate::EthereumSigner) can
+/// be used with this network. Blocks and receipts may include the state-sync transactions of Bor,
+/// see [`PolygonTransaction::is_state_sync`].
+///
+/// [`PolygonTransaction::is_state_sync`]: alloy_rpc_types::polygon::PolygonTransaction::is_state_sync
+#[derive(Clone, Copy, Debug)]
+pub struct Polygon {
+    _private: (),
+}
+
+impl Network for Polygon {
+    type TxType = alloy_rpc_types::polygon::PolygonTxType;
+
+    type TxEnvelope = alloy_consensus::TxEnvelope;
+
+    type UnsignedTx = alloy_consensus::TypedTransaction;
+
+    type ReceiptEnvelope = alloy_consensus::AnyReceiptEnvelope;
+
+    type Header = alloy_consensus::Header;
+
+    type TransactionRequest = alloy_rpc_types::polygon::PolygonTransactionRequest;
+
+    type TransactionResponse = alloy_rpc_types::polygon::PolygonTransaction;
+
+    type ReceiptResponse = PolygonTransactionReceipt;
+
+    type HeaderResponse = alloy_rpc_types::Header;
+}
+
+impl ReceiptResponse for PolygonTransactionReceipt {
+    fn contract_address(&self) -> Option<alloy_primitives::Address> {
+        self.inner.contract_address
+    }
+
+    fn block_number(&self) -> Option<alloy_primitives::BlockNumber> {
+        self.inner.block_number
+    }
+
+    fn block_hash(&self) -> Option<alloy_primitives::BlockHash> {
+        self.inner.block_hash
+    }
+
+    fn transaction_index(&self) -> Option<u64> {
+        self.inner.transaction_index
+    }
+
+    fn status(&self) -> bool {
+        self.inner.inner.status()
+    }
+
+    fn gas_used(&self) -> u128 {
+        self.inner.gas_used
+    }
+
+    fn effective_gas_price(&self) -> u128 {
+        self.inner.effective_gas_price
+    }
+
+    fn encoded_2718(&self) -> Option<Vec<u8>> {
+        Some(alloy_eips::eip2718::Encodable2718::encoded_2718(&self.inner.inner))
+    }
+
+    fn logs(&self) -> &[alloy_rpc_types::Log] {
+        self.inner.inner.logs()
+    }
+}
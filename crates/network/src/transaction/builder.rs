@@ -16,13 +16,17 @@ pub type Unbuilt<N> = (<N as Network>::TransactionRequest, TransactionBuilderErr
 #[derive(Debug, thiserror::Error)]
 pub enum TransactionBuilderError<N: Network> {
     /// Invalid transaction request
-    #[error("{0} transaction can't be built due to missing keys: {1:?}")]
+    #[error("{0} transaction can't be built due to missing keys: {}", .1.join(", "))]
     InvalidTransactionRequest(N::TxType, Vec<&'static str>),
 
     /// Signer cannot produce signature type required for transaction.
     #[error("Signer cannot produce signature type required for transaction")]
     UnsupportedSignatureType,
 
+    /// The network does not support the transaction type the request needs.
+    #[error("{0} transactions are not supported by the network")]
+    UnsupportedTxType(&'static str),
+
     /// Signer error.
     #[error(transparent)]
     Signer(#[from] alloy_signer::Error),
@@ -339,6 +339,15 @@ impl Geth {
         self
     }
 
+    /// Launches geth in dev mode, with an ephemeral proof-of-authority chain and a prefunded
+    /// developer account. This is the default.
+    ///
+    /// This discards any previously set options that cannot be used in dev mode.
+    pub fn dev(mut self) -> Self {
+        self.mode = GethMode::Dev(Default::default());
+        self
+    }
+
     /// Sets the block-time which will be used when the `geth-cli` instance is launched.
     ///
     /// This will put the geth instance in `dev` mode, discarding any previously set options that
@@ -733,6 +742,15 @@ mod tests {
         });
     }
 
+    #[test]
+    fn explicit_dev_mode() {
+        run_with_tempdir(|temp_dir_path| {
+            // dev mode discards the p2p options
+            let geth = Geth::new().disable_discovery().dev().data_dir(temp_dir_path).spawn();
+            assert!(geth.p2p_port().is_none());
+        })
+    }
+
     #[test]
     fn dev_mode() {
         run_with_tempdir(|temp_dir_path| {
@@ -25,6 +25,9 @@ pub use anvil::{Anvil, AnvilInstance};
 pub mod geth;
 pub use geth::{Geth, GethInstance};
 
+pub mod reth;
+pub use reth::{Reth, RethInstance};
+
 /// 1 Ether = 1e18 Wei == 0x0de0b6b3a7640000 Wei
 pub const WEI_IN_ETHER: U256 = U256::from_limbs([0x0de0b6b3a7640000, 0x0, 0x0, 0x0]);
 
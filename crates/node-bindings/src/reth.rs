@@ -0,0 +1,481 @@
+//! Utilities for launching a reth dev-mode instance.
+
+use crate::unused_port;
+use alloy_genesis::Genesis;
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, BufRead, BufReader},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    time::{Duration, Instant},
+};
+use tempfile::TempDir;
+use thiserror::Error;
+use url::Url;
+
+/// How long we will wait for reth to indicate that it is ready.
+const RETH_STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The exposed APIs
+const API: &str = "eth,net,web3,txpool,admin,debug,trace";
+
+/// The reth command
+const RETH: &str = "reth";
+
+/// A reth instance. Will close the instance and remove its temporary data directory, if any,
+/// when dropped.
+///
+/// Construct this using [`Reth`].
+#[derive(Debug)]
+pub struct RethInstance {
+    pid: Child,
+    http_port: u16,
+    ws_port: u16,
+    auth_port: u16,
+    p2p_port: Option<u16>,
+    ipc: Option<PathBuf>,
+    data_dir: PathBuf,
+    genesis: Option<Genesis>,
+    // Removed once the process is killed, as fields are dropped after `Drop::drop`.
+    _temp_dir: Option<TempDir>,
+}
+
+impl RethInstance {
+    /// Returns the HTTP port of this instance
+    pub fn http_port(&self) -> u16 {
+        self.http_port
+    }
+
+    /// Returns the Websocket port of this instance
+    pub fn ws_port(&self) -> u16 {
+        self.ws_port
+    }
+
+    /// Returns the port of the authenticated Engine API of this instance
+    pub fn auth_port(&self) -> u16 {
+        self.auth_port
+    }
+
+    /// Returns the p2p port of this instance, if not in dev mode
+    pub fn p2p_port(&self) -> Option<u16> {
+        self.p2p_port
+    }
+
+    /// Returns the HTTP endpoint of this instance
+    pub fn endpoint(&self) -> String {
+        format!("http://localhost:{}", self.http_port)
+    }
+
+    /// Returns the Websocket endpoint of this instance
+    pub fn ws_endpoint(&self) -> String {
+        format!("ws://localhost:{}", self.ws_port)
+    }
+
+    /// Returns the IPC endpoint of this instance, if IPC is enabled
+    pub fn ipc_endpoint(&self) -> Option<String> {
+        self.ipc.as_ref().map(|ipc| ipc.display().to_string())
+    }
+
+    /// Returns the HTTP endpoint url of this instance
+    pub fn endpoint_url(&self) -> Url {
+        Url::parse(&self.endpoint()).unwrap()
+    }
+
+    /// Returns the Websocket endpoint url of this instance
+    pub fn ws_endpoint_url(&self) -> Url {
+        Url::parse(&self.ws_endpoint()).unwrap()
+    }
+
+    /// Returns the path to this instances' data directory
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir
+    }
+
+    /// Returns the genesis configuration used to configure this instance
+    pub fn genesis(&self) -> Option<&Genesis> {
+        self.genesis.as_ref()
+    }
+}
+
+impl Drop for RethInstance {
+    fn drop(&mut self) {
+        self.pid.kill().expect("could not kill reth");
+        // Wait for the process to release the data directory before it is removed.
+        let _ = self.pid.wait();
+    }
+}
+
+/// Errors that can occur when working with the [`Reth`].
+#[derive(Debug, Error)]
+pub enum RethError {
+    /// Could not create the data directory.
+    #[error("could not create directory: {0}")]
+    CreateDirError(std::io::Error),
+    /// No stdout was captured from the child process.
+    #[error("no stdout was captured from the process")]
+    NoStdout,
+    /// Timed out waiting for reth to start.
+    #[error("timed out waiting for reth to spawn; is reth installed?")]
+    Timeout,
+    /// Reth exited before it was ready.
+    #[error("reth exited before its RPC servers started: {0}")]
+    Exited(String),
+    /// A line could not be read from the reth stdout.
+    #[error("could not read line from reth stdout: {0}")]
+    ReadLineError(std::io::Error),
+    /// Genesis error
+    #[error("genesis error occurred: {0}")]
+    GenesisError(String),
+    /// Spawn reth error
+    #[error("could not spawn reth: {0}")]
+    SpawnError(std::io::Error),
+}
+
+/// Builder for launching `reth`.
+///
+/// By default, reth is launched in dev mode, mining a block for each transaction, with a
+/// temporary data directory.
+///
+/// # Panics
+///
+/// If `spawn` is called without `reth` being available in the user's $PATH
+///
+/// # Example
+///
+/// ```no_run
+/// use alloy_node_bindings::Reth;
+///
+/// let reth = Reth::new().dev().block_time(1).spawn();
+///
+/// println!("Reth running at `{}`", reth.endpoint());
+///
+/// drop(reth); // this will kill the instance
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "This Builder struct does nothing unless it is `spawn`ed"]
+pub struct Reth {
+    program: Option<PathBuf>,
+    http_port: Option<u16>,
+    ws_port: Option<u16>,
+    auth_port: Option<u16>,
+    p2p_port: Option<u16>,
+    ipc_path: Option<PathBuf>,
+    ipc_enabled: bool,
+    data_dir: Option<PathBuf>,
+    genesis: Option<Genesis>,
+    dev: bool,
+    block_time: Option<u64>,
+    discovery: bool,
+    args: Vec<String>,
+}
+
+impl Default for Reth {
+    fn default() -> Self {
+        Self {
+            program: None,
+            http_port: None,
+            ws_port: None,
+            auth_port: None,
+            p2p_port: None,
+            ipc_path: None,
+            ipc_enabled: false,
+            data_dir: None,
+            genesis: None,
+            dev: true,
+            block_time: None,
+            discovery: false,
+            args: Vec::new(),
+        }
+    }
+}
+
+impl Reth {
+    /// Creates an empty Reth builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a Reth builder which will execute `reth` at the given path.
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self::new().path(path)
+    }
+
+    /// Sets the `path` to the `reth` executable
+    ///
+    /// By default, it's expected that `reth` is in `$PATH`, see also
+    /// [`std::process::Command::new()`]
+    pub fn path<T: Into<PathBuf>>(mut self, path: T) -> Self {
+        self.program = Some(path.into());
+        self
+    }
+
+    /// Launches reth in dev mode, with a local chain that mines its own blocks and prefunded
+    /// development accounts. This is the default.
+    pub fn dev(mut self) -> Self {
+        self.dev = true;
+        self
+    }
+
+    /// Sets the interval in seconds at which the dev chain will mine new blocks, instead of
+    /// mining a block for each transaction.
+    ///
+    /// This will put the reth instance in dev mode.
+    pub fn block_time(mut self, block_time: u64) -> Self {
+        self.dev = true;
+        self.block_time = Some(block_time);
+        self
+    }
+
+    /// Sets the port for HTTP connections.
+    pub fn http_port(mut self, port: u16) -> Self {
+        self.http_port = Some(port);
+        self
+    }
+
+    /// Sets the port for Websocket connections.
+    pub fn ws_port(mut self, port: u16) -> Self {
+        self.ws_port = Some(port);
+        self
+    }
+
+    /// Sets the port for authenticated RPC connections.
+    pub fn auth_port(mut self, port: u16) -> Self {
+        self.auth_port = Some(port);
+        self
+    }
+
+    /// Sets the port which will be used for incoming p2p connections.
+    ///
+    /// This will put the reth instance into non-dev mode.
+    pub fn p2p_port(mut self, port: u16) -> Self {
+        self.dev = false;
+        self.p2p_port = Some(port);
+        self
+    }
+
+    /// Enables peer discovery, which is disabled by default.
+    ///
+    /// This will put the reth instance into non-dev mode.
+    pub fn enable_discovery(mut self) -> Self {
+        self.dev = false;
+        self.discovery = true;
+        self
+    }
+
+    /// Enable IPC for the reth instance.
+    pub fn enable_ipc(mut self) -> Self {
+        self.ipc_enabled = true;
+        self
+    }
+
+    /// Sets the IPC path for the socket, and enables IPC.
+    pub fn ipc_path<T: Into<PathBuf>>(mut self, path: T) -> Self {
+        self.ipc_enabled = true;
+        self.ipc_path = Some(path.into());
+        self
+    }
+
+    /// Sets the data directory for reth. By default, a temporary directory is created, which is
+    /// removed when the instance is dropped.
+    pub fn data_dir<T: Into<PathBuf>>(mut self, path: T) -> Self {
+        self.data_dir = Some(path.into());
+        self
+    }
+
+    /// Sets the genesis of the chain, instead of the dev chain.
+    ///
+    /// The genesis is written to the data directory, and used as the chain specification.
+    pub fn genesis(mut self, genesis: Genesis) -> Self {
+        self.genesis = Some(genesis);
+        self
+    }
+
+    /// Adds an argument to pass to `reth node`.
+    pub fn arg<T: Into<String>>(mut self, arg: T) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Adds multiple arguments to pass to `reth node`.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Consumes the builder and spawns `reth`.
+    ///
+    /// # Panics
+    ///
+    /// If spawning the instance fails at any point.
+    #[track_caller]
+    pub fn spawn(self) -> RethInstance {
+        self.try_spawn().unwrap()
+    }
+
+    /// Consumes the builder and spawns `reth`. If spawning fails, returns an error.
+    pub fn try_spawn(self) -> Result<RethInstance, RethError> {
+        let mut cmd = Command::new(self.program.as_deref().unwrap_or(RETH.as_ref()));
+        // reth uses stdout for its logs
+        cmd.stdout(Stdio::piped()).stderr(Stdio::null());
+        cmd.arg("node").arg("--color").arg("never").arg("--log.file.max-files").arg("0");
+
+        let (temp_dir, data_dir) = match self.data_dir {
+            Some(data_dir) => {
+                create_dir_all(&data_dir).map_err(RethError::CreateDirError)?;
+                (None, data_dir)
+            }
+            None => {
+                let temp_dir = TempDir::with_prefix("reth-").map_err(RethError::CreateDirError)?;
+                let data_dir = temp_dir.path().to_path_buf();
+                (Some(temp_dir), data_dir)
+            }
+        };
+        cmd.arg("--datadir").arg(&data_dir);
+
+        if let Some(genesis) = &self.genesis {
+            let genesis_path = data_dir.join("genesis.json");
+            let file = File::create(&genesis_path).map_err(|_| {
+                RethError::GenesisError("could not create genesis file".to_string())
+            })?;
+            serde_json::to_writer_pretty(file, genesis).map_err(|_| {
+                RethError::GenesisError("could not write genesis to file".to_string())
+            })?;
+            cmd.arg("--chain").arg(genesis_path);
+        }
+
+        // Reserve every port, so that parallel instances don't collide on the defaults.
+        let mut http_port = self.http_port.unwrap_or_else(unused_port);
+        let mut ws_port = self.ws_port.unwrap_or_else(unused_port);
+        let auth_port = self.auth_port.unwrap_or_else(unused_port);
+        let p2p_port = self.p2p_port.unwrap_or_else(unused_port);
+
+        cmd.arg("--http").arg("--http.port").arg(http_port.to_string());
+        cmd.arg("--http.api").arg(API);
+        cmd.arg("--ws").arg("--ws.port").arg(ws_port.to_string());
+        cmd.arg("--ws.api").arg(API);
+        cmd.arg("--authrpc.port").arg(auth_port.to_string());
+        cmd.arg("--port").arg(p2p_port.to_string());
+        cmd.arg("--discovery.port").arg(p2p_port.to_string());
+
+        if !self.discovery {
+            cmd.arg("--disable-discovery");
+        }
+
+        let ipc = if self.ipc_enabled {
+            let ipc = self.ipc_path.unwrap_or_else(|| data_dir.join("reth.ipc"));
+            cmd.arg("--ipcpath").arg(&ipc);
+            Some(ipc)
+        } else {
+            cmd.arg("--ipcdisable");
+            None
+        };
+
+        if self.dev {
+            cmd.arg("--dev");
+            if let Some(block_time) = self.block_time {
+                cmd.arg("--dev.block-time").arg(format!("{block_time}s"));
+            }
+        }
+
+        cmd.args(self.args);
+
+        let mut child = cmd.spawn().map_err(RethError::SpawnError)?;
+        let stdout = child.stdout.take().ok_or(RethError::NoStdout)?;
+
+        let start = Instant::now();
+        let mut reader = BufReader::new(stdout);
+        let mut http_started = false;
+        let mut ws_started = false;
+        let mut last_line = String::new();
+
+        while !(http_started && ws_started) {
+            if start + RETH_STARTUP_TIMEOUT <= Instant::now() {
+                let _ = child.kill();
+                return Err(RethError::Timeout);
+            }
+
+            let mut line = String::with_capacity(120);
+            if reader.read_line(&mut line).map_err(RethError::ReadLineError)? == 0 {
+                let _ = child.wait();
+                return Err(RethError::Exited(last_line));
+            }
+
+            // Use the actual ports, in case port 0 was requested.
+            if line.contains("RPC HTTP server started") {
+                if let Some(addr) = extract_url(&line) {
+                    http_port = addr.port();
+                }
+                http_started = true;
+            }
+            if line.contains("RPC WS server started") {
+                if let Some(addr) = extract_url(&line) {
+                    ws_port = addr.port();
+                }
+                ws_started = true;
+            }
+            last_line = line;
+        }
+
+        // Keep draining the logs, so that reth does not block on a full pipe.
+        std::thread::spawn(move || io::copy(&mut reader, &mut io::sink()));
+
+        Ok(RethInstance {
+            pid: child,
+            http_port,
+            ws_port,
+            auth_port,
+            p2p_port: (!self.dev).then_some(p2p_port),
+            ipc,
+            data_dir,
+            genesis: self.genesis,
+            _temp_dir: temp_dir,
+        })
+    }
+}
+
+/// Extracts the address reth reports a server listening on.
+fn extract_url(line: &str) -> Option<SocketAddr> {
+    let start = line.find("url=")? + "url=".len();
+    let end = line[start..].find(char::is_whitespace).map_or(line.len(), |i| start + i);
+    line[start..end].parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_url() {
+        let line = "2024-05-06T10:51:26.186605Z  INFO RPC HTTP server started url=127.0.0.1:8545";
+        assert_eq!(extract_url(line), Some(SocketAddr::from(([127, 0, 0, 1], 8545))));
+    }
+
+    #[test]
+    fn dev_mode() {
+        let reth = Reth::new().spawn();
+        assert!(reth.p2p_port().is_none());
+        assert!(reth.data_dir().exists());
+    }
+
+    #[test]
+    fn temp_data_dir_removed() {
+        let reth = Reth::new().dev().block_time(1).spawn();
+        let data_dir = reth.data_dir().to_path_buf();
+        drop(reth);
+        assert!(!data_dir.exists());
+    }
+
+    #[test]
+    fn explicit_data_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let reth = Reth::new().data_dir(temp_dir.path()).spawn();
+        assert_eq!(reth.data_dir(), temp_dir.path());
+        drop(reth);
+        assert!(temp_dir.path().exists());
+    }
+}
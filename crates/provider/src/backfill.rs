@@ -0,0 +1,530 @@
+//! Throttled downloads of historical blocks, receipts and logs.
+//!
+//! A [`Backfill`] splits a block range into chunks, and fetches them with a bounded number of
+//! requests in flight, an optional request rate budget, and retries of transient errors. Chunks
+//! are delivered as a [`Stream`], in block order or as soon as they are fetched. With a
+//! [`KvStore`], progress is checkpointed, so that a restarted backfill skips the chunks already
+//! processed.
+//!
+//! ```no_run
+//! use alloy_provider::{backfill::Backfill, cursor::MemoryKvStore, ProviderBuilder};
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = ProviderBuilder::new().on_http("https://eth.merkle.io".parse()?);
+//! let store = MemoryKvStore::default();
+//! let backfill = Backfill::new(provider, 19_000_000..=19_010_000)
+//!     .concurrency(8)
+//!     .rate_limit(25)
+//!     .checkpoint(&store, "blocks");
+//! let mut chunks = Box::pin(backfill.blocks(false));
+//! while let Some(chunk) = chunks.next().await {
+//!     let chunk = chunk?;
+//!     println!("fetched blocks {:?}", chunk.range);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    cursor::{KvStore, MemoryKvStore},
+    Provider,
+};
+use alloy_json_rpc::ErrorKind;
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::BlockNumber;
+use alloy_rpc_types::{Block, BlockId, Filter, Log};
+use alloy_transport::{RetryableError, Transport, TransportError, TransportResult};
+use futures::{pin_mut, Future, Stream, StreamExt};
+use std::{
+    collections::BTreeMap, marker::PhantomData, ops::RangeInclusive, sync::Mutex, time::Duration,
+};
+use tokio::sync::Semaphore;
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{sleep, sleep_until, Instant};
+// Timers of `std` and `tokio` are not available in the browser.
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::{
+    std::Instant,
+    tokio::{sleep, sleep_until},
+};
+
+/// The default number of blocks per chunk.
+const DEFAULT_CHUNK_SIZE: u64 = 100;
+
+/// The default number of requests in flight.
+const DEFAULT_CONCURRENCY: usize = 4;
+
+/// The default number of retries of a failed request.
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// The default delay before the first retry of a failed request.
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// A range of blocks fetched by a [`Backfill`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BackfillChunk<T> {
+    /// The blocks covered by the chunk.
+    pub range: RangeInclusive<BlockNumber>,
+    /// The items fetched for the blocks of the chunk.
+    pub items: Vec<T>,
+}
+
+/// Error yielded by a [`Backfill`] stream.
+#[derive(Debug, thiserror::Error)]
+pub enum BackfillError<E> {
+    /// An RPC request failed, after all its retries.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// The node does not know a block of the range.
+    #[error("block {0} not found")]
+    MissingBlock(BlockNumber),
+    /// The checkpoint store failed.
+    #[error(transparent)]
+    Store(E),
+}
+
+/// A download of a range of blocks, receipts or logs.
+///
+/// The range is split into chunks of [`chunk_size`](Self::chunk_size) blocks, and the stream
+/// returned by [`blocks`](Self::blocks), [`receipts`](Self::receipts) or [`logs`](Self::logs)
+/// yields the chunks as they are fetched. Failed requests are retried with an exponential backoff
+/// if their error is [retryable](RetryableError), and the stream ends after the first error.
+///
+/// When a checkpoint store is set, a chunk is recorded as processed once the stream is polled
+/// again after yielding it, and a later backfill with the same store and key resumes after the
+/// processed chunks. Chunks yielded before the stream was dropped may thus be yielded again.
+#[derive(Debug)]
+pub struct Backfill<P, T, N = Ethereum, S = MemoryKvStore> {
+    provider: P,
+    range: RangeInclusive<BlockNumber>,
+    chunk_size: u64,
+    concurrency: usize,
+    permits: Semaphore,
+    request_interval: Option<Duration>,
+    next_request: Mutex<Option<Instant>>,
+    max_retries: u32,
+    retry_backoff: Duration,
+    ordered: bool,
+    checkpoint: Option<(S, String)>,
+    _phantom: PhantomData<(T, N)>,
+}
+
+impl<P, T, N> Backfill<P, T, N> {
+    /// Creates a backfill of the given range of blocks.
+    pub fn new(provider: P, range: RangeInclusive<BlockNumber>) -> Self {
+        Self {
+            provider,
+            range,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            permits: Semaphore::new(DEFAULT_CONCURRENCY),
+            request_interval: None,
+            next_request: Mutex::new(None),
+            max_retries: DEFAULT_MAX_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            ordered: true,
+            checkpoint: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P, T, N, S> Backfill<P, T, N, S> {
+    /// Sets the number of blocks per chunk. Defaults to 100.
+    ///
+    /// Logs are fetched with one request per chunk, split in halves while the node reports that
+    /// it exceeds its [limits](ErrorKind::LimitExceeded). Blocks and receipts are fetched with one
+    /// request per block.
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets the maximum number of requests in flight. Defaults to 4.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self.permits = Semaphore::new(self.concurrency);
+        self
+    }
+
+    /// Limits the rate of requests, retries included. A rate of zero removes the limit, which is
+    /// the default.
+    pub fn rate_limit(mut self, requests_per_second: u32) -> Self {
+        self.request_interval =
+            (requests_per_second > 0).then(|| Duration::from_secs(1) / requests_per_second);
+        self
+    }
+
+    /// Sets the maximum number of retries of a failed request. Defaults to 3.
+    pub const fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry of a failed request, doubled on every further retry.
+    /// Defaults to 500ms.
+    pub const fn retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Yields chunks as soon as they are fetched, instead of in block order.
+    pub const fn unordered(mut self) -> Self {
+        self.ordered = false;
+        self
+    }
+
+    /// Checkpoints the progress of the backfill under `key` in `store`, and resumes from the
+    /// progress already stored there.
+    pub fn checkpoint<S2: KvStore>(
+        self,
+        store: S2,
+        key: impl Into<String>,
+    ) -> Backfill<P, T, N, S2> {
+        Backfill {
+            provider: self.provider,
+            range: self.range,
+            chunk_size: self.chunk_size,
+            concurrency: self.concurrency,
+            permits: self.permits,
+            request_interval: self.request_interval,
+            next_request: self.next_request,
+            max_retries: self.max_retries,
+            retry_backoff: self.retry_backoff,
+            ordered: self.ordered,
+            checkpoint: Some((store, key.into())),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<P, T, N, S> Backfill<P, T, N, S>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    S: KvStore,
+{
+    /// Returns a stream of the blocks of the range, with their full transactions if `full` is
+    /// set, and only their hashes otherwise.
+    pub fn blocks(
+        self,
+        full: bool,
+    ) -> impl Stream<Item = Result<BackfillChunk<Block<N::TransactionResponse>>, BackfillError<S::Error>>>
+    {
+        async_stream::stream! {
+            let this = &self;
+            let chunks = this.deliver(move |range| async move {
+                futures::future::try_join_all(range.map(|number| async move {
+                    this.request(|| this.provider.get_block_by_number(number.into(), full))
+                        .await?
+                        .ok_or(BackfillError::MissingBlock(number))
+                }))
+                .await
+            });
+            pin_mut!(chunks);
+            while let Some(chunk) = chunks.next().await {
+                yield chunk;
+            }
+        }
+    }
+
+    /// Returns a stream of the receipts of the blocks of the range, one list per block.
+    pub fn receipts(
+        self,
+    ) -> impl Stream<Item = Result<BackfillChunk<Vec<N::ReceiptResponse>>, BackfillError<S::Error>>>
+    {
+        async_stream::stream! {
+            let this = &self;
+            let chunks = this.deliver(move |range| async move {
+                futures::future::try_join_all(range.map(|number| async move {
+                    this.request(|| this.provider.get_block_receipts_by_id(BlockId::number(number)))
+                        .await?
+                        .ok_or(BackfillError::MissingBlock(number))
+                }))
+                .await
+            });
+            pin_mut!(chunks);
+            while let Some(chunk) = chunks.next().await {
+                yield chunk;
+            }
+        }
+    }
+
+    /// Returns a stream of the logs of the range matching `filter`, whose block range is ignored.
+    pub fn logs(
+        self,
+        filter: &Filter,
+    ) -> impl Stream<Item = Result<BackfillChunk<Log>, BackfillError<S::Error>>> {
+        let filter = filter.clone();
+        async_stream::stream! {
+            let (this, filter) = (&self, &filter);
+            let chunks = this.deliver(move |range| async move {
+                let mut ranges = vec![range];
+                let mut logs = Vec::new();
+                while let Some(range) = ranges.pop() {
+                    let (from, to) = range.into_inner();
+                    let filter = filter.clone().from_block(from).to_block(to);
+                    match this.request(|| this.provider.get_logs(&filter)).await {
+                        Ok(found) => logs.extend(found),
+                        Err(err) if from < to && err.error_kind() == Some(ErrorKind::LimitExceeded) => {
+                            debug!(%err, from, to, "log query exceeded node limits, splitting it");
+                            let mid = from + (to - from) / 2;
+                            ranges.extend([mid + 1..=to, from..=mid]);
+                        }
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+                Ok(logs)
+            });
+            pin_mut!(chunks);
+            while let Some(chunk) = chunks.next().await {
+                yield chunk;
+            }
+        }
+    }
+
+    /// Fetches the chunks of the range not processed yet with `fetch`, and checkpoints the
+    /// progress as they are consumed.
+    fn deliver<'a, I, F, Fut>(
+        &'a self,
+        fetch: F,
+    ) -> impl Stream<Item = Result<BackfillChunk<I>, BackfillError<S::Error>>> + 'a
+    where
+        I: 'a,
+        F: Fn(RangeInclusive<BlockNumber>) -> Fut + 'a,
+        Fut: Future<Output = Result<Vec<I>, BackfillError<S::Error>>> + 'a,
+    {
+        async_stream::stream! {
+            let (start, end) = self.range.clone().into_inner();
+            let mut progress = Progress { next: start, done: BTreeMap::new() };
+            if let Some((store, key)) = &self.checkpoint {
+                match store.get(key) {
+                    Ok(stored) => {
+                        if let Some(next) = stored.and_then(|value| value.try_into().ok()) {
+                            progress.next = u64::from_be_bytes(next).max(start);
+                        }
+                    }
+                    Err(err) => {
+                        yield Err(BackfillError::Store(err));
+                        return;
+                    }
+                }
+            }
+
+            let fetch = &fetch;
+            let chunks = futures::stream::iter(chunks(progress.next..=end, self.chunk_size)).map(
+                |range| async move {
+                    let items = fetch(range.clone()).await?;
+                    Ok::<_, BackfillError<S::Error>>(BackfillChunk { range, items })
+                },
+            );
+            let chunks = if self.ordered {
+                chunks.buffered(self.concurrency).left_stream()
+            } else {
+                chunks.buffer_unordered(self.concurrency).right_stream()
+            };
+            pin_mut!(chunks);
+            while let Some(chunk) = chunks.next().await {
+                let chunk = match chunk {
+                    Ok(chunk) => chunk,
+                    Err(err) => {
+                        yield Err(err);
+                        return;
+                    }
+                };
+                let range = chunk.range.clone();
+                yield Ok(chunk);
+
+                // The stream is polled again, so the consumer is done with the chunk.
+                if !progress.complete(range) {
+                    continue;
+                }
+                if let Some((store, key)) = &self.checkpoint {
+                    if let Err(err) = store.put(key, progress.next.to_be_bytes().to_vec()) {
+                        yield Err(BackfillError::Store(err));
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends a request within the concurrency and rate budgets, retrying it on retryable errors.
+    async fn request<R, F, Fut>(&self, mut send: F) -> TransportResult<R>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = TransportResult<R>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = {
+                let _permit = self.permits.acquire().await.expect("the semaphore is never closed");
+                self.throttle().await;
+                send().await
+            };
+            match result {
+                Err(err) if attempt < self.max_retries && err.is_retryable() => {
+                    let backoff = self.retry_backoff.saturating_mul(1 << attempt.min(16));
+                    debug!(%err, attempt, ?backoff, "retrying backfill request");
+                    sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Waits for the next request slot of the rate budget.
+    async fn throttle(&self) {
+        let Some(interval) = self.request_interval else { return };
+        let slot = {
+            let mut next_request = self.next_request.lock().unwrap();
+            let now = Instant::now();
+            let slot = next_request.map_or(now, |next| next.max(now));
+            *next_request = Some(slot + interval);
+            slot
+        };
+        sleep_until(slot).await;
+    }
+}
+
+/// Splits `range` into consecutive ranges of up to `size` blocks.
+fn chunks(
+    range: RangeInclusive<BlockNumber>,
+    size: u64,
+) -> impl Iterator<Item = RangeInclusive<BlockNumber>> {
+    let end = *range.end();
+    range
+        .step_by(usize::try_from(size).unwrap_or(usize::MAX))
+        .map(move |start| start..=start.saturating_add(size - 1).min(end))
+}
+
+/// The processed chunks of a backfill.
+#[derive(Debug)]
+struct Progress {
+    /// The first block not processed yet.
+    next: BlockNumber,
+    /// The processed chunks after `next`, from their start to their end.
+    done: BTreeMap<BlockNumber, BlockNumber>,
+}
+
+impl Progress {
+    /// Records a processed chunk, returning whether the first unprocessed block moved.
+    fn complete(&mut self, range: RangeInclusive<BlockNumber>) -> bool {
+        let (start, end) = range.into_inner();
+        self.done.insert(start, end);
+        let mut moved = false;
+        while let Some(end) = self.done.remove(&self.next) {
+            self.next = end.saturating_add(1);
+            moved = true;
+        }
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{error_resp, mock_provider},
+        RootProvider,
+    };
+    use alloy_rpc_types::{BlockNumberOrTag, Header};
+    use alloy_transport::{BoxTransport, TransportErrorKind};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// Returns a node serving blocks and logs, failing the first request for block 3 with a rate
+    /// limiting error, and counting block requests. Log queries are limited to 2 blocks.
+    fn node(requests: Arc<AtomicUsize>) -> RootProvider<BoxTransport> {
+        let failed = Arc::new(Mutex::new(false));
+        mock_provider(move |method, params| match method {
+            "eth_getBlockByNumber" => {
+                requests.fetch_add(1, Ordering::SeqCst);
+                let (number, _): (BlockNumberOrTag, bool) = serde_json::from_str(params).unwrap();
+                let number = number.as_number().unwrap();
+                if number == 3 && !std::mem::replace(&mut *failed.lock().unwrap(), true) {
+                    return Err(TransportErrorKind::http_error(429, "slow down".into()));
+                }
+                let block = (number < 20).then(|| Block::<()> {
+                    header: Header { number: Some(number), ..Default::default() },
+                    ..Default::default()
+                });
+                Ok(serde_json::json!(block))
+            }
+            "eth_getLogs" => {
+                let (filter,): (Filter,) = serde_json::from_str(params).unwrap();
+                let (from, to) = (filter.get_from_block().unwrap(), filter.get_to_block().unwrap());
+                if to - from >= 2 {
+                    return Err(error_resp(-32005, "query exceeds the block range limit"));
+                }
+                let log: Log = Log { block_number: Some(from), ..Default::default() };
+                Ok(serde_json::json!([log]))
+            }
+            method => unreachable!("unexpected method {method}"),
+        })
+    }
+
+    #[tokio::test]
+    async fn backfills_blocks() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let backfill = Backfill::new(node(requests.clone()), 0..=9)
+            .chunk_size(4)
+            .retry_backoff(Duration::from_millis(1));
+        let chunks: Vec<_> = backfill.blocks(false).map(Result::unwrap).collect().await;
+        let ranges: Vec<_> = chunks.iter().map(|chunk| chunk.range.clone()).collect();
+        assert_eq!(ranges, [0..=3, 4..=7, 8..=9]);
+        let numbers: Vec<_> =
+            chunks.iter().flat_map(|chunk| &chunk.items).map(|block| block.header.number).collect();
+        assert_eq!(numbers, (0..=9).map(Some).collect::<Vec<_>>());
+        // The rate limited request was retried.
+        assert_eq!(requests.load(Ordering::SeqCst), 11);
+
+        let backfill = Backfill::new(node(requests.clone()), 15..=25).chunk_size(4).unordered();
+        let results: Vec<_> = backfill.blocks(false).collect().await;
+        assert!(matches!(results.last(), Some(Err(BackfillError::MissingBlock(20)))));
+
+        let backfill = Backfill::new(node(requests), 10..=15).chunk_size(5).rate_limit(1000);
+        let chunks: Vec<_> = backfill.logs(&Filter::new()).map(Result::unwrap).collect().await;
+        let blocks: Vec<_> =
+            chunks.iter().flat_map(|chunk| &chunk.items).map(|log| log.block_number).collect();
+        // Each chunk is split into queries of at most 2 blocks.
+        assert_eq!(blocks, [Some(10), Some(12), Some(13), Some(15)]);
+    }
+
+    #[tokio::test]
+    async fn resumes_from_checkpoint() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let store = MemoryKvStore::default();
+        let backfill = || {
+            Backfill::new(node(requests.clone()), 0..=9)
+                .chunk_size(2)
+                .retry_backoff(Duration::ZERO)
+                .checkpoint(&store, "blocks")
+        };
+
+        let mut chunks = Box::pin(backfill().blocks(false));
+        for _ in 0..2 {
+            chunks.next().await.unwrap().unwrap();
+        }
+        // The second chunk is only processed once the next one is polled.
+        drop(chunks);
+        assert_eq!(store.get("blocks").unwrap(), Some(2u64.to_be_bytes().to_vec()));
+
+        let chunks: Vec<_> = backfill().blocks(false).map(Result::unwrap).collect().await;
+        let ranges: Vec<_> = chunks.iter().map(|chunk| chunk.range.clone()).collect();
+        assert_eq!(ranges, [2..=3, 4..=5, 6..=7, 8..=9]);
+        assert_eq!(store.get("blocks").unwrap(), Some(10u64.to_be_bytes().to_vec()));
+        assert_eq!(backfill().blocks(false).count().await, 0);
+
+        let mut progress = Progress { next: 0, done: BTreeMap::new() };
+        assert!(!progress.complete(2..=3));
+        assert!(progress.complete(0..=1));
+        assert_eq!(progress.next, 4);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     fillers::{
-        ChainIdFiller, FillerControlFlow, GasFiller, JoinFill, NonceFiller, SignerFiller, TxFiller,
+        ChainIdFiller, FillerControlFlow, GasFiller, JoinFill, NodeSignerFiller, NonceFiller,
+        SignerFiller, TxFiller,
     },
     provider::SendableTx,
     Provider, RootProvider,
@@ -14,6 +15,35 @@ use std::marker::PhantomData;
 type RecommendFiller =
     JoinFill<JoinFill<JoinFill<Identity, GasFiller>, NonceFiller>, ChainIdFiller>;
 
+/// The HTTP transport authenticating to the Engine API, built by
+/// [`ProviderBuilder::on_auth_http`].
+#[cfg(all(feature = "reqwest", feature = "engine-api"))]
+type AuthHttp = alloy_transport::JwtService<alloy_transport_http::Http<reqwest::Client>>;
+
+/// A websocket connection authenticating to the Engine API with a JWT issued on every
+/// (re)connection, built by [`ProviderBuilder::on_auth_ws`].
+///
+/// Execution clients check the token in the handshake and reject tokens issued more than a
+/// minute before, so reusing a token would fail the reconnections.
+#[cfg(all(feature = "ws", feature = "engine-api"))]
+#[derive(Clone, Debug)]
+struct AuthWsConnect {
+    url: String,
+    secret: alloy_rpc_types_engine::JwtSecret,
+}
+
+#[cfg(all(feature = "ws", feature = "engine-api"))]
+impl alloy_pubsub::PubSubConnect for AuthWsConnect {
+    fn is_local(&self) -> bool {
+        alloy_transport::utils::guess_local_url(&self.url)
+    }
+
+    async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
+        let auth = alloy_transport::Authorization::jwt(&self.secret)?;
+        alloy_transport_ws::WsConnect::with_auth(self.url.clone(), Some(auth)).connect().await
+    }
+}
+
 /// A layering abstraction in the vein of [`tower::Layer`]
 ///
 /// [`tower::Layer`]: https://docs.rs/tower/latest/tower/trait.Layer.html
@@ -110,19 +140,33 @@ where
 pub struct ProviderBuilder<L, F, N = Ethereum> {
     layer: L,
     filler: F,
+    chain_id: Option<u64>,
+    chain_id_guard: bool,
     network: PhantomData<fn() -> N>,
 }
 
 impl ProviderBuilder<Identity, Identity, Ethereum> {
     /// Create a new [`ProviderBuilder`].
     pub const fn new() -> Self {
-        Self { layer: Identity, filler: Identity, network: PhantomData }
+        Self {
+            layer: Identity,
+            filler: Identity,
+            chain_id: None,
+            chain_id_guard: false,
+            network: PhantomData,
+        }
     }
 }
 
 impl<N> Default for ProviderBuilder<Identity, Identity, N> {
     fn default() -> Self {
-        Self { layer: Identity, filler: Identity, network: PhantomData }
+        Self {
+            layer: Identity,
+            filler: Identity,
+            chain_id: None,
+            chain_id_guard: false,
+            network: PhantomData,
+        }
     }
 }
 
@@ -130,14 +174,31 @@ impl<L, N> ProviderBuilder<L, Identity, N> {
     /// Add preconfigured set of layers handling gas estimation, nonce
     /// management, and chain-id fetching.
     pub fn with_recommended_fillers(self) -> ProviderBuilder<L, RecommendFiller, N> {
-        self.filler(GasFiller).filler(NonceFiller::default()).filler(ChainIdFiller::default())
+        self.filler(GasFiller::default())
+            .filler(NonceFiller::default())
+            .filler(ChainIdFiller::default())
     }
 
     /// Add gas estimation to the stack being built.
     ///
     /// See [`GasFiller`]
     pub fn with_gas_estimation(self) -> ProviderBuilder<L, JoinFill<Identity, GasFiller>, N> {
-        self.filler(GasFiller)
+        self.filler(GasFiller::default())
+    }
+
+    /// Add gas estimation with a configured [`GasFiller`] to the stack being
+    /// built, e.g. to add a buffer to gas estimates.
+    ///
+    /// ```
+    /// # use alloy_provider::{fillers::GasFiller, ProviderBuilder};
+    /// let builder = ProviderBuilder::new()
+    ///     .with_gas_filler(GasFiller::default().with_gas_limit_buffer(20).with_gas_limit_cap(5_000_000));
+    /// ```
+    pub fn with_gas_filler(
+        self,
+        filler: GasFiller,
+    ) -> ProviderBuilder<L, JoinFill<Identity, GasFiller>, N> {
+        self.filler(filler)
     }
 
     /// Add nonce management to the stack being built.
@@ -149,7 +210,7 @@ impl<L, N> ProviderBuilder<L, Identity, N> {
 
     /// Add a chain ID filler to the stack being built. The filler will attempt
     /// to fetch the chain ID from the provider using
-    /// [`Provider::get_chain_id`]. the first time a transaction is prepared,
+    /// [`Provider::cached_chain_id`]. the first time a transaction is prepared,
     /// and will cache it for future transactions.
     pub fn fetch_chain_id(self) -> ProviderBuilder<L, JoinFill<Identity, ChainIdFiller>, N> {
         self.filler(ChainIdFiller::default())
@@ -182,6 +243,8 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         ProviderBuilder {
             layer: Stack::new(layer, self.layer),
             filler: self.filler,
+            chain_id: self.chain_id,
+            chain_id_guard: self.chain_id_guard,
             network: PhantomData,
         }
     }
@@ -193,6 +256,8 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         ProviderBuilder {
             layer: self.layer,
             filler: JoinFill::new(self.filler, filler),
+            chain_id: self.chain_id,
+            chain_id_guard: self.chain_id_guard,
             network: PhantomData,
         }
     }
@@ -204,6 +269,13 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         self.filler(SignerFiller::new(signer))
     }
 
+    /// Add a signer layer deferring signing to the accounts of the node.
+    ///
+    /// See [`NodeSignerFiller`].
+    pub fn with_node_signer(self) -> ProviderBuilder<L, JoinFill<F, NodeSignerFiller>, N> {
+        self.filler(NodeSignerFiller::new())
+    }
+
     /// Change the network.
     ///
     /// By default, the network is `Ethereum`. This method must be called to configure a different
@@ -213,7 +285,48 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
     /// builder.network::<Arbitrum>()
     /// ```
     pub fn network<Net: Network>(self) -> ProviderBuilder<L, F, Net> {
-        ProviderBuilder { layer: self.layer, filler: self.filler, network: PhantomData }
+        ProviderBuilder {
+            layer: self.layer,
+            filler: self.filler,
+            chain_id: self.chain_id,
+            chain_id_guard: self.chain_id_guard,
+            network: PhantomData,
+        }
+    }
+
+    /// Require the endpoint to be connected to the chain with the given ID.
+    ///
+    /// The chain ID is checked when connecting with [`on_builtin`](Self::on_builtin),
+    /// [`on_ws`](Self::on_ws) or [`on_ipc`](Self::on_ipc), which fail with
+    /// [`TransportErrorKind::ChainIdMismatch`] if the endpoint reports another chain. Pubsub
+    /// connections are checked again every time they reconnect, and stop if the check fails.
+    ///
+    /// The synchronous constructors, such as [`on_http`](Self::on_http) or
+    /// [`on_client`](Self::on_client), cannot query the endpoint. They enable the chain ID guard
+    /// of [`with_chain_id_guard`](Self::with_chain_id_guard) instead, and the chain ID is checked
+    /// the first time it is fetched with [`Provider::cached_chain_id`], at the latest before
+    /// sending the first transaction.
+    ///
+    /// Unlike [`with_chain_id`](ProviderBuilder::with_chain_id), this does not fill the chain ID
+    /// of transactions.
+    ///
+    /// [`TransportErrorKind::ChainIdMismatch`]: alloy_transport::TransportErrorKind::ChainIdMismatch
+    pub const fn expect_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Reject transactions for another chain than the one of the endpoint, before sending them.
+    ///
+    /// The chain ID of the endpoint is fetched the first time a transaction is sent, and cached.
+    /// Transactions whose chain ID differs, such as transactions signed for mainnet sent to a
+    /// testnet, fail with [`TransportErrorKind::ChainIdMismatch`]. See
+    /// [`RootProvider::set_chain_id_guard`].
+    ///
+    /// [`TransportErrorKind::ChainIdMismatch`]: alloy_transport::TransportErrorKind::ChainIdMismatch
+    pub const fn with_chain_id_guard(mut self) -> Self {
+        self.chain_id_guard = true;
+        self
     }
 
     /// Finish the layer stack by providing a root [`Provider`], outputting
@@ -226,7 +339,13 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         T: Transport + Clone,
         N: Network,
     {
-        let Self { layer, filler, .. } = self;
+        let Self { layer, filler, chain_id, chain_id_guard, .. } = self;
+        if let Some(chain_id) = chain_id {
+            provider.root().expect_chain_id(chain_id);
+        }
+        if chain_id_guard || chain_id.is_some() {
+            provider.root().set_chain_id_guard(true);
+        }
         let stack = Stack::new(layer, filler);
         stack.layer(provider)
     }
@@ -256,7 +375,34 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         N: Network,
     {
         let connect: BuiltInConnectionString = s.parse()?;
-        let client = ClientBuilder::default().connect_boxed(connect).await?;
+        let client = match (self.chain_id, connect) {
+            #[cfg(all(not(target_arch = "wasm32"), feature = "ws"))]
+            (Some(chain_id), BuiltInConnectionString::Ws(url, auth)) => {
+                let connect = alloy_transport_ws::WsConnect::with_auth(url, auth);
+                ClientBuilder::default()
+                    .pubsub(alloy_pubsub::ChainIdCheck::new(connect, chain_id))
+                    .await?
+                    .boxed()
+            }
+            #[cfg(feature = "ipc")]
+            (Some(chain_id), BuiltInConnectionString::Ipc(path)) => {
+                let connect = alloy_transport_ipc::IpcConnect::new(path);
+                ClientBuilder::default()
+                    .pubsub(alloy_pubsub::ChainIdCheck::new(connect, chain_id))
+                    .await?
+                    .boxed()
+            }
+            (chain_id, connect) => {
+                let root =
+                    RootProvider::new(ClientBuilder::default().connect_boxed(connect).await?);
+                if let Some(chain_id) = chain_id {
+                    // checked against the expected chain ID when fetched
+                    root.expect_chain_id(chain_id);
+                    root.cached_chain_id().await?;
+                }
+                return Ok(self.on_provider(root));
+            }
+        };
         Ok(self.on_client(client))
     }
 
@@ -275,7 +421,46 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         F: TxFiller<N> + ProviderLayer<L::Provider, alloy_pubsub::PubSubFrontend, N>,
         N: Network,
     {
-        let client = ClientBuilder::default().ws(connect).await?;
+        let client = match self.chain_id {
+            Some(chain_id) => {
+                ClientBuilder::default()
+                    .pubsub(alloy_pubsub::ChainIdCheck::new(connect, chain_id))
+                    .await?
+            }
+            None => ClientBuilder::default().ws(connect).await?,
+        };
+        Ok(self.on_client(client))
+    }
+
+    /// Build this provider with a websocket connection authenticating to the Engine API of an
+    /// execution client with the given JWT secret.
+    ///
+    /// A token is issued for every connection and sent in its handshake, which is the only time
+    /// execution clients check it, so that reconnections are authenticated too.
+    #[cfg(all(feature = "ws", feature = "engine-api"))]
+    pub async fn on_auth_ws(
+        self,
+        url: impl Into<String>,
+        secret: alloy_rpc_types_engine::JwtSecret,
+    ) -> Result<F::Provider, TransportError>
+    where
+        L: ProviderLayer<
+            RootProvider<alloy_pubsub::PubSubFrontend, N>,
+            alloy_pubsub::PubSubFrontend,
+            N,
+        >,
+        F: TxFiller<N> + ProviderLayer<L::Provider, alloy_pubsub::PubSubFrontend, N>,
+        N: Network,
+    {
+        let connect = AuthWsConnect { url: url.into(), secret };
+        let client = match self.chain_id {
+            Some(chain_id) => {
+                ClientBuilder::default()
+                    .pubsub(alloy_pubsub::ChainIdCheck::new(connect, chain_id))
+                    .await?
+            }
+            None => ClientBuilder::default().pubsub(connect).await?,
+        };
         Ok(self.on_client(client))
     }
 
@@ -295,7 +480,14 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         F: TxFiller<N> + ProviderLayer<L::Provider, alloy_pubsub::PubSubFrontend, N>,
         N: Network,
     {
-        let client = ClientBuilder::default().ipc(connect).await?;
+        let client = match self.chain_id {
+            Some(chain_id) => {
+                ClientBuilder::default()
+                    .pubsub(alloy_pubsub::ChainIdCheck::new(connect, chain_id))
+                    .await?
+            }
+            None => ClientBuilder::default().ipc(connect).await?,
+        };
         Ok(self.on_client(client))
     }
 
@@ -311,6 +503,26 @@ impl<L, F, N> ProviderBuilder<L, F, N> {
         self.on_client(client)
     }
 
+    /// Build this provider with an HTTP transport authenticating to the Engine API of an execution
+    /// client with the given JWT secret.
+    ///
+    /// The URL should point to the authenticated port, usually `8551`. Use
+    /// [`EngineApi`](crate::ext::EngineApi) to send Engine API requests.
+    #[cfg(all(feature = "reqwest", feature = "engine-api"))]
+    pub fn on_auth_http(
+        self,
+        url: reqwest::Url,
+        secret: alloy_rpc_types_engine::JwtSecret,
+    ) -> F::Provider
+    where
+        L: ProviderLayer<RootProvider<AuthHttp, N>, AuthHttp, N>,
+        F: TxFiller<N> + ProviderLayer<L::Provider, AuthHttp, N>,
+        N: Network,
+    {
+        let layer = alloy_transport::JwtLayer::new(secret);
+        self.on_client(ClientBuilder::default().layer(layer).http(url))
+    }
+
     /// Build this provider with an Hyper HTTP transport.
     #[cfg(feature = "hyper")]
     pub fn on_hyper_http(self, url: url::Url) -> F::Provider
@@ -457,3 +669,44 @@ impl<L, F> ProviderBuilder<L, F, Ethereum> {
 // OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
+
+#[cfg(all(test, feature = "ws", feature = "engine-api"))]
+mod tests {
+    use super::*;
+    use alloy_pubsub::PubSubConnect;
+    use alloy_rpc_types_engine::JwtSecret;
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+    };
+
+    #[tokio::test]
+    async fn auth_ws_issues_token_per_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        // Returns the tokens of two handshakes, closing the connections without answering them.
+        let server = std::thread::spawn(move || {
+            (0..2)
+                .map(|_| {
+                    let (stream, _) = listener.accept().unwrap();
+                    BufReader::new(stream)
+                        .lines()
+                        .map(Result::unwrap)
+                        .take_while(|line| !line.is_empty())
+                        .find_map(|line| {
+                            line.strip_prefix("authorization: Bearer ").map(Into::into)
+                        })
+                        .unwrap()
+                })
+                .collect::<Vec<String>>()
+        });
+
+        let secret = JwtSecret::random();
+        let connect = AuthWsConnect { url, secret };
+        assert!(connect.connect().await.is_err());
+        assert!(connect.try_reconnect().await.is_err());
+        for token in server.join().unwrap() {
+            secret.validate(&token).unwrap();
+        }
+    }
+}
@@ -3,7 +3,7 @@ use alloy_network::{Ethereum, Network};
 use alloy_primitives::{BlockNumber, U64};
 use alloy_rpc_client::{PollerBuilder, WeakClient};
 use alloy_rpc_types::Block;
-use alloy_transport::{RpcError, Transport};
+use alloy_transport::Transport;
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use lru::LruCache;
@@ -87,7 +87,7 @@ impl<T: Transport + Clone, N: Network> ChainStreamPoller<T, N> {
                 debug!(number, "fetching block");
                 let block = match client.request("eth_getBlockByNumber", (U64::from(number), false)).await {
                     Ok(Some(block)) => block,
-                    Err(RpcError::Transport(err)) if retries > 0 && err.recoverable() => {
+                    Err(err) if retries > 0 && err.as_transport_err().is_some_and(|err| err.recoverable()) => {
                         debug!(number, %err, "failed to fetch block, retrying");
                         retries -= 1;
                         continue;
@@ -0,0 +1,391 @@
+//! Human-readable configuration values.
+//!
+//! Layer and filler builders take durations, fee amounts and multipliers as plain Rust values.
+//! The types of this module parse them from the strings found in configuration files, such as
+//! `"3s"`, `"2.5 gwei"` or `"1.2x"`, and deserialize from them, so that configuration structs
+//! can hold them directly:
+//!
+//! ```
+//! use alloy_provider::{
+//!     config::{ConfigDuration, FeeAmount, Multiplier},
+//!     fillers::GasFiller,
+//! };
+//! use std::time::Duration;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     fee_market_recheck: ConfigDuration,
+//!     max_fee: FeeAmount,
+//!     bump: Multiplier,
+//! }
+//!
+//! let config: Config = serde_json::from_str(
+//!     r#"{ "fee_market_recheck": "5m", "max_fee": "2.5 gwei", "bump": "1.2x" }"#,
+//! )
+//! .unwrap();
+//! assert_eq!(config.fee_market_recheck.get(), Duration::from_secs(300));
+//! assert_eq!(config.max_fee.get(), 2_500_000_000);
+//! assert_eq!(config.bump.apply(100), 120);
+//!
+//! let filler = GasFiller::default()
+//!     .with_fee_market_recheck_interval(config.fee_market_recheck.into());
+//! ```
+
+use alloy_primitives::utils::{ParseUnits, Unit};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr, time::Duration};
+use thiserror::Error;
+
+/// Errors thrown when parsing a configuration value.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ParseConfigError {
+    /// Thrown on a malformed duration.
+    #[error("invalid duration {0:?}, expected e.g. \"500ms\", \"3s\", \"1m30s\" or \"2h\"")]
+    Duration(String),
+    /// Thrown on a malformed fee amount.
+    #[error("invalid fee amount {0:?}, expected e.g. \"2.5 gwei\", \"0.01 ether\" or \"100 wei\"")]
+    FeeAmount(String),
+    /// Thrown on a malformed multiplier.
+    #[error("invalid multiplier {0:?}, expected e.g. \"1.2x\", \"120%\" or \"1.2\"")]
+    Multiplier(String),
+}
+
+/// Parses a duration made of one or more components with units, such as `"250ms"`, `"3s"`,
+/// `"1.5 m"` or `"1h30m"`.
+///
+/// Supported units are `ms`, `s`, `m` (or `min`) and `h`.
+pub fn parse_duration(s: &str) -> Result<Duration, ParseConfigError> {
+    let err = || ParseConfigError::Duration(s.to_string());
+    let mut rest = s.trim();
+    if rest.is_empty() {
+        return Err(err());
+    }
+
+    let mut total = Duration::ZERO;
+    while !rest.is_empty() {
+        let split = rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).ok_or_else(err)?;
+        let (value, tail) = rest.split_at(split);
+        let tail = tail.trim_start();
+        let split = tail.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(split);
+
+        let value: f64 = value.parse().map_err(|_| err())?;
+        let secs = match unit {
+            "ms" => value / 1000.0,
+            "s" => value,
+            "m" | "min" => value * 60.0,
+            "h" => value * 3600.0,
+            _ => return Err(err()),
+        };
+        total += Duration::try_from_secs_f64(secs).map_err(|_| err())?;
+        rest = tail.trim_start();
+    }
+    Ok(total)
+}
+
+/// Parses a fee amount in wei from a number followed by an optional unit, such as `"2.5 gwei"`,
+/// `"0.01 ether"` or `"100"`.
+///
+/// Amounts without a unit are in wei.
+pub fn parse_fee_amount(s: &str) -> Result<u128, ParseConfigError> {
+    let err = || ParseConfigError::FeeAmount(s.to_string());
+    let s = s.trim();
+    let split = s.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let unit: Unit = match unit.trim_start() {
+        "" => Unit::WEI,
+        unit => unit.parse().map_err(|_| err())?,
+    };
+    // Amounts are whole numbers of wei.
+    let decimals = value.split_once('.').map_or(0, |(_, decimals)| decimals.len());
+    if value.is_empty() || decimals > unit.get() as usize {
+        return Err(err());
+    }
+    ParseUnits::parse_units(value, unit)
+        .ok()
+        .and_then(|amount| amount.try_into().ok())
+        .ok_or_else(err)
+}
+
+/// Parses a non-negative multiplier, either as a factor such as `"1.2x"` or `"1.2"`, or as a
+/// percentage such as `"120%"`.
+pub fn parse_multiplier(s: &str) -> Result<f64, ParseConfigError> {
+    let err = || ParseConfigError::Multiplier(s.to_string());
+    let s = s.trim();
+    let (value, divisor) = match (s.strip_suffix('x'), s.strip_suffix('%')) {
+        (Some(factor), _) => (factor, 1.0),
+        (_, Some(percent)) => (percent, 100.0),
+        _ => (s, 1.0),
+    };
+    let value = value.trim_end().parse::<f64>().map_err(|_| err())? / divisor;
+    if value.is_finite() && value >= 0.0 {
+        Ok(value)
+    } else {
+        Err(err())
+    }
+}
+
+/// A [`Duration`] parsed with [`parse_duration`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConfigDuration(pub Duration);
+
+impl ConfigDuration {
+    /// Returns the duration.
+    pub const fn get(self) -> Duration {
+        self.0
+    }
+}
+
+impl From<Duration> for ConfigDuration {
+    fn from(duration: Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<ConfigDuration> for Duration {
+    fn from(duration: ConfigDuration) -> Self {
+        duration.0
+    }
+}
+
+impl FromStr for ConfigDuration {
+    type Err = ParseConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_duration(s).map(Self)
+    }
+}
+
+impl fmt::Display for ConfigDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = self.0.as_millis();
+        if millis % 1000 == 0 {
+            write!(f, "{}s", millis / 1000)
+        } else {
+            write!(f, "{millis}ms")
+        }
+    }
+}
+
+/// A fee amount in wei, parsed with [`parse_fee_amount`].
+///
+/// Deserializes from strings, and from integers in wei.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FeeAmount(pub u128);
+
+impl FeeAmount {
+    /// Returns the amount in wei.
+    pub const fn get(self) -> u128 {
+        self.0
+    }
+}
+
+impl From<u128> for FeeAmount {
+    fn from(wei: u128) -> Self {
+        Self(wei)
+    }
+}
+
+impl From<FeeAmount> for u128 {
+    fn from(amount: FeeAmount) -> Self {
+        amount.0
+    }
+}
+
+impl FromStr for FeeAmount {
+    type Err = ParseConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_fee_amount(s).map(Self)
+    }
+}
+
+impl fmt::Display for FeeAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} wei", self.0)
+    }
+}
+
+/// A multiplier parsed with [`parse_multiplier`].
+///
+/// Deserializes from strings, and from numbers as factors.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Multiplier(f64);
+
+impl Default for Multiplier {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl Multiplier {
+    /// Creates a multiplier from a factor, or returns `None` if it is negative or not finite.
+    pub fn new(factor: f64) -> Option<Self> {
+        (factor.is_finite() && factor >= 0.0).then_some(Self(factor))
+    }
+
+    /// Returns the factor.
+    pub const fn get(self) -> f64 {
+        self.0
+    }
+
+    /// Multiplies `value` by the factor, rounding up.
+    pub fn apply(self, value: u128) -> u128 {
+        (value as f64 * self.0).ceil() as u128
+    }
+}
+
+impl FromStr for Multiplier {
+    type Err = ParseConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_multiplier(s).map(Self)
+    }
+}
+
+impl fmt::Display for Multiplier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x", self.0)
+    }
+}
+
+macro_rules! impl_serde {
+    ($($ty:ident),*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(ConfigVisitor::<$ty>(std::marker::PhantomData))
+            }
+        }
+    )*};
+}
+
+impl_serde!(ConfigDuration, FeeAmount, Multiplier);
+
+struct ConfigVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T: FromNumber> de::Visitor<'de> for ConfigVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(T::EXPECTING)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<T, E> {
+        s.parse().map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<T, E> {
+        T::from_u64(n).ok_or_else(|| E::invalid_type(de::Unexpected::Unsigned(n), &self))
+    }
+
+    fn visit_f64<E: de::Error>(self, n: f64) -> Result<T, E> {
+        T::from_f64(n).ok_or_else(|| E::invalid_type(de::Unexpected::Float(n), &self))
+    }
+}
+
+/// Configuration values that may be written as bare numbers.
+trait FromNumber: FromStr<Err = ParseConfigError> {
+    const EXPECTING: &'static str;
+
+    fn from_u64(n: u64) -> Option<Self>;
+
+    fn from_f64(n: f64) -> Option<Self>;
+}
+
+impl FromNumber for ConfigDuration {
+    const EXPECTING: &'static str = "a duration with units";
+
+    // Bare numbers are ambiguous between seconds and milliseconds.
+    fn from_u64(_: u64) -> Option<Self> {
+        None
+    }
+
+    fn from_f64(_: f64) -> Option<Self> {
+        None
+    }
+}
+
+impl FromNumber for FeeAmount {
+    const EXPECTING: &'static str = "a fee amount in wei, or with units";
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Self(n.into()))
+    }
+
+    fn from_f64(_: f64) -> Option<Self> {
+        None
+    }
+}
+
+impl FromNumber for Multiplier {
+    const EXPECTING: &'static str = "a multiplier";
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Self::new(n as f64)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        Self::new(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("250ms"), Ok(Duration::from_millis(250)));
+        assert_eq!(parse_duration(" 3s "), Ok(Duration::from_secs(3)));
+        assert_eq!(parse_duration("1.5 m"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("2min 15s"), Ok(Duration::from_secs(135)));
+        for invalid in ["", "3", "s", "3 days", "-1s", "1..2s"] {
+            assert!(parse_duration(invalid).is_err(), "{invalid}");
+        }
+        assert_eq!(ConfigDuration(Duration::from_millis(1500)).to_string(), "1500ms");
+        assert_eq!(ConfigDuration(Duration::from_secs(3)).to_string(), "3s");
+    }
+
+    #[test]
+    fn parses_fee_amounts() {
+        assert_eq!(parse_fee_amount("2.5 gwei"), Ok(2_500_000_000));
+        assert_eq!(parse_fee_amount("0.01ether"), Ok(10_000_000_000_000_000));
+        assert_eq!(parse_fee_amount("100"), Ok(100));
+        assert_eq!(parse_fee_amount("100 wei"), Ok(100));
+        for invalid in ["", "gwei", "-1 gwei", "1.5 wei", "1 bitcoin"] {
+            assert!(parse_fee_amount(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn parses_multipliers() {
+        assert_eq!(parse_multiplier("1.2x"), Ok(1.2));
+        assert_eq!(parse_multiplier("150%"), Ok(1.5));
+        assert_eq!(parse_multiplier("2"), Ok(2.0));
+        for invalid in ["", "x", "-1x", "inf", "1.2y"] {
+            assert!(parse_multiplier(invalid).is_err(), "{invalid}");
+        }
+        assert_eq!("1.125x".parse::<Multiplier>().unwrap().apply(1_000_000_001), 1_125_000_002);
+    }
+
+    #[test]
+    fn deserializes_values() {
+        let amounts: Vec<FeeAmount> = serde_json::from_str(r#"["1 gwei", 7]"#).unwrap();
+        assert_eq!(amounts, [FeeAmount(1_000_000_000), FeeAmount(7)]);
+        let multipliers: Vec<Multiplier> = serde_json::from_str(r#"["110%", 1.5, 2]"#).unwrap();
+        assert_eq!(multipliers.iter().map(|m| m.get()).collect::<Vec<_>>(), [1.1, 1.5, 2.0]);
+        assert!(serde_json::from_str::<ConfigDuration>("3").is_err());
+
+        let duration = ConfigDuration(Duration::from_millis(2500));
+        let json = serde_json::to_string(&duration).unwrap();
+        assert_eq!(json, r#""2500ms""#);
+        assert_eq!(serde_json::from_str::<ConfigDuration>(&json).unwrap(), duration);
+    }
+}
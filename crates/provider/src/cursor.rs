@@ -0,0 +1,417 @@
+//! Resumable sync progress for indexers.
+//!
+//! A [`SyncCursor`] records the last blocks processed by a named stream in a [`KvStore`], so that
+//! an indexer can pick up where it left off after a restart. It keeps a bounded window of recent
+//! checkpoints to detect chain reorganizations and tell the indexer how far to roll back.
+
+use alloy_primitives::{BlockHash, BlockNumber};
+use std::{collections::HashMap, convert::Infallible, sync::Mutex};
+
+/// The default number of checkpoints kept by a [`SyncCursor`].
+pub const DEFAULT_HISTORY: usize = 64;
+
+/// The size of an encoded [`Checkpoint`].
+const CHECKPOINT_SIZE: usize = 40;
+
+/// A minimal persistent key-value store.
+///
+/// This is implemented for [`MemoryKvStore`]; implement it for your database of choice to persist
+/// [`SyncCursor`]s.
+pub trait KvStore: Send + Sync {
+    /// The error returned by the store.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Returns the value stored under `key`, if any.
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Stores `value` under `key`, replacing any previous value.
+    fn put(&self, key: &str, value: Vec<u8>) -> Result<(), Self::Error>;
+
+    /// Removes the value stored under `key`, if any.
+    fn delete(&self, key: &str) -> Result<(), Self::Error>;
+}
+
+impl<S: KvStore + ?Sized> KvStore for &S {
+    type Error = S::Error;
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, value: Vec<u8>) -> Result<(), Self::Error> {
+        (**self).put(key, value)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Self::Error> {
+        (**self).delete(key)
+    }
+}
+
+impl<S: KvStore + ?Sized> KvStore for std::sync::Arc<S> {
+    type Error = S::Error;
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        (**self).get(key)
+    }
+
+    fn put(&self, key: &str, value: Vec<u8>) -> Result<(), Self::Error> {
+        (**self).put(key, value)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Self::Error> {
+        (**self).delete(key)
+    }
+}
+
+/// An in-memory [`KvStore`], mostly useful for tests.
+#[derive(Debug, Default)]
+pub struct MemoryKvStore {
+    map: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryKvStore {
+    /// Creates a new empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KvStore for MemoryKvStore {
+    type Error = Infallible;
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.map.lock().unwrap().get(key).cloned())
+    }
+
+    fn put(&self, key: &str, value: Vec<u8>) -> Result<(), Self::Error> {
+        self.map.lock().unwrap().insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Self::Error> {
+        self.map.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+/// A processed block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Checkpoint {
+    /// The block number.
+    pub number: BlockNumber,
+    /// The block hash.
+    pub hash: BlockHash,
+}
+
+impl Checkpoint {
+    /// Creates a new checkpoint.
+    pub const fn new(number: BlockNumber, hash: BlockHash) -> Self {
+        Self { number, hash }
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.number.to_be_bytes());
+        out.extend_from_slice(self.hash.as_slice());
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        let (number, hash) = bytes.split_at(8);
+        Self {
+            number: BlockNumber::from_be_bytes(number.try_into().unwrap()),
+            hash: BlockHash::from_slice(hash),
+        }
+    }
+}
+
+/// The outcome of [`SyncCursor::advance`] and [`SyncCursor::check`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CursorUpdate {
+    /// The block extends the previous head, and is recorded.
+    Extended,
+    /// The block replaces previously processed blocks, starting at the given number, and is
+    /// recorded. Data derived from the replaced blocks should be reverted.
+    Replaced {
+        /// The first replaced block number.
+        from: BlockNumber,
+    },
+    /// The parent of the block was reorged out, and the cursor was rolled back to the given
+    /// checkpoint. The block is not recorded.
+    ///
+    /// Data derived from blocks after the checkpoint should be reverted, and processing should
+    /// resume from the block after it. `None` means that the reorg is deeper than the recorded
+    /// history, and the cursor is now empty.
+    RolledBack {
+        /// The new head of the cursor.
+        to: Option<Checkpoint>,
+    },
+}
+
+/// Error returned by a [`SyncCursor`].
+#[derive(Debug, thiserror::Error)]
+pub enum CursorError<E> {
+    /// The underlying store failed.
+    #[error(transparent)]
+    Store(E),
+    /// The stored checkpoints are corrupted.
+    #[error("corrupted checkpoints for stream {0:?}")]
+    Corrupted(String),
+    /// The block does not directly follow the head of the cursor.
+    #[error("expected block {expected}, got {got}")]
+    Gap {
+        /// The expected block number.
+        expected: BlockNumber,
+        /// The block number passed to the cursor.
+        got: BlockNumber,
+    },
+}
+
+/// Tracks the progress of a named stream of blocks, persisted in a [`KvStore`].
+///
+/// Call [`advance`](Self::advance) with every processed block, in order. The cursor checks each
+/// block against the recorded history, and reports reorgs through [`CursorUpdate`].
+///
+/// ```
+/// use alloy_primitives::B256;
+/// use alloy_provider::cursor::{CursorUpdate, MemoryKvStore, SyncCursor};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let store = MemoryKvStore::new();
+/// let cursor = SyncCursor::new(&store, "transfers");
+///
+/// let start = cursor.next_block()?.unwrap_or(0);
+/// # let (number, hash, parent_hash) = (start, B256::repeat_byte(1), B256::ZERO);
+/// match cursor.advance(number, hash, parent_hash)? {
+///     CursorUpdate::Extended => { /* index the block */ }
+///     CursorUpdate::Replaced { from } => { /* revert `from..`, then index the block */ }
+///     CursorUpdate::RolledBack { to } => { /* revert past `to` and resume after it */ }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SyncCursor<S> {
+    store: S,
+    key: String,
+    max_history: usize,
+}
+
+impl<S: KvStore> SyncCursor<S> {
+    /// Creates a new cursor for the given stream, keeping [`DEFAULT_HISTORY`] checkpoints.
+    pub fn new(store: S, stream: &str) -> Self {
+        Self { store, key: format!("sync_cursor/{stream}"), max_history: DEFAULT_HISTORY }
+    }
+
+    /// Sets the number of checkpoints to keep, which bounds the depth of detectable reorgs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_history` is zero.
+    pub fn with_history(mut self, max_history: usize) -> Self {
+        assert!(max_history > 0, "max_history must be at least 1");
+        self.max_history = max_history;
+        self
+    }
+
+    /// Returns a reference to the store.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the recorded checkpoints, oldest first.
+    pub fn checkpoints(&self) -> Result<Vec<Checkpoint>, CursorError<S::Error>> {
+        let Some(bytes) = self.store.get(&self.key).map_err(CursorError::Store)? else {
+            return Ok(Vec::new());
+        };
+        if bytes.len() % CHECKPOINT_SIZE != 0 {
+            return Err(CursorError::Corrupted(self.key.clone()));
+        }
+        Ok(bytes.chunks_exact(CHECKPOINT_SIZE).map(Checkpoint::decode).collect())
+    }
+
+    /// Returns the last processed block, if any.
+    pub fn head(&self) -> Result<Option<Checkpoint>, CursorError<S::Error>> {
+        Ok(self.checkpoints()?.pop())
+    }
+
+    /// Returns the number of the next block to process, if any block was processed.
+    pub fn next_block(&self) -> Result<Option<BlockNumber>, CursorError<S::Error>> {
+        Ok(self.head()?.map(|head| head.number + 1))
+    }
+
+    /// Records a processed block, checking it against the recorded history.
+    ///
+    /// The first block of a stream is always accepted. Afterwards, `number` must be at most one
+    /// past the head of the cursor.
+    pub fn advance(
+        &self,
+        number: BlockNumber,
+        hash: BlockHash,
+        parent_hash: BlockHash,
+    ) -> Result<CursorUpdate, CursorError<S::Error>> {
+        let (update, checkpoints) = self.plan(number, hash, parent_hash)?;
+        self.save(&checkpoints)?;
+        Ok(update)
+    }
+
+    /// Checks a block against the recorded history like [`advance`](Self::advance), without
+    /// recording it.
+    ///
+    /// This allows processing the block before committing to it.
+    pub fn check(
+        &self,
+        number: BlockNumber,
+        hash: BlockHash,
+        parent_hash: BlockHash,
+    ) -> Result<CursorUpdate, CursorError<S::Error>> {
+        self.plan(number, hash, parent_hash).map(|(update, _)| update)
+    }
+
+    /// Returns the outcome of recording a block, and the checkpoints to save.
+    fn plan(
+        &self,
+        number: BlockNumber,
+        hash: BlockHash,
+        parent_hash: BlockHash,
+    ) -> Result<(CursorUpdate, Vec<Checkpoint>), CursorError<S::Error>> {
+        let mut checkpoints = self.checkpoints()?;
+        let Some(head) = checkpoints.last() else {
+            return Ok((CursorUpdate::Extended, vec![Checkpoint::new(number, hash)]));
+        };
+        if number > head.number + 1 {
+            return Err(CursorError::Gap { expected: head.number + 1, got: number });
+        }
+
+        let replaced = number <= head.number;
+        checkpoints.retain(|checkpoint| checkpoint.number < number);
+
+        if let Some(parent) = checkpoints.last() {
+            if parent.hash != parent_hash {
+                checkpoints.pop();
+                let to = checkpoints.last().copied();
+                return Ok((CursorUpdate::RolledBack { to }, checkpoints));
+            }
+        } else if replaced {
+            // The parent is no longer in the recorded history, so it can't be checked.
+            return Ok((CursorUpdate::RolledBack { to: None }, Vec::new()));
+        }
+
+        checkpoints.push(Checkpoint::new(number, hash));
+        let excess = checkpoints.len().saturating_sub(self.max_history);
+        checkpoints.drain(..excess);
+        let update =
+            if replaced { CursorUpdate::Replaced { from: number } } else { CursorUpdate::Extended };
+        Ok((update, checkpoints))
+    }
+
+    /// Rolls the cursor back to the last checkpoint at or below `number`, returning it.
+    ///
+    /// This is useful when reorgs are detected by other means, e.g. a removed log.
+    pub fn rewind(&self, number: BlockNumber) -> Result<Option<Checkpoint>, CursorError<S::Error>> {
+        let mut checkpoints = self.checkpoints()?;
+        checkpoints.retain(|checkpoint| checkpoint.number <= number);
+        self.save(&checkpoints)?;
+        Ok(checkpoints.last().copied())
+    }
+
+    /// Removes all recorded checkpoints.
+    pub fn reset(&self) -> Result<(), CursorError<S::Error>> {
+        self.store.delete(&self.key).map_err(CursorError::Store)
+    }
+
+    fn save(&self, checkpoints: &[Checkpoint]) -> Result<(), CursorError<S::Error>> {
+        if checkpoints.is_empty() {
+            return self.reset();
+        }
+        let mut bytes = Vec::with_capacity(checkpoints.len() * CHECKPOINT_SIZE);
+        for checkpoint in checkpoints {
+            checkpoint.encode(&mut bytes);
+        }
+        self.store.put(&self.key, bytes).map_err(CursorError::Store)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn hash(number: u64, fork: u8) -> BlockHash {
+        let mut hash = B256::with_last_byte(fork);
+        hash[..8].copy_from_slice(&number.to_be_bytes());
+        hash
+    }
+
+    fn advance(cursor: &SyncCursor<&MemoryKvStore>, number: u64, fork: u8) -> CursorUpdate {
+        let parent = number.checked_sub(1).map(|parent| hash(parent, fork)).unwrap_or_default();
+        cursor.advance(number, hash(number, fork), parent).unwrap()
+    }
+
+    #[test]
+    fn resumes_from_store() {
+        let store = MemoryKvStore::new();
+        let cursor = SyncCursor::new(&store, "blocks");
+        assert_eq!(cursor.next_block().unwrap(), None);
+        for number in 10..15 {
+            assert_eq!(advance(&cursor, number, 0), CursorUpdate::Extended);
+        }
+
+        let cursor = SyncCursor::new(&store, "blocks");
+        assert_eq!(cursor.head().unwrap(), Some(Checkpoint::new(14, hash(14, 0))));
+        assert_eq!(cursor.next_block().unwrap(), Some(15));
+        assert_eq!(SyncCursor::new(&store, "other").head().unwrap(), None);
+
+        let err = cursor.advance(17, hash(17, 0), hash(16, 0)).unwrap_err();
+        assert!(matches!(err, CursorError::Gap { expected: 15, got: 17 }));
+    }
+
+    #[test]
+    fn handles_reorgs() {
+        let store = MemoryKvStore::new();
+        let cursor = SyncCursor::new(&store, "blocks");
+        for number in 0..10 {
+            advance(&cursor, number, 0);
+        }
+
+        // A new block at the head's height, built on the same parent.
+        assert_eq!(
+            cursor.advance(9, hash(9, 1), hash(8, 0)).unwrap(),
+            CursorUpdate::Replaced { from: 9 }
+        );
+
+        // A block whose parent was reorged out: walk back until the parent matches.
+        assert_eq!(
+            advance(&cursor, 10, 2),
+            CursorUpdate::RolledBack { to: Some(Checkpoint::new(8, hash(8, 0))) }
+        );
+        assert_eq!(
+            advance(&cursor, 9, 2),
+            CursorUpdate::RolledBack { to: Some(Checkpoint::new(7, hash(7, 0))) }
+        );
+        assert_eq!(cursor.advance(8, hash(8, 2), hash(7, 0)).unwrap(), CursorUpdate::Extended);
+        assert_eq!(advance(&cursor, 9, 2), CursorUpdate::Extended);
+        assert_eq!(cursor.head().unwrap(), Some(Checkpoint::new(9, hash(9, 2))));
+
+        assert_eq!(cursor.rewind(5).unwrap(), Some(Checkpoint::new(5, hash(5, 0))));
+        assert_eq!(cursor.next_block().unwrap(), Some(6));
+    }
+
+    #[test]
+    fn bounded_history() {
+        let store = MemoryKvStore::new();
+        let cursor = SyncCursor::new(&store, "blocks").with_history(3);
+        for number in 0..10 {
+            advance(&cursor, number, 0);
+        }
+        let numbers: Vec<_> = cursor.checkpoints().unwrap().iter().map(|c| c.number).collect();
+        assert_eq!(numbers, [7, 8, 9]);
+
+        // Deeper than the recorded history.
+        assert_eq!(
+            cursor.check(7, hash(7, 1), hash(6, 1)).unwrap(),
+            CursorUpdate::RolledBack { to: None }
+        );
+        assert_eq!(cursor.checkpoints().unwrap().len(), 3);
+        assert_eq!(advance(&cursor, 7, 1), CursorUpdate::RolledBack { to: None });
+        assert_eq!(cursor.head().unwrap(), None);
+    }
+}
@@ -0,0 +1,314 @@
+//! This module extends the Ethereum JSON-RPC provider with an address activity scanner built on
+//! top of `eth_getLogs` and `trace_filter`.
+use crate::{ext::TraceApi, Provider};
+use alloy_network::Network;
+use alloy_primitives::{b256, Address, BlockNumber, TxHash, B256, U256};
+use alloy_rpc_types::{Filter, Log};
+use alloy_rpc_types_trace::{
+    filter::TraceFilter,
+    parity::{Action, LocalizedTransactionTrace},
+};
+use alloy_transport::{Transport, TransportResult};
+use std::collections::HashSet;
+
+/// The `Transfer(address,address,uint256)` event signature shared by ERC-20 and ERC-721.
+pub const TRANSFER_EVENT_SIGNATURE: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// Whether an [`Activity`] moved value into or out of the scanned address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ActivityDirection {
+    /// The scanned address received value.
+    Incoming,
+    /// The scanned address sent value. Transfers to self are reported as outgoing.
+    Outgoing,
+}
+
+/// The asset moved by an [`Activity`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ActivityKind {
+    /// A native value transfer, observed through a call trace.
+    Native {
+        /// The transferred amount in wei.
+        value: U256,
+    },
+    /// An ERC-20 `Transfer` event.
+    Erc20 {
+        /// The token contract.
+        token: Address,
+        /// The transferred amount.
+        value: U256,
+    },
+    /// An ERC-721 `Transfer` event.
+    Erc721 {
+        /// The token contract.
+        token: Address,
+        /// The transferred token ID.
+        token_id: U256,
+    },
+}
+
+/// A single transfer involving the scanned address.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Activity {
+    /// Block the transfer was included in.
+    pub block_number: Option<BlockNumber>,
+    /// Hash of the transaction that performed the transfer.
+    pub transaction_hash: Option<TxHash>,
+    /// Index of the transaction in the block.
+    pub transaction_index: Option<u64>,
+    /// Index of the log in the block, for token transfers.
+    pub log_index: Option<u64>,
+    /// Sender of the asset.
+    pub from: Address,
+    /// Recipient of the asset.
+    pub to: Address,
+    /// Direction relative to the scanned address.
+    pub direction: ActivityDirection,
+    /// The transferred asset.
+    pub kind: ActivityKind,
+}
+
+impl Activity {
+    /// Decodes an ERC-20 or ERC-721 `Transfer` log into an activity relative to `address`.
+    ///
+    /// ERC-721 transfers are recognized by the token ID being indexed, i.e. the log having four
+    /// topics. Returns `None` if the log is not a transfer involving `address`.
+    pub fn from_transfer_log(address: Address, log: &Log) -> Option<Self> {
+        let topics = log.topics();
+        if topics.first() != Some(&TRANSFER_EVENT_SIGNATURE) {
+            return None;
+        }
+
+        let token = log.address();
+        let (from, to, kind) = match topics {
+            [_, from, to] => {
+                let data = &log.data().data;
+                if data.len() != 32 {
+                    return None;
+                }
+                let value = U256::from_be_slice(data);
+                (*from, *to, ActivityKind::Erc20 { token, value })
+            }
+            [_, from, to, token_id] => {
+                let token_id = U256::from_be_bytes(token_id.0);
+                (*from, *to, ActivityKind::Erc721 { token, token_id })
+            }
+            _ => return None,
+        };
+        let from = Address::from_word(from);
+        let to = Address::from_word(to);
+
+        Some(Self {
+            block_number: log.block_number,
+            transaction_hash: log.transaction_hash,
+            transaction_index: log.transaction_index,
+            log_index: log.log_index,
+            from,
+            to,
+            direction: direction(address, from, to)?,
+            kind,
+        })
+    }
+
+    /// Converts a call trace carrying value into an activity relative to `address`.
+    ///
+    /// Returns `None` for non-call traces, calls without value, and calls not involving
+    /// `address`.
+    pub fn from_trace(address: Address, trace: &LocalizedTransactionTrace) -> Option<Self> {
+        let Action::Call(call) = &trace.trace.action else { return None };
+        if call.value.is_zero() || trace.trace.error.is_some() {
+            return None;
+        }
+
+        Some(Self {
+            block_number: trace.block_number,
+            transaction_hash: trace.transaction_hash,
+            transaction_index: trace.transaction_position,
+            log_index: None,
+            from: call.from,
+            to: call.to,
+            direction: direction(address, call.from, call.to)?,
+            kind: ActivityKind::Native { value: call.value },
+        })
+    }
+}
+
+fn direction(address: Address, from: Address, to: Address) -> Option<ActivityDirection> {
+    if from == address {
+        Some(ActivityDirection::Outgoing)
+    } else if to == address {
+        Some(ActivityDirection::Incoming)
+    } else {
+        None
+    }
+}
+
+/// Address activity scanning.
+///
+/// Token transfers are discovered through `eth_getLogs` by filtering `Transfer` events on the
+/// sender and recipient topics. Native transfers, including internal ones, are discovered through
+/// `trace_filter` when the node supports it, and are skipped if the node reports the method as not
+/// found. Other `trace_filter` errors, e.g. rate or result size limits, are returned.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait ActivityApi<N, T>: Send + Sync {
+    /// Returns all incoming and outgoing transfers of `address` between `from_block` and
+    /// `to_block` (inclusive), ordered by block and transaction index.
+    async fn address_activity(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Vec<Activity>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> ActivityApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn address_activity(
+        &self,
+        address: Address,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> TransportResult<Vec<Activity>> {
+        let filter = TraceFilter::default()
+            .from_block(from_block)
+            .to_block(to_block)
+            .from_address(vec![address])
+            .to_address(vec![address]);
+        let traces = match self.trace_filter(&filter).await {
+            Ok(traces) => traces,
+            // The node does not support `trace_filter`.
+            Err(err) if err.as_error_resp().is_some_and(|payload| payload.code == -32601) => {
+                Vec::new()
+            }
+            Err(err) => return Err(err),
+        };
+        let mut activity: Vec<_> =
+            traces.iter().filter_map(|trace| Activity::from_trace(address, trace)).collect();
+
+        let topic = address.into_word();
+        let filter = Filter::new()
+            .from_block(from_block)
+            .to_block(to_block)
+            .event_signature(TRANSFER_EVENT_SIGNATURE);
+        let outgoing = filter.clone().topic1(topic);
+        let incoming = filter.topic2(topic);
+        let (outgoing, incoming) =
+            futures::try_join!(self.get_logs(&outgoing), self.get_logs(&incoming))?;
+
+        // Transfers to self show up in both queries.
+        let mut seen = HashSet::new();
+        activity.extend(
+            outgoing
+                .iter()
+                .chain(&incoming)
+                .filter(|log| seen.insert((log.block_hash, log.log_index)))
+                .filter_map(|log| Activity::from_transfer_log(address, log)),
+        );
+
+        activity.sort_by_key(|activity| (activity.block_number, activity.transaction_index));
+        Ok(activity)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::{address, bytes, LogData};
+    use alloy_rpc_types_trace::parity::{CallAction, CallType, TransactionTrace};
+
+    const ME: Address = address!("00000000000000000000000000000000000000aa");
+    const OTHER: Address = address!("00000000000000000000000000000000000000bb");
+    const TOKEN: Address = address!("00000000000000000000000000000000000000cc");
+
+    fn transfer_log(topics: Vec<B256>, data: alloy_primitives::Bytes) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address: TOKEN,
+                data: LogData::new_unchecked(topics, data),
+            },
+            block_number: Some(1),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn decodes_erc20_transfer() {
+        let log = transfer_log(
+            vec![TRANSFER_EVENT_SIGNATURE, OTHER.into_word(), ME.into_word()],
+            U256::from(42).to_be_bytes_vec().into(),
+        );
+        let activity = Activity::from_transfer_log(ME, &log).unwrap();
+        assert_eq!(activity.direction, ActivityDirection::Incoming);
+        assert_eq!(activity.from, OTHER);
+        assert_eq!(activity.to, ME);
+        assert_eq!(activity.kind, ActivityKind::Erc20 { token: TOKEN, value: U256::from(42) });
+
+        assert!(Activity::from_transfer_log(TOKEN, &log).is_none());
+    }
+
+    #[test]
+    fn decodes_erc721_transfer() {
+        let log = transfer_log(
+            vec![
+                TRANSFER_EVENT_SIGNATURE,
+                ME.into_word(),
+                OTHER.into_word(),
+                B256::with_last_byte(7),
+            ],
+            bytes!(""),
+        );
+        let activity = Activity::from_transfer_log(ME, &log).unwrap();
+        assert_eq!(activity.direction, ActivityDirection::Outgoing);
+        assert_eq!(activity.kind, ActivityKind::Erc721 { token: TOKEN, token_id: U256::from(7) });
+    }
+
+    #[test]
+    fn ignores_malformed_transfer() {
+        let log = transfer_log(
+            vec![TRANSFER_EVENT_SIGNATURE, ME.into_word(), OTHER.into_word()],
+            bytes!("01"),
+        );
+        assert!(Activity::from_transfer_log(ME, &log).is_none());
+
+        let log = transfer_log(vec![B256::ZERO, ME.into_word(), OTHER.into_word()], bytes!(""));
+        assert!(Activity::from_transfer_log(ME, &log).is_none());
+    }
+
+    #[test]
+    fn converts_value_trace() {
+        let mut trace = LocalizedTransactionTrace {
+            trace: TransactionTrace {
+                action: Action::Call(CallAction {
+                    from: OTHER,
+                    call_type: CallType::Call,
+                    gas: Default::default(),
+                    input: Default::default(),
+                    to: ME,
+                    value: U256::from(1),
+                }),
+                error: None,
+                result: None,
+                subtraces: 0,
+                trace_address: vec![],
+            },
+            block_hash: None,
+            block_number: Some(2),
+            transaction_hash: None,
+            transaction_position: Some(0),
+        };
+        let activity = Activity::from_trace(ME, &trace).unwrap();
+        assert_eq!(activity.direction, ActivityDirection::Incoming);
+        assert_eq!(activity.kind, ActivityKind::Native { value: U256::from(1) });
+
+        trace.trace.error = Some("reverted".to_string());
+        assert!(Activity::from_trace(ME, &trace).is_none());
+    }
+}
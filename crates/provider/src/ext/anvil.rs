@@ -0,0 +1,392 @@
+//! This module extends the Ethereum JSON-RPC provider with the Anvil namespace's RPC methods.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::{Address, Bytes, TxHash, B256, U256, U64};
+use alloy_transport::{Transport, TransportResult};
+
+/// Anvil namespace rpc interface that gives access to several non-standard RPC methods, to
+/// manipulate the state and the mining of a development node.
+///
+/// Most methods are also supported by Hardhat, with the `hardhat_` prefix instead of `anvil_`.
+#[allow(unused, unreachable_pub)]
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait AnvilApi<N, T>: Send + Sync {
+    /// Send transactions impersonating an externally owned account or contract.
+    ///
+    /// Transactions from `address` are then accepted by `eth_sendTransaction` without a
+    /// signature.
+    async fn anvil_impersonate_account(&self, address: Address) -> TransportResult<()>;
+
+    /// Stops impersonating an account, after having previously used
+    /// [`anvil_impersonate_account`](Self::anvil_impersonate_account).
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> TransportResult<()>;
+
+    /// If set to `true`, all accounts are impersonated.
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> TransportResult<()>;
+
+    /// Returns `true` if automatic mining is enabled, and `false` otherwise.
+    async fn anvil_get_auto_mine(&self) -> TransportResult<bool>;
+
+    /// Enables or disables automatic mining of new blocks with each new transaction.
+    async fn anvil_set_auto_mine(&self, enabled: bool) -> TransportResult<()>;
+
+    /// Mines `num_blocks` blocks, one by default, with `interval` seconds between their
+    /// timestamps, one by default.
+    async fn anvil_mine(
+        &self,
+        num_blocks: Option<u64>,
+        interval: Option<u64>,
+    ) -> TransportResult<()>;
+
+    /// Sets the mining behavior to interval mining, with the given interval in seconds. An
+    /// interval of `0` disables it.
+    async fn anvil_set_interval_mining(&self, secs: u64) -> TransportResult<()>;
+
+    /// Removes a transaction from the mempool, returning its hash if it was found.
+    async fn anvil_drop_transaction(&self, tx_hash: TxHash) -> TransportResult<Option<TxHash>>;
+
+    /// Sets the balance of an account.
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> TransportResult<()>;
+
+    /// Sets the code of an account.
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> TransportResult<()>;
+
+    /// Sets the nonce of an account.
+    async fn anvil_set_nonce(&self, address: Address, nonce: u64) -> TransportResult<()>;
+
+    /// Writes a single slot of the storage of an account, returning `true` on success.
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> TransportResult<bool>;
+
+    /// Sets the coinbase address of the next blocks.
+    async fn anvil_set_coinbase(&self, address: Address) -> TransportResult<()>;
+
+    /// Sets the chain ID of the node.
+    async fn anvil_set_chain_id(&self, chain_id: u64) -> TransportResult<()>;
+
+    /// Sets the base fee of the next block.
+    async fn anvil_set_next_block_base_fee_per_gas(&self, base_fee: u128) -> TransportResult<()>;
+
+    /// Enables or disables the logging of the node.
+    async fn anvil_set_logging(&self, enabled: bool) -> TransportResult<()>;
+
+    /// Serializes the current state, including all transactions and blocks, into a blob that can
+    /// be restored with [`anvil_load_state`](Self::anvil_load_state).
+    async fn anvil_dump_state(&self) -> TransportResult<Bytes>;
+
+    /// Appends a state blob created with [`anvil_dump_state`](Self::anvil_dump_state) to the
+    /// current state, returning `true` on success.
+    async fn anvil_load_state(&self, state: Bytes) -> TransportResult<bool>;
+
+    /// Snapshots the state of the blockchain at the current block, returning the ID of the
+    /// snapshot.
+    async fn evm_snapshot(&self) -> TransportResult<U256>;
+
+    /// Reverts the state of the blockchain to a previous snapshot, returning `true` if the
+    /// snapshot existed. Snapshots taken after it are deleted.
+    async fn evm_revert(&self, id: U256) -> TransportResult<bool>;
+
+    /// Jumps forward in time by the given number of seconds, returning the total time adjustment.
+    async fn evm_increase_time(&self, seconds: u64) -> TransportResult<i64>;
+
+    /// Sets the timestamp of the next block.
+    async fn evm_set_next_block_timestamp(&self, timestamp: u64) -> TransportResult<()>;
+
+    /// Sets the current time of the node, returning the time adjustment it caused.
+    async fn evm_set_time(&self, timestamp: u64) -> TransportResult<u64>;
+
+    /// Sets the gas limit of the next blocks, returning `true` on success.
+    async fn evm_set_block_gas_limit(&self, gas_limit: u128) -> TransportResult<bool>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> AnvilApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn anvil_impersonate_account(&self, address: Address) -> TransportResult<()> {
+        self.client().request("anvil_impersonateAccount", (address,)).await
+    }
+
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> TransportResult<()> {
+        self.client().request("anvil_stopImpersonatingAccount", (address,)).await
+    }
+
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> TransportResult<()> {
+        self.client().request("anvil_autoImpersonateAccount", (enabled,)).await
+    }
+
+    async fn anvil_get_auto_mine(&self) -> TransportResult<bool> {
+        self.client().request("anvil_getAutomine", ()).await
+    }
+
+    async fn anvil_set_auto_mine(&self, enabled: bool) -> TransportResult<()> {
+        self.client().request("anvil_setAutomine", (enabled,)).await
+    }
+
+    async fn anvil_mine(
+        &self,
+        num_blocks: Option<u64>,
+        interval: Option<u64>,
+    ) -> TransportResult<()> {
+        self.client()
+            .request("anvil_mine", (num_blocks.map(U64::from), interval.map(U64::from)))
+            .await
+    }
+
+    async fn anvil_set_interval_mining(&self, secs: u64) -> TransportResult<()> {
+        self.client().request("anvil_setIntervalMining", (secs,)).await
+    }
+
+    async fn anvil_drop_transaction(&self, tx_hash: TxHash) -> TransportResult<Option<TxHash>> {
+        self.client().request("anvil_dropTransaction", (tx_hash,)).await
+    }
+
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> TransportResult<()> {
+        self.client().request("anvil_setBalance", (address, balance)).await
+    }
+
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> TransportResult<()> {
+        self.client().request("anvil_setCode", (address, code)).await
+    }
+
+    async fn anvil_set_nonce(&self, address: Address, nonce: u64) -> TransportResult<()> {
+        self.client().request("anvil_setNonce", (address, U64::from(nonce))).await
+    }
+
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> TransportResult<bool> {
+        self.client().request("anvil_setStorageAt", (address, slot, value)).await
+    }
+
+    async fn anvil_set_coinbase(&self, address: Address) -> TransportResult<()> {
+        self.client().request("anvil_setCoinbase", (address,)).await
+    }
+
+    async fn anvil_set_chain_id(&self, chain_id: u64) -> TransportResult<()> {
+        self.client().request("anvil_setChainId", (chain_id,)).await
+    }
+
+    async fn anvil_set_next_block_base_fee_per_gas(&self, base_fee: u128) -> TransportResult<()> {
+        self.client().request("anvil_setNextBlockBaseFeePerGas", (U256::from(base_fee),)).await
+    }
+
+    async fn anvil_set_logging(&self, enabled: bool) -> TransportResult<()> {
+        self.client().request("anvil_setLoggingEnabled", (enabled,)).await
+    }
+
+    async fn anvil_dump_state(&self) -> TransportResult<Bytes> {
+        self.client().request("anvil_dumpState", ()).await
+    }
+
+    async fn anvil_load_state(&self, state: Bytes) -> TransportResult<bool> {
+        self.client().request("anvil_loadState", (state,)).await
+    }
+
+    async fn evm_snapshot(&self) -> TransportResult<U256> {
+        self.client().request("evm_snapshot", ()).await
+    }
+
+    async fn evm_revert(&self, id: U256) -> TransportResult<bool> {
+        self.client().request("evm_revert", (id,)).await
+    }
+
+    async fn evm_increase_time(&self, seconds: u64) -> TransportResult<i64> {
+        self.client().request("evm_increaseTime", (U256::from(seconds),)).await
+    }
+
+    async fn evm_set_next_block_timestamp(&self, timestamp: u64) -> TransportResult<()> {
+        self.client().request("evm_setNextBlockTimestamp", (timestamp,)).await
+    }
+
+    async fn evm_set_time(&self, timestamp: u64) -> TransportResult<u64> {
+        self.client().request("evm_setTime", (timestamp,)).await
+    }
+
+    async fn evm_set_block_gas_limit(&self, gas_limit: u128) -> TransportResult<bool> {
+        self.client().request("evm_setBlockGasLimit", (U256::from(gas_limit),)).await
+    }
+}
+
+/// Frozen gas market conditions of an Anvil node, to make fee-dependent tests reproducible.
+///
+/// Once [applied](Self::apply), the node no longer mines blocks on its own, and the base fee of
+/// every block mined through [`mine`](Self::mine) is the configured one, instead of following the
+/// gas used by its parent. Fee estimates, such as [`Provider::estimate_eip1559_fees`], then only
+/// depend on the transactions sent by the test: the priority fees of the fee history only come
+/// from the blocks it mines.
+///
+/// # Example
+///
+/// ```no_run
+/// # use alloy_provider::{ext::GasConditions, Provider, ProviderBuilder};
+/// # async fn test(url: url::Url) -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new().on_http(url);
+/// let conditions = GasConditions::new(1_000_000_000);
+/// conditions.apply(&provider).await?;
+///
+/// // Send transactions, then include them in a block at the frozen base fee.
+/// conditions.mine(&provider, 1).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GasConditions {
+    base_fee_per_gas: u128,
+}
+
+impl GasConditions {
+    /// Creates new conditions, with the given base fee for the next blocks.
+    pub const fn new(base_fee_per_gas: u128) -> Self {
+        Self { base_fee_per_gas }
+    }
+
+    /// Returns the base fee of the next blocks.
+    pub const fn base_fee_per_gas(&self) -> u128 {
+        self.base_fee_per_gas
+    }
+
+    /// Stops the automatic and interval mining of the node, and sets the base fee of the next
+    /// block.
+    pub async fn apply<P, N, T>(&self, provider: &P) -> TransportResult<()>
+    where
+        P: AnvilApi<N, T> + ?Sized,
+    {
+        provider.anvil_set_auto_mine(false).await?;
+        provider.anvil_set_interval_mining(0).await?;
+        provider.anvil_set_next_block_base_fee_per_gas(self.base_fee_per_gas).await
+    }
+
+    /// Mines `blocks` blocks, all with the configured base fee.
+    pub async fn mine<P, N, T>(&self, provider: &P, blocks: u64) -> TransportResult<()>
+    where
+        P: AnvilApi<N, T> + ?Sized,
+    {
+        for _ in 0..blocks {
+            provider.anvil_mine(Some(1), None).await?;
+            provider.anvil_set_next_block_base_fee_per_gas(self.base_fee_per_gas).await?;
+        }
+        Ok(())
+    }
+
+    /// Restores the automatic mining of the node. The base fee of the next blocks follows the gas
+    /// used by their parent again.
+    pub async fn release<P, N, T>(&self, provider: &P) -> TransportResult<()>
+    where
+        P: AnvilApi<N, T> + ?Sized,
+    {
+        provider.anvil_set_auto_mine(true).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_node_bindings::Anvil;
+
+    #[tokio::test]
+    async fn test_anvil_set_balance() {
+        let anvil = Anvil::new().spawn();
+        let provider = ProviderBuilder::new().on_http(anvil.endpoint_url());
+
+        let address = Address::with_last_byte(0x42);
+        let balance = U256::from(1_000_000_000_000_000_000u128);
+        provider.anvil_set_balance(address, balance).await.unwrap();
+        assert_eq!(provider.get_balance(address).await.unwrap(), balance);
+    }
+
+    #[tokio::test]
+    async fn test_anvil_set_storage_at() {
+        let anvil = Anvil::new().spawn();
+        let provider = ProviderBuilder::new().on_http(anvil.endpoint_url());
+
+        let address = Address::with_last_byte(0x42);
+        let value = B256::with_last_byte(42);
+        assert!(provider.anvil_set_storage_at(address, U256::ZERO, value).await.unwrap());
+        let stored = provider.get_storage_at(address, U256::ZERO).await.unwrap();
+        assert_eq!(B256::from(stored), value);
+    }
+
+    #[tokio::test]
+    async fn test_anvil_mine() {
+        let anvil = Anvil::new().spawn();
+        let provider = ProviderBuilder::new().on_http(anvil.endpoint_url());
+
+        let start = provider.get_block_number().await.unwrap();
+        provider.anvil_mine(Some(10), None).await.unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), start + 10);
+    }
+
+    #[tokio::test]
+    async fn test_evm_snapshot_revert() {
+        let anvil = Anvil::new().spawn();
+        let provider = ProviderBuilder::new().on_http(anvil.endpoint_url());
+
+        let start = provider.get_block_number().await.unwrap();
+        let id = provider.evm_snapshot().await.unwrap();
+        provider.anvil_mine(Some(3), None).await.unwrap();
+        assert!(provider.evm_revert(id).await.unwrap());
+        assert_eq!(provider.get_block_number().await.unwrap(), start);
+    }
+
+    #[tokio::test]
+    async fn test_anvil_impersonate_account() {
+        let anvil = Anvil::new().spawn();
+        let provider = ProviderBuilder::new().on_http(anvil.endpoint_url());
+
+        let impersonated = Address::with_last_byte(0x42);
+        provider.anvil_set_balance(impersonated, U256::from(1e18 as u128)).await.unwrap();
+        provider.anvil_impersonate_account(impersonated).await.unwrap();
+
+        let tx = alloy_rpc_types::TransactionRequest::default()
+            .from(impersonated)
+            .to(anvil.addresses()[0])
+            .value(U256::from(1));
+        let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+        assert_eq!(receipt.from, impersonated);
+
+        provider.anvil_stop_impersonating_account(impersonated).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_gas_conditions() {
+        let anvil = Anvil::new().spawn();
+        let provider = ProviderBuilder::new().on_http(anvil.endpoint_url());
+
+        let conditions = GasConditions::new(7_000_000_000);
+        conditions.apply(&provider).await.unwrap();
+        assert!(!provider.anvil_get_auto_mine().await.unwrap());
+
+        let start = provider.get_block_number().await.unwrap();
+        conditions.mine(&provider, 3).await.unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), start + 3);
+        for number in start + 1..=start + 3 {
+            let block = provider.get_block_by_number(number.into(), false).await.unwrap().unwrap();
+            assert_eq!(block.header.base_fee_per_gas, Some(conditions.base_fee_per_gas()));
+        }
+
+        // Empty blocks pay no priority fees.
+        let fees = provider.estimate_eip1559_fees(None).await.unwrap();
+        assert_eq!(fees.max_priority_fee_per_gas, 0);
+        assert_eq!(
+            fees.max_fee_per_gas,
+            conditions.base_fee_per_gas() * crate::utils::EIP1559_BASE_FEE_MULTIPLIER
+        );
+
+        conditions.release(&provider).await.unwrap();
+        assert!(provider.anvil_get_auto_mine().await.unwrap());
+    }
+}
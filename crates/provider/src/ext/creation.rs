@@ -0,0 +1,150 @@
+//! This module extends the Ethereum JSON-RPC provider with helpers to locate the deployment of a
+//! contract.
+use crate::Provider;
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, BlockNumber, TxHash};
+use alloy_rpc_types::BlockNumberOrTag;
+use alloy_rpc_types_trace::parity::{Action, LocalizedTransactionTrace, TraceOutput};
+use alloy_transport::{Transport, TransportResult};
+
+/// The deployment of a contract, as found by [`CreationApi::find_creation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContractCreation {
+    /// The first block at which the contract has code.
+    pub block_number: BlockNumber,
+    /// The hash of the transaction that deployed the contract, if it could be traced.
+    pub transaction_hash: Option<TxHash>,
+    /// The account that executed the `CREATE` or `CREATE2`, if it could be traced.
+    pub creator: Option<Address>,
+}
+
+/// Returns the trace that created `address`, if any.
+pub fn find_creation_trace(
+    address: Address,
+    traces: &[LocalizedTransactionTrace],
+) -> Option<&LocalizedTransactionTrace> {
+    traces.iter().find(|trace| {
+        matches!(trace.trace.action, Action::Create(_))
+            && matches!(&trace.trace.result, Some(TraceOutput::Create(out)) if out.address == address)
+    })
+}
+
+/// Contract creation lookups.
+///
+/// The creation block is found by binary searching `eth_getCode` between genesis and the latest
+/// block, which takes `O(log n)` requests and requires an archive node for historical state.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait CreationApi<N, T>: Send + Sync {
+    /// Returns the first block at which `address` has code, or `None` if it has no code at the
+    /// latest block.
+    ///
+    /// # Note
+    ///
+    /// The search assumes that code is never removed once deployed. For contracts that were
+    /// self-destructed and redeployed at the same address, any of the deployments may be found.
+    async fn find_creation_block(&self, address: Address) -> TransportResult<Option<BlockNumber>>;
+
+    /// Returns the creation block of `address`, along with the deploying transaction and creator
+    /// found with `trace_block`, which the node must support.
+    async fn find_creation(&self, address: Address) -> TransportResult<Option<ContractCreation>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> CreationApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn find_creation_block(&self, address: Address) -> TransportResult<Option<BlockNumber>> {
+        let latest = self.get_block_number().await?;
+        if self.get_code_at(address).block_id(BlockId::number(latest)).await?.is_empty() {
+            return Ok(None);
+        }
+
+        // Invariant: the contract has code at `high`, and no code before `low`.
+        let (mut low, mut high) = (0, latest);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.get_code_at(address).block_id(BlockId::number(mid)).await?.is_empty() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(Some(high))
+    }
+
+    async fn find_creation(&self, address: Address) -> TransportResult<Option<ContractCreation>> {
+        let Some(block_number) = self.find_creation_block(address).await? else {
+            return Ok(None);
+        };
+
+        let traces = self.trace_block(BlockNumberOrTag::Number(block_number)).await?;
+        let trace = find_creation_trace(address, &traces);
+
+        Ok(Some(ContractCreation {
+            block_number,
+            transaction_hash: trace.and_then(|trace| trace.transaction_hash),
+            creator: trace.and_then(|trace| match &trace.trace.action {
+                Action::Create(create) => Some(create.from),
+                _ => None,
+            }),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_primitives::{address, b256, Bytes, U256};
+    use alloy_rpc_types_trace::parity::{CreateAction, CreateOutput, TransactionTrace};
+
+    #[test]
+    fn finds_creation_trace() {
+        let deployed = address!("00000000000000000000000000000000000000dd");
+        let trace = |address| LocalizedTransactionTrace {
+            trace: TransactionTrace {
+                action: Action::Create(CreateAction {
+                    from: Address::ZERO,
+                    gas: Default::default(),
+                    init: Bytes::new(),
+                    value: U256::ZERO,
+                }),
+                error: None,
+                result: Some(TraceOutput::Create(CreateOutput {
+                    address,
+                    code: Bytes::new(),
+                    gas_used: Default::default(),
+                })),
+                subtraces: 0,
+                trace_address: vec![],
+            },
+            block_hash: None,
+            block_number: Some(1),
+            transaction_hash: Some(b256!(
+                "00000000000000000000000000000000000000000000000000000000000000ff"
+            )),
+            transaction_position: Some(0),
+        };
+
+        let traces = vec![trace(Address::ZERO), trace(deployed)];
+        assert_eq!(find_creation_trace(deployed, &traces), Some(&traces[1]));
+        assert_eq!(find_creation_trace(Address::with_last_byte(1), &traces), None);
+    }
+
+    #[tokio::test]
+    async fn finds_creation_block() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let addr = Address::with_last_byte(16);
+        assert_eq!(provider.find_creation_block(addr).await.unwrap(), None);
+
+        provider.set_code(addr, "0xbeef").await.unwrap();
+        let latest = provider.get_block_number().await.unwrap();
+        assert_eq!(provider.find_creation_block(addr).await.unwrap(), Some(latest));
+    }
+}
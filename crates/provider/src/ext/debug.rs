@@ -2,7 +2,7 @@
 use crate::Provider;
 use alloy_network::Network;
 use alloy_primitives::{TxHash, B256};
-use alloy_rpc_types::{BlockNumberOrTag, TransactionRequest};
+use alloy_rpc_types::{BlockNumberOrTag, Bundle, StateContext, TransactionRequest};
 use alloy_rpc_types_trace::geth::{
     GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
@@ -76,7 +76,12 @@ pub trait DebugApi<N, T>: Send + Sync {
         trace_options: GethDebugTracingCallOptions,
     ) -> TransportResult<GethTrace>;
 
-    /// Same as `debug_trace_call` but it used to run and trace multiple transactions at once.
+    /// Same as `debug_trace_call` but it used to run and trace multiple bundles of transactions
+    /// at once.
+    ///
+    /// The bundles are executed in order on top of the state selected by the [StateContext], each
+    /// transaction seeing the changes of the previous ones. The result contains one list of traces
+    /// per bundle, with one trace per transaction.
     ///
     /// [GethDebugTracingOptions] can be used to specify the trace options.
     ///
@@ -85,10 +90,10 @@ pub trait DebugApi<N, T>: Send + Sync {
     /// Not all nodes support this call.
     async fn debug_trace_call_many(
         &self,
-        txs: Vec<TransactionRequest>,
-        block: BlockNumberOrTag,
+        bundles: Vec<Bundle>,
+        state_context: StateContext,
         trace_options: GethDebugTracingCallOptions,
-    ) -> TransportResult<Vec<GethTrace>>;
+    ) -> TransportResult<Vec<Vec<GethTrace>>>;
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -134,11 +139,11 @@ where
 
     async fn debug_trace_call_many(
         &self,
-        txs: Vec<TransactionRequest>,
-        block: BlockNumberOrTag,
+        bundles: Vec<Bundle>,
+        state_context: StateContext,
         trace_options: GethDebugTracingCallOptions,
-    ) -> TransportResult<Vec<GethTrace>> {
-        self.client().request("debug_traceCallMany", (txs, block, trace_options)).await
+    ) -> TransportResult<Vec<Vec<GethTrace>>> {
+        self.client().request("debug_traceCallMany", (bundles, state_context, trace_options)).await
     }
 }
 
@@ -149,6 +154,7 @@ mod test {
     use super::*;
     use alloy_network::TransactionBuilder;
     use alloy_primitives::{address, U256};
+    use alloy_rpc_types_trace::geth::CallConfig;
 
     fn init_tracing() {
         let _ = tracing_subscriber::fmt::try_init();
@@ -201,4 +207,27 @@ mod test {
             assert!(!trace.struct_logs.is_empty());
         }
     }
+
+    #[tokio::test]
+    async fn test_debug_trace_call_with_call_tracer() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil_with_signer();
+        let from = provider.default_signer_address();
+        let gas_price = provider.get_gas_price().await.unwrap();
+        let tx = TransactionRequest::default()
+            .from(from)
+            .to(address!("deadbeef00000000deadbeef00000000deadbeef"))
+            .value(U256::from(100))
+            .max_fee_per_gas(gas_price + 1)
+            .max_priority_fee_per_gas(gas_price + 1);
+
+        let options = GethDebugTracingCallOptions::default().with_tracing_options(
+            GethDebugTracingOptions::call_tracer(CallConfig::default().only_top_call()),
+        );
+        let trace = provider.debug_trace_call(tx, BlockNumberOrTag::Latest, options).await.unwrap();
+
+        let GethTrace::CallTracer(frame) = trace else { panic!("expected a call frame") };
+        assert_eq!(frame.from, from);
+        assert!(frame.calls.is_empty());
+    }
 }
@@ -0,0 +1,93 @@
+//! This module extends the Ethereum JSON-RPC provider with the ERC-4337 bundler namespace's RPC
+//! methods.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types::erc4337::{
+    SendUserOperation, UserOperationByHash, UserOperationGasEstimation, UserOperationReceipt,
+};
+use alloy_transport::{Transport, TransportResult};
+
+/// ERC-4337 bundler rpc interface.
+///
+/// See [EIP-4337](https://eips.ethereum.org/EIPS/eip-4337#rpc-methods-eth-namespace) for more
+/// details.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait Erc4337Api<N, T>: Send + Sync {
+    /// Submits a user operation to the bundler's mempool for the given entry point, returning the
+    /// user operation hash.
+    async fn send_user_operation(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+    ) -> TransportResult<B256>;
+
+    /// Estimates the gas values of a user operation for the given entry point.
+    ///
+    /// The gas limits and signature of the operation may be left as placeholders.
+    async fn estimate_user_operation_gas(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+    ) -> TransportResult<UserOperationGasEstimation>;
+
+    /// Returns the user operation with the given hash, along with its inclusion info.
+    async fn get_user_operation_by_hash(
+        &self,
+        user_op_hash: B256,
+    ) -> TransportResult<Option<UserOperationByHash>>;
+
+    /// Returns the receipt of the user operation with the given hash, or `None` if it has not
+    /// been included yet.
+    async fn get_user_operation_receipt(
+        &self,
+        user_op_hash: B256,
+    ) -> TransportResult<Option<UserOperationReceipt>>;
+
+    /// Returns the entry points supported by the bundler, in order of preference.
+    async fn supported_entry_points(&self) -> TransportResult<Vec<Address>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> Erc4337Api<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn send_user_operation(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+    ) -> TransportResult<B256> {
+        self.client().request("eth_sendUserOperation", (user_op, entry_point)).await
+    }
+
+    async fn estimate_user_operation_gas(
+        &self,
+        user_op: &SendUserOperation,
+        entry_point: Address,
+    ) -> TransportResult<UserOperationGasEstimation> {
+        self.client().request("eth_estimateUserOperationGas", (user_op, entry_point)).await
+    }
+
+    async fn get_user_operation_by_hash(
+        &self,
+        user_op_hash: B256,
+    ) -> TransportResult<Option<UserOperationByHash>> {
+        self.client().request("eth_getUserOperationByHash", (user_op_hash,)).await
+    }
+
+    async fn get_user_operation_receipt(
+        &self,
+        user_op_hash: B256,
+    ) -> TransportResult<Option<UserOperationReceipt>> {
+        self.client().request("eth_getUserOperationReceipt", (user_op_hash,)).await
+    }
+
+    async fn supported_entry_points(&self) -> TransportResult<Vec<Address>> {
+        self.client().request("eth_supportedEntryPoints", ()).await
+    }
+}
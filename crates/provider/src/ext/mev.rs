@@ -0,0 +1,78 @@
+//! This module extends the Ethereum JSON-RPC provider with the bundle RPC methods of Flashbots
+//! relays and MEV-Share.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_rpc_types::mev::{
+    EthBundleHash, EthCallBundle, EthCallBundleResponse, EthSendBundle, SendBundleRequest,
+    SendBundleResponse, SimBundleOverrides, SimBundleResponse,
+};
+use alloy_transport::{Transport, TransportResult};
+
+/// Bundle rpc interface of Flashbots relays and MEV-Share.
+///
+/// Relays require every request to be signed with the `X-Flashbots-Signature` header. Connect the
+/// provider to the relay through the `FlashbotsHttp` transport of `alloy-transport-http`, with
+/// the `flashbots` feature enabled, to sign requests.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait MevApi<N, T>: Send + Sync {
+    /// Submits a bundle to be included in the given block.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#eth_sendbundle)
+    /// for more details.
+    async fn send_bundle(&self, bundle: &EthSendBundle) -> TransportResult<EthBundleHash>;
+
+    /// Simulates a bundle against the state of the given block.
+    ///
+    /// See [here](https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint#eth_callbundle)
+    /// for more details.
+    async fn call_bundle(&self, bundle: &EthCallBundle) -> TransportResult<EthCallBundleResponse>;
+
+    /// Submits a MEV-Share bundle.
+    ///
+    /// See [here](https://github.com/flashbots/mev-share/blob/main/specs/bundles/v0.1.md) for more
+    /// details.
+    async fn send_mev_bundle(
+        &self,
+        bundle: &SendBundleRequest,
+    ) -> TransportResult<SendBundleResponse>;
+
+    /// Simulates a MEV-Share bundle, optionally overriding the simulated block environment.
+    async fn sim_mev_bundle(
+        &self,
+        bundle: &SendBundleRequest,
+        overrides: &SimBundleOverrides,
+    ) -> TransportResult<SimBundleResponse>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> MevApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn send_bundle(&self, bundle: &EthSendBundle) -> TransportResult<EthBundleHash> {
+        self.client().request("eth_sendBundle", (bundle,)).await
+    }
+
+    async fn call_bundle(&self, bundle: &EthCallBundle) -> TransportResult<EthCallBundleResponse> {
+        self.client().request("eth_callBundle", (bundle,)).await
+    }
+
+    async fn send_mev_bundle(
+        &self,
+        bundle: &SendBundleRequest,
+    ) -> TransportResult<SendBundleResponse> {
+        self.client().request("mev_sendBundle", (bundle,)).await
+    }
+
+    async fn sim_mev_bundle(
+        &self,
+        bundle: &SendBundleRequest,
+        overrides: &SimBundleOverrides,
+    ) -> TransportResult<SimBundleResponse> {
+        self.client().request("mev_simBundle", (bundle, overrides)).await
+    }
+}
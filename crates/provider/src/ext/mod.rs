@@ -1,8 +1,19 @@
 //! Extended APIs for the provider module.
 
+mod activity;
+pub use activity::{
+    Activity, ActivityApi, ActivityDirection, ActivityKind, TRANSFER_EVENT_SIGNATURE,
+};
+
 mod admin;
 pub use admin::AdminApi;
 
+mod anvil;
+pub use anvil::{AnvilApi, GasConditions};
+
+mod creation;
+pub use creation::{find_creation_trace, ContractCreation, CreationApi};
+
 #[cfg(feature = "engine-api")]
 mod engine;
 #[cfg(feature = "engine-api")]
@@ -11,5 +22,34 @@ pub use engine::EngineApi;
 mod debug;
 pub use debug::DebugApi;
 
+mod erc4337;
+pub use erc4337::Erc4337Api;
+
+mod mev;
+pub use mev::MevApi;
+
+mod net;
+pub use net::NetApi;
+
+mod nonce;
+pub use nonce::{NonceDiagnosis, NonceDiagnosisApi, StuckReason, StuckTransaction};
+
+mod otterscan;
+pub use otterscan::OtterscanApi;
+
+mod paginate;
+pub use paginate::{
+    LogQuery, OtsSearchQuery, Page, PaginatedQuery, PaginationApi, TraceFilterQuery,
+};
+
+mod sampling;
+pub use sampling::{sample_block_numbers, Sample, SamplingApi};
+
+mod trace;
+pub use trace::TraceApi;
+
 mod txpool;
 pub use txpool::TxPoolApi;
+
+mod web3;
+pub use web3::Web3Api;
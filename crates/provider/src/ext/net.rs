@@ -0,0 +1,55 @@
+//! This module extends the Ethereum JSON-RPC provider with the Net namespace's RPC methods.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::U64;
+use alloy_transport::{Transport, TransportResult};
+
+/// Net namespace rpc interface that gives access to the node's network status.
+///
+/// The network ID is returned by [`Provider::get_net_version`].
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait NetApi<N, T>: Send + Sync {
+    /// Returns the number of peers currently connected to the node.
+    async fn net_peer_count(&self) -> TransportResult<u64>;
+
+    /// Returns `true` if the node is actively listening for network connections.
+    async fn net_listening(&self) -> TransportResult<bool>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> NetApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn net_peer_count(&self) -> TransportResult<u64> {
+        let count: U64 = self.client().request("net_peerCount", ()).await?;
+        Ok(count.to())
+    }
+
+    async fn net_listening(&self) -> TransportResult<bool> {
+        self.client().request("net_listening", ()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ProviderBuilder;
+
+    use super::*;
+    use alloy_node_bindings::Geth;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn net_status() {
+        let temp_dir = TempDir::with_prefix("geth-test-").unwrap();
+        let geth = Geth::new().disable_discovery().data_dir(temp_dir.path()).spawn();
+        let provider = ProviderBuilder::new().on_http(geth.endpoint_url());
+        assert_eq!(provider.get_net_version().await.unwrap(), 1337);
+        assert_eq!(provider.net_peer_count().await.unwrap(), 0);
+        assert!(provider.net_listening().await.unwrap());
+    }
+}
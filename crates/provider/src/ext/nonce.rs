@@ -0,0 +1,371 @@
+//! This module extends the Ethereum JSON-RPC provider with a diagnosis of stuck account nonces.
+use crate::{
+    ext::{OtterscanApi, TxPoolApi},
+    provider::{bump_fee, MIN_FEE_BUMP},
+    utils::{self, Eip1559Estimation},
+    Provider,
+};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_network::Network;
+use alloy_primitives::{Address, TxHash};
+use alloy_rpc_types::{Block, Transaction};
+use alloy_transport::{Transport, TransportResult};
+use std::collections::BTreeMap;
+
+/// The reason a transaction in the pool is not being included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StuckReason {
+    /// The transaction waits for lower nonces that are missing from the pool.
+    NonceGap,
+    /// The transaction tips less than currently needed to be included.
+    Underpriced,
+}
+
+/// A transaction of the sender that is stuck in the pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StuckTransaction {
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// Why the transaction is stuck.
+    pub reason: StuckReason,
+    /// The fees a replacement of an underpriced transaction should pay: its own fees bumped by
+    /// the minimum replacement bump, or the current network fees if higher.
+    ///
+    /// `None` for transactions stuck behind a nonce gap, which no fee bump can unblock.
+    pub suggested_fees: Option<Eip1559Estimation>,
+}
+
+/// The nonce state of an account, as returned by [`NonceDiagnosisApi::diagnose_nonce`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonceDiagnosis {
+    /// The number of transactions of the sender included in the latest block.
+    pub latest_nonce: u64,
+    /// The number of transactions of the sender including those executable from the pool.
+    pub pending_nonce: u64,
+    /// Whether the node exposes its transaction pool. Without it, gaps and stuck transactions
+    /// cannot be detected.
+    pub txpool_available: bool,
+    /// The nonces missing below the highest queued transaction of the sender.
+    pub missing_nonces: Vec<u64>,
+    /// The transactions of the sender that are stuck, by ascending nonce.
+    pub stuck: Vec<StuckTransaction>,
+    /// The current network fee estimate.
+    pub network_fees: Eip1559Estimation,
+}
+
+impl NonceDiagnosis {
+    /// Returns the number of transactions of the sender waiting in the pool to be executed.
+    pub const fn in_flight(&self) -> u64 {
+        self.pending_nonce.saturating_sub(self.latest_nonce)
+    }
+
+    /// Returns `true` if any nonce is missing or any transaction is stuck.
+    pub fn is_stuck(&self) -> bool {
+        !self.missing_nonces.is_empty() || !self.stuck.is_empty()
+    }
+}
+
+/// Diagnosis of accounts whose transactions are not being included.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait NonceDiagnosisApi<N, T>: Send + Sync {
+    /// Compares the latest and pending nonces of `sender`, and inspects its transactions in the
+    /// pool with `txpool_contentFrom`, when the node supports it.
+    ///
+    /// Queued transactions behind a missing nonce are reported as stuck on a gap. Executable
+    /// transactions tipping less than the current estimate are reported as underpriced, with the
+    /// fees a replacement should pay.
+    async fn diagnose_nonce(&self, sender: Address) -> TransportResult<NonceDiagnosis>;
+
+    /// Returns the hash of the transaction sent by `sender` with the given nonce, if any.
+    ///
+    /// Uses `ots_getTransactionBySenderAndNonce` when the node supports it. Otherwise, included
+    /// transactions are located by binary searching the block at which the nonce of the sender
+    /// was consumed, in at most `log2(block number)` nonce queries, which requires an archive
+    /// node for old transactions. Transactions that are not included yet are looked up in the
+    /// pool of the node with `txpool_contentFrom`.
+    async fn find_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> TransportResult<Option<TxHash>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> NonceDiagnosisApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn diagnose_nonce(&self, sender: Address) -> TransportResult<NonceDiagnosis> {
+        let latest_nonce = self.get_transaction_count(sender).block_id(BlockId::latest()).await?;
+        let pending_nonce = self.get_transaction_count(sender).block_id(BlockId::pending()).await?;
+
+        let fee_history = self
+            .get_fee_history(
+                utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+                BlockNumberOrTag::Latest,
+                &[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
+            )
+            .await?;
+        let base_fee = fee_history.latest_block_base_fee().unwrap_or_default();
+        let network_fees =
+            utils::eip1559_default_estimator(base_fee, &fee_history.reward.unwrap_or_default());
+
+        // Nodes without the txpool namespace answer with an error response.
+        let content = match self.txpool_content_from(sender).await {
+            Ok(content) => Some(content),
+            Err(err) if err.as_error_resp().is_some() => None,
+            Err(err) => return Err(err),
+        };
+        let Some(content) = content else {
+            return Ok(NonceDiagnosis {
+                latest_nonce,
+                pending_nonce,
+                txpool_available: false,
+                missing_nonces: Vec::new(),
+                stuck: Vec::new(),
+                network_fees,
+            });
+        };
+
+        let by_nonce = |txs: BTreeMap<String, Transaction>| {
+            txs.into_values().map(|tx| (tx.nonce, tx)).collect::<BTreeMap<_, _>>()
+        };
+        let pending = by_nonce(content.pending);
+        let queued = by_nonce(content.queued);
+
+        let missing_nonces = match queued.keys().next_back() {
+            Some(&highest) => (latest_nonce..highest)
+                .filter(|nonce| !pending.contains_key(nonce) && !queued.contains_key(nonce))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let underpriced = pending.values().filter_map(|tx| {
+            let stuck = StuckTransaction {
+                nonce: tx.nonce,
+                hash: tx.hash,
+                reason: StuckReason::Underpriced,
+                suggested_fees: None,
+            };
+            suggest_fees(tx, base_fee, network_fees)
+                .map(|fees| StuckTransaction { suggested_fees: Some(fees), ..stuck })
+        });
+        let gapped = queued.values().map(|tx| StuckTransaction {
+            nonce: tx.nonce,
+            hash: tx.hash,
+            reason: StuckReason::NonceGap,
+            suggested_fees: None,
+        });
+        let mut stuck: Vec<_> = underpriced.chain(gapped).collect();
+        stuck.sort_by_key(|tx| tx.nonce);
+
+        Ok(NonceDiagnosis {
+            latest_nonce,
+            pending_nonce,
+            txpool_available: true,
+            missing_nonces,
+            stuck,
+            network_fees,
+        })
+    }
+
+    async fn find_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> TransportResult<Option<TxHash>> {
+        match self.ots_get_transaction_by_sender_and_nonce(sender, nonce).await {
+            Ok(hash) => return Ok(hash),
+            Err(err) if err.as_error_resp().is_some() => {}
+            Err(err) => return Err(err),
+        }
+
+        let latest = self.get_block_number().await?;
+        let nonce_at = |block| self.get_transaction_count(sender).block_id(BlockId::number(block));
+        if nonce_at(latest).await? <= nonce {
+            return match self.txpool_content_from(sender).await {
+                Ok(content) => Ok(content
+                    .get_pending(nonce)
+                    .or_else(|| content.get_queued(nonce))
+                    .map(|tx| tx.hash)),
+                Err(err) if err.as_error_resp().is_some() => Ok(None),
+                Err(err) => Err(err),
+            };
+        }
+
+        // Find the first block after which the nonce of the sender is above `nonce`.
+        let (mut low, mut high) = (0, latest);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if nonce_at(mid).await? > nonce {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        // Only Ethereum fields are needed, which all networks share.
+        let block: Option<Block> = self
+            .client()
+            .request("eth_getBlockByNumber", (BlockNumberOrTag::Number(low), true))
+            .await?;
+        Ok(block.and_then(|block| {
+            block
+                .transactions
+                .txns()?
+                .find(|tx| tx.from == sender && tx.nonce == nonce)
+                .map(|tx| tx.hash)
+        }))
+    }
+}
+
+/// Returns the fees a replacement of `tx` should pay, or `None` if `tx` tips at least the network
+/// estimate at the given base fee.
+fn suggest_fees(
+    tx: &Transaction,
+    base_fee: u128,
+    network: Eip1559Estimation,
+) -> Option<Eip1559Estimation> {
+    let max_fee = tx.max_fee_per_gas.or(tx.gas_price).unwrap_or_default();
+    let max_priority_fee = tx.max_priority_fee_per_gas.unwrap_or(max_fee);
+    let tip = max_priority_fee.min(max_fee.saturating_sub(base_fee));
+    if tip >= network.max_priority_fee_per_gas {
+        return None;
+    }
+    Some(Eip1559Estimation {
+        max_fee_per_gas: bump_fee(max_fee, MIN_FEE_BUMP).max(network.max_fee_per_gas),
+        max_priority_fee_per_gas: bump_fee(max_priority_fee, MIN_FEE_BUMP)
+            .max(network.max_priority_fee_per_gas),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{error_resp, mock_provider},
+        RootProvider,
+    };
+    use alloy_network::Ethereum;
+    use alloy_primitives::B256;
+    use alloy_transport::BoxTransport;
+    use serde_json::json;
+
+    fn pool_tx(nonce: u64, max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Transaction {
+        Transaction {
+            hash: B256::with_last_byte(nonce as u8),
+            nonce,
+            max_fee_per_gas: Some(max_fee_per_gas),
+            max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+            transaction_type: Some(2),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a provider for a sender with nonce 5 mined, whose pool holds nonces 5, 6 and 9.
+    fn node(txpool: bool) -> RootProvider<BoxTransport, Ethereum> {
+        mock_provider(move |method, params| {
+            Ok(match method {
+                "eth_getTransactionCount" if params.contains("pending") => json!("0x7"),
+                "eth_getTransactionCount" => json!("0x5"),
+                "eth_feeHistory" => json!({
+                    "oldestBlock": "0x1",
+                    "baseFeePerGas": ["0x64", "0x64"],
+                    "gasUsedRatio": [0.5],
+                    "reward": [["0xa"]],
+                }),
+                "txpool_contentFrom" if txpool => json!({
+                    "pending": {
+                        "5": pool_tx(5, 200, 1),
+                        "6": pool_tx(6, 200, 20),
+                    },
+                    "queued": { "9": pool_tx(9, 200, 20) },
+                }),
+                _ => return Err(error_resp(-32601, "method not found")),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn diagnoses_gaps_and_underpriced() {
+        let diagnosis = node(true).diagnose_nonce(Address::ZERO).await.unwrap();
+        assert_eq!(diagnosis.in_flight(), 2);
+        assert_eq!(diagnosis.missing_nonces, vec![7, 8]);
+        assert_eq!(diagnosis.network_fees.max_priority_fee_per_gas, 10);
+
+        assert_eq!(diagnosis.stuck.len(), 2);
+        let underpriced = &diagnosis.stuck[0];
+        assert_eq!((underpriced.nonce, underpriced.reason), (5, StuckReason::Underpriced));
+        assert_eq!(
+            underpriced.suggested_fees,
+            Some(Eip1559Estimation { max_fee_per_gas: 220, max_priority_fee_per_gas: 10 })
+        );
+        let gapped = &diagnosis.stuck[1];
+        assert_eq!(
+            (gapped.nonce, gapped.reason, gapped.suggested_fees),
+            (9, StuckReason::NonceGap, None)
+        );
+    }
+
+    /// Returns a provider for a chain at block 16 without otterscan, where the sender included
+    /// its transaction of nonce `n` in block `2n + 1`, and has nonce 8 pending in the pool.
+    fn chain() -> RootProvider<BoxTransport, Ethereum> {
+        let block = |params: &[serde_json::Value]| {
+            let number = params[0].as_str().unwrap();
+            u64::from_str_radix(number.trim_start_matches("0x"), 16).unwrap()
+        };
+        mock_provider(move |method, params| {
+            let params: Vec<serde_json::Value> = serde_json::from_str(params).unwrap_or_default();
+            Ok(match method {
+                "eth_blockNumber" => json!("0x10"),
+                "eth_getTransactionCount" => {
+                    json!(format!("{:#x}", block(&params[1..]).div_ceil(2)))
+                }
+                "eth_getBlockByNumber" => {
+                    let number = block(&params);
+                    let tx = Transaction {
+                        hash: B256::with_last_byte(number as u8),
+                        nonce: number / 2,
+                        ..Default::default()
+                    };
+                    json!(Block {
+                        transactions: alloy_rpc_types::BlockTransactions::Full(vec![tx]),
+                        ..Default::default()
+                    })
+                }
+                "txpool_contentFrom" => json!({
+                    "pending": { "8": pool_tx(8, 200, 20) },
+                    "queued": {},
+                }),
+                _ => return Err(error_resp(-32601, "method not found")),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn finds_transaction_without_otterscan() {
+        let provider = chain();
+        for nonce in 0..8 {
+            let hash = provider.find_transaction_by_sender_and_nonce(Address::ZERO, nonce).await;
+            assert_eq!(hash.unwrap(), Some(B256::with_last_byte(2 * nonce as u8 + 1)));
+        }
+        let pending = provider.find_transaction_by_sender_and_nonce(Address::ZERO, 8).await;
+        assert_eq!(pending.unwrap(), Some(B256::with_last_byte(8)));
+        let unknown = provider.find_transaction_by_sender_and_nonce(Address::ZERO, 9).await;
+        assert_eq!(unknown.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn without_txpool() {
+        let diagnosis = node(false).diagnose_nonce(Address::ZERO).await.unwrap();
+        assert!(!diagnosis.txpool_available);
+        assert_eq!(diagnosis.in_flight(), 2);
+        assert!(!diagnosis.is_stuck());
+    }
+}
@@ -0,0 +1,219 @@
+//! This module extends the Ethereum JSON-RPC provider with the Otterscan namespace's RPC methods.
+use crate::Provider;
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, TxHash};
+use alloy_rpc_types_trace::otterscan::{
+    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
+    TransactionsWithReceipts,
+};
+use alloy_transport::{Transport, TransportResult};
+
+/// Otterscan namespace rpc interface, implemented by Erigon, reth and anvil.
+///
+/// See the [Otterscan docs](https://github.com/otterscan/otterscan/blob/develop/docs/custom-jsonrpc.md)
+/// for details on each method.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait OtterscanApi<N, T>: Send + Sync {
+    /// Returns the version of the Otterscan API implemented by the node.
+    async fn ots_get_api_level(&self) -> TransportResult<u64>;
+
+    /// Returns whether the address has code at the given block.
+    async fn ots_has_code(&self, address: Address, block: BlockId) -> TransportResult<bool>;
+
+    /// Returns the value transfers, self-destructs and contract creations that happened inside
+    /// the transaction.
+    async fn ots_get_internal_operations(
+        &self,
+        hash: TxHash,
+    ) -> TransportResult<Vec<InternalOperation>>;
+
+    /// Returns the raw revert data of a failed transaction, or empty bytes if it succeeded.
+    async fn ots_get_transaction_error(&self, hash: TxHash) -> TransportResult<Bytes>;
+
+    /// Returns the call tree of the transaction, as a flat list of entries.
+    async fn ots_trace_transaction(&self, hash: TxHash) -> TransportResult<Vec<TraceEntry>>;
+
+    /// Returns the block with its issuance and total fees, without its transactions.
+    async fn ots_get_block_details(
+        &self,
+        block: BlockNumber,
+    ) -> TransportResult<Option<BlockDetails>>;
+
+    /// Same as `ots_get_block_details` but the block is specified by hash.
+    async fn ots_get_block_details_by_hash(
+        &self,
+        hash: BlockHash,
+    ) -> TransportResult<Option<BlockDetails>>;
+
+    /// Returns a page of the transactions of the block, with their receipts.
+    async fn ots_get_block_transactions(
+        &self,
+        block: BlockNumber,
+        page_number: usize,
+        page_size: usize,
+    ) -> TransportResult<OtsBlockTransactions>;
+
+    /// Returns the transactions touching `address` in blocks before `block`, from newest to
+    /// oldest. Block `0` searches from the latest block.
+    ///
+    /// Pages contain whole blocks, so they may hold more than `page_size` transactions.
+    async fn ots_search_transactions_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        page_size: usize,
+    ) -> TransportResult<TransactionsWithReceipts>;
+
+    /// Returns the transactions touching `address` in blocks after `block`, from newest to
+    /// oldest. Block `0` searches from the genesis block.
+    ///
+    /// Pages contain whole blocks, so they may hold more than `page_size` transactions.
+    async fn ots_search_transactions_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        page_size: usize,
+    ) -> TransportResult<TransactionsWithReceipts>;
+
+    /// Returns the hash of the transaction sent by `sender` with the given nonce, if any.
+    async fn ots_get_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> TransportResult<Option<TxHash>>;
+
+    /// Returns the transaction that created the contract at `address`, and its sender.
+    ///
+    /// Returns `None` if the address is not a contract.
+    async fn ots_get_contract_creator(
+        &self,
+        address: Address,
+    ) -> TransportResult<Option<ContractCreator>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> OtterscanApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn ots_get_api_level(&self) -> TransportResult<u64> {
+        self.client().request("ots_getApiLevel", ()).await
+    }
+
+    async fn ots_has_code(&self, address: Address, block: BlockId) -> TransportResult<bool> {
+        self.client().request("ots_hasCode", (address, block)).await
+    }
+
+    async fn ots_get_internal_operations(
+        &self,
+        hash: TxHash,
+    ) -> TransportResult<Vec<InternalOperation>> {
+        self.client().request("ots_getInternalOperations", (hash,)).await
+    }
+
+    async fn ots_get_transaction_error(&self, hash: TxHash) -> TransportResult<Bytes> {
+        self.client().request("ots_getTransactionError", (hash,)).await
+    }
+
+    async fn ots_trace_transaction(&self, hash: TxHash) -> TransportResult<Vec<TraceEntry>> {
+        self.client().request("ots_traceTransaction", (hash,)).await
+    }
+
+    async fn ots_get_block_details(
+        &self,
+        block: BlockNumber,
+    ) -> TransportResult<Option<BlockDetails>> {
+        self.client().request("ots_getBlockDetails", (block,)).await
+    }
+
+    async fn ots_get_block_details_by_hash(
+        &self,
+        hash: BlockHash,
+    ) -> TransportResult<Option<BlockDetails>> {
+        self.client().request("ots_getBlockDetailsByHash", (hash,)).await
+    }
+
+    async fn ots_get_block_transactions(
+        &self,
+        block: BlockNumber,
+        page_number: usize,
+        page_size: usize,
+    ) -> TransportResult<OtsBlockTransactions> {
+        self.client().request("ots_getBlockTransactions", (block, page_number, page_size)).await
+    }
+
+    async fn ots_search_transactions_before(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        page_size: usize,
+    ) -> TransportResult<TransactionsWithReceipts> {
+        self.client().request("ots_searchTransactionsBefore", (address, block, page_size)).await
+    }
+
+    async fn ots_search_transactions_after(
+        &self,
+        address: Address,
+        block: BlockNumber,
+        page_size: usize,
+    ) -> TransportResult<TransactionsWithReceipts> {
+        self.client().request("ots_searchTransactionsAfter", (address, block, page_size)).await
+    }
+
+    async fn ots_get_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> TransportResult<Option<TxHash>> {
+        self.client().request("ots_getTransactionBySenderAndNonce", (sender, nonce)).await
+    }
+
+    async fn ots_get_contract_creator(
+        &self,
+        address: Address,
+    ) -> TransportResult<Option<ContractCreator>> {
+        self.client().request("ots_getContractCreator", (address,)).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ProviderBuilder, WalletProvider};
+    use alloy_network::TransactionBuilder;
+    use alloy_primitives::{address, U256};
+    use alloy_rpc_types::TransactionRequest;
+
+    #[tokio::test]
+    async fn ots_get_api_level() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let level = provider.ots_get_api_level().await.unwrap();
+        assert!(level >= 8);
+    }
+
+    #[tokio::test]
+    async fn ots_has_code() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let has_code = provider.ots_has_code(Address::ZERO, BlockId::latest()).await.unwrap();
+        assert!(!has_code);
+    }
+
+    #[tokio::test]
+    async fn ots_get_transaction_by_sender_and_nonce() {
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_signer();
+        let from = provider.default_signer_address();
+        let tx = TransactionRequest::default()
+            .with_from(from)
+            .with_to(address!("deadbeef00000000deadbeef00000000deadbeef"))
+            .with_value(U256::from(100));
+        let receipt = provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+
+        let hash = provider.ots_get_transaction_by_sender_and_nonce(from, 0).await.unwrap();
+        assert_eq!(hash, Some(receipt.transaction_hash));
+    }
+}
@@ -0,0 +1,403 @@
+//! This module extends the Ethereum JSON-RPC provider with a uniform way to iterate over
+//! paginated queries, such as `trace_filter`, `eth_getLogs` over large block ranges and the
+//! Otterscan search methods.
+use crate::{
+    ext::{OtterscanApi, TraceApi},
+    Provider,
+};
+use alloy_json_rpc::ErrorKind;
+use alloy_network::Network;
+use alloy_primitives::{Address, BlockNumber};
+use alloy_rpc_types::{Filter, Log, Transaction};
+use alloy_rpc_types_trace::{
+    filter::TraceFilter, otterscan::OtsTransactionReceipt, parity::LocalizedTransactionTrace,
+};
+use alloy_transport::{Transport, TransportResult};
+use futures::{Stream, TryStreamExt};
+use std::{
+    marker::PhantomData,
+    ops::RangeInclusive,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// A single page of results returned by a [`PaginatedQuery`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Page<T, C> {
+    /// The items of this page.
+    pub items: Vec<T>,
+    /// The cursor of the next page, or `None` if this is the last page.
+    pub next: Option<C>,
+}
+
+impl<T> Page<T, u64> {
+    /// Creates a page of an offset-based query, which started at `offset` and requested at most
+    /// `page_size` items.
+    ///
+    /// A page with fewer than `page_size` items is considered to be the last one.
+    pub fn from_offset(items: Vec<T>, offset: u64, page_size: u64) -> Self {
+        let len = items.len() as u64;
+        let next = (page_size != 0 && len >= page_size).then(|| offset + len);
+        Self { items, next }
+    }
+}
+
+/// A query whose results are fetched one page at a time.
+///
+/// The cursor is opaque to the iteration: it can be an offset, a page number, a block number or
+/// any token returned by the remote source. Implementing this trait for other paginated sources,
+/// such as block explorer APIs, gives them the same [`Stream`]-based iteration.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait PaginatedQuery: Send + Sync {
+    /// The type of the returned items.
+    type Item: Send;
+    /// The type of the cursor identifying a page.
+    type Cursor: Send;
+
+    /// Returns the cursor of the first page.
+    fn first_page(&self) -> Self::Cursor;
+
+    /// Fetches the page identified by `cursor`.
+    async fn fetch_page(
+        &self,
+        cursor: Self::Cursor,
+    ) -> TransportResult<Page<Self::Item, Self::Cursor>>;
+
+    /// Returns a stream of all pages, fetched lazily one after the other.
+    ///
+    /// The stream ends after the first error.
+    fn into_pages(self) -> impl Stream<Item = TransportResult<Vec<Self::Item>>>
+    where
+        Self: Sized,
+    {
+        let first = self.first_page();
+        futures::stream::try_unfold((self, Some(first)), |(query, cursor)| async move {
+            let Some(cursor) = cursor else { return Ok(None) };
+            let Page { items, next } = query.fetch_page(cursor).await?;
+            Ok(Some((items, (query, next))))
+        })
+    }
+
+    /// Returns a stream of all items, across all pages.
+    fn into_stream(self) -> impl Stream<Item = TransportResult<Self::Item>>
+    where
+        Self: Sized,
+    {
+        self.into_pages()
+            .map_ok(|items| futures::stream::iter(items.into_iter().map(Ok)))
+            .try_flatten()
+    }
+}
+
+/// Offset-based pagination of `trace_filter`, using the `after` and `count` fields of the filter.
+///
+/// Created by [`PaginationApi::trace_filter_paginated`].
+#[derive(Debug)]
+pub struct TraceFilterQuery<'a, P: ?Sized, T, N> {
+    provider: &'a P,
+    filter: TraceFilter,
+    page_size: u64,
+    _phantom: PhantomData<(T, N)>,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> PaginatedQuery for TraceFilterQuery<'_, P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    type Item = LocalizedTransactionTrace;
+    type Cursor = u64;
+
+    fn first_page(&self) -> u64 {
+        self.filter.after.unwrap_or_default()
+    }
+
+    async fn fetch_page(&self, offset: u64) -> TransportResult<Page<Self::Item, u64>> {
+        let filter =
+            TraceFilter { after: Some(offset), count: Some(self.page_size), ..self.filter.clone() };
+        let traces = self.provider.trace_filter(&filter).await?;
+        Ok(Page::from_offset(traces, offset, self.page_size))
+    }
+}
+
+/// Block range pagination of `eth_getLogs`, querying at most `chunk_size` blocks at once.
+///
+/// When the node rejects a query because it covers too many blocks or returns too many logs, with
+/// an [`ErrorKind::LimitExceeded`] error, the query is retried with half the block range, down to a single block. The range then grows back
+/// towards `chunk_size` with each successful query.
+///
+/// Created by [`PaginationApi::get_logs_paginated`].
+#[derive(Debug)]
+pub struct LogQuery<'a, P: ?Sized, T, N> {
+    provider: &'a P,
+    filter: Filter,
+    range: RangeInclusive<BlockNumber>,
+    chunk_size: u64,
+    /// The number of blocks of the next query, at most `chunk_size`.
+    window: AtomicU64,
+    _phantom: PhantomData<(T, N)>,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> PaginatedQuery for LogQuery<'_, P, T, N>
+where
+    P: Provider<T, N> + ?Sized,
+    T: Transport + Clone,
+    N: Network,
+{
+    type Item = Log;
+    type Cursor = BlockNumber;
+
+    fn first_page(&self) -> BlockNumber {
+        *self.range.start()
+    }
+
+    async fn fetch_page(&self, from: BlockNumber) -> TransportResult<Page<Log, BlockNumber>> {
+        let end = *self.range.end();
+        if from > end {
+            return Ok(Page { items: Vec::new(), next: None });
+        }
+        let mut window = self.window.load(Ordering::Relaxed);
+        loop {
+            let to = from.saturating_add(window - 1).min(end);
+            let filter = self.filter.clone().from_block(from).to_block(to);
+            match self.provider.get_logs(&filter).await {
+                Ok(logs) => {
+                    let grown = window.saturating_mul(2).min(self.chunk_size);
+                    self.window.store(grown, Ordering::Relaxed);
+                    return Ok(Page { items: logs, next: (to < end).then(|| to + 1) });
+                }
+                Err(err) if to > from && err.error_kind() == Some(ErrorKind::LimitExceeded) => {
+                    window = (to - from).div_ceil(2);
+                    debug!(%err, from, window, "log query exceeded node limits, retrying");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Block-based pagination of `ots_searchTransactionsBefore`, from the latest block backwards.
+///
+/// Created by [`PaginationApi::ots_search_transactions_before_paginated`].
+///
+/// # Note
+///
+/// This requires a node with the Otterscan API enabled.
+#[derive(Debug)]
+pub struct OtsSearchQuery<'a, P: ?Sized, T, N> {
+    provider: &'a P,
+    address: Address,
+    page_size: usize,
+    _phantom: PhantomData<(T, N)>,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<P, T, N> PaginatedQuery for OtsSearchQuery<'_, P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    type Item = (Transaction, OtsTransactionReceipt);
+    type Cursor = BlockNumber;
+
+    /// Block `0` instructs the node to search from the latest block.
+    fn first_page(&self) -> BlockNumber {
+        0
+    }
+
+    async fn fetch_page(
+        &self,
+        block: BlockNumber,
+    ) -> TransportResult<Page<Self::Item, BlockNumber>> {
+        let res = self
+            .provider
+            .ots_search_transactions_before(self.address, block, self.page_size)
+            .await?;
+        // Pages always contain whole blocks, so the next page starts before the oldest block.
+        let next = if res.last_page {
+            None
+        } else {
+            res.txs.last().and_then(|tx| tx.block_number).filter(|&number| number > 0)
+        };
+        Ok(Page { items: res.txs.into_iter().zip(res.receipts).collect(), next })
+    }
+}
+
+/// Paginated queries.
+///
+/// Each method returns a [`PaginatedQuery`] which can be turned into a [`Stream`] of items with
+/// [`PaginatedQuery::into_stream`].
+pub trait PaginationApi<N: Network, T>: Send + Sync {
+    /// Returns all traces matching the given [`TraceFilter`], `page_size` traces at a time.
+    ///
+    /// Pagination starts at the `after` offset of the filter, and overrides its `count`.
+    fn trace_filter_paginated(
+        &self,
+        filter: TraceFilter,
+        page_size: u64,
+    ) -> TraceFilterQuery<'_, Self, T, N>;
+
+    /// Returns all logs matching the given [`Filter`] within `range`, querying at most
+    /// `chunk_size` blocks at a time.
+    ///
+    /// The block range of the filter is overridden. Queries rejected by the node for covering too
+    /// many blocks or logs are split, see [`LogQuery`].
+    fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        range: RangeInclusive<BlockNumber>,
+        chunk_size: u64,
+    ) -> LogQuery<'_, Self, T, N>;
+
+    /// Returns all transactions touching `address`, from newest to oldest, with their receipts.
+    fn ots_search_transactions_before_paginated(
+        &self,
+        address: Address,
+        page_size: usize,
+    ) -> OtsSearchQuery<'_, Self, T, N>;
+}
+
+impl<N, T, P> PaginationApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    fn trace_filter_paginated(
+        &self,
+        filter: TraceFilter,
+        page_size: u64,
+    ) -> TraceFilterQuery<'_, Self, T, N> {
+        TraceFilterQuery { provider: self, filter, page_size, _phantom: PhantomData }
+    }
+
+    fn get_logs_paginated(
+        &self,
+        filter: Filter,
+        range: RangeInclusive<BlockNumber>,
+        chunk_size: u64,
+    ) -> LogQuery<'_, Self, T, N> {
+        let chunk_size = chunk_size.max(1);
+        LogQuery {
+            provider: self,
+            filter,
+            range,
+            chunk_size,
+            window: AtomicU64::new(chunk_size),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn ots_search_transactions_before_paginated(
+        &self,
+        address: Address,
+        page_size: usize,
+    ) -> OtsSearchQuery<'_, Self, T, N> {
+        OtsSearchQuery { provider: self, address, page_size, _phantom: PhantomData }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::StreamExt;
+
+    /// Serves `items` in pages of `page_size`.
+    struct VecQuery {
+        items: Vec<u64>,
+        page_size: u64,
+    }
+
+    #[async_trait::async_trait]
+    impl PaginatedQuery for VecQuery {
+        type Item = u64;
+        type Cursor = u64;
+
+        fn first_page(&self) -> u64 {
+            0
+        }
+
+        async fn fetch_page(&self, offset: u64) -> TransportResult<Page<u64, u64>> {
+            let items = self
+                .items
+                .iter()
+                .copied()
+                .skip(offset as usize)
+                .take(self.page_size as usize)
+                .collect();
+            Ok(Page::from_offset(items, offset, self.page_size))
+        }
+    }
+
+    #[test]
+    fn from_offset() {
+        assert_eq!(Page::from_offset(vec![1, 2], 4, 2).next, Some(6));
+        assert_eq!(Page::from_offset(vec![1], 4, 2).next, None);
+        assert_eq!(Page::<u64, _>::from_offset(vec![], 4, 0).next, None);
+    }
+
+    #[tokio::test]
+    async fn into_stream() {
+        let query = VecQuery { items: (0..7).collect(), page_size: 3 };
+        let items: Vec<_> = query.into_stream().try_collect().await.unwrap();
+        assert_eq!(items, (0..7).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn into_pages() {
+        let query = VecQuery { items: (0..6).collect(), page_size: 3 };
+        let pages: Vec<_> = query.into_pages().map(Result::unwrap).collect().await;
+        // The last full page is followed by an empty one.
+        assert_eq!(pages, vec![vec![0, 1, 2], vec![3, 4, 5], vec![]]);
+    }
+
+    #[tokio::test]
+    async fn get_logs_shrinks_window() {
+        use crate::test_utils::{error_resp, mock_provider};
+        use alloy_network::Ethereum;
+        use std::sync::{Arc, Mutex};
+
+        // A node rejecting queries over more than 4 blocks.
+        let queries = Arc::new(Mutex::new(Vec::new()));
+        let provider = {
+            let queries = queries.clone();
+            mock_provider::<Ethereum, _>(move |_, params| {
+                let (filter,): (Filter,) = serde_json::from_str(params).unwrap();
+                let range = (filter.get_from_block().unwrap(), filter.get_to_block().unwrap());
+                queries.lock().unwrap().push(range);
+                if range.1 - range.0 >= 4 {
+                    Err(error_resp(-32602, "query returned more than 10000 results"))
+                } else {
+                    Ok(serde_json::json!([Log::<alloy_primitives::LogData>::default()]))
+                }
+            })
+        };
+
+        let logs: Vec<_> = provider
+            .get_logs_paginated(Filter::new(), 0..=9, 10)
+            .into_stream()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 3);
+        assert_eq!(*queries.lock().unwrap(), [(0, 9), (0, 4), (0, 1), (2, 5), (6, 9)]);
+
+        // Rate limiting is not a limit of the query.
+        let provider =
+            mock_provider::<Ethereum, _>(|_, _| Err(error_resp(429, "too many requests")));
+        let err = provider
+            .get_logs_paginated(Filter::new(), 0..=9, 10)
+            .into_stream()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+        assert_eq!(err.error_kind(), Some(ErrorKind::RateLimited));
+    }
+}
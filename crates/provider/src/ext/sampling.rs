@@ -0,0 +1,156 @@
+//! This module extends the Ethereum JSON-RPC provider with helpers to sample historical state at
+//! evenly spaced blocks.
+use crate::Provider;
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, BlockNumber, Bytes, U256};
+use alloy_transport::{Transport, TransportResult};
+use futures::{StreamExt, TryStreamExt};
+use std::ops::RangeInclusive;
+
+/// A single value of a sampled time series, observed at the given block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sample<T> {
+    /// The block at which the value was observed.
+    pub block_number: BlockNumber,
+    /// The observed value.
+    pub value: T,
+}
+
+/// Returns `samples` block numbers evenly spaced over `range`, in ascending order.
+///
+/// The first and last blocks of the range are always included when `samples >= 2`. If the range
+/// contains fewer blocks than requested, every block in the range is returned.
+pub fn sample_block_numbers(
+    range: RangeInclusive<BlockNumber>,
+    samples: usize,
+) -> Vec<BlockNumber> {
+    let (start, end) = range.into_inner();
+    if samples == 0 || start > end {
+        return Vec::new();
+    }
+    if samples == 1 {
+        return vec![start];
+    }
+
+    let span = end - start;
+    if span < samples as u64 {
+        return (start..=end).collect();
+    }
+
+    let steps = (samples - 1) as u128;
+    (0..samples as u128).map(|i| start + (span as u128 * i / steps) as u64).collect()
+}
+
+/// Historical state sampling helpers.
+///
+/// Each method queries the node at `samples` evenly spaced blocks within the given range, running
+/// at most `concurrency` requests at once. Results are returned in ascending block order.
+///
+/// # Note
+///
+/// Sampling blocks older than the node's state retention requires an archive node.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait SamplingApi<N: Network, T>: Send + Sync {
+    /// Samples the balance of `address` over the given block range.
+    async fn sample_balance(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        samples: usize,
+        concurrency: usize,
+    ) -> TransportResult<Vec<Sample<U256>>>;
+
+    /// Samples the output of an `eth_call` with the given request over the given block range.
+    async fn sample_call(
+        &self,
+        tx: &N::TransactionRequest,
+        range: RangeInclusive<BlockNumber>,
+        samples: usize,
+        concurrency: usize,
+    ) -> TransportResult<Vec<Sample<Bytes>>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> SamplingApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn sample_balance(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        samples: usize,
+        concurrency: usize,
+    ) -> TransportResult<Vec<Sample<U256>>> {
+        futures::stream::iter(sample_block_numbers(range, samples))
+            .map(|block_number| async move {
+                let value =
+                    self.get_balance(address).block_id(BlockId::number(block_number)).await?;
+                Ok(Sample { block_number, value })
+            })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await
+    }
+
+    async fn sample_call(
+        &self,
+        tx: &N::TransactionRequest,
+        range: RangeInclusive<BlockNumber>,
+        samples: usize,
+        concurrency: usize,
+    ) -> TransportResult<Vec<Sample<Bytes>>> {
+        futures::stream::iter(sample_block_numbers(range, samples))
+            .map(|block_number| async move {
+                let value = self.call(tx).block(BlockId::number(block_number)).await?;
+                Ok(Sample { block_number, value })
+            })
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ProviderBuilder;
+    use alloy_network::TransactionBuilder;
+    use alloy_rpc_types::TransactionRequest;
+
+    #[test]
+    fn sample_block_numbers_spacing() {
+        assert_eq!(sample_block_numbers(0..=100, 5), vec![0, 25, 50, 75, 100]);
+        assert_eq!(sample_block_numbers(10..=20, 3), vec![10, 15, 20]);
+        assert_eq!(sample_block_numbers(0..=10, 1), vec![0]);
+        assert_eq!(sample_block_numbers(0..=10, 0), Vec::<u64>::new());
+        assert_eq!(sample_block_numbers(5..=7, 10), vec![5, 6, 7]);
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = sample_block_numbers(7..=5, 10);
+        assert!(empty.is_empty());
+        assert_eq!(sample_block_numbers(0..=u64::MAX, 2), vec![0, u64::MAX]);
+    }
+
+    #[tokio::test]
+    async fn sample_balance() {
+        let provider = ProviderBuilder::new().on_anvil_with_config(|a| a.block_time(1));
+        let accounts = provider.get_accounts().await.unwrap();
+        let tx = TransactionRequest::default()
+            .with_from(accounts[0])
+            .with_to(accounts[1])
+            .with_value(U256::from(100));
+        provider.send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+
+        let latest = provider.get_block_number().await.unwrap();
+        let samples = provider.sample_balance(accounts[1], 0..=latest, 2, 2).await.unwrap();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].block_number, 0);
+        assert_eq!(samples[1].block_number, latest);
+        assert_eq!(samples[1].value - samples[0].value, U256::from(100));
+    }
+}
@@ -0,0 +1,36 @@
+//! This module extends the Ethereum JSON-RPC provider with the Trace namespace's RPC methods.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_rpc_types_trace::{filter::TraceFilter, parity::LocalizedTransactionTrace};
+use alloy_transport::{Transport, TransportResult};
+
+/// Trace namespace rpc interface that gives access to the parity traces of a node.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait TraceApi<N, T>: Send + Sync {
+    /// Returns all traces matching the given [`TraceFilter`].
+    ///
+    /// # Note
+    ///
+    /// Not all nodes support this call.
+    async fn trace_filter(
+        &self,
+        filter: &TraceFilter,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> TraceApi<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn trace_filter(
+        &self,
+        filter: &TraceFilter,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
+        self.client().request("trace_filter", (filter,)).await
+    }
+}
@@ -0,0 +1,45 @@
+//! This module extends the Ethereum JSON-RPC provider with the Web3 namespace's RPC methods.
+use crate::Provider;
+use alloy_network::Network;
+use alloy_primitives::{Bytes, B256};
+use alloy_transport::{Transport, TransportResult};
+
+/// Web3 namespace rpc interface.
+///
+/// The client version is returned by [`Provider::get_client_version`].
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait Web3Api<N, T>: Send + Sync {
+    /// Returns the Keccak-256 hash of the given data, computed by the node.
+    async fn web3_sha3(&self, data: &[u8]) -> TransportResult<B256>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<N, T, P> Web3Api<N, T> for P
+where
+    N: Network,
+    T: Transport + Clone,
+    P: Provider<T, N>,
+{
+    async fn web3_sha3(&self, data: &[u8]) -> TransportResult<B256> {
+        self.client().request("web3_sha3", (Bytes::copy_from_slice(data),)).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ProviderBuilder;
+
+    use super::*;
+    use alloy_primitives::keccak256;
+
+    #[tokio::test]
+    async fn client_version_and_sha3() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let version = provider.get_client_version().await.unwrap();
+        assert!(version.starts_with("anvil/"));
+        let hash = provider.web3_sha3(b"hello").await.unwrap();
+        assert_eq!(hash, keccak256(b"hello"));
+    }
+}
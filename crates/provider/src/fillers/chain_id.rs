@@ -75,7 +75,7 @@ impl<N: Network> TxFiller<N> for ChainIdFiller {
         match self.0.get().copied() {
             Some(chain_id) => Ok(chain_id),
             None => {
-                let chain_id = provider.get_chain_id().await?;
+                let chain_id = provider.cached_chain_id().await?;
                 let chain_id = *self.0.get_or_init(|| chain_id);
                 Ok(chain_id)
             }
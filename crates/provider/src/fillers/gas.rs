@@ -1,16 +1,42 @@
-use std::future::IntoFuture;
-
 use crate::{
     fillers::{FillerControlFlow, TxFiller},
-    provider::SendableTx,
-    utils::Eip1559Estimation,
+    gas_oracle::GasOracle,
+    provider::{bump_fee, SendableTx},
+    utils::{BlobFeeUrgency, Eip1559Estimation, EstimatorFunction},
     Provider,
 };
-use alloy_json_rpc::RpcError;
+use alloy_eips::eip4844::BlobParams;
+use alloy_json_rpc::{ErrorKind, RpcError};
 use alloy_network::{Network, TransactionBuilder};
 use alloy_rpc_types::BlockNumberOrTag;
-use alloy_transport::{Transport, TransportResult};
+use alloy_transport::{Transport, TransportError, TransportResult};
 use futures::FutureExt;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// Timers of `std` are not available in the browser.
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
+/// The default interval after which the [`GasFiller`] checks again whether an endpoint that
+/// did not support EIP-1559 fees now does.
+pub const DEFAULT_FEE_MARKET_RECHECK_INTERVAL: Duration = Duration::from_secs(600);
+
+/// The kind of fees the [`GasFiller`] fills for transactions without fees.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeeMarket {
+    /// Fill EIP-1559 fees, unless the endpoint was found not to support them.
+    #[default]
+    Auto,
+    /// Always fill EIP-1559 fees.
+    Eip1559,
+    /// Always fill a legacy gas price.
+    Legacy,
+}
 
 /// An enum over the different types of gas fillable.
 #[allow(unreachable_pub)]
@@ -47,6 +73,32 @@ pub enum GasFillable {
 /// - If the network does not support EIP-1559, it will fallback to the legacy
 ///  tx and populate the `gas_limit` and `gas_price` fields if unset.
 ///
+/// Some chains and gateways do not support EIP-1559 fees, because the latest
+/// block has no base fee or because they reject EIP-1559 transactions. The
+/// filler remembers such endpoints, and fills legacy gas prices for them
+/// without trying EIP-1559 fees first, until it checks again after
+/// [`DEFAULT_FEE_MARKET_RECHECK_INTERVAL`]. This memory is shared by the
+/// clones of the filler. The kind of fees can also be forced with
+/// [`GasFiller::with_fee_market`].
+///
+/// By default, the estimate of [`Provider::estimate_gas`] is used as the gas
+/// limit as is. Since the gas used by a transaction depends on the state it is
+/// executed on, a buffer can be added to the estimate with
+/// [`GasFiller::with_gas_limit_buffer`], the gas limit can be capped with
+/// [`GasFiller::with_gas_limit_cap`], and a gas limit to use when the
+/// estimation fails, e.g. because the transaction reverts, can be set with
+/// [`GasFiller::with_fallback_gas_limit`].
+///
+/// EIP-1559 fees are estimated with [`Provider::estimate_eip1559_fees`] by
+/// default, with an estimator function set with
+/// [`GasFiller::with_eip1559_estimator`], e.g. the
+/// [`eip1559_arbitrum_estimator`](crate::utils::eip1559_arbitrum_estimator)
+/// of chains without priority fees, or with a [`GasOracle`] set with
+/// [`GasFiller::with_oracle`], e.g. to combine several sources of estimates. The blob fees of EIP-4844
+/// transactions are the blob base fee of the next block by default, or are
+/// estimated with [`Provider::estimate_blob_fee`] if an urgency and the blob
+/// parameters of the chain are set with [`GasFiller::with_blob_fee_urgency`].
+///
 /// # Example
 ///
 /// ```
@@ -63,10 +115,156 @@ pub enum GasFillable {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Clone, Copy, Debug, Default)]
-pub struct GasFiller;
+#[derive(Clone, Debug)]
+pub struct GasFiller {
+    gas_limit_buffer: u64,
+    gas_limit_cap: Option<u128>,
+    fallback_gas_limit: Option<u128>,
+    fee_market: FeeMarket,
+    recheck_interval: Duration,
+    oracle: Option<Arc<dyn GasOracle>>,
+    estimator: Option<EstimatorFunction>,
+    blob_fee_urgency: Option<(BlobFeeUrgency, BlobParams)>,
+    /// When the endpoint was last found not to support EIP-1559 fees, if it was.
+    legacy_since: Arc<Mutex<Option<Instant>>>,
+}
+
+impl Default for GasFiller {
+    fn default() -> Self {
+        Self {
+            gas_limit_buffer: 0,
+            gas_limit_cap: None,
+            fallback_gas_limit: None,
+            fee_market: FeeMarket::Auto,
+            recheck_interval: DEFAULT_FEE_MARKET_RECHECK_INTERVAL,
+            oracle: None,
+            estimator: None,
+            blob_fee_urgency: None,
+            legacy_since: Default::default(),
+        }
+    }
+}
 
 impl GasFiller {
+    /// Sets the buffer, in percent, added to gas estimates. For example, a
+    /// buffer of 20 fills gas limits of 120% of the estimate.
+    pub const fn with_gas_limit_buffer(mut self, percent: u64) -> Self {
+        self.gas_limit_buffer = percent;
+        self
+    }
+
+    /// Sets the maximum gas limit to fill, which bounds buffered estimates and
+    /// the fallback gas limit.
+    pub const fn with_gas_limit_cap(mut self, cap: u128) -> Self {
+        self.gas_limit_cap = Some(cap);
+        self
+    }
+
+    /// Sets the gas limit to fill when the node fails to estimate the gas of a
+    /// transaction, instead of returning the error.
+    pub const fn with_fallback_gas_limit(mut self, gas_limit: u128) -> Self {
+        self.fallback_gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the kind of fees to fill for transactions without fees.
+    pub const fn with_fee_market(mut self, fee_market: FeeMarket) -> Self {
+        self.fee_market = fee_market;
+        self
+    }
+
+    /// Sets the interval after which an endpoint that did not support
+    /// EIP-1559 fees is checked again.
+    pub const fn with_fee_market_recheck_interval(mut self, interval: Duration) -> Self {
+        self.recheck_interval = interval;
+        self
+    }
+
+    /// Sets the oracle estimating the EIP-1559 fees of transactions, instead
+    /// of [`Provider::estimate_eip1559_fees`].
+    pub fn with_oracle(mut self, oracle: impl GasOracle + 'static) -> Self {
+        self.oracle = Some(Arc::new(oracle));
+        self
+    }
+
+    /// Sets the function estimating the EIP-1559 fees of transactions from
+    /// the fee history, passed to [`Provider::estimate_eip1559_fees`].
+    ///
+    /// An oracle set with [`GasFiller::with_oracle`] takes precedence.
+    pub const fn with_eip1559_estimator(mut self, estimator: EstimatorFunction) -> Self {
+        self.estimator = Some(estimator);
+        self
+    }
+
+    /// Sets the urgency with which the blob fees of EIP-4844 transactions are
+    /// estimated, instead of using the blob base fee of the next block. The
+    /// fees are projected with the blob parameters of the chain's current
+    /// fork, e.g. [`BlobParams::prague`].
+    pub const fn with_blob_fee_urgency(
+        mut self,
+        urgency: BlobFeeUrgency,
+        params: BlobParams,
+    ) -> Self {
+        self.blob_fee_urgency = Some((urgency, params));
+        self
+    }
+
+    /// Estimates the EIP-1559 fees of a transaction.
+    async fn estimate_eip1559_fees<P, T, N>(
+        &self,
+        provider: &P,
+    ) -> TransportResult<Eip1559Estimation>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        match &self.oracle {
+            Some(oracle) => oracle.estimate_eip1559_fees().await,
+            None => provider.estimate_eip1559_fees(self.estimator).await,
+        }
+    }
+
+    /// Whether to fill legacy gas prices without trying EIP-1559 fees first.
+    fn prefers_legacy(&self) -> bool {
+        match self.fee_market {
+            FeeMarket::Auto => self
+                .legacy_since
+                .lock()
+                .unwrap()
+                .is_some_and(|since| since.elapsed() < self.recheck_interval),
+            FeeMarket::Eip1559 => false,
+            FeeMarket::Legacy => true,
+        }
+    }
+
+    /// Remembers whether the endpoint supports EIP-1559 fees.
+    fn set_supports_eip1559(&self, supported: bool) {
+        *self.legacy_since.lock().unwrap() = (!supported).then(Instant::now);
+    }
+
+    /// Estimates the gas limit of `tx`, applying the buffer, fallback and cap.
+    async fn estimate_gas_limit<P, T, N>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<u128>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let gas_limit = match provider.estimate_gas(tx).await {
+            Ok(estimate) => bump_fee(estimate, self.gas_limit_buffer),
+            // the node ran the transaction, which e.g. reverted
+            Err(err) if err.is_error_resp() && self.fallback_gas_limit.is_some() => {
+                self.fallback_gas_limit.expect("checked above")
+            }
+            Err(err) => return Err(err),
+        };
+        Ok(self.gas_limit_cap.map_or(gas_limit, |cap| gas_limit.min(cap)))
+    }
+
     async fn prepare_legacy<P, T, N>(
         &self,
         provider: &P,
@@ -86,7 +284,7 @@ impl GasFiller {
         let gas_limit_fut = if let Some(gas_limit) = tx.gas_limit() {
             async move { Ok(gas_limit) }.left_future()
         } else {
-            provider.estimate_gas(tx).into_future().right_future()
+            self.estimate_gas_limit(provider, tx).right_future()
         };
 
         let (gas_price, gas_limit) = futures::try_join!(gas_price_fut, gas_limit_fut)?;
@@ -107,7 +305,7 @@ impl GasFiller {
         let gas_limit_fut = if let Some(gas_limit) = tx.gas_limit() {
             async move { Ok(gas_limit) }.left_future()
         } else {
-            provider.estimate_gas(tx).into_future().right_future()
+            self.estimate_gas_limit(provider, tx).right_future()
         };
 
         let eip1559_fees_fut = if let (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) =
@@ -116,7 +314,7 @@ impl GasFiller {
             async move { Ok(Eip1559Estimation { max_fee_per_gas, max_priority_fee_per_gas }) }
                 .left_future()
         } else {
-            provider.estimate_eip1559_fees(None).right_future()
+            self.estimate_eip1559_fees(provider).right_future()
         };
 
         let (gas_limit, estimate) = futures::try_join!(gas_limit_fut, eip1559_fees_fut)?;
@@ -137,7 +335,7 @@ impl GasFiller {
         let gas_limit_fut = if let Some(gas_limit) = tx.gas_limit() {
             async move { Ok(gas_limit) }.left_future()
         } else {
-            provider.estimate_gas(tx).into_future().right_future()
+            self.estimate_gas_limit(provider, tx).right_future()
         };
 
         let eip1559_fees_fut = if let (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) =
@@ -146,12 +344,14 @@ impl GasFiller {
             async move { Ok(Eip1559Estimation { max_fee_per_gas, max_priority_fee_per_gas }) }
                 .left_future()
         } else {
-            provider.estimate_eip1559_fees(None).right_future()
+            self.estimate_eip1559_fees(provider).right_future()
         };
 
         let max_fee_per_blob_gas_fut = if let Some(max_fee_per_blob_gas) = tx.max_fee_per_blob_gas()
         {
             async move { Ok(max_fee_per_blob_gas) }.left_future()
+        } else if let Some((urgency, params)) = self.blob_fee_urgency {
+            provider.estimate_blob_fee(urgency, params).left_future().right_future()
         } else {
             async {
                 provider
@@ -163,6 +363,7 @@ impl GasFiller {
                     .ok_or(RpcError::UnsupportedFeature("eip4844"))
             }
             .right_future()
+            .right_future()
         };
 
         let (gas_limit, estimate, max_fee_per_blob_gas) =
@@ -215,16 +416,36 @@ impl<N: Network> TxFiller<N> for GasFiller {
             self.prepare_legacy(provider, tx).await
         } else if tx.blob_sidecar().is_some() {
             self.prepare_4844(provider, tx).await
+        } else if tx.max_fee_per_gas().is_none()
+            && tx.max_priority_fee_per_gas().is_none()
+            && self.prefers_legacy()
+        {
+            self.prepare_legacy(provider, tx).await
         } else {
             match self.prepare_1559(provider, tx).await {
+                Ok(estimate) => {
+                    if self.fee_market == FeeMarket::Auto {
+                        self.set_supports_eip1559(true);
+                    }
+                    Ok(estimate)
+                }
                 // fallback to legacy
-                Ok(estimate) => Ok(estimate),
-                Err(RpcError::UnsupportedFeature(_)) => self.prepare_legacy(provider, tx).await,
+                Err(e) if e.is_unsupported_feature() && self.fee_market == FeeMarket::Auto => {
+                    self.set_supports_eip1559(false);
+                    self.prepare_legacy(provider, tx).await
+                }
                 Err(e) => Err(e),
             }
         }
     }
 
+    fn rejected(&self, tx: &N::TransactionRequest, err: &TransportError) {
+        let eip1559 = tx.gas_price().is_none() && tx.max_fee_per_gas().is_some();
+        if eip1559 && self.fee_market == FeeMarket::Auto && rejects_eip1559(err) {
+            self.set_supports_eip1559(false);
+        }
+    }
+
     async fn fill(
         &self,
         fillable: Self::Fillable,
@@ -253,13 +474,145 @@ impl<N: Network> TxFiller<N> for GasFiller {
     }
 }
 
+/// Whether the node rejected a transaction because of its EIP-1559 type.
+fn rejects_eip1559(err: &TransportError) -> bool {
+    err.error_kind() == Some(ErrorKind::TransactionTypeNotSupported)
+}
+
 #[cfg(feature = "reqwest")]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{ProviderBuilder, WalletProvider};
+    use crate::{
+        test_utils::{error_resp, mock_provider},
+        ProviderBuilder, RootProvider, WalletProvider,
+    };
+    use alloy_json_rpc::RequestContext;
+    use alloy_network::Ethereum;
     use alloy_primitives::{address, U256};
-    use alloy_rpc_types::TransactionRequest;
+    use alloy_rpc_client::ErrorContext;
+    use alloy_rpc_types::{Block, FeeHistory, TransactionRequest};
+    use alloy_transport::BoxTransport;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Returns a node estimating 100_000 gas for transactions with a value, and reverting others.
+    fn node() -> RootProvider<BoxTransport, Ethereum> {
+        let provider = mock_provider(|method, params| {
+            assert_eq!(method, "eth_estimateGas");
+            if params.contains("value") {
+                Ok(serde_json::json!("0x186a0"))
+            } else {
+                Err(error_resp(3, "execution reverted"))
+            }
+        });
+        // Reverts are recognized through the request context of the errors.
+        provider.client().set_error_context(ErrorContext::new());
+        provider
+    }
+
+    async fn gas_limit(filler: GasFiller, tx: &TransactionRequest) -> TransportResult<u128> {
+        let tx = tx.clone().with_gas_price(1);
+        match TxFiller::<Ethereum>::prepare(&filler, &node(), &tx).await? {
+            GasFillable::Legacy { gas_limit, .. } => Ok(gas_limit),
+            fillable => panic!("unexpected fillable: {fillable:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn configures_gas_limit() {
+        let tx = TransactionRequest::default().value(U256::from(1));
+        let reverting = TransactionRequest::default();
+
+        assert_eq!(gas_limit(GasFiller::default(), &tx).await.unwrap(), 100_000);
+        assert!(gas_limit(GasFiller::default(), &reverting).await.is_err());
+
+        let filler = GasFiller::default().with_gas_limit_buffer(20);
+        assert_eq!(gas_limit(filler.clone(), &tx).await.unwrap(), 120_000);
+        let filler = filler.with_gas_limit_cap(110_000);
+        assert_eq!(gas_limit(filler.clone(), &tx).await.unwrap(), 110_000);
+        let filler = filler.with_fallback_gas_limit(500_000);
+        assert_eq!(gas_limit(filler, &reverting).await.unwrap(), 110_000);
+    }
+
+    /// Returns a node whose latest block has a base fee if `eip1559` is set, counting the fee
+    /// estimations in `estimations`.
+    fn fee_market_node(
+        eip1559: Arc<AtomicBool>,
+        estimations: Arc<AtomicUsize>,
+    ) -> RootProvider<BoxTransport, Ethereum> {
+        mock_provider(move |method, _| {
+            Ok(match method {
+                "eth_gasPrice" => serde_json::json!("0x1"),
+                "eth_feeHistory" => {
+                    estimations.fetch_add(1, Ordering::SeqCst);
+                    serde_json::json!(FeeHistory::default())
+                }
+                "eth_getBlockByNumber" => {
+                    let mut block: Block = Block::default();
+                    block.header.base_fee_per_gas = eip1559.load(Ordering::SeqCst).then_some(1);
+                    serde_json::json!(block)
+                }
+                method => unreachable!("{method}"),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn remembers_fee_market() {
+        let eip1559 = Arc::new(AtomicBool::new(false));
+        let estimations = Arc::new(AtomicUsize::new(0));
+        let node = fee_market_node(eip1559.clone(), estimations.clone());
+        let tx = TransactionRequest::default().with_gas_limit(21_000);
+        let prepare = |filler: &GasFiller| {
+            let (filler, node, tx) = (filler.clone(), node.clone(), tx.clone());
+            async move { TxFiller::<Ethereum>::prepare(&filler, &node, &tx).await.unwrap() }
+        };
+
+        // Without a base fee, the filler falls back to a legacy gas price once and remembers it.
+        let filler = GasFiller::default();
+        assert!(matches!(prepare(&filler).await, GasFillable::Legacy { gas_price: 1, .. }));
+        assert!(matches!(prepare(&filler).await, GasFillable::Legacy { .. }));
+        assert_eq!(estimations.load(Ordering::SeqCst), 1);
+
+        // Once rechecked, EIP-1559 fees are filled again.
+        eip1559.store(true, Ordering::SeqCst);
+        let filler = filler.with_fee_market_recheck_interval(Duration::ZERO);
+        assert!(matches!(prepare(&filler).await, GasFillable::Eip1559 { .. }));
+        assert_eq!(estimations.load(Ordering::SeqCst), 2);
+
+        // A gateway rejecting EIP-1559 transactions downgrades the endpoint.
+        let filler = filler.with_fee_market_recheck_interval(DEFAULT_FEE_MARKET_RECHECK_INTERVAL);
+        let rejected = tx.clone().with_max_fee_per_gas(2).with_max_priority_fee_per_gas(1);
+        let err = error_resp(-32000, "transaction type not supported")
+            .with_context(RequestContext::default());
+        TxFiller::<Ethereum>::rejected(&filler, &rejected, &err);
+        assert!(matches!(prepare(&filler).await, GasFillable::Legacy { .. }));
+        assert!(matches!(prepare(&filler).await, GasFillable::Legacy { .. }));
+        assert_eq!(estimations.load(Ordering::SeqCst), 2);
+
+        // The kind of fees can be forced.
+        let filler = GasFiller::default().with_fee_market(FeeMarket::Legacy);
+        assert!(matches!(prepare(&filler).await, GasFillable::Legacy { .. }));
+        let filler = GasFiller::default().with_fee_market(FeeMarket::Eip1559);
+        assert!(matches!(prepare(&filler).await, GasFillable::Eip1559 { .. }));
+        eip1559.store(false, Ordering::SeqCst);
+        assert!(TxFiller::<Ethereum>::prepare(&filler, &node, &tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn uses_eip1559_estimator() {
+        let node = fee_market_node(Arc::new(AtomicBool::new(true)), Default::default());
+        let tx = TransactionRequest::default().with_gas_limit(21_000);
+
+        let filler = GasFiller::default()
+            .with_fee_market(FeeMarket::Eip1559)
+            .with_eip1559_estimator(crate::utils::eip1559_arbitrum_estimator);
+        let fillable = TxFiller::<Ethereum>::prepare(&filler, &node, &tx).await.unwrap();
+        let GasFillable::Eip1559 { estimate, .. } = fillable else {
+            panic!("unexpected fillable: {fillable:?}");
+        };
+        assert_eq!(estimate, Eip1559Estimation { max_fee_per_gas: 2, max_priority_fee_per_gas: 0 });
+    }
 
     #[tokio::test]
     async fn no_gas_price_or_limit() {
@@ -307,7 +660,7 @@ mod tests {
     #[tokio::test]
     async fn non_eip1559_network() {
         let provider = ProviderBuilder::new()
-            .filler(crate::fillers::GasFiller)
+            .filler(crate::fillers::GasFiller::default())
             .filler(crate::fillers::NonceFiller::default())
             .filler(crate::fillers::ChainIdFiller::default())
             .on_anvil();
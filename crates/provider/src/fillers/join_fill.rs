@@ -4,8 +4,8 @@ use crate::{
     Provider, ProviderLayer,
 };
 use alloy_network::Network;
-use alloy_transport::{Transport, TransportResult};
-use futures::try_join;
+use alloy_transport::{Transport, TransportError, TransportResult};
+use futures::{join, try_join};
 
 /// A layer that can fill in a [`TransactionRequest`] with additional
 /// information by joining two [`TxFiller`]s. This  struct is itself a
@@ -119,6 +119,25 @@ where
         };
         Ok(tx)
     }
+
+    fn rejected(&self, tx: &N::TransactionRequest, err: &TransportError) {
+        self.left.rejected(tx, err);
+        self.right.rejected(tx, err);
+    }
+
+    async fn release(&self, fillable: &Self::Fillable) {
+        let left = async {
+            if let Some(fillable) = &fillable.0 {
+                self.left.release(fillable).await;
+            }
+        };
+        let right = async {
+            if let Some(fillable) = &fillable.1 {
+                self.right.release(fillable).await;
+            }
+        };
+        join!(left, right);
+    }
 }
 
 impl<L, R, P, T, N> ProviderLayer<P, T, N> for JoinFill<L, R>
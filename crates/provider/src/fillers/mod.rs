@@ -12,11 +12,14 @@ pub use chain_id::ChainIdFiller;
 mod signer;
 pub use signer::SignerFiller;
 
+mod node_signer;
+pub use node_signer::{NodeSignerFillable, NodeSignerFiller};
+
 mod nonce;
-pub use nonce::NonceFiller;
+pub use nonce::{NonceFiller, NonceReservation, DEFAULT_NONCE_RESYNC_INTERVAL};
 
 mod gas;
-pub use gas::GasFiller;
+pub use gas::{FeeMarket, GasFiller, DEFAULT_FEE_MARKET_RECHECK_INTERVAL};
 
 mod join_fill;
 pub use join_fill::JoinFill;
@@ -26,7 +29,7 @@ use crate::{
 };
 use alloy_json_rpc::RpcError;
 use alloy_network::{Ethereum, Network};
-use alloy_transport::{Transport, TransportResult};
+use alloy_transport::{Transport, TransportError, TransportResult};
 use async_trait::async_trait;
 use futures_utils_wasm::impl_future;
 use std::marker::PhantomData;
@@ -124,7 +127,7 @@ impl FillerControlFlow {
 pub trait TxFiller<N: Network = Ethereum>: Clone + Send + Sync + std::fmt::Debug {
     /// The properties that this filler retrieves from the RPC. to fill in the
     /// TransactionRequest.
-    type Fillable: Send + Sync + 'static;
+    type Fillable: Clone + Send + Sync + 'static;
 
     /// Joins this filler with another filler to compose multiple fillers.
     fn join_with<T>(self, other: T) -> JoinFill<Self, T>
@@ -171,6 +174,25 @@ pub trait TxFiller<N: Network = Ethereum>: Clone + Send + Sync + std::fmt::Debug
         tx: SendableTx<N>,
     ) -> impl_future!(<Output = TransportResult<SendableTx<N>>>);
 
+    /// Notifies the filler that the node failed to send a transaction,
+    /// before it is [released](Self::release) if it was not broadcast.
+    ///
+    /// `tx` is the request as filled, and `err` the error of the node. This
+    /// lets fillers adapt to what the node accepts. Does nothing by default.
+    fn rejected(&self, tx: &N::TransactionRequest, err: &TransportError) {
+        let _ = (tx, err);
+    }
+
+    /// Releases what was reserved by [`prepare`](Self::prepare) for a
+    /// transaction that failed before being broadcast, such as its nonce.
+    ///
+    /// `fillable` is the one prepared for the transaction, so that only the
+    /// reservations made for it are released. Does nothing by default.
+    fn release(&self, fillable: &Self::Fillable) -> impl_future!(<Output = ()>) {
+        let _ = fillable;
+        async {}
+    }
+
     /// Prepares and fills the transaction request with the fillable properties.
     fn prepare_and_fill<P, T>(
         &self,
@@ -238,6 +260,13 @@ where
         self.filler.join_with(other).layer(self.inner)
     }
 
+    /// Releases the reservations of the fillables prepared for a transaction.
+    async fn release(&self, fillables: &[F::Fillable]) {
+        for fillable in fillables {
+            self.filler.release(fillable).await;
+        }
+    }
+
     /// Fills the transaction request, using the configured fillers
     pub async fn fill(&self, tx: N::TransactionRequest) -> TransportResult<SendableTx<N>>
     where
@@ -265,9 +294,31 @@ where
         mut tx: SendableTx<N>,
     ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
         let mut count = 0;
+        // the request as last filled, and what was prepared for it, released if the transaction
+        // fails before being broadcast
+        let mut filled = tx.as_builder().cloned();
+        let mut fillables = Vec::new();
 
         while self.filler.continue_filling(&tx) {
-            tx = self.filler.prepare_and_fill(&self.inner, tx).await?;
+            let builder = tx.as_builder().expect("checked by continue_filling");
+            let fillable = match self.filler.prepare(&self.inner, builder).await {
+                Ok(fillable) => fillable,
+                Err(err) => {
+                    self.release(&fillables).await;
+                    return Err(err);
+                }
+            };
+            fillables.push(fillable.clone());
+            tx = match self.filler.fill(fillable, tx).await {
+                Ok(tx) => tx,
+                Err(err) => {
+                    self.release(&fillables).await;
+                    return Err(err);
+                }
+            };
+            if let Some(builder) = tx.as_builder() {
+                filled = Some(builder.clone());
+            }
 
             count += 1;
             if count >= 20 {
@@ -279,14 +330,36 @@ where
 
         if let Some(builder) = tx.as_builder() {
             if let FillerControlFlow::Missing(missing) = self.filler.status(builder) {
-                // TODO: improve this.
-                // blocked by #431
-                let message = format!("missing properties: {:?}", missing);
+                let missing = missing
+                    .iter()
+                    .map(|(name, keys)| format!("{} (required by {name})", keys.join(", ")))
+                    .collect::<Vec<_>>();
+                let message = format!("missing properties: {}", missing.join("; "));
+                self.release(&fillables).await;
                 return Err(RpcError::local_usage_str(&message));
             }
         }
 
         // Errors in tx building happen further down the stack.
-        self.inner.send_transaction_internal(tx).await
+        let result = self.inner.send_transaction_internal(tx).await;
+        if let (Err(err), Some(filled)) = (&result, &filled) {
+            self.filler.rejected(filled, err);
+            if is_unsent(err) {
+                self.release(&fillables).await;
+            }
+        }
+        result
+    }
+}
+
+/// Returns `true` if a failed send certainly did not broadcast the transaction.
+///
+/// Errors returned by the node reject the transaction, unless they report that its nonce was
+/// already used. Transport errors are ambiguous, as the transaction may have reached the node.
+fn is_unsent(err: &TransportError) -> bool {
+    if let Some(payload) = err.as_error_resp() {
+        let message = payload.message.to_lowercase();
+        return !(message.contains("nonce too low") || message.contains("already known"));
     }
+    err.is_unsupported_feature() || err.is_local_usage_error() || err.is_ser_error()
 }
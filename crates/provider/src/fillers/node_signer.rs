@@ -0,0 +1,207 @@
+use crate::{
+    fillers::{FillerControlFlow, TxFiller},
+    provider::SendableTx,
+    Provider,
+};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{Address, Bytes};
+use alloy_transport::{Transport, TransportResult};
+use std::sync::{Arc, OnceLock};
+
+/// The properties fetched by the [`NodeSignerFiller`].
+#[allow(unreachable_pub)]
+#[doc(hidden)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NodeSignerFillable {
+    /// The sender of a transaction without a `from` address.
+    Sender(Address),
+    /// The EIP-2718 encoding of the transaction signed by the node.
+    Signed(Bytes),
+}
+
+/// A [`TxFiller`] deferring signing to the accounts of the connected node.
+///
+/// This suits development networks with unlocked accounts, and setups where keys are held by
+/// the node or an external signer such as clef or web3signer. Transactions without a `from`
+/// address are sent from the first account returned by [`Provider::get_accounts`], unless a
+/// sender is configured with [`with_sender`](Self::with_sender).
+///
+/// By default, complete transactions are signed with [`Provider::sign_transaction`] and sent as
+/// raw transactions, like with a local signer. With
+/// [`send_through_node`](Self::send_through_node) they are instead left unsigned, and the node
+/// signs them as it receives them through `eth_sendTransaction`.
+///
+/// Messages can be signed by the node with [`Provider::sign_message`].
+///
+/// # Example
+///
+/// ```
+/// # use alloy_rpc_types::TransactionRequest;
+/// # use alloy_provider::{ProviderBuilder, Provider};
+/// # async fn test(url: url::Url) -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new().with_recommended_fillers().with_node_signer().on_http(url);
+///
+/// provider.send_transaction(TransactionRequest::default()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct NodeSignerFiller {
+    sender: Arc<OnceLock<Address>>,
+    send_through_node: bool,
+}
+
+impl NodeSignerFiller {
+    /// Creates a new filler signing with the accounts of the node.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends transactions without a `from` address from `sender`, instead of the first account of
+    /// the node.
+    pub fn with_sender(self, sender: Address) -> Self {
+        Self { sender: Arc::new(OnceLock::from(sender)), ..self }
+    }
+
+    /// Leaves transactions unsigned, to be signed by the node when sent through
+    /// `eth_sendTransaction`.
+    pub const fn send_through_node(mut self) -> Self {
+        self.send_through_node = true;
+        self
+    }
+
+    /// Returns the sender of transactions without a `from` address, fetching the accounts of the
+    /// node the first time.
+    async fn sender<P, T, N>(&self, provider: &P) -> TransportResult<Address>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        if let Some(sender) = self.sender.get() {
+            return Ok(*sender);
+        }
+        let accounts = provider.get_accounts().await?;
+        let sender = accounts
+            .first()
+            .copied()
+            .ok_or_else(|| RpcError::local_usage_str("the node has no accounts to sign with"))?;
+        Ok(*self.sender.get_or_init(|| sender))
+    }
+}
+
+impl<N: Network> TxFiller<N> for NodeSignerFiller {
+    type Fillable = NodeSignerFillable;
+
+    fn status(&self, tx: &N::TransactionRequest) -> FillerControlFlow {
+        if tx.from().is_none() {
+            return FillerControlFlow::Ready;
+        }
+        if self.send_through_node {
+            return FillerControlFlow::Finished;
+        }
+
+        match tx.complete_preferred() {
+            Ok(_) => FillerControlFlow::Ready,
+            Err(e) => FillerControlFlow::Missing(vec![("NodeSigner", e)]),
+        }
+    }
+
+    async fn prepare<P, T>(
+        &self,
+        provider: &P,
+        tx: &N::TransactionRequest,
+    ) -> TransportResult<Self::Fillable>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+    {
+        if tx.from().is_none() {
+            return self.sender(provider).await.map(NodeSignerFillable::Sender);
+        }
+        provider.sign_transaction(tx.clone()).await.map(NodeSignerFillable::Signed)
+    }
+
+    async fn fill(
+        &self,
+        fillable: Self::Fillable,
+        tx: SendableTx<N>,
+    ) -> TransportResult<SendableTx<N>> {
+        let mut builder = match tx {
+            SendableTx::Builder(builder) => builder,
+            _ => return Ok(tx),
+        };
+
+        match fillable {
+            NodeSignerFillable::Sender(sender) => {
+                builder.set_from(sender);
+                Ok(SendableTx::Builder(builder))
+            }
+            NodeSignerFillable::Signed(raw) => N::TxEnvelope::decode_2718_exact(&raw)
+                .map(SendableTx::Envelope)
+                .map_err(RpcError::local_usage),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::mock, Provider, ProviderBuilder};
+    use alloy_consensus::{SignableTransaction, TxEnvelope, TypedTransaction};
+    use alloy_eips::eip2718::Decodable2718;
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_network::{TransactionBuilder, TxSignerSync};
+    use alloy_primitives::{hex, Address, Bytes};
+    use alloy_rpc_client::ClientBuilder;
+    use alloy_rpc_types::TransactionRequest;
+    use alloy_signer_wallet::LocalWallet;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn signs_with_node_accounts() {
+        let wallet = LocalWallet::random();
+        let address = wallet.address();
+        let sent = Arc::new(Mutex::new(None));
+        let raw = sent.clone();
+        // A node holding the key of the wallet.
+        let service = mock(move |method, params| {
+            Ok(match method {
+                "eth_accounts" => json!([address]),
+                "eth_signTransaction" => {
+                    let (tx,): (TransactionRequest,) = serde_json::from_str(params).unwrap();
+                    assert_eq!(tx.from, Some(address));
+                    let Ok(TypedTransaction::Legacy(mut tx)) = tx.build_typed_tx() else {
+                        unreachable!()
+                    };
+                    let signature = wallet.sign_transaction_sync(&mut tx).unwrap();
+                    let tx: TxEnvelope = tx.into_signed(signature).into();
+                    json!(hex::encode_prefixed(tx.encoded_2718()))
+                }
+                "eth_sendRawTransaction" => {
+                    let (tx,): (Bytes,) = serde_json::from_str(params).unwrap();
+                    let tx = TxEnvelope::decode_2718_exact(&tx).unwrap();
+                    let hash = *tx.tx_hash();
+                    *raw.lock().unwrap() = Some(tx);
+                    json!(hash)
+                }
+                _ => unreachable!(),
+            })
+        });
+        let client = ClientBuilder::default().transport(service, true);
+        let provider = ProviderBuilder::new().with_node_signer().on_client(client);
+
+        let tx = TransactionRequest::default()
+            .with_to(Address::ZERO)
+            .with_nonce(0)
+            .with_gas_limit(21_000)
+            .with_gas_price(1);
+        let hash = *provider.send_transaction(tx).await.unwrap().tx_hash();
+        let Some(TxEnvelope::Legacy(signed)) = sent.lock().unwrap().take() else { unreachable!() };
+        assert_eq!(*signed.hash(), hash);
+        let signer = signed.signature().recover_address_from_prehash(&signed.signature_hash());
+        assert_eq!(signer.unwrap(), address);
+    }
+}
@@ -3,18 +3,42 @@ use crate::{
     provider::SendableTx,
     Provider,
 };
+use alloy_eips::BlockId;
 use alloy_network::{Network, TransactionBuilder};
 use alloy_primitives::Address;
 use alloy_transport::{Transport, TransportResult};
 use dashmap::DashMap;
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Duration,
+};
 use tokio::sync::Mutex;
 
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// Timers of `std` are not available in the browser.
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
+/// The default interval after which the locally tracked nonces are checked against the node.
+pub const DEFAULT_NONCE_RESYNC_INTERVAL: Duration = Duration::from_secs(60);
+
 /// A [`TxFiller`] that fills nonces on transactions.
 ///
-/// The filler will fetch the transaction count for any new account it sees,
-/// store it locally and increment the locally stored nonce as transactions are
-/// sent via [`Provider::send_transaction`].
+/// The filler will fetch the pending transaction count for any new account it
+/// sees, store it locally and increment the locally stored nonce as
+/// transactions are sent via [`Provider::send_transaction`]. Nonces are
+/// handed out under a per-account lock, so many transactions from the same
+/// sender can be in flight at once.
+///
+/// The local nonce is periodically resynced against the pending transaction
+/// count of the node, see [`NonceFiller::with_resync_interval`]. When a
+/// transaction fails before it is broadcast, e.g. because signing or
+/// submitting it failed, the [`NonceReservation`] made for it is released and
+/// its nonce handed out again to the next transaction, so that no gap is left
+/// behind. Releases only apply to the reservation they were made for, so a
+/// failed transaction never releases the nonce of another one.
 ///
 /// # Note
 ///
@@ -40,13 +64,95 @@ use tokio::sync::Mutex;
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct NonceFiller {
-    nonces: DashMap<Address, Arc<Mutex<Option<u64>>>>,
+    nonces: DashMap<Address, Arc<Mutex<NonceState>>>,
+    resync_interval: Duration,
+}
+
+impl Default for NonceFiller {
+    fn default() -> Self {
+        Self { nonces: DashMap::new(), resync_interval: DEFAULT_NONCE_RESYNC_INTERVAL }
+    }
+}
+
+/// A nonce handed out by a [`NonceFiller`] to a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NonceReservation {
+    from: Address,
+    nonce: u64,
+    /// Distinguishes the reservations of the same nonce, e.g. after it was released.
+    id: u64,
+}
+
+impl NonceReservation {
+    /// Returns the account of the nonce.
+    pub const fn from(&self) -> Address {
+        self.from
+    }
+
+    /// Returns the reserved nonce.
+    pub const fn nonce(&self) -> u64 {
+        self.nonce
+    }
+}
+
+/// The locally tracked nonces of an account.
+#[derive(Debug, Default)]
+struct NonceState {
+    /// The next nonce that has never been handed out.
+    next: u64,
+    /// Handed out nonces that have not been released, with the ID of their reservation.
+    reserved: BTreeMap<u64, u64>,
+    /// The ID of the next reservation.
+    next_id: u64,
+    /// Released nonces, to be handed out again before `next`.
+    released: BTreeSet<u64>,
+    /// When `next` was last checked against the node, if ever.
+    synced_at: Option<Instant>,
+}
+
+impl NonceState {
+    /// Whether the state should be checked against the node.
+    fn needs_sync(&self, interval: Duration) -> bool {
+        self.synced_at.map_or(true, |synced_at| synced_at.elapsed() >= interval)
+    }
+
+    /// Merges the pending transaction count of the node into the state.
+    ///
+    /// Nonces below the count are used on chain, so they are neither reserved nor released
+    /// anymore.
+    fn sync(&mut self, pending: u64) {
+        self.next = self.next.max(pending);
+        self.reserved = self.reserved.split_off(&pending);
+        self.released = self.released.split_off(&pending);
+        self.synced_at = Some(Instant::now());
+    }
+
+    /// Hands out the lowest released nonce, or the next one, returning it with the ID of its
+    /// reservation.
+    fn take(&mut self) -> (u64, u64) {
+        let nonce = self.released.pop_first().unwrap_or_else(|| {
+            self.next += 1;
+            self.next - 1
+        });
+        let id = self.next_id;
+        self.next_id += 1;
+        self.reserved.insert(nonce, id);
+        (nonce, id)
+    }
+
+    /// Releases a reserved nonce, unless it was reserved again since.
+    fn release(&mut self, nonce: u64, id: u64) {
+        if self.reserved.get(&nonce) == Some(&id) {
+            self.reserved.remove(&nonce);
+            self.released.insert(nonce);
+        }
+    }
 }
 
 impl<N: Network> TxFiller<N> for NonceFiller {
-    type Fillable = u64;
+    type Fillable = NonceReservation;
 
     fn status(&self, tx: &<N as Network>::TransactionRequest) -> FillerControlFlow {
         if tx.nonce().is_some() {
@@ -73,19 +179,41 @@ impl<N: Network> TxFiller<N> for NonceFiller {
 
     async fn fill(
         &self,
-        nonce: Self::Fillable,
+        reservation: Self::Fillable,
         mut tx: SendableTx<N>,
     ) -> TransportResult<SendableTx<N>> {
         if let Some(builder) = tx.as_mut_builder() {
-            builder.set_nonce(nonce);
+            builder.set_nonce(reservation.nonce);
         }
         Ok(tx)
     }
+
+    async fn release(&self, reservation: &NonceReservation) {
+        let Some(state) = self.nonces.get(&reservation.from).map(|state| Arc::clone(state.value()))
+        else {
+            return;
+        };
+        state.lock().await.release(reservation.nonce, reservation.id);
+    }
 }
 
 impl NonceFiller {
+    /// Sets the interval after which the locally tracked nonce of an account is
+    /// checked against its pending transaction count on the node, which picks
+    /// up transactions sent from elsewhere.
+    ///
+    /// Defaults to [`DEFAULT_NONCE_RESYNC_INTERVAL`].
+    pub const fn with_resync_interval(mut self, interval: Duration) -> Self {
+        self.resync_interval = interval;
+        self
+    }
+
     /// Get the next nonce for the given account.
-    async fn get_next_nonce<P, T, N>(&self, provider: &P, from: Address) -> TransportResult<u64>
+    async fn get_next_nonce<P, T, N>(
+        &self,
+        provider: &P,
+        from: Address,
+    ) -> TransportResult<NonceReservation>
     where
         P: Provider<T, N>,
         N: Network,
@@ -95,28 +223,105 @@ impl NonceFiller {
         let mutex = Arc::clone(self.nonces.entry(from).or_default().value());
 
         // locks the value (does not lock dashmap)
-        let mut nonce = mutex.lock().await;
-        match *nonce {
-            Some(ref mut nonce) => {
-                *nonce += 1;
-                Ok(*nonce)
-            }
-            None => {
-                // initialize the nonce if we haven't seen this account before
-                let initial_nonce = provider.get_transaction_count(from).await?;
-                *nonce = Some(initial_nonce);
-                Ok(initial_nonce)
-            }
+        let mut state = mutex.lock().await;
+        if state.needs_sync(self.resync_interval) {
+            // initialize the nonce if we haven't seen this account before, or resync it
+            let pending = provider.get_transaction_count(from).block_id(BlockId::pending()).await?;
+            state.sync(pending);
         }
+        let (nonce, id) = state.take();
+        Ok(NonceReservation { from, nonce, id })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        fillers::FillProvider,
+        test_utils::{error_resp, mock_provider},
+        RootProvider,
+    };
     use crate::{ProviderBuilder, WalletProvider};
-    use alloy_primitives::{address, U256};
+    use alloy_network::Ethereum;
+    use alloy_primitives::{address, B256, U256};
+    use alloy_rpc_client::ErrorContext;
     use alloy_rpc_types::TransactionRequest;
+    use alloy_transport::BoxTransport;
+    use serde_json::json;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex as StdMutex,
+    };
+
+    /// Returns a node with 5 pending transactions from every sender, which rejects the first
+    /// transaction sent to it and records the nonces of the accepted ones.
+    fn node(accepted: Arc<StdMutex<Vec<u64>>>) -> RootProvider<BoxTransport, Ethereum> {
+        let sent = Arc::new(AtomicUsize::new(0));
+        let provider = mock_provider(move |method, params| match method {
+            "eth_getTransactionCount" => Ok(json!("0x5")),
+            "eth_sendTransaction" if sent.fetch_add(1, Ordering::SeqCst) == 0 => {
+                Err(error_resp(-32000, "insufficient funds for gas * price + value"))
+            }
+            "eth_sendTransaction" => {
+                let [tx]: [TransactionRequest; 1] = serde_json::from_str(params).unwrap();
+                accepted.lock().unwrap().push(tx.nonce.unwrap());
+                Ok(json!(B256::with_last_byte(tx.nonce.unwrap() as u8)))
+            }
+            method => panic!("unexpected request: {method}"),
+        });
+        // Rejections are recognized through the request context of the errors.
+        provider.client().set_error_context(ErrorContext::new());
+        provider
+    }
+
+    #[tokio::test]
+    async fn concurrent_nonces_are_unique() {
+        let provider = node(Default::default());
+        let filler = NonceFiller::default();
+        let tx = TransactionRequest::default().from(Address::with_last_byte(1));
+
+        let nonces = futures::future::try_join_all(
+            (0..10).map(|_| TxFiller::<Ethereum>::prepare(&filler, &provider, &tx)),
+        )
+        .await
+        .unwrap();
+        let nonces: BTreeSet<_> =
+            nonces.into_iter().map(|reservation| reservation.nonce()).collect();
+        assert_eq!(nonces, (5..15).collect());
+    }
+
+    #[tokio::test]
+    async fn reuses_nonce_of_rejected_transaction() {
+        let accepted = Arc::new(StdMutex::new(Vec::new()));
+        let provider = FillProvider::new(node(accepted.clone()), NonceFiller::default());
+        let tx = TransactionRequest::default().from(Address::with_last_byte(1));
+
+        assert!(provider.send_transaction(tx.clone()).await.is_err());
+        let _ = provider.send_transaction(tx.clone()).await.unwrap();
+        let _ = provider.send_transaction(tx).await.unwrap();
+        assert_eq!(*accepted.lock().unwrap(), [5, 6]);
+    }
+
+    #[tokio::test]
+    async fn releases_only_own_reservation() {
+        let accepted = Arc::new(StdMutex::new(Vec::new()));
+        let provider = FillProvider::new(node(accepted.clone()), NonceFiller::default());
+        let tx = TransactionRequest::default().from(Address::with_last_byte(1));
+
+        let first = TxFiller::<Ethereum>::prepare(&provider.filler, &provider.inner, &tx).await;
+        let first = first.unwrap();
+        TxFiller::<Ethereum>::release(&provider.filler, &first).await;
+        let second = TxFiller::<Ethereum>::prepare(&provider.filler, &provider.inner, &tx).await;
+        assert_eq!(second.unwrap().nonce(), first.nonce());
+        // The first reservation was already released, and its nonce is reserved again.
+        TxFiller::<Ethereum>::release(&provider.filler, &first).await;
+
+        // A rejected transaction with the same nonce set by the caller releases nothing either.
+        assert!(provider.send_transaction(tx.clone().nonce(first.nonce())).await.is_err());
+        let _ = provider.send_transaction(tx).await.unwrap();
+        assert_eq!(*accepted.lock().unwrap(), [6]);
+    }
 
     #[tokio::test]
     async fn no_nonce_if_sender_unset() {
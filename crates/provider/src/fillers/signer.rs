@@ -11,6 +11,15 @@ use super::{FillerControlFlow, TxFiller};
 /// [`Provider::send_transaction`] locally before passing them to the node with
 /// [`Provider::send_raw_transaction`].
 ///
+/// Transactions are signed by the signer of their `from` address, or by the
+/// default signer if it is unset. Signers holding several credentials, such as
+/// [`EthereumSigner`], can thus sign for many senders, and credentials can be
+/// added or removed at runtime through [`WalletProvider::signer_mut`]. Filling
+/// a transaction from an address without a credential fails.
+///
+/// [`EthereumSigner`]: alloy_network::EthereumSigner
+/// [`WalletProvider::signer_mut`]: crate::WalletProvider::signer_mut
+///
 /// # Example
 ///
 /// ```
@@ -90,11 +99,17 @@ where
             _ => return Ok(tx),
         };
 
-        if builder.from().is_none() {
-            builder.set_from(self.signer.default_signer_address());
-            if !builder.can_build() {
-                return Ok(SendableTx::Builder(builder));
+        match builder.from() {
+            None => {
+                builder.set_from(self.signer.default_signer_address());
+                if !builder.can_build() {
+                    return Ok(SendableTx::Builder(builder));
+                }
             }
+            Some(from) if !self.signer.has_signer_for(&from) => {
+                return Err(RpcError::local_usage_str(&format!("no signer registered for {from}")));
+            }
+            Some(_) => {}
         }
 
         let envelope = builder.build(&self.signer).await.map_err(RpcError::local_usage)?;
@@ -106,9 +121,38 @@ where
 #[cfg(feature = "reqwest")]
 #[cfg(test)]
 mod tests {
-    use crate::{Provider, ProviderBuilder};
-    use alloy_primitives::{address, b256, U256};
+    use crate::{Provider, ProviderBuilder, WalletProvider};
+    use alloy_consensus::{TxEnvelope, TxLegacy};
+    use alloy_network::{EthereumSigner, TxSigner};
+    use alloy_primitives::{address, b256, TxKind, U256};
     use alloy_rpc_types::TransactionRequest;
+    use alloy_signer_wallet::LocalWallet;
+
+    #[tokio::test]
+    async fn selects_signer_by_sender() {
+        let (first, second) = (LocalWallet::random(), LocalWallet::random());
+        let mut provider = ProviderBuilder::new()
+            .signer(EthereumSigner::new(first.clone()))
+            .on_http("http://localhost:8545".parse().unwrap());
+        provider.signer_mut().register_signer(second.clone());
+
+        let mut legacy = TxLegacy {
+            chain_id: Some(1),
+            gas_limit: 21_000,
+            to: TxKind::Call(first.address()),
+            ..Default::default()
+        };
+        let tx: TransactionRequest = legacy.clone().into();
+        let tx = tx.from(second.address());
+        let filled = provider.fill(tx.clone()).await.unwrap();
+        let Some(TxEnvelope::Legacy(signed)) = filled.as_envelope() else { unreachable!() };
+        assert_eq!(*signed.signature(), second.sign_transaction(&mut legacy).await.unwrap());
+
+        assert!(provider.signer_mut().deregister_signer(first.address()).is_none());
+        assert!(provider.signer_mut().deregister_signer(second.address()).is_some());
+        let err = provider.fill(tx).await.unwrap_err();
+        assert!(err.to_string().contains("no signer registered"), "{err}");
+    }
 
     #[tokio::test]
     async fn poc() {
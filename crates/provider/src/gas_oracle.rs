@@ -0,0 +1,427 @@
+//! Fee estimation from several sources.
+//!
+//! A [`GasOracle`] estimates the base fee of the next block, the priority fee to pay, or both. The
+//! built-in oracles use the node:
+//! - [`FeeHistoryOracle`] uses a percentile of the priority fees of the last blocks, from
+//!   `eth_feeHistory`.
+//! - [`PendingBlockOracle`] projects the base fee of the block following the pending block.
+//! - [`MaxPriorityFeeOracle`] uses the priority fee suggested by `eth_maxPriorityFeePerGas`.
+//!
+//! Other sources, e.g. the API of a gas station, can implement [`GasOracle`] too, and a
+//! [`MedianOracle`] combines several sources, so that a single wrong source does not inflate the
+//! fees paid.
+//!
+//! An oracle can be used directly, or to fill the fees of transactions with
+//! [`GasFiller::with_oracle`]:
+//!
+//! ```no_run
+//! use alloy_provider::{
+//!     fillers::GasFiller,
+//!     gas_oracle::{FeeHistoryOracle, GasOracle, MaxPriorityFeeOracle, MedianOracle, PendingBlockOracle},
+//!     ProviderBuilder,
+//! };
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse()?);
+//! let oracle = MedianOracle::new()
+//!     .with_source(FeeHistoryOracle::new(provider.clone()).with_percentile(50.0))
+//!     .with_source(PendingBlockOracle::new(provider.clone()))
+//!     .with_source(MaxPriorityFeeOracle::new(provider.clone()));
+//!
+//! let fees = oracle.estimate_eip1559_fees().await?;
+//! println!("{} {}", fees.max_fee_per_gas, fees.max_priority_fee_per_gas);
+//!
+//! let filler = GasFiller::default().with_oracle(oracle);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`GasFiller::with_oracle`]: crate::fillers::GasFiller::with_oracle
+
+use crate::{
+    utils::{self, Eip1559Estimation},
+    Provider,
+};
+use alloy_eips::eip1559::BaseFeeParams;
+use alloy_json_rpc::RpcError;
+use alloy_network::Network;
+use alloy_rpc_types::BlockNumberOrTag;
+use alloy_transport::{Transport, TransportResult};
+use async_trait::async_trait;
+use std::{fmt, marker::PhantomData, sync::Arc};
+
+/// The fees estimated by a [`GasOracle`].
+///
+/// Each source may only estimate one of the fees, e.g. a source using `eth_maxPriorityFeePerGas`
+/// does not know the base fee.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeEstimate {
+    /// The estimated base fee per gas of the next block.
+    pub base_fee_per_gas: Option<u128>,
+    /// The estimated priority fee per gas to pay.
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+impl FeeEstimate {
+    /// Returns the EIP-1559 fees to pay, allowing the base fee to double before the transaction
+    /// is included, like [`Provider::estimate_eip1559_fees`].
+    ///
+    /// Fails with [`RpcError::UnsupportedFeature`] if the base fee is unknown, which is the case
+    /// of chains not supporting EIP-1559, and with a local usage error if the priority fee is
+    /// unknown.
+    pub fn eip1559(&self) -> TransportResult<Eip1559Estimation> {
+        let base_fee_per_gas =
+            self.base_fee_per_gas.ok_or(RpcError::UnsupportedFeature("eip1559"))?;
+        let max_priority_fee_per_gas = self.max_priority_fee_per_gas.ok_or_else(|| {
+            RpcError::local_usage_str("no gas oracle source estimated the priority fee")
+        })?;
+        Ok(Eip1559Estimation {
+            max_fee_per_gas: base_fee_per_gas * utils::EIP1559_BASE_FEE_MULTIPLIER
+                + max_priority_fee_per_gas,
+            max_priority_fee_per_gas,
+        })
+    }
+}
+
+/// A source of fee estimates.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait GasOracle: Send + Sync + fmt::Debug {
+    /// Estimates the fees of a transaction included in the next blocks.
+    async fn estimate(&self) -> TransportResult<FeeEstimate>;
+
+    /// Estimates the EIP-1559 fees of a transaction. See [`FeeEstimate::eip1559`].
+    async fn estimate_eip1559_fees(&self) -> TransportResult<Eip1559Estimation> {
+        self.estimate().await?.eip1559()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<O: GasOracle + ?Sized> GasOracle for Arc<O> {
+    async fn estimate(&self) -> TransportResult<FeeEstimate> {
+        (**self).estimate().await
+    }
+}
+
+/// A [`GasOracle`] using the fee history of the last blocks.
+///
+/// The base fee is the one of the next block, and the priority fee is the median, over the last
+/// blocks, of the given percentile of the priority fees paid in each block. By default, the 20th
+/// percentile of the last 10 blocks is used, like [`Provider::estimate_eip1559_fees`].
+pub struct FeeHistoryOracle<P, T, N> {
+    provider: P,
+    block_count: u64,
+    percentile: f64,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> fmt::Debug for FeeHistoryOracle<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FeeHistoryOracle")
+            .field("block_count", &self.block_count)
+            .field("percentile", &self.percentile)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> FeeHistoryOracle<P, T, N> {
+    /// Create a new oracle using the fee history of `provider`.
+    pub const fn new(provider: P) -> Self {
+        Self {
+            provider,
+            block_count: utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+            percentile: utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Sets the number of past blocks whose priority fees are used.
+    pub const fn with_block_count(mut self, block_count: u64) -> Self {
+        self.block_count = block_count;
+        self
+    }
+
+    /// Sets the percentile, between 0 and 100, of the priority fees paid in each block that is
+    /// used. Higher percentiles get transactions included faster.
+    pub const fn with_percentile(mut self, percentile: f64) -> Self {
+        self.percentile = percentile;
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<P, T, N> GasOracle for FeeHistoryOracle<P, T, N>
+where
+    P: Provider<T, N> + 'static,
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn estimate(&self) -> TransportResult<FeeEstimate> {
+        let fee_history = self
+            .provider
+            .get_fee_history(self.block_count, BlockNumberOrTag::Latest, &[self.percentile])
+            .await?;
+        let base_fee_per_gas = fee_history.next_block_base_fee().filter(|base_fee| *base_fee != 0);
+        let max_priority_fee_per_gas = fee_history
+            .reward
+            .as_deref()
+            .filter(|rewards| !rewards.is_empty())
+            .map(utils::estimate_priority_fee);
+        Ok(FeeEstimate { base_fee_per_gas, max_priority_fee_per_gas })
+    }
+}
+
+/// A [`GasOracle`] projecting the base fee of the block following the pending block.
+///
+/// The projection follows the base fee rules of EIP-1559 from the gas used by the pending block,
+/// with the parameters of Ethereum by default. This source does not estimate priority fees.
+pub struct PendingBlockOracle<P, T, N> {
+    provider: P,
+    base_fee_params: BaseFeeParams,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> fmt::Debug for PendingBlockOracle<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingBlockOracle")
+            .field("base_fee_params", &self.base_fee_params)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> PendingBlockOracle<P, T, N> {
+    /// Create a new oracle using the pending block of `provider`.
+    pub const fn new(provider: P) -> Self {
+        Self { provider, base_fee_params: BaseFeeParams::ethereum(), _pd: PhantomData }
+    }
+
+    /// Sets the base fee parameters of the chain, e.g. for OP stack chains.
+    pub const fn with_base_fee_params(mut self, base_fee_params: BaseFeeParams) -> Self {
+        self.base_fee_params = base_fee_params;
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<P, T, N> GasOracle for PendingBlockOracle<P, T, N>
+where
+    P: Provider<T, N> + 'static,
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn estimate(&self) -> TransportResult<FeeEstimate> {
+        let header = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Pending, false)
+            .await?
+            .ok_or(RpcError::NullResp)?
+            .header;
+        let base_fee_per_gas = header.base_fee_per_gas.map(|base_fee| {
+            self.base_fee_params.next_block_base_fee(header.gas_used, header.gas_limit, base_fee)
+        });
+        Ok(FeeEstimate { base_fee_per_gas, max_priority_fee_per_gas: None })
+    }
+}
+
+/// A [`GasOracle`] using the priority fee suggested by the node with `eth_maxPriorityFeePerGas`.
+///
+/// This source does not estimate base fees.
+pub struct MaxPriorityFeeOracle<P, T, N> {
+    provider: P,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P, T, N> fmt::Debug for MaxPriorityFeeOracle<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaxPriorityFeeOracle").finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> MaxPriorityFeeOracle<P, T, N> {
+    /// Create a new oracle using the suggestions of `provider`.
+    pub const fn new(provider: P) -> Self {
+        Self { provider, _pd: PhantomData }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<P, T, N> GasOracle for MaxPriorityFeeOracle<P, T, N>
+where
+    P: Provider<T, N> + 'static,
+    T: Transport + Clone,
+    N: Network,
+{
+    async fn estimate(&self) -> TransportResult<FeeEstimate> {
+        let max_priority_fee_per_gas = self.provider.get_max_priority_fee_per_gas().await?;
+        Ok(FeeEstimate {
+            base_fee_per_gas: None,
+            max_priority_fee_per_gas: Some(max_priority_fee_per_gas),
+        })
+    }
+}
+
+/// A [`GasOracle`] combining the estimates of several sources.
+///
+/// The sources are queried concurrently, and each fee is the median of the estimates of the
+/// sources that estimated it. Sources that fail are ignored, unless they all fail, in which case
+/// the error of the last one is returned.
+#[derive(Clone, Debug, Default)]
+pub struct MedianOracle {
+    sources: Vec<Arc<dyn GasOracle>>,
+}
+
+impl MedianOracle {
+    /// Create a new oracle without sources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a source of estimates.
+    pub fn with_source(mut self, source: impl GasOracle + 'static) -> Self {
+        self.sources.push(Arc::new(source));
+        self
+    }
+
+    /// Returns the number of sources.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns true if the oracle has no sources.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GasOracle for MedianOracle {
+    async fn estimate(&self) -> TransportResult<FeeEstimate> {
+        let results =
+            futures::future::join_all(self.sources.iter().map(|source| source.estimate())).await;
+
+        let mut base_fees = Vec::new();
+        let mut priority_fees = Vec::new();
+        let mut last_err = None;
+        let mut succeeded = false;
+        for result in results {
+            match result {
+                Ok(estimate) => {
+                    succeeded = true;
+                    base_fees.extend(estimate.base_fee_per_gas);
+                    priority_fees.extend(estimate.max_priority_fee_per_gas);
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+        if let Some(err) = last_err.filter(|_| !succeeded) {
+            return Err(err);
+        }
+
+        Ok(FeeEstimate {
+            base_fee_per_gas: median(base_fees),
+            max_priority_fee_per_gas: median(priority_fees),
+        })
+    }
+}
+
+/// Returns the median of `values`, if any.
+fn median(mut values: Vec<u128>) -> Option<u128> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let n = values.len();
+    Some(if n % 2 == 0 { (values[n / 2 - 1] + values[n / 2]) / 2 } else { values[n / 2] })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fillers::{GasFiller, TxFiller},
+        test_utils::mock_provider,
+        RootProvider, SendableTx,
+    };
+    use alloy_network::{Ethereum, TransactionBuilder};
+    use alloy_rpc_types::{Block, FeeHistory, TransactionRequest};
+    use alloy_transport::{BoxTransport, TransportErrorKind};
+
+    /// Returns a node whose next block has a base fee of 100, failing `eth_maxPriorityFeePerGas`
+    /// unless `max_priority_fee` is set.
+    fn node(max_priority_fee: bool) -> RootProvider<BoxTransport, Ethereum> {
+        mock_provider(move |method, params| {
+            Ok(match method {
+                "eth_feeHistory" => serde_json::json!(FeeHistory {
+                    base_fee_per_gas: vec![90, 100],
+                    reward: Some(vec![vec![1], vec![2], vec![6]]),
+                    ..Default::default()
+                }),
+                "eth_getBlockByNumber" => {
+                    assert!(params.contains("pending"));
+                    let mut block: Block = Block::default();
+                    // A full block increases the base fee by 12.5%.
+                    block.header.base_fee_per_gas = Some(96);
+                    block.header.gas_limit = 30_000_000;
+                    block.header.gas_used = 30_000_000;
+                    serde_json::json!(block)
+                }
+                "eth_maxPriorityFeePerGas" if max_priority_fee => serde_json::json!("0x4"),
+                "eth_maxPriorityFeePerGas" => {
+                    return Err(TransportErrorKind::custom_str("unavailable"))
+                }
+                method => unreachable!("{method}"),
+            })
+        })
+    }
+
+    fn oracle(node: RootProvider<BoxTransport, Ethereum>) -> MedianOracle {
+        MedianOracle::new()
+            .with_source(FeeHistoryOracle::new(node.clone()))
+            .with_source(PendingBlockOracle::new(node.clone()))
+            .with_source(MaxPriorityFeeOracle::new(node))
+    }
+
+    #[tokio::test]
+    async fn combines_sources() {
+        let node = node(true);
+        let estimate = FeeHistoryOracle::new(node.clone()).estimate().await.unwrap();
+        assert_eq!(
+            estimate,
+            FeeEstimate { base_fee_per_gas: Some(100), max_priority_fee_per_gas: Some(2) }
+        );
+        let estimate = PendingBlockOracle::new(node.clone()).estimate().await.unwrap();
+        assert_eq!(
+            estimate,
+            FeeEstimate { base_fee_per_gas: Some(108), max_priority_fee_per_gas: None }
+        );
+
+        let estimate = oracle(node.clone()).estimate().await.unwrap();
+        assert_eq!(
+            estimate,
+            FeeEstimate { base_fee_per_gas: Some(104), max_priority_fee_per_gas: Some(3) }
+        );
+        let fees = Eip1559Estimation { max_fee_per_gas: 211, max_priority_fee_per_gas: 3 };
+        assert_eq!(oracle(node.clone()).estimate_eip1559_fees().await.unwrap(), fees);
+
+        // Failing sources are ignored.
+        let estimate = oracle(self::node(false)).estimate().await.unwrap();
+        assert_eq!(estimate.max_priority_fee_per_gas, Some(2));
+        let failing = MedianOracle::new().with_source(MaxPriorityFeeOracle::new(self::node(false)));
+        assert!(failing.estimate().await.is_err());
+
+        // The oracle backs the fees filled by the gas filler.
+        let filler = GasFiller::default().with_oracle(oracle(node.clone()));
+        let tx = TransactionRequest::default().with_gas_limit(21_000);
+        let tx = TxFiller::<Ethereum>::prepare_and_fill(&filler, &node, SendableTx::Builder(tx))
+            .await
+            .unwrap();
+        let tx = tx.as_builder().unwrap();
+        assert_eq!(tx.max_fee_per_gas, Some(211));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(3));
+    }
+}
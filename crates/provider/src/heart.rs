@@ -2,7 +2,7 @@
 
 use crate::{Provider, RootProvider};
 use alloy_json_rpc::RpcError;
-use alloy_network::Network;
+use alloy_network::{FinalityPolicy, Network, ReceiptResponse};
 use alloy_primitives::B256;
 use alloy_rpc_types::Block;
 use alloy_transport::{utils::Spawnable, Transport, TransportErrorKind, TransportResult};
@@ -11,13 +11,22 @@ use std::{
     collections::{BTreeMap, HashMap},
     fmt,
     future::Future,
-    time::{Duration, Instant},
+    time::Duration,
 };
 use tokio::{
     select,
     sync::{mpsc, oneshot, watch},
 };
 
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{interval, sleep_until, Instant};
+// Timers of `std` and `tokio` are not available in the browser.
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::{
+    std::Instant,
+    tokio::{interval, sleep_until},
+};
+
 /// A builder for configuring a pending transaction watcher.
 ///
 /// # Examples
@@ -55,6 +64,7 @@ use tokio::{
 #[derive(Debug)]
 pub struct PendingTransactionBuilder<'a, T, N> {
     config: PendingTransactionConfig,
+    finality: Option<FinalityPolicy>,
     provider: &'a RootProvider<T, N>,
 }
 
@@ -69,7 +79,7 @@ impl<'a, T: Transport + Clone, N: Network> PendingTransactionBuilder<'a, T, N> {
         provider: &'a RootProvider<T, N>,
         config: PendingTransactionConfig,
     ) -> Self {
-        Self { config, provider }
+        Self { config, finality: None, provider }
     }
 
     /// Returns the inner configuration.
@@ -78,7 +88,7 @@ impl<'a, T: Transport + Clone, N: Network> PendingTransactionBuilder<'a, T, N> {
     }
 
     /// Consumes this builder, returning the inner configuration.
-    pub const fn into_inner(self) -> PendingTransactionConfig {
+    pub fn into_inner(self) -> PendingTransactionConfig {
         self.config
     }
 
@@ -88,7 +98,7 @@ impl<'a, T: Transport + Clone, N: Network> PendingTransactionBuilder<'a, T, N> {
     }
 
     /// Consumes this builder, returning the provider and the configuration.
-    pub const fn split(self) -> (&'a RootProvider<T, N>, PendingTransactionConfig) {
+    pub fn split(self) -> (&'a RootProvider<T, N>, PendingTransactionConfig) {
         (self.provider, self.config)
     }
 
@@ -127,6 +137,19 @@ impl<'a, T: Transport + Clone, N: Network> PendingTransactionBuilder<'a, T, N> {
         self
     }
 
+    /// Returns the finality policy used by [`get_final_receipt`](Self::get_final_receipt).
+    ///
+    /// Defaults to the policy of the network, see [`Network::finality_policy`].
+    pub fn finality(&self) -> FinalityPolicy {
+        self.finality.clone().unwrap_or_else(N::finality_policy)
+    }
+
+    /// Sets the finality policy used by [`get_final_receipt`](Self::get_final_receipt).
+    pub fn with_finality(mut self, finality: FinalityPolicy) -> Self {
+        self.finality = Some(finality);
+        self
+    }
+
     /// Returns the timeout.
     pub const fn timeout(&self) -> Option<Duration> {
         self.config.timeout()
@@ -185,7 +208,7 @@ impl<'a, T: Transport + Clone, N: Network> PendingTransactionBuilder<'a, T, N> {
 
         // FIXME: this is a hotfix to prevent a race condition where the heartbeat would miss the
         // block the tx was mined in
-        let mut interval = tokio::time::interval(self.provider.client().poll_interval());
+        let mut interval = interval(self.provider.client().poll_interval());
 
         loop {
             let mut confirmed = false;
@@ -209,6 +232,27 @@ impl<'a, T: Transport + Clone, N: Network> PendingTransactionBuilder<'a, T, N> {
             }
         }
     }
+
+    /// Waits for the transaction to be included in a block that is final under the
+    /// [finality policy](Self::finality), and then fetches its receipt.
+    ///
+    /// The timeout only applies to the inclusion of the transaction. If the transaction is
+    /// reorged out before its block is final, this waits for it to be included again.
+    pub async fn get_final_receipt(self) -> TransportResult<N::ReceiptResponse> {
+        let (provider, hash, policy) = (self.provider, *self.tx_hash(), self.finality());
+        self.get_receipt().await?;
+
+        let mut interval = interval(provider.client().poll_interval());
+        loop {
+            interval.tick().await;
+            // the transaction may have been reorged out, or into another block
+            let Some(receipt) = provider.get_transaction_receipt(hash).await? else { continue };
+            let Some(number) = receipt.block_number() else { continue };
+            if provider.get_final_block_number(&policy).await? >= number {
+                return Ok(receipt);
+            }
+        }
+    }
 }
 
 /// Configuration for watching a pending transaction.
@@ -515,7 +559,7 @@ impl<S: Stream<Item = Block> + Unpin + 'static> Heartbeat<S> {
         'shutdown: loop {
             {
                 let next_reap = self.next_reap();
-                let sleep = std::pin::pin!(tokio::time::sleep_until(next_reap.into()));
+                let sleep = std::pin::pin!(sleep_until(next_reap));
 
                 // We bias the select so that we always handle new messages
                 // before checking blocks, and reap timeouts are last.
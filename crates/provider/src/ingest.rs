@@ -0,0 +1,252 @@
+//! A batteries-included block ingestion pipeline for indexers.
+//!
+//! [`Ingest`] backfills blocks from a starting point up to the chain tip, then follows new blocks
+//! as they are produced. Progress is persisted through a [`SyncCursor`], so a restarted pipeline
+//! resumes where it left off, and reorgs are reported to the handler before the replacement blocks.
+
+use crate::{
+    cursor::{Checkpoint, CursorError, CursorUpdate, KvStore, SyncCursor},
+    Provider,
+};
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{BlockNumber, U64};
+use alloy_rpc_client::PollerBuilder;
+use alloy_rpc_types::{Block, Transaction};
+use alloy_transport::{Transport, TransportError};
+use futures::StreamExt;
+use std::{future::Future, marker::PhantomData, time::Duration};
+
+/// An event delivered to the [`Ingest`] handler.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IngestEvent<T = Transaction> {
+    /// A new canonical block.
+    Block(Box<Block<T>>),
+    /// A reorg: all data derived from blocks after the given checkpoint must be reverted.
+    ///
+    /// Replacement blocks follow as [`IngestEvent::Block`]s.
+    Reorg {
+        /// The last block that is still canonical.
+        last_valid: Checkpoint,
+    },
+}
+
+/// Error returned by [`Ingest::run`].
+#[derive(Debug, thiserror::Error)]
+pub enum IngestError<S, E> {
+    /// The cursor failed.
+    #[error(transparent)]
+    Cursor(#[from] CursorError<S>),
+    /// An RPC request failed.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// The handler failed.
+    #[error("handler failed: {0}")]
+    Handler(E),
+    /// A block returned by the node is missing its hash.
+    #[error("block {0} is missing its hash")]
+    PendingBlock(BlockNumber),
+    /// The reorg is deeper than the history recorded by the cursor.
+    #[error("reorg deeper than the recorded history")]
+    DeepReorg,
+    /// The chain tip poller stopped, e.g. because the client was dropped.
+    #[error("chain tip poller stopped")]
+    PollerStopped,
+}
+
+/// A block ingestion pipeline: backfill, then live.
+///
+/// Blocks are delivered to the handler in order, each exactly once unless a reorg is reported in
+/// between. A block is recorded in the cursor only after the handler returns successfully, so a
+/// block may be delivered again after a crash: handlers should be idempotent.
+///
+/// ```no_run
+/// use alloy_provider::{
+///     cursor::{MemoryKvStore, SyncCursor},
+///     ingest::{Ingest, IngestEvent},
+///     ProviderBuilder,
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse()?);
+/// let store = MemoryKvStore::new();
+/// let cursor = SyncCursor::new(&store, "blocks");
+///
+/// Ingest::new(&provider, cursor)
+///     .start_block(19_000_000)
+///     .confirmations(2)
+///     .run(|event| async move {
+///         match event {
+///             IngestEvent::Block(block) => println!("block {:?}", block.header.number),
+///             IngestEvent::Reorg { last_valid } => println!("revert after {}", last_valid.number),
+///         }
+///         Ok::<_, std::convert::Infallible>(())
+///     })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "ingest pipelines do nothing unless you `.run` them"]
+pub struct Ingest<P, S, T, N = Ethereum> {
+    provider: P,
+    cursor: SyncCursor<S>,
+    start_block: BlockNumber,
+    end_block: Option<BlockNumber>,
+    confirmations: u64,
+    poll_interval: Option<Duration>,
+    full_transactions: bool,
+    _phantom: PhantomData<(T, N)>,
+}
+
+impl<P, S, T, N> Ingest<P, S, T, N>
+where
+    P: Provider<T, N>,
+    S: KvStore,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new pipeline fetching blocks from `provider`, recording progress in `cursor`.
+    pub const fn new(provider: P, cursor: SyncCursor<S>) -> Self {
+        Self {
+            provider,
+            cursor,
+            start_block: 0,
+            end_block: None,
+            confirmations: 0,
+            poll_interval: None,
+            full_transactions: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the first block to ingest when the cursor is empty. Defaults to the genesis block.
+    pub const fn start_block(mut self, start_block: BlockNumber) -> Self {
+        self.start_block = start_block;
+        self
+    }
+
+    /// Stops the pipeline after ingesting the given block, instead of following the chain.
+    pub const fn end_block(mut self, end_block: BlockNumber) -> Self {
+        self.end_block = Some(end_block);
+        self
+    }
+
+    /// Sets the number of blocks to stay behind the chain tip. Defaults to 0.
+    ///
+    /// Staying behind the tip makes reorgs less likely to be reported.
+    pub const fn confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets the interval between chain tip polls. Defaults to the client's poll interval.
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = Some(poll_interval);
+        self
+    }
+
+    /// Fetches blocks with their full transactions instead of only their hashes.
+    pub const fn full_transactions(mut self) -> Self {
+        self.full_transactions = true;
+        self
+    }
+
+    /// Returns a reference to the cursor.
+    pub const fn cursor(&self) -> &SyncCursor<S> {
+        &self.cursor
+    }
+
+    /// Runs the pipeline, delivering events to `handler`.
+    ///
+    /// This only returns on error, or once the [end block](Self::end_block) is ingested.
+    pub async fn run<F, Fut, E>(self, mut handler: F) -> Result<(), IngestError<S::Error, E>>
+    where
+        F: FnMut(IngestEvent<N::TransactionResponse>) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+    {
+        let mut poller =
+            PollerBuilder::<T, (), U64>::new(self.provider.weak_client(), "eth_blockNumber", ());
+        if let Some(poll_interval) = self.poll_interval {
+            poller.set_poll_interval(poll_interval);
+        }
+        let mut tips = poller.into_stream();
+        let mut tip = None;
+
+        loop {
+            let next = self.cursor.next_block()?.unwrap_or(self.start_block);
+            if self.end_block.is_some_and(|end| next > end) {
+                return Ok(());
+            }
+
+            let target = tip.map(|tip: BlockNumber| tip.saturating_sub(self.confirmations));
+            if target.map_or(true, |target| next > target) {
+                let new_tip = tips.next().await.ok_or(IngestError::PollerStopped)?;
+                tip = Some(new_tip.to());
+                continue;
+            }
+
+            let Some(block) =
+                self.provider.get_block_by_number(next.into(), self.full_transactions).await?
+            else {
+                // The node is lagging behind its own tip, wait for the next poll.
+                debug!(number = next, "block not found");
+                tip = None;
+                continue;
+            };
+            let hash = block.header.hash.ok_or(IngestError::PendingBlock(next))?;
+            let parent_hash = block.header.parent_hash;
+
+            match self.cursor.check(next, hash, parent_hash)? {
+                CursorUpdate::Extended => {}
+                CursorUpdate::Replaced { .. } => unreachable!("blocks are ingested in order"),
+                CursorUpdate::RolledBack { to: Some(last_valid) } => {
+                    debug!(number = next, rollback = last_valid.number, "reorg detected");
+                    handler(IngestEvent::Reorg { last_valid })
+                        .await
+                        .map_err(IngestError::Handler)?;
+                    self.cursor.rewind(last_valid.number)?;
+                    continue;
+                }
+                CursorUpdate::RolledBack { to: None } => return Err(IngestError::DeepReorg),
+            }
+
+            trace!(number = next, "ingesting block");
+            handler(IngestEvent::Block(Box::new(block))).await.map_err(IngestError::Handler)?;
+            self.cursor.advance(next, hash, parent_hash)?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cursor::MemoryKvStore, ProviderBuilder};
+
+    #[tokio::test]
+    async fn backfill() {
+        let provider = ProviderBuilder::new().on_anvil();
+        let _: () = provider.client().request("anvil_mine", (U64::from(5),)).await.unwrap();
+
+        let store = MemoryKvStore::new();
+        let mut numbers = Vec::new();
+        Ingest::new(&provider, SyncCursor::new(&store, "blocks"))
+            .start_block(2)
+            .end_block(4)
+            .poll_interval(Duration::from_millis(10))
+            .run(|event| {
+                let IngestEvent::Block(block) = event else { panic!("unexpected reorg") };
+                numbers.push(block.header.number.unwrap());
+                async { Ok::<_, std::convert::Infallible>(()) }
+            })
+            .await
+            .unwrap();
+        assert_eq!(numbers, [2, 3, 4]);
+
+        // Resumes from the cursor.
+        let cursor = SyncCursor::new(&store, "blocks");
+        assert_eq!(cursor.next_block().unwrap(), Some(5));
+        let ingest = Ingest::new(&provider, cursor).end_block(5);
+        ingest.run(|_| async { Ok::<_, std::convert::Infallible>(()) }).await.unwrap();
+        assert_eq!(SyncCursor::new(&store, "blocks").next_block().unwrap(), Some(6));
+    }
+}
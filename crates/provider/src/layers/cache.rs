@@ -0,0 +1,571 @@
+//! Caching of responses that can no longer change.
+//!
+//! A [`CacheLayer`] memoizes the responses of requests whose result is immutable: requests by
+//! block hash, the chain ID, and requests at, or about transactions included in, a block that is
+//! final. A block is considered final according to a [`FinalityPolicy`], by default once it is
+//! [`DEFAULT_FINALITY_DEPTH`] blocks below the head of the chain. Requests at a block tag such
+//! as `latest` always reach the node.
+//!
+//! Entries are kept in memory with a least-recently-used eviction policy, and can be persisted to
+//! a [`KvStore`] so that they survive restarts.
+
+use crate::cursor::KvStore;
+use alloy_json_rpc::{Id, Request, RequestPacket, Response, ResponsePacket, ResponsePayload};
+use alloy_network::FinalityPolicy;
+use alloy_primitives::{keccak256, B256, U64};
+use alloy_transport::{TransportError, TransportFut};
+use lru::LruCache;
+use serde_json::{value::RawValue, Value};
+use std::{
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service, ServiceExt};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+// Timers of `std` are not available in the browser.
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
+/// The default number of blocks below the head after which a block is considered final.
+pub const DEFAULT_FINALITY_DEPTH: u64 = 64;
+
+/// The minimum interval between two requests for the latest final block.
+const HEAD_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The prefix of the keys of persisted entries.
+const STORE_PREFIX: &str = "rpc_cache/";
+
+/// The number of requests answered from, and missing in, a cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// The number of requests answered from the cache.
+    pub hits: u64,
+    /// The number of cacheable requests sent to the node.
+    pub misses: u64,
+}
+
+/// A [`Layer`] caching the responses of requests for immutable data.
+///
+/// Cloning a layer returns a handle to the same cache, so it can be shared by several clients of
+/// the same chain. Only single requests are cached, batches are forwarded as is.
+///
+/// ```no_run
+/// use alloy_provider::{layers::CacheLayer, ProviderBuilder};
+/// use alloy_rpc_client::ClientBuilder;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let cache = CacheLayer::new(10_000).with_finality_depth(128);
+/// let client = ClientBuilder::default().layer(cache.clone()).http("http://localhost:8545".parse()?);
+/// let provider = ProviderBuilder::new().on_client(client);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CacheLayer {
+    cache: Arc<Cache>,
+}
+
+impl CacheLayer {
+    /// Creates a new layer keeping at most `capacity` responses in memory.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            cache: Arc::new(Cache {
+                entries: Mutex::new(LruCache::new(capacity)),
+                store: None,
+                finality: FinalityPolicy::Confirmations(DEFAULT_FINALITY_DEPTH),
+                finalized: Mutex::new(Finalized { number: 0, refreshed_at: None }),
+                hits: AtomicU64::new(0),
+                misses: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Sets the number of blocks below the head after which a block is considered final.
+    ///
+    /// Defaults to [`DEFAULT_FINALITY_DEPTH`]. Must be called before the layer is shared.
+    pub fn with_finality_depth(self, depth: u64) -> Self {
+        self.with_finality(FinalityPolicy::Confirmations(depth))
+    }
+
+    /// Sets the policy deciding which blocks are final, such as the `finalized` block of the
+    /// node.
+    ///
+    /// Must be called before the layer is shared.
+    pub fn with_finality(mut self, finality: FinalityPolicy) -> Self {
+        self.cache_mut().finality = finality;
+        self
+    }
+
+    /// Persists the cached responses to `store`, and answers from it the requests missing in
+    /// memory.
+    ///
+    /// Errors of the store are logged, and treated as missing entries. Must be called before the
+    /// layer is shared.
+    pub fn with_store<S: KvStore + 'static>(mut self, store: S) -> Self {
+        self.cache_mut().store = Some(Box::new(store));
+        self
+    }
+
+    /// Returns the number of hits and misses of the cache so far.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.cache.hits.load(Ordering::Relaxed),
+            misses: self.cache.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Removes every response cached in memory. Persisted responses are kept.
+    pub fn clear(&self) {
+        self.cache.entries.lock().unwrap().clear();
+    }
+
+    fn cache_mut(&mut self) -> &mut Cache {
+        Arc::get_mut(&mut self.cache).expect("cache layer configured after being shared")
+    }
+}
+
+impl<S> Layer<S> for CacheLayer {
+    type Service = CacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CacheService { inner, cache: self.cache.clone() }
+    }
+}
+
+/// A transport caching the responses of requests for immutable data, created by a
+/// [`CacheLayer`].
+#[derive(Clone, Debug)]
+pub struct CacheService<S> {
+    inner: S,
+    cache: Arc<Cache>,
+}
+
+impl<S> Service<RequestPacket> for CacheService<S>
+where
+    S: Service<
+            RequestPacket,
+            Response = ResponsePacket,
+            Error = TransportError,
+            Future = TransportFut<'static>,
+        > + Clone
+        + Send
+        + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let RequestPacket::Single(single) = &req else { return self.inner.call(req) };
+        let raw_params = single.params().map_or("", RawValue::get);
+        let params: Vec<Value> = serde_json::from_str(raw_params).unwrap_or_default();
+        let policy = Policy::of(single.method(), &params);
+        if policy == Policy::Never {
+            return self.inner.call(req);
+        }
+
+        let key = keccak256([single.method().as_bytes(), b"/", raw_params.as_bytes()].concat());
+        let id = single.id().clone();
+        if let Some(result) = self.cache.get(&key) {
+            self.cache.hits.fetch_add(1, Ordering::Relaxed);
+            let res =
+                ResponsePacket::Single(Response { id, payload: ResponsePayload::Success(result) });
+            return Box::pin(async move { Ok(res) });
+        }
+        self.cache.misses.fetch_add(1, Ordering::Relaxed);
+
+        let cache = self.cache.clone();
+        let inner = self.inner.clone();
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let res = fut.await?;
+            let ResponsePacket::Single(Response {
+                payload: ResponsePayload::Success(result), ..
+            }) = &res
+            else {
+                return Ok(res);
+            };
+            if result.get() == "null" {
+                return Ok(res);
+            }
+            let block = match policy {
+                Policy::AtBlock(number) => Some(number),
+                Policy::IncludedBlock => match serde_json::from_str::<Included>(result.get()) {
+                    Ok(Included { block_number: Some(number) }) => Some(number.to()),
+                    _ => return Ok(res),
+                },
+                _ => None,
+            };
+            if let Some(number) = block {
+                if !cache.is_final(number, inner, id).await {
+                    return Ok(res);
+                }
+            }
+            cache.insert(key, result.clone());
+            Ok(res)
+        })
+    }
+}
+
+/// Whether the result of a request can be cached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Policy {
+    /// The result never changes.
+    Always,
+    /// The result never changes once the given block is final.
+    AtBlock(u64),
+    /// The result never changes once the block it was included in, as given by its
+    /// `blockNumber`, is final.
+    IncludedBlock,
+    /// The result may change.
+    Never,
+}
+
+impl Policy {
+    fn of(method: &str, params: &[Value]) -> Self {
+        match method {
+            "eth_chainId"
+            | "eth_getBlockByHash"
+            | "eth_getBlockTransactionCountByHash"
+            | "eth_getTransactionByBlockHashAndIndex"
+            | "eth_getUncleByBlockHashAndIndex" => Self::Always,
+            "eth_getBlockByNumber"
+            | "eth_getBlockReceipts"
+            | "eth_getBlockTransactionCountByNumber"
+            | "eth_getTransactionByBlockNumberAndIndex" => Self::at(params.first()),
+            "eth_getTransactionByHash" | "eth_getTransactionReceipt" => Self::IncludedBlock,
+            "eth_call"
+            | "eth_getBalance"
+            | "eth_getCode"
+            | "eth_getStorageAt"
+            | "eth_getTransactionCount"
+            | "eth_getProof" => Self::at(block_param(method, params)),
+            "eth_getLogs" => match params.first() {
+                Some(Value::Object(filter)) if filter.contains_key("blockHash") => Self::Always,
+                Some(Value::Object(filter)) => {
+                    let from = filter.get("fromBlock").and_then(Value::as_str);
+                    match (from, Self::at(filter.get("toBlock"))) {
+                        (Some(from), to @ Self::AtBlock(_))
+                            if from == "earliest" || parse_number(from).is_some() =>
+                        {
+                            to
+                        }
+                        _ => Self::Never,
+                    }
+                }
+                _ => Self::Never,
+            },
+            _ => Self::Never,
+        }
+    }
+
+    /// Returns the policy of a request at the given block parameter.
+    fn at(block: Option<&Value>) -> Self {
+        match block {
+            Some(Value::String(block)) => parse_number(block).map_or(Self::Never, Self::AtBlock),
+            Some(Value::Object(block)) if block.contains_key("blockHash") => Self::Always,
+            Some(Value::Object(block)) => block
+                .get("blockNumber")
+                .and_then(Value::as_str)
+                .and_then(parse_number)
+                .map_or(Self::Never, Self::AtBlock),
+            _ => Self::Never,
+        }
+    }
+}
+
+/// Returns the block parameter of a request reading the state at a block, if it has one.
+///
+/// The block is not always the last parameter: `eth_call` may be followed by state overrides, and
+/// an omitted block defaults to `latest`.
+pub(super) fn block_param<'a>(method: &str, params: &'a [Value]) -> Option<&'a Value> {
+    let index = match method {
+        "eth_getBalance"
+        | "eth_getCode"
+        | "eth_getTransactionCount"
+        | "eth_call"
+        | "eth_estimateGas"
+        | "eth_createAccessList"
+        | "debug_traceCall" => 1,
+        "eth_getStorageAt" | "eth_getProof" => 2,
+        _ => return None,
+    };
+    params.get(index)
+}
+
+/// Parses a hex block number, returning `None` for block tags.
+pub(super) fn parse_number(block: &str) -> Option<u64> {
+    u64::from_str_radix(block.strip_prefix("0x")?, 16).ok()
+}
+
+/// The block a transaction or receipt was included in.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Included {
+    block_number: Option<U64>,
+}
+
+/// The block number of a block returned by `eth_getBlockByNumber`.
+#[derive(serde::Deserialize)]
+struct BlockNumber {
+    number: Option<U64>,
+}
+
+/// The last known final block.
+#[derive(Debug)]
+struct Finalized {
+    number: u64,
+    refreshed_at: Option<Instant>,
+}
+
+struct Cache {
+    entries: Mutex<LruCache<B256, Box<RawValue>>>,
+    store: Option<Box<dyn ErasedStore>>,
+    finality: FinalityPolicy,
+    finalized: Mutex<Finalized>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl std::fmt::Debug for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Cache")
+            .field("len", &self.entries.lock().unwrap().len())
+            .field("persistent", &self.store.is_some())
+            .field("finality", &self.finality)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Cache {
+    fn get(&self, key: &B256) -> Option<Box<RawValue>> {
+        if let Some(result) = self.entries.lock().unwrap().get(key) {
+            return Some(result.clone());
+        }
+        let stored = self.store.as_ref()?.get(&store_key(key))?;
+        let result = String::from_utf8(stored).ok().and_then(|s| RawValue::from_string(s).ok())?;
+        self.entries.lock().unwrap().put(*key, result.clone());
+        Some(result)
+    }
+
+    fn insert(&self, key: B256, result: Box<RawValue>) {
+        if let Some(store) = &self.store {
+            store.put(&store_key(&key), result.get().as_bytes().to_vec());
+        }
+        self.entries.lock().unwrap().put(key, result);
+    }
+
+    /// Returns `true` if the block is final, requesting the latest final block from `inner` if
+    /// the last known one is not recent enough to tell.
+    ///
+    /// The request reuses `id`, the ID of the completed request whose result is being cached, so
+    /// it cannot collide with the requests of the client in flight.
+    async fn is_final<S>(&self, number: u64, inner: S, id: Id) -> bool
+    where
+        S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>,
+    {
+        {
+            let finalized = self.finalized.lock().unwrap();
+            if number <= finalized.number
+                || finalized.refreshed_at.is_some_and(|at| at.elapsed() < HEAD_REFRESH_INTERVAL)
+            {
+                return number <= finalized.number;
+            }
+        }
+
+        let Some(final_number) = self.fetch_final_block(inner, id).await else { return false };
+        let mut finalized = self.finalized.lock().unwrap();
+        finalized.number = finalized.number.max(final_number);
+        finalized.refreshed_at = Some(Instant::now());
+        number <= finalized.number
+    }
+
+    /// Requests the latest final block from `inner`.
+    async fn fetch_final_block<S>(&self, inner: S, id: Id) -> Option<u64>
+    where
+        S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>,
+    {
+        let tag = self.finality.block_tag();
+        let req = match tag {
+            Some(tag) => Request::new("eth_getBlockByNumber", id, (tag, false)).serialize(),
+            None => Request::new("eth_blockNumber", id, ()).serialize(),
+        };
+        let Ok(ResponsePacket::Single(res)) = inner.oneshot(RequestPacket::Single(req.ok()?)).await
+        else {
+            return None;
+        };
+        let ResponsePayload::Success(result) = res.payload else { return None };
+        match tag {
+            Some(_) => {
+                serde_json::from_str::<BlockNumber>(result.get()).ok()?.number.map(|n| n.to())
+            }
+            None => {
+                self.finality.final_block_at(serde_json::from_str::<U64>(result.get()).ok()?.to())
+            }
+        }
+    }
+}
+
+fn store_key(key: &B256) -> String {
+    format!("{STORE_PREFIX}{key:x}")
+}
+
+/// An object-safe [`KvStore`] with errors logged instead of returned.
+trait ErasedStore: Send + Sync {
+    fn get(&self, key: &str) -> Option<Vec<u8>>;
+
+    fn put(&self, key: &str, value: Vec<u8>);
+}
+
+impl<S: KvStore> ErasedStore for S {
+    fn get(&self, key: &str) -> Option<Vec<u8>> {
+        KvStore::get(self, key)
+            .map_err(|err| tracing::warn!(%err, key, "failed to read cached response"))
+            .ok()
+            .flatten()
+    }
+
+    fn put(&self, key: &str, value: Vec<u8>) {
+        if let Err(err) = KvStore::put(self, key, value) {
+            tracing::warn!(%err, key, "failed to persist cached response");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cursor::MemoryKvStore, test_utils::mock, Provider, ProviderBuilder};
+    use alloy_primitives::TxHash;
+    use alloy_rpc_client::ClientBuilder;
+    use alloy_rpc_types::BlockNumberOrTag;
+    use alloy_transport::BoxTransport;
+    use serde_json::json;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Returns a node at block 100, with block 90 finalized, answering every request, and the
+    /// number of requests it got.
+    fn node() -> (BoxTransport, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let service = mock(move |method, _| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            Ok(match method {
+                "eth_blockNumber" => json!("0x64"),
+                "eth_chainId" => json!("0x1"),
+                "eth_getBalance" => json!("0x2a"),
+                "eth_getBlockByNumber" => json!({ "number": "0x5a" }),
+                "eth_getTransactionReceipt" => json!(null),
+                _ => unreachable!(),
+            })
+        });
+        (service, requests)
+    }
+
+    #[tokio::test]
+    async fn caches_final_responses() {
+        let (service, requests) = node();
+        let cache = CacheLayer::new(16);
+        let client = ClientBuilder::default().layer(cache.clone()).transport(service, true);
+        let provider = ProviderBuilder::new().on_client(client);
+        let address = Default::default();
+
+        for _ in 0..2 {
+            assert_eq!(provider.get_chain_id().await.unwrap(), 1);
+            // Block 10 is final at head 100, block 90 is not.
+            let old = provider.get_balance(address).block_id(BlockNumberOrTag::Number(10).into());
+            assert_eq!(old.await.unwrap(), alloy_primitives::U256::from(42));
+            let recent =
+                provider.get_balance(address).block_id(BlockNumberOrTag::Number(90).into());
+            recent.await.unwrap();
+            provider.get_balance(address).await.unwrap();
+            // Missing receipts are not cached.
+            assert!(provider.get_transaction_receipt(TxHash::ZERO).await.unwrap().is_none());
+        }
+
+        // One head refresh, then the chain ID, the final balance, twice the recent and latest
+        // balances and the receipt.
+        assert_eq!(requests.load(Ordering::Relaxed), 1 + 2 + 2 * 3);
+        assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 6 });
+    }
+
+    #[tokio::test]
+    async fn follows_finalized_block() {
+        let (service, requests) = node();
+        let cache = CacheLayer::new(16).with_finality(FinalityPolicy::Finalized);
+        let client = ClientBuilder::default().layer(cache.clone()).transport(service, true);
+        let provider = ProviderBuilder::new().on_client(client);
+
+        for _ in 0..2 {
+            let at = |number| {
+                provider
+                    .get_balance(Default::default())
+                    .block_id(BlockNumberOrTag::Number(number).into())
+            };
+            at(90).await.unwrap();
+            at(91).await.unwrap();
+        }
+
+        // One finalized block refresh, the final balance and twice the balance at block 91.
+        assert_eq!(requests.load(Ordering::Relaxed), 1 + 1 + 2);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 3 });
+    }
+
+    #[tokio::test]
+    async fn persists_responses() {
+        let store = Arc::new(MemoryKvStore::new());
+        let (service, requests) = node();
+        let cache = CacheLayer::new(16).with_store(store.clone());
+        let mut service = cache.layer(service);
+        let req = || {
+            let req = Request::new("eth_chainId", Id::Number(1), ()).serialize().unwrap();
+            RequestPacket::Single(req)
+        };
+        service.call(req()).await.unwrap();
+
+        // A new cache over the same store answers without reaching the node.
+        let cache = CacheLayer::new(16).with_store(store);
+        let mut service = cache.layer(service.inner);
+        let ResponsePacket::Single(res) = service.call(req()).await.unwrap() else {
+            unreachable!()
+        };
+        assert_eq!(res.id, Id::Number(1));
+        assert_eq!(requests.load(Ordering::Relaxed), 1);
+        assert_eq!(cache.stats().hits, 1);
+    }
+
+    #[test]
+    fn policies() {
+        let at = |method, params: Value| Policy::of(method, params.as_array().unwrap());
+        assert_eq!(at("eth_getBlockByNumber", json!(["0x10", false])), Policy::AtBlock(16));
+        assert_eq!(at("eth_getBlockByNumber", json!(["latest", false])), Policy::Never);
+        assert_eq!(at("eth_call", json!([{}, {"blockHash": "0x00"}])), Policy::Always);
+        assert_eq!(at("eth_call", json!([{}, "0x1", {}])), Policy::AtBlock(1));
+        assert_eq!(
+            at("eth_getLogs", json!([{"fromBlock": "earliest", "toBlock": "0x5"}])),
+            Policy::AtBlock(5)
+        );
+        assert_eq!(at("eth_getLogs", json!([{"fromBlock": "0x1"}])), Policy::Never);
+        assert_eq!(at("eth_getStorageAt", json!(["0x00", "0x1", "0x2"])), Policy::AtBlock(2));
+        assert_eq!(
+            at("eth_getProof", json!(["0x00", ["0x1"], {"blockNumber": "0x3"}])),
+            Policy::AtBlock(3)
+        );
+        // An omitted block is `latest`, the last parameter is not a block.
+        assert_eq!(at("eth_getBalance", json!(["0x01"])), Policy::Never);
+        assert_eq!(at("eth_getStorageAt", json!(["0x00", "0x1"])), Policy::Never);
+        assert_eq!(at("eth_sendRawTransaction", json!(["0x00"])), Policy::Never);
+    }
+}
@@ -0,0 +1,421 @@
+//! Consistency checks of the responses of load-balanced endpoints.
+//!
+//! Public endpoints often spread requests over several nodes, which may lag behind each other, or
+//! briefly follow different forks. A [`ConsistencyLayer`] cross-checks the responses most exposed
+//! to this, and fails the requests whose responses are inconsistent with an
+//! [`InconsistentProvider`] error.
+
+use alloy_json_rpc::{Id, Request, RequestPacket, Response, ResponsePacket, ResponsePayload};
+use alloy_primitives::{B256, U64};
+use alloy_transport::{TransportError, TransportErrorKind, TransportFut};
+use serde_json::{value::RawValue, Value};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tower::{Layer, Service, ServiceExt};
+
+/// An inconsistency detected by a [`ConsistencyLayer`].
+///
+/// This is returned as a [custom](TransportErrorKind::Custom) transport error, see
+/// [`InconsistentProvider::from_error`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum InconsistentProvider {
+    /// The latest block number is behind the one of an earlier response.
+    #[error("latest block {got} is behind block {highest} returned earlier")]
+    BlockNumberRegressed {
+        /// The highest latest block number returned earlier.
+        highest: u64,
+        /// The latest block number of the response.
+        got: u64,
+    },
+    /// The block hash of a receipt is not the hash of the block at its number.
+    #[error(
+        "receipt of transaction {transaction_hash} is in block {receipt_block_hash}, but block \
+         {block_number} is {header_hash}"
+    )]
+    ReceiptBlockMismatch {
+        /// The hash of the transaction of the receipt.
+        transaction_hash: B256,
+        /// The number of the block of the receipt.
+        block_number: u64,
+        /// The block hash of the receipt.
+        receipt_block_hash: B256,
+        /// The hash of the block at the number of the receipt.
+        header_hash: B256,
+    },
+}
+
+impl InconsistentProvider {
+    /// Returns the inconsistency of a transport error returned by a [`ConsistencyLayer`], if it
+    /// is one.
+    pub fn from_error(err: &TransportError) -> Option<&Self> {
+        match err.as_transport_err()? {
+            TransportErrorKind::Custom(err) => err.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// A [`Layer`] checking that responses are consistent with each other.
+///
+/// Two checks are made:
+/// - the latest block number, returned by `eth_blockNumber` or by `eth_getBlockByNumber` for the
+///   `latest` tag, must not go back by more than the [tolerance](Self::with_tolerance), which is 0
+///   by default;
+/// - the block hash of a receipt returned by `eth_getTransactionReceipt` must be the hash of the
+///   block at its number, fetched with one more request. Receipts whose block is not known yet
+///   are not checked.
+///
+/// Cloning a layer returns a handle to the same latest block number. Only single requests are
+/// checked, batches are forwarded as is.
+///
+/// ```no_run
+/// use alloy_provider::{
+///     layers::{ConsistencyLayer, InconsistentProvider},
+///     Provider, ProviderBuilder,
+/// };
+/// use alloy_rpc_client::ClientBuilder;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let layer = ConsistencyLayer::new().with_tolerance(2);
+/// let client = ClientBuilder::default().layer(layer).http("https://eth.merkle.io".parse()?);
+/// let provider = ProviderBuilder::new().on_client(client);
+/// match provider.get_block_number().await {
+///     Ok(number) => println!("latest block {number}"),
+///     Err(err) => match InconsistentProvider::from_error(&err) {
+///         Some(inconsistency) => println!("inconsistent endpoint: {inconsistency}"),
+///         None => return Err(err.into()),
+///     },
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConsistencyLayer {
+    tolerance: u64,
+    check_receipts: bool,
+    highest: Arc<AtomicU64>,
+}
+
+impl Default for ConsistencyLayer {
+    fn default() -> Self {
+        Self { tolerance: 0, check_receipts: true, highest: Default::default() }
+    }
+}
+
+impl ConsistencyLayer {
+    /// Creates a new layer with all checks enabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of blocks the latest block number may go back by, e.g. when the nodes
+    /// behind the endpoint are known to lag by a block or two.
+    pub const fn with_tolerance(mut self, tolerance: u64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Enables or disables the check of the block hashes of receipts.
+    pub const fn with_receipt_checks(mut self, check_receipts: bool) -> Self {
+        self.check_receipts = check_receipts;
+        self
+    }
+
+    /// Returns the highest latest block number returned so far.
+    pub fn highest_block(&self) -> u64 {
+        self.highest.load(Ordering::Relaxed)
+    }
+}
+
+impl<S> Layer<S> for ConsistencyLayer {
+    type Service = ConsistencyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConsistencyService {
+            inner,
+            tolerance: self.tolerance,
+            check_receipts: self.check_receipts,
+            highest: self.highest.clone(),
+        }
+    }
+}
+
+/// A transport checking that responses are consistent with each other, created by a
+/// [`ConsistencyLayer`].
+#[derive(Clone, Debug)]
+pub struct ConsistencyService<S> {
+    inner: S,
+    tolerance: u64,
+    check_receipts: bool,
+    highest: Arc<AtomicU64>,
+}
+
+impl<S> Service<RequestPacket> for ConsistencyService<S>
+where
+    S: Service<
+            RequestPacket,
+            Response = ResponsePacket,
+            Error = TransportError,
+            Future = TransportFut<'static>,
+        > + Clone
+        + Send
+        + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let RequestPacket::Single(single) = &req else { return self.inner.call(req) };
+        let check = match single.method() {
+            "eth_blockNumber" => Check::BlockNumber,
+            "eth_getBlockByNumber" => {
+                let params: Vec<Value> =
+                    serde_json::from_str(single.params().map_or("", RawValue::get))
+                        .unwrap_or_default();
+                if !matches!(params.first(), Some(Value::String(tag)) if tag == "latest") {
+                    return self.inner.call(req);
+                }
+                Check::LatestBlock
+            }
+            "eth_getTransactionReceipt" if self.check_receipts => Check::Receipt,
+            _ => return self.inner.call(req),
+        };
+
+        let (tolerance, highest) = (self.tolerance, self.highest.clone());
+        let (inner, id) = (self.inner.clone(), single.id().clone());
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let res = fut.await?;
+            let ResponsePacket::Single(Response {
+                payload: ResponsePayload::Success(result), ..
+            }) = &res
+            else {
+                return Ok(res);
+            };
+            let latest = match check {
+                Check::BlockNumber => {
+                    serde_json::from_str::<U64>(result.get()).ok().map(|n| n.to())
+                }
+                Check::LatestBlock => serde_json::from_str::<Option<Header>>(result.get())
+                    .ok()
+                    .flatten()
+                    .and_then(|header| header.number)
+                    .map(|n| n.to()),
+                Check::Receipt => {
+                    check_receipt(result, inner, id).await?;
+                    None
+                }
+            };
+            if let Some(got) = latest {
+                let previous = highest.fetch_max(got, Ordering::Relaxed);
+                if got.saturating_add(tolerance) < previous {
+                    debug!(got, highest = previous, "latest block number regressed");
+                    return Err(TransportErrorKind::custom(
+                        InconsistentProvider::BlockNumberRegressed { highest: previous, got },
+                    ));
+                }
+            }
+            Ok(res)
+        })
+    }
+}
+
+/// The check of a response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Check {
+    /// The latest block number, returned by `eth_blockNumber`.
+    BlockNumber,
+    /// The latest block, returned by `eth_getBlockByNumber`.
+    LatestBlock,
+    /// A receipt, returned by `eth_getTransactionReceipt`.
+    Receipt,
+}
+
+/// The block a receipt was included in.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Included {
+    transaction_hash: B256,
+    block_hash: Option<B256>,
+    block_number: Option<U64>,
+}
+
+/// The fields of a block checked against other responses.
+#[derive(serde::Deserialize)]
+struct Header {
+    hash: Option<B256>,
+    number: Option<U64>,
+}
+
+/// Checks that the block hash of a receipt is the hash of the block at its number, requested
+/// from `inner` with `id`, the ID of the completed receipt request.
+///
+/// The receipt is left unchecked if its block cannot be fetched.
+async fn check_receipt<S>(result: &RawValue, inner: S, id: Id) -> Result<(), TransportError>
+where
+    S: Service<RequestPacket, Response = ResponsePacket, Error = TransportError>,
+{
+    let Ok(Some(Included {
+        transaction_hash,
+        block_hash: Some(receipt_block_hash),
+        block_number: Some(block_number),
+    })) = serde_json::from_str::<Option<Included>>(result.get())
+    else {
+        return Ok(());
+    };
+
+    let Ok(req) = Request::new("eth_getBlockByNumber", id, (block_number, false)).serialize()
+    else {
+        return Ok(());
+    };
+    let res = match inner.oneshot(RequestPacket::Single(req)).await {
+        Ok(ResponsePacket::Single(res)) => res,
+        Ok(ResponsePacket::Batch(_)) => return Ok(()),
+        Err(err) => {
+            debug!(%transaction_hash, %block_number, %err, "failed to fetch block of receipt");
+            return Ok(());
+        }
+    };
+    let ResponsePayload::Success(header) = res.payload else { return Ok(()) };
+    let Ok(Some(Header { hash: Some(header_hash), .. })) =
+        serde_json::from_str::<Option<Header>>(header.get())
+    else {
+        trace!(%transaction_hash, %block_number, "block of receipt not known yet");
+        return Ok(());
+    };
+    if header_hash != receipt_block_hash {
+        debug!(%transaction_hash, %receipt_block_hash, %header_hash, "receipt block mismatch");
+        return Err(TransportErrorKind::custom(InconsistentProvider::ReceiptBlockMismatch {
+            transaction_hash,
+            block_number: block_number.to(),
+            receipt_block_hash,
+            header_hash,
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layers::cache::parse_number, test_utils::mock, Provider, ProviderBuilder};
+    use alloy_rpc_client::ClientBuilder;
+    use alloy_transport::BoxTransport;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    /// Returns a node answering with the given block numbers in turn, and with a receipt in
+    /// block 5 with the hash `receipt_block_hash`, whose header has the hash `0x11..11`. The
+    /// header of block 6 is unknown, and requesting block 7 fails.
+    fn node(block_numbers: Vec<u64>, receipt_block_hash: B256) -> BoxTransport {
+        let block_numbers = Arc::new(Mutex::new(block_numbers.into_iter()));
+        mock(move |method, params| {
+            let params: Vec<Value> = serde_json::from_str(params).unwrap_or_default();
+            Ok(match method {
+                "eth_blockNumber" => {
+                    json!(U64::from(block_numbers.lock().unwrap().next().unwrap()))
+                }
+                "eth_getBlockByNumber" => {
+                    let number = parse_number(params[0].as_str().unwrap());
+                    match number {
+                        Some(5) => json!({ "hash": B256::repeat_byte(0x11), "number": "0x5" }),
+                        Some(7) => return Err(TransportErrorKind::backend_gone()),
+                        _ => Value::Null,
+                    }
+                }
+                "eth_getTransactionReceipt" => {
+                    let block = if params[0] == json!(B256::ZERO) {
+                        "0x5"
+                    } else if params[0] == json!(B256::repeat_byte(2)) {
+                        "0x7"
+                    } else {
+                        "0x6"
+                    };
+                    json!({
+                        "transactionHash": params[0],
+                        "blockHash": receipt_block_hash,
+                        "blockNumber": block,
+                    })
+                }
+                method => unreachable!("unexpected method {method}"),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn detects_block_number_regressions() {
+        let layer = ConsistencyLayer::new();
+        let client = ClientBuilder::default()
+            .layer(layer.clone())
+            .transport(node(vec![10, 12, 11], B256::ZERO), true);
+        let provider = ProviderBuilder::new().on_client(client);
+        assert_eq!(provider.get_block_number().await.unwrap(), 10);
+        assert_eq!(provider.get_block_number().await.unwrap(), 12);
+        let err = provider.get_block_number().await.unwrap_err();
+        assert_eq!(
+            InconsistentProvider::from_error(&err),
+            Some(&InconsistentProvider::BlockNumberRegressed { highest: 12, got: 11 })
+        );
+        assert_eq!(layer.highest_block(), 12);
+
+        let layer = ConsistencyLayer::new().with_tolerance(1);
+        let client =
+            ClientBuilder::default().layer(layer).transport(node(vec![12, 11], B256::ZERO), true);
+        let provider = ProviderBuilder::new().on_client(client);
+        provider.get_block_number().await.unwrap();
+        provider.get_block_number().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn detects_receipt_block_mismatches() {
+        let receipt = |receipt_block_hash, checks| {
+            let layer = ConsistencyLayer::new().with_receipt_checks(checks);
+            let client = ClientBuilder::default()
+                .layer(layer)
+                .transport(node(Vec::new(), receipt_block_hash), true);
+            async move { client.request::<_, Value>("eth_getTransactionReceipt", (B256::ZERO,)).await }
+        };
+        receipt(B256::repeat_byte(0x11), true).await.unwrap();
+        receipt(B256::repeat_byte(0x22), false).await.unwrap();
+        let err = receipt(B256::repeat_byte(0x22), true).await.unwrap_err();
+        assert_eq!(
+            InconsistentProvider::from_error(&err),
+            Some(&InconsistentProvider::ReceiptBlockMismatch {
+                transaction_hash: B256::ZERO,
+                block_number: 5,
+                receipt_block_hash: B256::repeat_byte(0x22),
+                header_hash: B256::repeat_byte(0x11),
+            })
+        );
+
+        // The block of the receipt is not known, so the receipt cannot be checked.
+        let layer = ConsistencyLayer::new();
+        let client = ClientBuilder::default()
+            .layer(layer)
+            .transport(node(Vec::new(), B256::repeat_byte(0x22)), true);
+        client
+            .request::<_, Value>("eth_getTransactionReceipt", (B256::repeat_byte(1),))
+            .await
+            .unwrap();
+
+        // Nor can it be if fetching its block fails.
+        let layer = ConsistencyLayer::new();
+        let client = ClientBuilder::default()
+            .layer(layer)
+            .transport(node(Vec::new(), B256::repeat_byte(0x22)), true);
+        client
+            .request::<_, Value>("eth_getTransactionReceipt", (B256::repeat_byte(2),))
+            .await
+            .unwrap();
+    }
+}
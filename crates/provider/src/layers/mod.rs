@@ -1,8 +1,19 @@
 //! Useful layer implementations for the provider. Currently this
 //! module contains the `AnvilLayer` and `AnvilProvider` types, when the anvil
-//! feature is enabled.
+//! feature is enabled, the [`CacheLayer`] caching responses of the RPC client, the
+//! [`PruningLayer`] routing requests for pruned data to archive endpoints, and the
+//! [`ConsistencyLayer`] checking the responses of load-balanced endpoints.
 
 #[cfg(any(test, feature = "anvil"))]
 mod anvil;
 #[cfg(any(test, feature = "anvil"))]
 pub use anvil::{AnvilLayer, AnvilProvider};
+
+mod cache;
+pub use cache::{CacheLayer, CacheService, CacheStats, DEFAULT_FINALITY_DEPTH};
+
+mod consistency;
+pub use consistency::{ConsistencyLayer, ConsistencyService, InconsistentProvider};
+
+mod pruning;
+pub use pruning::{DataKind, NodeCapabilities, PruningLayer, PruningService};
@@ -0,0 +1,405 @@
+//! Awareness of the data pruned by a node.
+//!
+//! Full nodes keep the state of recent blocks only, and may also drop old block bodies, receipts
+//! and logs. Requests for pruned data fail with errors such as `missing trie node`. A
+//! [`PruningLayer`] detects these errors, records the earliest block the node still has data for,
+//! and sends the requests for older blocks to archive endpoints instead.
+
+use super::cache::{block_param, parse_number};
+use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, ResponsePayload};
+use alloy_transport::{BoxTransport, TransportError, TransportFut};
+use serde_json::{value::RawValue, Value};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+use tower::{Layer, Service, ServiceExt};
+
+/// The messages of the errors of requests for pruned data, in lowercase.
+const PRUNED_ERRORS: &[&str] = &[
+    "missing trie node",
+    "historical state",
+    "state is not available",
+    "state not available",
+    "pruned history",
+    "history has been pruned",
+    "history expired",
+];
+
+/// The kind of data a request reads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DataKind {
+    /// The state of accounts and storage at a block, read e.g. by `eth_getBalance` and
+    /// `eth_call`.
+    State,
+    /// The blocks, receipts and logs of the chain.
+    History,
+}
+
+/// The data available on a node, as detected by a [`PruningLayer`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NodeCapabilities {
+    /// The earliest block whose state may be available, or 0 if no pruned state was detected.
+    pub earliest_state: u64,
+    /// The earliest block whose history may be available, or 0 if no pruned history was
+    /// detected.
+    pub earliest_history: u64,
+}
+
+impl NodeCapabilities {
+    /// Returns the earliest block whose data of the given kind may be available.
+    pub const fn earliest(&self, kind: DataKind) -> u64 {
+        match kind {
+            DataKind::State => self.earliest_state,
+            DataKind::History => self.earliest_history,
+        }
+    }
+
+    /// Returns `false` if the data of the given kind at `block` is known to be pruned.
+    pub const fn is_available(&self, kind: DataKind, block: u64) -> bool {
+        block >= self.earliest(kind)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Detected {
+    earliest_state: AtomicU64,
+    earliest_history: AtomicU64,
+}
+
+impl Detected {
+    const fn earliest(&self, kind: DataKind) -> &AtomicU64 {
+        match kind {
+            DataKind::State => &self.earliest_state,
+            DataKind::History => &self.earliest_history,
+        }
+    }
+
+    fn capabilities(&self) -> NodeCapabilities {
+        NodeCapabilities {
+            earliest_state: self.earliest_state.load(Ordering::Relaxed),
+            earliest_history: self.earliest_history.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A [`Layer`] routing the requests for data pruned by the node to archive endpoints.
+///
+/// When a request for a block fails because the node has pruned its data, the block is recorded
+/// as pruned, and the request is retried on the archive endpoints, in order. Later requests for
+/// a pruned block are sent to the archive endpoints directly. Without archive endpoints, errors
+/// are returned as is, and the detected ranges are only exposed by
+/// [`capabilities`](Self::capabilities).
+///
+/// Cloning a layer returns a handle to the same detected ranges. Only single requests are
+/// routed, batches are forwarded as is.
+///
+/// ```no_run
+/// use alloy_provider::{layers::PruningLayer, ProviderBuilder};
+/// use alloy_rpc_client::ClientBuilder;
+/// use alloy_transport::Transport;
+/// use alloy_transport_http::ReqwestTransport;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let archive = ReqwestTransport::new("http://archive:8545".parse()?).boxed();
+/// let pruning = PruningLayer::new().with_archive(archive);
+/// let client =
+///     ClientBuilder::default().layer(pruning.clone()).http("http://localhost:8545".parse()?);
+/// let provider = ProviderBuilder::new().on_client(client);
+///
+/// // Later, the blocks found to be pruned by the node.
+/// let capabilities = pruning.capabilities();
+/// println!("state available from block {}", capabilities.earliest_state);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PruningLayer {
+    archives: Arc<[BoxTransport]>,
+    detected: Arc<Detected>,
+}
+
+impl PruningLayer {
+    /// Creates a new layer without archive endpoints.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an archive endpoint, tried after the previous ones.
+    pub fn with_archive(mut self, archive: BoxTransport) -> Self {
+        let mut archives = self.archives.to_vec();
+        archives.push(archive);
+        self.archives = archives.into();
+        self
+    }
+
+    /// Sets the earliest block whose data of the given kind is known to be available, as
+    /// configured on the node.
+    ///
+    /// Detection only ever raises the earliest block, so this prevents a failed request per
+    /// pruned range.
+    pub fn with_earliest(self, kind: DataKind, block: u64) -> Self {
+        self.detected.earliest(kind).fetch_max(block, Ordering::Relaxed);
+        self
+    }
+
+    /// Returns the data available on the node, as detected so far.
+    pub fn capabilities(&self) -> NodeCapabilities {
+        self.detected.capabilities()
+    }
+}
+
+impl<S> Layer<S> for PruningLayer {
+    type Service = PruningService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PruningService { inner, archives: self.archives.clone(), detected: self.detected.clone() }
+    }
+}
+
+/// A transport routing the requests for data pruned by the node to archive endpoints, created by
+/// a [`PruningLayer`].
+#[derive(Clone, Debug)]
+pub struct PruningService<S> {
+    inner: S,
+    archives: Arc<[BoxTransport]>,
+    detected: Arc<Detected>,
+}
+
+impl<S> PruningService<S> {
+    /// Returns the data available on the node, as detected so far.
+    pub fn capabilities(&self) -> NodeCapabilities {
+        self.detected.capabilities()
+    }
+}
+
+impl<S> Service<RequestPacket> for PruningService<S>
+where
+    S: Service<
+            RequestPacket,
+            Response = ResponsePacket,
+            Error = TransportError,
+            Future = TransportFut<'static>,
+        > + Send
+        + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let RequestPacket::Single(single) = &req else { return self.inner.call(req) };
+        let params: Vec<Value> =
+            serde_json::from_str(single.params().map_or("", RawValue::get)).unwrap_or_default();
+        let target = requested_block(single.method(), &params);
+
+        let archives = self.archives.clone();
+        if let Some((kind, block)) = target {
+            let earliest = self.detected.earliest(kind).load(Ordering::Relaxed);
+            if block < earliest && !archives.is_empty() {
+                return Box::pin(call_archives(archives, req));
+            }
+        }
+
+        let detected = self.detected.clone();
+        let fut = self.inner.call(req.clone());
+        Box::pin(async move {
+            let res = fut.await?;
+            if !is_pruned(&res) {
+                return Ok(res);
+            }
+            if let Some((kind, block)) = target {
+                let previous = detected.earliest(kind).fetch_max(block + 1, Ordering::Relaxed);
+                if previous <= block {
+                    debug!(?kind, block, "detected pruned data");
+                }
+            }
+            if archives.is_empty() {
+                return Ok(res);
+            }
+            call_archives(archives, req).await
+        })
+    }
+}
+
+/// Sends a request to the archive endpoints in order, until one of them has the data.
+async fn call_archives(
+    archives: Arc<[BoxTransport]>,
+    req: RequestPacket,
+) -> Result<ResponsePacket, TransportError> {
+    let mut last = None;
+    for archive in archives.iter() {
+        let res = archive.clone().oneshot(req.clone()).await;
+        if matches!(&res, Ok(res) if !is_pruned(res)) {
+            return res;
+        }
+        last = Some(res);
+    }
+    last.expect("no archive endpoints")
+}
+
+/// Returns `true` if the response is an error for pruned data.
+fn is_pruned(res: &ResponsePacket) -> bool {
+    let ResponsePacket::Single(Response { payload: ResponsePayload::Failure(err), .. }) = res
+    else {
+        return false;
+    };
+    let message = err.message.to_lowercase();
+    PRUNED_ERRORS.iter().any(|pruned| message.contains(pruned))
+}
+
+/// Returns the kind of data read by a request, and the earliest block it reads it at, if known.
+fn requested_block(method: &str, params: &[Value]) -> Option<(DataKind, u64)> {
+    let (kind, block) = match method {
+        "eth_getBalance"
+        | "eth_getCode"
+        | "eth_getStorageAt"
+        | "eth_getTransactionCount"
+        | "eth_getProof"
+        | "eth_call"
+        | "eth_estimateGas"
+        | "eth_createAccessList"
+        | "debug_traceCall" => (DataKind::State, block_param(method, params)),
+        "debug_traceBlockByNumber" | "trace_replayBlockTransactions" => {
+            (DataKind::State, params.first())
+        }
+        "eth_getBlockByNumber"
+        | "eth_getBlockReceipts"
+        | "eth_getBlockTransactionCountByNumber"
+        | "eth_getTransactionByBlockNumberAndIndex"
+        | "trace_block" => (DataKind::History, params.first()),
+        "eth_getLogs" => match params.first() {
+            Some(Value::Object(filter)) => (DataKind::History, filter.get("fromBlock")),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    block_number(block?).map(|block| (kind, block))
+}
+
+/// Returns the number of a block parameter, or `None` for block hashes and tags other than
+/// `earliest`.
+fn block_number(block: &Value) -> Option<u64> {
+    let number = match block {
+        Value::String(block) => block,
+        Value::Object(block) => block.get("blockNumber")?.as_str()?,
+        _ => return None,
+    };
+    if number == "earliest" {
+        return Some(0);
+    }
+    parse_number(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{error_resp, mock},
+        Provider, ProviderBuilder,
+    };
+    use alloy_rpc_client::ClientBuilder;
+    use alloy_rpc_types::BlockNumberOrTag;
+    use serde_json::json;
+    use std::sync::atomic::AtomicUsize;
+
+    /// Returns a node answering balance requests, which has pruned the state before block
+    /// `earliest_state`, and the number of requests it got.
+    fn node(earliest_state: u64) -> (BoxTransport, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let service = mock(move |method, params| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            assert_eq!(method, "eth_getBalance");
+            let params: Vec<Value> = serde_json::from_str(params).unwrap();
+            let block = block_number(&params[1]).unwrap_or(u64::MAX);
+            if block < earliest_state {
+                Err(error_resp(-32000, "missing trie node 4a2b (path ) <nil>"))
+            } else {
+                Ok(json!("0x2a"))
+            }
+        });
+        (service, requests)
+    }
+
+    #[tokio::test]
+    async fn routes_pruned_requests_to_archives() {
+        let (full, full_requests) = node(100);
+        let (archive, archive_requests) = node(0);
+        let pruning = PruningLayer::new().with_archive(archive);
+        let client = ClientBuilder::default().layer(pruning.clone()).transport(full, true);
+        let provider = ProviderBuilder::new().on_client(client);
+        let balance_at = |number| {
+            provider
+                .get_balance(Default::default())
+                .block_id(BlockNumberOrTag::Number(number).into())
+        };
+
+        // The first request fails on the full node, and is retried on the archive.
+        assert_eq!(balance_at(10).await.unwrap(), alloy_primitives::U256::from(42));
+        assert_eq!(pruning.capabilities().earliest_state, 11);
+        assert!(!pruning.capabilities().is_available(DataKind::State, 10));
+        assert_eq!(pruning.capabilities().earliest_history, 0);
+
+        // Older blocks now go to the archive directly, others to the full node.
+        balance_at(5).await.unwrap();
+        balance_at(150).await.unwrap();
+        provider.get_balance(Default::default()).await.unwrap();
+        assert_eq!(full_requests.load(Ordering::Relaxed), 3);
+        assert_eq!(archive_requests.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn detects_without_archives() {
+        let (full, _) = node(100);
+        let pruning = PruningLayer::new().with_earliest(DataKind::History, 7);
+        let client = ClientBuilder::default().layer(pruning.clone()).transport(full, true);
+        let provider = ProviderBuilder::new().on_client(client);
+
+        let at = BlockNumberOrTag::Number(50).into();
+        let err = provider.get_balance(Default::default()).block_id(at).await.unwrap_err();
+        assert!(err.to_string().contains("missing trie node"), "{err}");
+        assert_eq!(
+            pruning.capabilities(),
+            NodeCapabilities { earliest_state: 51, earliest_history: 7 }
+        );
+    }
+
+    #[test]
+    fn requested_blocks() {
+        let at = |method, params: Value| requested_block(method, params.as_array().unwrap());
+        assert_eq!(at("eth_call", json!([{}, "0x10"])), Some((DataKind::State, 16)));
+        assert_eq!(at("eth_getCode", json!(["0x00", "latest"])), None);
+        assert_eq!(
+            at("eth_getBlockByNumber", json!(["earliest", false])),
+            Some((DataKind::History, 0))
+        );
+        assert_eq!(
+            at("eth_getLogs", json!([{"fromBlock": "0x5", "toBlock": "latest"}])),
+            Some((DataKind::History, 5))
+        );
+        assert_eq!(
+            at("eth_getProof", json!(["0x00", [], {"blockNumber": "0x1"}])),
+            Some((DataKind::State, 1))
+        );
+        assert_eq!(at("eth_getTransactionReceipt", json!(["0x00"])), None);
+        // The block is not the last parameter, or is omitted.
+        assert_eq!(
+            at("eth_call", json!([{}, "0x10", {"0x00": {"balance": "0x1"}}])),
+            Some((DataKind::State, 16))
+        );
+        assert_eq!(
+            at("eth_getStorageAt", json!(["0x00", "0x01", "0x2"])),
+            Some((DataKind::State, 2))
+        );
+        assert_eq!(at("eth_getBalance", json!(["0x01"])), None);
+        assert_eq!(at("eth_getStorageAt", json!(["0x00", "0x01"])), None);
+    }
+}
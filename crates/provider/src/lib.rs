@@ -40,20 +40,54 @@ pub use builder::{Identity, ProviderBuilder, ProviderLayer, Stack};
 pub mod ext;
 
 pub mod fillers;
+
+pub mod gas_oracle;
+
 pub mod layers;
 
 mod chain;
 
+pub mod config;
+
+pub mod cursor;
+
+pub mod backfill;
+
+pub mod ingest;
+
+pub mod light;
+
+#[cfg(feature = "log-cache")]
+pub mod log_cache;
+
+pub mod log_filter;
+
+pub mod policy;
+
+pub mod reorg;
+
+#[cfg(feature = "replay")]
+pub mod replay;
+
+pub mod storage;
+
+pub mod stream;
+
+pub mod verify;
+
 mod heart;
 pub use heart::{PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig};
 
 mod provider;
 pub use provider::{
-    EthCall, FilterPollerBuilder, Provider, RootProvider, RpcWithBlock, SendableTx, TraceCallList,
-    WalletProvider,
+    Broadcast, CancelOutcome, DynProvider, EthCall, FilterPollerBuilder, NodeWallet, Provider,
+    RootProvider, RpcWithBlock, SendableTx, TraceCallList, WalletProvider,
 };
 
 pub mod utils;
 
+#[cfg(test)]
+mod test_utils;
+
 #[doc(no_inline)]
 pub use alloy_network::{self as network, Network};
@@ -0,0 +1,177 @@
+//! Light verification of block headers.
+//!
+//! Starting from a trusted header, e.g. one obtained from a trusted node or checkpoint, the
+//! headers of its ancestors can be fetched from any node and verified without trusting it: each
+//! header is recomputed from its fields, and must hash to the parent hash of its child.
+//!
+//! ```no_run
+//! use alloy_provider::{light::verify_header_chain, ProviderBuilder};
+//!
+//! # async fn example(trusted: alloy_consensus::Header) -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = ProviderBuilder::new().on_http("https://eth.merkle.io".parse()?);
+//! let chain = verify_header_chain(&provider, trusted, 64).await?;
+//! for header in &chain {
+//!     println!("block {} has hash {}", header.number, header.hash());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Provider;
+use alloy_consensus::{Header, Sealable, Sealed};
+use alloy_network::Network;
+use alloy_primitives::{BlockNumber, B256};
+use alloy_rpc_types::ConversionError;
+use alloy_transport::{Transport, TransportError};
+
+/// Error returned by [`verify_header_chain`].
+#[derive(Debug, thiserror::Error)]
+pub enum HeaderChainError {
+    /// An RPC request failed.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// The node does not know an ancestor of the trusted header.
+    #[error("block {0} not found")]
+    MissingBlock(B256),
+    /// The header returned by the node cannot be converted to a consensus header.
+    #[error("invalid header for block {hash}: {source}")]
+    InvalidHeader {
+        /// The hash of the requested block.
+        hash: B256,
+        /// The conversion error.
+        #[source]
+        source: ConversionError,
+    },
+    /// The header returned by the node does not hash to the parent hash of its child.
+    #[error("header returned for block {expected} hashes to {actual}")]
+    HashMismatch {
+        /// The hash of the requested block.
+        expected: B256,
+        /// The hash of the returned header.
+        actual: B256,
+    },
+    /// The header returned by the node does not have the number preceding its child.
+    #[error("block {hash} has number {actual}, expected {expected}")]
+    NumberMismatch {
+        /// The hash of the block.
+        hash: B256,
+        /// The number preceding the number of its child.
+        expected: BlockNumber,
+        /// The number of the returned header.
+        actual: BlockNumber,
+    },
+}
+
+/// Fetches and verifies the headers of up to `count` ancestors of the `trusted` header, walking
+/// back from its parent with `eth_getBlockByHash`.
+///
+/// Returns the verified headers from the newest to the oldest, starting with the trusted header,
+/// and ending early at the genesis block.
+pub async fn verify_header_chain<P, T, N>(
+    provider: &P,
+    trusted: Header,
+    count: u64,
+) -> Result<Vec<Sealed<Header>>, HeaderChainError>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let mut chain = vec![trusted.seal_slow()];
+    for _ in 0..count {
+        let child = chain.last().expect("the chain starts with the trusted header");
+        if child.number == 0 {
+            break;
+        }
+        let (hash, number) = (child.parent_hash, child.number - 1);
+
+        let block = provider
+            .get_block_by_hash(hash, false)
+            .await?
+            .ok_or(HeaderChainError::MissingBlock(hash))?;
+        let header = Header::try_from(block.header)
+            .map_err(|source| HeaderChainError::InvalidHeader { hash, source })?
+            .seal_slow();
+        if header.hash() != hash {
+            return Err(HeaderChainError::HashMismatch { expected: hash, actual: header.hash() });
+        }
+        if header.number != number {
+            return Err(HeaderChainError::NumberMismatch {
+                hash,
+                expected: number,
+                actual: header.number,
+            });
+        }
+        chain.push(header);
+    }
+    Ok(chain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::mock_provider, RootProvider};
+    use alloy_rpc_types::Block;
+    use alloy_transport::BoxTransport;
+    use std::{collections::HashMap, sync::Arc};
+
+    /// Returns a chain of `len` RPC headers, from the genesis block, along with their hashes.
+    fn headers(len: u64) -> Vec<(B256, alloy_rpc_types::Header)> {
+        let mut parent_hash = B256::ZERO;
+        (0..len)
+            .map(|number| {
+                let header = alloy_rpc_types::Header {
+                    parent_hash,
+                    number: Some(number),
+                    timestamp: number * 12,
+                    ..Default::default()
+                };
+                parent_hash = header.hash_slow().unwrap();
+                (parent_hash, header)
+            })
+            .collect()
+    }
+
+    /// Returns a provider serving the blocks of `headers` by hash.
+    fn node(headers: &[(B256, alloy_rpc_types::Header)]) -> RootProvider<BoxTransport> {
+        let blocks: Arc<HashMap<B256, Block>> = Arc::new(
+            headers
+                .iter()
+                .map(|(hash, header)| {
+                    (*hash, Block { header: header.clone(), ..Default::default() })
+                })
+                .collect(),
+        );
+        mock_provider(move |method, params| {
+            assert_eq!(method, "eth_getBlockByHash");
+            let (hash, _): (B256, bool) = serde_json::from_str(params).unwrap();
+            Ok(serde_json::json!(blocks.get(&hash)))
+        })
+    }
+
+    #[tokio::test]
+    async fn verifies_header_chain() {
+        let mut headers = headers(10);
+        let trusted = Header::try_from(headers[9].1.clone()).unwrap();
+
+        let chain = verify_header_chain(&node(&headers), trusted.clone(), 3).await.unwrap();
+        let hashes: Vec<_> = chain.iter().map(Sealed::hash).collect();
+        assert_eq!(hashes, [headers[9].0, headers[8].0, headers[7].0, headers[6].0]);
+
+        // The walk stops at the genesis block.
+        let chain = verify_header_chain(&node(&headers), trusted.clone(), 100).await.unwrap();
+        assert_eq!(chain.len(), 10);
+        assert_eq!(chain.last().unwrap().number, 0);
+
+        // A node returning a tampered header is detected.
+        headers[7].1.state_root = B256::repeat_byte(1);
+        let err = verify_header_chain(&node(&headers), trusted.clone(), 3).await.unwrap_err();
+        assert!(
+            matches!(err, HeaderChainError::HashMismatch { expected, .. } if expected == headers[7].0)
+        );
+
+        headers.remove(7);
+        let err = verify_header_chain(&node(&headers), trusted, 3).await.unwrap_err();
+        assert!(matches!(err, HeaderChainError::MissingBlock(_)));
+    }
+}
@@ -0,0 +1,354 @@
+//! A local cache of historical logs, stored in compressed flat files.
+//!
+//! A [`LogCache`] answers `eth_getLogs` queries over final blocks from disk. Logs are fetched and
+//! stored in segments of [`SEGMENT_SIZE`] blocks, each a file holding the logs of the segment
+//! compressed with zstd, and a bloom of their addresses and topics. Following queries
+//! over the same segments are answered from the files, and the bloom of a segment lets queries
+//! skip it without decompressing it when none of its logs can match.
+//!
+//! The logs of a segment are fetched for the addresses and the event signature (`topic0`) of a
+//! query only, ignoring its other topics, so that queries for the same events differing only in
+//! their indexed arguments share the same segments. These other topics are matched locally.
+//! Segments that are not final, according to a finality depth, are fetched for the query only
+//! and never stored.
+
+use crate::{layers::CacheStats, Provider};
+use alloy_network::Network;
+use alloy_primitives::{hex, keccak256, Bloom};
+use alloy_rpc_types::{Filter, FilteredParams, Log};
+use alloy_transport::{Transport, TransportError};
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+use thiserror::Error;
+
+pub use crate::layers::DEFAULT_FINALITY_DEPTH;
+
+/// The number of blocks of a segment.
+pub const SEGMENT_SIZE: u64 = 10_000;
+
+/// The magic bytes at the start of segment files, followed by the bloom of the segment.
+const SEGMENT_MAGIC: &[u8; 4] = b"alc2";
+
+/// The zstd compression level of segments.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Error returned by a [`LogCache`].
+#[derive(Debug, Error)]
+pub enum LogCacheError {
+    /// Failed to read or write a segment file.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Failed to encode or decode the logs of a segment.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    /// Failed to fetch logs or the head of the chain.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// A segment file is not a valid segment.
+    #[error("corrupted log cache segment {}", .0.display())]
+    Corrupted(PathBuf),
+    /// The block range of a filter starts after its end.
+    #[error("invalid block range: from block {from} is after to block {to}")]
+    InvalidRange {
+        /// The first block of the range.
+        from: u64,
+        /// The last block of the range.
+        to: u64,
+    },
+}
+
+/// A cache of historical logs in a directory, see the [module documentation](self).
+///
+/// ```no_run
+/// use alloy_primitives::address;
+/// use alloy_provider::{log_cache::LogCache, Provider};
+/// use alloy_rpc_types::Filter;
+///
+/// # async fn example(provider: impl Provider) -> Result<(), Box<dyn std::error::Error>> {
+/// let cache = LogCache::new("./logs");
+///
+/// let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+/// let filter = Filter::new()
+///     .address(usdc)
+///     .event("Transfer(address,address,uint256)")
+///     .from_block(17_000_000)
+///     .to_block(17_100_000);
+/// // The first query fetches the logs of the segments, the second one reads them from disk.
+/// let logs = cache.get_logs(&provider, &filter).await?;
+/// let logs = cache.get_logs(&provider, &filter).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LogCache {
+    dir: PathBuf,
+    finality_depth: u64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl LogCache {
+    /// Creates a cache storing its segments in the given directory, which is created on the first
+    /// write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            finality_depth: DEFAULT_FINALITY_DEPTH,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets the number of blocks below the head after which a block is considered final.
+    pub const fn with_finality_depth(mut self, depth: u64) -> Self {
+        self.finality_depth = depth;
+        self
+    }
+
+    /// Returns the directory of the cache.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the number of segments answered from disk, and fetched from the node, so far.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the logs matching the filter, reading the logs of final blocks from the cache and
+    /// fetching the missing ones from the provider.
+    ///
+    /// Filters without a numeric block range, such as those at a block hash or up to `latest`,
+    /// are forwarded to the provider. Filters whose range starts after its end are rejected with
+    /// [`LogCacheError::InvalidRange`].
+    pub async fn get_logs<P, T, N>(
+        &self,
+        provider: &P,
+        filter: &Filter,
+    ) -> Result<Vec<Log>, LogCacheError>
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        let (Some(from), Some(to)) = (filter.get_from_block(), filter.get_to_block()) else {
+            return Ok(provider.get_logs(filter).await?);
+        };
+        if from > to {
+            return Err(LogCacheError::InvalidRange { from, to });
+        }
+        let head = provider.get_block_number().await?;
+        let last_final = head.checked_sub(self.finality_depth);
+
+        let scope = scope(filter);
+        let scope_dir = self.dir.join(scope_key(&scope)?);
+        let params = FilteredParams::new(Some(filter.clone()));
+        let address_filter = FilteredParams::address_filter(&filter.address);
+        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+
+        let mut logs = Vec::new();
+        let mut start = from - from % SEGMENT_SIZE;
+        while start <= to {
+            let end = start.saturating_add(SEGMENT_SIZE - 1);
+            if last_final.map_or(true, |last_final| end > last_final) {
+                // The segment may still change: only fetch the queried blocks.
+                let range = filter.clone().from_block(start.max(from)).to_block(end.min(to));
+                logs.extend(provider.get_logs(&range).await?);
+            } else {
+                let path = scope_dir.join(format!("{start:012}.seg"));
+                let segment = match read_bloom(&path)? {
+                    Some(bloom)
+                        if !FilteredParams::matches_address(bloom, &address_filter)
+                            || !FilteredParams::matches_topics(bloom, &topics_filter) =>
+                    {
+                        self.hits.fetch_add(1, Ordering::Relaxed);
+                        Vec::new()
+                    }
+                    Some(_) => {
+                        self.hits.fetch_add(1, Ordering::Relaxed);
+                        read_segment(&path)?
+                    }
+                    None => {
+                        self.misses.fetch_add(1, Ordering::Relaxed);
+                        let range = scope.clone().from_block(start).to_block(end);
+                        let segment = provider.get_logs(&range).await?;
+                        write_segment(&path, &segment)?;
+                        segment
+                    }
+                };
+                logs.extend(segment.into_iter().filter(|log| {
+                    log.block_number.is_some_and(|number| from <= number && number <= to)
+                        && params.filter_address(&log.address())
+                        && params.filter_topics(log.topics())
+                }));
+            }
+            start = match end.checked_add(1) {
+                Some(start) => start,
+                None => break,
+            };
+        }
+        Ok(logs)
+    }
+}
+
+/// Returns the filter for which the logs of segments are fetched: the addresses and the event
+/// signature of the given filter, without block range.
+fn scope(filter: &Filter) -> Filter {
+    Filter {
+        address: filter.address.clone(),
+        topics: [
+            filter.topics[0].clone(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        ],
+        ..Default::default()
+    }
+}
+
+/// Returns the name of the directory of the segments of a scope.
+fn scope_key(scope: &Filter) -> Result<String, serde_json::Error> {
+    let mut addresses = scope.address.iter().collect::<Vec<_>>();
+    addresses.sort();
+    let mut topics = scope.topics[0].iter().collect::<Vec<_>>();
+    topics.sort();
+    let hash = keccak256(serde_json::to_vec(&(addresses, topics))?);
+    Ok(hex::encode(&hash[..16]))
+}
+
+/// Reads the bloom of a segment, or returns `None` if the segment is not stored.
+fn read_bloom(path: &Path) -> Result<Option<Bloom>, LogCacheError> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let mut header = [0; 4 + 256];
+    file.read_exact(&mut header).map_err(|_| LogCacheError::Corrupted(path.to_path_buf()))?;
+    if &header[..4] != SEGMENT_MAGIC {
+        return Err(LogCacheError::Corrupted(path.to_path_buf()));
+    }
+    Ok(Some(Bloom::from_slice(&header[4..])))
+}
+
+/// Reads the logs of a stored segment.
+fn read_segment(path: &Path) -> Result<Vec<Log>, LogCacheError> {
+    let bytes = fs::read(path)?;
+    let compressed = bytes.get(4 + 256..).ok_or_else(|| LogCacheError::Corrupted(path.into()))?;
+    let json =
+        zstd::decode_all(compressed).map_err(|_| LogCacheError::Corrupted(path.to_path_buf()))?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// Stores the logs of a segment, atomically replacing the file.
+fn write_segment(path: &Path, logs: &[Log]) -> Result<(), LogCacheError> {
+    let mut bloom = Bloom::default();
+    for log in logs {
+        bloom.accrue_raw_log(log.address(), log.topics());
+    }
+    let compressed = zstd::encode_all(&serde_json::to_vec(logs)?[..], COMPRESSION_LEVEL)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    let mut file = File::create(&tmp)?;
+    file.write_all(SEGMENT_MAGIC)?;
+    file.write_all(bloom.as_slice())?;
+    file.write_all(&compressed)?;
+    file.sync_all()?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::mock_provider, RootProvider};
+    use alloy_primitives::{Address, LogData, B256};
+    use alloy_transport::BoxTransport;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    fn log(number: u64, address: Address, topics: Vec<B256>) -> Log {
+        Log {
+            inner: alloy_primitives::Log {
+                address,
+                data: LogData::new_unchecked(topics, Default::default()),
+            },
+            block_number: Some(number),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_final_segments() {
+        let token = Address::repeat_byte(1);
+        let (transfer, alice, bob) =
+            (B256::repeat_byte(0xaa), B256::repeat_byte(0xa1), B256::repeat_byte(0xb0));
+        let chain = [
+            log(5_000, token, vec![transfer, alice]),
+            log(12_000, token, vec![transfer, bob]),
+            log(25_000, token, vec![transfer, alice]),
+        ];
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let seen = requests.clone();
+        let provider: RootProvider<BoxTransport> = mock_provider(move |method, params| {
+            Ok(match method {
+                "eth_blockNumber" => json!(format!("{:#x}", 30_000)),
+                "eth_getLogs" => {
+                    let (filter,): (Filter,) = serde_json::from_str(params).unwrap();
+                    seen.lock().unwrap().push((filter.get_from_block(), filter.get_to_block()));
+                    let params = FilteredParams::new(Some(filter.clone()));
+                    let logs = chain
+                        .iter()
+                        .filter(|log| {
+                            params.filter_block_range(log.block_number.unwrap())
+                                && params.filter_topics(log.topics())
+                        })
+                        .collect::<Vec<_>>();
+                    json!(logs)
+                }
+                method => unreachable!("{method}"),
+            })
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        // Blocks up to 20,000 are final, so the segments [0, 9,999] and [10,000, 19,999] are stored.
+        let cache = LogCache::new(dir.path()).with_finality_depth(10_000);
+        let filter = Filter::new().address(token).event_signature(transfer).topic1(alice);
+
+        let logs = cache
+            .get_logs(&provider, &filter.clone().from_block(3).to_block(27_000))
+            .await
+            .unwrap();
+        assert_eq!(
+            logs.iter().map(|log| log.block_number.unwrap()).collect::<Vec<_>>(),
+            [5_000, 25_000]
+        );
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 2 });
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [(Some(0), Some(9_999)), (Some(10_000), Some(19_999)), (Some(20_000), Some(27_000))]
+        );
+
+        // Stored segments are read from disk, and the bloom skips the one without a match.
+        requests.lock().unwrap().clear();
+        let filter = filter.topic1(bob).from_block(0).to_block(19_999);
+        let logs = cache.get_logs(&provider, &filter).await.unwrap();
+        assert_eq!(logs.iter().map(|log| log.block_number.unwrap()).collect::<Vec<_>>(), [12_000]);
+        assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 2 });
+        assert!(requests.lock().unwrap().is_empty());
+
+        let err = cache.get_logs(&provider, &filter.from_block(10).to_block(9)).await.unwrap_err();
+        assert!(matches!(err, LogCacheError::InvalidRange { from: 10, to: 9 }), "{err}");
+    }
+}
@@ -0,0 +1,315 @@
+//! A log stream over an installed filter, surviving the loss of the filter.
+//!
+//! Nodes uninstall filters that are not polled for a while, and load balancers may route polls to
+//! nodes that never installed them. [`PersistentLogFilter`] detects the resulting "filter not
+//! found" errors, installs the filter again, and fetches the logs emitted while the filter was
+//! lost with `eth_getLogs`, so that the stream neither stalls nor skips logs.
+
+use crate::{
+    ext::{PaginatedQuery, PaginationApi},
+    Provider,
+};
+use alloy_json_rpc::ErrorKind;
+use alloy_network::{Ethereum, Network};
+use alloy_primitives::{BlockNumber, U256};
+use alloy_rpc_types::{Filter, Log};
+use alloy_transport::{Transport, TransportResult};
+use futures::Stream;
+use std::{marker::PhantomData, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+// Timers of `std` and `tokio` are not available in the browser.
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::tokio::sleep;
+
+/// The maximum number of blocks of an `eth_getLogs` query catching up with a lost filter. Queries
+/// rejected by the node for covering too many blocks or logs are split further.
+const CATCH_UP_CHUNK_SIZE: u64 = 1_000;
+
+/// A stream of the logs matching a filter, polled with `eth_getFilterChanges`.
+///
+/// Created with [`Provider::watch_logs_persistent`].
+///
+/// When the node no longer knows the filter, it is installed again, and the logs of the blocks
+/// after the last block reported by the filter, up to the chain tip, are fetched with `eth_getLogs` and yielded
+/// first, at most 1000 blocks at a time. Logs of these blocks later reported by the new filter
+/// are skipped. Other errors are yielded without ending the stream, and the request is retried on
+/// the next poll, resuming the catch-up after the last fetched chunk.
+///
+/// ```no_run
+/// use alloy_provider::{Provider, ProviderBuilder};
+/// use alloy_rpc_types::Filter;
+/// use futures::StreamExt;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse()?);
+/// let filter = Filter::new().event("Transfer(address,address,uint256)");
+/// let mut logs = Box::pin(provider.watch_logs_persistent(&filter).into_stream());
+/// while let Some(log) = logs.next().await {
+///     println!("log at block {:?}", log?.block_number);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct PersistentLogFilter<P, T, N = Ethereum> {
+    provider: P,
+    filter: Filter,
+    poll_interval: Option<Duration>,
+    _phantom: PhantomData<(T, N)>,
+}
+
+impl<P, T, N> PersistentLogFilter<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new stream of the logs of `provider` matching `filter`.
+    pub const fn new(provider: P, filter: Filter) -> Self {
+        Self { provider, filter, poll_interval: None, _phantom: PhantomData }
+    }
+
+    /// Sets the interval between filter polls. Defaults to the client's poll interval.
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = Some(poll_interval);
+        self
+    }
+
+    /// Returns the stream of logs.
+    pub fn into_stream(self) -> impl Stream<Item = TransportResult<Log>> {
+        async_stream::stream! {
+            let provider = &self.provider;
+            let poll_interval =
+                self.poll_interval.unwrap_or_else(|| provider.client().poll_interval());
+            let mut filter_id: Option<U256> = None;
+            // The last block whose matching logs were all yielded: the tip when the first filter
+            // was installed, the last block reported by a filter, or the last caught up block.
+            let mut covered: Option<BlockNumber> = None;
+            // The last block whose logs were fetched after the filter was installed again.
+            let mut skip_through: Option<BlockNumber> = None;
+            let mut catch_up = false;
+            loop {
+                let Some(id) = filter_id else {
+                    if covered.is_none() {
+                        match provider.get_block_number().await {
+                            Ok(number) => covered = Some(number),
+                            Err(err) => {
+                                yield Err(err);
+                                sleep(poll_interval).await;
+                                continue;
+                            }
+                        }
+                    } else {
+                        catch_up = true;
+                    }
+                    match provider.new_filter(&self.filter).await {
+                        Ok(id) => filter_id = Some(id),
+                        Err(err) => {
+                            yield Err(err);
+                            sleep(poll_interval).await;
+                        }
+                    }
+                    continue;
+                };
+
+                if catch_up {
+                    // The new filter only reports the blocks after it was installed, so the tip
+                    // is fetched after the filter.
+                    let tip = match provider.get_block_number().await {
+                        Ok(tip) => tip,
+                        Err(err) => {
+                            yield Err(err);
+                            sleep(poll_interval).await;
+                            continue;
+                        }
+                    };
+                    let from = covered.map_or(0, |covered| covered + 1);
+                    let query = provider.get_logs_paginated(
+                        self.filter.clone(),
+                        from..=tip,
+                        CATCH_UP_CHUNK_SIZE,
+                    );
+                    let mut next = (from <= tip).then_some(from);
+                    let mut failed = false;
+                    while let Some(from) = next {
+                        match query.fetch_page(from).await {
+                            Ok(page) => {
+                                let to = page.next.map_or(tip, |next| next - 1);
+                                let logs = page.items.len();
+                                debug!(from, to, logs, "caught up with lost filter");
+                                for log in page.items {
+                                    yield Ok(log);
+                                }
+                                covered = Some(to);
+                                next = page.next;
+                            }
+                            Err(err) => {
+                                yield Err(err);
+                                failed = true;
+                                break;
+                            }
+                        }
+                    }
+                    if failed {
+                        sleep(poll_interval).await;
+                        continue;
+                    }
+                    covered = Some(covered.map_or(tip, |covered| covered.max(tip)));
+                    skip_through = Some(tip);
+                    catch_up = false;
+                }
+
+                match provider.get_filter_changes::<Log>(id).await {
+                    Ok(logs) => {
+                        // Only the blocks the filter reported are known to be covered, the node
+                        // may not have processed the blocks of the current tip yet.
+                        for log in logs {
+                            if !log.removed {
+                                let number = log.block_number.unwrap_or_default();
+                                if skip_through.is_some_and(|skip_through| number <= skip_through) {
+                                    continue;
+                                }
+                                covered = Some(covered.map_or(number, |covered| covered.max(number)));
+                            }
+                            yield Ok(log);
+                        }
+                    }
+                    Err(err) if err.error_kind() == Some(ErrorKind::FilterNotFound) => {
+                        debug!(%id, "filter not found, installing it again");
+                        filter_id = None;
+                        continue;
+                    }
+                    Err(err) => yield Err(err),
+                }
+                sleep(poll_interval).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{error_resp, mock_provider},
+        RootProvider,
+    };
+    use alloy_primitives::U64;
+    use alloy_transport::BoxTransport;
+    use futures::StreamExt;
+    use serde_json::json;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    /// A node with one matching log per block, mining a block on every filter poll, and
+    /// forgetting its filters on the given polls.
+    #[derive(Default)]
+    struct Node {
+        tip: u64,
+        polls: usize,
+        next_id: u64,
+        /// The last block reported by each filter.
+        filters: HashMap<U256, u64>,
+        forget_on: Vec<usize>,
+        /// The maximum number of blocks of an `eth_getLogs` query.
+        max_range: Option<u64>,
+        /// Whether only the even blocks have a matching log.
+        sparse: bool,
+        block_number_calls: usize,
+    }
+
+    impl Node {
+        fn logs(&self, blocks: std::ops::RangeInclusive<u64>) -> Vec<Log> {
+            blocks
+                .filter(|number| !self.sparse || number % 2 == 0)
+                .map(|number| Log { block_number: Some(number), ..Default::default() })
+                .collect()
+        }
+    }
+
+    fn provider(node: Arc<Mutex<Node>>) -> RootProvider<BoxTransport> {
+        mock_provider(move |method, params| {
+            let mut node = node.lock().unwrap();
+            Ok(match method {
+                "eth_blockNumber" => {
+                    node.block_number_calls += 1;
+                    json!(U64::from(node.tip))
+                }
+                "eth_newFilter" => {
+                    node.next_id += 1;
+                    let (id, tip) = (U256::from(node.next_id), node.tip);
+                    node.filters.insert(id, tip);
+                    // A block is mined before the tip is fetched.
+                    node.tip += 1;
+                    json!(id)
+                }
+                "eth_getFilterChanges" => {
+                    node.polls += 1;
+                    if node.forget_on.contains(&node.polls) {
+                        node.filters.clear();
+                    }
+                    node.tip += 1;
+                    let (id,): (U256,) = serde_json::from_str(params).unwrap();
+                    let tip = node.tip;
+                    let Some(last) = node.filters.get_mut(&id) else {
+                        return Err(error_resp(-32000, "filter not found"));
+                    };
+                    let from = *last + 1;
+                    *last = tip;
+                    json!(node.logs(from..=tip))
+                }
+                "eth_getLogs" => {
+                    let (filter,): (Filter,) = serde_json::from_str(params).unwrap();
+                    let (from, to) = (filter.get_from_block(), filter.get_to_block());
+                    let (from, to) = (from.unwrap(), to.unwrap());
+                    if node.max_range.is_some_and(|max_range| to - from + 1 > max_range) {
+                        return Err(error_resp(-32005, "block range too large"));
+                    }
+                    json!(node.logs(from..=to))
+                }
+                method => unreachable!("unexpected method {method}"),
+            })
+        })
+    }
+
+    async fn watch(node: &Arc<Mutex<Node>>, logs: usize) -> Vec<u64> {
+        provider(node.clone())
+            .watch_logs_persistent(&Filter::new())
+            .poll_interval(Duration::from_millis(1))
+            .into_stream()
+            .map(|log| log.unwrap().block_number.unwrap())
+            .take(logs)
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn reinstalls_lost_filter() {
+        let node = Node { tip: 10, forget_on: vec![3, 4, 7], ..Default::default() };
+        let node = Arc::new(Mutex::new(node));
+        // Every block after the tip at the start is reported once, in order.
+        assert_eq!(watch(&node, 20).await, (11..=30).collect::<Vec<_>>());
+        // The tip is only fetched when the filter is installed.
+        assert_eq!(node.lock().unwrap().block_number_calls, 4);
+    }
+
+    #[tokio::test]
+    async fn splits_catch_up() {
+        let node =
+            Node { tip: 10, forget_on: vec![3, 4, 7], max_range: Some(1), ..Default::default() };
+        let node = Arc::new(Mutex::new(node));
+        assert_eq!(watch(&node, 20).await, (11..=30).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn catches_up_after_blocks_without_logs() {
+        let node = Node { tip: 10, forget_on: vec![2, 5, 6], sparse: true, ..Default::default() };
+        let node = Arc::new(Mutex::new(node));
+        assert_eq!(watch(&node, 10).await, (6..=15).map(|n| 2 * n).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,402 @@
+//! Spending limits enforced when signing transactions.
+//!
+//! A [`PolicySigner`] wraps a [`NetworkSigner`] and checks every transaction request against a
+//! [`SpendingPolicy`] before signing it. The amounts spent by each sender during the current day
+//! are recorded in a [`KvStore`], so limits survive restarts when the store is persistent.
+//!
+//! Wrapping the signer of a provider applies the policy to every transaction filled by the
+//! provider:
+//!
+//! ```no_run
+//! use alloy_network::EthereumSigner;
+//! use alloy_primitives::{address, U256};
+//! use alloy_provider::{
+//!     cursor::MemoryKvStore,
+//!     policy::{PolicySigner, SpendingPolicy},
+//!     ProviderBuilder,
+//! };
+//! use std::sync::Arc;
+//!
+//! # fn example(signer: EthereumSigner) -> Result<(), Box<dyn std::error::Error>> {
+//! let policy = SpendingPolicy::new()
+//!     .daily_value_limit(U256::from(10).pow(U256::from(18)))
+//!     .allow(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"));
+//! let signer = PolicySigner::new(signer, policy, Arc::new(MemoryKvStore::new()));
+//! let provider = ProviderBuilder::new()
+//!     .with_recommended_fillers()
+//!     .signer(signer)
+//!     .on_http("http://localhost:8545".parse()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::cursor::KvStore;
+use alloy_network::{Network, NetworkSigner, TransactionBuilder};
+use alloy_primitives::{Address, U256};
+use async_trait::async_trait;
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The number of seconds in a day.
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The size of an encoded [`Spending`].
+const SPENDING_SIZE: usize = 72;
+
+/// Limits applied to the transactions signed by a [`PolicySigner`].
+///
+/// Daily limits apply separately to each sender, and reset at midnight UTC. By default, nothing is
+/// limited.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpendingPolicy {
+    daily_value_limit: Option<U256>,
+    daily_gas_fee_limit: Option<U256>,
+    allowlist: Option<HashSet<Address>>,
+}
+
+impl SpendingPolicy {
+    /// Creates a new policy without any limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum value, in wei, transferred by a sender per day.
+    pub const fn daily_value_limit(mut self, limit: U256) -> Self {
+        self.daily_value_limit = Some(limit);
+        self
+    }
+
+    /// Sets the maximum gas fees, in wei, spent by a sender per day.
+    ///
+    /// The fees of a transaction are accounted at their maximum, which is the gas limit multiplied
+    /// by the max fee per gas, or by the gas price for legacy transactions.
+    pub const fn daily_gas_fee_limit(mut self, limit: U256) -> Self {
+        self.daily_gas_fee_limit = Some(limit);
+        self
+    }
+
+    /// Adds an address to the allowed destinations.
+    ///
+    /// Once an address is allowed, transactions to any other address are rejected, and so are
+    /// contract creations.
+    pub fn allow(mut self, address: Address) -> Self {
+        self.allowlist.get_or_insert_with(HashSet::new).insert(address);
+        self
+    }
+
+    /// Adds addresses to the allowed destinations. See [`allow`](Self::allow).
+    pub fn allow_all(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.allowlist.get_or_insert_with(HashSet::new).extend(addresses);
+        self
+    }
+}
+
+/// The amounts spent by a sender during a day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Spending {
+    /// The day, counted in days since the Unix epoch.
+    pub day: u64,
+    /// The value transferred, in wei.
+    pub value: U256,
+    /// The maximum gas fees of the signed transactions, in wei.
+    pub gas_fees: U256,
+}
+
+impl Spending {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(SPENDING_SIZE);
+        out.extend_from_slice(&self.day.to_be_bytes());
+        out.extend_from_slice(&self.value.to_be_bytes::<32>());
+        out.extend_from_slice(&self.gas_fees.to_be_bytes::<32>());
+        out
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != SPENDING_SIZE {
+            return None;
+        }
+        let (day, rest) = bytes.split_at(8);
+        let (value, gas_fees) = rest.split_at(32);
+        Some(Self {
+            day: u64::from_be_bytes(day.try_into().unwrap()),
+            value: U256::from_be_slice(value),
+            gas_fees: U256::from_be_slice(gas_fees),
+        })
+    }
+}
+
+/// Error returned when a transaction is rejected by a [`PolicySigner`].
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyError<E> {
+    /// The underlying store failed.
+    #[error(transparent)]
+    Store(E),
+    /// The recorded spending of the sender is corrupted.
+    #[error("corrupted spending record for {0}")]
+    Corrupted(Address),
+    /// The destination is not in the allowlist. `None` is a contract creation.
+    #[error("destination {0:?} is not allowed")]
+    DestinationNotAllowed(Option<Address>),
+    /// The transaction would exceed the daily value limit of the sender.
+    #[error("daily value limit of {limit} wei exceeded: {spent} spent, {requested} requested")]
+    ValueLimitExceeded {
+        /// The daily limit.
+        limit: U256,
+        /// The value already transferred today.
+        spent: U256,
+        /// The value of the transaction.
+        requested: U256,
+    },
+    /// The transaction would exceed the daily gas fee limit of the sender.
+    #[error("daily gas fee limit of {limit} wei exceeded: {spent} spent, {requested} requested")]
+    GasFeeLimitExceeded {
+        /// The daily limit.
+        limit: U256,
+        /// The gas fees already spent today.
+        spent: U256,
+        /// The maximum gas fees of the transaction.
+        requested: U256,
+    },
+    /// An unsigned transaction was passed directly, so it could not be checked.
+    #[error("the spending policy only applies to transaction requests")]
+    UncheckedTransaction,
+}
+
+/// A [`NetworkSigner`] enforcing a [`SpendingPolicy`].
+///
+/// Transactions are only signed through [`NetworkSigner::sign_request`], which is used by
+/// [`SignerFiller`](crate::fillers::SignerFiller). Signing an unsigned transaction directly is
+/// rejected with [`PolicyError::UncheckedTransaction`], since it would bypass the policy.
+///
+/// A transaction is recorded in the store before it is signed, and removed from it if signing
+/// fails. Transactions that are signed but never sent still count against the limits.
+#[derive(Clone, Debug)]
+pub struct PolicySigner<S, K> {
+    signer: S,
+    policy: SpendingPolicy,
+    store: K,
+    lock: Arc<Mutex<()>>,
+}
+
+impl<S, K: KvStore> PolicySigner<S, K> {
+    /// Wraps `signer`, enforcing `policy` and recording spending in `store`.
+    pub fn new(signer: S, policy: SpendingPolicy, store: K) -> Self {
+        Self { signer, policy, store, lock: Arc::default() }
+    }
+
+    /// Returns a reference to the wrapped signer.
+    pub const fn inner(&self) -> &S {
+        &self.signer
+    }
+
+    /// Returns the policy.
+    pub const fn policy(&self) -> &SpendingPolicy {
+        &self.policy
+    }
+
+    /// Returns the amounts spent by `sender` today.
+    pub fn spent_today(&self, sender: Address) -> Result<Spending, PolicyError<K::Error>> {
+        let day = current_day();
+        self.load(sender, day)
+    }
+
+    /// Checks a transaction against the policy, and records it if it is allowed.
+    fn reserve(
+        &self,
+        sender: Address,
+        to: Option<Address>,
+        value: U256,
+        gas_fees: U256,
+        day: u64,
+    ) -> Result<(), PolicyError<K::Error>> {
+        if let Some(allowlist) = &self.policy.allowlist {
+            if !to.is_some_and(|to| allowlist.contains(&to)) {
+                return Err(PolicyError::DestinationNotAllowed(to));
+            }
+        }
+
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut spending = self.load(sender, day)?;
+        if let Some(limit) = self.policy.daily_value_limit {
+            if spending.value.saturating_add(value) > limit {
+                return Err(PolicyError::ValueLimitExceeded {
+                    limit,
+                    spent: spending.value,
+                    requested: value,
+                });
+            }
+        }
+        if let Some(limit) = self.policy.daily_gas_fee_limit {
+            if spending.gas_fees.saturating_add(gas_fees) > limit {
+                return Err(PolicyError::GasFeeLimitExceeded {
+                    limit,
+                    spent: spending.gas_fees,
+                    requested: gas_fees,
+                });
+            }
+        }
+        spending.value = spending.value.saturating_add(value);
+        spending.gas_fees = spending.gas_fees.saturating_add(gas_fees);
+        self.store.put(&key(sender), spending.encode()).map_err(PolicyError::Store)
+    }
+
+    /// Removes a reserved transaction from the record of the sender.
+    fn release(
+        &self,
+        sender: Address,
+        value: U256,
+        gas_fees: U256,
+        day: u64,
+    ) -> Result<(), PolicyError<K::Error>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut spending = self.load(sender, day)?;
+        if spending.day != day {
+            return Ok(());
+        }
+        spending.value = spending.value.saturating_sub(value);
+        spending.gas_fees = spending.gas_fees.saturating_sub(gas_fees);
+        self.store.put(&key(sender), spending.encode()).map_err(PolicyError::Store)
+    }
+
+    /// Loads the spending of `sender` on `day`, which is empty if the record is of another day.
+    fn load(&self, sender: Address, day: u64) -> Result<Spending, PolicyError<K::Error>> {
+        let Some(bytes) = self.store.get(&key(sender)).map_err(PolicyError::Store)? else {
+            return Ok(Spending { day, ..Default::default() });
+        };
+        let spending = Spending::decode(&bytes).ok_or(PolicyError::Corrupted(sender))?;
+        Ok(if spending.day == day { spending } else { Spending { day, ..Default::default() } })
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S, K, N> NetworkSigner<N> for PolicySigner<S, K>
+where
+    S: NetworkSigner<N>,
+    K: KvStore + std::fmt::Debug,
+    N: Network,
+    N::UnsignedTx: Send,
+{
+    fn default_signer_address(&self) -> Address {
+        self.signer.default_signer_address()
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        self.signer.has_signer_for(address)
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        self.signer.signer_addresses()
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        _sender: Address,
+        _tx: N::UnsignedTx,
+    ) -> alloy_signer::Result<N::TxEnvelope> {
+        Err(alloy_signer::Error::other(PolicyError::<K::Error>::UncheckedTransaction))
+    }
+
+    async fn sign_request(
+        &self,
+        request: N::TransactionRequest,
+    ) -> alloy_signer::Result<N::TxEnvelope> {
+        let sender = request.from().unwrap_or_else(|| self.signer.default_signer_address());
+        let to = request.kind().and_then(|kind| kind.to().copied());
+        let value = request.value().unwrap_or_default();
+        let fee_per_gas = request.max_fee_per_gas().or_else(|| request.gas_price());
+        let gas_fees = U256::from(request.gas_limit().unwrap_or_default())
+            .saturating_mul(U256::from(fee_per_gas.unwrap_or_default()));
+
+        let day = current_day();
+        self.reserve(sender, to, value, gas_fees, day).map_err(alloy_signer::Error::other)?;
+        let result = self.signer.sign_request(request).await;
+        if result.is_err() {
+            self.release(sender, value, gas_fees, day).map_err(alloy_signer::Error::other)?;
+        }
+        result
+    }
+}
+
+/// Returns the key under which the spending of `sender` is stored.
+fn key(sender: Address) -> String {
+    format!("spending_policy/{sender}")
+}
+
+/// Returns the current day, counted in days since the Unix epoch.
+fn current_day() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() / SECONDS_PER_DAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cursor::MemoryKvStore;
+    use alloy_network::{Ethereum, EthereumSigner};
+    use alloy_primitives::address;
+    use alloy_rpc_types::TransactionRequest;
+    use alloy_signer_wallet::LocalWallet;
+
+    const TO: Address = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+
+    fn request(from: Address, value: u64) -> TransactionRequest {
+        TransactionRequest::default()
+            .with_from(from)
+            .with_to(TO)
+            .with_value(U256::from(value))
+            .with_nonce(0)
+            .with_chain_id(1)
+            .with_gas_limit(21_000)
+            .with_max_fee_per_gas(10)
+            .with_max_priority_fee_per_gas(1)
+    }
+
+    #[test]
+    fn reserves_per_day() {
+        let store = MemoryKvStore::new();
+        let policy = SpendingPolicy::new().daily_value_limit(U256::from(100));
+        let signer = PolicySigner::new((), policy, &store);
+        let sender = Address::repeat_byte(1);
+
+        signer.reserve(sender, Some(TO), U256::from(60), U256::ZERO, 10).unwrap();
+        assert!(matches!(
+            signer.reserve(sender, Some(TO), U256::from(50), U256::ZERO, 10),
+            Err(PolicyError::ValueLimitExceeded { spent, .. }) if spent == U256::from(60)
+        ));
+        // Other senders and days have their own limits.
+        signer.reserve(Address::repeat_byte(2), Some(TO), U256::from(50), U256::ZERO, 10).unwrap();
+        signer.reserve(sender, Some(TO), U256::from(50), U256::ZERO, 11).unwrap();
+
+        // The record survives the signer.
+        let signer = PolicySigner::new((), SpendingPolicy::new(), &store);
+        assert_eq!(signer.load(sender, 11).unwrap().value, U256::from(50));
+        signer.release(sender, U256::from(50), U256::ZERO, 11).unwrap();
+        assert_eq!(signer.load(sender, 11).unwrap().value, U256::ZERO);
+    }
+
+    #[tokio::test]
+    async fn enforces_policy() {
+        let wallet = LocalWallet::random();
+        let sender = wallet.address();
+        let policy = SpendingPolicy::new()
+            .daily_value_limit(U256::from(1000))
+            .daily_gas_fee_limit(U256::from(21_000 * 10 * 2))
+            .allow(TO);
+        let signer = PolicySigner::new(EthereumSigner::from(wallet), policy, MemoryKvStore::new());
+        let sign = |request| NetworkSigner::<Ethereum>::sign_request(&signer, request);
+
+        sign(request(sender, 400)).await.unwrap();
+        let err = sign(request(sender, 700)).await.unwrap_err();
+        assert!(err.to_string().contains("daily value limit"), "{err}");
+        sign(request(sender, 600)).await.unwrap();
+        assert_eq!(signer.spent_today(sender).unwrap().gas_fees, U256::from(21_000 * 10 * 2),);
+        let err = sign(request(sender, 0)).await.unwrap_err();
+        assert!(err.to_string().contains("daily gas fee limit"), "{err}");
+
+        let err = sign(request(sender, 0).with_to(Address::ZERO)).await.unwrap_err();
+        assert!(err.to_string().contains("not allowed"), "{err}");
+    }
+}
@@ -0,0 +1,95 @@
+use crate::PendingTransactionBuilder;
+use alloy_network::Network;
+use alloy_primitives::TxHash;
+use alloy_transport::{Transport, TransportError, TransportResult};
+
+/// Substrings of the errors returned by nodes that already have the transaction in their pool.
+const ALREADY_KNOWN_ERRORS: &[&str] =
+    &["already known", "known transaction", "already imported", "already exists"];
+
+/// The outcome of [`Provider::send_raw_transaction_broadcast`].
+///
+/// [`Provider::send_raw_transaction_broadcast`]: crate::Provider::send_raw_transaction_broadcast
+#[derive(Debug)]
+#[must_use = "this type does nothing unless you call `into_pending` or inspect its results"]
+pub struct Broadcast<'a, T, N> {
+    pending: PendingTransactionBuilder<'a, T, N>,
+    results: Vec<TransportResult<()>>,
+}
+
+impl<'a, T: Transport + Clone, N: Network> Broadcast<'a, T, N> {
+    pub(crate) const fn new(
+        pending: PendingTransactionBuilder<'a, T, N>,
+        results: Vec<TransportResult<()>>,
+    ) -> Self {
+        Self { pending, results }
+    }
+
+    /// Returns the hash of the broadcast transaction.
+    pub const fn tx_hash(&self) -> &TxHash {
+        self.pending.tx_hash()
+    }
+
+    /// Returns the result of each submission: the provider's own first, followed by the
+    /// endpoints in order.
+    ///
+    /// Nodes that already knew the transaction are reported as successful.
+    pub fn results(&self) -> &[TransportResult<()>] {
+        &self.results
+    }
+
+    /// Returns the number of nodes that accepted the transaction.
+    pub fn accepted(&self) -> usize {
+        self.results.iter().filter(|result| result.is_ok()).count()
+    }
+
+    /// Returns the builder for watching the transaction through the provider.
+    pub fn into_pending(self) -> PendingTransactionBuilder<'a, T, N> {
+        self.pending
+    }
+}
+
+/// Returns `true` if the error means that the node already has the transaction.
+pub(crate) fn is_already_known(err: &TransportError) -> bool {
+    err.as_error_resp().is_some_and(|payload| {
+        let message = payload.message.to_lowercase();
+        ALREADY_KNOWN_ERRORS.iter().any(|known| message.contains(known))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_utils::{error_resp, mock_provider},
+        Provider, RootProvider,
+    };
+    use alloy_network::Ethereum;
+    use alloy_primitives::{keccak256, B256};
+    use alloy_transport::BoxTransport;
+
+    /// Returns a provider whose node rejects transactions with `error`, or accepts them.
+    fn node(error: Option<&'static str>) -> RootProvider<BoxTransport, Ethereum> {
+        mock_provider(move |_, _| match error {
+            Some(message) => Err(error_resp(-32000, message)),
+            None => Ok(serde_json::json!(B256::ZERO)),
+        })
+    }
+
+    #[tokio::test]
+    async fn broadcast() {
+        let tx = [0x02, 0xc0];
+        let endpoints = [node(None), node(Some("nonce too low"))];
+
+        let provider = node(Some("already known"));
+        let broadcast = provider.send_raw_transaction_broadcast(&tx, &endpoints).await.unwrap();
+        assert_eq!(*broadcast.tx_hash(), keccak256(tx));
+        assert_eq!(broadcast.accepted(), 2);
+        assert!(broadcast.results()[2].is_err());
+
+        let err = node(Some("insufficient funds"))
+            .send_raw_transaction_broadcast(&tx, &endpoints[1..])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("insufficient funds"), "{err}");
+    }
+}
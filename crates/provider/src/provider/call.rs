@@ -2,10 +2,50 @@ use alloy_eips::BlockId;
 use alloy_network::Network;
 use alloy_primitives::Bytes;
 use alloy_rpc_client::{RpcCall, WeakClient};
-use alloy_rpc_types::state::StateOverride;
+use alloy_rpc_types::{state::StateOverride, BlockOverrides};
 use alloy_transport::{Transport, TransportErrorKind, TransportResult};
 use futures::FutureExt;
-use std::{borrow::Cow, future::Future, task::Poll};
+use serde::ser::SerializeSeq;
+use std::{future::Future, task::Poll};
+
+/// The parameters of an `eth_call` or `eth_estimateGas` request.
+///
+/// Overrides are positional, so the state overrides are serialized as an empty set when only block
+/// overrides are given.
+#[derive(Debug, Clone)]
+pub(crate) struct CallParams<'req, 'state, R> {
+    pub(crate) data: &'req R,
+    pub(crate) block: BlockId,
+    pub(crate) overrides: Option<&'state StateOverride>,
+    pub(crate) block_overrides: Option<&'state BlockOverrides>,
+    /// Whether to serialize the state overrides when there are none, as `eth_call` always did.
+    pub(crate) empty_overrides: bool,
+}
+
+impl<R: serde::Serialize> serde::Serialize for CallParams<'_, '_, R> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let len = if self.block_overrides.is_some() {
+            4
+        } else if self.overrides.is_some() || self.empty_overrides {
+            3
+        } else {
+            2
+        };
+        let mut seq = serializer.serialize_seq(Some(len))?;
+        seq.serialize_element(self.data)?;
+        seq.serialize_element(&self.block)?;
+        if len > 2 {
+            match self.overrides {
+                Some(overrides) => seq.serialize_element(overrides)?,
+                None => seq.serialize_element(&StateOverride::default())?,
+            }
+        }
+        if let Some(block_overrides) = self.block_overrides {
+            seq.serialize_element(block_overrides)?;
+        }
+        seq.end()
+    }
+}
 
 /// States for the [`EthCallFut`] future.
 #[derive(Debug, Clone)]
@@ -18,9 +58,10 @@ where
         client: WeakClient<T>,
         data: &'req N::TransactionRequest,
         overrides: Option<&'state StateOverride>,
+        block_overrides: Option<&'state BlockOverrides>,
         block: Option<BlockId>,
     },
-    Running(RpcCall<T, (&'req N::TransactionRequest, BlockId, Cow<'state, StateOverride>), Bytes>),
+    Running(RpcCall<T, CallParams<'req, 'state, N::TransactionRequest>, Bytes>),
 }
 
 /// Future for [`EthCall`]. Simple wrapper around [`RpcCall`].
@@ -43,7 +84,9 @@ where
         cx: &mut std::task::Context<'_>,
     ) -> Poll<TransportResult<Bytes>> {
         let fut = {
-            let States::Preparing { client, data, overrides, block } = &self.as_ref().state else {
+            let States::Preparing { client, data, overrides, block_overrides, block } =
+                &self.as_ref().state
+            else {
                 unreachable!("bad state")
             };
 
@@ -52,11 +95,14 @@ where
                 Err(e) => return std::task::Poll::Ready(Err(e)),
             };
 
-            let overrides = match overrides {
-                Some(overrides) => Cow::Borrowed(*overrides),
-                None => Cow::Owned(StateOverride::default()),
+            let params = CallParams {
+                data: *data,
+                block: block.unwrap_or_default(),
+                overrides: *overrides,
+                block_overrides: *block_overrides,
+                empty_overrides: true,
             };
-            client.request("eth_call", (*data, block.unwrap_or_default(), overrides))
+            client.request("eth_call", params)
         };
 
         self.state = States::Running(fut);
@@ -109,6 +155,7 @@ where
 
     data: &'req N::TransactionRequest,
     overrides: Option<&'state StateOverride>,
+    block_overrides: Option<&'state BlockOverrides>,
     block: Option<BlockId>,
 }
 
@@ -119,7 +166,7 @@ where
 {
     /// Create a new CallBuilder.
     pub const fn new(client: WeakClient<T>, data: &'req N::TransactionRequest) -> Self {
-        Self { client, data, overrides: None, block: None }
+        Self { client, data, overrides: None, block_overrides: None, block: None }
     }
 }
 
@@ -135,6 +182,13 @@ where
         self
     }
 
+    /// Set the block overrides for this call.
+    #[allow(clippy::missing_const_for_fn)] // false positive
+    pub fn block_overrides(mut self, block_overrides: &'state BlockOverrides) -> Self {
+        self.block_overrides = Some(block_overrides);
+        self
+    }
+
     /// Set the block to use for this call.
     #[allow(clippy::missing_const_for_fn)] // false positive
     pub fn block(mut self, block: BlockId) -> Self {
@@ -157,9 +211,42 @@ where
             client: self.client,
             data: self.data,
             overrides: self.overrides,
+            block_overrides: self.block_overrides,
             block: self.block,
         };
 
         EthCallFut { state }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use alloy_rpc_types::TransactionRequest;
+
+    #[test]
+    fn serialize_params() {
+        let tx = TransactionRequest::default();
+        let mut params = CallParams {
+            data: &tx,
+            block: BlockId::latest(),
+            overrides: None,
+            block_overrides: None,
+            empty_overrides: false,
+        };
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"[{},"latest"]"#);
+        params.empty_overrides = true;
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"[{},"latest",{}]"#);
+        params.empty_overrides = false;
+
+        let block_overrides = BlockOverrides { number: Some(U256::from(1)), ..Default::default() };
+        params.block_overrides = Some(&block_overrides);
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"[{},"latest",{},{"number":"0x1"}]"#);
+
+        let overrides = StateOverride::default();
+        params.overrides = Some(&overrides);
+        params.block_overrides = None;
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"[{},"latest",{}]"#);
+    }
+}
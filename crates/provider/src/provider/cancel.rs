@@ -0,0 +1,55 @@
+use alloy_primitives::TxHash;
+
+/// The minimum fee bump, in percent, accepted by most nodes to replace a pending transaction.
+pub(crate) const MIN_FEE_BUMP: u64 = 10;
+
+/// The outcome of [`Provider::cancel_transaction`].
+///
+/// [`Provider::cancel_transaction`]: crate::Provider::cancel_transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CancelOutcome<R> {
+    /// The cancellation was included, so the original transaction will never be.
+    Cancelled {
+        /// The hash of the cancellation transaction.
+        tx_hash: TxHash,
+        /// The receipt of the cancellation transaction.
+        receipt: R,
+    },
+    /// The original transaction was included, before or despite the cancellation.
+    Included {
+        /// The receipt of the original transaction.
+        receipt: R,
+    },
+}
+
+impl<R> CancelOutcome<R> {
+    /// Returns `true` if the original transaction was cancelled.
+    pub const fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled { .. })
+    }
+
+    /// Returns the receipt of the included transaction.
+    pub const fn receipt(&self) -> &R {
+        match self {
+            Self::Cancelled { receipt, .. } | Self::Included { receipt } => receipt,
+        }
+    }
+}
+
+/// Bumps a fee by `percent`, rounding up.
+pub(crate) const fn bump_fee(fee: u128, percent: u64) -> u128 {
+    let bumped = fee.saturating_mul(100 + percent as u128);
+    bumped / 100 + (bumped % 100 != 0) as u128
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bumps_fees() {
+        assert_eq!(bump_fee(100, 10), 110);
+        assert_eq!(bump_fee(15, 10), 17);
+        assert_eq!(bump_fee(0, 10), 0);
+    }
+}
@@ -0,0 +1,550 @@
+use crate::{
+    provider::{FilterPollerBuilder, TraceCallList},
+    utils::{BlobFeeUrgency, Eip1559Estimation, EstimatorFunction},
+    EthCall, PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig, Provider,
+    RootProvider, RpcWithBlock, SendableTx,
+};
+use alloy_eips::eip4844::BlobParams;
+use alloy_network::{Ethereum, FinalityPolicy, Network};
+use alloy_primitives::{
+    Address, BlockHash, BlockNumber, Bytes, ChainId, Signature, StorageKey, StorageValue, TxHash,
+    B256, U128, U256, U64,
+};
+use alloy_rpc_client::{ClientRef, RpcCall, WeakClient};
+use alloy_rpc_types::{
+    simulate::{SimulatePayload, SimulatedBlock},
+    state::StateOverride,
+    AccessListResult, Account, Block, BlockId, BlockNumberOrTag, BlockOverrides, Bundle,
+    EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Filter, FilterChanges, Log,
+    ProvenReceipt, StateContext, SyncStatus,
+};
+use alloy_rpc_types_trace::parity::{LocalizedTransactionTrace, TraceResults, TraceType};
+use alloy_transport::{BoxTransport, Transport, TransportResult};
+use serde_json::value::RawValue;
+use std::{borrow::Cow, fmt, sync::Arc, time::Duration};
+
+/// A type-erased, cheaply cloneable [`Provider`].
+///
+/// Layered providers have long, nested types, which are painful to name in application structs.
+/// Erasing the layers behind a `DynProvider` keeps their behavior, e.g. transactions are still
+/// filled by the fillers of a [`FillProvider`](crate::fillers::FillProvider), while the type only
+/// depends on the transport and network. Every method that can be called on a trait object is
+/// forwarded to the erased provider, including those a layer overrides.
+///
+/// Unlike `Arc<dyn Provider>`, this type implements [`Provider`] itself, so the provider
+/// extension traits and the generic methods of [`Provider`] are available on it.
+///
+/// Created with [`Provider::erased`].
+#[derive(Clone)]
+pub struct DynProvider<T = BoxTransport, N = Ethereum>(Arc<dyn Provider<T, N> + 'static>);
+
+impl<T, N> fmt::Debug for DynProvider<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynProvider").finish_non_exhaustive()
+    }
+}
+
+impl<T: Transport + Clone, N: Network> DynProvider<T, N> {
+    /// Erases the type of the given provider.
+    pub fn new<P: Provider<T, N> + 'static>(provider: P) -> Self {
+        Self(Arc::new(provider))
+    }
+}
+
+impl<T: Transport + Clone, N: Network> From<Arc<dyn Provider<T, N> + 'static>>
+    for DynProvider<T, N>
+{
+    fn from(provider: Arc<dyn Provider<T, N> + 'static>) -> Self {
+        Self(provider)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl<T: Transport + Clone, N: Network> Provider<T, N> for DynProvider<T, N> {
+    #[inline]
+    fn root(&self) -> &RootProvider<T, N> {
+        self.0.root()
+    }
+
+    #[inline]
+    fn client(&self) -> ClientRef<'_, T> {
+        self.0.client()
+    }
+
+    #[inline]
+    fn weak_client(&self) -> WeakClient<T> {
+        self.0.weak_client()
+    }
+
+    async fn watch_pending_transaction(
+        &self,
+        config: PendingTransactionConfig,
+    ) -> TransportResult<PendingTransaction> {
+        self.0.watch_pending_transaction(config).await
+    }
+
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_blocks(&self) -> TransportResult<alloy_pubsub::Subscription<Block>> {
+        self.0.subscribe_blocks().await
+    }
+
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_pending_transactions(
+        &self,
+    ) -> TransportResult<alloy_pubsub::Subscription<B256>> {
+        self.0.subscribe_pending_transactions().await
+    }
+
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_full_pending_transactions(
+        &self,
+    ) -> TransportResult<alloy_pubsub::Subscription<N::TransactionResponse>> {
+        self.0.subscribe_full_pending_transactions().await
+    }
+
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_logs(
+        &self,
+        filter: &Filter,
+    ) -> TransportResult<alloy_pubsub::Subscription<Log>> {
+        self.0.subscribe_logs(filter).await
+    }
+
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_syncing(
+        &self,
+    ) -> TransportResult<alloy_pubsub::Subscription<alloy_rpc_types::pubsub::PubSubSyncStatus>>
+    {
+        self.0.subscribe_syncing().await
+    }
+
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_sync_status(
+        &self,
+    ) -> TransportResult<futures::stream::BoxStream<'static, SyncStatus>> {
+        self.0.subscribe_sync_status().await
+    }
+
+    #[cfg(feature = "pubsub")]
+    async fn unsubscribe(&self, id: U256) -> TransportResult<()> {
+        self.0.unsubscribe(id).await
+    }
+
+    async fn watch_blocks(&self) -> TransportResult<FilterPollerBuilder<T, B256>> {
+        self.0.watch_blocks().await
+    }
+
+    async fn watch_pending_transactions(&self) -> TransportResult<FilterPollerBuilder<T, B256>> {
+        self.0.watch_pending_transactions().await
+    }
+
+    async fn watch_full_pending_transactions(
+        &self,
+    ) -> TransportResult<FilterPollerBuilder<T, N::TransactionResponse>> {
+        self.0.watch_full_pending_transactions().await
+    }
+
+    async fn watch_logs(&self, filter: &Filter) -> TransportResult<FilterPollerBuilder<T, Log>> {
+        self.0.watch_logs(filter).await
+    }
+
+    async fn new_block_filter(&self) -> TransportResult<U256> {
+        self.0.new_block_filter().await
+    }
+
+    async fn new_pending_transactions_filter(&self, full: bool) -> TransportResult<U256> {
+        self.0.new_pending_transactions_filter(full).await
+    }
+
+    async fn new_filter(&self, filter: &Filter) -> TransportResult<U256> {
+        self.0.new_filter(filter).await
+    }
+
+    async fn get_filter_changes_dyn(&self, id: U256) -> TransportResult<FilterChanges> {
+        self.0.get_filter_changes_dyn(id).await
+    }
+
+    #[inline]
+    fn get_block_number(&self) -> RpcCall<T, (), U64, BlockNumber> {
+        self.0.get_block_number()
+    }
+
+    #[inline]
+    fn get_transaction_count(&self, address: Address) -> RpcWithBlock<T, Address, U64, u64> {
+        self.0.get_transaction_count(address)
+    }
+
+    async fn get_block_by_number(
+        &self,
+        number: BlockNumberOrTag,
+        hydrate: bool,
+    ) -> TransportResult<Option<Block<N::TransactionResponse>>> {
+        self.0.get_block_by_number(number, hydrate).await
+    }
+
+    async fn get_final_block_number(
+        &self,
+        policy: &FinalityPolicy,
+    ) -> TransportResult<BlockNumber> {
+        self.0.get_final_block_number(policy).await
+    }
+
+    async fn send_transaction(
+        &self,
+        tx: N::TransactionRequest,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        self.0.send_transaction(tx).await
+    }
+
+    async fn transfer(
+        &self,
+        to: Address,
+        value: U256,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        self.0.transfer(to, value).await
+    }
+
+    async fn send_transaction_internal(
+        &self,
+        tx: SendableTx<N>,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        self.0.send_transaction_internal(tx).await
+    }
+
+    async fn send_raw_transaction(
+        &self,
+        encoded_tx: &[u8],
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        self.0.send_raw_transaction(encoded_tx).await
+    }
+
+    #[inline]
+    fn get_balance(&self, address: Address) -> RpcWithBlock<T, Address, U256> {
+        self.0.get_balance(address)
+    }
+
+    async fn get_block(
+        &self,
+        id: BlockId,
+        full: bool,
+    ) -> TransportResult<Option<Block<N::TransactionResponse>>> {
+        self.0.get_block(id, full).await
+    }
+
+    async fn get_block_by_hash(
+        &self,
+        hash: BlockHash,
+        full: bool,
+    ) -> TransportResult<Option<Block<N::TransactionResponse>>> {
+        self.0.get_block_by_hash(hash, full).await
+    }
+
+    async fn get_client_version(&self) -> TransportResult<String> {
+        self.0.get_client_version().await
+    }
+
+    #[inline]
+    fn get_chain_id(&self) -> RpcCall<T, (), U64, u64> {
+        self.0.get_chain_id()
+    }
+
+    #[inline]
+    async fn cached_chain_id(&self) -> TransportResult<ChainId> {
+        self.0.cached_chain_id().await
+    }
+
+    #[inline]
+    fn chain_id(&self) -> Option<ChainId> {
+        self.0.chain_id()
+    }
+
+    #[inline]
+    fn get_net_version(&self) -> RpcCall<T, (), U64, u64> {
+        self.0.get_net_version()
+    }
+
+    #[inline]
+    fn get_storage_at(
+        &self,
+        address: Address,
+        key: U256,
+    ) -> RpcWithBlock<T, (Address, U256), StorageValue> {
+        self.0.get_storage_at(address, key)
+    }
+
+    #[inline]
+    fn get_code_at(&self, address: Address) -> RpcWithBlock<T, Address, Bytes> {
+        self.0.get_code_at(address)
+    }
+
+    #[inline]
+    async fn get_account(&self, address: Address, block: BlockId) -> TransportResult<Account> {
+        self.0.get_account(address, block).await
+    }
+
+    async fn get_transaction_by_hash(
+        &self,
+        hash: TxHash,
+    ) -> TransportResult<Option<N::TransactionResponse>> {
+        self.0.get_transaction_by_hash(hash).await
+    }
+
+    async fn get_raw_transaction_by_hash(&self, hash: TxHash) -> TransportResult<Option<Bytes>> {
+        self.0.get_raw_transaction_by_hash(hash).await
+    }
+
+    #[inline]
+    fn decode_raw_transaction(&self, raw: &[u8]) -> TransportResult<N::TxEnvelope> {
+        self.0.decode_raw_transaction(raw)
+    }
+
+    async fn get_logs(&self, filter: &Filter) -> TransportResult<Vec<Log>> {
+        self.0.get_logs(filter).await
+    }
+
+    async fn get_logs_at(&self, block_hash: B256, filter: &Filter) -> TransportResult<Vec<Log>> {
+        self.0.get_logs_at(block_hash, filter).await
+    }
+
+    async fn get_accounts(&self) -> TransportResult<Vec<Address>> {
+        self.0.get_accounts().await
+    }
+
+    async fn sign_transaction(&self, tx: N::TransactionRequest) -> TransportResult<Bytes> {
+        self.0.sign_transaction(tx).await
+    }
+
+    async fn sign_message(&self, address: Address, message: &[u8]) -> TransportResult<Signature> {
+        self.0.sign_message(address, message).await
+    }
+
+    #[inline]
+    fn get_gas_price(&self) -> RpcCall<T, (), U128, u128> {
+        self.0.get_gas_price()
+    }
+
+    async fn get_max_priority_fee_per_gas(&self) -> TransportResult<u128> {
+        self.0.get_max_priority_fee_per_gas().await
+    }
+
+    async fn get_blob_base_fee(&self) -> TransportResult<u128> {
+        self.0.get_blob_base_fee().await
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        hash: TxHash,
+    ) -> TransportResult<Option<N::ReceiptResponse>> {
+        self.0.get_transaction_receipt(hash).await
+    }
+
+    async fn get_block_receipts(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> TransportResult<Option<Vec<N::ReceiptResponse>>> {
+        self.0.get_block_receipts(block).await
+    }
+
+    async fn get_block_receipts_by_id(
+        &self,
+        block: BlockId,
+    ) -> TransportResult<Option<Vec<N::ReceiptResponse>>> {
+        self.0.get_block_receipts_by_id(block).await
+    }
+
+    async fn fetch_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> TransportResult<Option<Vec<N::ReceiptResponse>>> {
+        self.0.fetch_block_receipts(block).await
+    }
+
+    async fn get_proven_receipt(
+        &self,
+        hash: TxHash,
+    ) -> TransportResult<Option<ProvenReceipt<N::ReceiptResponse>>> {
+        self.0.get_proven_receipt(hash).await
+    }
+
+    async fn get_uncle(
+        &self,
+        tag: BlockId,
+        idx: u64,
+    ) -> TransportResult<Option<Block<N::TransactionResponse>>> {
+        self.0.get_uncle(tag, idx).await
+    }
+
+    async fn get_uncle_count(&self, tag: BlockId) -> TransportResult<u64> {
+        self.0.get_uncle_count(tag).await
+    }
+
+    async fn syncing(&self) -> TransportResult<SyncStatus> {
+        self.0.syncing().await
+    }
+
+    async fn wait_until_synced(&self, timeout: Duration) -> TransportResult<()> {
+        self.0.wait_until_synced(timeout).await
+    }
+
+    #[inline]
+    fn call<'req>(&self, tx: &'req N::TransactionRequest) -> EthCall<'req, 'static, T, N> {
+        self.0.call(tx)
+    }
+
+    #[inline]
+    fn simulate<'req>(
+        &self,
+        payload: &'req SimulatePayload,
+    ) -> RpcWithBlock<T, &'req SimulatePayload, Vec<SimulatedBlock>> {
+        self.0.simulate(payload)
+    }
+
+    async fn call_many(
+        &self,
+        bundles: &[Bundle],
+        state_context: Option<StateContext>,
+        state_override: Option<&StateOverride>,
+    ) -> TransportResult<Vec<Vec<EthCallResponse>>> {
+        self.0.call_many(bundles, state_context, state_override).await
+    }
+
+    async fn get_fee_history(
+        &self,
+        block_count: u64,
+        last_block: BlockNumberOrTag,
+        reward_percentiles: &[f64],
+    ) -> TransportResult<FeeHistory> {
+        self.0.get_fee_history(block_count, last_block, reward_percentiles).await
+    }
+
+    #[inline]
+    async fn get_fee_history_range(
+        &self,
+        from: u64,
+        to: u64,
+        reward_percentiles: &[f64],
+    ) -> TransportResult<FeeHistory> {
+        self.0.get_fee_history_range(from, to, reward_percentiles).await
+    }
+
+    #[inline]
+    fn estimate_gas<'a>(
+        &self,
+        tx: &'a N::TransactionRequest,
+    ) -> RpcWithBlock<T, &'a N::TransactionRequest, U128, u128> {
+        self.0.estimate_gas(tx)
+    }
+
+    async fn estimate_gas_with_overrides(
+        &self,
+        tx: &N::TransactionRequest,
+        block: BlockId,
+        overrides: Option<&StateOverride>,
+        block_overrides: Option<&BlockOverrides>,
+    ) -> TransportResult<u128> {
+        self.0.estimate_gas_with_overrides(tx, block, overrides, block_overrides).await
+    }
+
+    async fn estimate_eip1559_fees(
+        &self,
+        estimator: Option<EstimatorFunction>,
+    ) -> TransportResult<Eip1559Estimation> {
+        self.0.estimate_eip1559_fees(estimator).await
+    }
+
+    async fn estimate_blob_fee(
+        &self,
+        urgency: BlobFeeUrgency,
+        params: BlobParams,
+    ) -> TransportResult<u128> {
+        self.0.estimate_blob_fee(urgency, params).await
+    }
+
+    #[inline]
+    fn get_proof(
+        &self,
+        address: Address,
+        keys: Vec<StorageKey>,
+    ) -> RpcWithBlock<T, (Address, Vec<StorageKey>), EIP1186AccountProofResponse> {
+        self.0.get_proof(address, keys)
+    }
+
+    #[inline]
+    fn create_access_list<'a>(
+        &self,
+        request: &'a N::TransactionRequest,
+    ) -> RpcWithBlock<T, &'a N::TransactionRequest, AccessListResult> {
+        self.0.create_access_list(request)
+    }
+
+    #[inline]
+    fn trace_call<'a, 'b>(
+        &self,
+        request: &'a N::TransactionRequest,
+        trace_type: &'b [TraceType],
+    ) -> RpcWithBlock<T, (&'a N::TransactionRequest, &'b [TraceType]), TraceResults> {
+        self.0.trace_call(request, trace_type)
+    }
+
+    #[inline]
+    fn trace_call_many<'a>(
+        &self,
+        request: TraceCallList<'a, N>,
+    ) -> RpcWithBlock<T, TraceCallList<'a, N>, TraceResults> {
+        self.0.trace_call_many(request)
+    }
+
+    async fn trace_transaction(
+        &self,
+        hash: TxHash,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
+        self.0.trace_transaction(hash).await
+    }
+
+    async fn trace_block(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> TransportResult<Vec<LocalizedTransactionTrace>> {
+        self.0.trace_block(block).await
+    }
+
+    async fn set_code(&self, address: Address, code: &str) -> TransportResult<()> {
+        self.0.set_code(address, code).await
+    }
+
+    async fn raw_request_dyn(
+        &self,
+        method: Cow<'static, str>,
+        params: &RawValue,
+    ) -> TransportResult<Box<RawValue>> {
+        self.0.raw_request_dyn(method, params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::mock_provider;
+
+    /// A layer answering `eth_accounts` itself.
+    struct FixedAccounts(RootProvider<BoxTransport>);
+
+    #[async_trait::async_trait]
+    impl Provider for FixedAccounts {
+        fn root(&self) -> &RootProvider<BoxTransport> {
+            &self.0
+        }
+
+        async fn get_accounts(&self) -> TransportResult<Vec<Address>> {
+            Ok(vec![Address::with_last_byte(1)])
+        }
+    }
+
+    #[tokio::test]
+    async fn forwards_overridden_methods() {
+        let root = mock_provider(|_, _| unreachable!("requests are answered by the layer"));
+        let erased = FixedAccounts(root).erased();
+        assert_eq!(erased.get_accounts().await.unwrap(), vec![Address::with_last_byte(1)]);
+    }
+}
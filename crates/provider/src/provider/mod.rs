@@ -1,6 +1,16 @@
+mod broadcast;
+pub use broadcast::Broadcast;
+
 mod call;
 pub use call::EthCall;
 
+mod cancel;
+pub use cancel::CancelOutcome;
+pub(crate) use cancel::{bump_fee, MIN_FEE_BUMP};
+
+mod erased;
+pub use erased::DynProvider;
+
 mod root;
 pub use root::RootProvider;
 
@@ -10,6 +20,9 @@ pub use sendable::SendableTx;
 mod r#trait;
 pub use r#trait::{FilterPollerBuilder, Provider, TraceCallList};
 
+mod node_wallet;
+pub use node_wallet::NodeWallet;
+
 mod wallet;
 pub use wallet::WalletProvider;
 
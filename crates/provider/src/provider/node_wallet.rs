@@ -0,0 +1,216 @@
+use crate::Provider;
+use alloy_eips::eip2718::Decodable2718;
+use alloy_json_rpc::RpcError;
+use alloy_network::{Ethereum, Network, NetworkSigner, TransactionBuilder};
+use alloy_primitives::Address;
+use alloy_transport::{Transport, TransportResult};
+use async_trait::async_trait;
+use std::{fmt, marker::PhantomData};
+
+/// A [`NetworkSigner`] signing with the accounts of the connected node, such as the unlocked
+/// accounts of Anvil, or the accounts of an external signer like clef.
+///
+/// The accounts are fetched with `eth_accounts` when the wallet is created, and transactions are
+/// signed by the node with `eth_signTransaction`, through the provider given to the wallet. This
+/// lets development setups use the same [`ProviderBuilder::signer`] path as local signers, and
+/// switch to a local key without changing the rest of the stack.
+///
+/// To have the node sign transactions as they are sent through `eth_sendTransaction` instead,
+/// see [`NodeSignerFiller`](crate::fillers::NodeSignerFiller).
+///
+/// # Example
+///
+/// ```
+/// # use alloy_provider::{NodeWallet, Provider, ProviderBuilder, RootProvider};
+/// # use alloy_rpc_types::TransactionRequest;
+/// # async fn test(url: url::Url) -> Result<(), Box<dyn std::error::Error>> {
+/// let node = ProviderBuilder::new().on_http(url);
+/// let wallet = NodeWallet::new(node.clone()).await?;
+/// let provider = ProviderBuilder::new().with_recommended_fillers().signer(wallet).on_provider(node);
+///
+/// provider.send_transaction(TransactionRequest::default()).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`ProviderBuilder::signer`]: crate::ProviderBuilder::signer
+pub struct NodeWallet<P, T, N = Ethereum> {
+    provider: P,
+    accounts: Vec<Address>,
+    default: Address,
+    _pd: PhantomData<fn() -> (T, N)>,
+}
+
+impl<P: Clone, T, N> Clone for NodeWallet<P, T, N> {
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider.clone(),
+            accounts: self.accounts.clone(),
+            default: self.default,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<P, T, N> fmt::Debug for NodeWallet<P, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeWallet")
+            .field("accounts", &self.accounts)
+            .field("default", &self.default)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, T, N> NodeWallet<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new wallet signing with the accounts of the node of `provider`, with its first
+    /// account as the default signer.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the accounts cannot be fetched, or if the node has no accounts.
+    pub async fn new(provider: P) -> TransportResult<Self> {
+        let accounts = provider.get_accounts().await?;
+        let default = *accounts
+            .first()
+            .ok_or_else(|| RpcError::local_usage_str("the node has no accounts to sign with"))?;
+        Ok(Self { provider, accounts, default, _pd: PhantomData })
+    }
+
+    /// Signs `request` with the account of its `from` address, or the default signer.
+    async fn sign(
+        &self,
+        mut request: N::TransactionRequest,
+    ) -> alloy_signer::Result<N::TxEnvelope> {
+        if request.from().is_none() {
+            request.set_from(self.default);
+        }
+        let raw =
+            self.provider.sign_transaction(request).await.map_err(alloy_signer::Error::other)?;
+        N::TxEnvelope::decode_2718_exact(&raw).map_err(alloy_signer::Error::other)
+    }
+}
+
+impl<P, T, N> NodeWallet<P, T, N> {
+    /// Sets the default signer, used for transactions without a `from` address.
+    ///
+    /// The address is added to the accounts of the wallet if the node did not list it, e.g. for
+    /// external signers only exposing accounts on demand.
+    pub fn with_default_signer(mut self, address: Address) -> Self {
+        if !self.accounts.contains(&address) {
+            self.accounts.push(address);
+        }
+        self.default = address;
+        self
+    }
+
+    /// Returns the accounts of the node.
+    pub fn accounts(&self) -> &[Address] {
+        &self.accounts
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<P, T, N> NetworkSigner<N> for NodeWallet<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+    N::TransactionRequest: From<N::UnsignedTx>,
+    N::UnsignedTx: Send,
+{
+    fn default_signer_address(&self) -> Address {
+        self.default
+    }
+
+    fn has_signer_for(&self, address: &Address) -> bool {
+        self.accounts.contains(address)
+    }
+
+    fn signer_addresses(&self) -> impl Iterator<Item = Address> {
+        self.accounts.iter().copied()
+    }
+
+    async fn sign_transaction_from(
+        &self,
+        sender: Address,
+        tx: N::UnsignedTx,
+    ) -> alloy_signer::Result<N::TxEnvelope> {
+        self.sign(<N::TransactionRequest as From<_>>::from(tx).with_from(sender)).await
+    }
+
+    async fn sign_request(
+        &self,
+        request: N::TransactionRequest,
+    ) -> alloy_signer::Result<N::TxEnvelope> {
+        self.sign(request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::mock, ProviderBuilder, RootProvider};
+    use alloy_consensus::{SignableTransaction, TxEnvelope, TypedTransaction};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_network::TxSignerSync;
+    use alloy_primitives::{hex, Bytes};
+    use alloy_rpc_client::ClientBuilder;
+    use alloy_rpc_types::TransactionRequest;
+    use alloy_signer_wallet::LocalWallet;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn signs_with_node_accounts() {
+        let wallet = LocalWallet::random();
+        let address = wallet.address();
+        let sent = Arc::new(Mutex::new(None));
+        let raw = sent.clone();
+        // A node holding the key of the wallet.
+        let service = mock(move |method, params| {
+            Ok(match method {
+                "eth_accounts" => json!([address]),
+                "eth_signTransaction" => {
+                    let (tx,): (TransactionRequest,) = serde_json::from_str(params).unwrap();
+                    assert_eq!(tx.from, Some(address));
+                    let Ok(TypedTransaction::Legacy(mut tx)) = tx.build_typed_tx() else {
+                        unreachable!()
+                    };
+                    let signature = wallet.sign_transaction_sync(&mut tx).unwrap();
+                    let tx: TxEnvelope = tx.into_signed(signature).into();
+                    json!(hex::encode_prefixed(tx.encoded_2718()))
+                }
+                "eth_sendRawTransaction" => {
+                    let (tx,): (Bytes,) = serde_json::from_str(params).unwrap();
+                    let tx = TxEnvelope::decode_2718_exact(&tx).unwrap();
+                    let hash = *tx.tx_hash();
+                    *raw.lock().unwrap() = Some(tx);
+                    json!(hash)
+                }
+                _ => unreachable!(),
+            })
+        });
+        let client = ClientBuilder::default().transport(service, true);
+        let node = RootProvider::new(client);
+        let node_wallet = NodeWallet::new(node.clone()).await.unwrap();
+        assert_eq!(node_wallet.accounts(), [address]);
+        let provider = ProviderBuilder::new().signer(node_wallet).on_provider(node);
+
+        let tx = TransactionRequest::default()
+            .with_to(Address::ZERO)
+            .with_nonce(0)
+            .with_gas_limit(21_000)
+            .with_gas_price(1);
+        let hash = *provider.send_transaction(tx).await.unwrap().tx_hash();
+        let Some(TxEnvelope::Legacy(signed)) = sent.lock().unwrap().take() else { unreachable!() };
+        assert_eq!(*signed.hash(), hash);
+        let signer = signed.signature().recover_address_from_prehash(&signed.signature_hash());
+        assert_eq!(signer.unwrap(), address);
+    }
+}
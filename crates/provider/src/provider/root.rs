@@ -1,14 +1,23 @@
+use crate::Provider;
 use crate::{
     chain::ChainStreamPoller,
     heart::{Heartbeat, HeartbeatHandle},
 };
-use alloy_network::{Ethereum, Network};
+use alloy_eips::eip2718::Decodable2718;
+use alloy_network::{Ethereum, Network, TransactionBuilder};
+use alloy_primitives::ChainId;
 use alloy_rpc_client::{BuiltInConnectionString, ClientBuilder, ClientRef, RpcClient, WeakClient};
-use alloy_transport::{BoxTransport, BoxTransportConnect, Transport, TransportError};
+use alloy_transport::{
+    BoxTransport, BoxTransportConnect, Transport, TransportError, TransportErrorKind,
+    TransportResult,
+};
 use std::{
     fmt,
     marker::PhantomData,
-    sync::{Arc, OnceLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
 };
 
 #[cfg(feature = "reqwest")]
@@ -107,6 +116,70 @@ impl<T: Transport + Clone, N: Network> RootProvider<T, N> {
         self.inner.client.transport()
     }
 
+    /// Enables or disables the chain ID guard.
+    ///
+    /// When enabled, transactions are checked before being sent to have the chain ID of the node,
+    /// fetched with [`Provider::cached_chain_id`] the first time. Transactions for another chain
+    /// are rejected with [`TransportErrorKind::ChainIdMismatch`], and are not sent. Transactions
+    /// without a chain ID, such as legacy transactions without [EIP-155] replay protection, are
+    /// sent without being checked, unless the chain ID of the node is required with
+    /// [`ProviderBuilder::expect_chain_id`].
+    ///
+    /// The guard is shared by all the providers built on this root provider, and by its clones.
+    ///
+    /// [`Provider::cached_chain_id`]: crate::Provider::cached_chain_id
+    /// [`ProviderBuilder::expect_chain_id`]: crate::ProviderBuilder::expect_chain_id
+    /// [`TransportErrorKind::ChainIdMismatch`]: alloy_transport::TransportErrorKind::ChainIdMismatch
+    /// [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+    pub fn set_chain_id_guard(&self, enabled: bool) {
+        self.inner.chain_id_guard.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns true if the chain ID guard is enabled, see
+    /// [`set_chain_id_guard`](Self::set_chain_id_guard).
+    pub fn chain_id_guard(&self) -> bool {
+        self.inner.chain_id_guard.load(Ordering::Relaxed)
+    }
+
+    /// Requires the node to be on the chain with the given ID, checked when its chain ID is
+    /// fetched by [`Provider::cached_chain_id`].
+    pub(crate) fn expect_chain_id(&self, chain_id: ChainId) {
+        let _ = self.inner.expected_chain_id.set(chain_id);
+    }
+
+    /// Returns the chain ID required by [`expect_chain_id`](Self::expect_chain_id), if any.
+    pub(crate) fn expected_chain_id(&self) -> Option<ChainId> {
+        self.inner.expected_chain_id.get().copied()
+    }
+
+    /// Rejects `chain_id` if the chain ID guard is enabled and it is not the chain ID of the node.
+    ///
+    /// Without a chain ID, the chain ID of the node is still checked against the expected one.
+    pub(crate) async fn guard_chain_id(&self, chain_id: Option<ChainId>) -> TransportResult<()> {
+        if !self.chain_id_guard() {
+            return Ok(());
+        }
+        let Some(chain_id) = chain_id.or_else(|| self.expected_chain_id()) else {
+            return Ok(());
+        };
+        TransportErrorKind::check_chain_id(chain_id, self.cached_chain_id().await?)
+    }
+
+    /// Rejects an encoded transaction for another chain than the node's, see
+    /// [`guard_chain_id`](Self::guard_chain_id).
+    ///
+    /// Transactions which cannot be decoded are left for the node to reject.
+    pub(crate) async fn guard_raw_chain_id(&self, encoded_tx: &[u8]) -> TransportResult<()> {
+        if !self.chain_id_guard() {
+            return Ok(());
+        }
+        let Ok(tx) = N::TxEnvelope::decode_2718(&mut &encoded_tx[..]) else {
+            return Ok(());
+        };
+        self.guard_chain_id(<N::TransactionRequest as From<N::TxEnvelope>>::from(tx).chain_id())
+            .await
+    }
+
     #[inline]
     pub(crate) fn get_heart(&self) -> &HeartbeatHandle {
         self.inner.heart.get_or_init(|| {
@@ -122,18 +195,35 @@ impl<T: Transport + Clone, N: Network> RootProvider<T, N> {
 pub(crate) struct RootProviderInner<T, N = Ethereum> {
     client: RpcClient<T>,
     heart: OnceLock<HeartbeatHandle>,
+    pub(crate) chain_id: OnceLock<ChainId>,
+    expected_chain_id: OnceLock<ChainId>,
+    chain_id_guard: AtomicBool,
     _network: PhantomData<N>,
 }
 
 impl<T, N> Clone for RootProviderInner<T, N> {
     fn clone(&self) -> Self {
-        Self { client: self.client.clone(), heart: self.heart.clone(), _network: PhantomData }
+        Self {
+            client: self.client.clone(),
+            heart: self.heart.clone(),
+            chain_id: self.chain_id.clone(),
+            expected_chain_id: self.expected_chain_id.clone(),
+            chain_id_guard: AtomicBool::new(self.chain_id_guard.load(Ordering::Relaxed)),
+            _network: PhantomData,
+        }
     }
 }
 
 impl<T, N> RootProviderInner<T, N> {
     pub(crate) fn new(client: RpcClient<T>) -> Self {
-        Self { client, heart: OnceLock::new(), _network: PhantomData }
+        Self {
+            client,
+            heart: OnceLock::new(),
+            chain_id: OnceLock::new(),
+            expected_chain_id: OnceLock::new(),
+            chain_id_guard: AtomicBool::new(false),
+            _network: PhantomData,
+        }
     }
 
     pub(crate) fn weak_client(&self) -> WeakClient<T> {
@@ -147,6 +237,13 @@ impl<T, N> RootProviderInner<T, N> {
 
 impl<T: Transport + Clone, N> RootProviderInner<T, N> {
     fn boxed(self) -> RootProviderInner<BoxTransport, N> {
-        RootProviderInner { client: self.client.boxed(), heart: self.heart, _network: PhantomData }
+        RootProviderInner {
+            client: self.client.boxed(),
+            heart: self.heart,
+            chain_id: self.chain_id,
+            expected_chain_id: self.expected_chain_id,
+            chain_id_guard: self.chain_id_guard,
+            _network: PhantomData,
+        }
     }
 }
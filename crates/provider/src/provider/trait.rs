@@ -1,26 +1,52 @@
 //! Ethereum JSON-RPC provider.
 
 use crate::{
-    utils::{self, Eip1559Estimation, EstimatorFunction},
-    EthCall, PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig, RootProvider,
-    RpcWithBlock, SendableTx,
+    log_filter::PersistentLogFilter,
+    provider::{
+        broadcast::{is_already_known, Broadcast},
+        call::CallParams,
+        cancel::{bump_fee, CancelOutcome, MIN_FEE_BUMP},
+    },
+    reorg::ReorgAwareBlocks,
+    storage::{self, StorageLocation},
+    utils::{self, BlobFeeUrgency, Eip1559Estimation, EstimatorFunction},
+    DynProvider, EthCall, PendingTransaction, PendingTransactionBuilder, PendingTransactionConfig,
+    RootProvider, RpcWithBlock, SendableTx,
 };
-use alloy_eips::eip2718::Encodable2718;
-use alloy_json_rpc::{RpcError, RpcParam, RpcReturn};
-use alloy_network::{Ethereum, Network};
+use alloy_consensus::{
+    trie::{ordered_trie_proof, ordered_trie_root},
+    TxType,
+};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    eip4844::BlobParams,
+};
+use alloy_json_rpc::{ErrorKind, RpcError, RpcParam, RpcReturn};
+use alloy_network::{Ethereum, FinalityPolicy, Network, ReceiptResponse, TransactionBuilder};
 use alloy_primitives::{
-    hex, Address, BlockHash, BlockNumber, Bytes, StorageKey, StorageValue, TxHash, B256, U128,
-    U256, U64,
+    hex, keccak256, Address, BlockHash, BlockNumber, Bytes, ChainId, Signature, StorageKey,
+    StorageValue, TxHash, B256, U128, U256, U64,
 };
-use alloy_rpc_client::{ClientRef, PollerBuilder, RpcCall, WeakClient};
+use alloy_rpc_client::{BatchRequest, ClientRef, PollerBuilder, RpcCall, WeakClient};
 use alloy_rpc_types::{
-    AccessListWithGasUsed, Block, BlockId, BlockNumberOrTag, EIP1186AccountProofResponse,
-    FeeHistory, Filter, FilterChanges, Log, SyncStatus,
+    simulate::{SimulatePayload, SimulatedBlock},
+    state::StateOverride,
+    AccessListResult, Account, Block, BlockId, BlockNumberOrTag, BlockOverrides, Bundle,
+    EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Filter, FilterChanges, Log,
+    ProvenReceipt, StateContext, SyncStatus,
 };
 use alloy_rpc_types_trace::parity::{LocalizedTransactionTrace, TraceResults, TraceType};
+use alloy_sol_types::SolType;
 use alloy_transport::{BoxTransport, Transport, TransportErrorKind, TransportResult};
+use futures::future::Either;
 use serde_json::value::RawValue;
-use std::borrow::Cow;
+use std::{borrow::Cow, time::Duration};
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::{sleep_until, Instant};
+// Timers of `std` and `tokio` are not available in the browser.
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::{std::Instant, tokio::sleep_until};
 
 /// A task that polls the provider with `eth_getFilterChanges`, returning a list of `R`.
 ///
@@ -71,6 +97,17 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.root().weak_client()
     }
 
+    /// Erases the type of this provider, keeping the behavior of all its layers.
+    ///
+    /// See [`DynProvider`] for more details.
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    fn erased(self) -> DynProvider<T, N>
+    where
+        Self: Sized + 'static,
+    {
+        DynProvider::new(self)
+    }
+
     /// Watch for the confirmation of a single pending transaction with the given configuration.
     ///
     /// Note that this is handled internally rather than calling any specific RPC method, and as
@@ -153,7 +190,10 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
     ///
     /// # Support
     ///
-    /// This endpoint is compatible only with Geth client version 1.11.0 or later.
+    /// This endpoint is compatible only with Geth client version 1.11.0 or later. Other nodes may
+    /// reject the subscription, or notify transaction hashes instead of bodies, which are then
+    /// discarded. [`stream::full_pending_transactions`](crate::stream::full_pending_transactions)
+    /// falls back to fetching the bodies of the hashes on such nodes.
     ///
     /// # Errors
     ///
@@ -228,6 +268,67 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.root().get_subscription(id).await
     }
 
+    /// Subscribe to a stream of syncing status changes.
+    ///
+    /// The node notifies when it starts syncing, possibly with its progress, and sends `false`
+    /// once it is done.
+    ///
+    /// # Errors
+    ///
+    /// This method is only available on `pubsub` clients, such as Websockets or IPC, and will
+    /// return a [`PubsubUnavailable`](TransportErrorKind::PubsubUnavailable) transport error if the
+    /// client does not support it.
+    ///
+    /// # Examples
+    ///
+    /// Wait for the node to finish syncing:
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::StreamExt;
+    ///
+    /// let sub = provider.subscribe_syncing().await?;
+    /// let mut stream = sub.into_stream();
+    /// while let Some(status) = stream.next().await {
+    ///     if !status.is_syncing() {
+    ///         break;
+    ///     }
+    ///     if let Some(progress) = status.metadata() {
+    ///         println!("synced up to block {}", progress.current_block);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_syncing(
+        &self,
+    ) -> TransportResult<alloy_pubsub::Subscription<alloy_rpc_types::pubsub::PubSubSyncStatus>>
+    {
+        self.root().pubsub_frontend()?;
+        let id = self.client().request("eth_subscribe", ("syncing",)).await?;
+        self.root().get_subscription(id).await
+    }
+
+    /// Subscribe to the sync status of the node, with the notifications of
+    /// [`subscribe_syncing`](Self::subscribe_syncing) converted to the [`SyncStatus`] returned by
+    /// [`syncing`](Self::syncing).
+    ///
+    /// # Errors
+    ///
+    /// This method is only available on `pubsub` clients, such as WebSockets or IPC, and will
+    /// return a [`PubsubUnavailable`](TransportErrorKind::PubsubUnavailable) transport error if the
+    /// client does not support it.
+    #[cfg(feature = "pubsub")]
+    async fn subscribe_sync_status(
+        &self,
+    ) -> TransportResult<futures::stream::BoxStream<'static, SyncStatus>> {
+        use futures::StreamExt;
+
+        let sub = self.subscribe_syncing().await?;
+        Ok(sub.into_stream().map(SyncStatus::from).boxed())
+    }
+
     /// Subscribe to an RPC event.
     #[cfg(feature = "pubsub")]
     #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
@@ -275,6 +376,30 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         Ok(PollerBuilder::new(self.weak_client(), "eth_getFilterChanges", (id,)))
     }
 
+    /// Watches the canonical chain by polling the chain tip, reporting the blocks removed by reorgs
+    /// before the blocks replacing them.
+    ///
+    /// Returns a builder of the stream. See [`ReorgAwareBlocks`] for more details.
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    fn watch_blocks_with_reorgs(&self) -> ReorgAwareBlocks<&Self, T, N>
+    where
+        Self: Sized,
+    {
+        ReorgAwareBlocks::new(self)
+    }
+
+    /// Watches the logs matching `filter` by polling an installed filter, installing it again
+    /// when the node loses it.
+    ///
+    /// Returns a builder of the stream. See [`PersistentLogFilter`] for more details.
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    fn watch_logs_persistent(&self, filter: &Filter) -> PersistentLogFilter<&Self, T, N>
+    where
+        Self: Sized,
+    {
+        PersistentLogFilter::new(self, filter.clone())
+    }
+
     /// Watch for new pending transaction by polling the provider with
     /// [`eth_getFilterChanges`](Self::get_filter_changes).
     ///
@@ -435,15 +560,34 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
     }
 
     /// Get a block by its number.
-    // TODO: Network associate
     async fn get_block_by_number(
         &self,
         number: BlockNumberOrTag,
         hydrate: bool,
-    ) -> TransportResult<Option<Block>> {
+    ) -> TransportResult<Option<Block<N::TransactionResponse>>> {
         self.client().request("eth_getBlockByNumber", (number, hydrate)).await
     }
 
+    /// Get the number of the latest final block under the given [`FinalityPolicy`].
+    ///
+    /// The default policy of the network is returned by [`Network::finality_policy`].
+    async fn get_final_block_number(
+        &self,
+        policy: &FinalityPolicy,
+    ) -> TransportResult<BlockNumber> {
+        match policy.block_tag() {
+            Some(tag) => self
+                .get_block_by_number(tag, false)
+                .await?
+                .and_then(|block| block.header.number)
+                .ok_or(RpcError::NullResp),
+            None => {
+                let head = self.get_block_number().await?;
+                Ok(policy.final_block_at(head).expect("policy does not rely on the node"))
+            }
+        }
+    }
+
     /// Broadcasts a transaction to the network.
     ///
     /// Returns a type that can be used to configure how and when to await the
@@ -471,6 +615,30 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.send_transaction_internal(SendableTx::Builder(tx)).await
     }
 
+    /// Sends `value` wei of the native currency to `to`.
+    ///
+    /// The transaction goes through the same filling and signing as
+    /// [`send_transaction`](Self::send_transaction), so the sender is the default signer of the
+    /// provider.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider, to: alloy_primitives::Address) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_primitives::utils::parse_ether;
+    ///
+    /// let receipt = provider.transfer(to, parse_ether("0.1")?).await?.get_receipt().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn transfer(
+        &self,
+        to: Address,
+        value: U256,
+    ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        self.send_transaction(N::TransactionRequest::default().with_to(to).with_value(value)).await
+    }
+
     ///
     /// This method allows [`ProviderLayer`] and [`TxFiller`] to bulid the
     /// transaction and send it to the network without changing user-facing
@@ -486,6 +654,7 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
     ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
         match tx {
             SendableTx::Builder(mut tx) => {
+                self.root().guard_chain_id(tx.chain_id()).await?;
                 alloy_network::TransactionBuilder::prep_for_submission(&mut tx);
                 let tx_hash = self.client().request("eth_sendTransaction", (tx,)).await?;
                 Ok(PendingTransactionBuilder::new(self.root(), tx_hash))
@@ -505,18 +674,142 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         &self,
         encoded_tx: &[u8],
     ) -> TransportResult<PendingTransactionBuilder<'_, T, N>> {
+        self.root().guard_raw_chain_id(encoded_tx).await?;
         let rlp_hex = hex::encode_prefixed(encoded_tx);
         let tx_hash = self.client().request("eth_sendRawTransaction", (rlp_hex,)).await?;
         Ok(PendingTransactionBuilder::new(self.root(), tx_hash))
     }
 
+    /// Broadcasts a raw transaction to the node of this provider and to every endpoint in
+    /// parallel, so that it reaches more of the network sooner.
+    ///
+    /// Endpoints can be regular nodes or services forwarding transactions to builders, as long as
+    /// they support `eth_sendRawTransaction`. Nodes that already know the transaction count as
+    /// successful submissions. Returns an error only if every submission failed, in which case the
+    /// first error is returned.
+    ///
+    /// The returned [`Broadcast`] reports the result of each submission, and watches the
+    /// transaction through this provider.
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    async fn send_raw_transaction_broadcast<P, U>(
+        &self,
+        encoded_tx: &[u8],
+        endpoints: &[P],
+    ) -> TransportResult<Broadcast<'_, T, N>>
+    where
+        Self: Sized,
+        P: Provider<U, N>,
+        U: Transport + Clone,
+    {
+        self.root().guard_raw_chain_id(encoded_tx).await?;
+        let rlp_hex = hex::encode_prefixed(encoded_tx);
+        let own = self.client().request::<_, TxHash>("eth_sendRawTransaction", (rlp_hex.clone(),));
+        let others = futures::future::join_all(endpoints.iter().map(|endpoint| {
+            endpoint.client().request::<_, TxHash>("eth_sendRawTransaction", (rlp_hex.clone(),))
+        }));
+        let (own, others) = futures::join!(own, others);
+
+        let results: Vec<_> = std::iter::once(own)
+            .chain(others)
+            .map(|result| match result {
+                Err(err) if !is_already_known(&err) => Err(err),
+                _ => Ok(()),
+            })
+            .collect();
+        if !results.iter().any(Result::is_ok) {
+            return Err(results.into_iter().find_map(Result::err).unwrap());
+        }
+        let tx_hash = keccak256(encoded_tx);
+        Ok(Broadcast::new(PendingTransactionBuilder::new(self.root(), tx_hash), results))
+    }
+
+    /// Cancels a pending transaction by replacing it with a 0-value transfer from its sender to
+    /// itself, with the same nonce.
+    ///
+    /// The replacement has the fee type of the original transaction, and its gas limit is
+    /// estimated by the node, as transfers cost more than 21000 gas on some networks. The fees of
+    /// the original transaction are bumped by `fee_bump` percent, which is raised to the 10%
+    /// minimum accepted by nodes, and further raised to the current network fees if they are
+    /// higher. The provider must be able to sign for the sender, e.g. with a
+    /// [`SignerFiller`](crate::fillers::SignerFiller). Blob transactions cannot be cancelled this
+    /// way, as nodes only replace them with other blob transactions, and are rejected.
+    ///
+    /// Waits until either transaction is included, and reports which one was. Returns
+    /// [`CancelOutcome::Included`] without sending anything if the original transaction is
+    /// already included.
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    async fn cancel_transaction(
+        &self,
+        tx_hash: TxHash,
+        fee_bump: u64,
+    ) -> TransportResult<CancelOutcome<N::ReceiptResponse>>
+    where
+        Self: Sized,
+        N::TransactionRequest: Default,
+    {
+        let tx: Option<alloy_rpc_types::Transaction> =
+            self.client().request("eth_getTransactionByHash", (tx_hash,)).await?;
+        let tx = tx.ok_or(RpcError::NullResp)?;
+        let original = PendingTransactionBuilder::new(self.root(), tx_hash);
+        if tx.block_hash.is_some() {
+            return Ok(CancelOutcome::Included { receipt: original.get_receipt().await? });
+        }
+
+        if tx.transaction_type == Some(TxType::Eip4844 as u8) {
+            return Err(TransportErrorKind::custom_str("blob transactions can't be cancelled"));
+        }
+
+        let fee_bump = fee_bump.max(MIN_FEE_BUMP);
+        let mut request = N::TransactionRequest::default()
+            .with_from(tx.from)
+            .with_to(tx.from)
+            .with_value(U256::ZERO)
+            .with_nonce(tx.nonce);
+        if let Some(chain_id) = tx.chain_id {
+            request.set_chain_id(chain_id);
+        }
+        if let Some(max_fee_per_gas) = tx.max_fee_per_gas {
+            let estimate = self.estimate_eip1559_fees(None).await?;
+            let priority_fee = bump_fee(tx.max_priority_fee_per_gas.unwrap_or_default(), fee_bump)
+                .max(estimate.max_priority_fee_per_gas);
+            let max_fee =
+                bump_fee(max_fee_per_gas, fee_bump).max(estimate.max_fee_per_gas).max(priority_fee);
+            request.set_max_priority_fee_per_gas(priority_fee);
+            request.set_max_fee_per_gas(max_fee);
+        } else {
+            let gas_price = self.get_gas_price().await?;
+            request
+                .set_gas_price(bump_fee(tx.gas_price.unwrap_or_default(), fee_bump).max(gas_price));
+            if tx.transaction_type == Some(TxType::Eip2930 as u8) {
+                request.set_access_list(tx.access_list.unwrap_or_default());
+            }
+        }
+        let gas_limit = self.estimate_gas(&request).await?;
+        request.set_gas_limit(gas_limit);
+
+        let cancel = self.send_transaction(request).await?;
+        let cancel_hash = *cancel.tx_hash();
+        let cancelled = std::pin::pin!(cancel.get_receipt());
+        let included = std::pin::pin!(original.get_receipt());
+        match futures::future::select(cancelled, included).await {
+            Either::Left((receipt, _)) => {
+                Ok(CancelOutcome::Cancelled { tx_hash: cancel_hash, receipt: receipt? })
+            }
+            Either::Right((receipt, _)) => Ok(CancelOutcome::Included { receipt: receipt? }),
+        }
+    }
+
     /// Gets the balance of the account at the specified tag, which defaults to latest.
     fn get_balance(&self, address: Address) -> RpcWithBlock<T, Address, U256> {
         RpcWithBlock::new(self.weak_client(), "eth_getBalance", address)
     }
 
     /// Gets a block by either its hash, tag, or number, with full transactions or only hashes.
-    async fn get_block(&self, id: BlockId, full: bool) -> TransportResult<Option<Block>> {
+    async fn get_block(
+        &self,
+        id: BlockId,
+        full: bool,
+    ) -> TransportResult<Option<Block<N::TransactionResponse>>> {
         match id {
             BlockId::Hash(hash) => self.get_block_by_hash(hash.into(), full).await,
             BlockId::Number(number) => self.get_block_by_number(number, full).await,
@@ -528,7 +821,7 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         &self,
         hash: BlockHash,
         full: bool,
-    ) -> TransportResult<Option<Block>> {
+    ) -> TransportResult<Option<Block<N::TransactionResponse>>> {
         self.client().request("eth_getBlockByHash", (hash, full)).await
     }
 
@@ -542,6 +835,33 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_chainId", ()).map_resp(crate::utils::convert_u64)
     }
 
+    /// Gets the chain ID, fetching it with [`get_chain_id`](Self::get_chain_id) the first time
+    /// and caching it in the [`RootProvider`] afterwards.
+    ///
+    /// Once cached, the chain ID is also returned by [`chain_id`](Self::chain_id).
+    ///
+    /// Fails with [`TransportErrorKind::ChainIdMismatch`] without caching the chain ID if it is not
+    /// the one required by [`ProviderBuilder::expect_chain_id`].
+    ///
+    /// [`ProviderBuilder::expect_chain_id`]: crate::ProviderBuilder::expect_chain_id
+    async fn cached_chain_id(&self) -> TransportResult<ChainId> {
+        let cache = &self.root().inner.chain_id;
+        if let Some(chain_id) = cache.get() {
+            return Ok(*chain_id);
+        }
+        let chain_id = self.get_chain_id().await?;
+        if let Some(expected) = self.root().expected_chain_id() {
+            TransportErrorKind::check_chain_id(expected, chain_id)?;
+        }
+        Ok(*cache.get_or_init(|| chain_id))
+    }
+
+    /// Returns the chain ID cached by [`cached_chain_id`](Self::cached_chain_id), or `None` if
+    /// it has not been fetched yet.
+    fn chain_id(&self) -> Option<ChainId> {
+        self.root().inner.chain_id.get().copied()
+    }
+
     /// Gets the network ID. Same as `eth_chainId`.
     fn get_net_version(&self) -> RpcCall<T, (), U64, u64> {
         self.client().request("net_version", ()).map_resp(crate::utils::convert_u64)
@@ -556,11 +876,63 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         RpcWithBlock::new(self.weak_client(), "eth_getStorageAt", (address, key))
     }
 
+    /// Gets the value stored at `location` of [Address] in the given block, decoded as the
+    /// Solidity value type `V`.
+    ///
+    /// Packed values are extracted from their slot using the offset of the location. See the
+    /// [`storage`](crate::storage) module for computing the locations of state variables.
+    #[auto_impl(keep_default_for(&, &mut, Rc, Arc, Box))]
+    async fn get_storage_value<V, L>(
+        &self,
+        address: Address,
+        location: L,
+        block: BlockId,
+    ) -> TransportResult<V::RustType>
+    where
+        Self: Sized,
+        V: SolType,
+        L: Into<StorageLocation> + Send,
+    {
+        let location = location.into();
+        let value = self.get_storage_at(address, location.slot).block_id(block).await?;
+        storage::decode_storage_value::<V>(value, location.offset)
+            .map_err(|err| RpcError::local_usage_str(&err.to_string()))
+    }
+
     /// Gets the bytecode located at the corresponding [Address].
     fn get_code_at(&self, address: Address) -> RpcWithBlock<T, Address, Bytes> {
         RpcWithBlock::new(self.weak_client(), "eth_getCode", address)
     }
 
+    /// Gets the balance, nonce and code hash of an account at the given block.
+    ///
+    /// If the node doesn't know `eth_getAccount`, failing with a
+    /// [`MethodNotFound`](ErrorKind::MethodNotFound) error, this falls back to fetching the nonce,
+    /// balance and code of the account in a single batch request. The storage root of the account
+    /// is then unknown.
+    async fn get_account(&self, address: Address, block: BlockId) -> TransportResult<Account> {
+        match self.client().request("eth_getAccount", (address, block)).await {
+            Err(err) if err.error_kind() == Some(ErrorKind::MethodNotFound) => {
+                debug!(%err, "eth_getAccount not found, fetching the account fields individually");
+            }
+            res => return res,
+        }
+
+        let mut batch = BatchRequest::new(self.client());
+        let nonce = batch.add_call::<_, U64>("eth_getTransactionCount", &(address, block))?;
+        let balance = batch.add_call::<_, U256>("eth_getBalance", &(address, block))?;
+        let code = batch.add_call::<_, Bytes>("eth_getCode", &(address, block))?;
+        batch.send().await?;
+
+        let (nonce, balance, code) = futures::try_join!(nonce, balance, code)?;
+        Ok(Account {
+            balance,
+            nonce: utils::convert_u64(nonce),
+            code_hash: keccak256(code),
+            storage_root: None,
+        })
+    }
+
     /// Gets a transaction by its [TxHash].
     async fn get_transaction_by_hash(
         &self,
@@ -569,8 +941,38 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getTransactionByHash", (hash,)).await
     }
 
+    /// Gets the EIP-2718 encoded bytes of a transaction by its [TxHash].
+    ///
+    /// The result can be decoded with [`decode_raw_transaction`](Self::decode_raw_transaction).
+    async fn get_raw_transaction_by_hash(&self, hash: TxHash) -> TransportResult<Option<Bytes>> {
+        self.client().request("eth_getRawTransactionByHash", (hash,)).await
+    }
+
+    /// Decodes an EIP-2718 encoded transaction of the provider's network, e.g. the result of
+    /// [`get_raw_transaction_by_hash`](Self::get_raw_transaction_by_hash) or a raw transaction
+    /// about to be sent with [`send_raw_transaction`](Self::send_raw_transaction).
+    ///
+    /// Fails with a local usage error if the bytes are not exactly one valid transaction.
+    fn decode_raw_transaction(&self, raw: &[u8]) -> TransportResult<N::TxEnvelope> {
+        N::TxEnvelope::decode_2718_exact(raw).map_err(RpcError::local_usage)
+    }
+
     /// Retrieves a [`Vec<Log>`] with the given [Filter].
+    ///
+    /// Fails with a local usage error if the block range of the filter is empty.
     async fn get_logs(&self, filter: &Filter) -> TransportResult<Vec<Log>> {
+        filter.validate().map_err(RpcError::local_usage)?;
+        self.client().request("eth_getLogs", (filter,)).await
+    }
+
+    /// Retrieves the logs of the block of the given hash matching the addresses and topics of
+    /// `filter`.
+    ///
+    /// Unlike a query by block number, this never returns the logs of another block if the chain
+    /// reorganizes: the node fails if the block is not part of the chain. Fails with a local usage
+    /// error if `filter` has a from or to block.
+    async fn get_logs_at(&self, block_hash: B256, filter: &Filter) -> TransportResult<Vec<Log>> {
+        let filter = filter.clone().try_at_block_hash(block_hash).map_err(RpcError::local_usage)?;
         self.client().request("eth_getLogs", (filter,)).await
     }
 
@@ -580,6 +982,31 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_accounts", ()).await
     }
 
+    /// Signs a transaction with an account of the remote node, without sending it, and returns
+    /// its EIP-2718 encoding.
+    ///
+    /// The node must hold the key of the `from` account, such as an unlocked development account
+    /// or an account of an external signer like clef.
+    async fn sign_transaction(&self, mut tx: N::TransactionRequest) -> TransportResult<Bytes> {
+        tx.prep_for_submission();
+        self.client()
+            .request("eth_signTransaction", (tx,))
+            .await
+            .map(utils::SignedTransaction::into_raw)
+    }
+
+    /// Signs a message with an account of the remote node using `personal_sign`, which prefixes
+    /// it as specified by [EIP-191].
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    async fn sign_message(&self, address: Address, message: &[u8]) -> TransportResult<Signature> {
+        let signature: Bytes = self
+            .client()
+            .request("personal_sign", (hex::encode_prefixed(message), address))
+            .await?;
+        Signature::try_from(signature.as_ref()).map_err(RpcError::local_usage)
+    }
+
     /// Gets the current gas price in wei.
     fn get_gas_price(&self) -> RpcCall<T, (), U128, u128> {
         self.client().request("eth_gasPrice", ()).map_resp(crate::utils::convert_u128)
@@ -614,8 +1041,130 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_getBlockReceipts", (block,)).await
     }
 
+    /// Gets the receipts of all transactions in the block specified by the tag [BlockId], which
+    /// can also select the block by its hash.
+    async fn get_block_receipts_by_id(
+        &self,
+        block: BlockId,
+    ) -> TransportResult<Option<Vec<N::ReceiptResponse>>> {
+        self.client().request("eth_getBlockReceipts", (block,)).await
+    }
+
+    /// Gets the receipts of all transactions in the block specified by the tag [BlockId], like
+    /// [`get_block_receipts_by_id`](Self::get_block_receipts_by_id).
+    ///
+    /// If the node doesn't support `eth_getBlockReceipts`, this falls back to fetching the
+    /// block's transaction hashes and then all of their receipts in a single batch request.
+    async fn fetch_block_receipts(
+        &self,
+        block: BlockId,
+    ) -> TransportResult<Option<Vec<N::ReceiptResponse>>> {
+        match self.get_block_receipts_by_id(block).await {
+            Err(err) if err.is_error_resp() => {
+                debug!(%err, "eth_getBlockReceipts failed, fetching receipts individually");
+            }
+            res => return res,
+        }
+
+        let Some(block) = self.get_block(block, false).await? else {
+            return Ok(None);
+        };
+        let hashes = block.transactions.as_hashes().unwrap_or_default();
+        if hashes.is_empty() {
+            return Ok(Some(Vec::new()));
+        }
+
+        let client = self.client();
+        let mut batch = BatchRequest::new(client);
+        let waiters = hashes
+            .iter()
+            .map(|hash| {
+                batch.add_call::<_, Option<N::ReceiptResponse>>(
+                    "eth_getTransactionReceipt",
+                    &(hash,),
+                )
+            })
+            .collect::<TransportResult<Vec<_>>>()?;
+        batch.send().await?;
+
+        let mut receipts = Vec::with_capacity(waiters.len());
+        for waiter in waiters {
+            receipts.push(waiter.await?.ok_or(RpcError::NullResp)?);
+        }
+        Ok(Some(receipts))
+    }
+
+    /// Gets a transaction receipt along with the proof of its inclusion in the receipts root of
+    /// its block, or `None` if the transaction is unknown or pending.
+    ///
+    /// The proof is built from the receipts of the block, fetched with
+    /// [`fetch_block_receipts`](Self::fetch_block_receipts), which are checked against the
+    /// receipts root of the block header, itself checked against the block hash. Fails with [`RpcError::UnsupportedFeature`] if the
+    /// receipts of the network cannot be [encoded](ReceiptResponse::encoded_2718).
+    async fn get_proven_receipt(
+        &self,
+        hash: TxHash,
+    ) -> TransportResult<Option<ProvenReceipt<N::ReceiptResponse>>> {
+        let Some(receipt) = self.get_transaction_receipt(hash).await? else {
+            return Ok(None);
+        };
+        let (Some(block_hash), Some(transaction_index)) =
+            (receipt.block_hash(), receipt.transaction_index())
+        else {
+            return Ok(None);
+        };
+
+        let (receipts, block) = futures::try_join!(
+            self.fetch_block_receipts(block_hash.into()),
+            self.get_block_by_hash(block_hash, false)
+        )?;
+        let (Some(receipts), Some(block)) = (receipts, block) else {
+            // The block was reorged out since the receipt was fetched.
+            return Ok(None);
+        };
+        if block.header.hash_slow().ok() != Some(block_hash) {
+            return Err(RpcError::local_usage_str(
+                "the header of the block does not match its hash",
+            ));
+        }
+        let receipts_root = block.header.receipts_root;
+        let Some(encoded) =
+            receipts.iter().map(ReceiptResponse::encoded_2718).collect::<Option<Vec<_>>>()
+        else {
+            return Err(RpcError::UnsupportedFeature("encoding the receipts of the network"));
+        };
+        if ordered_trie_root(&encoded) != receipts_root {
+            return Err(RpcError::local_usage_str(
+                "the receipts of the block do not match its receipts root",
+            ));
+        }
+
+        let Some(encoded_receipt) = receipt.encoded_2718() else {
+            return Err(RpcError::UnsupportedFeature("encoding the receipts of the network"));
+        };
+        let index = usize::try_from(transaction_index).unwrap_or(usize::MAX);
+        if encoded.get(index) != Some(&encoded_receipt) {
+            return Err(RpcError::local_usage_str(
+                "the receipt does not match the receipts of its block",
+            ));
+        }
+        let proof = ordered_trie_proof(&encoded, index).expect("index within the receipts");
+        Ok(Some(ProvenReceipt {
+            receipt,
+            encoded_receipt: encoded_receipt.into(),
+            block_hash,
+            transaction_index,
+            receipts_root,
+            proof,
+        }))
+    }
+
     /// Gets an uncle block through the tag [BlockId] and index [u64].
-    async fn get_uncle(&self, tag: BlockId, idx: u64) -> TransportResult<Option<Block>> {
+    async fn get_uncle(
+        &self,
+        tag: BlockId,
+        idx: u64,
+    ) -> TransportResult<Option<Block<N::TransactionResponse>>> {
         let idx = U64::from(idx);
         match tag {
             BlockId::Hash(hash) => {
@@ -650,6 +1199,29 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         self.client().request("eth_syncing", ()).await
     }
 
+    /// Waits until the node reports that it is not syncing, polling [`syncing`](Self::syncing)
+    /// at the poll interval of the client.
+    ///
+    /// Fails with a [`Timeout`](TransportErrorKind::Timeout) error if the node is still syncing
+    /// after `timeout`.
+    async fn wait_until_synced(&self, timeout: Duration) -> TransportResult<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let status = self.syncing().await?;
+            if !status.is_syncing() {
+                return Ok(());
+            }
+            if let Some(info) = status.info() {
+                debug!(current = %info.current_block, highest = %info.highest_block, "node is syncing");
+            }
+            let next_poll = Instant::now() + self.client().poll_interval();
+            if next_poll > deadline {
+                return Err(TransportErrorKind::timeout(timeout));
+            }
+            sleep_until(next_poll).await;
+        }
+    }
+
     /// Execute a smart contract call with a transaction request and state
     /// overrides, without publishing a transaction.
     ///
@@ -691,6 +1263,83 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         EthCall::new(self.weak_client(), tx)
     }
 
+    /// Simulates a sequence of blocks of calls with `eth_simulateV1`, without publishing any
+    /// transaction.
+    ///
+    /// Each simulated block is executed on top of the state of the previous one, starting from the
+    /// block set with [`RpcWithBlock::block_id`], which defaults to the latest block.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use alloy_provider::Provider;
+    /// # use alloy_rpc_types::simulate::{SimBlock, SimulatePayload};
+    /// # use alloy_transport::BoxTransport;
+    /// # async fn example<P: Provider<BoxTransport>>(
+    /// #    provider: P,
+    /// # ) -> Result<(), Box<dyn std::error::Error>> {
+    /// # let tx = alloy_rpc_types::transaction::TransactionRequest::default();
+    /// let payload = SimulatePayload::default().block(SimBlock::default().call(tx));
+    /// let blocks = provider.simulate(&payload).await?;
+    /// for call in &blocks[0].calls {
+    ///     println!("status: {}, gas used: {}", call.status, call.gas_used);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// Not all client implementations support `eth_simulateV1`.
+    #[doc(alias = "eth_simulateV1")]
+    fn simulate<'req>(
+        &self,
+        payload: &'req SimulatePayload,
+    ) -> RpcWithBlock<T, &'req SimulatePayload, Vec<SimulatedBlock>> {
+        RpcWithBlock::new(self.weak_client(), "eth_simulateV1", payload)
+    }
+
+    /// Executes bundles of calls with `eth_callMany`, without publishing any transaction.
+    ///
+    /// The bundles are executed in order on top of the state selected by `state_context`, which
+    /// defaults to the latest block, each call seeing the changes of the previous ones. Each
+    /// [`Bundle`] may override the block environment of its calls, while `state_override` applies
+    /// to the starting state. The result contains one list of responses per bundle, with one
+    /// response per call.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # use alloy_provider::Provider;
+    /// # use alloy_rpc_types::{Bundle, TransactionRequest};
+    /// # use alloy_transport::BoxTransport;
+    /// # async fn example<P: Provider<BoxTransport>>(
+    /// #    provider: P,
+    /// #    approve: TransactionRequest,
+    /// #    swap: TransactionRequest,
+    /// # ) -> Result<(), Box<dyn std::error::Error>> {
+    /// // The swap is executed on top of the approval.
+    /// let bundle = Bundle::from(vec![approve, swap]);
+    /// let responses = provider.call_many(&[bundle], None, None).await?;
+    /// let swap_output = responses[0][1].clone().ensure_ok()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// Not all client implementations support `eth_callMany`, which is mostly found on nodes
+    /// without [`simulate`](Self::simulate) support, such as Erigon.
+    #[doc(alias = "eth_callMany")]
+    async fn call_many(
+        &self,
+        bundles: &[Bundle],
+        state_context: Option<StateContext>,
+        state_override: Option<&StateOverride>,
+    ) -> TransportResult<Vec<Vec<EthCallResponse>>> {
+        self.client().request("eth_callMany", (bundles, state_context, state_override)).await
+    }
+
     /// Returns a collection of historical gas information [FeeHistory] which
     /// can be used to calculate the EIP1559 fields `maxFeePerGas` and `maxPriorityFeePerGas`.
     /// `block_count` can range from 1 to 1024 blocks in a single request.
@@ -705,6 +1354,47 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
             .await
     }
 
+    /// Returns the [FeeHistory] of the blocks `from..=to`, which may span more than the
+    /// [`MAX_FEE_HISTORY_BLOCKS`](utils::MAX_FEE_HISTORY_BLOCKS) blocks nodes return at once.
+    ///
+    /// The range is split into chunks, at most
+    /// [`MAX_CONCURRENT_FEE_HISTORY_REQUESTS`](utils::MAX_CONCURRENT_FEE_HISTORY_REQUESTS) of
+    /// which are fetched concurrently, and their responses are stitched together with
+    /// [`FeeHistory::append`]. Fails if the node does not return the fee history of
+    /// every block of the range, e.g. because it pruned the oldest ones.
+    async fn get_fee_history_range(
+        &self,
+        from: u64,
+        to: u64,
+        reward_percentiles: &[f64],
+    ) -> TransportResult<FeeHistory> {
+        use futures::{StreamExt, TryStreamExt};
+
+        if from > to {
+            return Err(RpcError::local_usage_str("the fee history range is empty"));
+        }
+        let chunks = (from..=to).step_by(utils::MAX_FEE_HISTORY_BLOCKS as usize).map(|start| {
+            let end = to.min(start + (utils::MAX_FEE_HISTORY_BLOCKS - 1));
+            self.get_fee_history(end - start + 1, end.into(), reward_percentiles)
+        });
+        let chunks: Vec<_> = futures::stream::iter(chunks)
+            .buffered(utils::MAX_CONCURRENT_FEE_HISTORY_REQUESTS)
+            .try_collect()
+            .await?;
+        let mut chunks = chunks.into_iter();
+
+        let mut fee_history = chunks.next().expect("the range is not empty");
+        for chunk in chunks {
+            fee_history.append(chunk).map_err(RpcError::local_usage)?;
+        }
+        if fee_history.oldest_block != from || fee_history.block_count() as u64 != to - from + 1 {
+            return Err(RpcError::local_usage_str(
+                "the fee history does not cover the requested blocks",
+            ));
+        }
+        Ok(fee_history)
+    }
+
     /// Estimate the gas needed for a transaction.
     fn estimate_gas<'a>(
         &self,
@@ -714,6 +1404,23 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
             .map_resp(crate::utils::convert_u128)
     }
 
+    /// Estimate the gas needed for a transaction, with state and block overrides applied.
+    ///
+    /// # Note
+    ///
+    /// Not all client implementations support overrides on `eth_estimateGas`.
+    async fn estimate_gas_with_overrides(
+        &self,
+        tx: &N::TransactionRequest,
+        block: BlockId,
+        overrides: Option<&StateOverride>,
+        block_overrides: Option<&BlockOverrides>,
+    ) -> TransportResult<u128> {
+        let params =
+            CallParams { data: tx, block, overrides, block_overrides, empty_overrides: false };
+        self.client().request("eth_estimateGas", params).await.map(crate::utils::convert_u128)
+    }
+
     /// Estimates the EIP1559 `maxFeePerGas` and `maxPriorityFeePerGas` fields.
     ///
     /// Receives an optional [EstimatorFunction] that can be used to modify
@@ -751,6 +1458,28 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
         ))
     }
 
+    /// Estimates the `max_fee_per_blob_gas` of an EIP-4844 transaction, projecting the blob base
+    /// fee of the next blocks from the blob gas used by the last ones.
+    ///
+    /// The fees are projected with the blob parameters of the chain's current fork, e.g.
+    /// [`BlobParams::prague`]. See [`BlobFeeUrgency`] for how long the estimate remains
+    /// sufficient. Fails with [`RpcError::UnsupportedFeature`] if the next block does not support
+    /// blobs.
+    async fn estimate_blob_fee(
+        &self,
+        urgency: BlobFeeUrgency,
+        params: BlobParams,
+    ) -> TransportResult<u128> {
+        let fee_history = self
+            .get_fee_history(
+                utils::EIP4844_FEE_ESTIMATION_PAST_BLOCKS,
+                BlockNumberOrTag::Latest,
+                &[],
+            )
+            .await?;
+        urgency.estimate(&fee_history, params).ok_or(RpcError::UnsupportedFeature("eip4844"))
+    }
+
     /// Get the account and storage values of the specified account including the merkle proofs.
     ///
     /// This call can be used to verify that the data has not been tampered with.
@@ -764,11 +1493,14 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
 
     /// Create an [EIP-2930] access list.
     ///
+    /// If the transaction fails, the node still returns the access list generated so far, with
+    /// the error in [`AccessListResult::error`]; see [`AccessListResult::ensure_ok`].
+    ///
     /// [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
     fn create_access_list<'a>(
         &self,
         request: &'a N::TransactionRequest,
-    ) -> RpcWithBlock<T, &'a N::TransactionRequest, AccessListWithGasUsed> {
+    ) -> RpcWithBlock<T, &'a N::TransactionRequest, AccessListResult> {
         RpcWithBlock::new(self.weak_client(), "eth_createAccessList", request)
     }
 
@@ -850,6 +1582,21 @@ pub trait Provider<T: Transport + Clone = BoxTransport, N: Network = Ethereum>:
     /// # Ok(())
     /// # }
     /// ```
+    ///
+    /// Large responses can be returned as a [`LazyValue`](alloy_json_rpc::LazyValue), to be
+    /// deserialized later, e.g. on a thread pool, instead of while awaiting the request:
+    ///
+    /// ```no_run
+    /// # async fn example(provider: impl alloy_provider::Provider) -> Result<(), Box<dyn std::error::Error>> {
+    /// use alloy_json_rpc::LazyValue;
+    /// use alloy_rpc_types::{Block, BlockNumberOrTag};
+    ///
+    /// let params = (BlockNumberOrTag::Latest, true);
+    /// let lazy: LazyValue<Block> = provider.raw_request("eth_getBlockByNumber".into(), params).await?;
+    /// let block = std::thread::spawn(move || lazy.decode()).join().unwrap()?;
+    /// # Ok(())
+    /// # }
+    /// ```
     async fn raw_request<P, R>(&self, method: Cow<'static, str>, params: P) -> TransportResult<R>
     where
         P: RpcParam,
@@ -921,7 +1668,11 @@ impl<T: Transport + Clone, N: Network> Provider<T, N> for RootProvider<T, N> {
 #[allow(clippy::missing_const_for_fn)]
 mod tests {
     use super::*;
-    use crate::{ProviderBuilder, WalletProvider};
+    use crate::{
+        ext::AnvilApi,
+        test_utils::{error_resp, mock, mock_provider},
+        ProviderBuilder, WalletProvider,
+    };
     use alloy_node_bindings::Anvil;
     use alloy_primitives::{address, b256, bytes};
     use alloy_rpc_types::request::TransactionRequest;
@@ -966,6 +1717,30 @@ mod tests {
         assert_eq!(0, num);
     }
 
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn erased_provider() {
+        use crate::ext::AnvilApi;
+        use alloy_network::TransactionBuilder;
+
+        init_tracing();
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_signer();
+        let from = provider.default_signer_address();
+
+        let erased: DynProvider<alloy_transport_http::Http<reqwest::Client>> = provider.erased();
+        erased.anvil_mine(Some(1), None).await.unwrap();
+
+        // The fillers of the erased provider are still applied.
+        let tx = TransactionRequest::default()
+            .with_from(from)
+            .with_to(address!("deadbeef00000000deadbeef00000000deadbeef"))
+            .with_value(U256::from(100));
+        let receipt =
+            erased.clone().send_transaction(tx).await.unwrap().get_receipt().await.unwrap();
+        assert!(receipt.status());
+        assert_eq!(erased.get_block_number().await.unwrap(), 2);
+    }
+
     #[cfg(feature = "ws")]
     #[tokio::test]
     async fn subscribe_blocks_http() {
@@ -974,7 +1749,7 @@ mod tests {
         let provider = ProviderBuilder::new().on_anvil_with_config(|a| a.block_time(1));
 
         let err = provider.subscribe_blocks().await.unwrap_err();
-        let alloy_json_rpc::RpcError::Transport(TransportErrorKind::PubsubUnavailable) = err else {
+        let Some(TransportErrorKind::PubsubUnavailable) = err.as_transport_err() else {
             panic!("{err:?}");
         };
     }
@@ -1022,6 +1797,36 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn expect_chain_id() {
+        init_tracing();
+        let anvil = Anvil::new().spawn();
+        let url = anvil.endpoint();
+
+        let provider = ProviderBuilder::new().expect_chain_id(31337).on_builtin(&url).await;
+        assert_eq!(provider.unwrap().get_chain_id().await.unwrap(), 31337);
+
+        let err = ProviderBuilder::new().expect_chain_id(1).on_builtin(&url).await.unwrap_err();
+        assert!(matches!(
+            err.as_transport_err(),
+            Some(TransportErrorKind::ChainIdMismatch { expected: 1, actual: 31337 })
+        ));
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn expect_chain_id_ws() {
+        init_tracing();
+        let anvil = Anvil::new().spawn();
+        let ws = alloy_rpc_client::WsConnect::new(anvil.ws_endpoint());
+
+        let err = ProviderBuilder::new().expect_chain_id(1).on_ws(ws).await.unwrap_err();
+        assert!(matches!(
+            err.as_transport_err(),
+            Some(TransportErrorKind::ChainIdMismatch { expected: 1, actual: 31337 })
+        ));
+    }
+
     #[tokio::test]
     #[cfg(feature = "ws")]
     async fn subscribe_blocks_ws_remote() {
@@ -1064,6 +1869,19 @@ mod tests {
         assert_eq!(hash1, hash2);
     }
 
+    #[tokio::test]
+    async fn transfers_value() {
+        init_tracing();
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_signer();
+        let to = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let before = provider.get_balance(to).await.unwrap();
+
+        let receipt =
+            provider.transfer(to, U256::from(100)).await.unwrap().get_receipt().await.unwrap();
+        assert!(receipt.status());
+        assert_eq!(provider.get_balance(to).await.unwrap(), before + U256::from(100));
+    }
+
     #[tokio::test]
     async fn gets_block_number() {
         init_tracing();
@@ -1185,6 +2003,103 @@ mod tests {
         assert_eq!(storage, U256::ZERO);
     }
 
+    #[tokio::test]
+    async fn cancels_transaction() {
+        init_tracing();
+        let provider = ProviderBuilder::new().with_recommended_fillers().on_anvil_with_signer();
+        provider.anvil_set_auto_mine(false).await.unwrap();
+
+        let tx = TransactionRequest::default()
+            .with_to(address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045"))
+            .with_value(U256::from(100));
+        let pending = provider.send_transaction(tx).await.unwrap();
+        let tx_hash = *pending.tx_hash();
+
+        let mine = async {
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            provider.anvil_mine(Some(1), None).await.unwrap();
+        };
+        let (outcome, ()) = tokio::join!(provider.cancel_transaction(tx_hash, 10), mine);
+        let outcome = outcome.unwrap();
+        assert!(outcome.is_cancelled());
+        assert!(provider.get_transaction_receipt(tx_hash).await.unwrap().is_none());
+        assert_eq!(
+            provider.get_transaction_count(provider.default_signer_address()).await.unwrap(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn cancels_with_original_tx_type() {
+        use std::sync::{Arc, Mutex};
+
+        let sent = Arc::new(Mutex::new(None));
+        let sent_tx = sent.clone();
+        let provider = mock_provider::<Ethereum, _>(move |method, params| {
+            Ok(match method {
+                "eth_getTransactionByHash" => serde_json::json!(alloy_rpc_types::Transaction {
+                    from: Address::with_last_byte(1),
+                    nonce: 7,
+                    gas: 100_000,
+                    gas_price: Some(100),
+                    chain_id: Some(42_161),
+                    access_list: Some(alloy_rpc_types::AccessList(vec![
+                        alloy_rpc_types::AccessListItem {
+                            address: Address::with_last_byte(2),
+                            storage_keys: Vec::new(),
+                        },
+                    ])),
+                    transaction_type: Some(1),
+                    ..Default::default()
+                }),
+                "eth_gasPrice" => serde_json::json!("0x64"),
+                // The node charges more than 21000 gas for transfers.
+                "eth_estimateGas" => serde_json::json!("0x7a120"),
+                "eth_sendTransaction" => {
+                    *sent_tx.lock().unwrap() = Some(params.to_owned());
+                    return Err(error_resp(-32000, "transaction underpriced"));
+                }
+                method => unreachable!("unexpected method {method}"),
+            })
+        });
+
+        assert!(provider.cancel_transaction(B256::ZERO, 10).await.is_err());
+        let sent = sent.lock().unwrap().clone().unwrap();
+        let (request,): (alloy_rpc_types::TransactionRequest,) =
+            serde_json::from_str(&sent).unwrap();
+        assert_eq!(request.nonce, Some(7));
+        assert_eq!(request.gas, Some(500_000));
+        assert_eq!(request.gas_price, Some(110));
+        assert_eq!(request.chain_id, Some(42_161));
+        assert_eq!(request.preferred_type(), TxType::Eip2930);
+
+        let provider = mock_provider::<Ethereum, _>(|method, _| match method {
+            "eth_getTransactionByHash" => Ok(serde_json::json!(alloy_rpc_types::Transaction {
+                transaction_type: Some(3),
+                ..Default::default()
+            })),
+            method => unreachable!("unexpected method {method}"),
+        });
+        assert!(provider.cancel_transaction(B256::ZERO, 10).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn gets_storage_value() {
+        use alloy_sol_types::sol_data;
+
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let addr = Address::with_last_byte(16);
+        provider.anvil_set_storage_at(addr, U256::ZERO, B256::with_last_byte(1)).await.unwrap();
+
+        let latest = BlockId::latest();
+        let value = provider.get_storage_value::<sol_data::Bool, _>(addr, U256::ZERO, latest).await;
+        assert!(value.unwrap());
+        let location = crate::storage::StorageLocation::new(U256::ZERO).with_offset(1);
+        let value = provider.get_storage_value::<sol_data::Uint<8>, _>(addr, location, latest);
+        assert_eq!(value.await.unwrap(), 0);
+    }
+
     #[tokio::test]
     async fn gets_transaction_by_hash_not_found() {
         init_tracing();
@@ -1233,6 +2148,18 @@ mod tests {
         assert_eq!(logs.len(), 1);
     }
 
+    #[cfg(feature = "reqwest")]
+    #[tokio::test]
+    async fn rejects_invalid_log_filters() {
+        // Invalid filters fail before reaching the node.
+        let provider = ProviderBuilder::new().on_http("http://localhost:1".parse().unwrap());
+        let filter = Filter::new().from_block(10).to_block(5);
+        let err = provider.get_logs(&filter).await.unwrap_err();
+        assert!(err.to_string().contains("after `toBlock`"), "{err}");
+        let err = provider.get_logs_at(B256::ZERO, &filter).await.unwrap_err();
+        assert!(err.to_string().contains("cannot be combined"), "{err}");
+    }
+
     #[tokio::test]
     #[ignore]
     async fn gets_tx_receipt() {
@@ -1283,6 +2210,24 @@ mod tests {
         assert!(receipts.is_some());
     }
 
+    #[tokio::test]
+    async fn gets_block_receipts_by_id() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let block = provider.get_block_by_number(BlockNumberOrTag::Latest, false).await.unwrap();
+        let hash = block.unwrap().header.hash.unwrap();
+        let receipts = provider.get_block_receipts_by_id(BlockId::hash(hash)).await.unwrap();
+        assert!(receipts.is_some());
+    }
+
+    #[tokio::test]
+    async fn fetches_block_receipts() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let receipts = provider.fetch_block_receipts(BlockId::latest()).await.unwrap();
+        assert_eq!(receipts.map(|receipts| receipts.len()), Some(0));
+    }
+
     #[tokio::test]
     async fn gets_block_traces() {
         init_tracing();
@@ -1307,6 +2252,22 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn gets_and_decodes_raw_transaction() {
+        init_tracing();
+        let provider = ProviderBuilder::new().on_anvil();
+        let raw = bytes!("f865808477359400825208940000000000000000000000000000000000000000018082f4f5a00505e227c1c636c76fac55795db1a40a4d24840d81b40d2fe0cc85767f6bd202a01e91b437099a8a90234ac5af3cb7ca4fb1432e133f75f9a91678eaf5f487c74b");
+        let pending = provider.send_raw_transaction(raw.as_ref()).await.unwrap();
+        let hash = *pending.tx_hash();
+
+        let fetched = provider.get_raw_transaction_by_hash(hash).await.unwrap().unwrap();
+        assert_eq!(fetched, raw);
+
+        let tx = provider.decode_raw_transaction(&fetched).unwrap();
+        assert_eq!(*tx.tx_hash(), hash);
+        assert!(provider.decode_raw_transaction(&fetched[..fetched.len() - 1]).is_err());
+    }
+
     #[tokio::test]
     async fn connect_boxed() {
         init_tracing();
@@ -1338,4 +2299,377 @@ mod tests {
         let count = provider.get_uncle_count(0.into()).await.unwrap();
         assert_eq!(count, 0);
     }
+
+    /// Returns the receipt of the transaction at `index` in block 1, with the hash `index + 2`.
+    fn receipt(index: u64, block_hash: B256) -> alloy_rpc_types::TransactionReceipt {
+        use alloy_consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+
+        alloy_rpc_types::TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+                receipt: Receipt {
+                    status: Eip658Value::Eip658(true),
+                    cumulative_gas_used: 21_000 * (index as u128 + 1),
+                    logs: vec![],
+                },
+                logs_bloom: alloy_primitives::Bloom::ZERO,
+            }),
+            transaction_hash: B256::with_last_byte(index as u8 + 2),
+            transaction_index: Some(index),
+            block_hash: Some(block_hash),
+            block_number: Some(1),
+            gas_used: 21_000,
+            effective_gas_price: 1,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::ZERO,
+            to: Some(Address::ZERO),
+            contract_address: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn fetches_block_receipts_individually() {
+        let block_hash = B256::with_last_byte(1);
+        let provider = mock_provider::<Ethereum, _>(move |method, params| {
+            Ok(match method {
+                "eth_getBlockReceipts" => {
+                    return Err(error_resp(
+                        -32601,
+                        "the method eth_getBlockReceipts does not exist",
+                    ))
+                }
+                "eth_getBlockByHash" => {
+                    let hashes = (0..3).map(|i| B256::with_last_byte(i + 2)).collect();
+                    serde_json::json!(Block::<alloy_rpc_types::Transaction> {
+                        transactions: alloy_rpc_types::BlockTransactions::Hashes(hashes),
+                        ..Default::default()
+                    })
+                }
+                "eth_getTransactionReceipt" => {
+                    let (hash,): (B256,) = serde_json::from_str(params).unwrap();
+                    serde_json::json!(receipt(u64::from(hash[31]) - 2, block_hash))
+                }
+                method => unreachable!("unexpected method {method}"),
+            })
+        });
+        // The fallback looks through the request context of the error.
+        provider.client().set_error_context(alloy_rpc_client::ErrorContext::new());
+
+        let receipts = provider.fetch_block_receipts(BlockId::hash(block_hash)).await.unwrap();
+        let receipts = receipts.unwrap();
+        assert_eq!(receipts, (0..3).map(|i| receipt(i, block_hash)).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn gets_proven_receipt() {
+        // The receipts root does not depend on the block hash in the receipts.
+        let receipts_root = ordered_trie_root(
+            &(0..20).filter_map(|i| receipt(i, B256::ZERO).encoded_2718()).collect::<Vec<_>>(),
+        );
+        let mut block = Block::<alloy_rpc_types::Transaction>::default();
+        block.header.number = Some(1);
+        block.header.receipts_root = receipts_root;
+        let block_hash = block.header.hash_slow().unwrap();
+        block.header.hash = Some(block_hash);
+        let receipts: Vec<_> = (0..20).map(|i| receipt(i, block_hash)).collect();
+
+        let node = |block: Block, block_receipts: Vec<alloy_rpc_types::TransactionReceipt>| {
+            let receipts = receipts.clone();
+            mock_provider::<Ethereum, _>(move |method, params| {
+                Ok(match method {
+                    "eth_getTransactionReceipt" => {
+                        let (hash,): (B256,) = serde_json::from_str(params).unwrap();
+                        let receipt = receipts.iter().find(|r| r.transaction_hash == hash);
+                        serde_json::to_value(receipt).unwrap()
+                    }
+                    "eth_getBlockReceipts" => serde_json::to_value(&block_receipts).unwrap(),
+                    "eth_getBlockByHash" => serde_json::to_value(&block).unwrap(),
+                    _ => unreachable!(),
+                })
+            })
+        };
+
+        let provider = node(block.clone(), receipts.clone());
+        let proven = provider.get_proven_receipt(B256::with_last_byte(9)).await.unwrap().unwrap();
+        assert_eq!(proven.receipt, receipts[7]);
+        assert_eq!(proven.transaction_index, 7);
+        assert_eq!(proven.receipts_root, block.header.receipts_root);
+        assert!(proven.verify());
+        assert!(provider.get_proven_receipt(B256::ZERO).await.unwrap().is_none());
+
+        // The header must match the block hash of the receipt.
+        let mut forged = block.clone();
+        forged.header.gas_used = 1;
+        let err = node(forged, receipts.clone())
+            .get_proven_receipt(B256::with_last_byte(9))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match its hash"), "{err}");
+
+        // The receipts must match the receipts root of the block.
+        let mut forged = receipts.clone();
+        forged[3] = receipt(4, block_hash);
+        let err =
+            node(block, forged).get_proven_receipt(B256::with_last_byte(9)).await.unwrap_err();
+        assert!(err.to_string().contains("receipts root"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn calls_many() {
+        use alloy_rpc_types::{TransactionIndex, TransactionRequest};
+
+        let provider = mock_provider::<Ethereum, _>(|method, params| {
+            assert_eq!(method, "eth_callMany");
+            let (bundles, context, overrides): (Vec<Bundle>, StateContext, Option<StateOverride>) =
+                serde_json::from_str(params).unwrap();
+            assert_eq!(context.transaction_index, Some(TransactionIndex::All));
+            assert!(overrides.is_none());
+            // Each call returns its index in the bundle, and the last one reverts.
+            let result: Vec<Vec<EthCallResponse>> = bundles
+                .iter()
+                .map(|bundle| {
+                    let mut responses: Vec<_> = (0..bundle.transactions.len())
+                        .map(|i| EthCallResponse {
+                            value: Some(Bytes::from(vec![i as u8])),
+                            error: None,
+                        })
+                        .collect();
+                    responses.last_mut().unwrap().value = None;
+                    responses.last_mut().unwrap().error = Some("execution reverted".into());
+                    responses
+                })
+                .collect();
+            Ok(serde_json::json!(result))
+        });
+
+        let bundles = [
+            Bundle::from(vec![TransactionRequest::default(); 2]),
+            Bundle::from(vec![TransactionRequest::default(); 3])
+                .with_block_override(BlockOverrides::default()),
+        ];
+        let context = StateContext {
+            block_number: Some(BlockId::latest()),
+            transaction_index: Some(TransactionIndex::All),
+        };
+        let responses = provider.call_many(&bundles, Some(context), None).await.unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[1].len(), 3);
+        assert_eq!(responses[1][1].clone().ensure_ok().unwrap(), Bytes::from(vec![1]));
+        assert_eq!(responses[1][2].clone().ensure_ok().unwrap_err(), "execution reverted");
+    }
+
+    #[tokio::test]
+    async fn guards_chain_id() {
+        use alloy_consensus::{SignableTransaction, TxEip1559, TxEnvelope};
+        use alloy_network::TxSignerSync;
+        use alloy_rpc_types::TransactionRequest;
+        use alloy_signer_wallet::LocalWallet;
+        use std::sync::{Arc, Mutex};
+
+        let methods = Arc::new(Mutex::new(Vec::new()));
+        let sent = methods.clone();
+        // A node on chain 5.
+        let transport = mock(move |method, _| {
+            sent.lock().unwrap().push(method.to_owned());
+            Ok(match method {
+                "eth_chainId" => serde_json::json!("0x5"),
+                _ => serde_json::json!(B256::ZERO),
+            })
+        });
+        let provider = RootProvider::<_, Ethereum>::new(alloy_rpc_client::RpcClient::new(
+            transport.clone(),
+            true,
+        ));
+        assert_eq!(provider.chain_id(), None);
+        assert!(!provider.chain_id_guard());
+
+        let wallet = LocalWallet::random();
+        let raw_tx = |chain_id| {
+            let mut tx = TxEip1559 { chain_id, gas_limit: 21_000, ..Default::default() };
+            let signature = wallet.sign_transaction_sync(&mut tx).unwrap();
+            TxEnvelope::from(tx.into_signed(signature)).encoded_2718()
+        };
+
+        // Without the guard, transactions are sent as is.
+        let _ = provider.send_raw_transaction(&raw_tx(1)).await.unwrap();
+        assert_eq!(*methods.lock().unwrap(), ["eth_sendRawTransaction"]);
+        methods.lock().unwrap().clear();
+
+        provider.set_chain_id_guard(true);
+        let err = provider.send_raw_transaction(&raw_tx(1)).await.unwrap_err();
+        assert!(matches!(
+            err.as_transport_err(),
+            Some(TransportErrorKind::ChainIdMismatch { expected: 1, actual: 5 })
+        ));
+        let tx = TransactionRequest::default().with_chain_id(1);
+        let err = provider.send_transaction(tx).await.unwrap_err();
+        assert!(matches!(
+            err.as_transport_err(),
+            Some(TransportErrorKind::ChainIdMismatch { expected: 1, actual: 5 })
+        ));
+        assert_eq!(provider.chain_id(), Some(5));
+
+        let _ = provider.send_raw_transaction(&raw_tx(5)).await.unwrap();
+        let _ = provider
+            .send_transaction(TransactionRequest::default().with_chain_id(5))
+            .await
+            .unwrap();
+        let _ = provider.send_transaction(TransactionRequest::default()).await.unwrap();
+        // The chain ID is only fetched once.
+        assert_eq!(
+            *methods.lock().unwrap(),
+            ["eth_chainId", "eth_sendRawTransaction", "eth_sendTransaction", "eth_sendTransaction"]
+        );
+        assert_eq!(provider.cached_chain_id().await.unwrap(), 5);
+
+        // The chain ID of the node is checked before sending the first transaction.
+        let client = alloy_rpc_client::RpcClient::new(transport, true);
+        let provider = ProviderBuilder::new().expect_chain_id(1).on_client(client);
+        methods.lock().unwrap().clear();
+        let err = provider.send_raw_transaction(&raw_tx(5)).await.unwrap_err();
+        assert!(matches!(
+            err.as_transport_err(),
+            Some(TransportErrorKind::ChainIdMismatch { expected: 1, actual: 5 })
+        ));
+        let err = provider.send_transaction(TransactionRequest::default()).await.unwrap_err();
+        assert!(matches!(
+            err.as_transport_err(),
+            Some(TransportErrorKind::ChainIdMismatch { expected: 1, actual: 5 })
+        ));
+        assert_eq!(*methods.lock().unwrap(), ["eth_chainId", "eth_chainId"]);
+        assert_eq!(provider.chain_id(), None);
+    }
+
+    #[tokio::test]
+    async fn gets_account() {
+        use alloy_consensus::constants::KECCAK_EMPTY;
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        // Fails `eth_getAccount` with the given error, if any.
+        let provider = |get_account_error: Option<(i64, &'static str)>| {
+            let fell_back = Arc::new(AtomicBool::new(false));
+            let sent = fell_back.clone();
+            let provider = mock_provider::<Ethereum, _>(move |method, _| {
+                if method != "eth_getAccount" {
+                    sent.store(true, Ordering::Relaxed);
+                }
+                Ok(match method {
+                    "eth_getAccount" if get_account_error.is_some() => {
+                        let (code, message) = get_account_error.unwrap();
+                        return Err(error_resp(code, message));
+                    }
+                    "eth_getTransactionCount" => serde_json::json!("0x7"),
+                    "eth_getBalance" => serde_json::json!("0x64"),
+                    "eth_getCode" => serde_json::json!("0x6000"),
+                    "eth_getAccount" => serde_json::json!({
+                        "codeHash": KECCAK_EMPTY,
+                        "storageRoot": B256::ZERO,
+                        "balance": "0x1",
+                        "nonce": "0x2"
+                    }),
+                    _ => unreachable!(),
+                })
+            });
+            // The fallback looks through the request context of the error.
+            provider.client().set_error_context(alloy_rpc_client::ErrorContext::new());
+            (provider, fell_back)
+        };
+
+        let (node, fell_back) = provider(None);
+        let account = node.get_account(Address::ZERO, BlockId::latest()).await.unwrap();
+        assert_eq!(account.nonce, 2);
+        assert_eq!(account.storage_root, Some(B256::ZERO));
+        assert!(!account.has_code());
+        assert!(!fell_back.load(Ordering::Relaxed));
+
+        for error in
+            [(-32601, "the method eth_getAccount does not exist"), (-32000, "method not found")]
+        {
+            let (node, fell_back) = provider(Some(error));
+            let account = node.get_account(Address::ZERO, BlockId::latest()).await.unwrap();
+            assert_eq!(account.nonce, 7);
+            assert_eq!(account.balance, U256::from(100));
+            assert_eq!(account.code_hash, keccak256(&code));
+            assert_eq!(account.storage_root, None);
+            assert!(account.has_code());
+            assert!(fell_back.load(Ordering::Relaxed));
+        }
+
+        // Other errors are returned as is.
+        let (node, fell_back) = provider(Some((-32000, "header not found")));
+        let err = node.get_account(Address::ZERO, BlockId::latest()).await.unwrap_err();
+        assert_eq!(err.error_kind(), Some(ErrorKind::HeaderNotFound));
+        assert!(!fell_back.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn gets_fee_history_range() {
+        // Answers with base fees equal to the block numbers, the newest block being 5000.
+        let provider = mock_provider::<Ethereum, _>(|method, params| {
+            assert_eq!(method, "eth_feeHistory");
+            let (count, last, percentiles): (U64, BlockNumberOrTag, Vec<f64>) =
+                serde_json::from_str(params).unwrap();
+            let count = count.to::<u64>().min(utils::MAX_FEE_HISTORY_BLOCKS);
+            let last = last.as_number().unwrap().min(5000);
+            let oldest = last + 1 - count;
+            let fee_history = FeeHistory {
+                base_fee_per_gas: (oldest..=last + 1).map(u128::from).collect(),
+                gas_used_ratio: vec![0.5; count as usize],
+                oldest_block: oldest,
+                reward: (!percentiles.is_empty())
+                    .then(|| (oldest..=last).map(|n| vec![n as u128; percentiles.len()]).collect()),
+                ..Default::default()
+            };
+            Ok(serde_json::json!(fee_history))
+        });
+
+        let fee_history = provider.get_fee_history_range(100, 2600, &[50.0]).await.unwrap();
+        assert_eq!(fee_history.oldest_block, 100);
+        assert_eq!(fee_history.base_fee_per_gas, (100..=2601).collect::<Vec<u128>>());
+        assert_eq!(fee_history.block_reward(2600), Some(&[2600][..]));
+        fee_history.validate(2501, &[50.0]).unwrap();
+
+        let fee_history = provider.get_fee_history_range(7, 7, &[]).await.unwrap();
+        assert_eq!(fee_history.base_fee_per_gas, vec![7, 8]);
+        assert_eq!(fee_history.reward, None);
+
+        // The last chunk goes beyond the newest block.
+        provider.get_fee_history_range(4000, 5100, &[]).await.unwrap_err();
+        provider.get_fee_history_range(8, 7, &[]).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn waits_until_synced() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        // Reports syncing until the node was asked `synced_after` times.
+        let provider = |synced_after: usize| {
+            let polls = Arc::new(AtomicUsize::new(0));
+            let transport = mock(move |method, _| {
+                assert_eq!(method, "eth_syncing");
+                let status = if polls.fetch_add(1, Ordering::SeqCst) + 1 < synced_after {
+                    SyncStatus::Info(Default::default())
+                } else {
+                    SyncStatus::None
+                };
+                Ok(serde_json::json!(status))
+            });
+            let client = alloy_rpc_client::RpcClient::new(transport, true);
+            client.set_poll_interval(1);
+            RootProvider::<_, Ethereum>::new(client)
+        };
+
+        provider(1).wait_until_synced(Duration::ZERO).await.unwrap();
+        provider(3).wait_until_synced(Duration::from_secs(5)).await.unwrap();
+        let err = provider(usize::MAX).wait_until_synced(Duration::from_millis(20)).await;
+        let err = err.unwrap_err();
+        assert!(matches!(err.as_transport_err(), Some(TransportErrorKind::Timeout(_))), "{err}");
+    }
 }
@@ -1,5 +1,5 @@
 use alloy_eips::BlockId;
-use alloy_json_rpc::{RpcError, RpcParam, RpcReturn};
+use alloy_json_rpc::{LazyValue, RpcError, RpcParam, RpcReturn};
 use alloy_rpc_client::{RpcCall, WeakClient};
 use alloy_transport::{Transport, TransportErrorKind, TransportResult};
 use futures::FutureExt;
@@ -223,6 +223,21 @@ where
         }
     }
 
+    /// Returns the response as raw JSON, to be deserialized later by the caller.
+    ///
+    /// The mapping of the response, if any, is dropped: the [`LazyValue`] decodes into the
+    /// response type of the method, such as `U64` rather than `u64`.
+    pub fn into_raw(self) -> RpcWithBlock<T, Params, LazyValue<Resp>> {
+        RpcWithBlock {
+            client: self.client,
+            method: self.method,
+            params: self.params,
+            block_id: self.block_id,
+            map: std::convert::identity,
+            _pd: PhantomData,
+        }
+    }
+
     /// Set the block id.
     #[allow(clippy::missing_const_for_fn)] // false positive
     pub fn block_id(mut self, block_id: BlockId) -> Self {
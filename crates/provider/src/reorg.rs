@@ -0,0 +1,367 @@
+//! A block stream following the canonical chain across reorgs.
+//!
+//! [`ReorgAwareBlocks`] links each new block to its parent by hash. When a new block does not
+//! extend the tracked chain, the tracked blocks are walked back until the new chain connects to
+//! them, and the abandoned blocks are reported before the replacement blocks.
+
+use crate::Provider;
+use alloy_network::{Ethereum, FinalityPolicy, Network};
+use alloy_primitives::{BlockNumber, U64};
+use alloy_rpc_client::PollerBuilder;
+use alloy_rpc_types::{Block, Transaction};
+use alloy_transport::{Transport, TransportError, TransportResult};
+use futures::{Future, Stream, StreamExt};
+use std::{collections::VecDeque, marker::PhantomData, time::Duration};
+
+/// The default number of blocks tracked to detect reorgs.
+const DEFAULT_DEPTH: usize = 64;
+
+/// An event of a [`ReorgAwareBlocks`] stream.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainEvent<T = Transaction> {
+    /// A new block was added to the canonical chain.
+    Committed(Box<Block<T>>),
+    /// Blocks were removed from the canonical chain by a reorg, from the newest to the oldest.
+    ///
+    /// The blocks replacing them follow as [`ChainEvent::Committed`] events.
+    Reverted(Vec<Block<T>>),
+}
+
+/// Error yielded by a [`ReorgAwareBlocks`] stream.
+#[derive(Debug, thiserror::Error)]
+pub enum ReorgError {
+    /// An RPC request failed.
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    /// A block returned by the node is missing its hash.
+    #[error("block {0} is missing its hash")]
+    PendingBlock(BlockNumber),
+    /// The reorg is deeper than the tracked blocks, so the common ancestor cannot be found.
+    #[error("reorg deeper than the {0} tracked blocks")]
+    TooDeep(usize),
+}
+
+/// A stream of canonical chain events, starting at the chain tip.
+///
+/// Created with [`Provider::watch_blocks_with_reorgs`].
+///
+/// A reorg is detected once the new canonical chain is longer than the tracked one. Errors of
+/// single requests are yielded without ending the stream, and the fetch is retried on the next
+/// poll. The stream ends after a [`ReorgError::TooDeep`] error.
+///
+/// ```no_run
+/// use alloy_provider::{reorg::ChainEvent, Provider, ProviderBuilder};
+/// use futures::StreamExt;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new().on_http("http://localhost:8545".parse()?);
+/// let mut stream = Box::pin(provider.watch_blocks_with_reorgs().depth(128).into_stream());
+/// while let Some(event) = stream.next().await {
+///     match event? {
+///         ChainEvent::Committed(block) => println!("block {:?}", block.header.number),
+///         ChainEvent::Reverted(blocks) => println!("reverted {} blocks", blocks.len()),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ReorgAwareBlocks<P, T, N = Ethereum> {
+    provider: P,
+    depth: usize,
+    poll_interval: Option<Duration>,
+    full_transactions: bool,
+    _phantom: PhantomData<(T, N)>,
+}
+
+impl<P, T, N> ReorgAwareBlocks<P, T, N>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    /// Creates a new stream of the blocks of `provider`.
+    pub const fn new(provider: P) -> Self {
+        Self {
+            provider,
+            depth: DEFAULT_DEPTH,
+            poll_interval: None,
+            full_transactions: false,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the number of recent blocks kept to detect reorgs, which is the deepest reorg that
+    /// can be reported. Defaults to 64.
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth.max(1);
+        self
+    }
+
+    /// Keeps enough blocks to report any reorg of blocks that are not final under `policy`.
+    ///
+    /// Only [`FinalityPolicy::Confirmations`] bounds the depth of reorgs in advance, other
+    /// policies keep the configured [`depth`](Self::depth).
+    pub fn finality(self, policy: &FinalityPolicy) -> Self {
+        match policy {
+            FinalityPolicy::Confirmations(confirmations) => {
+                self.depth(usize::try_from(*confirmations).unwrap_or(usize::MAX).saturating_add(1))
+            }
+            _ => self,
+        }
+    }
+
+    /// Sets the interval between chain tip polls. Defaults to the client's poll interval.
+    pub const fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = Some(poll_interval);
+        self
+    }
+
+    /// Fetches blocks with their full transactions instead of only their hashes.
+    pub const fn full_transactions(mut self) -> Self {
+        self.full_transactions = true;
+        self
+    }
+
+    /// Returns the stream of chain events.
+    pub fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<ChainEvent<N::TransactionResponse>, ReorgError>> {
+        let mut poller =
+            PollerBuilder::<T, (), U64>::new(self.provider.weak_client(), "eth_blockNumber", ());
+        if let Some(poll_interval) = self.poll_interval {
+            poller.set_poll_interval(poll_interval);
+        }
+        let tips = poller.into_stream().map(|tip| tip.to::<BlockNumber>());
+
+        async_stream::stream! {
+            let provider = &self.provider;
+            let full = self.full_transactions;
+            let events = follow(
+                tips,
+                move |number| provider.get_block_by_number(number.into(), full),
+                self.depth,
+            );
+            futures::pin_mut!(events);
+            while let Some(event) = events.next().await {
+                yield event;
+            }
+        }
+    }
+}
+
+/// Follows the chain up to each new tip, fetching blocks with `fetch`.
+fn follow<Tx, S, F, Fut>(
+    mut tips: S,
+    mut fetch: F,
+    depth: usize,
+) -> impl Stream<Item = Result<ChainEvent<Tx>, ReorgError>>
+where
+    Tx: Clone,
+    S: Stream<Item = BlockNumber> + Unpin,
+    F: FnMut(BlockNumber) -> Fut,
+    Fut: Future<Output = TransportResult<Option<Block<Tx>>>>,
+{
+    async_stream::stream! {
+        let mut chain: VecDeque<Block<Tx>> = VecDeque::with_capacity(depth);
+        let mut reverted = Vec::new();
+        let mut next = None;
+        while let Some(tip) = tips.next().await {
+            let mut number = next.unwrap_or(tip);
+            while number <= tip {
+                let block = match fetch(number).await {
+                    Ok(Some(block)) => block,
+                    Ok(None) => {
+                        // The node is lagging behind its own tip, wait for the next poll.
+                        debug!(number, "block not found");
+                        break;
+                    }
+                    Err(err) => {
+                        yield Err(err.into());
+                        break;
+                    }
+                };
+                if block.header.hash.is_none() {
+                    yield Err(ReorgError::PendingBlock(number));
+                    break;
+                }
+
+                if let Some(parent) = chain.back() {
+                    if parent.header.hash != Some(block.header.parent_hash) {
+                        debug!(number, "block does not extend the chain, reverting its parent");
+                        reverted.extend(chain.pop_back());
+                        if chain.is_empty() {
+                            yield Err(ReorgError::TooDeep(depth));
+                            return;
+                        }
+                        number -= 1;
+                        // Resume the walk back from here if the next fetch fails.
+                        next = Some(number);
+                        continue;
+                    }
+                }
+
+                if !reverted.is_empty() {
+                    yield Ok(ChainEvent::Reverted(std::mem::take(&mut reverted)));
+                }
+                if chain.len() == depth {
+                    chain.pop_front();
+                }
+                chain.push_back(block.clone());
+                trace!(number, "committing block");
+                yield Ok(ChainEvent::Committed(Box::new(block)));
+                number += 1;
+                next = Some(number);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use alloy_rpc_types::Header;
+    use futures::channel::mpsc;
+    use std::sync::{Arc, Mutex};
+
+    /// Returns the blocks `from..=to` of a chain, forked from block `from - 1` with the given
+    /// fork id.
+    fn blocks(from: u64, to: u64, fork: u8) -> Vec<Block> {
+        (from..=to)
+            .map(|number| {
+                let hash = |number: u64| {
+                    let fork = if number < from { 0 } else { fork };
+                    B256::left_padding_from(&[fork, number as u8])
+                };
+                Block {
+                    header: Header {
+                        number: Some(number),
+                        hash: Some(hash(number)),
+                        parent_hash: hash(number.wrapping_sub(1)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+
+    async fn next<S>(events: &mut S) -> ChainEvent
+    where
+        S: Stream<Item = Result<ChainEvent, ReorgError>> + Unpin,
+    {
+        events.next().await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn reverts_reorged_blocks() {
+        let chain = Arc::new(Mutex::new(blocks(0, 3, 0)));
+        let (tips, rx) = mpsc::unbounded();
+        let fetch = {
+            let chain = chain.clone();
+            move |number: BlockNumber| {
+                let block = chain.lock().unwrap().get(number as usize).cloned();
+                async move { Ok(block) }
+            }
+        };
+        let events = follow(rx, fetch, 4);
+        futures::pin_mut!(events);
+
+        tips.unbounded_send(1).unwrap();
+        assert_eq!(
+            next(&mut events).await,
+            ChainEvent::Committed(Box::new(blocks(1, 1, 0).remove(0)))
+        );
+        tips.unbounded_send(3).unwrap();
+        assert_eq!(
+            next(&mut events).await,
+            ChainEvent::Committed(Box::new(blocks(2, 2, 0).remove(0)))
+        );
+        assert_eq!(
+            next(&mut events).await,
+            ChainEvent::Committed(Box::new(blocks(3, 3, 0).remove(0)))
+        );
+
+        // Blocks 2 and 3 are replaced, and the new chain grows to block 4.
+        chain.lock().unwrap().truncate(2);
+        chain.lock().unwrap().extend(blocks(2, 4, 1));
+        tips.unbounded_send(4).unwrap();
+        let mut old = blocks(2, 3, 0);
+        old.reverse();
+        assert_eq!(next(&mut events).await, ChainEvent::Reverted(old));
+        for block in blocks(2, 4, 1) {
+            assert_eq!(next(&mut events).await, ChainEvent::Committed(Box::new(block)));
+        }
+    }
+
+    #[tokio::test]
+    async fn resumes_reorg_after_failed_fetch() {
+        let chain = Arc::new(Mutex::new(blocks(0, 3, 0)));
+        let fail = Arc::new(Mutex::new(None));
+        let (tips, rx) = mpsc::unbounded();
+        let fetch = {
+            let (chain, fail) = (chain.clone(), fail.clone());
+            move |number: BlockNumber| {
+                let block = chain.lock().unwrap().get(number as usize).cloned();
+                let fail = *fail.lock().unwrap() == Some(number);
+                async move {
+                    if fail {
+                        return Err(alloy_transport::TransportErrorKind::custom_str("unavailable"));
+                    }
+                    Ok(block)
+                }
+            }
+        };
+        let events = follow(rx, fetch, 4);
+        futures::pin_mut!(events);
+
+        tips.unbounded_send(1).unwrap();
+        tips.unbounded_send(3).unwrap();
+        for _ in 1..=3 {
+            assert!(matches!(next(&mut events).await, ChainEvent::Committed(_)));
+        }
+
+        // Blocks 2 and 3 are replaced, and fetching the new block 3 fails during the walk back.
+        chain.lock().unwrap().truncate(2);
+        chain.lock().unwrap().extend(blocks(2, 4, 1));
+        *fail.lock().unwrap() = Some(3);
+        tips.unbounded_send(4).unwrap();
+        assert!(events.next().await.unwrap().is_err());
+
+        // The walk back resumes from block 3, so only blocks 2 and 3 are reverted.
+        *fail.lock().unwrap() = None;
+        tips.unbounded_send(4).unwrap();
+        let mut old = blocks(2, 3, 0);
+        old.reverse();
+        assert_eq!(next(&mut events).await, ChainEvent::Reverted(old));
+        for block in blocks(2, 4, 1) {
+            assert_eq!(next(&mut events).await, ChainEvent::Committed(Box::new(block)));
+        }
+    }
+
+    #[tokio::test]
+    async fn too_deep_reorg() {
+        let chain = Arc::new(Mutex::new(blocks(0, 2, 0)));
+        let (tips, rx) = mpsc::unbounded();
+        let fetch = {
+            let chain = chain.clone();
+            move |number: BlockNumber| {
+                let block = chain.lock().unwrap().get(number as usize).cloned();
+                async move { Ok(block) }
+            }
+        };
+        let events = follow(rx, fetch, 1);
+        futures::pin_mut!(events);
+
+        tips.unbounded_send(2).unwrap();
+        assert!(events.next().await.unwrap().is_ok());
+
+        chain.lock().unwrap().truncate(1);
+        chain.lock().unwrap().extend(blocks(1, 3, 1));
+        tips.unbounded_send(3).unwrap();
+        assert!(matches!(events.next().await, Some(Err(ReorgError::TooDeep(1)))));
+        assert!(events.next().await.is_none());
+    }
+}
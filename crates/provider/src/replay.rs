@@ -0,0 +1,228 @@
+//! Replay checks for signed transactions and authorizations.
+//!
+//! A signed payload can be replayed on any chain that accepts its signature: transactions signed
+//! before [EIP-155] carry no chain ID, and [EIP-7702] authorizations with a chain ID of zero are
+//! valid on every chain. Whether a replay is viable then only depends on the nonce of the signer
+//! on the target chain.
+//!
+//! [`check_replay`] reports the [`ReplayRisk`] of a [`SignedPayload`] on the chain of a
+//! provider, and [`scan_replay`] on the chains of several providers at once.
+//!
+//! ```no_run
+//! use alloy_provider::{
+//!     replay::{scan_replay, SignedPayload},
+//!     ProviderBuilder, RootProvider,
+//! };
+//! # use alloy_transport_http::Http;
+//!
+//! # async fn example(tx: alloy_consensus::TxEnvelope) -> Result<(), Box<dyn std::error::Error>> {
+//! let chains: Vec<RootProvider<Http<reqwest::Client>>> = vec![
+//!     ProviderBuilder::new().on_http("https://eth.merkle.io".parse()?),
+//!     ProviderBuilder::new().on_http("https://mainnet.optimism.io".parse()?),
+//! ];
+//! for report in scan_replay(&SignedPayload::from(tx), &chains).await? {
+//!     if report.risk.is_viable() {
+//!         println!("replayable on chain {}", report.chain_id);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [EIP-155]: https://eips.ethereum.org/EIPS/eip-155
+//! [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+
+use crate::Provider;
+use alloy_consensus::{Transaction, TxEnvelope, TypedTransaction};
+use alloy_eips::eip7702::SignedAuthorization;
+use alloy_json_rpc::RpcError;
+use alloy_network::Network;
+use alloy_primitives::{Address, ChainId, SignatureError};
+use alloy_transport::{Transport, TransportResult};
+
+/// A signed payload that may be replayed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignedPayload {
+    /// A signed transaction.
+    Transaction(Box<TxEnvelope>),
+    /// A signed EIP-7702 authorization.
+    Authorization(SignedAuthorization),
+}
+
+impl From<TxEnvelope> for SignedPayload {
+    fn from(tx: TxEnvelope) -> Self {
+        Self::Transaction(Box::new(tx))
+    }
+}
+
+impl From<SignedAuthorization> for SignedPayload {
+    fn from(authorization: SignedAuthorization) -> Self {
+        Self::Authorization(authorization)
+    }
+}
+
+impl SignedPayload {
+    /// Returns the chain the payload is bound to, or `None` if it is valid on every chain.
+    pub fn chain_id(&self) -> Option<ChainId> {
+        match self {
+            Self::Transaction(tx) => TypedTransaction::from(tx.as_ref().clone()).chain_id(),
+            Self::Authorization(auth) => (!auth.is_chain_agnostic()).then_some(auth.chain_id),
+        }
+    }
+
+    /// Returns `true` if the payload is valid on every chain.
+    pub fn is_chain_agnostic(&self) -> bool {
+        self.chain_id().is_none()
+    }
+
+    /// Returns the nonce the signer must have for the payload to be valid.
+    pub fn nonce(&self) -> u64 {
+        match self {
+            Self::Transaction(tx) => TypedTransaction::from(tx.as_ref().clone()).nonce(),
+            Self::Authorization(auth) => auth.nonce,
+        }
+    }
+
+    /// Recovers the signer of the payload, the sender of a transaction or the authority of an
+    /// authorization.
+    pub fn recover_signer(&self) -> Result<Address, SignatureError> {
+        match self {
+            Self::Transaction(tx) => tx.recover_signer(),
+            Self::Authorization(auth) => auth.recover_authority(),
+        }
+    }
+}
+
+/// The risk of a payload being replayed on a chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayRisk {
+    /// The payload is bound to another chain.
+    OtherChain,
+    /// The signer already used the nonce of the payload on the chain.
+    NonceUsed,
+    /// The payload can be included on the chain now.
+    Replayable,
+    /// The payload becomes replayable once the signer used `gap` more nonces on the chain.
+    Pending {
+        /// The number of nonces between the current nonce of the signer and the payload's.
+        gap: u64,
+    },
+}
+
+impl ReplayRisk {
+    /// Returns `true` if the payload can be included on the chain, now or later.
+    pub const fn is_viable(&self) -> bool {
+        matches!(self, Self::Replayable | Self::Pending { .. })
+    }
+}
+
+/// The [`ReplayRisk`] of a payload on a chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// The ID of the chain.
+    pub chain_id: ChainId,
+    /// The signer of the payload.
+    pub signer: Address,
+    /// The risk of the payload being replayed on the chain.
+    pub risk: ReplayRisk,
+}
+
+/// Checks whether `payload` can be replayed on the chain of `provider`.
+///
+/// On the chain the payload is bound to, this reports whether the payload can still be included.
+/// Fails with a local usage error if the signer cannot be recovered.
+pub async fn check_replay<P, T, N>(
+    provider: &P,
+    payload: &SignedPayload,
+) -> TransportResult<ReplayReport>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let signer = payload.recover_signer().map_err(RpcError::local_usage)?;
+    let chain_id = provider.get_chain_id().await?;
+    if payload.chain_id().is_some_and(|bound| bound != chain_id) {
+        return Ok(ReplayReport { chain_id, signer, risk: ReplayRisk::OtherChain });
+    }
+
+    let nonce = provider.get_transaction_count(signer).await?;
+    let risk = match payload.nonce().checked_sub(nonce) {
+        None => ReplayRisk::NonceUsed,
+        Some(0) => ReplayRisk::Replayable,
+        Some(gap) => ReplayRisk::Pending { gap },
+    };
+    Ok(ReplayReport { chain_id, signer, risk })
+}
+
+/// Checks whether `payload` can be replayed on the chains of `providers`, returning a report per
+/// provider in the same order.
+pub async fn scan_replay<P, T, N>(
+    payload: &SignedPayload,
+    providers: &[P],
+) -> TransportResult<Vec<ReplayReport>>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    futures::future::try_join_all(providers.iter().map(|provider| check_replay(provider, payload)))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::mock_provider, RootProvider};
+    use alloy_consensus::{SignableTransaction, TxEip1559, TxLegacy};
+    use alloy_eips::eip7702::Authorization;
+    use alloy_network::TxSignerSync;
+    use alloy_primitives::TxKind;
+    use alloy_signer::SignerSync;
+    use alloy_signer_wallet::LocalWallet;
+    use alloy_transport::BoxTransport;
+    use serde_json::json;
+
+    /// Returns a provider of a chain where every account has a nonce of 1.
+    fn chain(chain_id: ChainId) -> RootProvider<BoxTransport> {
+        mock_provider(move |method, _| {
+            Ok(match method {
+                "eth_chainId" => json!(format!("{chain_id:#x}")),
+                "eth_getTransactionCount" => json!("0x1"),
+                _ => unreachable!(),
+            })
+        })
+    }
+
+    async fn risks(payload: SignedPayload, signer: Address) -> Vec<ReplayRisk> {
+        let reports = scan_replay(&payload, &[chain(1), chain(10)]).await.unwrap();
+        assert!(reports.iter().all(|report| report.signer == signer));
+        reports.into_iter().map(|report| report.risk).collect()
+    }
+
+    #[tokio::test]
+    async fn scans_chains() {
+        let wallet = LocalWallet::random();
+        let risks = |payload| risks(payload, wallet.address());
+
+        // Pre EIP-155 transactions are valid on every chain.
+        let mut tx = TxLegacy { nonce: 1, to: TxKind::Create, ..Default::default() };
+        let signature = wallet.sign_transaction_sync(&mut tx).unwrap();
+        let payload = SignedPayload::from(TxEnvelope::from(tx.into_signed(signature)));
+        assert!(payload.is_chain_agnostic());
+        assert_eq!(risks(payload).await, [ReplayRisk::Replayable, ReplayRisk::Replayable]);
+
+        let mut tx = TxEip1559 { chain_id: 1, nonce: 0, ..Default::default() };
+        let signature = wallet.sign_transaction_sync(&mut tx).unwrap();
+        let payload = SignedPayload::from(TxEnvelope::from(tx.into_signed(signature)));
+        assert_eq!(risks(payload).await, [ReplayRisk::NonceUsed, ReplayRisk::OtherChain]);
+
+        let auth = Authorization { chain_id: 0, address: Address::ZERO, nonce: 3 };
+        let signature = wallet.sign_hash_sync(&auth.signature_hash()).unwrap();
+        let payload = SignedPayload::from(auth.into_signed(signature));
+        assert_eq!(
+            risks(payload).await,
+            [ReplayRisk::Pending { gap: 2 }, ReplayRisk::Pending { gap: 2 }]
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Storage slot computation following the Solidity storage layout.
+//!
+//! State variables are laid out from slot 0, in declaration order. Value types smaller than 32
+//! bytes are packed together into a single slot, starting from its lower-order bytes. The values
+//! of mappings and dynamic arrays are stored at slots derived from the slot of the variable with
+//! `keccak256`. See the [Solidity documentation](https://docs.soliditylang.org/en/latest/internals/layout_in_storage.html)
+//! for the full layout rules.
+//!
+//! ```
+//! use alloy_primitives::{address, U256};
+//! use alloy_provider::storage::{mapping_slot, StorageLocation};
+//!
+//! // `mapping(address => mapping(address => uint256)) allowance;` declared at slot 1.
+//! let owner = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+//! let spender = address!("000000000022D473030F116dDEE9F6B43aC78BA3");
+//! let slot = mapping_slot(mapping_slot(U256::from(1), &owner), &spender);
+//!
+//! // The second field of `struct { address owner; uint64 nonce; }` declared at slot 2, packed
+//! // after the 20 bytes of `owner`.
+//! let nonce = StorageLocation::new(U256::from(2)).with_offset(20);
+//! ```
+
+use alloy_primitives::{keccak256, StorageValue, U256};
+use alloy_sol_types::{SolType, SolValue};
+
+/// The location of a value in storage: a slot, and the byte offset of the value in the slot.
+///
+/// The offset is counted from the lower-order end of the slot, as in Solidity's storage layout
+/// output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StorageLocation {
+    /// The storage slot.
+    pub slot: U256,
+    /// The byte offset of the value in the slot.
+    pub offset: usize,
+}
+
+impl StorageLocation {
+    /// Creates a new location at the start of the given slot.
+    pub const fn new(slot: U256) -> Self {
+        Self { slot, offset: 0 }
+    }
+
+    /// Sets the byte offset of the value in the slot.
+    pub const fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+impl From<U256> for StorageLocation {
+    fn from(slot: U256) -> Self {
+        Self::new(slot)
+    }
+}
+
+/// Returns the slot of the value of `key` in a mapping stored at `slot`.
+///
+/// Value type keys are padded to 32 bytes, while `string` and `bytes` keys are hashed as is.
+/// Nest calls to compute the slots of nested mappings.
+pub fn mapping_slot<K: SolValue>(slot: U256, key: &K) -> U256 {
+    let mut preimage = if K::SolType::DYNAMIC { key.abi_encode_packed() } else { key.abi_encode() };
+    preimage.extend_from_slice(&slot.to_be_bytes::<32>());
+    keccak256(preimage).into()
+}
+
+/// Returns the first slot of the elements of a dynamic array stored at `slot`.
+///
+/// The slot itself holds the length of the array.
+pub fn array_data_slot(slot: U256) -> U256 {
+    keccak256(slot.to_be_bytes::<32>()).into()
+}
+
+/// Returns the location of the element at `index` of a dynamic array stored at `slot`, whose
+/// elements take `element_size` bytes.
+///
+/// Elements smaller than 32 bytes are packed together as long as they fit in a slot, while larger
+/// elements, such as structs, start a new slot each. `element_size` must be a multiple of 32 for
+/// elements spanning multiple slots.
+pub fn array_element_location(slot: U256, index: u64, element_size: usize) -> StorageLocation {
+    let data = array_data_slot(slot);
+    if element_size == 0 || element_size >= 32 {
+        let slots = element_size.div_ceil(32).max(1) as u64;
+        return StorageLocation::new(data + U256::from(index) * U256::from(slots));
+    }
+    let per_slot = (32 / element_size) as u64;
+    StorageLocation::new(data + U256::from(index / per_slot))
+        .with_offset((index % per_slot) as usize * element_size)
+}
+
+/// Returns the slot of a struct member, `member_slot` slots after the first slot of the struct.
+///
+/// Use [`StorageLocation::with_offset`] for members packed in the middle of a slot.
+pub fn struct_member_slot(struct_slot: U256, member_slot: u64) -> U256 {
+    struct_slot + U256::from(member_slot)
+}
+
+/// Decodes the Solidity value type `V` stored at `offset` in the storage word `value`.
+///
+/// Only value types are supported: `bool`, `address`, `uintN`, `intN` and `bytesN`.
+pub fn decode_storage_value<V: SolType>(
+    value: StorageValue,
+    offset: usize,
+) -> alloy_sol_types::Result<V::RustType> {
+    let unsupported =
+        || alloy_sol_types::Error::custom(format!("unsupported type {}", V::SOL_NAME));
+    let size = value_size(V::SOL_NAME).ok_or_else(unsupported)?;
+    if offset.checked_add(size).map_or(true, |end| end > 32) {
+        return Err(alloy_sol_types::Error::custom(format!(
+            "{} at offset {offset} does not fit in a slot",
+            V::SOL_NAME
+        )));
+    }
+
+    let word = value.to_be_bytes::<32>();
+    let bytes = &word[32 - offset - size..32 - offset];
+    let mut encoded = [0u8; 32];
+    if V::SOL_NAME.starts_with("bytes") {
+        // Fixed bytes are left-aligned in ABI encoding.
+        encoded[..size].copy_from_slice(bytes);
+    } else {
+        if V::SOL_NAME.starts_with("int") && bytes[0] & 0x80 != 0 {
+            encoded = [0xff; 32];
+        }
+        encoded[32 - size..].copy_from_slice(bytes);
+    }
+    V::abi_decode(&encoded, true)
+}
+
+/// Returns the size in bytes of a Solidity value type.
+fn value_size(name: &str) -> Option<usize> {
+    let bits = |s: &str| s.parse::<usize>().ok().filter(|n| *n > 0 && *n <= 256 && n % 8 == 0);
+    match name {
+        "bool" => Some(1),
+        "address" => Some(20),
+        _ => {
+            if let Some(n) = name.strip_prefix("uint").or_else(|| name.strip_prefix("int")) {
+                bits(n).map(|bits| bits / 8)
+            } else {
+                name.strip_prefix("bytes")
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .filter(|n| (1..=32).contains(n))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, FixedBytes, B256};
+    use alloy_sol_types::sol_data;
+
+    #[test]
+    fn mapping_slots() {
+        // `balanceOf` of WETH is at slot 3.
+        let owner = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        assert_eq!(
+            B256::from(mapping_slot(U256::from(3), &owner)),
+            keccak256([owner.into_word().as_slice(), &[0; 31], &[3]].concat())
+        );
+        // String keys are hashed unpadded.
+        assert_eq!(
+            mapping_slot(U256::ZERO, &String::from("a")),
+            U256::from_be_bytes(keccak256([b"a".as_slice(), &[0; 32]].concat()).0)
+        );
+    }
+
+    #[test]
+    fn array_locations() {
+        let data = array_data_slot(U256::from(5));
+        assert_eq!(B256::from(data), keccak256(B256::with_last_byte(5)));
+        assert_eq!(
+            array_element_location(U256::from(5), 3, 32),
+            StorageLocation::new(data + U256::from(3))
+        );
+        assert_eq!(
+            array_element_location(U256::from(5), 3, 64),
+            StorageLocation::new(data + U256::from(6))
+        );
+        // Four `uint64` per slot.
+        assert_eq!(
+            array_element_location(U256::from(5), 5, 8),
+            StorageLocation::new(data + U256::from(1)).with_offset(8)
+        );
+    }
+
+    #[test]
+    fn decode_packed_values() {
+        // `address owner; uint64 nonce; bool paused;` packed in a single slot.
+        let owner = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let mut word = [0u8; 32];
+        word[3] = 1;
+        word[4..12].copy_from_slice(&7u64.to_be_bytes());
+        word[12..].copy_from_slice(owner.as_slice());
+        let value = U256::from_be_bytes(word);
+
+        assert_eq!(decode_storage_value::<sol_data::Address>(value, 0).unwrap(), owner);
+        assert_eq!(decode_storage_value::<sol_data::Uint<64>>(value, 20).unwrap(), 7);
+        assert!(decode_storage_value::<sol_data::Bool>(value, 28).unwrap());
+        assert!(decode_storage_value::<sol_data::Uint<64>>(value, 28).is_err());
+        assert!(decode_storage_value::<sol_data::Bool>(value, usize::MAX).is_err());
+
+        assert_eq!(decode_storage_value::<sol_data::Int<8>>(U256::from(0xff), 0).unwrap(), -1);
+        assert_eq!(
+            decode_storage_value::<sol_data::FixedBytes<2>>(U256::from(0x1234), 0).unwrap(),
+            FixedBytes([0x12, 0x34])
+        );
+        assert!(decode_storage_value::<sol_data::String>(value, 0).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Stream combinators for subscriptions and other notification streams.
+//!
+//! These cover the glue that applications otherwise write around
+//! [`Subscription::into_stream`](alloy_pubsub::Subscription::into_stream): following several
+//! chains at once, keeping only the logs of a given event, and recovering from subscriptions
+//! that silently stop delivering items.
+
+use alloy_rpc_types::Log;
+use alloy_sol_types::SolEvent;
+use futures::{Stream, StreamExt};
+
+/// Merges several streams into one, tagging each item with the key of the stream it was received
+/// from, e.g. a chain ID when following the heads of multiple chains.
+///
+/// Items are yielded as soon as they are received from any of the streams. The merged stream ends
+/// once all streams have ended.
+pub fn merge_tagged<K, S>(
+    streams: impl IntoIterator<Item = (K, S)>,
+) -> impl Stream<Item = (K, S::Item)>
+where
+    K: Clone,
+    S: Stream + Unpin,
+{
+    futures::stream::select_all(
+        streams.into_iter().map(|(key, stream)| stream.map(move |item| (key.clone(), item))),
+    )
+}
+
+/// Decodes a stream of logs as events of type `E`, discarding the logs that do not match the
+/// event or fail to decode.
+pub fn decode_logs<E, S>(logs: S) -> impl Stream<Item = Log<E>>
+where
+    E: SolEvent,
+    S: Stream<Item = Log>,
+{
+    logs.filter_map(|log| {
+        let decoded = match log.log_decode::<E>() {
+            Ok(decoded) => Some(decoded),
+            Err(err) => {
+                trace!(%err, address = %log.address(), "skipping log");
+                None
+            }
+        };
+        futures::future::ready(decoded)
+    })
+}
+
+#[cfg(feature = "pubsub")]
+pub use pending::full_pending_transactions;
+#[cfg(feature = "pubsub")]
+pub use resubscribe::resubscribe_on_timeout;
+
+#[cfg(feature = "pubsub")]
+mod pending {
+    use crate::Provider;
+    use alloy_network::Network;
+    use alloy_primitives::B256;
+    use alloy_transport::{Transport, TransportResult};
+    use futures::{Stream, StreamExt};
+
+    /// Returns a stream of the bodies of new pending transactions.
+    ///
+    /// This uses [`Provider::subscribe_full_pending_transactions`] where possible. Nodes that do
+    /// not support full transaction notifications either reject the subscription, or ignore the
+    /// flag and notify hashes only. In both cases, the body of each notified hash is fetched with
+    /// [`Provider::get_transaction_by_hash`]; transactions that are dropped before they are
+    /// fetched are skipped.
+    ///
+    /// The stream yields an error and ends if subscribing fails. Failed lookups are yielded as
+    /// errors, without ending the stream.
+    pub fn full_pending_transactions<'a, P, T, N>(
+        provider: &'a P,
+    ) -> impl Stream<Item = TransportResult<N::TransactionResponse>> + 'a
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+    {
+        async_stream::stream! {
+            let sub = match provider.subscribe_full_pending_transactions().await {
+                Ok(sub) => Ok(sub.into_raw()),
+                Err(err) if err.is_error_resp() => {
+                    debug!(%err, "full pending transactions unsupported, subscribing to hashes");
+                    provider.subscribe_pending_transactions().await.map(|sub| sub.into_raw())
+                }
+                Err(err) => Err(err),
+            };
+            let sub = match sub {
+                Ok(sub) => sub,
+                Err(err) => {
+                    yield Err(err);
+                    return;
+                }
+            };
+            let local_id = *sub.local_id();
+            let mut stream = sub.into_stream();
+            while let Some(item) = stream.next().await {
+                let value = match item {
+                    Ok(value) => value,
+                    Err(err) => {
+                        debug!(%err, %local_id, "stream lagged");
+                        continue;
+                    }
+                };
+                if let Ok(tx) = serde_json::from_str::<N::TransactionResponse>(value.get()) {
+                    yield Ok(tx);
+                } else if let Ok(hash) = serde_json::from_str::<B256>(value.get()) {
+                    match provider.get_transaction_by_hash(hash).await {
+                        Ok(Some(tx)) => yield Ok(tx),
+                        Ok(None) => trace!(%hash, "pending transaction dropped before fetching"),
+                        Err(err) => yield Err(err),
+                    }
+                } else {
+                    trace!(value = value.get(), %local_id, "skipping unexpected notification");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "pubsub")]
+mod resubscribe {
+    use crate::Provider;
+    use alloy_json_rpc::{RpcParam, RpcReturn};
+    use alloy_network::Network;
+    use alloy_transport::{Transport, TransportResult};
+    use futures::{Stream, StreamExt};
+    use std::time::Duration;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    use tokio::time::timeout as with_timeout;
+    #[cfg(target_arch = "wasm32")]
+    use wasmtimer::tokio::timeout as with_timeout;
+
+    /// Subscribes with the given `eth_subscribe` params, and resubscribes whenever no item is
+    /// received within `timeout`, or when the subscription ends.
+    ///
+    /// Some nodes stop delivering notifications without closing the subscription, for example
+    /// after an upstream failover. The stale subscription is cancelled before subscribing again.
+    /// Items delivered during the resubscription gap are missed, so `timeout` should be chosen
+    /// well above the expected interval between items.
+    ///
+    /// The stream yields an error and ends if subscribing fails.
+    pub fn resubscribe_on_timeout<'a, P, T, N, Params, R>(
+        provider: &'a P,
+        params: Params,
+        timeout: Duration,
+    ) -> impl Stream<Item = TransportResult<R>> + 'a
+    where
+        P: Provider<T, N>,
+        T: Transport + Clone,
+        N: Network,
+        Params: RpcParam + 'a,
+        R: RpcReturn,
+    {
+        async_stream::stream! {
+            loop {
+                let sub = match provider.subscribe::<_, R>(params.clone()).await {
+                    Ok(sub) => sub,
+                    Err(err) => {
+                        yield Err(err);
+                        break;
+                    }
+                };
+                let local_id = *sub.local_id();
+                let mut stream = sub.into_stream();
+                loop {
+                    match with_timeout(timeout, stream.next()).await {
+                        Ok(Some(item)) => yield Ok(item),
+                        Ok(None) => {
+                            debug!(%local_id, "subscription ended, resubscribing");
+                            break;
+                        }
+                        Err(_) => {
+                            debug!(%local_id, ?timeout, "no item received, resubscribing");
+                            break;
+                        }
+                    }
+                }
+                if let Err(err) = provider.unsubscribe(local_id.into()).await {
+                    debug!(%err, %local_id, "failed to cancel stale subscription");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Address, LogData, U256};
+    use alloy_sol_types::sol;
+
+    sol! {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+    }
+
+    #[tokio::test]
+    async fn merge_tagged_streams() {
+        let a = futures::stream::iter([1, 2]);
+        let b = futures::stream::iter([3]);
+        let mut items: Vec<_> = merge_tagged([("a", a.boxed()), ("b", b.boxed())]).collect().await;
+        items.sort();
+        assert_eq!(items, vec![("a", 1), ("a", 2), ("b", 3)]);
+    }
+
+    #[tokio::test]
+    async fn decode_matching_logs() {
+        let from = address!("00000000000000000000000000000000000000aa");
+        let to = address!("00000000000000000000000000000000000000bb");
+        let transfer = Transfer { from, to, value: U256::from(7) };
+        let matching = Log {
+            inner: alloy_primitives::Log {
+                address: Address::ZERO,
+                data: transfer.encode_log_data(),
+            },
+            ..Default::default()
+        };
+        let other = Log {
+            inner: alloy_primitives::Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(vec![], Default::default()),
+            },
+            ..Default::default()
+        };
+
+        let decoded: Vec<_> =
+            decode_logs::<Transfer, _>(futures::stream::iter([other, matching])).collect().await;
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].inner.data.to, to);
+        assert_eq!(decoded[0].inner.data.value, U256::from(7));
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn full_pending_transactions_ws() {
+        use crate::{Provider, RootProvider};
+        use alloy_network::{Ethereum, TransactionBuilder};
+        use alloy_node_bindings::Anvil;
+        use alloy_rpc_types::TransactionRequest;
+
+        let anvil = Anvil::new().spawn();
+        let ws = alloy_rpc_client::WsConnect::new(anvil.ws_endpoint());
+        let client = alloy_rpc_client::RpcClient::connect_pubsub(ws).await.unwrap();
+        let provider = RootProvider::<_, Ethereum>::new(client);
+
+        let mut stream = Box::pin(full_pending_transactions(&provider));
+        let tx = TransactionRequest::default()
+            .with_from(anvil.addresses()[0])
+            .with_to(Address::ZERO)
+            .with_value(U256::from(1));
+        // Leave the subscription time to be created before sending the transaction.
+        let send = async {
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            provider.send_transaction(tx).await
+        };
+        let (tx, pending) = futures::join!(stream.next(), send);
+        let tx = tx.unwrap().unwrap();
+        assert_eq!(tx.hash, *pending.unwrap().tx_hash());
+        assert_eq!(tx.from, anvil.addresses()[0]);
+    }
+}
@@ -0,0 +1,58 @@
+//! Helpers shared by the unit tests of this crate.
+
+use crate::RootProvider;
+use alloy_json_rpc::{
+    ErrorPayload, RequestPacket, Response, ResponsePacket, ResponsePayload, RpcError,
+    SerializedRequest,
+};
+use alloy_network::Network;
+use alloy_rpc_client::RpcClient;
+use alloy_transport::{BoxTransport, TransportError, TransportFut, TransportResult};
+use serde_json::{value::RawValue, Value};
+
+/// Returns a transport that answers requests with `handler`.
+///
+/// The handler is called with the method name and the JSON encoded params
+/// (`null` if there are none). A [`RpcError::ErrorResp`] it returns is sent
+/// back as an error response, any other error fails the transport call itself.
+/// Batches are answered request by request.
+pub(crate) fn mock<F>(handler: F) -> BoxTransport
+where
+    F: Fn(&str, &str) -> TransportResult<Value> + Clone + Send + Sync + 'static,
+{
+    let service = tower::service_fn(move |req: RequestPacket| -> TransportFut<'static> {
+        let respond = |req: &SerializedRequest| {
+            let params = req.params().map_or("null", RawValue::get);
+            let payload = match handler(req.method(), params) {
+                Ok(result) => ResponsePayload::Success(
+                    serde_json::value::to_raw_value(&result).expect("result serializes"),
+                ),
+                Err(RpcError::ErrorResp(payload)) => ResponsePayload::Failure(payload),
+                Err(err) => return Err(err),
+            };
+            Ok(Response { id: req.id().clone(), payload })
+        };
+        let res = match req {
+            RequestPacket::Single(req) => respond(&req).map(ResponsePacket::Single),
+            RequestPacket::Batch(reqs) => {
+                reqs.iter().map(respond).collect::<Result<_, _>>().map(ResponsePacket::Batch)
+            }
+        };
+        Box::pin(async move { res })
+    });
+    BoxTransport::new(service)
+}
+
+/// Returns a provider whose node answers requests with `handler`, see [`mock`].
+pub(crate) fn mock_provider<N, F>(handler: F) -> RootProvider<BoxTransport, N>
+where
+    N: Network,
+    F: Fn(&str, &str) -> TransportResult<Value> + Clone + Send + Sync + 'static,
+{
+    RootProvider::new(RpcClient::new(mock(handler), true))
+}
+
+/// Returns an error response with the given code and message.
+pub(crate) fn error_resp(code: i64, message: &str) -> TransportError {
+    RpcError::ErrorResp(ErrorPayload { code, message: message.into(), data: None })
+}
@@ -1,6 +1,8 @@
 //! Provider-related utilities.
 
-use alloy_primitives::{U128, U64};
+use alloy_eips::eip4844::BlobParams;
+use alloy_primitives::{Bytes, U128, U64};
+use alloy_rpc_types::FeeHistory;
 
 /// The number of blocks from the past for which the fee rewards are fetched for fee estimation.
 pub const EIP1559_FEE_ESTIMATION_PAST_BLOCKS: u64 = 10;
@@ -9,6 +11,55 @@ pub const EIP1559_BASE_FEE_MULTIPLIER: u128 = 2;
 /// The default percentile of gas premiums that are fetched for fee estimation.
 pub const EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE: f64 = 20.0;
 
+/// The maximum number of blocks nodes return in a single `eth_feeHistory` response.
+pub const MAX_FEE_HISTORY_BLOCKS: u64 = 1024;
+/// The maximum number of `eth_feeHistory` requests in flight when fetching a long range of blocks.
+pub const MAX_CONCURRENT_FEE_HISTORY_REQUESTS: usize = 4;
+
+/// The number of blocks from the past whose blob gas usage is used for blob fee estimation.
+pub const EIP4844_FEE_ESTIMATION_PAST_BLOCKS: u64 = 20;
+
+/// How soon a blob transaction should be included, for [`Provider::estimate_blob_fee`].
+///
+/// The estimated `max_fee_per_blob_gas` is the highest blob base fee projected over the blocks
+/// the transaction should remain includable for.
+///
+/// [`Provider::estimate_blob_fee`]: crate::Provider::estimate_blob_fee
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BlobFeeUrgency {
+    /// Pay the blob base fee of the next block. The transaction waits if the fee rises.
+    Low,
+    /// Remain includable for the next 3 blocks if they use as much blob gas as the last ones.
+    #[default]
+    Medium,
+    /// Remain includable for the next 6 blocks even if they are all full, which is about twice
+    /// the blob base fee of the next block in Cancun, and about 1.5 times in Prague.
+    High,
+}
+
+impl BlobFeeUrgency {
+    /// Returns the number of blocks to remain includable for, and the blob gas used ratio to
+    /// assume, if not the observed one.
+    const fn horizon(&self) -> (usize, Option<f64>) {
+        match self {
+            Self::Low => (1, None),
+            Self::Medium => (3, None),
+            Self::High => (6, Some(1.0)),
+        }
+    }
+
+    /// Estimates the `max_fee_per_blob_gas` of a transaction from a fee history and the blob
+    /// parameters of the chain's current fork, if its next block supports blobs.
+    pub fn estimate(&self, fee_history: &FeeHistory, params: BlobParams) -> Option<u128> {
+        let (blocks, ratio) = self.horizon();
+        let fees = match ratio {
+            Some(ratio) => fee_history.project_blob_base_fees(blocks, ratio, params),
+            None => fee_history.projected_blob_base_fees(blocks, params),
+        };
+        fees.into_iter().max()
+    }
+}
+
 /// An estimator function for EIP1559 fees.
 pub type EstimatorFunction = fn(u128, &[Vec<u128>]) -> Eip1559Estimation;
 
@@ -21,7 +72,7 @@ pub struct Eip1559Estimation {
     pub max_priority_fee_per_gas: u128,
 }
 
-fn estimate_priority_fee(rewards: &[Vec<u128>]) -> u128 {
+pub(crate) fn estimate_priority_fee(rewards: &[Vec<u128>]) -> u128 {
     let mut rewards =
         rewards.iter().filter_map(|r| r.first()).filter(|r| **r > 0_u128).collect::<Vec<_>>();
     if rewards.is_empty() {
@@ -55,6 +106,22 @@ pub fn eip1559_default_estimator(
     }
 }
 
+/// An EIP-1559 fee estimator for Arbitrum.
+///
+/// The Arbitrum sequencer orders transactions by arrival, and does not pay priority fees, so this
+/// only pays for the increase of the base fee. Set it on a
+/// [`GasFiller`](crate::fillers::GasFiller) with
+/// [`GasFiller::with_eip1559_estimator`](crate::fillers::GasFiller::with_eip1559_estimator).
+pub const fn eip1559_arbitrum_estimator(
+    base_fee_per_gas: u128,
+    _rewards: &[Vec<u128>],
+) -> Eip1559Estimation {
+    Eip1559Estimation {
+        max_fee_per_gas: base_fee_per_gas * EIP1559_BASE_FEE_MULTIPLIER,
+        max_priority_fee_per_gas: 0,
+    }
+}
+
 /// Convert `U128` to `u128`.
 pub(crate) fn convert_u128(r: U128) -> u128 {
     r.to::<u128>()
@@ -64,11 +131,58 @@ pub(crate) fn convert_u64(r: U64) -> u64 {
     r.to::<u64>()
 }
 
+/// A transaction signed by `eth_signTransaction`, which nodes return either as its raw encoding
+/// or, like geth, as an object holding it along with the decoded transaction.
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+pub(crate) enum SignedTransaction {
+    Raw(Bytes),
+    Object { raw: Bytes },
+}
+
+impl SignedTransaction {
+    /// Returns the EIP-2718 encoding of the transaction.
+    pub(crate) fn into_raw(self) -> Bytes {
+        match self {
+            Self::Raw(raw) | Self::Object { raw } => raw,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::vec;
 
+    #[test]
+    fn blob_fee_urgency() {
+        let fee_history = FeeHistory {
+            base_fee_per_blob_gas: vec![1_000, 1_000_000],
+            blob_gas_used_ratio: vec![0.0],
+            ..Default::default()
+        };
+        let cancun = BlobParams::cancun();
+        // Empty blocks decrease the fee, so only the next block's fee matters.
+        assert_eq!(BlobFeeUrgency::Low.estimate(&fee_history, cancun), Some(1_000_000));
+        assert_eq!(BlobFeeUrgency::Medium.estimate(&fee_history, cancun), Some(1_000_000));
+        let high = BlobFeeUrgency::High.estimate(&fee_history, cancun).unwrap();
+        assert!((1_800_000..2_000_000).contains(&high), "{high}");
+        // Full blocks raise the fee more slowly in Prague.
+        let high = BlobFeeUrgency::High.estimate(&fee_history, BlobParams::prague()).unwrap();
+        assert!((1_400_000..1_600_000).contains(&high), "{high}");
+
+        let pre_cancun = FeeHistory { base_fee_per_blob_gas: vec![0, 0], ..Default::default() };
+        assert_eq!(BlobFeeUrgency::High.estimate(&pre_cancun, cancun), None);
+    }
+
+    #[test]
+    fn signed_transaction_formats() {
+        for json in [r#""0x02ab""#, r#"{"raw":"0x02ab","tx":{}}"#] {
+            let signed: SignedTransaction = serde_json::from_str(json).unwrap();
+            assert_eq!(signed.into_raw(), Bytes::from_static(&[0x02, 0xab]));
+        }
+    }
+
     #[test]
     fn test_estimate_priority_fee() {
         let rewards =
@@ -122,4 +236,13 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_eip1559_arbitrum_estimator() {
+        let rewards = vec![vec![200_000_000_000_u128], vec![300_000_000_000_u128]];
+        assert_eq!(
+            super::eip1559_arbitrum_estimator(10_000_000_u128, &rewards),
+            Eip1559Estimation { max_fee_per_gas: 20_000_000_u128, max_priority_fee_per_gas: 0 }
+        );
+    }
 }
@@ -0,0 +1,284 @@
+//! Signature verification for externally owned and smart contract accounts.
+//!
+//! A signature of an externally owned account is verified by recovering its signer. Contract
+//! accounts cannot sign, and instead validate signatures themselves through the
+//! `isValidSignature` function of [ERC-1271]. Accounts that are not deployed yet can still sign
+//! with an [ERC-6492] wrapped signature, which also carries the call to the factory deploying the
+//! account: the signature is then verified by deploying the account in a simulated call.
+//!
+//! ```no_run
+//! use alloy_primitives::{address, b256, bytes};
+//! use alloy_provider::{verify::verify_signature, ProviderBuilder};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = ProviderBuilder::new().on_http("https://eth.merkle.io".parse()?);
+//! let account = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+//! let hash = b256!("a8d4b1e0d3bbc5a28dd6e4e7e0fc0a1d2b7e6a0c4d1f8f1b1ab7e5f2d3c4b5a6");
+//! let signature = bytes!("00");
+//! if verify_signature(&provider, account, hash, &signature).await? {
+//!     println!("{account} signed {hash}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+//! [ERC-6492]: https://eips.ethereum.org/EIPS/eip-6492
+
+use crate::Provider;
+use alloy_network::{Network, TransactionBuilder};
+use alloy_primitives::{b256, hex, Address, Bytes, Signature, B256, U256};
+use alloy_sol_types::{sol, SolCall, SolValue};
+use alloy_transport::{Transport, TransportResult};
+
+sol! {
+    /// The [ERC-1271](https://eips.ethereum.org/EIPS/eip-1271) signature validation interface.
+    #[allow(missing_docs)]
+    interface IERC1271 {
+        /// Returns [`ERC1271_MAGIC_VALUE`](super::ERC1271_MAGIC_VALUE) if `signature` is a valid
+        /// signature of `hash` by the account.
+        function isValidSignature(bytes32 hash, bytes signature) external view returns (bytes4 magicValue);
+    }
+}
+
+/// The value returned by `isValidSignature` for valid signatures, its function selector.
+pub const ERC1271_MAGIC_VALUE: [u8; 4] = IERC1271::isValidSignatureCall::SELECTOR;
+
+/// The suffix of [ERC-6492] wrapped signatures.
+///
+/// [ERC-6492]: https://eips.ethereum.org/EIPS/eip-6492
+pub const ERC6492_MAGIC_SUFFIX: B256 =
+    b256!("6492649264926492649264926492649264926492649264926492649264926492");
+
+/// Init code validating a signature of a counterfactual account.
+///
+/// The code expects to be followed by the account and the factory as words, the length of the
+/// factory calldata as a word, the factory calldata, and the `isValidSignature` calldata. It calls
+/// the factory, ignoring failures as the account may already be deployed in the meantime, then
+/// returns the output of `isValidSignature`, or reverts if the call fails.
+const ERC6492_VALIDATOR: [u8; 57] = hex!(
+    "60393803806039600039" // codecopy(0, 57, codesize - 57)
+    "60006000604051606060006020515af150" // pop(call(gas, factory, 0, 96, factoryLen, 0, 0))
+    "602081604051606001808403906000515afa" // staticcall(gas, account, 96 + factoryLen, .., end, 32)
+    "603457600080fd5b602090f3" // revert(0, 0) on failure, else return(end, 32)
+);
+
+/// An [ERC-6492] wrapped signature of an account that may not be deployed yet.
+///
+/// [ERC-6492]: https://eips.ethereum.org/EIPS/eip-6492
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Erc6492Signature {
+    /// The factory deploying the account.
+    pub factory: Address,
+    /// The calldata of the factory call deploying the account.
+    pub factory_calldata: Bytes,
+    /// The signature validated by the deployed account.
+    pub signature: Bytes,
+}
+
+impl Erc6492Signature {
+    /// Decodes a wrapped signature, returning `None` if `signature` is not wrapped.
+    pub fn decode(signature: &[u8]) -> Option<Self> {
+        let wrapped = signature.strip_suffix(ERC6492_MAGIC_SUFFIX.as_slice())?;
+        let (factory, factory_calldata, signature) =
+            <(Address, Bytes, Bytes)>::abi_decode_params(wrapped, true).ok()?;
+        Some(Self { factory, factory_calldata, signature })
+    }
+
+    /// Encodes the wrapped signature.
+    pub fn encode(&self) -> Bytes {
+        let mut encoded =
+            (self.factory, &self.factory_calldata, &self.signature).abi_encode_params();
+        encoded.extend_from_slice(ERC6492_MAGIC_SUFFIX.as_slice());
+        encoded.into()
+    }
+
+    /// Returns the init code of a call validating the signature of `hash` by `account`, deploying
+    /// the account first.
+    fn validator_code(&self, account: Address, hash: B256) -> Bytes {
+        let is_valid_signature =
+            IERC1271::isValidSignatureCall { hash, signature: self.signature.clone() };
+        [
+            &ERC6492_VALIDATOR[..],
+            account.into_word().as_slice(),
+            self.factory.into_word().as_slice(),
+            &U256::from(self.factory_calldata.len()).to_be_bytes::<32>(),
+            &self.factory_calldata,
+            &is_valid_signature.abi_encode(),
+        ]
+        .concat()
+        .into()
+    }
+}
+
+/// Verifies that `signature` is a valid signature of `hash` by `account`.
+///
+/// [ERC-6492] wrapped signatures are validated by deploying the account in a simulated call if
+/// it is not deployed yet. Signatures of deployed contract accounts are validated with their
+/// [ERC-1271] `isValidSignature` function, and other signatures by recovering their signer. A
+/// reverting validation call counts as an invalid signature, while other errors are returned.
+///
+/// [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+/// [ERC-6492]: https://eips.ethereum.org/EIPS/eip-6492
+pub async fn verify_signature<P, T, N>(
+    provider: &P,
+    account: Address,
+    hash: B256,
+    signature: &[u8],
+) -> TransportResult<bool>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let wrapped = Erc6492Signature::decode(signature);
+    let deployed = !provider.get_code_at(account).await?.is_empty();
+
+    match wrapped {
+        Some(wrapped) if !deployed => {
+            let tx = N::TransactionRequest::default()
+                .with_deploy_code(wrapped.validator_code(account, hash));
+            is_magic_value(provider.call(&tx).await)
+        }
+        Some(wrapped) => is_valid_signature(provider, account, hash, wrapped.signature).await,
+        None if deployed => {
+            is_valid_signature(provider, account, hash, Bytes::copy_from_slice(signature)).await
+        }
+        None => Ok(Signature::try_from(signature)
+            .and_then(|signature| signature.recover_address_from_prehash(&hash))
+            .is_ok_and(|signer| signer == account)),
+    }
+}
+
+/// Calls the [ERC-1271] `isValidSignature` function of `account`.
+///
+/// [ERC-1271]: https://eips.ethereum.org/EIPS/eip-1271
+async fn is_valid_signature<P, T, N>(
+    provider: &P,
+    account: Address,
+    hash: B256,
+    signature: Bytes,
+) -> TransportResult<bool>
+where
+    P: Provider<T, N>,
+    T: Transport + Clone,
+    N: Network,
+{
+    let tx = N::TransactionRequest::default()
+        .with_to(account)
+        .with_call(&IERC1271::isValidSignatureCall { hash, signature });
+    is_magic_value(provider.call(&tx).await)
+}
+
+/// Returns whether the output of a validation call is the ERC-1271 magic value, treating error
+/// responses as reverts.
+fn is_magic_value(output: TransportResult<Bytes>) -> TransportResult<bool> {
+    let output = match output {
+        Ok(output) => output,
+        Err(e) if e.is_error_resp() => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    Ok(IERC1271::isValidSignatureCall::abi_decode_returns(&output, true)
+        .is_ok_and(|ret| ret.magicValue == ERC1271_MAGIC_VALUE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{error_resp, mock_provider},
+        RootProvider,
+    };
+    use alloy_primitives::{address, bytes};
+    use alloy_rpc_client::ErrorContext;
+    use alloy_rpc_types::{state::StateOverride, TransactionRequest};
+    use alloy_signer::SignerSync;
+    use alloy_signer_wallet::LocalWallet;
+    use alloy_transport::BoxTransport;
+    use serde_json::json;
+
+    const FACTORY: Address = address!("00000000000000000000000000000000000000fa");
+
+    /// Returns a provider of a chain where `code` is deployed at every address, and whose calls
+    /// return the magic value only for `valid`.
+    fn chain(code: Bytes, valid: Bytes) -> RootProvider<BoxTransport> {
+        let provider = mock_provider(move |method, params| match method {
+            "eth_getCode" => Ok(json!(code)),
+            "eth_call" => {
+                let (tx, _, _): (TransactionRequest, String, StateOverride) =
+                    serde_json::from_str(params).unwrap();
+                let input = tx.input.input().unwrap();
+                let signature = if tx.to.is_some() {
+                    IERC1271::isValidSignatureCall::abi_decode(input, true).unwrap().signature
+                } else {
+                    // The account and factory follow the validator code.
+                    let (code, args) = input.split_at(ERC6492_VALIDATOR.len());
+                    assert_eq!(code, ERC6492_VALIDATOR);
+                    assert_eq!(&args[44..64], FACTORY.as_slice());
+                    let calldata_len = usize::from(args[95]);
+                    assert_eq!(&args[96..96 + calldata_len], b"deploy");
+                    let calldata = &args[96 + calldata_len..];
+                    IERC1271::isValidSignatureCall::abi_decode(calldata, true).unwrap().signature
+                };
+                if signature == valid {
+                    let ret =
+                        IERC1271::isValidSignatureCall::abi_encode_returns(&(ERC1271_MAGIC_VALUE,));
+                    Ok(json!(Bytes::from(ret)))
+                } else {
+                    Err(error_resp(3, "execution reverted"))
+                }
+            }
+            _ => unreachable!(),
+        });
+        // Reverts are recognized through the request context of the errors.
+        provider.client().set_error_context(ErrorContext::new());
+        provider
+    }
+
+    #[tokio::test]
+    async fn verifies_eoa_signatures() {
+        let wallet = LocalWallet::random();
+        let hash = B256::repeat_byte(1);
+        let signature = wallet.sign_hash_sync(&hash).unwrap().as_bytes();
+
+        let provider = chain(Bytes::new(), Bytes::new());
+        assert!(verify_signature(&provider, wallet.address(), hash, &signature).await.unwrap());
+        let other = B256::repeat_byte(2);
+        assert!(!verify_signature(&provider, wallet.address(), other, &signature).await.unwrap());
+        assert!(!verify_signature(&provider, wallet.address(), hash, &[0; 3]).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn verifies_contract_signatures() {
+        let account = Address::repeat_byte(0xaa);
+        let hash = B256::repeat_byte(1);
+        let provider = chain(bytes!("00"), bytes!("c0ffee"));
+        assert!(verify_signature(&provider, account, hash, &bytes!("c0ffee")).await.unwrap());
+        assert!(!verify_signature(&provider, account, hash, &bytes!("dead")).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn verifies_counterfactual_signatures() {
+        let account = Address::repeat_byte(0xaa);
+        let hash = B256::repeat_byte(1);
+        let wrap = |signature| {
+            Erc6492Signature {
+                factory: FACTORY,
+                factory_calldata: Bytes::from_static(b"deploy"),
+                signature,
+            }
+            .encode()
+        };
+        let wrapped = wrap(bytes!("c0ffee"));
+        assert_eq!(Erc6492Signature::decode(&wrapped).unwrap().signature, bytes!("c0ffee"));
+        assert_eq!(Erc6492Signature::decode(&bytes!("c0ffee")), None);
+
+        let provider = chain(Bytes::new(), bytes!("c0ffee"));
+        assert!(verify_signature(&provider, account, hash, &wrapped).await.unwrap());
+        assert!(!verify_signature(&provider, account, hash, &wrap(bytes!("dead"))).await.unwrap());
+
+        // Deployed accounts validate the unwrapped signature.
+        let provider = chain(bytes!("00"), bytes!("c0ffee"));
+        assert!(verify_signature(&provider, account, hash, &wrapped).await.unwrap());
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{ConnectionHandle, PubSubConnect};
+use alloy_json_rpc::{Id, PubSubItem, Request, ResponsePayload, RpcError};
+use alloy_primitives::U64;
+use alloy_transport::{impl_future, TransportErrorKind, TransportResult};
+use futures::future::{self, Either};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::tokio::sleep;
+
+/// The default time a [`ChainIdCheck`] waits for the chain ID of a new backend.
+pub const DEFAULT_CHAIN_ID_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A [`PubSubConnect`] that checks the chain ID of the endpoint every time it connects.
+///
+/// The chain ID is requested on each new backend, before it is handed to the pubsub service, so
+/// that a connection or reconnection to an endpoint of another chain fails with
+/// [`TransportErrorKind::ChainIdMismatch`] instead of silently resuming on the wrong chain.
+/// The backend is shut down if the check fails, including when the endpoint does not answer
+/// with a chain ID within [`DEFAULT_CHAIN_ID_CHECK_TIMEOUT`], or the timeout set with
+/// [`with_timeout`](Self::with_timeout).
+#[derive(Clone, Debug)]
+pub struct ChainIdCheck<C> {
+    inner: C,
+    chain_id: u64,
+    timeout: Duration,
+}
+
+impl<C> ChainIdCheck<C> {
+    /// Wrap a connector, expecting the endpoint to report the given chain ID.
+    pub const fn new(inner: C, chain_id: u64) -> Self {
+        Self { inner, chain_id, timeout: DEFAULT_CHAIN_ID_CHECK_TIMEOUT }
+    }
+
+    /// Set the time to wait for the chain ID of a new backend.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Get the expected chain ID.
+    pub const fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    /// Get a reference to the wrapped connector.
+    pub const fn inner(&self) -> &C {
+        &self.inner
+    }
+}
+
+impl<C: PubSubConnect> ChainIdCheck<C> {
+    async fn check(&self, mut handle: ConnectionHandle) -> TransportResult<ConnectionHandle> {
+        let timer = Box::pin(sleep(self.timeout));
+        let res = match future::select(Box::pin(request_chain_id(&mut handle)), timer).await {
+            Either::Left((res, _)) => res,
+            Either::Right(_) => Err(TransportErrorKind::timeout(self.timeout)),
+        };
+        match res.and_then(|actual| TransportErrorKind::check_chain_id(self.chain_id, actual)) {
+            Ok(()) => Ok(handle),
+            Err(err) => {
+                handle.shutdown();
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Request the chain ID of the endpoint of a new backend.
+async fn request_chain_id(handle: &mut ConnectionHandle) -> TransportResult<u64> {
+    let id = Id::String("alloy-chain-id-check".into());
+    let req = Request::new("eth_chainId", id.clone(), ());
+    let brv = req.serialize().map_err(RpcError::ser_err)?.take_request();
+    handle.to_socket.send(brv).map_err(|_| TransportErrorKind::backend_gone())?;
+
+    // the backend is new, so the only item expected is the response to the check
+    let payload = loop {
+        match handle.from_socket.recv().await {
+            Some(PubSubItem::Response(resp)) if resp.id == id => break resp.payload,
+            Some(item) => debug!(?item, "ignoring item received before the chain ID"),
+            None => return Err(TransportErrorKind::backend_gone()),
+        }
+    };
+
+    match payload {
+        ResponsePayload::Success(result) => serde_json::from_str::<U64>(result.get())
+            .map(|chain_id| chain_id.to::<u64>())
+            .map_err(|err| RpcError::deser_err(err, result.get())),
+        ResponsePayload::Failure(err) => Err(RpcError::err_resp(err)),
+    }
+}
+
+impl<C: PubSubConnect> PubSubConnect for ChainIdCheck<C> {
+    fn is_local(&self) -> bool {
+        self.inner.is_local()
+    }
+
+    fn connect(&self) -> impl_future!(<Output = TransportResult<ConnectionHandle>>) {
+        async move { self.check(self.inner.connect().await?).await }
+    }
+
+    fn try_reconnect(&self) -> impl_future!(<Output = TransportResult<ConnectionHandle>>) {
+        async move { self.check(self.inner.try_reconnect().await?).await }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::Response;
+    use serde_json::value::RawValue;
+    use std::sync::{Arc, Mutex};
+
+    /// A connector whose backend answers requests with `result`, or never answers if unset, and
+    /// records whether it was shut down.
+    #[derive(Clone, Debug)]
+    struct Backend {
+        result: Option<&'static str>,
+        shut_down: Arc<Mutex<Option<bool>>>,
+    }
+
+    impl PubSubConnect for Backend {
+        fn is_local(&self) -> bool {
+            true
+        }
+
+        async fn connect(&self) -> TransportResult<ConnectionHandle> {
+            let (handle, mut interface) = ConnectionHandle::new();
+            let (result, shut_down) = (self.result, self.shut_down.clone());
+            tokio::spawn(async move {
+                while let Some(req) = interface.from_frontend.recv().await {
+                    let Some(result) = result else { continue };
+                    let id = serde_json::from_str::<serde_json::Value>(req.get()).unwrap()["id"]
+                        .as_str()
+                        .map(|id| Id::String(id.into()))
+                        .unwrap();
+                    let payload =
+                        ResponsePayload::Success(RawValue::from_string(result.into()).unwrap());
+                    let _ =
+                        interface.send_to_frontend(PubSubItem::Response(Response { id, payload }));
+                }
+                *shut_down.lock().unwrap() = Some(interface.shutdown.try_recv().is_ok());
+            });
+            Ok(handle)
+        }
+    }
+
+    async fn check(result: Option<&'static str>) -> (TransportResult<()>, Option<bool>) {
+        let shut_down = Arc::new(Mutex::new(None));
+        let backend = Backend { result, shut_down: shut_down.clone() };
+        let check = ChainIdCheck::new(backend, 1).with_timeout(Duration::from_millis(50));
+        let res = check.connect().await.map(drop);
+        // let the backend see the shutdown, or the dropped handle
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let shut_down = *shut_down.lock().unwrap();
+        (res, shut_down)
+    }
+
+    #[tokio::test]
+    async fn checks_chain_id_and_shuts_down_on_failure() {
+        let (res, shut_down) = check(Some(r#""0x1""#)).await;
+        assert!(res.is_ok());
+        assert_eq!(shut_down, Some(false));
+
+        let (res, shut_down) = check(Some(r#""0x5""#)).await;
+        assert!(matches!(
+            res.unwrap_err().as_transport_err(),
+            Some(TransportErrorKind::ChainIdMismatch { expected: 1, actual: 5 })
+        ));
+        assert_eq!(shut_down, Some(true));
+
+        let (res, shut_down) = check(Some(r#""mainnet""#)).await;
+        assert!(matches!(res, Err(RpcError::DeserError { .. })));
+        assert_eq!(shut_down, Some(true));
+
+        let (res, shut_down) = check(None).await;
+        assert!(matches!(
+            res.unwrap_err().as_transport_err(),
+            Some(TransportErrorKind::Timeout(timeout)) if *timeout == Duration::from_millis(50)
+        ));
+        assert_eq!(shut_down, Some(true));
+    }
+}
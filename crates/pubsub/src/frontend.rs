@@ -1,11 +1,11 @@
-use crate::{ix::PubSubInstruction, managers::InFlight, RawSubscription};
+use crate::{ix::PubSubInstruction, managers::InFlight, BackpressurePolicy, RawSubscription};
 use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, SerializedRequest};
 use alloy_primitives::U256;
 use alloy_transport::{TransportError, TransportErrorKind, TransportFut, TransportResult};
 use futures::{future::try_join_all, FutureExt, TryFutureExt};
 use std::{
     future::Future,
-    sync::atomic::{AtomicUsize, Ordering},
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
     task::{Context, Poll},
 };
 use tokio::sync::{mpsc, oneshot};
@@ -20,19 +20,31 @@ pub struct PubSubFrontend {
     /// The number of items to buffer in new subscription channels. Defaults to
     /// 16. See [`tokio::sync::broadcast::channel`] for a description.
     channel_size: AtomicUsize,
+    /// The policy applied when new subscription channels are full. Defaults to
+    /// [`BackpressurePolicy::DropOldest`].
+    policy: AtomicU8,
 }
 
 impl Clone for PubSubFrontend {
     fn clone(&self) -> Self {
         let channel_size = self.channel_size.load(Ordering::Relaxed);
-        Self { tx: self.tx.clone(), channel_size: AtomicUsize::new(channel_size) }
+        let policy = self.policy.load(Ordering::Relaxed);
+        Self {
+            tx: self.tx.clone(),
+            channel_size: AtomicUsize::new(channel_size),
+            policy: AtomicU8::new(policy),
+        }
     }
 }
 
 impl PubSubFrontend {
     /// Create a new frontend.
     pub(crate) const fn new(tx: mpsc::UnboundedSender<PubSubInstruction>) -> Self {
-        Self { tx, channel_size: AtomicUsize::new(16) }
+        Self {
+            tx,
+            channel_size: AtomicUsize::new(16),
+            policy: AtomicU8::new(BackpressurePolicy::DropOldest as u8),
+        }
     }
 
     /// Get the subscription ID for a local ID.
@@ -64,9 +76,10 @@ impl PubSubFrontend {
     ) -> impl Future<Output = TransportResult<Response>> + Send + 'static {
         let tx = self.tx.clone();
         let channel_size = self.channel_size.load(Ordering::Relaxed);
+        let policy = self.backpressure_policy();
 
         async move {
-            let (in_flight, rx) = InFlight::new(req, channel_size);
+            let (in_flight, rx) = InFlight::new(req, channel_size, policy);
             tx.send(PubSubInstruction::Request(in_flight))
                 .map_err(|_| TransportErrorKind::backend_gone())?;
             rx.await.map_err(|_| TransportErrorKind::backend_gone())?
@@ -94,13 +107,33 @@ impl PubSubFrontend {
     }
 
     /// Set the channel size. This is the number of items to buffer in new
-    /// subscription channels. Defaults to 16. See
-    /// [`tokio::sync::broadcast`] for a description of relevant
-    /// behavior.
+    /// subscription channels. Defaults to 16. The channels round it up to a
+    /// power of two. See [`tokio::sync::broadcast`] for a description of
+    /// relevant behavior.
     pub fn set_channel_size(&self, channel_size: usize) {
         debug_assert_ne!(channel_size, 0, "channel size must be non-zero");
         self.channel_size.store(channel_size, Ordering::Relaxed);
     }
+
+    /// Get the currently configured backpressure policy. This is what happens
+    /// when a new subscription channel is full. Defaults to
+    /// [`BackpressurePolicy::DropOldest`].
+    pub fn backpressure_policy(&self) -> BackpressurePolicy {
+        BackpressurePolicy::from_u8(self.policy.load(Ordering::Relaxed))
+    }
+
+    /// Set the backpressure policy. This is what happens when a new
+    /// subscription channel is full. Defaults to
+    /// [`BackpressurePolicy::DropOldest`].
+    ///
+    /// Like the channel size, this only applies to the subscriptions created
+    /// afterwards. The policy of an existing subscription can be changed with
+    /// [`Subscription::set_policy`].
+    ///
+    /// [`Subscription::set_policy`]: crate::Subscription::set_policy
+    pub fn set_backpressure_policy(&self, policy: BackpressurePolicy) {
+        self.policy.store(policy as u8, Ordering::Relaxed);
+    }
 }
 
 impl tower::Service<RequestPacket> for PubSubFrontend {
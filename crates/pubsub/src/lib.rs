@@ -18,6 +18,9 @@
 #[macro_use]
 extern crate tracing;
 
+mod chain_id;
+pub use chain_id::{ChainIdCheck, DEFAULT_CHAIN_ID_CHECK_TIMEOUT};
+
 mod connect;
 pub use connect::PubSubConnect;
 
@@ -34,4 +37,4 @@ mod managers;
 mod service;
 
 mod sub;
-pub use sub::{RawSubscription, Subscription, SubscriptionItem};
+pub use sub::{BackpressurePolicy, RawSubscription, Subscription, SubscriptionItem};
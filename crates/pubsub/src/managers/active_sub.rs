@@ -1,8 +1,15 @@
-use crate::RawSubscription;
+use crate::{
+    sub::{BackpressurePolicy, ChannelConfig},
+    RawSubscription,
+};
 use alloy_json_rpc::SerializedRequest;
 use alloy_primitives::B256;
 use serde_json::value::RawValue;
-use std::{fmt, hash::Hash};
+use std::{
+    fmt,
+    hash::Hash,
+    sync::{atomic::Ordering, Arc},
+};
 use tokio::sync::broadcast;
 
 /// An active subscription.
@@ -14,6 +21,8 @@ pub(crate) struct ActiveSubscription {
     pub(crate) request: SerializedRequest,
     /// The channel via which notifications are broadcast.
     pub(crate) tx: broadcast::Sender<Box<RawValue>>,
+    /// The configuration of the channel.
+    pub(crate) config: Arc<ChannelConfig>,
 }
 
 // NB: We implement this to prevent any incorrect future implementations.
@@ -50,16 +59,23 @@ impl fmt::Debug for ActiveSubscription {
             .field("local_id", &self.local_id)
             .field("request", &self.request)
             .field("subscribers", &self.tx.receiver_count())
+            .field("policy", &self.config.policy())
             .finish()
     }
 }
 
 impl ActiveSubscription {
     /// Create a new active subscription.
-    pub(crate) fn new(request: SerializedRequest, channel_size: usize) -> Self {
+    pub(crate) fn new(
+        request: SerializedRequest,
+        channel_size: usize,
+        policy: BackpressurePolicy,
+    ) -> Self {
         let local_id = request.params_hash();
         let (tx, _rx) = broadcast::channel(channel_size);
-        Self { request, local_id, tx }
+        // The channel rounds its capacity up to a power of two.
+        let config = Arc::new(ChannelConfig::new(channel_size.next_power_of_two(), policy));
+        Self { request, local_id, tx, config }
     }
 
     /// Serialize the request as a boxed [`RawValue`].
@@ -71,14 +87,131 @@ impl ActiveSubscription {
 
     /// Get a subscription.
     pub(crate) fn subscribe(&self) -> RawSubscription {
-        RawSubscription { rx: self.tx.subscribe(), local_id: self.local_id }
+        RawSubscription::new(self.tx.subscribe(), self.local_id, self.config.clone())
     }
 
     /// Notify the subscription channel of a new value, if any receiver exists.
     /// If no receiver exists, the notification is dropped.
-    pub(crate) fn notify(&mut self, notification: Box<RawValue>) {
-        if self.tx.receiver_count() > 0 {
-            let _ = self.tx.send(notification);
+    ///
+    /// Returns `false` if the channel is full and the policy is
+    /// [`BackpressurePolicy::Fail`], in which case the subscription must be
+    /// removed.
+    pub(crate) fn notify(&mut self, notification: Box<RawValue>) -> bool {
+        if self.tx.receiver_count() == 0 {
+            return true;
+        }
+        if self.tx.len() >= self.config.capacity {
+            match self.config.policy() {
+                BackpressurePolicy::DropOldest => {}
+                BackpressurePolicy::DropNewest => {
+                    self.config.dropped.fetch_add(1, Ordering::Relaxed);
+                    return true;
+                }
+                BackpressurePolicy::Fail => {
+                    self.config.dropped.fetch_add(1, Ordering::Relaxed);
+                    return false;
+                }
+            }
+        }
+        let _ = self.tx.send(notification);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Subscription, SubscriptionItem};
+    use alloy_json_rpc::{Id, Request};
+    use broadcast::error::TryRecvError;
+    use futures::{FutureExt, StreamExt};
+
+    fn active(channel_size: usize, policy: BackpressurePolicy) -> ActiveSubscription {
+        let request = Request::new("eth_subscribe", Id::Number(1), ["newHeads"]);
+        ActiveSubscription::new(request.serialize().unwrap(), channel_size, policy)
+    }
+
+    fn notification(n: u64) -> Box<RawValue> {
+        serde_json::value::to_raw_value(&n).unwrap()
+    }
+
+    fn recv(sub: &mut RawSubscription) -> Result<u64, TryRecvError> {
+        sub.try_recv().map(|value| serde_json::from_str(value.get()).unwrap())
+    }
+
+    #[test]
+    fn drop_oldest() {
+        let mut active = active(2, BackpressurePolicy::DropOldest);
+        let mut sub = active.subscribe();
+        for n in 1..=3 {
+            assert!(active.notify(notification(n)));
         }
+        assert_eq!(recv(&mut sub), Err(TryRecvError::Lagged(1)));
+        assert_eq!(recv(&mut sub), Ok(2));
+        assert_eq!(recv(&mut sub), Ok(3));
+        assert_eq!(recv(&mut sub), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn drop_newest() {
+        let mut active = active(2, BackpressurePolicy::DropNewest);
+        let mut sub = active.subscribe();
+        for n in 1..=4 {
+            assert!(active.notify(notification(n)));
+        }
+        assert_eq!(recv(&mut sub), Err(TryRecvError::Lagged(2)));
+        assert_eq!(recv(&mut sub), Ok(1));
+        // The channel has room again once the receiver caught up.
+        assert!(active.notify(notification(5)));
+        assert_eq!(recv(&mut sub), Ok(2));
+        assert_eq!(recv(&mut sub), Ok(5));
+        assert_eq!(recv(&mut sub), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn fail() {
+        let mut active = active(2, BackpressurePolicy::Fail);
+        let mut sub = active.subscribe();
+        assert!(active.notify(notification(1)));
+        assert!(active.notify(notification(2)));
+        assert!(!active.notify(notification(3)));
+        // The manager drops the subscription, the receiver gets the buffered notifications.
+        drop(active);
+        assert_eq!(recv(&mut sub), Err(TryRecvError::Lagged(1)));
+        assert_eq!(recv(&mut sub), Ok(1));
+        assert_eq!(recv(&mut sub), Ok(2));
+        assert_eq!(recv(&mut sub), Err(TryRecvError::Closed));
+    }
+
+    #[test]
+    fn capacity_is_rounded_up() {
+        let mut active = active(3, BackpressurePolicy::DropNewest);
+        let mut sub = active.subscribe();
+        assert_eq!(sub.capacity(), 4);
+        for n in 1..=5 {
+            assert!(active.notify(notification(n)));
+        }
+        assert_eq!(recv(&mut sub), Err(TryRecvError::Lagged(1)));
+        for n in 1..=4 {
+            assert_eq!(recv(&mut sub), Ok(n));
+        }
+    }
+
+    #[test]
+    fn any_stream_yields_lagged() {
+        let mut active = active(1, BackpressurePolicy::DropNewest);
+        let sub = Subscription::<u64>::from(active.subscribe());
+        assert!(active.notify(notification(1)));
+        assert!(active.notify(notification(2)));
+        // Policies can be changed for the existing receivers.
+        sub.set_policy(BackpressurePolicy::DropOldest);
+        assert!(active.notify(notification(3)));
+
+        let mut stream = sub.into_any_stream();
+        let mut next = || stream.next().now_or_never().flatten();
+        assert!(matches!(next(), Some(SubscriptionItem::Lagged(1))));
+        assert!(matches!(next(), Some(SubscriptionItem::Lagged(1))));
+        assert!(matches!(next(), Some(SubscriptionItem::Item(3))));
+        assert!(next().is_none());
     }
 }
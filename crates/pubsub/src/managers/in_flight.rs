@@ -1,3 +1,4 @@
+use crate::BackpressurePolicy;
 use alloy_json_rpc::{Response, ResponsePayload, SerializedRequest};
 use alloy_primitives::U256;
 use alloy_transport::{TransportError, TransportResult};
@@ -15,6 +16,9 @@ pub(crate) struct InFlight {
     /// The number of items to buffer in the subscription channel.
     pub(crate) channel_size: usize,
 
+    /// The policy applied when the subscription channel is full.
+    pub(crate) policy: BackpressurePolicy,
+
     /// The channel to send the response on.
     pub(crate) tx: oneshot::Sender<TransportResult<Response>>,
 }
@@ -24,6 +28,7 @@ impl fmt::Debug for InFlight {
         f.debug_struct("InFlight")
             .field("request", &self.request)
             .field("channel_size", &self.channel_size)
+            .field("policy", &self.policy)
             .field("tx_is_closed", &self.tx.is_closed())
             .finish()
     }
@@ -34,10 +39,11 @@ impl InFlight {
     pub(crate) fn new(
         request: SerializedRequest,
         channel_size: usize,
+        policy: BackpressurePolicy,
     ) -> (Self, oneshot::Receiver<TransportResult<Response>>) {
         let (tx, rx) = oneshot::channel();
 
-        (Self { request, channel_size, tx }, rx)
+        (Self { request, channel_size, policy, tx }, rx)
     }
 
     /// Check if the request is a subscription.
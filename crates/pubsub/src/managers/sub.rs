@@ -1,4 +1,4 @@
-use crate::{managers::ActiveSubscription, RawSubscription};
+use crate::{managers::ActiveSubscription, BackpressurePolicy, RawSubscription};
 use alloy_json_rpc::{EthNotification, SerializedRequest};
 use alloy_primitives::{B256, U256};
 use bimap::BiBTreeMap;
@@ -28,8 +28,9 @@ impl SubscriptionManager {
         request: SerializedRequest,
         server_id: U256,
         channel_size: usize,
+        policy: BackpressurePolicy,
     ) -> RawSubscription {
-        let active = ActiveSubscription::new(request, channel_size);
+        let active = ActiveSubscription::new(request, channel_size, policy);
         let sub = active.subscribe();
 
         let local_id = active.local_id;
@@ -45,6 +46,7 @@ impl SubscriptionManager {
         request: SerializedRequest,
         server_id: U256,
         channel_size: usize,
+        policy: BackpressurePolicy,
     ) -> RawSubscription {
         let local_id = request.params_hash();
 
@@ -54,7 +56,7 @@ impl SubscriptionManager {
             self.change_server_id(local_id, server_id);
             self.get_subscription(local_id).expect("checked existence")
         } else {
-            self.insert(request, server_id, channel_size)
+            self.insert(request, server_id, channel_size, policy)
         }
     }
 
@@ -82,12 +84,20 @@ impl SubscriptionManager {
     /// Notify the subscription channel of a new value, if the sub is known,
     /// and if any receiver exists. If the sub id is unknown, or no receiver
     /// exists, the notification is dropped.
-    pub(crate) fn notify(&mut self, notification: EthNotification) {
-        if let Some(local_id) = self.local_id_for(notification.subscription) {
-            if let Some((_, mut sub)) = self.local_to_sub.remove_by_left(&local_id) {
-                sub.notify(notification.result);
-                self.local_to_sub.insert(local_id, sub);
-            }
+    ///
+    /// If the channel is full and its policy is to fail, the subscription is
+    /// removed, and its server id is returned so that the server can be told
+    /// to unsubscribe.
+    pub(crate) fn notify(&mut self, notification: EthNotification) -> Option<U256> {
+        let server_id = notification.subscription;
+        let local_id = self.local_id_for(server_id)?;
+        let (_, mut sub) = self.local_to_sub.remove_by_left(&local_id)?;
+        if sub.notify(notification.result) {
+            self.local_to_sub.insert(local_id, sub);
+            None
+        } else {
+            let _ = self.local_to_server.remove_by_left(&local_id);
+            Some(server_id)
         }
     }
 
@@ -96,3 +106,29 @@ impl SubscriptionManager {
         self.local_to_sub.get_by_left(&local_id).map(ActiveSubscription::subscribe)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request};
+
+    #[test]
+    fn failing_subscription_is_removed() {
+        let mut subs = SubscriptionManager::default();
+        let request = Request::new("eth_subscribe", Id::Number(1), ["newHeads"]);
+        let server_id = U256::from(7);
+        let sub = subs.upsert(request.serialize().unwrap(), server_id, 1, BackpressurePolicy::Fail);
+
+        let notification = |n: u64| EthNotification {
+            subscription: server_id,
+            result: serde_json::value::to_raw_value(&n).unwrap(),
+        };
+        assert_eq!(subs.notify(notification(1)), None);
+        assert_eq!(subs.notify(notification(2)), Some(server_id));
+        assert_eq!(subs.len(), 0);
+        assert_eq!(subs.local_id_for(server_id), None);
+        // Later notifications of the server are dropped.
+        assert_eq!(subs.notify(notification(3)), None);
+        drop(sub);
+    }
+}
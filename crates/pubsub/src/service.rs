@@ -92,7 +92,7 @@ impl<T: PubSubConnect> PubSubService<T> {
             let req = sub.request().to_owned();
             // 0 is a dummy value, we don't care about the channel size here,
             // as none of these will result in channel creation.
-            let (in_flight, _) = InFlight::new(req.clone(), 0);
+            let (in_flight, _) = InFlight::new(req.clone(), 0, Default::default());
             self.in_flights.insert(in_flight);
 
             let msg = req.into_serialized();
@@ -159,10 +159,14 @@ impl<T: PubSubConnect> PubSubService<T> {
                 Some((server_id, in_flight)) => self.handle_sub_response(in_flight, server_id),
                 None => Ok(()),
             },
-            PubSubItem::Notification(notification) => {
-                self.subs.notify(notification);
-                Ok(())
-            }
+            PubSubItem::Notification(notification) => match self.subs.notify(notification) {
+                Some(server_id) => {
+                    warn!(%server_id, "subscription channel full, unsubscribing");
+                    let req = Request::new("eth_unsubscribe", Id::None, [server_id]);
+                    self.dispatch_request(req.serialize().expect("no ser error").take_request())
+                }
+                None => Ok(()),
+            },
         }
     }
 
@@ -171,7 +175,7 @@ impl<T: PubSubConnect> PubSubService<T> {
         let request = in_flight.request;
         let id = request.id().clone();
 
-        self.subs.upsert(request, server_id, in_flight.channel_size);
+        self.subs.upsert(request, server_id, in_flight.channel_size, in_flight.policy);
 
         // lie to the client about the sub id.
         let local_id = self.subs.local_id_for(server_id).unwrap();
@@ -2,10 +2,93 @@ use alloy_primitives::B256;
 use futures::{ready, Stream, StreamExt};
 use serde::de::DeserializeOwned;
 use serde_json::value::RawValue;
-use std::{pin::Pin, task};
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, AtomicU8, Ordering},
+        Arc,
+    },
+    task,
+};
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 
+/// What happens to new notifications when the channel of a subscription is full, because one of
+/// its receivers does not keep up.
+///
+/// Receivers are told how many notifications they missed with a `Lagged` error, or a
+/// [`SubscriptionItem::Lagged`] item.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BackpressurePolicy {
+    /// Overwrite the oldest buffered notification. This is the behavior of
+    /// [`broadcast`] channels.
+    #[default]
+    DropOldest,
+    /// Drop new notifications until the slowest receiver catches up.
+    DropNewest,
+    /// Unsubscribe. The receivers are closed once they received the buffered notifications.
+    Fail,
+}
+
+impl BackpressurePolicy {
+    pub(crate) const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::DropNewest,
+            2 => Self::Fail,
+            _ => Self::DropOldest,
+        }
+    }
+}
+
+/// The configuration of the channel of a subscription, shared by its receivers.
+#[derive(Debug)]
+pub(crate) struct ChannelConfig {
+    /// The number of notifications buffered in the channel, which is the requested channel size
+    /// rounded up to a power of two.
+    pub(crate) capacity: usize,
+    /// The policy applied when the channel is full.
+    policy: AtomicU8,
+    /// The number of notifications dropped by the policy, rather than by the channel.
+    pub(crate) dropped: AtomicU64,
+}
+
+impl ChannelConfig {
+    pub(crate) const fn new(capacity: usize, policy: BackpressurePolicy) -> Self {
+        Self { capacity, policy: AtomicU8::new(policy as u8), dropped: AtomicU64::new(0) }
+    }
+
+    pub(crate) fn policy(&self) -> BackpressurePolicy {
+        BackpressurePolicy::from_u8(self.policy.load(Ordering::Relaxed))
+    }
+
+    fn set_policy(&self, policy: BackpressurePolicy) {
+        self.policy.store(policy as u8, Ordering::Relaxed);
+    }
+}
+
+/// Tracks the notifications dropped by the policy of a channel that a receiver has not been told
+/// about yet.
+#[derive(Debug)]
+struct Missed {
+    config: Arc<ChannelConfig>,
+    reported: u64,
+}
+
+impl Missed {
+    fn new(config: Arc<ChannelConfig>) -> Self {
+        let reported = config.dropped.load(Ordering::Relaxed);
+        Self { config, reported }
+    }
+
+    /// Returns the number of notifications dropped since the last call, if any.
+    fn take(&mut self) -> Option<u64> {
+        let dropped = self.config.dropped.load(Ordering::Relaxed);
+        let missed = dropped - self.reported;
+        self.reported = dropped;
+        (missed > 0).then_some(missed)
+    }
+}
+
 /// A Subscription is a feed of notifications from the server, identified by a
 /// local ID.
 ///
@@ -14,22 +97,57 @@ use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
 #[derive(Debug)]
 pub struct RawSubscription {
     /// The channel via which notifications are received.
-    pub(crate) rx: broadcast::Receiver<Box<RawValue>>,
+    rx: broadcast::Receiver<Box<RawValue>>,
     /// The local ID of the subscription.
     pub(crate) local_id: B256,
+    /// The notifications dropped by the policy of the channel.
+    missed: Missed,
 }
 
 impl RawSubscription {
+    pub(crate) fn new(
+        rx: broadcast::Receiver<Box<RawValue>>,
+        local_id: B256,
+        config: Arc<ChannelConfig>,
+    ) -> Self {
+        Self { rx, local_id, missed: Missed::new(config) }
+    }
+
     /// Get the local ID of the subscription.
     pub const fn local_id(&self) -> &B256 {
         &self.local_id
     }
 
+    /// Returns the number of notifications buffered in the channel. This is the
+    /// channel size the subscription was created with, rounded up to a power
+    /// of two.
+    pub fn capacity(&self) -> usize {
+        self.missed.config.capacity
+    }
+
+    /// Returns the policy applied when the channel is full.
+    pub fn policy(&self) -> BackpressurePolicy {
+        self.missed.config.policy()
+    }
+
+    /// Sets the policy applied when the channel is full. The channel is shared
+    /// by all the receivers of the subscription, so this affects them all.
+    pub fn set_policy(&self, policy: BackpressurePolicy) {
+        self.missed.config.set_policy(policy);
+    }
+
     /// Wrapper for [`blocking_recv`]. Block the current thread until a message
     /// is available.
     ///
+    /// Fails with [`RecvError::Lagged`] if notifications were missed since the
+    /// last call.
+    ///
     /// [`blocking_recv`]: broadcast::Receiver::blocking_recv
+    /// [`RecvError::Lagged`]: broadcast::error::RecvError::Lagged
     pub fn blocking_recv(&mut self) -> Result<Box<RawValue>, broadcast::error::RecvError> {
+        if let Some(missed) = self.missed.take() {
+            return Err(broadcast::error::RecvError::Lagged(missed));
+        }
         self.rx.blocking_recv()
     }
 
@@ -47,8 +165,15 @@ impl RawSubscription {
 
     /// Wrapper for [`recv`]. Await an item from the channel.
     ///
+    /// Fails with [`RecvError::Lagged`] if notifications were missed since the
+    /// last call.
+    ///
     /// [`recv`]: broadcast::Receiver::recv
+    /// [`RecvError::Lagged`]: broadcast::error::RecvError::Lagged
     pub async fn recv(&mut self) -> Result<Box<RawValue>, broadcast::error::RecvError> {
+        if let Some(missed) = self.missed.take() {
+            return Err(broadcast::error::RecvError::Lagged(missed));
+        }
         self.rx.recv().await
     }
 
@@ -57,7 +182,7 @@ impl RawSubscription {
     ///
     /// [`resubscribe`]: broadcast::Receiver::resubscribe
     pub fn resubscribe(&self) -> Self {
-        Self { rx: self.rx.resubscribe(), local_id: self.local_id }
+        Self::new(self.rx.resubscribe(), self.local_id, self.missed.config.clone())
     }
 
     /// Wrapper for [`same_channel`]. Returns `true` if the two subscriptions
@@ -73,23 +198,59 @@ impl RawSubscription {
     ///
     /// [`try_recv`]: broadcast::Receiver::try_recv
     pub fn try_recv(&mut self) -> Result<Box<RawValue>, broadcast::error::TryRecvError> {
+        if let Some(missed) = self.missed.take() {
+            return Err(broadcast::error::TryRecvError::Lagged(missed));
+        }
         self.rx.try_recv()
     }
 
     /// Convert the subscription into a stream.
+    ///
+    /// The stream only reports the notifications overwritten in the channel, not those dropped
+    /// by the [`BackpressurePolicy::DropNewest`] policy. Use
+    /// [`Subscription::into_any_stream`] to be told about both.
     pub fn into_stream(self) -> BroadcastStream<Box<RawValue>> {
         self.rx.into()
     }
+
+    fn into_lagging_stream(self) -> LaggingStream {
+        LaggingStream { inner: self.rx.into(), missed: self.missed }
+    }
+}
+
+/// A [`BroadcastStream`] also reporting the notifications dropped by the policy of the channel.
+#[derive(Debug)]
+struct LaggingStream {
+    inner: BroadcastStream<Box<RawValue>>,
+    missed: Missed,
+}
+
+impl Stream for LaggingStream {
+    type Item = Result<Box<RawValue>, BroadcastStreamRecvError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Option<Self::Item>> {
+        if let Some(missed) = self.missed.take() {
+            return task::Poll::Ready(Some(Err(BroadcastStreamRecvError::Lagged(missed))));
+        }
+        self.inner.poll_next_unpin(cx)
+    }
 }
 
 /// An item in a typed [`Subscription`]. This is either the expected type, or
 /// some serialized value of another type.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum SubscriptionItem<T> {
     /// The expected item.
     Item(T),
     /// Some other value.
     Other(Box<RawValue>),
+    /// The number of notifications missed because the receiver did not keep up, as yielded by
+    /// [`SubAnyStream`].
+    Lagged(u64),
 }
 
 impl<T: DeserializeOwned> From<Box<RawValue>> for SubscriptionItem<T> {
@@ -132,6 +293,24 @@ impl<T> Subscription<T> {
         self.inner.local_id()
     }
 
+    /// Returns the number of notifications buffered in the channel. This is the
+    /// channel size the subscription was created with, rounded up to a power
+    /// of two.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Returns the policy applied when the channel is full.
+    pub fn policy(&self) -> BackpressurePolicy {
+        self.inner.policy()
+    }
+
+    /// Sets the policy applied when the channel is full. The channel is shared
+    /// by all the receivers of the subscription, so this affects them all.
+    pub fn set_policy(&self, policy: BackpressurePolicy) {
+        self.inner.set_policy(policy);
+    }
+
     /// Convert the subscription into its inner [`RawSubscription`].
     #[allow(clippy::missing_const_for_fn)] // erroneous lint
     pub fn into_raw(self) -> RawSubscription {
@@ -217,11 +396,12 @@ impl<T: DeserializeOwned> Subscription<T> {
 
     /// Convert the subscription into a stream.
     ///
-    /// Errors are logged and ignored.
+    /// Errors are logged and ignored, including missed notifications. Use
+    /// [`into_any_stream`](Self::into_any_stream) to be told about them.
     pub fn into_stream(self) -> SubscriptionStream<T> {
         SubscriptionStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            inner: self.inner.into_lagging_stream(),
             _pd: std::marker::PhantomData,
         }
     }
@@ -230,16 +410,17 @@ impl<T: DeserializeOwned> Subscription<T> {
     pub fn into_result_stream(self) -> SubResultStream<T> {
         SubResultStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            inner: self.inner.into_lagging_stream(),
             _pd: std::marker::PhantomData,
         }
     }
 
-    /// Convert the subscription into a stream that may yield unexpected types.
+    /// Convert the subscription into a stream that may yield unexpected types, and yields
+    /// [`SubscriptionItem::Lagged`] when notifications were missed.
     pub fn into_any_stream(self) -> SubAnyStream<T> {
         SubAnyStream {
             id: self.inner.local_id,
-            inner: self.inner.into_stream(),
+            inner: self.inner.into_lagging_stream(),
             _pd: std::marker::PhantomData,
         }
     }
@@ -252,7 +433,7 @@ impl<T: DeserializeOwned> Subscription<T> {
         loop {
             match self.blocking_recv_any()? {
                 SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+                SubscriptionItem::Other(_) | SubscriptionItem::Lagged(_) => continue,
             }
         }
     }
@@ -265,7 +446,7 @@ impl<T: DeserializeOwned> Subscription<T> {
         loop {
             match self.recv_any().await? {
                 SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+                SubscriptionItem::Other(_) | SubscriptionItem::Lagged(_) => continue,
             }
         }
     }
@@ -278,7 +459,7 @@ impl<T: DeserializeOwned> Subscription<T> {
         loop {
             match self.try_recv_any()? {
                 SubscriptionItem::Item(item) => return Ok(item),
-                SubscriptionItem::Other(_) => continue,
+                SubscriptionItem::Other(_) | SubscriptionItem::Lagged(_) => continue,
             }
         }
     }
@@ -319,7 +500,7 @@ impl<T: DeserializeOwned> Subscription<T> {
 #[derive(Debug)]
 pub struct SubAnyStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: LaggingStream,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -337,17 +518,12 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
         mut self: Pin<&mut Self>,
         cx: &mut task::Context<'_>,
     ) -> task::Poll<Option<Self::Item>> {
-        loop {
-            match ready!(self.inner.poll_next_unpin(cx)) {
-                Some(Ok(value)) => return task::Poll::Ready(Some(value.into())),
-                Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
-                    continue;
-                }
-                None => return task::Poll::Ready(None),
-            }
-        }
+        let item = match ready!(self.inner.poll_next_unpin(cx)) {
+            Some(Ok(value)) => value.into(),
+            Some(Err(BroadcastStreamRecvError::Lagged(missed))) => SubscriptionItem::Lagged(missed),
+            None => return task::Poll::Ready(None),
+        };
+        task::Poll::Ready(Some(item))
     }
 }
 
@@ -357,7 +533,7 @@ impl<T: DeserializeOwned> Stream for SubAnyStream<T> {
 #[derive(Debug)]
 pub struct SubscriptionStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: LaggingStream,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -386,8 +562,7 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
                     }
                 },
                 Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
+                    warn!(%err, %self.id, "stream lagged");
                     continue;
                 }
                 None => return task::Poll::Ready(None),
@@ -403,7 +578,7 @@ impl<T: DeserializeOwned> Stream for SubscriptionStream<T> {
 #[derive(Debug)]
 pub struct SubResultStream<T> {
     id: B256,
-    inner: BroadcastStream<Box<RawValue>>,
+    inner: LaggingStream,
     _pd: std::marker::PhantomData<fn() -> T>,
 }
 
@@ -427,8 +602,7 @@ impl<T: DeserializeOwned> Stream for SubResultStream<T> {
                     return task::Poll::Ready(Some(serde_json::from_str(value.get())))
                 }
                 Some(Err(err @ BroadcastStreamRecvError::Lagged(_))) => {
-                    // This is OK.
-                    debug!(%err, %self.id, "stream lagged");
+                    warn!(%err, %self.id, "stream lagged");
                     continue;
                 }
                 None => return task::Poll::Ready(None),
@@ -20,6 +20,11 @@ pub(crate) type ChannelMap = HashMap<Id, Channel>;
 
 /// A batch JSON-RPC request, used to bundle requests into a single transport
 /// call.
+///
+/// Batches exceeding the limits of a provider can be split into several
+/// batches by adding a [`BatchLimitLayer`] to the client.
+///
+/// [`BatchLimitLayer`]: alloy_transport::batch::BatchLimitLayer
 #[derive(Debug)]
 #[must_use = "A BatchRequest does nothing unless sent via `send_batch` and `.await`"]
 pub struct BatchRequest<'a, T> {
@@ -31,6 +31,12 @@ impl<L> ClientBuilder<L> {
     ///
     /// This is a wrapper around [`tower::ServiceBuilder::layer`]. Layers that
     /// are added first will be called with the request first.
+    ///
+    /// Custom middleware seeing each request and response can be written as
+    /// an [`Interceptor`], and added with a [`TransportLayer`].
+    ///
+    /// [`Interceptor`]: alloy_transport::intercept::Interceptor
+    /// [`TransportLayer`]: alloy_transport::intercept::TransportLayer
     pub fn layer<M>(self, layer: M) -> ClientBuilder<Stack<M, L>> {
         ClientBuilder { builder: self.builder.layer(layer) }
     }
@@ -60,6 +66,80 @@ impl<L> ClientBuilder<L> {
         self.transport(transport, is_local)
     }
 
+    /// Convenience function to create a new [`RpcClient`] with a [`reqwest`]
+    /// HTTP transport sending `headers` with every request.
+    ///
+    /// This is suited for gateways authenticating with API key headers or
+    /// cookies, or to set a custom `User-Agent`.
+    #[cfg(feature = "reqwest")]
+    pub fn http_with_headers(
+        self,
+        url: url::Url,
+        headers: reqwest::header::HeaderMap,
+    ) -> TransportResult<RpcClient<L::Service>>
+    where
+        L: Layer<alloy_transport_http::Http<reqwest::Client>>,
+        L::Service: Transport,
+    {
+        let transport = alloy_transport_http::Http::with_headers(url, headers)?;
+        let is_local = transport.guess_local();
+
+        Ok(self.transport(transport, is_local))
+    }
+
+    /// Convenience function to create a new [`RpcClient`] with a [`reqwest`]
+    /// HTTP transport authenticating every request with `auth`, in the
+    /// `Authorization` header.
+    #[cfg(feature = "reqwest")]
+    pub fn http_with_auth(
+        self,
+        url: url::Url,
+        auth: alloy_transport::Authorization,
+    ) -> TransportResult<RpcClient<L::Service>>
+    where
+        L: Layer<alloy_transport_http::Http<reqwest::Client>>,
+        L::Service: Transport,
+    {
+        let transport = alloy_transport_http::Http::with_auth(url, auth)?;
+        let is_local = transport.guess_local();
+
+        Ok(self.transport(transport, is_local))
+    }
+
+    /// Convenience function to create a new [`RpcClient`] with a [`reqwest`]
+    /// HTTP transport using the given connection settings, e.g. to keep more
+    /// idle connections alive.
+    #[cfg(feature = "reqwest")]
+    pub fn http_with_config(
+        self,
+        url: url::Url,
+        config: alloy_transport_http::ConnectionConfig,
+    ) -> RpcClient<L::Service>
+    where
+        L: Layer<alloy_transport_http::Http<reqwest::Client>>,
+        L::Service: Transport,
+    {
+        let transport = alloy_transport_http::Http::with_config(url, config);
+        let is_local = transport.guess_local();
+
+        self.transport(transport, is_local)
+    }
+
+    /// Convenience function to create a new [`RpcClient`] with a [`reqwest`]
+    /// HTTP transport using the given client, e.g. one configured with a
+    /// proxy or custom TLS settings.
+    #[cfg(feature = "reqwest")]
+    pub fn http_with_client(self, client: reqwest::Client, url: url::Url) -> RpcClient<L::Service>
+    where
+        L: Layer<alloy_transport_http::Http<reqwest::Client>>,
+        L::Service: Transport,
+    {
+        let transport = alloy_transport_http::Http::with_client(client, url);
+        let is_local = transport.guess_local();
+
+        self.transport(transport, is_local)
+    }
+
     /// Convenience function to create a new [`RpcClient`] with a `hyper` HTTP transport.
     #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
     pub fn hyper_http(self, url: url::Url) -> RpcClient<L::Service>
@@ -1,8 +1,12 @@
+use crate::context::{CallContext, ErrorContext};
 use alloy_json_rpc::{
-    transform_response, try_deserialize_ok, Request, RequestPacket, ResponsePacket, RpcParam,
-    RpcResult, RpcReturn,
+    transform_response, try_deserialize_ok, LazyValue, Request, RequestPacket, ResponsePacket,
+    RpcParam, RpcResult, RpcReturn,
+};
+use alloy_transport::{
+    timeout::{with_deadline, with_timeout},
+    RpcFut, Transport, TransportError, TransportErrorKind, TransportFut, TransportResult,
 };
-use alloy_transport::{RpcFut, Transport, TransportError, TransportResult};
 use core::panic;
 use serde_json::value::RawValue;
 use std::{
@@ -10,10 +14,17 @@ use std::{
     future::Future,
     marker::PhantomData,
     pin::Pin,
+    sync::Arc,
     task::{self, Poll::Ready},
+    time::Duration,
 };
 use tower::Service;
 
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
 /// The states of the [`RpcCall`] future.
 #[must_use = "futures do nothing unless you `.await` or poll them"]
 #[pin_project::pin_project(project = CallStateProj)]
@@ -25,6 +36,8 @@ where
     Prepared {
         request: Option<Request<Params>>,
         connection: Conn,
+        /// Fails at the deadline of the request, while waiting for the transport to be ready.
+        queued: Option<TransportFut<'static>>,
     },
     AwaitingResponse {
         #[pin]
@@ -40,9 +53,11 @@ where
 {
     fn clone(&self) -> Self {
         match self {
-            Self::Prepared { request, connection } => {
-                Self::Prepared { request: request.clone(), connection: connection.clone() }
-            }
+            Self::Prepared { request, connection, .. } => Self::Prepared {
+                request: request.clone(),
+                connection: connection.clone(),
+                queued: None,
+            },
             _ => panic!("cloned after dispatch"),
         }
     }
@@ -72,23 +87,51 @@ where
         cx: &mut task::Context<'_>,
     ) -> task::Poll<<Self as Future>::Output> {
         let fut = {
-            let CallStateProj::Prepared { connection, request } = self.as_mut().project() else {
+            let CallStateProj::Prepared { connection, request, queued } = self.as_mut().project()
+            else {
                 unreachable!("Called poll_prepared in incorrect state")
             };
+            let deadline = request.as_ref().and_then(|request| request.meta.deadline());
 
-            if let Err(e) = task::ready!(Service::<RequestPacket>::poll_ready(connection, cx)) {
+            match Service::<RequestPacket>::poll_ready(connection, cx) {
+                Ready(Ok(())) => {}
+                Ready(Err(e)) => {
+                    self.set(CallState::Complete);
+                    return Ready(RpcResult::Err(e));
+                }
+                task::Poll::Pending => {
+                    // Waiting for the transport is bounded by the deadline too.
+                    let Some(deadline) = deadline else { return task::Poll::Pending };
+                    let timer = queued.get_or_insert_with(|| {
+                        with_deadline(Box::pin(futures::future::pending()), deadline)
+                    });
+                    let _ = task::ready!(timer.as_mut().poll(cx));
+                    self.set(CallState::Complete);
+                    return Ready(Err(TransportErrorKind::deadline_exceeded()));
+                }
+            }
+            if deadline.is_some_and(|deadline| deadline <= Instant::now()) {
                 self.set(CallState::Complete);
-                return Ready(RpcResult::Err(e));
+                return Ready(Err(TransportErrorKind::deadline_exceeded()));
             }
 
             let request = request.take().expect("no request");
+            let timeout = request.meta.timeout();
             debug!(method=%request.meta.method, id=%request.meta.id, "sending request");
             trace!(params_ty=%std::any::type_name::<Params>(), ?request, "full request");
             let request = request.serialize();
             match request {
                 Ok(request) => {
                     trace!(request=%request.serialized(), "serialized request");
-                    connection.call(request.into())
+                    let fut = connection.call(request.into());
+                    let fut = match timeout {
+                        Some(timeout) => with_timeout(fut, timeout),
+                        None => fut,
+                    };
+                    match deadline {
+                        Some(deadline) => with_deadline(fut, deadline),
+                        None => fut,
+                    }
                 }
                 Err(err) => {
                     trace!(?err, "failed to serialize request");
@@ -170,6 +213,7 @@ where
     #[pin]
     state: CallState<Params, Conn>,
     map: Map,
+    context: Option<Box<CallContext>>,
     _pd: core::marker::PhantomData<fn() -> (Resp, Output)>,
 }
 
@@ -192,11 +236,19 @@ where
     #[doc(hidden)]
     pub fn new(req: Request<Params>, connection: Conn) -> Self {
         Self {
-            state: CallState::Prepared { request: Some(req), connection },
+            state: CallState::Prepared { request: Some(req), connection, queued: None },
             map: std::convert::identity,
+            context: None,
             _pd: PhantomData,
         }
     }
+
+    /// Attach the context of the request to the error of the call, if it fails.
+    pub(crate) fn with_error_context(mut self, config: Arc<ErrorContext>) -> Self {
+        let method = self.request().meta.method.clone();
+        self.context = Some(Box::new(CallContext::new(config, method)));
+        self
+    }
 }
 
 impl<Conn, Params, Resp, Output, Map> RpcCall<Conn, Params, Resp, Output, Map>
@@ -213,7 +265,55 @@ where
     where
         NewMap: Fn(Resp) -> NewOutput,
     {
-        RpcCall { state: self.state, map, _pd: PhantomData }
+        RpcCall { state: self.state, map, context: self.context, _pd: PhantomData }
+    }
+
+    /// Returns the response as raw JSON, to be deserialized later by the caller.
+    ///
+    /// The mapping of the response, if any, is dropped: the [`LazyValue`] decodes into the
+    /// response type of the method, such as `U64` rather than `u64`.
+    pub fn into_raw(self) -> RpcCall<Conn, Params, LazyValue<Resp>> {
+        RpcCall {
+            state: self.state,
+            map: std::convert::identity,
+            context: self.context,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Set the time after which the request is abandoned, failing with
+    /// [`TransportErrorKind::Timeout`].
+    ///
+    /// It overrides the default timeout of the transport, if any, such as the one of a
+    /// [`TimeoutLayer`], so that slow methods can be given more time than others. The in-flight
+    /// request is canceled when the call times out, or when its future is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    ///
+    /// [`TransportErrorKind::Timeout`]: alloy_transport::TransportErrorKind::Timeout
+    /// [`TimeoutLayer`]: alloy_transport::timeout::TimeoutLayer
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.request_mut().meta.set_timeout(Some(timeout));
+        self
+    }
+
+    /// Set the instant after which the request is abandoned, failing with
+    /// [`TransportErrorKind::DeadlineExceeded`].
+    ///
+    /// Unlike [`timeout`](Self::timeout), which applies to each attempt, the deadline bounds the
+    /// total latency of the call: it includes the time spent waiting for the transport to be
+    /// ready, e.g. behind a rate limiter, and it is kept by the clones of the call used to retry
+    /// it. Layers retrying or failing over requests can read it with [`RequestPacket::deadline`]
+    /// to stop once it passed. A timeout can still be set to bound each attempt.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the request has been sent.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.request_mut().meta.set_deadline(Some(deadline));
+        self
     }
 
     /// Returns `true` if the request is a subscription.
@@ -289,14 +389,15 @@ where
     ///
     /// Panics if called after the request has been sent.
     pub fn into_owned_params(self) -> RpcCall<Conn, Params, Resp, Output, Map> {
-        let CallState::Prepared { request, connection } = self.state else {
+        let CallState::Prepared { request, connection, .. } = self.state else {
             panic!("Cannot get params after request has been sent");
         };
         let request = request.expect("no request in prepared").into_owned_params();
 
         RpcCall {
-            state: CallState::Prepared { request: Some(request), connection },
+            state: CallState::Prepared { request: Some(request), connection, queued: None },
             map: self.map,
+            context: self.context,
             _pd: PhantomData,
         }
     }
@@ -329,6 +430,117 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> task::Poll<Self::Output> {
         trace!(?self.state, "polling RpcCall");
         let this = self.project();
-        this.state.poll(cx).map(try_deserialize_ok).map(|r| r.map(this.map))
+        if let (Some(context), CallState::Prepared { request: Some(request), .. }) =
+            (this.context.as_mut(), &*this.state)
+        {
+            if !context.is_sent() {
+                context.sent(&request.params);
+            }
+        }
+        this.state.poll(cx).map(try_deserialize_ok).map(|r| match r {
+            Ok(resp) => Ok((this.map)(resp)),
+            Err(err) => Err(match this.context {
+                Some(context) => context.attach(err),
+                None => err,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ClientBuilder;
+    use alloy_json_rpc::{RequestPacket, Response, ResponsePacket, ResponsePayload};
+    use alloy_transport::{TransportError, TransportErrorKind, TransportFut};
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        task::{Context, Poll},
+        time::{Duration, Instant},
+    };
+
+    #[tokio::test(start_paused = true)]
+    async fn call_timeout() {
+        // A transport taking 5 seconds to answer.
+        let service = tower::service_fn(|req: RequestPacket| -> TransportFut<'static> {
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                let RequestPacket::Single(req) = req else { unreachable!() };
+                let payload =
+                    ResponsePayload::Success(serde_json::value::to_raw_value(&"0x1").unwrap());
+                Ok(ResponsePacket::Single(Response { id: req.id().clone(), payload }))
+            })
+        });
+        let client = ClientBuilder::default().transport(service, true);
+
+        let call = client.request::<_, String>("eth_chainId", ()).timeout(Duration::from_secs(1));
+        let err = call.await.unwrap_err();
+        assert!(matches!(err.as_transport_err(), Some(TransportErrorKind::Timeout(_))), "{err}");
+
+        let call = client.request::<_, String>("eth_chainId", ()).timeout(Duration::from_secs(6));
+        assert_eq!(call.await.unwrap(), "0x1");
+    }
+
+    /// A transport that is never ready, e.g. behind an exhausted rate limiter.
+    #[derive(Clone)]
+    struct Saturated;
+
+    impl tower::Service<RequestPacket> for Saturated {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = TransportFut<'static>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Pending
+        }
+
+        fn call(&mut self, _req: RequestPacket) -> Self::Future {
+            unreachable!("never ready")
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn call_deadline() {
+        let sent = Arc::new(AtomicUsize::new(0));
+        let counter = sent.clone();
+        // A transport taking 5 seconds to answer.
+        let service = tower::service_fn(move |req: RequestPacket| -> TransportFut<'static> {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async move {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                let RequestPacket::Single(req) = req else { unreachable!() };
+                let payload =
+                    ResponsePayload::Success(serde_json::value::to_raw_value(&"0x1").unwrap());
+                Ok(ResponsePacket::Single(Response { id: req.id().clone(), payload }))
+            })
+        });
+        let client = ClientBuilder::default().transport(service, true);
+        let deadline_exceeded = |err: TransportError| {
+            matches!(err.as_transport_err(), Some(TransportErrorKind::DeadlineExceeded))
+        };
+
+        // The deadline is shorter than the timeout of the attempt.
+        let call = client
+            .request::<_, String>("eth_chainId", ())
+            .timeout(Duration::from_secs(4))
+            .deadline(Instant::now() + Duration::from_secs(3));
+        assert!(deadline_exceeded(call.await.unwrap_err()));
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+
+        // Retrying a call past its deadline does not send it again.
+        let call = client.request::<_, String>("eth_chainId", ()).deadline(Instant::now());
+        let retry = call.clone();
+        assert!(deadline_exceeded(call.await.unwrap_err()));
+        assert!(deadline_exceeded(retry.await.unwrap_err()));
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+
+        // Waiting for the transport to be ready counts towards the deadline.
+        let client = ClientBuilder::default().transport(Saturated, true);
+        let call = client
+            .request::<_, String>("eth_chainId", ())
+            .deadline(Instant::now() + Duration::from_secs(1));
+        assert!(deadline_exceeded(call.await.unwrap_err()));
     }
 }
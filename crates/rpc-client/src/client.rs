@@ -1,13 +1,13 @@
-use crate::{poller::PollerBuilder, BatchRequest, ClientBuilder, RpcCall};
-use alloy_json_rpc::{Id, Request, RpcParam, RpcReturn};
+use crate::{poller::PollerBuilder, BatchRequest, ClientBuilder, ErrorContext, RpcCall};
+use alloy_json_rpc::{Id, Request, RpcParam, RpcReturn, TraceContext};
 use alloy_transport::{BoxTransport, Transport};
 use alloy_transport_http::Http;
 use std::{
     borrow::Cow,
     ops::Deref,
     sync::{
-        atomic::{AtomicU64, Ordering},
-        Arc, Weak,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, RwLock, Weak,
     },
     time::Duration,
 };
@@ -19,6 +19,18 @@ pub type WeakClient<T> = Weak<RpcClientInner<T>>;
 /// A borrowed [`RpcClient`].
 pub type ClientRef<'a, T> = &'a RpcClientInner<T>;
 
+type TraceContextFn = Arc<dyn Fn() -> Option<TraceContext> + Send + Sync>;
+
+/// Holds the function returning the trace context of the caller of a request.
+pub(crate) struct TraceContextHook(RwLock<Option<TraceContextFn>>);
+
+impl std::fmt::Debug for TraceContextHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let is_set = self.0.read().is_ok_and(|hook| hook.is_some());
+        f.debug_struct("TraceContextHook").field("is_set", &is_set).finish()
+    }
+}
+
 /// A JSON-RPC client.
 ///
 /// [`RpcClient`] should never be instantiated directly. Instead, use
@@ -90,6 +102,33 @@ impl<T> RpcClient<T> {
         self.inner().set_poll_interval(poll_interval);
         self
     }
+
+    /// Sets the function returning the trace context of the caller of each request.
+    ///
+    /// See [`RpcClientInner::set_trace_context_fn`] for more details.
+    pub fn with_trace_context_fn(
+        self,
+        f: impl Fn() -> Option<TraceContext> + Send + Sync + 'static,
+    ) -> Self {
+        self.inner().set_trace_context_fn(f);
+        self
+    }
+
+    /// Attaches the context of failed requests to their errors.
+    ///
+    /// See [`RpcClientInner::set_error_context`] for more details.
+    pub fn with_error_context(self, config: ErrorContext) -> Self {
+        self.inner().set_error_context(config);
+        self
+    }
+
+    /// Omits trailing `null` params from requests.
+    ///
+    /// See [`RpcClientInner::set_strict_serialization`] for more details.
+    pub fn with_strict_serialization(self) -> Self {
+        self.inner().set_strict_serialization(true);
+        self
+    }
 }
 
 impl<T: Transport> RpcClient<T> {
@@ -163,6 +202,12 @@ pub struct RpcClientInner<T> {
     pub(crate) id: AtomicU64,
     /// The poll interval for the client in milliseconds.
     pub(crate) poll_interval: AtomicU64,
+    /// Returns the trace context to attach to requests.
+    pub(crate) trace_context: TraceContextHook,
+    /// The context attached to the errors of failed requests.
+    pub(crate) error_context: RwLock<Option<Arc<ErrorContext>>>,
+    /// Whether trailing `null` params are omitted from requests.
+    pub(crate) strict: AtomicBool,
 }
 
 impl<T> RpcClientInner<T> {
@@ -177,6 +222,9 @@ impl<T> RpcClientInner<T> {
             is_local,
             id: AtomicU64::new(0),
             poll_interval: if is_local { AtomicU64::new(250) } else { AtomicU64::new(7000) },
+            trace_context: TraceContextHook(RwLock::new(None)),
+            error_context: RwLock::new(None),
+            strict: AtomicBool::new(false),
         }
     }
 
@@ -190,6 +238,73 @@ impl<T> RpcClientInner<T> {
         self.poll_interval.store(poll_interval, Ordering::Relaxed);
     }
 
+    /// Sets the function returning the trace context of the caller of each request.
+    ///
+    /// The function is called synchronously when a request is created, so it can read the
+    /// context of the calling span. The context is attached to the request's
+    /// [`RequestMeta`](alloy_json_rpc::RequestMeta), and HTTP transports propagate it in the
+    /// `traceparent` and `tracestate` headers.
+    ///
+    /// With OpenTelemetry, this would usually extract the context of [`tracing::Span::current`]
+    /// through `tracing-opentelemetry`:
+    ///
+    /// ```ignore
+    /// use opentelemetry::trace::TraceContextExt;
+    /// use tracing_opentelemetry::OpenTelemetrySpanExt;
+    ///
+    /// client.set_trace_context_fn(|| {
+    ///     let context = tracing::Span::current().context();
+    ///     let span = context.span().span_context().clone();
+    ///     span.is_valid().then(|| {
+    ///         TraceContext::new(span.trace_id().to_bytes().into(), span.span_id().to_bytes().into())
+    ///             .with_flags(span.trace_flags().to_u8())
+    ///     })
+    /// });
+    /// ```
+    pub fn set_trace_context_fn(
+        &self,
+        f: impl Fn() -> Option<TraceContext> + Send + Sync + 'static,
+    ) {
+        *self.trace_context.0.write().unwrap() = Some(Arc::new(f));
+    }
+
+    /// Removes the function set with [`set_trace_context_fn`](Self::set_trace_context_fn).
+    pub fn clear_trace_context_fn(&self) {
+        *self.trace_context.0.write().unwrap() = None;
+    }
+
+    /// Returns the trace context of the current caller, if any.
+    pub fn trace_context(&self) -> Option<TraceContext> {
+        let hook = self.trace_context.0.read().unwrap().clone();
+        hook.and_then(|hook| hook())
+    }
+
+    /// Attaches the context of failed requests to their errors, as an
+    /// [`RpcError::WithContext`](alloy_json_rpc::RpcError::WithContext).
+    ///
+    /// This only applies to requests created afterwards, and not to batch requests.
+    pub fn set_error_context(&self, config: ErrorContext) {
+        *self.error_context.write().unwrap() = Some(Arc::new(config));
+    }
+
+    /// Stops attaching request context to errors.
+    pub fn clear_error_context(&self) {
+        *self.error_context.write().unwrap() = None;
+    }
+
+    /// Omits trailing `null` params from requests, for servers and gateways rejecting them in
+    /// place of omitted optional params. Disabled by default.
+    ///
+    /// See [`RequestMeta::set_strict`](alloy_json_rpc::RequestMeta::set_strict) for more details.
+    pub fn set_strict_serialization(&self, strict: bool) {
+        self.strict.store(strict, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if trailing `null` params are omitted from requests.
+    pub fn is_strict_serialization(&self) -> bool {
+        self.strict.load(Ordering::Relaxed)
+    }
+
     /// Returns a reference to the underlying transport.
     #[inline]
     pub const fn transport(&self) -> &T {
@@ -219,7 +334,10 @@ impl<T> RpcClientInner<T> {
         method: impl Into<Cow<'static, str>>,
         params: Params,
     ) -> Request<Params> {
-        Request::new(method, self.next_id(), params)
+        let mut request =
+            Request::new(method, self.next_id(), params).with_trace_context(self.trace_context());
+        request.meta.set_strict(self.is_strict_serialization());
+        request
     }
 
     /// `true` if the client believes the transport is local.
@@ -270,7 +388,11 @@ impl<T: Transport + Clone> RpcClientInner<T> {
         params: Params,
     ) -> RpcCall<T, Params, Resp> {
         let request = self.make_request(method, params);
-        RpcCall::new(request, self.transport.clone())
+        let call = RpcCall::new(request, self.transport.clone());
+        match self.error_context.read().unwrap().clone() {
+            Some(config) => call.with_error_context(config),
+            None => call,
+        }
     }
 
     /// Type erase the service in the transport, allowing it to be used in a
@@ -287,6 +409,9 @@ impl<T: Transport + Clone> RpcClientInner<T> {
             is_local: self.is_local,
             id: self.id,
             poll_interval: self.poll_interval,
+            trace_context: self.trace_context,
+            error_context: self.error_context,
+            strict: self.strict,
         }
     }
 }
@@ -349,4 +474,62 @@ mod tests {
         // let client = client;
         assert_eq!(client.poll_interval(), Duration::from_millis(5000));
     }
+
+    #[test]
+    fn test_client_with_trace_context() {
+        let context: TraceContext =
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".parse().unwrap();
+        let client = RpcClient::new_http(reqwest::Url::parse("http://localhost").unwrap());
+        assert_eq!(client.make_request("eth_chainId", ()).meta.trace_context(), None);
+
+        let expected = context.clone();
+        let client = client.with_trace_context_fn(move || Some(context.clone()));
+        let request = client.make_request("eth_chainId", ()).serialize().unwrap();
+        assert_eq!(request.trace_context(), Some(&expected));
+
+        client.clear_trace_context_fn();
+        assert_eq!(client.make_request("eth_chainId", ()).meta.trace_context(), None);
+    }
+
+    #[test]
+    fn test_client_with_error_context() {
+        use alloy_json_rpc::RequestPacket;
+        use alloy_transport::{TransportErrorKind, TransportFut};
+
+        let transport = tower::service_fn(|_: RequestPacket| -> TransportFut<'static> {
+            Box::pin(async { Err(TransportErrorKind::backend_gone()) })
+        });
+        let client = RpcClient::new(transport, false);
+        let err = futures::executor::block_on(
+            client.request::<_, serde_json::Value>("eth_blockNumber", ()),
+        )
+        .unwrap_err();
+        assert!(err.context().is_none());
+
+        client.set_error_context(ErrorContext::new().endpoint("archive").with_params(64));
+        let call = client.request::<_, serde_json::Value>("eth_getBalance", ("0x01", "latest"));
+        let retry = call.clone();
+        futures::executor::block_on(call).unwrap_err();
+        let err = futures::executor::block_on(retry).unwrap_err();
+        assert!(err.is_transport_error());
+        let context = err.context().unwrap();
+        assert_eq!(context.method, "eth_getBalance");
+        assert_eq!(context.endpoint.as_deref(), Some("archive"));
+        assert_eq!(context.params.as_deref(), Some(r#"["0x01","latest"]"#));
+        assert_eq!(context.attempt, 2);
+    }
+
+    #[test]
+    fn test_client_strict_serialization() {
+        let client = RpcClient::new((), false).with_strict_serialization();
+        let params = (serde_json::json!({"to": "0x01", "value": null}), None::<u64>);
+        let request = client.make_request("eth_call", params).serialize().unwrap();
+        assert_eq!(
+            request.serialized().get(),
+            r#"{"method":"eth_call","params":[{"to":"0x01","value":null}],"id":0,"jsonrpc":"2.0"}"#
+        );
+
+        client.set_strict_serialization(false);
+        assert!(!client.make_request("eth_call", ()).meta.is_strict());
+    }
 }
@@ -0,0 +1,158 @@
+use alloy_json_rpc::{RequestContext, RpcParam};
+use alloy_transport::TransportError;
+use std::{
+    borrow::Cow,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::std::Instant;
+
+/// Configuration of the [`RequestContext`] attached to the errors of failed calls.
+///
+/// Set it with [`RpcClient::with_error_context`](crate::RpcClient::with_error_context). Errors
+/// then carry the method, endpoint label, attempt and elapsed time of the failed request, and its
+/// parameters if enabled with [`with_params`](Self::with_params).
+///
+/// ```
+/// use alloy_rpc_client::{ErrorContext, RpcClient};
+///
+/// let client = RpcClient::new_http("http://localhost:8545".parse().unwrap())
+///     .with_error_context(ErrorContext::new().endpoint("mainnet-archive").with_params(256));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    endpoint: Option<String>,
+    params_limit: Option<usize>,
+}
+
+impl ErrorContext {
+    /// Create a new configuration, attaching the method, attempt and elapsed time of failed
+    /// requests.
+    pub const fn new() -> Self {
+        Self { endpoint: None, params_limit: None }
+    }
+
+    /// Set the label of the endpoint, e.g. the provider name. URLs often contain API keys, so
+    /// they are not included by default.
+    pub fn endpoint(mut self, label: impl Into<String>) -> Self {
+        self.endpoint = Some(label.into());
+        self
+    }
+
+    /// Echo the serialized parameters of failed requests, truncated to `limit` bytes.
+    ///
+    /// Parameters may contain sensitive data, such as signed transactions, so this is disabled
+    /// by default.
+    pub const fn with_params(mut self, limit: usize) -> Self {
+        self.params_limit = Some(limit);
+        self
+    }
+
+    /// Returns the label of the endpoint, if set.
+    pub fn endpoint_label(&self) -> Option<&str> {
+        self.endpoint.as_deref()
+    }
+
+    /// Returns the maximum length of the echoed parameters, if enabled.
+    pub const fn params_limit(&self) -> Option<usize> {
+        self.params_limit
+    }
+}
+
+/// The state of an [`RpcCall`](crate::RpcCall) needed to build its error context.
+#[derive(Clone, Debug)]
+pub(crate) struct CallContext {
+    config: Arc<ErrorContext>,
+    /// Shared between clones of the call, so that retrying a call by cloning it counts attempts.
+    attempts: Arc<AtomicU32>,
+    method: Cow<'static, str>,
+    params: Option<String>,
+    attempt: u32,
+    sent_at: Option<Instant>,
+}
+
+impl CallContext {
+    pub(crate) fn new(config: Arc<ErrorContext>, method: Cow<'static, str>) -> Self {
+        Self { config, attempts: Arc::default(), method, params: None, attempt: 0, sent_at: None }
+    }
+
+    /// Returns `true` if the call was sent.
+    pub(crate) const fn is_sent(&self) -> bool {
+        self.attempt != 0
+    }
+
+    /// Records that the call was sent with `params`.
+    pub(crate) fn sent<Params: RpcParam>(&mut self, params: &Params) {
+        self.attempt = self.attempts.fetch_add(1, Ordering::Relaxed) + 1;
+        self.sent_at = Some(Instant::now());
+        self.params = self.config.params_limit.map(|limit| {
+            let params = serde_json::to_string(params).unwrap_or_default();
+            truncate(params, limit)
+        });
+    }
+
+    /// Attaches the context to `err`.
+    pub(crate) fn attach(&self, err: TransportError) -> TransportError {
+        let elapsed = self.sent_at.map(|sent_at| sent_at.elapsed()).unwrap_or(Duration::ZERO);
+        err.with_context(RequestContext {
+            method: self.method.clone(),
+            endpoint: self.config.endpoint.clone(),
+            params: self.params.clone(),
+            attempt: self.attempt,
+            elapsed,
+        })
+    }
+}
+
+fn truncate(mut s: String, limit: usize) -> String {
+    if s.len() > limit {
+        let mut end = limit;
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        s.truncate(end);
+        s.push_str("...");
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_transport::TransportErrorKind;
+
+    #[test]
+    fn attaches_context() {
+        let config = Arc::new(ErrorContext::new().endpoint("archive").with_params(12));
+        let mut context = CallContext::new(config, "eth_getBalance".into());
+        context.sent(&("0x0000000000000000000000000000000000000000", "latest"));
+        let mut retry = context.clone();
+        retry.sent(&("0x0000000000000000000000000000000000000000", "latest"));
+
+        let err = retry.attach(TransportErrorKind::backend_gone());
+        assert!(err.is_transport_error());
+        let attached = err.context().unwrap();
+        assert_eq!(attached.method, "eth_getBalance");
+        assert_eq!(attached.endpoint.as_deref(), Some("archive"));
+        assert_eq!(attached.params.as_deref(), Some(r#"["0x00000000..."#));
+        assert_eq!(attached.attempt, 2);
+        assert!(err.to_string().starts_with(
+            "backend connection task has stopped (method `eth_getBalance`, endpoint `archive`, \
+             attempt 2, elapsed "
+        ));
+
+        let config = Arc::new(ErrorContext::new());
+        let mut context = CallContext::new(config, "eth_sendRawTransaction".into());
+        context.sent(&("0x02f8",));
+        let err = context.attach(TransportErrorKind::backend_gone());
+        assert_eq!(err.context().unwrap().params, None);
+        assert!(err.without_context().context().is_none());
+    }
+}
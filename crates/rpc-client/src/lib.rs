@@ -30,6 +30,9 @@ pub use builtin::BuiltInConnectionString;
 mod call;
 pub use call::RpcCall;
 
+mod context;
+pub use context::ErrorContext;
+
 mod client;
 pub use client::{ClientRef, RpcClient, WeakClient};
 
@@ -1,5 +1,5 @@
 use crate::WeakClient;
-use alloy_json_rpc::{RpcError, RpcParam, RpcReturn};
+use alloy_json_rpc::{RpcParam, RpcReturn};
 use alloy_transport::{utils::Spawnable, Transport};
 use futures::{Stream, StreamExt};
 use serde::Serialize;
@@ -14,6 +14,11 @@ use tokio::sync::broadcast;
 use tokio_stream::wrappers::BroadcastStream;
 use tracing::Instrument;
 
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::tokio::sleep;
+
 /// The number of retries for polling a request.
 const MAX_RETRIES: usize = 3;
 
@@ -177,7 +182,10 @@ where
                                 break 'outer;
                             }
                         }
-                        Err(RpcError::Transport(err)) if retries > 0 && err.recoverable() => {
+                        Err(err)
+                            if retries > 0
+                                && err.as_transport_err().is_some_and(|err| err.recoverable()) =>
+                        {
                             debug!(%err, "failed to poll, retrying");
                             retries -= 1;
                             continue;
@@ -191,7 +199,7 @@ where
                 }
 
                 trace!(duration=?self.poll_interval, "sleeping");
-                tokio::time::sleep(self.poll_interval).await;
+                sleep(self.poll_interval).await;
             }
         };
         fut.instrument(span).spawn_task();
@@ -0,0 +1,77 @@
+//! Identifiers of beacon blocks in requests to the Beacon API.
+//!
+//! See also the `block_id` parameter of <https://ethereum.github.io/beacon-APIs/#/Beacon/getBlockHeader>
+
+use alloy_primitives::B256;
+use std::{fmt, str::FromStr};
+
+/// Identifier of a beacon block: the `block_id` path parameter of the Beacon API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BlockId {
+    /// The canonical head of the beacon node.
+    #[default]
+    Head,
+    /// The genesis block.
+    Genesis,
+    /// The latest finalized block.
+    Finalized,
+    /// The block at the given slot.
+    Slot(u64),
+    /// The block with the given root.
+    Root(B256),
+}
+
+impl From<u64> for BlockId {
+    fn from(slot: u64) -> Self {
+        Self::Slot(slot)
+    }
+}
+
+impl From<B256> for BlockId {
+    fn from(root: B256) -> Self {
+        Self::Root(root)
+    }
+}
+
+impl fmt::Display for BlockId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Head => f.write_str("head"),
+            Self::Genesis => f.write_str("genesis"),
+            Self::Finalized => f.write_str("finalized"),
+            Self::Slot(slot) => slot.fmt(f),
+            Self::Root(root) => root.fmt(f),
+        }
+    }
+}
+
+impl FromStr for BlockId {
+    type Err = alloy_primitives::hex::FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(Self::Head),
+            "genesis" => Ok(Self::Genesis),
+            "finalized" => Ok(Self::Finalized),
+            _ => match s.parse() {
+                Ok(slot) => Ok(Self::Slot(slot)),
+                Err(_) => s.parse().map(Self::Root),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_id_roundtrip() {
+        let root = B256::repeat_byte(0xab);
+        for id in [BlockId::Head, BlockId::Genesis, BlockId::Finalized, 42.into(), root.into()] {
+            assert_eq!(id.to_string().parse::<BlockId>().unwrap(), id);
+        }
+        assert_eq!(BlockId::Slot(42).to_string(), "42");
+        assert!("latest".parse::<BlockId>().is_err());
+    }
+}
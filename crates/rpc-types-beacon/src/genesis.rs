@@ -0,0 +1,80 @@
+//! Beacon chain genesis types.
+//!
+//! See also <https://ethereum.github.io/beacon-APIs/#/Beacon/getGenesis>
+
+use crate::Version;
+use alloy_eips::merge::SLOT_DURATION_SECS;
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+/// The response to a request for the genesis of the beacon chain: `getGenesis`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisResponse {
+    /// Container for the genesis data
+    pub data: GenesisData,
+}
+
+/// The genesis of the beacon chain.
+#[serde_as]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenesisData {
+    /// The unix timestamp of the genesis, in seconds
+    #[serde_as(as = "DisplayFromStr")]
+    pub genesis_time: u64,
+    /// The hash tree root of the validators of the genesis state
+    pub genesis_validators_root: B256,
+    /// The fork version of the genesis
+    pub genesis_fork_version: Version,
+}
+
+impl GenesisData {
+    /// Returns the slot that starts at the given unix timestamp, in seconds.
+    ///
+    /// Returns `None` if the timestamp is before the genesis or between two slots. This is the
+    /// slot of an execution payload of the given timestamp, such as the block of a blob
+    /// transaction.
+    pub const fn slot_at(&self, timestamp: u64) -> Option<u64> {
+        if timestamp < self.genesis_time {
+            return None;
+        }
+        let elapsed = timestamp - self.genesis_time;
+        if elapsed % SLOT_DURATION_SECS != 0 {
+            return None;
+        }
+        Some(elapsed / SLOT_DURATION_SECS)
+    }
+
+    /// Returns the unix timestamp of the start of the given slot, in seconds.
+    pub const fn slot_timestamp(&self, slot: u64) -> u64 {
+        self.genesis_time + slot * SLOT_DURATION_SECS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_genesis_response() {
+        let s = r#"{
+            "data": {
+                "genesis_time": "1606824023",
+                "genesis_validators_root": "0x4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95",
+                "genesis_fork_version": "0x00000000"
+            }
+        }"#;
+        let resp: GenesisResponse = serde_json::from_str(s).unwrap();
+        assert_eq!(resp.data.genesis_time, 1606824023);
+        assert_eq!(
+            serde_json::to_value(&resp).unwrap(),
+            serde_json::from_str::<serde_json::Value>(s).unwrap()
+        );
+
+        let genesis = resp.data;
+        assert_eq!(genesis.slot_at(1606824023 + 24), Some(2));
+        assert_eq!(genesis.slot_timestamp(2), 1606824023 + 24);
+        assert_eq!(genesis.slot_at(1606824023 + 25), None);
+        assert_eq!(genesis.slot_at(1606824022), None);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! See also <https://ethereum.github.io/beacon-APIs/#/Beacon/getBlockHeaders>
 
+#[cfg(feature = "ssz")]
+use alloy_eips::tree_hash::{self, HashTreeRoot};
 use alloy_primitives::{Bytes, B256};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
@@ -54,8 +56,11 @@ pub struct Header {
 }
 
 /// The header of a beacon block.
+///
+/// With the `ssz` feature, its hash tree root is the root of the block.
 #[serde_as]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ssz", derive(ssz_derive::Encode, ssz_derive::Decode))]
 pub struct BeaconBlockHeader {
     /// The slot to which this block corresponds.
     #[serde_as(as = "DisplayFromStr")]
@@ -71,6 +76,19 @@ pub struct BeaconBlockHeader {
     pub body_root: B256,
 }
 
+#[cfg(feature = "ssz")]
+impl HashTreeRoot for BeaconBlockHeader {
+    fn hash_tree_root(&self) -> B256 {
+        tree_hash::container_root(&[
+            self.slot.hash_tree_root(),
+            self.proposer_index.hash_tree_root(),
+            self.parent_root,
+            self.state_root,
+            self.body_root,
+        ])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +140,31 @@ mod tests {
         }"#;
         let _header_response: HeaderResponse = serde_json::from_str(s).unwrap();
     }
+
+    #[test]
+    #[cfg(feature = "ssz")]
+    fn ssz_beacon_block_header() {
+        use alloy_eips::tree_hash::merkleize;
+        use ssz::{Decode, Encode};
+
+        let header = BeaconBlockHeader {
+            slot: 1,
+            proposer_index: 2,
+            parent_root: B256::repeat_byte(3),
+            state_root: B256::repeat_byte(4),
+            body_root: B256::repeat_byte(5),
+        };
+        let bytes = header.as_ssz_bytes();
+        assert_eq!(bytes.len(), 112);
+        assert_eq!(BeaconBlockHeader::from_ssz_bytes(&bytes).unwrap(), header);
+
+        let leaves = [
+            1u64.hash_tree_root(),
+            2u64.hash_tree_root(),
+            header.parent_root,
+            header.state_root,
+            header.body_root,
+        ];
+        assert_eq!(header.hash_tree_root(), merkleize(&leaves, 8));
+    }
 }
@@ -12,14 +12,20 @@
 use alloy_primitives::FixedBytes;
 use constants::{BLS_PUBLIC_KEY_BYTES_LEN, BLS_SIGNATURE_BYTES_LEN};
 
+pub mod block_id;
 pub mod constants;
 /// Beacon API events support.
 pub mod events;
+pub mod genesis;
 pub mod header;
 pub mod payload;
+pub mod primitives;
 pub mod sidecar;
 pub mod withdrawals;
 
+pub use block_id::BlockId;
+pub use primitives::{Domain, DomainType, ForkDigest, Root, Version};
+
 /// BLS signature type
 pub type BlsSignature = FixedBytes<BLS_SIGNATURE_BYTES_LEN>;
 
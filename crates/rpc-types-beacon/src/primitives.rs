@@ -0,0 +1,144 @@
+//! Aliases of the consensus layer for fixed bytes, and helpers converting between them and the
+//! execution layer types.
+//!
+//! See the [phase0 custom types](https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#custom-types)
+//! of the consensus specs.
+
+use alloy_primitives::{FixedBytes, B256, U256};
+
+/// The SSZ hash tree root of an object.
+pub type Root = B256;
+
+/// A signature domain, see [`compute_domain`].
+pub type Domain = B256;
+
+/// The type of a signature domain, such as `DOMAIN_BEACON_PROPOSER`.
+pub type DomainType = FixedBytes<4>;
+
+/// A fork version.
+pub type Version = FixedBytes<4>;
+
+/// A digest of the current fork data, used to separate gossip topics, see [`fork_digest`].
+pub type ForkDigest = FixedBytes<4>;
+
+/// Checks at compile time that `LEN` bytes starting at `OFFSET` fit in `N` bytes.
+struct SliceBounds<const N: usize, const OFFSET: usize, const LEN: usize>;
+
+impl<const N: usize, const OFFSET: usize, const LEN: usize> SliceBounds<N, OFFSET, LEN> {
+    const CHECK: () = assert!(OFFSET + LEN <= N, "slice out of bounds");
+}
+
+/// Returns the `LEN` bytes of `bytes` starting at `OFFSET`.
+///
+/// Out of bounds slices fail to compile instead of panicking:
+///
+/// ```
+/// use alloy_primitives::{b256, fixed_bytes};
+/// use alloy_rpc_types_beacon::primitives::slice;
+///
+/// let root = b256!("0102030405060708091011121314151617181920212223242526272829303132");
+/// assert_eq!(slice::<32, 4, 2>(&root), fixed_bytes!("0506"));
+/// ```
+///
+/// ```compile_fail
+/// # use alloy_primitives::B256;
+/// # use alloy_rpc_types_beacon::primitives::slice;
+/// let _ = slice::<32, 30, 4>(&B256::ZERO);
+/// ```
+pub const fn slice<const N: usize, const OFFSET: usize, const LEN: usize>(
+    bytes: &FixedBytes<N>,
+) -> FixedBytes<LEN> {
+    #[allow(clippy::let_unit_value)]
+    let () = SliceBounds::<N, OFFSET, LEN>::CHECK;
+    let mut out = [0; LEN];
+    let mut i = 0;
+    while i < LEN {
+        out[i] = bytes.0[OFFSET + i];
+        i += 1;
+    }
+    FixedBytes(out)
+}
+
+/// Checks at compile time that `A` and `B` bytes make up `N` bytes.
+struct ConcatBounds<const A: usize, const B: usize, const N: usize>;
+
+impl<const A: usize, const B: usize, const N: usize> ConcatBounds<A, B, N> {
+    const CHECK: () = assert!(A + B == N, "length mismatch");
+}
+
+/// Returns `a` followed by `b`, where `N` must be the sum of their lengths `A` and `B`.
+///
+/// ```
+/// use alloy_primitives::fixed_bytes;
+/// use alloy_rpc_types_beacon::primitives::concat;
+///
+/// let bytes = concat::<2, 1, 3>(&fixed_bytes!("0102"), &fixed_bytes!("03"));
+/// assert_eq!(bytes, fixed_bytes!("010203"));
+/// ```
+pub const fn concat<const A: usize, const B: usize, const N: usize>(
+    a: &FixedBytes<A>,
+    b: &FixedBytes<B>,
+) -> FixedBytes<N> {
+    #[allow(clippy::let_unit_value)]
+    let () = ConcatBounds::<A, B, N>::CHECK;
+    let mut out = [0; N];
+    let mut i = 0;
+    while i < A {
+        out[i] = a.0[i];
+        i += 1;
+    }
+    while i < N {
+        out[i] = b.0[i - A];
+        i += 1;
+    }
+    FixedBytes(out)
+}
+
+/// Computes the signature domain of `domain_type` for the given fork data root.
+///
+/// See [`compute_domain`](https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#compute_domain).
+pub const fn compute_domain(domain_type: DomainType, fork_data_root: Root) -> Domain {
+    concat::<4, 28, 32>(&domain_type, &slice::<32, 0, 28>(&fork_data_root))
+}
+
+/// Returns the fork digest of the given fork data root.
+///
+/// See [`compute_fork_digest`](https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#compute_fork_digest).
+pub const fn fork_digest(fork_data_root: Root) -> ForkDigest {
+    slice::<32, 0, 4>(&fork_data_root)
+}
+
+/// Returns the SSZ encoding of `value`, which is also its hash tree root.
+///
+/// SSZ integers are little-endian, unlike the big-endian [`B256`] conversions of [`U256`].
+pub const fn uint256_to_root(value: U256) -> Root {
+    FixedBytes(value.to_le_bytes())
+}
+
+/// Decodes a [`U256`] from its SSZ encoding, see [`uint256_to_root`].
+pub const fn uint256_from_root(root: Root) -> U256 {
+    U256::from_le_bytes(root.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, fixed_bytes};
+
+    #[test]
+    fn computes_domain() {
+        let root = b256!("0102030405060708091011121314151617181920212223242526272829303132");
+        assert_eq!(
+            compute_domain(fixed_bytes!("07000000"), root),
+            b256!("0700000001020304050607080910111213141516171819202122232425262728")
+        );
+        assert_eq!(fork_digest(root), fixed_bytes!("01020304"));
+    }
+
+    #[test]
+    fn uint256_roundtrip() {
+        let root = uint256_to_root(U256::from(1));
+        assert_eq!(root, b256!("0100000000000000000000000000000000000000000000000000000000000000"));
+        assert_eq!(uint256_from_root(root), U256::from(1));
+    }
+}
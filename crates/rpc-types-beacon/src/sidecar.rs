@@ -1,5 +1,5 @@
 use crate::header::Header;
-use alloy_eips::eip4844::{Blob, BlobTransactionSidecar, Bytes48};
+use alloy_eips::eip4844::{kzg_to_versioned_hash, Blob, BlobTransactionSidecar, Bytes48};
 use alloy_primitives::{Bytes, B256};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
@@ -13,6 +13,29 @@ pub struct BeaconBlobBundle {
     data: Vec<BlobData>,
 }
 
+impl BeaconBlobBundle {
+    /// Returns the blob data of the bundle, ordered by index.
+    pub fn data(&self) -> &[BlobData] {
+        &self.data
+    }
+
+    /// Returns the sidecar of the transaction with the given blob versioned hashes.
+    ///
+    /// Blobs are matched by the versioned hash of their commitment, so that the bundle of the
+    /// block of a transaction yields its sidecar regardless of the other blob transactions in the
+    /// block. Returns `None` if a blob is missing.
+    pub fn sidecar_for(&self, versioned_hashes: &[B256]) -> Option<BlobTransactionSidecar> {
+        let mut sidecar = BlobTransactionSidecar::default();
+        for hash in versioned_hashes {
+            let blob = self.data.iter().find(|blob| blob.versioned_hash() == *hash)?;
+            sidecar.blobs.push(*blob.blob);
+            sidecar.commitments.push(blob.kzg_commitment);
+            sidecar.proofs.push(blob.kzg_proof);
+        }
+        Some(sidecar)
+    }
+}
+
 /// Yields an iterator for BlobData
 impl IntoIterator for BeaconBlobBundle {
     type Item = BlobData;
@@ -77,6 +100,14 @@ pub struct BlobData {
     pub kzg_commitment_inclusion_proof: Vec<B256>,
 }
 
+impl BlobData {
+    /// Returns the versioned hash of the blob's commitment, which is referenced by its
+    /// transaction.
+    pub fn versioned_hash(&self) -> B256 {
+        kzg_to_versioned_hash(self.kzg_commitment.as_slice())
+    }
+}
+
 /// Helper function to deserialize boxed blobs
 fn deserialize_blob<'de, D>(deserializer: D) -> Result<Box<Blob>, D::Error>
 where
@@ -102,4 +133,16 @@ mod tests {
         assert_eq!(json, serde_json::to_value(resp.clone()).unwrap());
         assert_eq!(6, resp.data.len());
     }
+
+    #[test]
+    fn sidecar_for_versioned_hashes() {
+        let resp: BeaconBlobBundle =
+            serde_json::from_str(include_str!("examples/sidecar.json")).unwrap();
+        let hashes = [resp.data()[3].versioned_hash(), resp.data()[1].versioned_hash()];
+        let sidecar = resp.sidecar_for(&hashes).unwrap();
+        assert_eq!(sidecar.commitments, [resp.data[3].kzg_commitment, resp.data[1].kzg_commitment]);
+        assert_eq!(sidecar.versioned_hashes().collect::<Vec<_>>(), hashes);
+
+        assert!(resp.sidecar_for(&[B256::ZERO]).is_none());
+    }
 }
@@ -0,0 +1,265 @@
+//! Conversions between execution payloads and consensus blocks.
+
+use crate::{
+    ExecutionPayload, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3,
+    ExecutionPayloadV4, PayloadError,
+};
+use alloy_consensus::{trie::ordered_trie_root, Header, TxEnvelope, EMPTY_OMMER_ROOT_HASH};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    eip6110::DepositRequest,
+    eip7002::{WithdrawalRequest, WITHDRAWAL_REQUEST_TYPE},
+};
+use alloy_primitives::{Bytes, B256, B64, U256};
+use alloy_rpc_types::Withdrawal;
+
+/// The [EIP-7685] type of deposit requests.
+///
+/// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+const DEPOSIT_REQUEST_TYPE: u8 = 0x00;
+
+/// A block in its consensus representation: a header and the decoded body.
+///
+/// This is what an [`ExecutionPayload`] carries, with the transactions decoded and the roots
+/// committed to in the header computed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExecutionBlock {
+    /// The block header.
+    pub header: Header,
+    /// The transactions of the block.
+    pub transactions: Vec<TxEnvelope>,
+    /// The withdrawals of the block, since Shanghai.
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// The deposit requests of the block, since Prague.
+    pub deposit_requests: Option<Vec<DepositRequest>>,
+    /// The withdrawal requests of the block, since Prague.
+    pub withdrawal_requests: Option<Vec<WithdrawalRequest>>,
+}
+
+impl ExecutionBlock {
+    /// Computes the hash of the block.
+    pub fn hash_slow(&self) -> B256 {
+        self.header.hash_slow()
+    }
+
+    /// Returns the versioned hashes of all blobs in the block, in order, as passed to
+    /// `engine_newPayloadV3`.
+    pub fn blob_versioned_hashes(&self) -> Vec<B256> {
+        self.transactions
+            .iter()
+            .filter_map(|tx| match tx {
+                TxEnvelope::Eip4844(tx) => Some(tx.tx().tx().blob_versioned_hashes.iter().copied()),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Converts the block into an execution payload.
+    ///
+    /// The payload version is the lowest one that can carry all fields of the block, e.g. a block
+    /// with withdrawals but no blob gas fields is converted to an [`ExecutionPayloadV2`]. The
+    /// parent beacon block root is not part of the payload, and must be sent alongside it.
+    pub fn into_payload(self) -> ExecutionPayload {
+        let header = self.header;
+        let block_hash = header.hash_slow();
+        let v1 = ExecutionPayloadV1 {
+            parent_hash: header.parent_hash,
+            fee_recipient: header.beneficiary,
+            state_root: header.state_root,
+            receipts_root: header.receipts_root,
+            logs_bloom: header.logs_bloom,
+            prev_randao: header.mix_hash,
+            block_number: header.number,
+            gas_limit: header.gas_limit as u64,
+            gas_used: header.gas_used as u64,
+            timestamp: header.timestamp,
+            extra_data: header.extra_data,
+            base_fee_per_gas: U256::from(header.base_fee_per_gas.unwrap_or_default()),
+            block_hash,
+            transactions: self
+                .transactions
+                .iter()
+                .map(|tx| Bytes::from(tx.encoded_2718()))
+                .collect(),
+        };
+        let Some(withdrawals) = self.withdrawals else {
+            return v1.into();
+        };
+        let v2 = ExecutionPayloadV2 { payload_inner: v1, withdrawals };
+        let (Some(blob_gas_used), Some(excess_blob_gas)) =
+            (header.blob_gas_used, header.excess_blob_gas)
+        else {
+            return v2.into();
+        };
+        let v3 = ExecutionPayloadV3 {
+            payload_inner: v2,
+            blob_gas_used: blob_gas_used as u64,
+            excess_blob_gas: excess_blob_gas as u64,
+        };
+        if self.deposit_requests.is_none() && self.withdrawal_requests.is_none() {
+            return v3.into();
+        }
+        ExecutionPayloadV4 {
+            payload_inner: v3,
+            deposit_requests: self.deposit_requests.unwrap_or_default(),
+            withdrawal_requests: self.withdrawal_requests.unwrap_or_default(),
+        }
+        .into()
+    }
+}
+
+impl From<ExecutionBlock> for ExecutionPayload {
+    fn from(block: ExecutionBlock) -> Self {
+        block.into_payload()
+    }
+}
+
+impl ExecutionPayload {
+    /// Decodes the payload into a block, verifying its block hash.
+    ///
+    /// `parent_beacon_block_root` must be set for Cancun payloads and later, and unset before.
+    pub fn try_into_block(
+        self,
+        parent_beacon_block_root: Option<B256>,
+    ) -> Result<ExecutionBlock, PayloadError> {
+        let (v1, withdrawals, blob_gas, requests) = match self {
+            Self::V1(v1) => (v1, None, None, None),
+            Self::V2(v2) => (v2.payload_inner, Some(v2.withdrawals), None, None),
+            Self::V3(v3) => (
+                v3.payload_inner.payload_inner,
+                Some(v3.payload_inner.withdrawals),
+                Some((v3.blob_gas_used, v3.excess_blob_gas)),
+                None,
+            ),
+            Self::V4(v4) => {
+                let v3 = v4.payload_inner;
+                (
+                    v3.payload_inner.payload_inner,
+                    Some(v3.payload_inner.withdrawals),
+                    Some((v3.blob_gas_used, v3.excess_blob_gas)),
+                    Some((v4.deposit_requests, v4.withdrawal_requests)),
+                )
+            }
+        };
+        match (&blob_gas, parent_beacon_block_root) {
+            (Some(_), None) => return Err(PayloadError::PostCancunWithoutCancunFields),
+            (None, Some(_)) => return Err(PayloadError::PreCancunWithCancunFields),
+            _ => {}
+        }
+        if v1.extra_data.len() > 32 {
+            return Err(PayloadError::ExtraData(v1.extra_data));
+        }
+        let base_fee_per_gas: u128 = v1
+            .base_fee_per_gas
+            .try_into()
+            .map_err(|_| PayloadError::BaseFee(v1.base_fee_per_gas))?;
+
+        let transactions = v1
+            .transactions
+            .iter()
+            .map(|tx| TxEnvelope::decode_2718(&mut tx.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if blob_gas.is_none() && transactions.iter().any(|tx| matches!(tx, TxEnvelope::Eip4844(_)))
+        {
+            return Err(PayloadError::PreCancunBlockWithBlobTransactions);
+        }
+
+        let withdrawals_root = withdrawals.as_ref().map(|withdrawals| {
+            let encoded: Vec<_> = withdrawals.iter().map(alloy_rlp::encode).collect();
+            ordered_trie_root(&encoded)
+        });
+        let requests_root = requests.as_ref().map(|(deposits, withdrawal_requests)| {
+            let deposits = deposits.iter().map(|r| encode_request(DEPOSIT_REQUEST_TYPE, r));
+            let withdrawal_requests =
+                withdrawal_requests.iter().map(|r| encode_request(WITHDRAWAL_REQUEST_TYPE, r));
+            ordered_trie_root(&deposits.chain(withdrawal_requests).collect::<Vec<_>>())
+        });
+
+        let header = Header {
+            parent_hash: v1.parent_hash,
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            beneficiary: v1.fee_recipient,
+            state_root: v1.state_root,
+            transactions_root: ordered_trie_root(&v1.transactions),
+            receipts_root: v1.receipts_root,
+            withdrawals_root,
+            logs_bloom: v1.logs_bloom,
+            difficulty: U256::ZERO,
+            number: v1.block_number,
+            gas_limit: v1.gas_limit as u128,
+            gas_used: v1.gas_used as u128,
+            timestamp: v1.timestamp,
+            mix_hash: v1.prev_randao,
+            nonce: B64::ZERO,
+            base_fee_per_gas: Some(base_fee_per_gas),
+            blob_gas_used: blob_gas.map(|(used, _)| used as u128),
+            excess_blob_gas: blob_gas.map(|(_, excess)| excess as u128),
+            parent_beacon_block_root,
+            requests_root,
+            extra_data: v1.extra_data,
+        };
+
+        let execution = header.hash_slow();
+        if execution != v1.block_hash {
+            return Err(PayloadError::BlockHash { execution, consensus: v1.block_hash });
+        }
+
+        let (deposit_requests, withdrawal_requests) = requests.unzip();
+        Ok(ExecutionBlock {
+            header,
+            transactions,
+            withdrawals,
+            deposit_requests,
+            withdrawal_requests,
+        })
+    }
+}
+
+fn encode_request(ty: u8, request: &impl alloy_rlp::Encodable) -> Vec<u8> {
+    let mut out = vec![ty];
+    request.encode(&mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // pulled from hive tests
+    const V1_PAYLOAD: &str = r#"{"parentHash":"0x67ead97eb79b47a1638659942384143f36ed44275d4182799875ab5a87324055","feeRecipient":"0x0000000000000000000000000000000000000000","stateRoot":"0x0000000000000000000000000000000000000000000000000000000000000000","receiptsRoot":"0x4e3c608a9f2e129fccb91a1dae7472e78013b8e654bccc8d224ce3d63ae17006","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","prevRandao":"0x44bb4b98c59dbb726f96ffceb5ee028dcbe35b9bba4f9ffd56aeebf8d1e4db62","blockNumber":"0x1","gasLimit":"0x2fefd8","gasUsed":"0xa860","timestamp":"0x1235","extraData":"0x8b726574682f76302e312e30","baseFeePerGas":"0x342770c0","blockHash":"0x5655011482546f16b2312ef18e9fad03d6a52b1be95401aea884b222477f9e64","transactions":["0xf865808506fc23ac00830124f8940000000000000000000000000000000000000316018032a044b25a8b9b247d01586b3d59c71728ff49c9b84928d9e7fa3377ead3b5570b5da03ceac696601ff7ee6f5fe8864e2998db9babdf5eeba1a0cd5b4d44b3fcbd181b"]}"#;
+
+    #[test]
+    fn payload_block_roundtrip() {
+        let payload = ExecutionPayload::V1(serde_json::from_str(V1_PAYLOAD).unwrap());
+        let block = payload.clone().try_into_block(None).unwrap();
+        assert_eq!(block.transactions.len(), 1);
+        assert_eq!(block.hash_slow(), payload.block_hash());
+        assert_eq!(block.into_payload(), payload);
+    }
+
+    #[test]
+    fn shanghai_block_roundtrip() {
+        let v1: ExecutionPayloadV1 = serde_json::from_str(V1_PAYLOAD).unwrap();
+        let mut block = ExecutionPayload::V1(v1).try_into_block(None).unwrap();
+        let withdrawals: Vec<_> = (0..20)
+            .map(|index| Withdrawal { index, amount: 1000 + index, ..Default::default() })
+            .collect();
+        let encoded: Vec<_> = withdrawals.iter().map(alloy_rlp::encode).collect();
+        block.header.withdrawals_root = Some(ordered_trie_root(&encoded));
+        block.withdrawals = Some(withdrawals);
+
+        let payload = block.clone().into_payload();
+        assert!(matches!(payload, ExecutionPayload::V2(_)));
+        assert_eq!(payload.block_hash(), block.hash_slow());
+        assert_eq!(payload.clone().try_into_block(None).unwrap(), block);
+
+        let mut tampered = payload.clone();
+        tampered.as_v2_mut().unwrap().withdrawals.pop();
+        assert!(tampered.try_into_block(None).unwrap_err().is_block_hash_mismatch());
+        assert!(matches!(
+            payload.try_into_block(Some(B256::ZERO)),
+            Err(PayloadError::PreCancunWithCancunFields)
+        ));
+    }
+}
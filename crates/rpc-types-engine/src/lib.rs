@@ -20,6 +20,7 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod block;
 mod cancun;
 mod forkchoice;
 mod identification;
@@ -29,7 +30,8 @@ pub mod payload;
 mod transition;
 
 pub use self::{
-    cancun::*, forkchoice::*, identification::*, jwt::*, optimism::*, payload::*, transition::*,
+    block::*, cancun::*, forkchoice::*, identification::*, jwt::*, optimism::*, payload::*,
+    transition::*,
 };
 
 #[doc(inline)]
@@ -39,7 +41,7 @@ pub use alloy_eips::eip6110::DepositRequest as DepositRequestV1;
 pub use alloy_eips::eip7002::WithdrawalRequest as WithdrawalRequestV1;
 
 /// The list of all supported Engine capabilities available over the engine endpoint.
-pub const CAPABILITIES: [&str; 12] = [
+pub const CAPABILITIES: [&str; 14] = [
     "engine_forkchoiceUpdatedV1",
     "engine_forkchoiceUpdatedV2",
     "engine_forkchoiceUpdatedV3",
@@ -47,9 +49,11 @@ pub const CAPABILITIES: [&str; 12] = [
     "engine_getPayloadV1",
     "engine_getPayloadV2",
     "engine_getPayloadV3",
+    "engine_getPayloadV4",
     "engine_newPayloadV1",
     "engine_newPayloadV2",
     "engine_newPayloadV3",
+    "engine_newPayloadV4",
     "engine_getPayloadBodiesByHashV1",
     "engine_getPayloadBodiesByRangeV1",
 ];
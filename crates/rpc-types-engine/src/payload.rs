@@ -1,5 +1,7 @@
 //! Payload types.
 use alloy_consensus::{Blob, Bytes48};
+#[cfg(feature = "ssz")]
+use alloy_eips::tree_hash::{self, HashTreeRoot};
 use alloy_eips::{eip6110::DepositRequest, eip7002::WithdrawalRequest};
 use alloy_primitives::{Address, Bloom, Bytes, B256, B64, U256};
 use alloy_rpc_types::{transaction::BlobTransactionSidecar, Withdrawal};
@@ -453,6 +455,207 @@ impl ExecutionPayloadV4 {
     }
 }
 
+#[cfg(feature = "ssz")]
+impl ssz::Decode for ExecutionPayloadV4 {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        let mut builder = ssz::SszDecoderBuilder::new(bytes);
+
+        builder.register_type::<B256>()?;
+        builder.register_type::<Address>()?;
+        builder.register_type::<B256>()?;
+        builder.register_type::<B256>()?;
+        builder.register_type::<Bloom>()?;
+        builder.register_type::<B256>()?;
+        builder.register_type::<u64>()?;
+        builder.register_type::<u64>()?;
+        builder.register_type::<u64>()?;
+        builder.register_type::<u64>()?;
+        builder.register_type::<Bytes>()?;
+        builder.register_type::<U256>()?;
+        builder.register_type::<B256>()?;
+        builder.register_type::<Vec<Bytes>>()?;
+        builder.register_type::<Vec<Withdrawal>>()?;
+        builder.register_type::<u64>()?;
+        builder.register_type::<u64>()?;
+        builder.register_type::<Vec<DepositRequest>>()?;
+        builder.register_type::<Vec<WithdrawalRequest>>()?;
+
+        let mut decoder = builder.build()?;
+
+        Ok(Self {
+            payload_inner: ExecutionPayloadV3 {
+                payload_inner: ExecutionPayloadV2 {
+                    payload_inner: ExecutionPayloadV1 {
+                        parent_hash: decoder.decode_next()?,
+                        fee_recipient: decoder.decode_next()?,
+                        state_root: decoder.decode_next()?,
+                        receipts_root: decoder.decode_next()?,
+                        logs_bloom: decoder.decode_next()?,
+                        prev_randao: decoder.decode_next()?,
+                        block_number: decoder.decode_next()?,
+                        gas_limit: decoder.decode_next()?,
+                        gas_used: decoder.decode_next()?,
+                        timestamp: decoder.decode_next()?,
+                        extra_data: decoder.decode_next()?,
+                        base_fee_per_gas: decoder.decode_next()?,
+                        block_hash: decoder.decode_next()?,
+                        transactions: decoder.decode_next()?,
+                    },
+                    withdrawals: decoder.decode_next()?,
+                },
+                blob_gas_used: decoder.decode_next()?,
+                excess_blob_gas: decoder.decode_next()?,
+            },
+            deposit_requests: decoder.decode_next()?,
+            withdrawal_requests: decoder.decode_next()?,
+        })
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl ssz::Encode for ExecutionPayloadV4 {
+    fn is_ssz_fixed_len() -> bool {
+        false
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        let offset = <B256 as ssz::Encode>::ssz_fixed_len() * 5
+            + <Address as ssz::Encode>::ssz_fixed_len()
+            + <Bloom as ssz::Encode>::ssz_fixed_len()
+            + <u64 as ssz::Encode>::ssz_fixed_len() * 6
+            + <U256 as ssz::Encode>::ssz_fixed_len()
+            + ssz::BYTES_PER_LENGTH_OFFSET * 5;
+
+        let mut encoder = ssz::SszEncoder::container(buf, offset);
+
+        let v3 = &self.payload_inner;
+        let v1 = &v3.payload_inner.payload_inner;
+        encoder.append(&v1.parent_hash);
+        encoder.append(&v1.fee_recipient);
+        encoder.append(&v1.state_root);
+        encoder.append(&v1.receipts_root);
+        encoder.append(&v1.logs_bloom);
+        encoder.append(&v1.prev_randao);
+        encoder.append(&v1.block_number);
+        encoder.append(&v1.gas_limit);
+        encoder.append(&v1.gas_used);
+        encoder.append(&v1.timestamp);
+        encoder.append(&v1.extra_data);
+        encoder.append(&v1.base_fee_per_gas);
+        encoder.append(&v1.block_hash);
+        encoder.append(&v1.transactions);
+        encoder.append(&v3.payload_inner.withdrawals);
+        encoder.append(&v3.blob_gas_used);
+        encoder.append(&v3.excess_blob_gas);
+        encoder.append(&self.deposit_requests);
+        encoder.append(&self.withdrawal_requests);
+
+        encoder.finalize();
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        <ExecutionPayloadV3 as ssz::Encode>::ssz_bytes_len(&self.payload_inner)
+            + ssz::BYTES_PER_LENGTH_OFFSET * 2
+            + self.deposit_requests.ssz_bytes_len()
+            + self.withdrawal_requests.ssz_bytes_len()
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl ExecutionPayloadV1 {
+    /// Returns the hash tree roots of the fields of the payload, in order.
+    fn field_roots(&self) -> Vec<B256> {
+        let transactions = self
+            .transactions
+            .iter()
+            .map(|tx| tree_hash::byte_list_root(tx, tree_hash::MAX_BYTES_PER_TRANSACTION))
+            .collect::<Vec<_>>();
+        let transactions_root = tree_hash::mix_in_length(
+            tree_hash::merkleize(&transactions, tree_hash::MAX_TRANSACTIONS_PER_PAYLOAD),
+            transactions.len(),
+        );
+        vec![
+            self.parent_hash.hash_tree_root(),
+            self.fee_recipient.hash_tree_root(),
+            self.state_root.hash_tree_root(),
+            self.receipts_root.hash_tree_root(),
+            self.logs_bloom.hash_tree_root(),
+            self.prev_randao.hash_tree_root(),
+            self.block_number.hash_tree_root(),
+            self.gas_limit.hash_tree_root(),
+            self.gas_used.hash_tree_root(),
+            self.timestamp.hash_tree_root(),
+            tree_hash::byte_list_root(&self.extra_data, tree_hash::MAX_EXTRA_DATA_BYTES),
+            self.base_fee_per_gas.hash_tree_root(),
+            self.block_hash.hash_tree_root(),
+            transactions_root,
+        ]
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl ExecutionPayloadV2 {
+    /// Returns the hash tree roots of the fields of the payload, in order.
+    fn field_roots(&self) -> Vec<B256> {
+        let mut roots = self.payload_inner.field_roots();
+        roots.push(tree_hash::list_root(&self.withdrawals, tree_hash::MAX_WITHDRAWALS_PER_PAYLOAD));
+        roots
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl ExecutionPayloadV3 {
+    /// Returns the hash tree roots of the fields of the payload, in order.
+    fn field_roots(&self) -> Vec<B256> {
+        let mut roots = self.payload_inner.field_roots();
+        roots.push(self.blob_gas_used.hash_tree_root());
+        roots.push(self.excess_blob_gas.hash_tree_root());
+        roots
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl ExecutionPayloadV4 {
+    /// Returns the hash tree roots of the fields of the payload, in order.
+    fn field_roots(&self) -> Vec<B256> {
+        let mut roots = self.payload_inner.field_roots();
+        roots.push(tree_hash::list_root(
+            &self.deposit_requests,
+            tree_hash::MAX_DEPOSIT_REQUESTS_PER_PAYLOAD,
+        ));
+        roots.push(tree_hash::list_root(
+            &self.withdrawal_requests,
+            tree_hash::MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        ));
+        roots
+    }
+}
+
+// The hash tree root of a payload is the root of the `execution_payload` field of its beacon
+// block body, which is also the hash tree root of the matching `ExecutionPayloadHeader`.
+#[cfg(feature = "ssz")]
+macro_rules! impl_payload_hash_tree_root {
+    ($($payload:ty),*) => {$(
+        impl HashTreeRoot for $payload {
+            fn hash_tree_root(&self) -> B256 {
+                tree_hash::container_root(&self.field_roots())
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "ssz")]
+impl_payload_hash_tree_root!(
+    ExecutionPayloadV1,
+    ExecutionPayloadV2,
+    ExecutionPayloadV3,
+    ExecutionPayloadV4
+);
+
 /// This includes all bundled blob related data of an executed payload.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BlobsBundleV1 {
@@ -594,6 +797,18 @@ pub enum ExecutionPayload {
     V4(ExecutionPayloadV4),
 }
 
+#[cfg(feature = "ssz")]
+impl HashTreeRoot for ExecutionPayload {
+    fn hash_tree_root(&self) -> B256 {
+        match self {
+            ExecutionPayload::V1(payload) => payload.hash_tree_root(),
+            ExecutionPayload::V2(payload) => payload.hash_tree_root(),
+            ExecutionPayload::V3(payload) => payload.hash_tree_root(),
+            ExecutionPayload::V4(payload) => payload.hash_tree_root(),
+        }
+    }
+}
+
 impl ExecutionPayload {
     /// Returns a reference to the V1 payload.
     pub const fn as_v1(&self) -> &ExecutionPayloadV1 {
@@ -1342,4 +1557,50 @@ mod tests {
             serde_json::from_str(input);
         assert!(payload_res.is_err());
     }
+
+    #[test]
+    #[cfg(feature = "ssz")]
+    fn ssz_payload_v4() {
+        use ssz::{Decode, Encode};
+
+        let s = r#"{"parentHash":"0x67ead97eb79b47a1638659942384143f36ed44275d4182799875ab5a87324055","feeRecipient":"0x0000000000000000000000000000000000000000","stateRoot":"0x76a03cbcb7adce07fd284c61e4fa31e5e786175cefac54a29e46ec8efa28ea41","receiptsRoot":"0x4e3c608a9f2e129fccb91a1dae7472e78013b8e654bccc8d224ce3d63ae17006","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","prevRandao":"0x028111cb7d25918386a69656b3d17b2febe95fd0f11572c1a55c14f99fdfe3df","blockNumber":"0x1","gasLimit":"0x2fefd8","gasUsed":"0xa860","timestamp":"0x1235","extraData":"0x8b726574682f76302e312e30","baseFeePerGas":"0x342770c0","blockHash":"0xa6f40ed042e61e88e76125dede8fff8026751ea14454b68fb534cea99f2b2a77","transactions":["0xf865808506fc23ac00830124f8940000000000000000000000000000000000000316018032a044b25a8b9b247d01586b3d59c71728ff49c9b84928d9e7fa3377ead3b5570b5da03ceac696601ff7ee6f5fe8864e2998db9babdf5eeba1a0cd5b4d44b3fcbd181b"],"withdrawals":[],"blobGasUsed":"0xb10b","excessBlobGas":"0xb10b"}"#;
+        let v3: ExecutionPayloadV3 = serde_json::from_str(s).unwrap();
+        let payload = ExecutionPayloadV4 {
+            payload_inner: v3.clone(),
+            deposit_requests: vec![DepositRequest {
+                pubkey: Default::default(),
+                withdrawal_credentials: B256::repeat_byte(1),
+                amount: 32_000_000_000,
+                signature: Default::default(),
+                index: 7,
+            }],
+            withdrawal_requests: vec![WithdrawalRequest {
+                source_address: Address::repeat_byte(2),
+                validator_public_key: Default::default(),
+                amount: 1,
+            }],
+        };
+
+        let bytes = payload.as_ssz_bytes();
+        assert_eq!(bytes.len(), payload.ssz_bytes_len());
+        assert_eq!(ExecutionPayloadV4::from_ssz_bytes(&bytes).unwrap(), payload);
+        assert_eq!(ExecutionPayloadV3::from_ssz_bytes(&v3.as_ssz_bytes()).unwrap(), v3);
+
+        // The roots of later payloads extend the fields of the previous ones.
+        let roots = v3.field_roots();
+        assert_eq!(roots.len(), 17);
+        assert_eq!(
+            roots[14],
+            alloy_primitives::b256!(
+                "792930bbd5baac43bcc798ee49aa8185ef76bb3b44ba62b91d86ae569e4bb535"
+            )
+        );
+        assert_eq!(payload.field_roots()[..17], roots[..]);
+        assert_eq!(v3.hash_tree_root(), tree_hash::container_root(&roots));
+        assert_eq!(
+            ExecutionPayload::from(payload.clone()).hash_tree_root(),
+            payload.hash_tree_root()
+        );
+        assert_ne!(payload.hash_tree_root(), v3.hash_tree_root());
+    }
 }
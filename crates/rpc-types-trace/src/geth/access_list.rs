@@ -0,0 +1,159 @@
+//! Conversion of geth tracer outputs into [EIP-2930] access lists.
+//!
+//! Simulating a transaction with the prestate or call tracer reveals the accounts and storage
+//! slots it accesses. Sending the transaction with these in its access list makes the accesses
+//! cheaper.
+//!
+//! [EIP-2930]: https://eips.ethereum.org/EIPS/eip-2930
+
+use crate::geth::{CallFrame, PreStateFrame};
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types::{AccessList, AccessListItem};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Builds an [`AccessList`] from the outputs of geth tracers.
+///
+/// Accounts and storage slots are deduplicated, and sorted by address and slot. Tracers also
+/// report the accounts that are warm at the start of a transaction, such as the sender, the
+/// recipient and the precompiles, which only make the transaction more expensive if listed:
+/// remove them with [`exclude`](Self::exclude).
+///
+/// ```
+/// use alloy_primitives::Address;
+/// use alloy_rpc_types_trace::geth::{access_list::AccessListBuilder, PreStateFrame};
+///
+/// # fn example(trace: PreStateFrame, from: Address, to: Address) {
+/// let access_list = AccessListBuilder::new().with_pre_state(&trace).exclude([from, to]).build();
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessListBuilder {
+    accessed: BTreeMap<Address, BTreeSet<B256>>,
+}
+
+impl AccessListBuilder {
+    /// Creates an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an accessed account.
+    pub fn with_address(mut self, address: Address) -> Self {
+        self.accessed.entry(address).or_default();
+        self
+    }
+
+    /// Adds an accessed storage slot of an account.
+    pub fn with_slot(mut self, address: Address, slot: B256) -> Self {
+        self.accessed.entry(address).or_default().insert(slot);
+        self
+    }
+
+    /// Adds the accounts and storage slots of a prestate tracer output.
+    ///
+    /// In diff mode, this only covers the accounts and slots changed by the transaction.
+    pub fn with_pre_state(mut self, frame: &PreStateFrame) -> Self {
+        let states = match frame {
+            PreStateFrame::Default(mode) => vec![&mode.0],
+            PreStateFrame::Diff(mode) => vec![&mode.pre, &mode.post],
+        };
+        for (address, state) in states.into_iter().flatten() {
+            self.accessed.entry(*address).or_default().extend(state.storage.keys().copied());
+        }
+        self
+    }
+
+    /// Adds the accounts called by a call tracer output and its subcalls.
+    ///
+    /// The call tracer does not report storage accesses. Created contracts are skipped, as they
+    /// are warm once created.
+    pub fn with_call_frame(mut self, frame: &CallFrame) -> Self {
+        let mut frames = vec![frame];
+        while let Some(frame) = frames.pop() {
+            if let Some(to) = frame.to.filter(|_| !frame.typ.starts_with("CREATE")) {
+                self.accessed.entry(to).or_default();
+            }
+            frames.extend(&frame.calls);
+        }
+        self
+    }
+
+    /// Removes accounts, along with their storage slots.
+    pub fn exclude(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        for address in addresses {
+            self.accessed.remove(&address);
+        }
+        self
+    }
+
+    /// Returns the access list.
+    pub fn build(self) -> AccessList {
+        self.accessed
+            .into_iter()
+            .map(|(address, slots)| AccessListItem {
+                address,
+                storage_keys: slots.into_iter().collect(),
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geth::{AccountState, DiffMode, PreStateMode};
+
+    fn state(slots: &[u8]) -> AccountState {
+        AccountState {
+            storage: slots.iter().map(|slot| (B256::with_last_byte(*slot), B256::ZERO)).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pre_state_access_list() {
+        let (a, b, sender) = (Address::repeat_byte(0xa), Address::repeat_byte(0xb), Address::ZERO);
+        let frame = PreStateFrame::Diff(DiffMode {
+            pre: BTreeMap::from([(b, state(&[2, 1])), (sender, state(&[]))]),
+            post: BTreeMap::from([(b, state(&[3, 1])), (a, state(&[]))]),
+        });
+        let access_list = AccessListBuilder::new().with_pre_state(&frame).exclude([sender]).build();
+        assert_eq!(
+            access_list.0,
+            [
+                AccessListItem { address: a, storage_keys: vec![] },
+                AccessListItem {
+                    address: b,
+                    storage_keys: vec![
+                        B256::with_last_byte(1),
+                        B256::with_last_byte(2),
+                        B256::with_last_byte(3)
+                    ],
+                },
+            ]
+        );
+
+        let frame = PreStateFrame::Default(PreStateMode(BTreeMap::from([(a, state(&[1]))])));
+        let access_list = AccessListBuilder::new().with_pre_state(&frame).build();
+        assert_eq!(access_list.contains_storage(a, B256::with_last_byte(1)), (true, true));
+    }
+
+    #[test]
+    fn call_frame_access_list() {
+        let (a, b, c) =
+            (Address::repeat_byte(0xa), Address::repeat_byte(0xb), Address::repeat_byte(0xc));
+        let frame = CallFrame {
+            to: Some(a),
+            typ: "CALL".into(),
+            calls: vec![
+                CallFrame { to: Some(c), typ: "STATICCALL".into(), ..Default::default() },
+                CallFrame { to: Some(b), typ: "CREATE2".into(), ..Default::default() },
+                CallFrame { to: Some(c), typ: "DELEGATECALL".into(), ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let access_list = AccessListBuilder::new().with_call_frame(&frame).exclude([a]).build();
+        assert_eq!(access_list.0, [AccessListItem { address: c, storage_keys: vec![] }]);
+    }
+}
@@ -0,0 +1,230 @@
+//! Gas accounting derived from geth traces.
+
+use crate::geth::{CallFrame, DefaultFrame, StructLog};
+use std::collections::BTreeMap;
+
+/// Category of an EVM opcode, used to break down the gas spent by a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpcodeCategory {
+    /// Arithmetic operations, e.g. `ADD`, `MUL` or `EXP`.
+    Arithmetic,
+    /// Comparison and bitwise operations, e.g. `LT`, `AND` or `SHL`.
+    Bitwise,
+    /// `KECCAK256`.
+    Hashing,
+    /// Reads of the execution environment, e.g. `CALLER`, `CALLDATACOPY` or `BALANCE`.
+    Environment,
+    /// Reads of the block context, e.g. `NUMBER`, `TIMESTAMP` or `BLOBHASH`.
+    Block,
+    /// Stack operations: `POP`, `PUSH*`, `DUP*` and `SWAP*`.
+    Stack,
+    /// Memory operations, e.g. `MLOAD`, `MSTORE` or `MCOPY`.
+    Memory,
+    /// Persistent and transient storage operations: `SLOAD`, `SSTORE`, `TLOAD` and `TSTORE`.
+    Storage,
+    /// Control flow operations: `JUMP`, `JUMPI`, `JUMPDEST` and `PC`.
+    Flow,
+    /// `LOG0` to `LOG4`.
+    Log,
+    /// Message calls: `CALL`, `CALLCODE`, `DELEGATECALL` and `STATICCALL`.
+    Call,
+    /// Contract creations: `CREATE` and `CREATE2`.
+    Create,
+    /// Halting operations: `STOP`, `RETURN`, `REVERT`, `INVALID` and `SELFDESTRUCT`.
+    Halt,
+    /// Any opcode not known to this crate.
+    Other,
+}
+
+impl OpcodeCategory {
+    /// Returns the category of the opcode with the given name, as it appears in
+    /// [`StructLog::op`].
+    pub fn of(op: &str) -> Self {
+        match op {
+            "ADD" | "MUL" | "SUB" | "DIV" | "SDIV" | "MOD" | "SMOD" | "ADDMOD" | "MULMOD"
+            | "EXP" | "SIGNEXTEND" => Self::Arithmetic,
+            "LT" | "GT" | "SLT" | "SGT" | "EQ" | "ISZERO" | "AND" | "OR" | "XOR" | "NOT"
+            | "BYTE" | "SHL" | "SHR" | "SAR" => Self::Bitwise,
+            "KECCAK256" | "SHA3" => Self::Hashing,
+            "ADDRESS" | "BALANCE" | "ORIGIN" | "CALLER" | "CALLVALUE" | "CALLDATALOAD"
+            | "CALLDATASIZE" | "CALLDATACOPY" | "CODESIZE" | "CODECOPY" | "GASPRICE"
+            | "EXTCODESIZE" | "EXTCODECOPY" | "RETURNDATASIZE" | "RETURNDATACOPY"
+            | "EXTCODEHASH" | "SELFBALANCE" | "GAS" => Self::Environment,
+            "BLOCKHASH" | "COINBASE" | "TIMESTAMP" | "NUMBER" | "DIFFICULTY" | "PREVRANDAO"
+            | "RANDOM" | "GASLIMIT" | "CHAINID" | "BASEFEE" | "BLOBHASH" | "BLOBBASEFEE" => {
+                Self::Block
+            }
+            "POP" => Self::Stack,
+            "MLOAD" | "MSTORE" | "MSTORE8" | "MSIZE" | "MCOPY" => Self::Memory,
+            "SLOAD" | "SSTORE" | "TLOAD" | "TSTORE" => Self::Storage,
+            "JUMP" | "JUMPI" | "JUMPDEST" | "PC" => Self::Flow,
+            "CALL" | "CALLCODE" | "DELEGATECALL" | "STATICCALL" => Self::Call,
+            "CREATE" | "CREATE2" => Self::Create,
+            "STOP" | "RETURN" | "REVERT" | "INVALID" | "SELFDESTRUCT" => Self::Halt,
+            _ if op.starts_with("PUSH") || op.starts_with("DUP") || op.starts_with("SWAP") => {
+                Self::Stack
+            }
+            _ if op.starts_with("LOG") => Self::Log,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Gas accounting of a traced transaction.
+///
+/// Created from a struct log trace with [`GasReport::from_struct_logs`], or from a call trace
+/// with [`GasReport::from_call_frame`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GasReport {
+    /// The gas limit of the execution.
+    pub gas_limit: u64,
+    /// The gas used by the execution, after refunds.
+    pub gas_used: u64,
+    /// The gas refunded at the end of the execution, if known.
+    ///
+    /// Call traces do not include the refund, so this is always `None` for them.
+    pub refund: Option<u64>,
+    /// The gas spent by the opcodes of each category.
+    ///
+    /// This is only available for struct log traces, and excludes the intrinsic gas of the
+    /// transaction. The gas of calls and creations is the overhead of the operation itself. The
+    /// gas spent by the callee is attributed to the categories of its own opcodes.
+    pub categories: BTreeMap<OpcodeCategory, u64>,
+}
+
+impl GasReport {
+    /// Creates the report of a struct log trace, returned by the default tracer, for a
+    /// transaction with the given gas limit.
+    pub fn from_struct_logs(frame: &DefaultFrame, gas_limit: u64) -> Self {
+        let logs = &frame.struct_logs;
+        let mut categories = BTreeMap::new();
+        for (i, log) in logs.iter().enumerate() {
+            *categories.entry(OpcodeCategory::of(&log.op)).or_default() += step_cost(logs, i);
+        }
+
+        // The gas left after the last step is what remains of the limit before the refund.
+        let remaining = logs.last().map_or(0, |log| log.gas.saturating_sub(log.gas_cost));
+        let refund = if logs.is_empty() {
+            0
+        } else {
+            gas_limit.saturating_sub(remaining).saturating_sub(frame.gas)
+        };
+
+        Self { gas_limit, gas_used: frame.gas, refund: Some(refund), categories }
+    }
+
+    /// Creates the report of a top-level call tracer frame.
+    ///
+    /// The per-category breakdown is not available from call traces.
+    pub fn from_call_frame(frame: &CallFrame) -> Self {
+        Self {
+            gas_limit: frame.gas.saturating_to(),
+            gas_used: frame.gas_used.saturating_to(),
+            refund: None,
+            categories: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the gas that was left unused out of the gas limit.
+    pub const fn gas_unused(&self) -> u64 {
+        self.gas_limit.saturating_sub(self.gas_used)
+    }
+
+    /// Returns the ratio of the gas used to the gas limit, between `0.0` and `1.0`.
+    pub fn gas_used_ratio(&self) -> f64 {
+        if self.gas_limit == 0 {
+            return 0.0;
+        }
+        self.gas_used as f64 / self.gas_limit as f64
+    }
+
+    /// Returns the gas spent by the opcodes of the given category.
+    pub fn category_gas(&self, category: OpcodeCategory) -> u64 {
+        self.categories.get(&category).copied().unwrap_or_default()
+    }
+}
+
+/// Returns the gas actually spent by the step at index `i`.
+///
+/// The reported `gas_cost` of a call includes the gas forwarded to the callee. When the callee is
+/// traced, the cost is instead the gas consumed across the call minus the gas consumed by the
+/// steps of the callee.
+fn step_cost(logs: &[StructLog], i: usize) -> u64 {
+    let log = &logs[i];
+    let Some(next) = logs.get(i + 1) else { return log.gas_cost };
+    if next.depth == log.depth {
+        return log.gas.saturating_sub(next.gas);
+    }
+    if next.depth < log.depth {
+        return log.gas_cost;
+    }
+
+    let Some(ret) = logs[i + 1..].iter().position(|l| l.depth <= log.depth).map(|pos| i + 1 + pos)
+    else {
+        return log.gas_cost;
+    };
+    let last = &logs[ret - 1];
+    let callee_spent = next.gas.saturating_sub(last.gas.saturating_sub(last.gas_cost));
+    log.gas.saturating_sub(logs[ret].gas).saturating_sub(callee_spent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+
+    fn step(op: &str, gas: u64, gas_cost: u64, depth: u64) -> StructLog {
+        StructLog { op: op.to_string(), gas, gas_cost, depth, ..Default::default() }
+    }
+
+    #[test]
+    fn categories() {
+        assert_eq!(OpcodeCategory::of("PUSH32"), OpcodeCategory::Stack);
+        assert_eq!(OpcodeCategory::of("DUP1"), OpcodeCategory::Stack);
+        assert_eq!(OpcodeCategory::of("LOG2"), OpcodeCategory::Log);
+        assert_eq!(OpcodeCategory::of("SSTORE"), OpcodeCategory::Storage);
+        assert_eq!(OpcodeCategory::of("opcode 0xfe not defined"), OpcodeCategory::Other);
+    }
+
+    #[test]
+    fn struct_logs_report() {
+        // 21000 intrinsic gas out of a 100000 limit, then a call with an overhead of 700 gas to a
+        // callee spending 5003 gas, and a refund of 4800 for clearing a slot.
+        let mut sstore = step("SSTORE", 28_997, 5000, 2);
+        sstore.refund_counter = Some(4800);
+        let mut stop = step("STOP", 73_294, 0, 1);
+        stop.refund_counter = Some(4800);
+        let frame = DefaultFrame {
+            gas: 21_906,
+            struct_logs: vec![
+                step("PUSH1", 79_000, 3, 1),
+                step("CALL", 78_997, 51_100, 1),
+                step("PUSH1", 29_000, 3, 2),
+                sstore,
+                step("STOP", 23_997, 0, 2),
+                stop,
+            ],
+            ..Default::default()
+        };
+
+        let report = GasReport::from_struct_logs(&frame, 100_000);
+        assert_eq!(report.category_gas(OpcodeCategory::Stack), 6);
+        assert_eq!(report.category_gas(OpcodeCategory::Storage), 5000);
+        assert_eq!(report.category_gas(OpcodeCategory::Call), 700);
+        assert_eq!(report.refund, Some(4800));
+        assert_eq!(report.gas_unused(), 78_094);
+    }
+
+    #[test]
+    fn call_frame_report() {
+        let frame = CallFrame {
+            gas: U256::from(100_000),
+            gas_used: U256::from(25_000),
+            ..Default::default()
+        };
+        let report = GasReport::from_call_frame(&frame);
+        assert_eq!(report.refund, None);
+        assert_eq!(report.gas_unused(), 75_000);
+        assert_eq!(report.gas_used_ratio(), 0.25);
+    }
+}
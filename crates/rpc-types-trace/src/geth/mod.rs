@@ -8,6 +8,7 @@ use std::{collections::BTreeMap, time::Duration};
 
 // re-exports
 pub use self::{
+    access_list::AccessListBuilder,
     call::{CallConfig, CallFrame, CallLogFrame},
     four_byte::FourByteFrame,
     noop::NoopFrame,
@@ -17,8 +18,10 @@ pub use self::{
     },
 };
 
+pub mod access_list;
 pub mod call;
 pub mod four_byte;
+pub mod gas;
 pub mod mux;
 pub mod noop;
 pub mod pre_state;
@@ -123,6 +126,19 @@ pub enum GethTrace {
     JS(serde_json::Value),
 }
 
+impl GethTrace {
+    /// Decodes the response of a custom javascript tracer into `T`.
+    ///
+    /// Since responses deserialize untagged, the output of a js tracer may have been matched as
+    /// another variant, in which case it is re-serialized before being decoded.
+    pub fn try_into_js_response<T: DeserializeOwned>(self) -> Result<T, serde_json::Error> {
+        match self {
+            GethTrace::JS(value) => serde_json::from_value(value),
+            trace => serde_json::from_value(serde_json::to_value(trace)?),
+        }
+    }
+}
+
 impl From<DefaultFrame> for GethTrace {
     fn from(value: DefaultFrame) -> Self {
         GethTrace::Default(value)
@@ -268,6 +284,24 @@ impl From<serde_json::Value> for GethDebugTracerConfig {
     }
 }
 
+impl From<CallConfig> for GethDebugTracerConfig {
+    fn from(value: CallConfig) -> Self {
+        GethDebugTracerConfig(serde_json::to_value(value).expect("is serializable"))
+    }
+}
+
+impl From<PreStateConfig> for GethDebugTracerConfig {
+    fn from(value: PreStateConfig) -> Self {
+        GethDebugTracerConfig(serde_json::to_value(value).expect("is serializable"))
+    }
+}
+
+impl From<MuxConfig> for GethDebugTracerConfig {
+    fn from(value: MuxConfig) -> Self {
+        GethDebugTracerConfig(serde_json::to_value(value).expect("is serializable"))
+    }
+}
+
 /// Bindings for additional `debug_traceTransaction` options
 ///
 /// See <https://geth.ethereum.org/docs/rpc/ns-debug#debug_tracetransaction>
@@ -299,12 +333,48 @@ pub struct GethDebugTracingOptions {
 }
 
 impl GethDebugTracingOptions {
+    /// Options for the `callTracer` with the given [CallConfig].
+    pub fn call_tracer(config: CallConfig) -> Self {
+        Self::default()
+            .with_tracer(GethDebugBuiltInTracerType::CallTracer.into())
+            .call_config(config)
+    }
+
+    /// Options for the `prestateTracer` with the given [PreStateConfig].
+    pub fn prestate_tracer(config: PreStateConfig) -> Self {
+        Self::default()
+            .with_tracer(GethDebugBuiltInTracerType::PreStateTracer.into())
+            .prestate_config(config)
+    }
+
+    /// Options for the `muxTracer` running the tracers of the given [MuxConfig].
+    pub fn mux_tracer(config: MuxConfig) -> Self {
+        Self::default().with_tracer(GethDebugBuiltInTracerType::MuxTracer.into()).mux_config(config)
+    }
+
+    /// Options for a custom javascript tracer with the given source code.
+    ///
+    /// The config of the tracer can be set with [Self::with_tracer_config], and is passed to the
+    /// `setup` function of the tracer object.
+    pub fn js_tracer(code: impl Into<String>) -> Self {
+        Self::default().with_tracer(GethDebugTracerType::JsTracer(code.into()))
+    }
+
     /// Sets the tracer to use
     pub fn with_tracer(mut self, tracer: GethDebugTracerType) -> Self {
         self.tracer = Some(tracer);
         self
     }
 
+    /// Sets an arbitrary config for the tracer, such as the config of a custom javascript tracer.
+    pub fn with_tracer_config<C: Serialize>(
+        mut self,
+        config: C,
+    ) -> Result<Self, serde_json::Error> {
+        self.tracer_config = GethDebugTracerConfig(serde_json::to_value(config)?);
+        Ok(self)
+    }
+
     /// Sets the timeout to use for tracing
     pub fn with_timeout(mut self, duration: Duration) -> Self {
         self.timeout = Some(format!("{}ms", duration.as_millis()));
@@ -324,6 +394,12 @@ impl GethDebugTracingOptions {
             GethDebugTracerConfig(serde_json::to_value(config).expect("is serializable"));
         self
     }
+
+    /// Configures a [MuxConfig]
+    pub fn mux_config(mut self, config: MuxConfig) -> Self {
+        self.tracer_config = config.into();
+        self
+    }
 }
 
 /// Default tracing options for the struct looger.
@@ -628,4 +704,58 @@ mod tests {
         let val = serde_json::from_str::<serde_json::Value>(s).unwrap();
         similar_asserts::assert_eq!(val, de);
     }
+
+    #[test]
+    fn test_js_tracer_options() {
+        #[derive(Serialize)]
+        struct Config {
+            target: &'static str,
+        }
+
+        let code = "{data: [], step: function(log) {}, result: function() { return this.data; }}";
+        let opts = GethDebugTracingOptions::js_tracer(code)
+            .with_tracer_config(Config { target: "0xdead" })
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&opts).unwrap(),
+            serde_json::json!({ "tracer": code, "tracerConfig": { "target": "0xdead" } })
+        );
+    }
+
+    #[test]
+    fn test_builtin_tracer_options() {
+        let opts = GethDebugTracingOptions::call_tracer(CallConfig::default().only_top_call());
+        assert_eq!(
+            serde_json::to_value(&opts).unwrap(),
+            serde_json::json!({ "tracer": "callTracer", "tracerConfig": { "onlyTopCall": true } })
+        );
+
+        let opts = GethDebugTracingOptions::mux_tracer(MuxConfig::default().with_tracer(
+            GethDebugBuiltInTracerType::PreStateTracer,
+            Some(PreStateConfig { diff_mode: Some(true) }.into()),
+        ));
+        assert_eq!(
+            serde_json::to_value(&opts).unwrap(),
+            serde_json::json!({
+                "tracer": "muxTracer",
+                "tracerConfig": { "prestateTracer": { "diffMode": true } }
+            })
+        );
+    }
+
+    #[test]
+    fn test_js_response() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Opcodes {
+            count: u64,
+        }
+
+        // The response is matched as a 4byte trace, which has the same shape.
+        let trace: GethTrace = serde_json::from_str(r#"{"count": 3}"#).unwrap();
+        assert!(matches!(trace, GethTrace::FourByteTracer(_)));
+        assert_eq!(trace.try_into_js_response::<Opcodes>().unwrap(), Opcodes { count: 3 });
+
+        let trace = GethTrace::JS(serde_json::json!({ "count": 5 }));
+        assert_eq!(trace.try_into_js_response::<Opcodes>().unwrap(), Opcodes { count: 5 });
+    }
 }
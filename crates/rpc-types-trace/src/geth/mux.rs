@@ -9,6 +9,18 @@ use std::collections::HashMap;
 #[serde(rename_all = "camelCase")]
 pub struct MuxConfig(pub HashMap<GethDebugBuiltInTracerType, Option<GethDebugTracerConfig>>);
 
+impl MuxConfig {
+    /// Adds a tracer to run, with an optional config.
+    pub fn with_tracer(
+        mut self,
+        tracer: GethDebugBuiltInTracerType,
+        config: Option<GethDebugTracerConfig>,
+    ) -> Self {
+        self.0.insert(tracer, config);
+        self
+    }
+}
+
 /// A `muxTracer` frame response that contains the results of multiple tracers
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MuxFrame(pub HashMap<GethDebugBuiltInTracerType, GethTrace>);
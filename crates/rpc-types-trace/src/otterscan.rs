@@ -39,8 +39,11 @@ pub struct TraceEntry {
     pub depth: u32,
     pub from: Address,
     pub to: Address,
-    pub value: U256,
+    /// The transferred value, `None` for static and delegate calls.
+    pub value: Option<U256>,
     pub input: Bytes,
+    #[serde(default)]
+    pub output: Bytes,
 }
 
 /// Internal issuance struct for `BlockDetails` struct
@@ -175,4 +178,33 @@ mod tests {
 
         let _receipt: OtsTransactionReceipt = serde_json::from_str(s).unwrap();
     }
+
+    #[test]
+    fn test_otterscan_trace_entry() {
+        let s = r#"[
+      {
+        "type": "CALL",
+        "depth": 0,
+        "from": "0x793abeea78d94c14b884a56788f549836a35db65",
+        "to": "0x06450dee7fd2fb8e39061434babcfc05599a6fb8",
+        "value": "0x0",
+        "input": "0xa9059cbb",
+        "output": "0x"
+      },
+      {
+        "type": "STATICCALL",
+        "depth": 1,
+        "from": "0x06450dee7fd2fb8e39061434babcfc05599a6fb8",
+        "to": "0x0000000000000000000000000000000000000001",
+        "value": null,
+        "input": "0x",
+        "output": "0x01"
+      }
+    ]"#;
+
+        let entries: Vec<TraceEntry> = serde_json::from_str(s).unwrap();
+        assert_eq!(entries[0].value, Some(U256::ZERO));
+        assert_eq!(entries[1].value, None);
+        assert_eq!(entries[1].output, Bytes::from_static(&[1]));
+    }
 }
@@ -1,3 +1,4 @@
+use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_primitives::{Address, Bytes, B256, B512, U256, U64};
 use alloy_serde::storage::JsonStorageKey;
 use serde::{Deserialize, Serialize};
@@ -9,6 +10,29 @@ pub struct AccountInfo {
     pub name: String,
 }
 
+/// The state of an account, as returned by `eth_getAccount`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    /// The account balance.
+    pub balance: U256,
+    /// The account nonce.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub nonce: u64,
+    /// The hash of the code of the account.
+    pub code_hash: B256,
+    /// The root of the storage trie of the account, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_root: Option<B256>,
+}
+
+impl Account {
+    /// Returns `true` if the account has code, i.e. if it is a contract.
+    pub fn has_code(&self) -> bool {
+        self.code_hash != KECCAK_EMPTY
+    }
+}
+
 /// Data structure with proof for one single storage-entry
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -69,6 +93,23 @@ pub struct RecoveredAccount {
     pub is_valid_for_current_chain: bool,
 }
 
+#[test]
+fn test_account() {
+    let response = r#"{
+        "codeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        "storageRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+        "balance":"0x1bc16d674ec80000",
+        "nonce":"0x3"
+    }"#;
+    let account = serde_json::from_str::<Account>(response).unwrap();
+    assert_eq!(account.nonce, 3);
+    assert_eq!(account.balance, U256::from(2_000_000_000_000_000_000u128));
+    assert!(!account.has_code());
+    assert!(account.storage_root.is_some());
+    let value = serde_json::to_value(account).unwrap();
+    assert_eq!(serde_json::from_value::<Account>(value).unwrap(), account);
+}
+
 #[test]
 fn test_eip_1186_account_without_storage_proof() {
     let response = r#"{
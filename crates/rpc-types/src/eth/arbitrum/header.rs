@@ -0,0 +1,35 @@
+use crate::Header;
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Block header returned by Arbitrum nodes.
+///
+/// Arbitrum blocks are produced by the sequencer, and record the L1 block they were produced at
+/// and the state of the outbox of L2 to L1 messages.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArbitrumHeader {
+    /// The Ethereum header fields.
+    #[serde(flatten)]
+    pub inner: Header,
+    /// The number of the L1 block this block was produced at.
+    ///
+    /// This is the value returned by the `NUMBER` opcode on Arbitrum, while the `number` field of
+    /// the header is the L2 block number.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub l1_block_number: u64,
+    /// The number of L2 to L1 messages sent so far.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub send_count: u64,
+    /// The root of the merkle accumulator of L2 to L1 messages.
+    pub send_root: B256,
+}
+
+impl Deref for ArbitrumHeader {
+    type Target = Header;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
@@ -0,0 +1,116 @@
+//! Arbitrum Nitro specific types.
+//!
+//! Arbitrum extends the Ethereum RPC objects with the L1 context of their block, and with the
+//! transaction types created by the rollup for L1 to L2 messages and retryable tickets. These
+//! types cannot be signed by users, so they only appear in responses.
+
+use alloy_eips::eip2718::Eip2718Error;
+use std::fmt;
+
+mod header;
+pub use header::ArbitrumHeader;
+
+mod receipt;
+pub use receipt::ArbitrumTransactionReceipt;
+
+mod request;
+pub use request::ArbitrumTransactionRequest;
+
+mod transaction;
+pub use transaction::ArbitrumTransaction;
+
+/// Arbitrum `TransactionType` flags.
+///
+/// EIP-4844 blob transactions are not supported.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ArbitrumTxType {
+    /// Legacy transaction type.
+    Legacy = 0,
+    /// EIP-2930 transaction type.
+    Eip2930 = 1,
+    /// EIP-1559 transaction type.
+    Eip1559 = 2,
+    /// Deposit of ETH from L1.
+    Deposit = 0x64,
+    /// Unsigned transaction sent from L1 by an externally owned account.
+    Unsigned = 0x65,
+    /// Unsigned transaction sent from L1 by a contract.
+    Contract = 0x66,
+    /// Redemption of a retryable ticket.
+    Retry = 0x68,
+    /// Creation of a retryable ticket.
+    SubmitRetryable = 0x69,
+    /// Internal transaction of ArbOS, such as the start of a block.
+    Internal = 0x6a,
+    /// Legacy transaction from the pre-Nitro chain.
+    ArbitrumLegacy = 0x78,
+}
+
+impl ArbitrumTxType {
+    /// Returns true if transactions of this type are created by the rollup instead of being
+    /// signed by a user.
+    pub const fn is_system(&self) -> bool {
+        !matches!(self, Self::Legacy | Self::Eip2930 | Self::Eip1559 | Self::ArbitrumLegacy)
+    }
+}
+
+impl From<ArbitrumTxType> for u8 {
+    fn from(value: ArbitrumTxType) -> Self {
+        value as u8
+    }
+}
+
+impl fmt::Display for ArbitrumTxType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Legacy => write!(f, "Legacy"),
+            Self::Eip2930 => write!(f, "EIP-2930"),
+            Self::Eip1559 => write!(f, "EIP-1559"),
+            Self::Deposit => write!(f, "Deposit"),
+            Self::Unsigned => write!(f, "Unsigned"),
+            Self::Contract => write!(f, "Contract"),
+            Self::Retry => write!(f, "Retry"),
+            Self::SubmitRetryable => write!(f, "SubmitRetryable"),
+            Self::Internal => write!(f, "Internal"),
+            Self::ArbitrumLegacy => write!(f, "ArbitrumLegacy"),
+        }
+    }
+}
+
+impl TryFrom<u8> for ArbitrumTxType {
+    type Error = Eip2718Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Legacy,
+            1 => Self::Eip2930,
+            2 => Self::Eip1559,
+            0x64 => Self::Deposit,
+            0x65 => Self::Unsigned,
+            0x66 => Self::Contract,
+            0x68 => Self::Retry,
+            0x69 => Self::SubmitRetryable,
+            0x6a => Self::Internal,
+            0x78 => Self::ArbitrumLegacy,
+            _ => return Err(Eip2718Error::UnexpectedType(value)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tx_type_roundtrip() {
+        for ty in 0..=u8::MAX {
+            if let Ok(tx_type) = ArbitrumTxType::try_from(ty) {
+                assert_eq!(u8::from(tx_type), ty);
+            }
+        }
+        assert!(ArbitrumTxType::SubmitRetryable.is_system());
+        assert!(!ArbitrumTxType::Eip1559.is_system());
+        assert!(ArbitrumTxType::try_from(3).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{Log, TransactionReceipt};
+use alloy_consensus::AnyReceiptEnvelope;
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Transaction receipt returned by Arbitrum nodes.
+///
+/// The receipt is generic over [`AnyReceiptEnvelope`], since receipts of the Arbitrum
+/// transaction types have no Ethereum envelope.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArbitrumTransactionReceipt {
+    /// The Ethereum receipt fields.
+    #[serde(flatten)]
+    pub inner: TransactionReceipt<AnyReceiptEnvelope<Log>>,
+    /// The part of [`gas_used`](TransactionReceipt::gas_used) paying for the posting of the
+    /// transaction to L1.
+    #[serde(rename = "gasUsedForL1", with = "alloy_serde::num::u128_via_ruint")]
+    pub gas_used_for_l1: u128,
+    /// The number of the L1 block the transaction was included at.
+    #[serde(rename = "l1BlockNumber", with = "alloy_serde::num::u64_via_ruint")]
+    pub l1_block_number: u64,
+}
+
+impl ArbitrumTransactionReceipt {
+    /// Returns the gas used by the L2 execution of the transaction, excluding the L1 component.
+    pub const fn gas_used_for_l2(&self) -> u128 {
+        self.inner.gas_used.saturating_sub(self.gas_used_for_l1)
+    }
+}
+
+impl Deref for ArbitrumTransactionReceipt {
+    type Target = TransactionReceipt<AnyReceiptEnvelope<Log>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_receipt() {
+        // The receipt of the transaction of `transaction::tests::deserialize_submit_retryable`, in
+        // the `eth_getTransactionReceipt` format of Nitro nodes. The values are made up, this is
+        // not the response of a node.
+        let receipt = r#"{"blockHash":"0x8e1a2ccd5c2b5d9a975d9fb17ba0c7519b2a2dc5c1e3d92fa8cc1bb67fc67e1c","blockNumber":"0xbdb3d0d","contractAddress":null,"cumulativeGasUsed":"0xd8ae6","effectiveGasPrice":"0x989680","from":"0xe65a2c8dd8dbd6ec4c6c784ccba6a747c9451bc3","gasUsed":"0x2fc4d","gasUsedForL1":"0xc6f6","l1BlockNumber":"0x12a8e4a","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","status":"0x1","to":"0x1b02da8cb0d097eb8d57a175b88c7d8b47997506","transactionHash":"0x502cd81c8a84d0b0f3e68ed87740c31ad131ea6abd9e0f06ec7401e244e2769d","transactionIndex":"0x3","type":"0x69"}"#;
+
+        let receipt = serde_json::from_str::<ArbitrumTransactionReceipt>(receipt).unwrap();
+        assert_eq!(receipt.inner.inner.r#type, 0x69);
+        assert_eq!(receipt.gas_used_for_l1, 0xc6f6);
+        assert_eq!(receipt.gas_used_for_l2(), 0x2fc4d - 0xc6f6);
+        assert_eq!(receipt.l1_block_number, 0x12a8e4a);
+
+        let serialized = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(serialized["gasUsedForL1"], "0xc6f6");
+        assert_eq!(
+            serde_json::from_value::<ArbitrumTransactionReceipt>(serialized).unwrap(),
+            receipt
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use super::ArbitrumTxType;
+use crate::Transaction;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Transaction object returned by Arbitrum nodes.
+///
+/// The fields of the L1 to L2 message and retryable ticket transactions are captured here,
+/// instead of in [`Transaction::other`]. Each of them is only set for some of the
+/// [`ArbitrumTxType`]s.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArbitrumTransaction {
+    /// The Ethereum transaction fields.
+    #[serde(flatten)]
+    pub inner: Transaction,
+    /// The identifier of the L1 message, for deposit, contract and submit retryable
+    /// transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<B256>,
+    /// The retryable ticket redeemed by a retry transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket_id: Option<B256>,
+    /// The maximum refund of a retry transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_refund: Option<U256>,
+    /// The refund of the submission fee of a retry transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub submission_fee_refund: Option<U256>,
+    /// The address refunded the excess fees of a retryable ticket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund_to: Option<Address>,
+    /// The L1 base fee when the retryable ticket was submitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub l1_base_fee: Option<U256>,
+    /// The ETH deposited with the retryable ticket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit_value: Option<U256>,
+    /// The recipient of the retryable ticket redemption.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_to: Option<Address>,
+    /// The value of the retryable ticket redemption.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_value: Option<U256>,
+    /// The calldata of the retryable ticket redemption.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_data: Option<Bytes>,
+    /// The address that can cancel the retryable ticket, and receives its call value if it
+    /// expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beneficiary: Option<Address>,
+    /// The maximum fee paid for the submission of the retryable ticket.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_submission_fee: Option<U256>,
+}
+
+impl ArbitrumTransaction {
+    /// Returns the [`ArbitrumTxType`] of the transaction, or `None` if it is unknown.
+    pub fn tx_type(&self) -> Option<ArbitrumTxType> {
+        self.inner.transaction_type.unwrap_or_default().try_into().ok()
+    }
+
+    /// Returns true if the transaction was created by the rollup instead of being signed by a
+    /// user.
+    pub fn is_system(&self) -> bool {
+        self.tx_type().is_some_and(|ty| ty.is_system())
+    }
+}
+
+impl Deref for ArbitrumTransaction {
+    type Target = Transaction;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for ArbitrumTransaction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn deserialize_submit_retryable() {
+        // A submit retryable transaction in the `eth_getTransactionByHash` format of Nitro nodes.
+        // The values are made up, this is not the response of a node.
+        let rpc_tx = r#"{"beneficiary":"0xe65a2c8dd8dbd6ec4c6c784ccba6a747c9451bc3","blockHash":"0x8e1a2ccd5c2b5d9a975d9fb17ba0c7519b2a2dc5c1e3d92fa8cc1bb67fc67e1c","blockNumber":"0xbdb3d0d","chainId":"0xa4b1","depositValue":"0x2386f26fc10000","from":"0xf75a2c8dd8dbd6ec4c6c784ccba6a747c9452cd4","gas":"0x2fc4d","gasPrice":"0x989680","hash":"0x502cd81c8a84d0b0f3e68ed87740c31ad131ea6abd9e0f06ec7401e244e2769d","input":"0x","l1BaseFee":"0x6fc23ac00","maxFeePerGas":"0x989680","maxSubmissionFee":"0x1c6bf526340","nonce":"0x0","refundTo":"0xe65a2c8dd8dbd6ec4c6c784ccba6a747c9451bc3","requestId":"0x00000000000000000000000000000000000000000000000000000000001a6c0e","retryData":"0x","retryTo":"0x1b02da8cb0d097eb8d57a175b88c7d8b47997506","retryValue":"0x2386f26fc10000","to":"0x000000000000000000000000000000000000006e","transactionIndex":"0x3","type":"0x69","value":"0x0"}"#;
+
+        let tx = serde_json::from_str::<ArbitrumTransaction>(rpc_tx).unwrap();
+        assert_eq!(tx.tx_type(), Some(ArbitrumTxType::SubmitRetryable));
+        assert!(tx.is_system());
+        assert!(tx.inner.signature.is_none());
+        assert!(tx.inner.other.is_empty());
+        assert_eq!(tx.retry_to, Some(address!("1b02da8cb0d097eb8d57a175b88c7d8b47997506")));
+        assert_eq!(tx.max_submission_fee, Some(U256::from(0x1c6bf526340u64)));
+
+        let serialized = serde_json::to_value(&tx).unwrap();
+        assert_eq!(serde_json::from_value::<ArbitrumTransaction>(serialized).unwrap(), tx);
+    }
+}
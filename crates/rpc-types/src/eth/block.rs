@@ -2,13 +2,14 @@
 
 #![allow(unknown_lints, non_local_definitions)]
 
-use crate::{other::OtherFields, Transaction, Withdrawal};
+use crate::{other::OtherFields, ConversionError, Transaction, Withdrawal};
+use alloy_consensus::{Request, TxEnvelope};
 pub use alloy_eips::{
     calc_blob_gasprice, calc_excess_blob_gas, BlockHashOrNumber, BlockId, BlockNumHash,
     BlockNumberOrTag, ForkBlock, RpcBlockHash,
 };
 use alloy_primitives::{Address, Bloom, Bytes, B256, B64, U256, U64};
-use serde::{ser::Error, Deserialize, Serialize, Serializer};
+use serde::{ser::Error, Deserialize, Deserializer, Serialize, Serializer};
 use std::{collections::BTreeMap, ops::Deref};
 
 /// Block representation
@@ -19,13 +20,15 @@ pub struct Block<T = Transaction> {
     #[serde(flatten)]
     pub header: Header,
     /// Uncles' hashes.
-    #[serde(default)]
+    #[serde(default, deserialize_with = "alloy_serde::lenient::null_as_default")]
     pub uncles: Vec<B256>,
     /// Block Transactions. In the case of an uncle block, this field is not included in RPC
     /// responses, and when deserialized, it will be set to [BlockTransactions::Uncle].
     #[serde(
         default = "BlockTransactions::uncle",
-        skip_serializing_if = "BlockTransactions::is_uncle"
+        skip_serializing_if = "BlockTransactions::is_uncle",
+        deserialize_with = "alloy_serde::lenient::null_as_default",
+        bound(deserialize = "T: Deserialize<'de>")
     )]
     pub transactions: BlockTransactions<T>,
     /// Integer the size of this block in bytes.
@@ -34,6 +37,11 @@ pub struct Block<T = Transaction> {
     /// Withdrawals in the block.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub withdrawals: Option<Vec<Withdrawal>>,
+    /// [EIP-7685] requests in the block, since Prague.
+    ///
+    /// [EIP-7685]: https://eips.ethereum.org/EIPS/eip-7685
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests: Option<Vec<Request>>,
     /// Support for arbitrary additional fields.
     #[serde(flatten)]
     pub other: OtherFields,
@@ -69,6 +77,9 @@ pub struct Header {
     /// Logs bloom
     pub logs_bloom: Bloom,
     /// Difficulty
+    ///
+    /// This is zero after the merge, and may be omitted by non-ethereum networks.
+    #[serde(default)]
     pub difficulty: U256,
     /// Block number
     #[serde(default, with = "alloy_serde::num::u64_opt_via_ruint")]
@@ -83,7 +94,14 @@ pub struct Header {
     #[serde(default, with = "alloy_serde::num::u64_via_ruint")]
     pub timestamp: u64,
     /// Total difficulty
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ///
+    /// Nodes stop returning this after the merge. It is parsed leniently, as some return it as a
+    /// decimal string or a JSON number.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "alloy_serde::ttd::deserialize_json_ttd_opt"
+    )]
     pub total_difficulty: Option<U256>,
     /// Extra data
     pub extra_data: Bytes,
@@ -101,7 +119,13 @@ pub struct Header {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mix_hash: Option<B256>,
     /// Nonce
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ///
+    /// Some nodes return this as a quantity, e.g. `0x0`, rather than as 8 bytes.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_nonce_opt"
+    )]
     pub nonce: Option<B64>,
     /// Base fee per unit of gas (if past London)
     #[serde(
@@ -133,9 +157,44 @@ pub struct Header {
     /// EIP-7685 requests root.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub requests_root: Option<B256>,
+    /// EIP-7685 requests hash, which replaces the requests root on later Prague devnets.
+    ///
+    /// See [`requests_hash`](alloy_eips::eip7685::requests_hash).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_hash: Option<B256>,
 }
 
 impl Header {
+    /// Returns the total difficulty of the chain up to this block, if the node returned it.
+    ///
+    /// Nodes stop returning it after the merge, when it stops changing. See also
+    /// [`Self::is_pre_merge`].
+    pub const fn total_difficulty(&self) -> Option<U256> {
+        self.total_difficulty
+    }
+
+    /// Returns true if this is a proof-of-work block, with a non-zero difficulty.
+    pub fn is_pre_merge(&self) -> bool {
+        !self.difficulty.is_zero()
+    }
+
+    /// Returns the proof-of-work seal of the block, its mix hash and nonce, if this is a
+    /// pre-merge block.
+    pub fn pow_seal(&self) -> Option<(B256, B64)> {
+        if !self.is_pre_merge() {
+            return None;
+        }
+        Some((self.mix_hash?, self.nonce?))
+    }
+
+    /// Returns the `prevRandao` value of the block, if this is a post-merge block.
+    pub fn prev_randao(&self) -> Option<B256> {
+        if self.is_pre_merge() {
+            return None;
+        }
+        self.mix_hash
+    }
+
     /// Returns the blob fee for _this_ block according to the EIP-4844 spec.
     ///
     /// Returns `None` if `excess_blob_gas` is None
@@ -161,6 +220,79 @@ impl Header {
     }
 }
 
+impl Header {
+    /// Computes the hash of the header from its fields, to compare with the [`hash`](Self::hash)
+    /// returned by the node.
+    ///
+    /// Fails if the header lacks a field of the consensus header, such as the number of a pending
+    /// block.
+    pub fn hash_slow(&self) -> Result<B256, ConversionError> {
+        alloy_consensus::Header::try_from(self.clone()).map(|header| header.hash_slow())
+    }
+}
+
+impl TryFrom<Header> for alloy_consensus::Header {
+    type Error = ConversionError;
+
+    fn try_from(header: Header) -> Result<Self, Self::Error> {
+        Ok(Self {
+            parent_hash: header.parent_hash,
+            ommers_hash: header.uncles_hash,
+            beneficiary: header.miner,
+            state_root: header.state_root,
+            transactions_root: header.transactions_root,
+            receipts_root: header.receipts_root,
+            withdrawals_root: header.withdrawals_root,
+            logs_bloom: header.logs_bloom,
+            difficulty: header.difficulty,
+            number: header.number.ok_or(ConversionError::MissingBlockNumber)?,
+            gas_limit: header.gas_limit,
+            gas_used: header.gas_used,
+            timestamp: header.timestamp,
+            mix_hash: header.mix_hash.unwrap_or_default(),
+            nonce: header.nonce.unwrap_or_default(),
+            base_fee_per_gas: header.base_fee_per_gas,
+            blob_gas_used: header.blob_gas_used,
+            excess_blob_gas: header.excess_blob_gas,
+            parent_beacon_block_root: header.parent_beacon_block_root,
+            requests_root: header.requests_root.or(header.requests_hash),
+            extra_data: header.extra_data,
+        })
+    }
+}
+
+impl TryFrom<Block> for alloy_consensus::Block {
+    type Error = ConversionError;
+
+    fn try_from(block: Block) -> Result<Self, Self::Error> {
+        if !block.uncles.is_empty() {
+            return Err(ConversionError::MissingUncleHeaders);
+        }
+        let transactions = match block.transactions {
+            BlockTransactions::Full(transactions) => {
+                transactions.into_iter().map(TxEnvelope::try_from).collect::<Result<_, _>>()?
+            }
+            BlockTransactions::Hashes(hashes) if hashes.is_empty() => Vec::new(),
+            _ => return Err(ConversionError::MissingFullTransactions),
+        };
+        Ok(Self {
+            header: block.header.try_into()?,
+            transactions,
+            ommers: Vec::new(),
+            withdrawals: block.withdrawals,
+            requests: block.requests,
+        })
+    }
+}
+
+/// Deserializes a block nonce, either as 8 bytes or as a quantity.
+fn deserialize_nonce_opt<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<B64>, D::Error> {
+    let nonce = Option::<U64>::deserialize(deserializer)?;
+    Ok(nonce.map(|nonce| B64::from(nonce.to::<u64>())))
+}
+
 /// Block Transactions depending on the boolean attribute of `eth_getBlockBy*`,
 /// or if used by `eth_getUncle*`
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -566,6 +698,7 @@ pub struct BlockOverrides {
 
 #[cfg(test)]
 mod tests {
+    use alloy_primitives::address;
     use arbitrary::Arbitrary;
     use rand::Rng;
 
@@ -625,11 +758,13 @@ mod tests {
                 excess_blob_gas: None,
                 parent_beacon_block_root: None,
                 requests_root: None,
+                requests_hash: None,
             },
             uncles: vec![B256::with_last_byte(17)],
             transactions: vec![B256::with_last_byte(18)].into(),
             size: Some(U256::from(19)),
             withdrawals: Some(vec![]),
+            requests: None,
             other: Default::default(),
         };
         let serialized = serde_json::to_string(&block).unwrap();
@@ -668,11 +803,13 @@ mod tests {
                 excess_blob_gas: None,
                 parent_beacon_block_root: None,
                 requests_root: None,
+                requests_hash: None,
             },
             uncles: vec![],
             transactions: BlockTransactions::Uncle,
             size: Some(U256::from(19)),
             withdrawals: None,
+            requests: None,
             other: Default::default(),
         };
         let serialized = serde_json::to_string(&block).unwrap();
@@ -711,11 +848,13 @@ mod tests {
                 excess_blob_gas: None,
                 parent_beacon_block_root: None,
                 requests_root: None,
+                requests_hash: None,
             },
             uncles: vec![B256::with_last_byte(17)],
             transactions: vec![B256::with_last_byte(18)].into(),
             size: Some(U256::from(19)),
             withdrawals: None,
+            requests: None,
             other: Default::default(),
         };
         let serialized = serde_json::to_string(&block).unwrap();
@@ -733,6 +872,28 @@ mod tests {
         let _overrides = serde_json::from_str::<BlockOverrides>(s).unwrap();
     }
 
+    #[test]
+    fn serde_pre_merge_header() {
+        // Block 46147 of mainnet, with the total difficulty as a decimal string.
+        let s = r#"{"difficulty":"0x2d3a678cddba9b","extraData":"0x","gasLimit":"0x7a1200","gasUsed":"0x5208","hash":"0x4e3a3754410177e6937ef1f84bba68ea139e8d1a2258c5f85db9f1cd715a1bdd","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","miner":"0xe6a7a1d47ff21b6321162aea7c6cb457d5476bca","mixHash":"0x7a60bf2e5812c8dd1d4fe51a2c1707e3afe9a2a9929db0d9746abf4e764b7610","nonce":"0x5b2ab3a0a9a78d","number":"0xb443","parentHash":"0x5a41d0e66b4120775176c09fcf39e7c0520517a13d2b57b18d33d342df038bfc","receiptsRoot":"0xfe70ae4a136d98944951b2123859698d59ad251a381abc9960fa81cae3d0d4a0","sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","stateRoot":"0x4ca4a704423da2813c2b8cbf75eea66589856d9d35bfc380bf0637a9c9b39fe6","timestamp":"0x55d0d804","totalDifficulty":"2209248519207204150","transactionsRoot":"0x4513310fcb9f6f616972a3b948dc5d547f280849a87ebb5af0191f98b87be598"}"#;
+        let header = serde_json::from_str::<Header>(s).unwrap();
+        assert_eq!(header.total_difficulty(), Some(U256::from(2209248519207204150u64)));
+        assert!(header.is_pre_merge());
+        let (_, nonce) = header.pow_seal().unwrap();
+        assert_eq!(nonce, B64::from(0x5b2ab3a0a9a78du64));
+        assert_eq!(header.prev_randao(), None);
+
+        // Post-merge nodes may omit the difficulty and the total difficulty.
+        let mut value = serde_json::to_value(&header).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("difficulty");
+        object.remove("totalDifficulty");
+        let header = serde_json::from_value::<Header>(value).unwrap();
+        assert_eq!(header.total_difficulty(), None);
+        assert!(!header.is_pre_merge());
+        assert_eq!(header.prev_randao(), header.mix_hash);
+    }
+
     #[test]
     fn serde_rich_block() {
         let s = r#"{
@@ -761,6 +922,7 @@ mod tests {
 }"#;
 
         let block = serde_json::from_str::<RichBlock>(s).unwrap();
+        assert_eq!(block.header.hash_slow().unwrap(), block.header.hash.unwrap());
         let serialized = serde_json::to_string(&block).unwrap();
         let block2 = serde_json::from_str::<RichBlock>(&serialized).unwrap();
         assert_eq!(block, block2);
@@ -802,6 +964,17 @@ mod tests {
          }"#;
 
         let block = serde_json::from_str::<Block>(s).unwrap();
+        assert_eq!(block.header.hash_slow().unwrap(), block.header.hash.unwrap());
+        let header = alloy_consensus::Header::try_from(block.header.clone()).unwrap();
+        let encoded = alloy_rlp::encode(&header);
+        assert_eq!(alloy_rlp::decode_exact::<alloy_consensus::Header>(&encoded).unwrap(), header);
+        assert!(matches!(
+            alloy_consensus::Block::try_from(block.clone()),
+            Err(ConversionError::MissingFullTransactions)
+        ));
+        let pending = Header { number: None, ..block.header.clone() };
+        assert!(matches!(pending.hash_slow(), Err(ConversionError::MissingBlockNumber)));
+
         let serialized = serde_json::to_string(&block).unwrap();
         let block2 = serde_json::from_str::<Block>(&serialized).unwrap();
         assert_eq!(block, block2);
@@ -849,4 +1022,71 @@ mod tests {
         let block2 = serde_json::from_str::<Block>(&serialized).unwrap();
         assert_eq!(block, block2);
     }
+
+    #[test]
+    fn serde_block_with_requests() {
+        let s = r#"{
+            "baseFeePerGas":"0x7",
+            "blobGasUsed":"0x0",
+            "difficulty":"0x0",
+            "excessBlobGas":"0x0",
+            "extraData":"0x",
+            "gasLimit":"0x1c9c380",
+            "gasUsed":"0x0",
+            "hash":"0x4c0c84aa4747b6b560fbee6ba3738e81de32a59d22c3a7ba3e254bf4dc4fb016",
+            "logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "miner":"0x0000000000000000000000000000000000000000",
+            "mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000",
+            "nonce":"0x0000000000000000",
+            "number":"0x12",
+            "parentBeaconBlockRoot":"0x0000000000000000000000000000000000000000000000000000000000000000",
+            "parentHash":"0x2bd4cfcbc7429a7d9b7bb6a2dcb8e5a2bd2ed5cc13a6c4ab7e2864c1fca1e869",
+            "receiptsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "requestsHash":"0x5c8d7d8d9c1f4c0d8fd5ab1e1c7e20d4b2b0d6b3d3c0a42f1e6e6ad3b7f4f5a1",
+            "sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+            "size":"0x2c8",
+            "stateRoot":"0x9f4a0d1c8e1e2bb0e9ff2dc4a50b7f9e12c8f0e7c1d3a6b95e3c6e2a9f0b9e11",
+            "timestamp":"0x66a1b2c3",
+            "totalDifficulty":"0x0",
+            "transactions":[],
+            "transactionsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "uncles":[],
+            "withdrawals":[],
+            "withdrawalsRoot":"0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+            "requests":[
+               {
+                  "pubkey":"0x85103a8d2d0f3e5bfd4b4b8de4dd0c3576d7d6b7a1db35a98bbae1dd4e3e32f9b8f0c8e9da19a6ff4c7b0d77c0d6b1a3",
+                  "withdrawalCredentials":"0x00aa0000000000000000000000000000000000000000000000000000000000bb",
+                  "amount":"0x773594000",
+                  "signature":"0xa1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90",
+                  "index":"0x0"
+               },
+               {
+                  "sourceAddress":"0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+                  "validatorPublicKey":"0x85103a8d2d0f3e5bfd4b4b8de4dd0c3576d7d6b7a1db35a98bbae1dd4e3e32f9b8f0c8e9da19a6ff4c7b0d77c0d6b1a3",
+                  "amount":"0x0"
+               },
+               {
+                  "sourceAddress":"0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+                  "sourcePubkey":"0x85103a8d2d0f3e5bfd4b4b8de4dd0c3576d7d6b7a1db35a98bbae1dd4e3e32f9b8f0c8e9da19a6ff4c7b0d77c0d6b1a3",
+                  "targetPubkey":"0xb3a1f2c4d5e6071829304a5b6c7d8e9fa0b1c2d3e4f5061728394a5b6c7d8e9fa0b1c2d3e4f5061728394a5b6c7d8e9f"
+               }
+            ]
+         }"#;
+
+        let block = serde_json::from_str::<Block>(s).unwrap();
+        let requests = block.requests.as_ref().unwrap();
+        assert!(requests[0].is_deposit_request());
+        assert!(requests[1].is_withdrawal_request());
+        let consolidation = requests[2].as_consolidation_request().unwrap();
+        assert_eq!(
+            consolidation.source_address,
+            address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b")
+        );
+        assert!(block.header.requests_hash.is_some());
+        assert_eq!(block.header.requests_root, None);
+        let serialized = serde_json::to_string(&block).unwrap();
+        let block2 = serde_json::from_str::<Block>(&serialized).unwrap();
+        assert_eq!(block, block2);
+    }
 }
@@ -0,0 +1,39 @@
+//! BNB Smart Chain specific types.
+//!
+//! The validators of BNB Smart Chain end each block they produce with system transactions,
+//! calling the system contracts deployed at genesis to distribute the block rewards and to slash
+//! the validators missing their turn. These transactions are signed by the validator, with a zero
+//! gas price, so they are valid Ethereum transactions which can only be told apart with the block
+//! they are in.
+
+use alloy_primitives::Address;
+
+mod request;
+pub use request::BscTransactionRequest;
+
+mod transaction;
+pub use transaction::BscTransaction;
+
+/// Returns true if `address` is a system contract of BNB Smart Chain.
+///
+/// System contracts are deployed at the low addresses from `0x…1000` to `0x…3000`, such as the
+/// validator set contract at `0x…1000` and the stake hub at `0x…2002`.
+pub fn is_system_contract(address: &Address) -> bool {
+    let (prefix, suffix) = address.split_at(18);
+    prefix.iter().all(|&byte| byte == 0)
+        && (0x1000..=0x3000).contains(&u16::from_be_bytes([suffix[0], suffix[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn system_contracts() {
+        assert!(is_system_contract(&address!("0000000000000000000000000000000000001000")));
+        assert!(is_system_contract(&address!("0000000000000000000000000000000000002002")));
+        assert!(!is_system_contract(&address!("0000000000000000000000000000000000000001")));
+        assert!(!is_system_contract(&address!("1000000000000000000000000000000000001000")));
+    }
+}
@@ -0,0 +1,48 @@
+use crate::TransactionRequest;
+use alloy_consensus::{TxEnvelope, TypedTransaction};
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Transaction request for BNB Smart Chain.
+///
+/// BNB Smart Chain has the Ethereum transaction types, so this has the same fields as
+/// [`TransactionRequest`]. It is a distinct type so that it can be built for the BNB Smart Chain
+/// network.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BscTransactionRequest {
+    /// The Ethereum transaction request fields.
+    #[serde(flatten)]
+    pub inner: TransactionRequest,
+}
+
+impl Deref for BscTransactionRequest {
+    type Target = TransactionRequest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for BscTransactionRequest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl From<TransactionRequest> for BscTransactionRequest {
+    fn from(inner: TransactionRequest) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<TypedTransaction> for BscTransactionRequest {
+    fn from(tx: TypedTransaction) -> Self {
+        Self { inner: tx.into() }
+    }
+}
+
+impl From<TxEnvelope> for BscTransactionRequest {
+    fn from(envelope: TxEnvelope) -> Self {
+        Self { inner: envelope.into() }
+    }
+}
@@ -0,0 +1,66 @@
+use super::is_system_contract;
+use crate::Transaction;
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Transaction object returned by BNB Smart Chain nodes.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BscTransaction {
+    /// The Ethereum transaction fields.
+    #[serde(flatten)]
+    pub inner: Transaction,
+}
+
+impl BscTransaction {
+    /// Returns true if this is a system transaction of a block produced by `coinbase`, i.e. the
+    /// `miner` of the block.
+    ///
+    /// As in the Parlia consensus engine, system transactions are sent by the validator that
+    /// produced the block to a [system contract](is_system_contract), with a zero gas price.
+    pub fn is_system(&self, coinbase: Address) -> bool {
+        self.inner.from == coinbase
+            && self.inner.to.as_ref().is_some_and(is_system_contract)
+            && self.inner.gas_price.unwrap_or_default() == 0
+    }
+}
+
+impl Deref for BscTransaction {
+    type Target = Transaction;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for BscTransaction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl From<Transaction> for BscTransaction {
+    fn from(inner: Transaction) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn deserialize_system_transaction() {
+        // A validator depositing the block fees to the validator set contract.
+        let rpc_tx = r#"{"blockHash":"0x6b7a9c1d2e3f40516273849a5b6c7d8e9f0a1b2c3d4e5f60718293a4b5c6d7e8","blockNumber":"0x2625a00","from":"0x72b61c6014342d914470ec7ac2975be345796c2b","gas":"0x7fffffffffffffff","gasPrice":"0x0","hash":"0x1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b1c2d","input":"0xf340fa0100000000000000000000000072b61c6014342d914470ec7ac2975be345796c2b","nonce":"0x8a1c3","to":"0x0000000000000000000000000000000000001000","transactionIndex":"0x9c","value":"0x2386f26fc10000","type":"0x0","chainId":"0x38","v":"0x94","r":"0x5d2e4f6a8b0c1d3e5f7a9b0c2d4e6f8a0b1c3d5e7f9a1b2c4d6e8f0a1b3c5d7e","s":"0x2b4c6d8e0f1a3b5c7d9e1f2a4b6c8d0e2f3a5b7c9d1e3f4a6b8c0d2e4f5a7b9c"}"#;
+        let coinbase = address!("72b61c6014342d914470ec7ac2975be345796c2b");
+
+        let tx = serde_json::from_str::<BscTransaction>(rpc_tx).unwrap();
+        assert!(tx.is_system(coinbase));
+        assert!(!tx.is_system(Address::ZERO));
+
+        let serialized = serde_json::to_value(&tx).unwrap();
+        assert_eq!(serde_json::from_value::<BscTransaction>(serialized).unwrap(), tx);
+    }
+}
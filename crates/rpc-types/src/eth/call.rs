@@ -12,6 +12,20 @@ pub struct Bundle {
     pub block_override: Option<BlockOverrides>,
 }
 
+impl Bundle {
+    /// Sets the block overrides of the calls of the bundle.
+    pub fn with_block_override(mut self, block_override: BlockOverrides) -> Self {
+        self.block_override = Some(block_override);
+        self
+    }
+}
+
+impl From<Vec<TransactionRequest>> for Bundle {
+    fn from(transactions: Vec<TransactionRequest>) -> Self {
+        Self { transactions, block_override: None }
+    }
+}
+
 /// State context for callMany
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
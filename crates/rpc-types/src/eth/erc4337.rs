@@ -0,0 +1,623 @@
+//! Types for the [ERC-4337] bundler RPC namespace.
+//!
+//! The v0.7 entry point packs several fields of a user operation on-chain, such as the gas
+//! limits in the two 16-byte halves of a word. The helpers of [`PackedUserOperation`] convert
+//! between the RPC and on-chain forms, and compute the user operation hash.
+//!
+//! Bundlers submit user operations to the entry point in bundle transactions, and
+//! [`user_operation_receipts`] reconstructs the receipt of each operation from the events of the
+//! bundle receipt.
+//!
+//! [ERC-4337]: https://eips.ethereum.org/EIPS/eip-4337
+
+use crate::{Log, TransactionReceipt};
+use alloy_primitives::{keccak256, Address, BlockHash, Bytes, ChainId, TxHash, B256, U256};
+use alloy_sol_types::{sol, SolEvent};
+use serde::{Deserialize, Serialize};
+
+sol! {
+    /// The events of the entry point, shared by v0.6 and v0.7.
+    #[allow(missing_docs)]
+    interface IEntryPoint {
+        event UserOperationEvent(bytes32 indexed userOpHash, address indexed sender, address indexed paymaster, uint256 nonce, bool success, uint256 actualGasCost, uint256 actualGasUsed);
+        event UserOperationRevertReason(bytes32 indexed userOpHash, address indexed sender, uint256 nonce, bytes revertReason);
+        event BeforeExecution();
+    }
+}
+
+/// A user operation for the v0.6 entry point.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// The account making the operation.
+    pub sender: Address,
+    /// Anti-replay parameter, also used as the salt for first-time account creation.
+    pub nonce: U256,
+    /// The factory address followed by its calldata, or empty if the account already exists.
+    pub init_code: Bytes,
+    /// The data to pass to the sender during the main execution call.
+    pub call_data: Bytes,
+    /// The amount of gas to allocate for the main execution call.
+    pub call_gas_limit: U256,
+    /// The amount of gas to allocate for the verification step.
+    pub verification_gas_limit: U256,
+    /// The amount of gas to pay the bundler for pre-verification execution and calldata.
+    pub pre_verification_gas: U256,
+    /// Maximum fee per gas, similar to EIP-1559 `max_fee_per_gas`.
+    pub max_fee_per_gas: U256,
+    /// Maximum priority fee per gas, similar to EIP-1559 `max_priority_fee_per_gas`.
+    pub max_priority_fee_per_gas: U256,
+    /// The paymaster address followed by its data, or empty if the account pays for itself.
+    pub paymaster_and_data: Bytes,
+    /// The data passed to the account along with the nonce during the verification step.
+    pub signature: Bytes,
+}
+
+/// A user operation for the v0.7 entry point.
+///
+/// This is the unpacked form used over RPC, in which the factory and paymaster fields of the
+/// on-chain `PackedUserOperation` are sent separately.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackedUserOperation {
+    /// The account making the operation.
+    pub sender: Address,
+    /// Anti-replay parameter, also used as the salt for first-time account creation.
+    pub nonce: U256,
+    /// The account factory, only for new accounts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    /// The data for the account factory, only if the factory is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    /// The data to pass to the sender during the main execution call.
+    pub call_data: Bytes,
+    /// The amount of gas to allocate for the main execution call.
+    pub call_gas_limit: U256,
+    /// The amount of gas to allocate for the verification step.
+    pub verification_gas_limit: U256,
+    /// The amount of gas to pay the bundler for pre-verification execution and calldata.
+    pub pre_verification_gas: U256,
+    /// Maximum fee per gas, similar to EIP-1559 `max_fee_per_gas`.
+    pub max_fee_per_gas: U256,
+    /// Maximum priority fee per gas, similar to EIP-1559 `max_priority_fee_per_gas`.
+    pub max_priority_fee_per_gas: U256,
+    /// The paymaster address, or `None` if the account pays for itself.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    /// The amount of gas to allocate for the paymaster validation code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    /// The amount of gas to allocate for the paymaster post-operation code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    /// The data for the paymaster, only if the paymaster is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    /// The data passed to the account along with the nonce during the verification step.
+    pub signature: Bytes,
+}
+
+impl PackedUserOperation {
+    /// Returns the on-chain `initCode`: the factory followed by its data, or empty if the account
+    /// already exists.
+    pub fn init_code(&self) -> Bytes {
+        self.factory_and_data().map(|factory| factory.encode()).unwrap_or_default()
+    }
+
+    /// Returns the factory along with its data, if the factory is set.
+    pub fn factory_and_data(&self) -> Option<FactoryAndData> {
+        Some(FactoryAndData {
+            factory: self.factory?,
+            data: self.factory_data.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Sets the factory and its data, or unsets them if `None`.
+    pub fn set_factory_and_data(&mut self, factory: Option<FactoryAndData>) {
+        let (factory, data) = factory.map(|factory| (factory.factory, factory.data)).unzip();
+        self.factory = factory;
+        self.factory_data = data;
+    }
+
+    /// Returns the on-chain `paymasterAndData`: the paymaster followed by its gas limits and
+    /// data, or empty if the account pays for itself.
+    pub fn paymaster_and_data(&self) -> Bytes {
+        self.paymaster().map(|paymaster| paymaster.encode()).unwrap_or_default()
+    }
+
+    /// Returns the paymaster along with its gas limits and data, if the paymaster is set.
+    ///
+    /// Gas limits above [`u128::MAX`] are capped, as they cannot be packed.
+    pub fn paymaster(&self) -> Option<PaymasterAndData> {
+        Some(PaymasterAndData {
+            paymaster: self.paymaster?,
+            verification_gas_limit: self
+                .paymaster_verification_gas_limit
+                .map_or(0, |limit| limit.saturating_to()),
+            post_op_gas_limit: self
+                .paymaster_post_op_gas_limit
+                .map_or(0, |limit| limit.saturating_to()),
+            data: self.paymaster_data.clone().unwrap_or_default(),
+        })
+    }
+
+    /// Sets the paymaster along with its gas limits and data, or unsets them if `None`.
+    pub fn set_paymaster(&mut self, paymaster: Option<PaymasterAndData>) {
+        self.paymaster = paymaster.as_ref().map(|paymaster| paymaster.paymaster);
+        self.paymaster_verification_gas_limit =
+            paymaster.as_ref().map(|paymaster| U256::from(paymaster.verification_gas_limit));
+        self.paymaster_post_op_gas_limit =
+            paymaster.as_ref().map(|paymaster| U256::from(paymaster.post_op_gas_limit));
+        self.paymaster_data = paymaster.map(|paymaster| paymaster.data);
+    }
+
+    /// Returns the on-chain `accountGasLimits`: the verification gas limit followed by the call
+    /// gas limit.
+    ///
+    /// Gas limits above [`u128::MAX`] are capped, as they cannot be packed.
+    pub fn account_gas_limits(&self) -> B256 {
+        pack_u128s(self.verification_gas_limit.saturating_to(), self.call_gas_limit.saturating_to())
+    }
+
+    /// Returns the on-chain `gasFees`: the max priority fee per gas followed by the max fee per
+    /// gas.
+    ///
+    /// Fees above [`u128::MAX`] are capped, as they cannot be packed.
+    pub fn gas_fees(&self) -> B256 {
+        pack_u128s(
+            self.max_priority_fee_per_gas.saturating_to(),
+            self.max_fee_per_gas.saturating_to(),
+        )
+    }
+
+    /// Computes the hash of the operation for the given v0.7 entry point and chain, as returned
+    /// by `getUserOpHash`, which is what the account signs.
+    ///
+    /// The signature of the operation is not part of the hash.
+    pub fn hash(&self, entry_point: Address, chain_id: ChainId) -> B256 {
+        let mut packed = Vec::with_capacity(8 * 32);
+        packed.extend_from_slice(self.sender.into_word().as_slice());
+        packed.extend_from_slice(&self.nonce.to_be_bytes::<32>());
+        packed.extend_from_slice(keccak256(self.init_code()).as_slice());
+        packed.extend_from_slice(keccak256(&self.call_data).as_slice());
+        packed.extend_from_slice(self.account_gas_limits().as_slice());
+        packed.extend_from_slice(&self.pre_verification_gas.to_be_bytes::<32>());
+        packed.extend_from_slice(self.gas_fees().as_slice());
+        packed.extend_from_slice(keccak256(self.paymaster_and_data()).as_slice());
+
+        let mut encoded = Vec::with_capacity(3 * 32);
+        encoded.extend_from_slice(keccak256(packed).as_slice());
+        encoded.extend_from_slice(entry_point.into_word().as_slice());
+        encoded.extend_from_slice(&U256::from(chain_id).to_be_bytes::<32>());
+        keccak256(encoded)
+    }
+}
+
+/// A v0.7 account factory along with its data, packed on-chain into the `initCode` of a user
+/// operation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FactoryAndData {
+    /// The account factory.
+    pub factory: Address,
+    /// The data for the account factory.
+    pub data: Bytes,
+}
+
+impl FactoryAndData {
+    /// Returns the factory followed by its data.
+    pub fn encode(&self) -> Bytes {
+        [self.factory.as_slice(), &self.data].concat().into()
+    }
+
+    /// Splits `init_code` into the factory and its data.
+    ///
+    /// Returns `None` if it is too short to hold a factory address, which includes the empty
+    /// `initCode` of existing accounts.
+    pub fn decode(init_code: &[u8]) -> Option<Self> {
+        if init_code.len() < Address::len_bytes() {
+            return None;
+        }
+        let (factory, data) = init_code.split_at(Address::len_bytes());
+        Some(Self { factory: Address::from_slice(factory), data: Bytes::copy_from_slice(data) })
+    }
+}
+
+/// A v0.7 paymaster along with its gas limits and data, packed on-chain into the
+/// `paymasterAndData` of a user operation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaymasterAndData {
+    /// The paymaster.
+    pub paymaster: Address,
+    /// The amount of gas to allocate for the paymaster validation code.
+    pub verification_gas_limit: u128,
+    /// The amount of gas to allocate for the paymaster post-operation code.
+    pub post_op_gas_limit: u128,
+    /// The data for the paymaster.
+    pub data: Bytes,
+}
+
+impl PaymasterAndData {
+    /// The length of the paymaster address and gas limits preceding the paymaster data.
+    pub const DATA_OFFSET: usize = 20 + 16 + 16;
+
+    /// Returns the paymaster followed by its gas limits and data.
+    pub fn encode(&self) -> Bytes {
+        let mut encoded = Vec::with_capacity(Self::DATA_OFFSET + self.data.len());
+        encoded.extend_from_slice(self.paymaster.as_slice());
+        encoded.extend_from_slice(&self.verification_gas_limit.to_be_bytes());
+        encoded.extend_from_slice(&self.post_op_gas_limit.to_be_bytes());
+        encoded.extend_from_slice(&self.data);
+        encoded.into()
+    }
+
+    /// Splits `paymaster_and_data` into the paymaster, its gas limits and data.
+    ///
+    /// Returns `None` if it is too short to hold the paymaster address and gas limits, which
+    /// includes the empty `paymasterAndData` of self-sponsored operations.
+    pub fn decode(paymaster_and_data: &[u8]) -> Option<Self> {
+        if paymaster_and_data.len() < Self::DATA_OFFSET {
+            return None;
+        }
+        let (paymaster, rest) = paymaster_and_data.split_at(20);
+        let (verification_gas_limit, rest) = rest.split_at(16);
+        let (post_op_gas_limit, data) = rest.split_at(16);
+        Some(Self {
+            paymaster: Address::from_slice(paymaster),
+            verification_gas_limit: u128::from_be_bytes(verification_gas_limit.try_into().ok()?),
+            post_op_gas_limit: u128::from_be_bytes(post_op_gas_limit.try_into().ok()?),
+            data: Bytes::copy_from_slice(data),
+        })
+    }
+}
+
+/// Packs two `uint128` values into the two halves of a word, `high` first, like the
+/// `accountGasLimits` and `gasFees` fields of a v0.7 user operation.
+pub fn pack_u128s(high: u128, low: u128) -> B256 {
+    let mut word = B256::ZERO;
+    word[..16].copy_from_slice(&high.to_be_bytes());
+    word[16..].copy_from_slice(&low.to_be_bytes());
+    word
+}
+
+/// Unpacks the two `uint128` halves of a word, `high` first, see [`pack_u128s`].
+pub fn unpack_u128s(word: B256) -> (u128, u128) {
+    let (high, low) = word.split_at(16);
+    (
+        u128::from_be_bytes(high.try_into().expect("16 bytes")),
+        u128::from_be_bytes(low.try_into().expect("16 bytes")),
+    )
+}
+
+/// A user operation for either entry point version, as sent to `eth_sendUserOperation`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SendUserOperation {
+    /// A v0.6 user operation.
+    EntryPointV06(UserOperation),
+    /// A v0.7 user operation.
+    EntryPointV07(PackedUserOperation),
+}
+
+impl From<UserOperation> for SendUserOperation {
+    fn from(op: UserOperation) -> Self {
+        Self::EntryPointV06(op)
+    }
+}
+
+impl From<PackedUserOperation> for SendUserOperation {
+    fn from(op: PackedUserOperation) -> Self {
+        Self::EntryPointV07(op)
+    }
+}
+
+/// Gas estimates returned by `eth_estimateUserOperationGas`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGasEstimation {
+    /// The gas overhead of the operation.
+    pub pre_verification_gas: U256,
+    /// The gas limit for the verification step.
+    pub verification_gas_limit: U256,
+    /// The gas limit for the paymaster validation code, for v0.7 operations.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    /// The gas limit for the main execution call.
+    pub call_gas_limit: U256,
+}
+
+/// A user operation along with its inclusion info, as returned by `eth_getUserOperationByHash`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationByHash {
+    /// The user operation.
+    pub user_operation: SendUserOperation,
+    /// The entry point the operation was sent to.
+    pub entry_point: Address,
+    /// The block the operation was included in, if any.
+    pub block_number: Option<U256>,
+    /// The hash of the block the operation was included in, if any.
+    pub block_hash: Option<BlockHash>,
+    /// The hash of the bundle transaction that included the operation, if any.
+    pub transaction_hash: Option<TxHash>,
+}
+
+/// The receipt of an included user operation, as returned by `eth_getUserOperationReceipt`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    /// The hash of the user operation.
+    pub user_op_hash: B256,
+    /// The entry point the operation was sent to.
+    pub entry_point: Address,
+    /// The account making the operation.
+    pub sender: Address,
+    /// The nonce of the operation.
+    pub nonce: U256,
+    /// The paymaster that paid for the operation, or zero if self-sponsored.
+    pub paymaster: Address,
+    /// The actual amount paid for the operation, by the account or the paymaster.
+    pub actual_gas_cost: U256,
+    /// The total gas used by the operation, including pre-verification, creation, validation
+    /// and execution.
+    pub actual_gas_used: U256,
+    /// Whether the execution call completed without reverting.
+    pub success: bool,
+    /// The revert reason, if the execution call reverted.
+    #[serde(default)]
+    pub reason: Bytes,
+    /// The logs emitted by the operation.
+    pub logs: Vec<Log>,
+    /// The receipt of the bundle transaction that included the operation.
+    pub receipt: TransactionReceipt,
+}
+
+/// Splits the receipt of a bundle transaction sent to `entry_point` into the receipts of its user
+/// operations, in execution order.
+///
+/// The entry point emits a `UserOperationEvent` once it has executed an operation, so the logs of
+/// an operation are those emitted after the event of the previous operation, or after the
+/// `BeforeExecution` event for the first one. Logs emitted during validation are not attributed
+/// to any operation. The revert reason is taken from the `UserOperationRevertReason` event of
+/// the operation, if any.
+pub fn user_operation_receipts(
+    entry_point: Address,
+    receipt: &TransactionReceipt,
+) -> Vec<UserOperationReceipt> {
+    let logs = receipt.inner.logs();
+    let mut start = logs
+        .iter()
+        .position(|log| {
+            entry_point_event::<IEntryPoint::BeforeExecution>(entry_point, log).is_some()
+        })
+        .map_or(0, |i| i + 1);
+    let mut receipts = Vec::new();
+    for (i, log) in logs.iter().enumerate() {
+        let Some(event) = entry_point_event::<IEntryPoint::UserOperationEvent>(entry_point, log)
+        else {
+            continue;
+        };
+        let op_logs = &logs[start..i];
+        start = i + 1;
+
+        let reason = op_logs
+            .iter()
+            .filter_map(|log| {
+                entry_point_event::<IEntryPoint::UserOperationRevertReason>(entry_point, log)
+            })
+            .find(|revert| revert.userOpHash == event.userOpHash)
+            .map(|revert| revert.revertReason)
+            .unwrap_or_default();
+        receipts.push(UserOperationReceipt {
+            user_op_hash: event.userOpHash,
+            entry_point,
+            sender: event.sender,
+            nonce: event.nonce,
+            paymaster: event.paymaster,
+            actual_gas_cost: event.actualGasCost,
+            actual_gas_used: event.actualGasUsed,
+            success: event.success,
+            reason,
+            logs: op_logs.to_vec(),
+            receipt: receipt.clone(),
+        });
+    }
+    receipts
+}
+
+/// Decodes `log` as an event `E` emitted by `entry_point`.
+fn entry_point_event<E: SolEvent>(entry_point: Address, log: &Log) -> Option<E> {
+    if log.address() != entry_point || log.topics().first() != Some(&E::SIGNATURE_HASH) {
+        return None;
+    }
+    log.log_decode::<E>().ok().map(|log| log.inner.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_user_operation_v06() {
+        let json = r#"{"sender":"0x9c5754de1443984659e1b3a8d1931d83475ba29c","nonce":"0x1","initCode":"0x","callData":"0xb61d27f6","callGasLimit":"0x5208","verificationGasLimit":"0x186a0","preVerificationGas":"0xb5e8","maxFeePerGas":"0x3b9aca00","maxPriorityFeePerGas":"0x3b9aca00","paymasterAndData":"0x","signature":"0x"}"#;
+        let op: SendUserOperation = serde_json::from_str(json).unwrap();
+        let SendUserOperation::EntryPointV06(ref inner) = op else { panic!("expected v0.6") };
+        assert_eq!(inner.call_gas_limit, U256::from(21000));
+        assert_eq!(serde_json::to_string(&op).unwrap(), json);
+    }
+
+    #[test]
+    fn serde_user_operation_v07() {
+        let json = r#"{"sender":"0x9c5754de1443984659e1b3a8d1931d83475ba29c","nonce":"0x1","factory":"0x9406cc6185a346906296840746125a0e44976454","factoryData":"0x5fbfb9cf","callData":"0xb61d27f6","callGasLimit":"0x5208","verificationGasLimit":"0x186a0","preVerificationGas":"0xb5e8","maxFeePerGas":"0x3b9aca00","maxPriorityFeePerGas":"0x3b9aca00","signature":"0x"}"#;
+        let op: SendUserOperation = serde_json::from_str(json).unwrap();
+        let SendUserOperation::EntryPointV07(ref inner) = op else { panic!("expected v0.7") };
+        assert!(inner.paymaster.is_none());
+        assert_eq!(serde_json::to_string(&op).unwrap(), json);
+    }
+
+    #[test]
+    fn packs_v07_fields() {
+        let mut op = PackedUserOperation {
+            call_gas_limit: U256::from(0x5208),
+            verification_gas_limit: U256::from(0x186a0),
+            max_fee_per_gas: U256::from(2),
+            max_priority_fee_per_gas: U256::from(1),
+            ..Default::default()
+        };
+        assert!(op.init_code().is_empty() && op.paymaster_and_data().is_empty());
+        assert_eq!(unpack_u128s(op.account_gas_limits()), (0x186a0, 0x5208));
+        assert_eq!(unpack_u128s(op.gas_fees()), (1, 2));
+
+        let paymaster = PaymasterAndData {
+            paymaster: Address::repeat_byte(0xaa),
+            verification_gas_limit: 1,
+            post_op_gas_limit: 2,
+            data: Bytes::from_static(&[3]),
+        };
+        op.set_paymaster(Some(paymaster.clone()));
+        let encoded = op.paymaster_and_data();
+        assert_eq!(encoded.len(), PaymasterAndData::DATA_OFFSET + 1);
+        assert_eq!(encoded[35], 1);
+        assert_eq!(PaymasterAndData::decode(&encoded), Some(paymaster));
+
+        let factory =
+            FactoryAndData { factory: Address::repeat_byte(0xbb), data: Bytes::from_static(&[4]) };
+        op.set_factory_and_data(Some(factory.clone()));
+        assert_eq!(FactoryAndData::decode(&op.init_code()), Some(factory));
+        op.set_factory_and_data(None);
+        assert_eq!(op.factory_data, None);
+        assert_eq!(FactoryAndData::decode(&[]), None);
+    }
+
+    #[test]
+    fn user_operation_hash_v07() {
+        use alloy_primitives::{address, b256};
+        use alloy_sol_types::SolValue;
+
+        // The canonical v0.7 entry point deployment.
+        let entry_point = address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+        let mut op = PackedUserOperation {
+            sender: address!("9c5754De1443984659E1b3a8d1931D83475ba29C"),
+            nonce: U256::from(1),
+            call_data: Bytes::from_static(&[0xb6, 0x1d, 0x27, 0xf6]),
+            call_gas_limit: U256::from(0x5208),
+            verification_gas_limit: U256::from(0x186a0),
+            pre_verification_gas: U256::from(0xb5e8),
+            max_fee_per_gas: U256::from(0x3b9aca00),
+            max_priority_fee_per_gas: U256::from(0x3b9aca00),
+            ..Default::default()
+        };
+        op.set_factory_and_data(Some(FactoryAndData {
+            factory: address!("9406Cc6185a346906296840746125a0E44976454"),
+            data: Bytes::from_static(&[0x5f, 0xbf, 0xb9, 0xcf]),
+        }));
+        op.set_paymaster(Some(PaymasterAndData {
+            paymaster: Address::repeat_byte(0xaa),
+            verification_gas_limit: 0x7530,
+            post_op_gas_limit: 0x2710,
+            data: Bytes::from_static(&[1]),
+        }));
+
+        // `UserOperationLib.hash` and `EntryPoint.getUserOpHash`, ABI encoded independently of
+        // the packing in `hash`.
+        let inner = (
+            op.sender,
+            op.nonce,
+            keccak256(op.init_code()),
+            keccak256(&op.call_data),
+            op.account_gas_limits(),
+            op.pre_verification_gas,
+            op.gas_fees(),
+            keccak256(op.paymaster_and_data()),
+        )
+            .abi_encode();
+        let expected = keccak256((keccak256(inner), entry_point, U256::from(1)).abi_encode());
+
+        assert_eq!(op.hash(entry_point, 1), expected);
+        // Pinned so that changes to either encoding are caught, this is not a captured
+        // on-chain hash.
+        assert_eq!(
+            op.hash(entry_point, 1),
+            b256!("5db823a9207b8d5e58532f502c3a1a439e4a48c6433535d62a78271b2001da70")
+        );
+        assert_ne!(op.hash(entry_point, 1), op.hash(entry_point, 10));
+    }
+
+    #[test]
+    fn serde_gas_estimation() {
+        let json = r#"{"preVerificationGas":"0xb5e8","verificationGasLimit":"0x186a0","callGasLimit":"0x5208"}"#;
+        let estimate: UserOperationGasEstimation = serde_json::from_str(json).unwrap();
+        assert_eq!(estimate.paymaster_verification_gas_limit, None);
+        assert_eq!(serde_json::to_string(&estimate).unwrap(), json);
+    }
+
+    #[test]
+    fn splits_bundle_receipt() {
+        use alloy_consensus::{Receipt, ReceiptEnvelope, ReceiptWithBloom};
+
+        let entry_point = Address::repeat_byte(0xee);
+        let log = |address, data: alloy_primitives::LogData| Log {
+            inner: alloy_primitives::Log { address, data },
+            ..Default::default()
+        };
+        let op_event = |hash: u8, success| {
+            IEntryPoint::UserOperationEvent {
+                userOpHash: B256::repeat_byte(hash),
+                sender: Address::repeat_byte(hash),
+                paymaster: Address::ZERO,
+                nonce: U256::ZERO,
+                success,
+                actualGasCost: U256::from(hash),
+                actualGasUsed: U256::from(21_000),
+            }
+            .encode_log_data()
+        };
+        let transfer = log(Address::repeat_byte(0xcc), Default::default());
+        let logs = vec![
+            // Validation.
+            transfer.clone(),
+            log(entry_point, IEntryPoint::BeforeExecution {}.encode_log_data()),
+            transfer.clone(),
+            log(entry_point, op_event(1, true)),
+            log(
+                entry_point,
+                IEntryPoint::UserOperationRevertReason {
+                    userOpHash: B256::repeat_byte(2),
+                    sender: Address::repeat_byte(2),
+                    nonce: U256::ZERO,
+                    revertReason: Bytes::from_static(&[0xde, 0xad]),
+                }
+                .encode_log_data(),
+            ),
+            log(entry_point, op_event(2, false)),
+        ];
+        let receipt = TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+                receipt: Receipt { status: true.into(), cumulative_gas_used: 0, logs },
+                logs_bloom: Default::default(),
+            }),
+            transaction_hash: B256::ZERO,
+            transaction_index: None,
+            block_hash: None,
+            block_number: None,
+            gas_used: 0,
+            effective_gas_price: 0,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::ZERO,
+            to: Some(entry_point),
+            contract_address: None,
+        };
+
+        let [first, second] = &user_operation_receipts(entry_point, &receipt)[..] else {
+            panic!("expected two user operations")
+        };
+        assert_eq!(first.user_op_hash, B256::repeat_byte(1));
+        assert!(first.success && first.reason.is_empty());
+        assert_eq!(first.logs, [transfer]);
+        assert_eq!(second.actual_gas_cost, U256::from(2));
+        assert!(!second.success);
+        assert_eq!(second.reason, Bytes::from_static(&[0xde, 0xad]));
+        assert_eq!(second.logs.len(), 1);
+    }
+}
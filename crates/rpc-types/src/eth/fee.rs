@@ -1,3 +1,5 @@
+use crate::{Header, TransactionReceipt};
+use alloy_eips::eip4844::BlobParams;
 use serde::{Deserialize, Serialize};
 
 /// Internal struct to calculate reward percentiles
@@ -9,6 +11,25 @@ pub struct TxGasAndReward {
     pub reward: u128,
 }
 
+impl TxGasAndReward {
+    /// Returns the gas used and the effective tip of each transaction of a block, from the
+    /// receipts of the block, sorted by reward as expected by [`FeeHistory::reward_percentiles`].
+    pub fn from_receipts<T>(
+        receipts: &[TransactionReceipt<T>],
+        base_fee_per_gas: u128,
+    ) -> Vec<Self> {
+        let mut transactions: Vec<_> = receipts
+            .iter()
+            .map(|receipt| Self {
+                gas_used: receipt.gas_used.try_into().unwrap_or(u64::MAX),
+                reward: receipt.effective_gas_price.saturating_sub(base_fee_per_gas),
+            })
+            .collect();
+        transactions.sort();
+        transactions
+    }
+}
+
 impl PartialOrd for TxGasAndReward {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(self.cmp(other))
@@ -24,6 +45,32 @@ impl Ord for TxGasAndReward {
     }
 }
 
+/// The multiple of the update fraction above which blob base fees are not projected, at which the
+/// blob base fee is about `e^40` wei, far above any realistic fee.
+const MAX_PROJECTED_UPDATE_FRACTIONS: u128 = 40;
+
+/// Returns the largest excess blob gas whose blob base fee under `params` does not exceed
+/// `blob_base_fee`.
+///
+/// Several excess blob gas values map to the same fee, so this is the most conservative
+/// starting point for projecting the next fees.
+fn excess_blob_gas_for_price(blob_base_fee: u128, params: BlobParams) -> u128 {
+    let (mut low, mut high) = (0, MAX_PROJECTED_UPDATE_FRACTIONS * params.update_fraction);
+    if params.calc_blob_gasprice(high) <= blob_base_fee {
+        return high;
+    }
+    // Invariant: the fee at `low` does not exceed `blob_base_fee`, the fee at `high` does.
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if params.calc_blob_gasprice(mid) <= blob_base_fee {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
 /// Response type for `eth_feeHistory`
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -49,6 +96,7 @@ pub struct FeeHistory {
     ///
     /// The `Option` is only for compatibility with Erigon and Geth.
     // #[serde(default, with = "alloy_serde::num::f64_hex_vec")]
+    #[serde(deserialize_with = "alloy_serde::lenient::null_as_default")]
     pub gas_used_ratio: Vec<f64>,
     /// An array of block base fees per blob gas. This includes the next block after the newest
     /// of  the returned range, because this value can be derived from the newest block. Zeroes
@@ -61,7 +109,11 @@ pub struct FeeHistory {
     pub base_fee_per_blob_gas: Vec<u128>,
     /// An array of block blob gas used ratios. These are calculated as the ratio of gasUsed and
     /// gasLimit.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "alloy_serde::lenient::null_as_default"
+    )]
     pub blob_gas_used_ratio: Vec<f64>,
     /// Lowest number block of the returned range.
     #[serde(default, with = "alloy_serde::num::u64_via_ruint")]
@@ -115,13 +167,332 @@ impl FeeHistory {
             })
             .copied()
     }
+
+    /// Returns the rewards of the block with the given number, if it is covered by the response.
+    pub fn block_reward(&self, number: u64) -> Option<&[u128]> {
+        let index = number.checked_sub(self.oldest_block)?;
+        self.reward.as_ref()?.get(usize::try_from(index).ok()?).map(Vec::as_slice)
+    }
+
+    /// Computes the rewards of a block at the given `percentiles`, as returned in the `reward`
+    /// field of an `eth_feeHistory` response.
+    ///
+    /// `transactions` must be sorted by reward, see [`TxGasAndReward::from_receipts`].
+    /// Percentiles are weighted by the gas used of the transactions.
+    pub fn reward_percentiles(
+        transactions: &[TxGasAndReward],
+        block_gas_used: u128,
+        percentiles: &[f64],
+    ) -> Vec<u128> {
+        let Some(first) = transactions.first() else {
+            // All zeroes are returned for empty blocks.
+            return vec![0; percentiles.len()];
+        };
+
+        let mut index = 0;
+        let mut cumulative_gas = first.gas_used as u128;
+        percentiles
+            .iter()
+            .map(|percentile| {
+                let threshold = (block_gas_used as f64 * percentile / 100.0) as u128;
+                while cumulative_gas < threshold && index < transactions.len() - 1 {
+                    index += 1;
+                    cumulative_gas += transactions[index].gas_used as u128;
+                }
+                transactions[index].reward
+            })
+            .collect()
+    }
+
+    /// Computes the rewards of a block from its header and receipts, at the given
+    /// `percentiles`.
+    ///
+    /// This is the reference for the rows of [`reward`](Self::reward), e.g. to compare a locally
+    /// built fee history with the response of another node.
+    pub fn block_reward_from_receipts<T>(
+        header: &Header,
+        receipts: &[TransactionReceipt<T>],
+        percentiles: &[f64],
+    ) -> Vec<u128> {
+        let transactions =
+            TxGasAndReward::from_receipts(receipts, header.base_fee_per_gas.unwrap_or_default());
+        Self::reward_percentiles(&transactions, header.gas_used, percentiles)
+    }
+
+    /// Returns the blocks covered by the response, from the oldest to the newest.
+    pub fn blocks(&self) -> impl Iterator<Item = FeeHistoryBlock<'_>> + '_ {
+        (0..self.block_count()).map(move |i| FeeHistoryBlock {
+            number: self.oldest_block + i as u64,
+            base_fee_per_gas: self.base_fee_per_gas.get(i).copied().unwrap_or_default(),
+            base_fee_per_blob_gas: self.base_fee_per_blob_gas.get(i).copied().filter(|fee| {
+                // skip zero value that is returned for pre-EIP-4844 blocks
+                *fee != 0
+            }),
+            gas_used_ratio: self.gas_used_ratio[i],
+            blob_gas_used_ratio: self.blob_gas_used_ratio.get(i).copied(),
+            reward: self.reward.as_ref().and_then(|rewards| rewards.get(i)).map(Vec::as_slice),
+        })
+    }
+
+    /// Returns the rewards at the `index`-th of the requested percentiles, along with the number
+    /// of their block.
+    ///
+    /// Blocks without such a reward are skipped.
+    pub fn reward_percentile_series(&self, index: usize) -> Vec<(u64, u128)> {
+        self.blocks().filter_map(|block| Some((block.number, *block.reward?.get(index)?))).collect()
+    }
+
+    /// Returns the mean of the gas used ratios of the blocks covered by the response.
+    pub fn average_gas_used_ratio(&self) -> Option<f64> {
+        if self.gas_used_ratio.is_empty() {
+            return None;
+        }
+        Some(self.gas_used_ratio.iter().sum::<f64>() / self.gas_used_ratio.len() as f64)
+    }
+
+    /// Returns the relative change of the base fee from the oldest block of the response to the
+    /// next block, e.g. `0.125` for an increase of 12.5%.
+    ///
+    /// Returns `None` if the response has no base fee of the next block, or if the base fee of
+    /// the oldest block is zero.
+    pub fn base_fee_trend(&self) -> Option<f64> {
+        let oldest = *self.base_fee_per_gas.first().filter(|fee| **fee != 0)?;
+        let next = self.base_fee_per_gas.get(1..)?.last()?;
+        Some((*next as f64 - oldest as f64) / oldest as f64)
+    }
+
+    /// Returns the mean of the blob gas used ratios of the blocks covered by the response.
+    pub fn average_blob_gas_used_ratio(&self) -> Option<f64> {
+        if self.blob_gas_used_ratio.is_empty() {
+            return None;
+        }
+        Some(self.blob_gas_used_ratio.iter().sum::<f64>() / self.blob_gas_used_ratio.len() as f64)
+    }
+
+    /// Projects the blob base fees of the `blocks` blocks following the newest block of the
+    /// response, assuming each of them uses the given ratio of the maximum blob gas of a block.
+    ///
+    /// The first fee is the one of the next block, which is known, and the following ones are
+    /// derived with the excess blob gas update rule of EIP-4844 under the blob parameters of the
+    /// chain's current fork, e.g. [`BlobParams::prague`]. Returns an empty list if the next block
+    /// is pre-EIP-4844.
+    pub fn project_blob_base_fees(
+        &self,
+        blocks: usize,
+        blob_gas_used_ratio: f64,
+        params: BlobParams,
+    ) -> Vec<u128> {
+        let Some(next_fee) = self.next_block_blob_base_fee() else { return Vec::new() };
+        let blob_gas_used =
+            (params.max_blob_gas_per_block() as f64 * blob_gas_used_ratio.clamp(0.0, 1.0)) as u128;
+        let mut excess_blob_gas = excess_blob_gas_for_price(next_fee, params);
+        let mut fees = Vec::with_capacity(blocks);
+        if blocks > 0 {
+            fees.push(next_fee);
+        }
+        for _ in 1..blocks {
+            excess_blob_gas = params.next_block_excess_blob_gas(excess_blob_gas, blob_gas_used);
+            fees.push(params.calc_blob_gasprice(excess_blob_gas));
+        }
+        fees
+    }
+
+    /// Projects the blob base fees of the `blocks` blocks following the newest block of the
+    /// response, assuming they use as much blob gas as the blocks of the response did on average.
+    ///
+    /// See [`project_blob_base_fees`](Self::project_blob_base_fees).
+    pub fn projected_blob_base_fees(&self, blocks: usize, params: BlobParams) -> Vec<u128> {
+        let ratio = self.average_blob_gas_used_ratio().unwrap_or_default();
+        self.project_blob_base_fees(blocks, ratio, params)
+    }
+
+    /// Returns the number of blocks covered by the response.
+    pub fn block_count(&self) -> usize {
+        self.gas_used_ratio.len()
+    }
+
+    /// Checks the invariants of an `eth_feeHistory` response to a request for
+    /// `requested_block_count` blocks with the given reward `percentiles`.
+    ///
+    /// For `n` returned blocks, with `n` at most the requested block count:
+    /// - `base_fee_per_gas` has `n + 1` entries, including the next block,
+    /// - `base_fee_per_blob_gas` and `blob_gas_used_ratio`, if returned, have `n + 1` and `n`
+    ///   entries,
+    /// - `reward` has `n` rows with one entry per percentile, if any percentile was requested.
+    pub fn validate(
+        &self,
+        requested_block_count: u64,
+        percentiles: &[f64],
+    ) -> Result<(), FeeHistoryError> {
+        let n = self.block_count();
+        if n as u64 > requested_block_count {
+            return Err(FeeHistoryError::TooManyBlocks {
+                requested: requested_block_count,
+                returned: n,
+            });
+        }
+
+        // Responses without any block may omit the fee of the next block.
+        if n > 0 || !self.base_fee_per_gas.is_empty() {
+            FeeHistoryError::check_len("baseFeePerGas", n + 1, self.base_fee_per_gas.len())?;
+        }
+        if !self.base_fee_per_blob_gas.is_empty() || !self.blob_gas_used_ratio.is_empty() {
+            FeeHistoryError::check_len(
+                "baseFeePerBlobGas",
+                n + 1,
+                self.base_fee_per_blob_gas.len(),
+            )?;
+            FeeHistoryError::check_len("blobGasUsedRatio", n, self.blob_gas_used_ratio.len())?;
+        }
+
+        if percentiles.is_empty() {
+            return Ok(());
+        }
+        let rewards = match &self.reward {
+            Some(rewards) => rewards,
+            None if n == 0 => return Ok(()),
+            None => return Err(FeeHistoryError::MissingReward),
+        };
+        FeeHistoryError::check_len("reward", n, rewards.len())?;
+        for (i, row) in rewards.iter().enumerate() {
+            if row.len() != percentiles.len() {
+                return Err(FeeHistoryError::RewardPercentiles {
+                    block: self.oldest_block + i as u64,
+                    expected: percentiles.len(),
+                    actual: row.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends the fee history of the blocks following the newest block of this one, as if both
+    /// were returned by a single `eth_feeHistory` request.
+    ///
+    /// The base fees of the next block of `self` are replaced by the ones of `other`. If only one
+    /// of the responses has blob fees, the blocks of the other one get zero blob fees, as returned
+    /// for pre-EIP-4844 blocks; if only one of them has rewards, the blocks of the other one get
+    /// empty rewards.
+    pub fn append(&mut self, mut other: Self) -> Result<(), FeeHistoryError> {
+        let n = self.block_count();
+        let next_block = self.oldest_block + n as u64;
+        if other.oldest_block != next_block {
+            return Err(FeeHistoryError::NotContiguous {
+                expected: next_block,
+                actual: other.oldest_block,
+            });
+        }
+
+        if self.base_fee_per_blob_gas.is_empty() != other.base_fee_per_blob_gas.is_empty() {
+            self.pad_blob_fees();
+            other.pad_blob_fees();
+        }
+        let other_count = other.block_count();
+        self.base_fee_per_gas.truncate(n);
+        self.base_fee_per_gas.append(&mut other.base_fee_per_gas);
+        self.base_fee_per_blob_gas.truncate(n);
+        self.base_fee_per_blob_gas.append(&mut other.base_fee_per_blob_gas);
+        self.gas_used_ratio.append(&mut other.gas_used_ratio);
+        self.blob_gas_used_ratio.append(&mut other.blob_gas_used_ratio);
+
+        self.reward = match (self.reward.take(), other.reward) {
+            (None, None) => None,
+            (rewards, other_rewards) => {
+                let mut rewards = rewards.unwrap_or_else(|| vec![Vec::new(); n]);
+                rewards.extend(other_rewards.unwrap_or_else(|| vec![Vec::new(); other_count]));
+                Some(rewards)
+            }
+        };
+        Ok(())
+    }
+
+    /// Fills in the zero blob fees of pre-EIP-4844 blocks, if the response omits them.
+    fn pad_blob_fees(&mut self) {
+        if self.base_fee_per_blob_gas.is_empty() {
+            let n = self.block_count();
+            self.base_fee_per_blob_gas = vec![0; n + 1];
+            self.blob_gas_used_ratio = vec![0.0; n];
+        }
+    }
+}
+
+/// The fees of a block covered by a [`FeeHistory`], as returned by [`FeeHistory::blocks`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FeeHistoryBlock<'a> {
+    /// The number of the block.
+    pub number: u64,
+    /// The base fee per gas of the block, zero for pre-EIP-1559 blocks.
+    pub base_fee_per_gas: u128,
+    /// The base fee per blob gas of the block, if it is post-EIP-4844.
+    pub base_fee_per_blob_gas: Option<u128>,
+    /// The ratio of the gas used and the gas limit of the block.
+    pub gas_used_ratio: f64,
+    /// The ratio of the blob gas used and the maximum blob gas of the block, if returned.
+    pub blob_gas_used_ratio: Option<f64>,
+    /// The rewards of the block at the requested percentiles, if any.
+    pub reward: Option<&'a [u128]>,
+}
+
+/// A violated invariant of an `eth_feeHistory` response, returned by [`FeeHistory::validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum FeeHistoryError {
+    /// The response covers more blocks than requested.
+    #[error("requested {requested} blocks, got {returned}")]
+    TooManyBlocks {
+        /// The requested number of blocks.
+        requested: u64,
+        /// The number of returned blocks.
+        returned: usize,
+    },
+    /// An array of the response has an unexpected length.
+    #[error("expected {expected} entries in `{field}`, got {actual}")]
+    Length {
+        /// The name of the array, as in the JSON response.
+        field: &'static str,
+        /// The expected length.
+        expected: usize,
+        /// The actual length.
+        actual: usize,
+    },
+    /// The response has no rewards although percentiles were requested.
+    #[error("missing `reward` for the requested percentiles")]
+    MissingReward,
+    /// The rewards of a block do not match the requested percentiles.
+    #[error("expected {expected} rewards for block {block}, got {actual}")]
+    RewardPercentiles {
+        /// The number of the block.
+        block: u64,
+        /// The number of requested percentiles.
+        expected: usize,
+        /// The number of returned rewards.
+        actual: usize,
+    },
+    /// The appended response does not start right after the newest block of the other one.
+    #[error("expected the fee history to continue at block {expected}, got block {actual}")]
+    NotContiguous {
+        /// The block following the newest block of the response.
+        expected: u64,
+        /// The oldest block of the appended response.
+        actual: u64,
+    },
+}
+
+impl FeeHistoryError {
+    const fn check_len(field: &'static str, expected: usize, actual: usize) -> Result<(), Self> {
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(Self::Length { field, expected, actual })
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use similar_asserts::assert_eq;
 
-    use crate::FeeHistory;
+    use crate::{FeeHistory, FeeHistoryBlock, FeeHistoryError};
+    use alloy_eips::eip4844::BlobParams;
 
     #[test]
     fn test_fee_history_serde() {
@@ -145,4 +516,236 @@ mod tests {
         let json = r#"{"baseFeePerBlobGas":["0xc0","0xb2","0xab","0x98","0x9e","0x92","0xa4","0xb9","0xd0","0xea","0xfd"],"baseFeePerGas":["0x4cb8cf181","0x53075988e","0x4fb92ee18","0x45c209055","0x4e790dca2","0x58462e84e","0x5b7659f4e","0x5d66ea3aa","0x6283c6e45","0x5ecf0e1e5","0x5da59cf89"],"blobGasUsedRatio":[0.16666666666666666,0.3333333333333333,0,0.6666666666666666,0.16666666666666666,1,1,1,1,0.8333333333333334],"gasUsedRatio":[0.8288135,0.3407616666666667,0,0.9997232,0.999601,0.6444664333333333,0.5848306333333333,0.7189564,0.34952733333333336,0.4509799666666667],"oldestBlock":"0x59f94f","reward":[["0x59682f00"],["0x59682f00"],["0x0"],["0x59682f00"],["0x59682f00"],["0x3b9aca00"],["0x59682f00"],["0x59682f00"],["0x3b9aca00"],["0x59682f00"]]}"#;
         let _actual = serde_json::from_str::<FeeHistory>(json).unwrap();
     }
+
+    #[test]
+    #[cfg(feature = "lenient")]
+    fn test_fee_history_serde_lenient() {
+        // Quantities without prefix, decimal quantities, and `null` lists.
+        let json = r#"{"baseFeePerGas":["342770c0","765625000"],"gasUsedRatio":[0.5],"baseFeePerBlobGas":null,"blobGasUsedRatio":null,"oldestBlock":"1"}"#;
+        let fee_history: FeeHistory = serde_json::from_str(json).unwrap();
+        let expected = FeeHistory {
+            base_fee_per_gas: vec![875000000, 765625000],
+            gas_used_ratio: vec![0.5],
+            oldest_block: 1,
+            ..Default::default()
+        };
+        assert_eq!(fee_history, expected);
+        assert_eq!(
+            serde_json::to_string(&fee_history).unwrap(),
+            r#"{"baseFeePerGas":["0x342770c0","0x2da282a8"],"gasUsedRatio":[0.5],"oldestBlock":"0x1"}"#
+        );
+    }
+
+    #[test]
+    fn validate_fee_history() {
+        let fee_history = FeeHistory {
+            base_fee_per_gas: vec![1, 2, 3],
+            gas_used_ratio: vec![0.5, 0.5],
+            oldest_block: 10,
+            reward: Some(vec![vec![1, 2], vec![3, 4]]),
+            ..Default::default()
+        };
+        fee_history.validate(2, &[25.0, 75.0]).unwrap();
+        fee_history.validate(5, &[25.0, 75.0]).unwrap();
+
+        assert_eq!(
+            fee_history.validate(1, &[]),
+            Err(FeeHistoryError::TooManyBlocks { requested: 1, returned: 2 })
+        );
+        assert_eq!(
+            fee_history.validate(2, &[50.0]),
+            Err(FeeHistoryError::RewardPercentiles { block: 10, expected: 1, actual: 2 })
+        );
+
+        let truncated = FeeHistory { base_fee_per_gas: vec![1, 2], ..fee_history.clone() };
+        assert_eq!(
+            truncated.validate(2, &[]),
+            Err(FeeHistoryError::Length { field: "baseFeePerGas", expected: 3, actual: 2 })
+        );
+
+        let no_reward = FeeHistory { reward: None, ..fee_history };
+        assert_eq!(no_reward.validate(2, &[50.0]), Err(FeeHistoryError::MissingReward));
+    }
+
+    #[test]
+    fn appends_fee_history() {
+        let mut fee_history = FeeHistory {
+            base_fee_per_gas: vec![1, 2, 3],
+            gas_used_ratio: vec![0.1, 0.2],
+            oldest_block: 10,
+            reward: Some(vec![vec![1], vec![2]]),
+            ..Default::default()
+        };
+        let next = FeeHistory {
+            base_fee_per_gas: vec![3, 4],
+            gas_used_ratio: vec![0.3],
+            base_fee_per_blob_gas: vec![5, 6],
+            blob_gas_used_ratio: vec![0.5],
+            oldest_block: 12,
+            reward: None,
+        };
+        assert_eq!(
+            fee_history.clone().append(FeeHistory { oldest_block: 13, ..next.clone() }),
+            Err(FeeHistoryError::NotContiguous { expected: 12, actual: 13 })
+        );
+
+        fee_history.append(next).unwrap();
+        assert_eq!(
+            fee_history,
+            FeeHistory {
+                base_fee_per_gas: vec![1, 2, 3, 4],
+                gas_used_ratio: vec![0.1, 0.2, 0.3],
+                base_fee_per_blob_gas: vec![0, 0, 5, 6],
+                blob_gas_used_ratio: vec![0.0, 0.0, 0.5],
+                oldest_block: 10,
+                reward: Some(vec![vec![1], vec![2], vec![]]),
+            }
+        );
+        fee_history.validate(3, &[]).unwrap();
+    }
+
+    #[test]
+    fn fee_history_blocks() {
+        let fee_history = FeeHistory {
+            base_fee_per_gas: vec![100, 110, 120],
+            gas_used_ratio: vec![0.25, 0.75],
+            base_fee_per_blob_gas: vec![0, 1, 1],
+            blob_gas_used_ratio: vec![0.0, 0.5],
+            oldest_block: 10,
+            reward: Some(vec![vec![1, 2], vec![3]]),
+        };
+        let blocks: Vec<_> = fee_history.blocks().collect();
+        assert_eq!(
+            blocks,
+            [
+                FeeHistoryBlock {
+                    number: 10,
+                    base_fee_per_gas: 100,
+                    base_fee_per_blob_gas: None,
+                    gas_used_ratio: 0.25,
+                    blob_gas_used_ratio: Some(0.0),
+                    reward: Some(&[1, 2]),
+                },
+                FeeHistoryBlock {
+                    number: 11,
+                    base_fee_per_gas: 110,
+                    base_fee_per_blob_gas: Some(1),
+                    gas_used_ratio: 0.75,
+                    blob_gas_used_ratio: Some(0.5),
+                    reward: Some(&[3]),
+                },
+            ]
+        );
+        assert_eq!(fee_history.reward_percentile_series(0), [(10, 1), (11, 3)]);
+        assert_eq!(fee_history.reward_percentile_series(1), [(10, 2)]);
+        assert_eq!(fee_history.average_gas_used_ratio(), Some(0.5));
+        assert_eq!(fee_history.base_fee_trend(), Some(0.2));
+
+        let no_reward = FeeHistory { reward: None, ..fee_history };
+        assert_eq!(no_reward.blocks().filter_map(|block| block.reward).count(), 0);
+        assert!(no_reward.reward_percentile_series(0).is_empty());
+        assert_eq!(FeeHistory::default().base_fee_trend(), None);
+        assert_eq!(FeeHistory::default().average_gas_used_ratio(), None);
+    }
+
+    #[test]
+    fn reward_percentiles_from_receipts() {
+        use crate::{Header, TransactionReceipt};
+
+        let receipt = |gas_used, effective_gas_price| TransactionReceipt {
+            inner: (),
+            transaction_hash: Default::default(),
+            transaction_index: None,
+            block_hash: None,
+            block_number: None,
+            gas_used,
+            effective_gas_price,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Default::default(),
+            to: None,
+            contract_address: None,
+        };
+        let header = Header { gas_used: 100_000, base_fee_per_gas: Some(10), ..Default::default() };
+        let receipts = [receipt(50_000, 15), receipt(30_000, 11), receipt(20_000, 30)];
+
+        let rewards =
+            FeeHistory::block_reward_from_receipts(&header, &receipts, &[0.0, 25.0, 50.0, 90.0]);
+        // Sorted by reward: 1 (30000 gas), 5 (50000 gas), 20 (20000 gas).
+        assert_eq!(rewards, [1, 1, 5, 20]);
+
+        let fee_history = FeeHistory {
+            oldest_block: 7,
+            reward: Some(vec![vec![0, 0, 0, 0], rewards.clone()]),
+            ..Default::default()
+        };
+        assert_eq!(fee_history.block_reward(8), Some(rewards.as_slice()));
+        assert_eq!(fee_history.block_reward(9), None);
+
+        let empty = FeeHistory::block_reward_from_receipts::<()>(&header, &[], &[50.0]);
+        assert_eq!(empty, [0]);
+    }
+
+    #[test]
+    fn projects_blob_base_fees() {
+        use crate::{calc_blob_gasprice, calc_excess_blob_gas};
+        use alloy_eips::eip4844::{BLOB_GASPRICE_UPDATE_FRACTION, MAX_DATA_GAS_PER_BLOCK};
+
+        let cancun = BlobParams::cancun();
+        let excess_blob_gas = 10 * BLOB_GASPRICE_UPDATE_FRACTION;
+        let next_fee = calc_blob_gasprice(excess_blob_gas);
+        let fee_history = FeeHistory {
+            base_fee_per_blob_gas: vec![next_fee - 1, next_fee],
+            blob_gas_used_ratio: vec![1.0],
+            ..Default::default()
+        };
+        assert_eq!(fee_history.average_blob_gas_used_ratio(), Some(1.0));
+
+        // Full blocks increase the fee by about 12.5% each.
+        let fees = fee_history.projected_blob_base_fees(3, cancun);
+        let expected = calc_blob_gasprice(calc_excess_blob_gas(
+            excess_blob_gas,
+            MAX_DATA_GAS_PER_BLOCK as u128,
+        ));
+        assert_eq!(fees[0], next_fee);
+        assert!(fees[1].abs_diff(expected) <= 1, "{} {expected}", fees[1]);
+        assert!(fees[2] > fees[1] * 112 / 100);
+
+        // Blocks at the target keep the fee, and empty blocks decrease it.
+        assert_eq!(fee_history.project_blob_base_fees(3, 0.5, cancun), [next_fee; 3]);
+        let fees = fee_history.project_blob_base_fees(100, 0.0, cancun);
+        assert!(fees.windows(2).all(|w| w[1] <= w[0]));
+        assert_eq!(fees.last(), Some(&1));
+
+        assert!(fee_history.project_blob_base_fees(0, 1.0, cancun).is_empty());
+        assert!(FeeHistory::default().projected_blob_base_fees(3, cancun).is_empty());
+    }
+
+    #[test]
+    fn projects_prague_blob_base_fees() {
+        let prague = BlobParams::prague();
+        let excess_blob_gas = 10 * prague.update_fraction;
+        let next_fee = prague.calc_blob_gasprice(excess_blob_gas);
+        let fee_history = FeeHistory {
+            base_fee_per_blob_gas: vec![next_fee - 1, next_fee],
+            blob_gas_used_ratio: vec![1.0],
+            ..Default::default()
+        };
+
+        // Full blocks have 3 blobs above the target of 6, which increases the fee by about 8%.
+        let fees = fee_history.projected_blob_base_fees(2, prague);
+        let expected =
+            prague.calc_blob_gasprice(prague.next_block_excess_blob_gas(
+                excess_blob_gas,
+                prague.max_blob_gas_per_block() as u128,
+            ));
+        assert!(fees[1].abs_diff(expected) <= 1, "{} {expected}", fees[1]);
+        assert!((next_fee * 107 / 100..next_fee * 109 / 100).contains(&fees[1]), "{}", fees[1]);
+        // The same blocks increase the fee faster under the Cancun parameters.
+        assert!(fee_history.projected_blob_base_fees(2, BlobParams::cancun())[1] > fees[1]);
+
+        // Six of nine blobs are on target, while half full blocks decrease the fee.
+        assert_eq!(fee_history.project_blob_base_fees(3, 2.0 / 3.0, prague), [next_fee; 3]);
+        assert!(fee_history.project_blob_base_fees(2, 0.5, prague)[1] < next_fee);
+    }
 }
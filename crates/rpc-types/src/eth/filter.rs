@@ -1,5 +1,6 @@
 use crate::{eth::log::Log as RpcLog, BlockNumberOrTag, Transaction};
 use alloy_primitives::{keccak256, Address, Bloom, BloomInput, B256, U256, U64};
+use alloy_sol_types::{EventTopic, SolEvent};
 use itertools::{EitherOrBoth::*, Itertools};
 use serde::{
     de::{DeserializeOwned, MapAccess, Visitor},
@@ -95,6 +96,11 @@ impl<T: Eq + Hash> FilterSet<T> {
     pub fn matches(&self, value: &T) -> bool {
         self.is_empty() || self.0.contains(value)
     }
+
+    /// Returns an iterator over the values of the filter, in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
 }
 
 impl<T: AsRef<[u8]> + Eq + Hash> FilterSet<T> {
@@ -129,6 +135,32 @@ impl From<U256> for Topic {
     }
 }
 
+impl From<Address> for Topic {
+    fn from(src: Address) -> Self {
+        src.into_word().into()
+    }
+}
+
+impl Topic {
+    /// Creates a topic matching an indexed event parameter of the Solidity type `T`.
+    ///
+    /// Value types are matched by their ABI encoding, and dynamic types, such as strings, by the
+    /// hash of their encoding, as they are indexed.
+    ///
+    /// ```
+    /// use alloy_primitives::U256;
+    /// use alloy_rpc_types::{Filter, Topic};
+    /// use alloy_sol_types::sol_data::{String, Uint};
+    ///
+    /// let filter = Filter::new()
+    ///     .topic1(Topic::from_indexed::<Uint<256>>(&U256::from(1)))
+    ///     .topic2(Topic::from_indexed::<String>(&"alice".to_string()));
+    /// ```
+    pub fn from_indexed<T: EventTopic>(value: &T::RustType) -> Self {
+        T::encode_topic(value).0.into()
+    }
+}
+
 /// Represents the target range of blocks for the filter
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum FilterBlockOption {
@@ -252,6 +284,22 @@ impl FilterBlockOption {
     }
 }
 
+/// An invalid combination of the block options of a [`Filter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum FilterError {
+    /// The filter has both a block hash and a from or to block.
+    #[error("`blockHash` cannot be combined with `fromBlock` or `toBlock`")]
+    BlockHashWithRange,
+    /// The from block is after the to block.
+    #[error("`fromBlock` {from} is after `toBlock` {to}")]
+    InvalidBlockRange {
+        /// The from block.
+        from: u64,
+        /// The to block.
+        to: u64,
+    },
+}
+
 /// Filter for logs.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Filter {
@@ -270,6 +318,26 @@ impl Filter {
         Self::default()
     }
 
+    /// Creates a new filter matching the logs of the event `E`.
+    ///
+    /// See [`event_type`](Self::event_type).
+    ///
+    /// ```
+    /// use alloy_primitives::Address;
+    /// use alloy_rpc_types::Filter;
+    /// use alloy_sol_types::sol;
+    ///
+    /// sol! {
+    ///     event Transfer(address indexed from, address indexed to, uint256 value);
+    /// }
+    ///
+    /// // The transfers received by an account.
+    /// let filter = Filter::from_event::<Transfer>().topic2(Address::ZERO);
+    /// ```
+    pub fn from_event<E: SolEvent>() -> Self {
+        Self::new().event_type::<E>()
+    }
+
     /// Sets the inner filter object
     ///
     /// *NOTE:* ranges are always inclusive
@@ -368,6 +436,55 @@ impl Filter {
         self.block_option = self.block_option.set_hash(hash.into());
         self
     }
+
+    /// Sets the from block number, failing if the filter is pinned to a block hash.
+    ///
+    /// Unlike [`from_block`](Self::from_block), which replaces the block hash with a range, this
+    /// catches the mistake of combining the two, which EIP-234 forbids.
+    pub fn try_from_block<T: Into<BlockNumberOrTag>>(self, block: T) -> Result<Self, FilterError> {
+        if self.get_block_hash().is_some() {
+            return Err(FilterError::BlockHashWithRange);
+        }
+        Ok(self.from_block(block))
+    }
+
+    /// Sets the to block number, failing if the filter is pinned to a block hash.
+    ///
+    /// See [`try_from_block`](Self::try_from_block).
+    pub fn try_to_block<T: Into<BlockNumberOrTag>>(self, block: T) -> Result<Self, FilterError> {
+        if self.get_block_hash().is_some() {
+            return Err(FilterError::BlockHashWithRange);
+        }
+        Ok(self.to_block(block))
+    }
+
+    /// Pins the block hash for the filter, failing if the filter has a from or to block.
+    ///
+    /// See [`try_from_block`](Self::try_from_block).
+    pub fn try_at_block_hash<T: Into<B256>>(self, hash: T) -> Result<Self, FilterError> {
+        if self.has_block_range() {
+            return Err(FilterError::BlockHashWithRange);
+        }
+        Ok(self.at_block_hash(hash))
+    }
+
+    /// Returns `true` if the filter has a from or to block.
+    pub const fn has_block_range(&self) -> bool {
+        matches!(
+            self.block_option,
+            FilterBlockOption::Range { from_block: Some(_), .. }
+                | FilterBlockOption::Range { to_block: Some(_), .. }
+        )
+    }
+
+    /// Checks that the block range of the filter is not empty, when both its ends are block
+    /// numbers.
+    pub fn validate(&self) -> Result<(), FilterError> {
+        match (self.get_from_block(), self.get_to_block()) {
+            (Some(from), Some(to)) if from > to => Err(FilterError::InvalidBlockRange { from, to }),
+            _ => Ok(()),
+        }
+    }
     /// Sets the inner filter object
     ///
     /// *NOTE:* ranges are always inclusive
@@ -419,6 +536,20 @@ impl Filter {
         self.event_signature(events)
     }
 
+    /// Sets event_signature(topic0) to the signature of the event `E`, unless it is anonymous.
+    ///
+    /// The indexed parameters of the event can then be constrained with
+    /// [`topic1`](Self::topic1), [`topic2`](Self::topic2) and [`topic3`](Self::topic3). The
+    /// topics of anonymous events start with their first indexed parameter instead.
+    #[must_use]
+    pub fn event_type<E: SolEvent>(self) -> Self {
+        if E::ANONYMOUS {
+            self
+        } else {
+            self.event_signature(E::SIGNATURE_HASH)
+        }
+    }
+
     /// Sets event_signature(topic0) (the event name for non-anonymous events)
     #[must_use]
     pub fn event_signature<T: Into<Topic>>(mut self, topic: T) -> Self {
@@ -1029,6 +1160,48 @@ mod tests {
         serde_json::to_value(t).expect("Failed to serialize value")
     }
 
+    #[test]
+    fn event_filter() {
+        use alloy_sol_types::{sol, sol_data};
+
+        sol! {
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            event Anonymous(string indexed name) anonymous;
+        }
+
+        let to = Address::with_last_byte(1);
+        let filter = Filter::from_event::<Transfer>().topic2(to);
+        assert_eq!(filter.topics[0], Transfer::SIGNATURE_HASH.into());
+        assert!(filter.topics[1].is_empty());
+        assert_eq!(filter.topics[2], to.into_word().into());
+
+        let filter =
+            Filter::from_event::<Anonymous>()
+                .event_signature(Topic::from_indexed::<sol_data::String>(&"alice".to_string()));
+        assert_eq!(filter.topics[0], keccak256("alice").into());
+    }
+
+    #[test]
+    fn block_hash_exclusive_with_range() {
+        let hash = B256::repeat_byte(1);
+        let filter = Filter::new().try_at_block_hash(hash).unwrap();
+        assert_eq!(filter.get_block_hash(), Some(hash));
+        assert_eq!(filter.clone().try_from_block(1).unwrap_err(), FilterError::BlockHashWithRange);
+        assert_eq!(filter.try_to_block(1).unwrap_err(), FilterError::BlockHashWithRange);
+
+        let filter = Filter::new().try_to_block(BlockNumberOrTag::Latest).unwrap();
+        assert!(filter.has_block_range());
+        assert_eq!(filter.try_at_block_hash(hash).unwrap_err(), FilterError::BlockHashWithRange);
+        assert!(!Filter::new().has_block_range());
+
+        assert!(Filter::new().from_block(5).to_block(5).validate().is_ok());
+        assert_eq!(
+            Filter::new().from_block(6).to_block(5).validate(),
+            Err(FilterError::InvalidBlockRange { from: 6, to: 5 })
+        );
+        assert!(Filter::new().from_block(6).to_block(BlockNumberOrTag::Latest).validate().is_ok());
+    }
+
     #[test]
     fn test_empty_filter_topics_list() {
         let s = r#"{"fromBlock": "0xfc359e", "toBlock": "0xfc359e", "topics": [["0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"], [], ["0x0000000000000000000000000c17e776cd218252adfca8d4e761d3fe757e9778"]]}"#;
@@ -0,0 +1,272 @@
+//! Interning of the addresses and hashes repeated across large sets of logs.
+//!
+//! A backfill of `eth_getLogs` typically returns thousands of logs emitted by a handful of
+//! contracts, with the same event signatures and block hashes. [`Interner`] deduplicates these
+//! values so that each distinct one is allocated once and shared by every [`InternedLog`]
+//! referencing it, and [`LogsSeed`] interns the logs while they are deserialized, so that the
+//! response is never fully materialized as [`Log`]s.
+//!
+//! The interner keeps the most recently used values only, up to a capacity, so that its memory
+//! stays bounded over long backfills whose transaction hashes and topics are mostly distinct.
+
+use crate::Log;
+use alloy_primitives::{Address, Bytes, B256};
+use lru::LruCache;
+use serde::de::{DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use std::{fmt, hash::Hash, num::NonZeroUsize, sync::Arc};
+
+/// The default number of values of each kind kept by an [`Interner`].
+pub const DEFAULT_INTERNER_CAPACITY: usize = 65_536;
+
+/// Deduplicates values, returning a shared handle to the first equal value seen.
+///
+/// Handles returned by the same interner for equal values point to the same allocation, so they
+/// can be compared with [`Arc::ptr_eq`] instead of by value. The interner keeps at most a
+/// capacity of addresses, of hashes and of data payloads, evicting the least recently used ones:
+/// handles to evicted values remain valid, but are no longer shared with the values interned
+/// afterwards.
+#[derive(Clone, Debug)]
+pub struct Interner {
+    addresses: LruCache<Address, Arc<Address>>,
+    hashes: LruCache<B256, Arc<B256>>,
+    data: LruCache<Bytes, Bytes>,
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_INTERNER_CAPACITY)
+    }
+}
+
+impl Interner {
+    /// Create a new, empty interner keeping [`DEFAULT_INTERNER_CAPACITY`] values of each kind.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new, empty interner keeping at most `capacity` values of each kind.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            addresses: LruCache::new(capacity),
+            hashes: LruCache::new(capacity),
+            data: LruCache::new(capacity),
+        }
+    }
+
+    /// Intern an address.
+    pub fn address(&mut self, address: Address) -> Arc<Address> {
+        intern(&mut self.addresses, address)
+    }
+
+    /// Intern a hash or topic.
+    pub fn hash(&mut self, hash: B256) -> Arc<B256> {
+        intern(&mut self.hashes, hash)
+    }
+
+    /// Intern a data payload. [`Bytes`] are reference counted, so the returned value shares the
+    /// buffer of the first equal payload.
+    pub fn bytes(&mut self, bytes: Bytes) -> Bytes {
+        self.data.get_or_insert(bytes.clone(), || bytes).clone()
+    }
+
+    /// Intern the address, topics, data and hashes of a log.
+    pub fn log(&mut self, log: Log) -> InternedLog {
+        let Log {
+            inner,
+            block_hash,
+            block_number,
+            block_timestamp,
+            transaction_hash,
+            transaction_index,
+            log_index,
+            removed,
+        } = log;
+        let (topics, data) = inner.data.split();
+        InternedLog {
+            address: self.address(inner.address),
+            topics: topics.into_iter().map(|topic| self.hash(topic)).collect(),
+            data: self.bytes(data),
+            block_hash: block_hash.map(|hash| self.hash(hash)),
+            block_number,
+            block_timestamp,
+            transaction_hash: transaction_hash.map(|hash| self.hash(hash)),
+            transaction_index,
+            log_index,
+            removed,
+        }
+    }
+
+    /// Returns a seed deserializing a sequence of logs into [`InternedLog`]s.
+    pub fn logs_seed(&mut self) -> LogsSeed<'_> {
+        LogsSeed(self)
+    }
+
+    /// Returns the number of distinct values interned, and not evicted since.
+    pub fn len(&self) -> usize {
+        self.addresses.len() + self.hashes.len() + self.data.len()
+    }
+
+    /// Returns `true` if no value was interned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget all interned values. Handles returned previously remain valid, but are no longer
+    /// shared with the values interned afterwards.
+    pub fn clear(&mut self) {
+        self.addresses.clear();
+        self.hashes.clear();
+        self.data.clear();
+    }
+}
+
+fn intern<T: Copy + Eq + Hash>(cache: &mut LruCache<T, Arc<T>>, value: T) -> Arc<T> {
+    cache.get_or_insert(value, || Arc::new(value)).clone()
+}
+
+/// A [`Log`] whose addresses, hashes and data are shared through an [`Interner`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InternedLog {
+    /// Address of the contract that emitted the log.
+    pub address: Arc<Address>,
+    /// Topics of the log.
+    pub topics: Vec<Arc<B256>>,
+    /// Data of the log.
+    pub data: Bytes,
+    /// Hash of the block the transaction that emitted this log was mined in.
+    pub block_hash: Option<Arc<B256>>,
+    /// Number of the block the transaction that emitted this log was mined in.
+    pub block_number: Option<u64>,
+    /// Timestamp of the block the transaction that emitted this log was mined in.
+    pub block_timestamp: Option<u64>,
+    /// Hash of the transaction that emitted this log.
+    pub transaction_hash: Option<Arc<B256>>,
+    /// Index of the transaction in the block.
+    pub transaction_index: Option<u64>,
+    /// Index of the log in the block.
+    pub log_index: Option<u64>,
+    /// Whether this log was removed by a reorganization.
+    pub removed: bool,
+}
+
+impl From<InternedLog> for Log {
+    fn from(log: InternedLog) -> Self {
+        Self {
+            inner: alloy_primitives::Log::new_unchecked(
+                *log.address,
+                log.topics.into_iter().map(|topic| *topic).collect(),
+                log.data,
+            ),
+            block_hash: log.block_hash.map(|hash| *hash),
+            block_number: log.block_number,
+            block_timestamp: log.block_timestamp,
+            transaction_hash: log.transaction_hash.map(|hash| *hash),
+            transaction_index: log.transaction_index,
+            log_index: log.log_index,
+            removed: log.removed,
+        }
+    }
+}
+
+/// A [`DeserializeSeed`] interning a sequence of logs as they are deserialized.
+///
+/// ```
+/// use alloy_rpc_types::interner::Interner;
+/// use serde::de::DeserializeSeed;
+///
+/// let json = r#"[{"address":"0x0000000000000000000000000000000000000001","topics":[],"data":"0x","blockHash":null,"blockNumber":null,"transactionHash":null,"transactionIndex":null,"logIndex":null}]"#;
+/// let mut interner = Interner::new();
+/// let mut de = serde_json::Deserializer::from_str(json);
+/// let logs = interner.logs_seed().deserialize(&mut de).unwrap();
+/// assert_eq!(logs.len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct LogsSeed<'a>(&'a mut Interner);
+
+impl<'de, 'a> DeserializeSeed<'de> for LogsSeed<'a> {
+    type Value = Vec<InternedLog>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LogsVisitor<'a>(&'a mut Interner);
+
+        impl<'de, 'a> Visitor<'de> for LogsVisitor<'a> {
+            type Value = Vec<InternedLog>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a sequence of logs")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let mut logs = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(log) = seq.next_element::<Log>()? {
+                    logs.push(self.0.log(log));
+                }
+                Ok(logs)
+            }
+        }
+
+        deserializer.deserialize_seq(LogsVisitor(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn interns_logs() {
+        let token = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        let transfer = b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        let block_hash = b256!("0000000000000000000000000000000000000000000000000000000000000042");
+        let log = |index: u64| Log {
+            inner: alloy_primitives::Log::new_unchecked(
+                token,
+                vec![transfer, B256::with_last_byte(index as u8)],
+                Bytes::from_static(&[0; 32]),
+            ),
+            block_hash: Some(block_hash),
+            block_number: Some(1),
+            log_index: Some(index),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&(0..16).map(log).collect::<Vec<_>>()).unwrap();
+
+        let mut interner = Interner::new();
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let logs = interner.logs_seed().deserialize(&mut de).unwrap();
+        assert_eq!(logs.len(), 16);
+        // one address, the signature, 16 distinct topics, one block hash, and one data payload
+        assert_eq!(interner.len(), 20);
+
+        let (first, last) = (&logs[0], &logs[15]);
+        assert!(Arc::ptr_eq(&first.address, &last.address));
+        assert!(Arc::ptr_eq(&first.topics[0], &last.topics[0]));
+        assert!(!Arc::ptr_eq(&first.topics[1], &last.topics[1]));
+        assert!(Arc::ptr_eq(first.block_hash.as_ref().unwrap(), last.block_hash.as_ref().unwrap()));
+        assert_eq!(first.data.as_ptr(), last.data.as_ptr());
+
+        assert_eq!(Log::from(last.clone()), log(15));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut interner = Interner::with_capacity(2);
+        let (a, b, c) = (B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3));
+
+        let (first_a, first_b) = (interner.hash(a), interner.hash(b));
+        // `a` is used again, so `b` is the least recently used value when `c` is interned.
+        assert!(Arc::ptr_eq(&interner.hash(a), &first_a));
+        interner.hash(c);
+        assert_eq!(interner.len(), 2);
+        assert!(Arc::ptr_eq(&interner.hash(a), &first_a));
+        assert!(!Arc::ptr_eq(&interner.hash(b), &first_b));
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(unknown_lints, non_local_definitions)]
 
-use alloy_primitives::{LogData, B256};
+use alloy_primitives::{IntoLogData, LogData, B256};
 use serde::{Deserialize, Serialize};
 
 /// Ethereum Log emitted by a transaction
@@ -82,16 +82,59 @@ impl Log<LogData> {
     }
 }
 
-impl<T> alloy_rlp::Encodable for Log<T>
-where
-    for<'a> &'a T: Into<LogData>,
-{
+/// What to do with the logs which cannot be decoded as the expected event, when decoding several
+/// logs with [`Log::decode_logs`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LogDecodePolicy {
+    /// Fail on the first log which cannot be decoded.
+    #[default]
+    Error,
+    /// Skip the logs which cannot be decoded, e.g. the logs of other events matched by a filter.
+    Skip,
+}
+
+impl Log<LogData> {
+    /// Decode the event data of `logs`, handling the logs which cannot be decoded according to
+    /// `policy`.
+    ///
+    /// Unless `T` is anonymous, the first topic of a log must be the signature hash of `T`, so
+    /// that the logs of events with the same parameters, such as `Approval` and `Transfer`, are
+    /// not decoded as `T`.
+    pub fn decode_logs<'a, T: alloy_sol_types::SolEvent>(
+        logs: impl IntoIterator<Item = &'a Self>,
+        policy: LogDecodePolicy,
+    ) -> alloy_sol_types::Result<Vec<T>> {
+        let decoded = logs.into_iter().map(|log| {
+            let data = log.data();
+            if !T::ANONYMOUS && data.topics().first() != Some(&T::SIGNATURE_HASH) {
+                return Err(alloy_sol_types::Error::InvalidLog {
+                    name: T::SIGNATURE,
+                    log: Box::new(data.clone()),
+                });
+            }
+            T::decode_log_data(data, false)
+        });
+        match policy {
+            LogDecodePolicy::Error => decoded.collect(),
+            LogDecodePolicy::Skip => Ok(decoded.filter_map(Result::ok).collect()),
+        }
+    }
+}
+
+impl<T: IntoLogData> alloy_rlp::Encodable for Log<T> {
     fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
-        self.reserialize_inner().encode(out)
+        self.consensus_log().encode(out)
     }
 
     fn length(&self) -> usize {
-        self.reserialize_inner().length()
+        self.consensus_log().length()
+    }
+}
+
+impl<T: IntoLogData> Log<T> {
+    /// Returns the consensus log, as encoded in receipts.
+    fn consensus_log(&self) -> alloy_primitives::Log {
+        alloy_primitives::Log { address: self.inner.address, data: self.inner.data.to_log_data() }
     }
 }
 
@@ -147,7 +190,7 @@ impl<T> AsMut<T> for Log<T> {
 
 #[cfg(test)]
 mod tests {
-    use alloy_primitives::{Address, Bytes};
+    use alloy_primitives::{Address, Bytes, U256};
 
     use super::*;
     use arbitrary::Arbitrary;
@@ -198,4 +241,45 @@ mod tests {
         let deserialized: Log = serde_json::from_str(&serialized).unwrap();
         assert_eq!(log, deserialized);
     }
+
+    #[test]
+    fn decode_logs() {
+        use alloy_sol_types::{sol, SolEvent};
+
+        sol! {
+            #[derive(Debug, PartialEq)]
+            event Transfer(address indexed from, address indexed to, uint256 value);
+            #[derive(Debug, PartialEq)]
+            event Approval(address indexed owner, address indexed spender, uint256 value);
+        }
+
+        let transfer =
+            Transfer { from: Address::ZERO, to: Address::with_last_byte(1), value: U256::from(2) };
+        let log = |data| Log {
+            inner: alloy_primitives::Log { address: Address::ZERO, data },
+            ..Default::default()
+        };
+        let logs = [
+            log(transfer.encode_log_data()),
+            log(LogData::new_unchecked(vec![B256::ZERO], Bytes::new())),
+            log(transfer.encode_log_data()),
+        ];
+
+        assert!(Log::decode_logs::<Transfer>(&logs, LogDecodePolicy::Error).is_err());
+        let decoded = Log::decode_logs::<Transfer>(&logs, LogDecodePolicy::Skip).unwrap();
+        assert_eq!(decoded, [transfer.clone(), transfer.clone()]);
+
+        // An event with the same parameters but another signature is not decoded.
+        let approval = Approval {
+            owner: Address::ZERO,
+            spender: Address::with_last_byte(1),
+            value: U256::from(2),
+        };
+        let logs = [log(approval.encode_log_data()), log(transfer.encode_log_data())];
+        assert!(Log::decode_logs::<Transfer>(&logs, LogDecodePolicy::Error).is_err());
+        let decoded = Log::decode_logs::<Transfer>(&logs, LogDecodePolicy::Skip).unwrap();
+        assert_eq!(decoded, [transfer]);
+        let decoded = Log::decode_logs::<Approval>(&logs, LogDecodePolicy::Skip).unwrap();
+        assert_eq!(decoded, [approval]);
+    }
 }
@@ -0,0 +1,410 @@
+//! Types for the Flashbots bundle RPC methods (`eth_sendBundle`, `eth_callBundle`) and the
+//! [MEV-Share] bundle RPC methods (`mev_sendBundle`, `mev_simBundle`).
+//!
+//! [MEV-Share]: https://github.com/flashbots/mev-share
+
+use crate::{BlockId, BlockNumberOrTag, Log};
+use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// Bundle of transactions for `eth_sendBundle`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthSendBundle {
+    /// Signed transactions to execute in the bundle.
+    pub txs: Vec<Bytes>,
+    /// The block the bundle is valid for.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub block_number: u64,
+    /// The minimum UNIX timestamp for which the bundle is valid.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u64_opt_via_ruint"
+    )]
+    pub min_timestamp: Option<u64>,
+    /// The maximum UNIX timestamp for which the bundle is valid.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u64_opt_via_ruint"
+    )]
+    pub max_timestamp: Option<u64>,
+    /// Hashes of the transactions that are allowed to revert.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reverting_tx_hashes: Vec<TxHash>,
+    /// A UUID that can be used to cancel or replace the bundle.
+    #[serde(default, rename = "replacementUuid", skip_serializing_if = "Option::is_none")]
+    pub replacement_uuid: Option<String>,
+}
+
+/// Response of `eth_sendBundle`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthBundleHash {
+    /// The hash of the bundle.
+    pub bundle_hash: B256,
+}
+
+/// Bundle of transactions for `eth_callBundle`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallBundle {
+    /// Signed transactions to simulate.
+    pub txs: Vec<Bytes>,
+    /// The block the bundle is simulated for.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub block_number: u64,
+    /// The block whose state the simulation is based on.
+    pub state_block_number: BlockNumberOrTag,
+    /// The timestamp to use for the simulation.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u64_opt_via_ruint"
+    )]
+    pub timestamp: Option<u64>,
+}
+
+/// Response of `eth_callBundle`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallBundleResponse {
+    /// The hash of the bundle.
+    pub bundle_hash: B256,
+    /// The effective gas price of the bundle.
+    pub bundle_gas_price: U256,
+    /// The change in the coinbase balance.
+    pub coinbase_diff: U256,
+    /// The amount sent to the coinbase directly.
+    pub eth_sent_to_coinbase: U256,
+    /// The gas fees paid by the bundle.
+    pub gas_fees: U256,
+    /// The results of each transaction.
+    pub results: Vec<EthCallBundleTransactionResult>,
+    /// The block whose state the simulation was based on.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub state_block_number: u64,
+    /// The total gas used by the bundle.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub total_gas_used: u64,
+}
+
+/// Result of a single transaction of an `eth_callBundle` simulation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallBundleTransactionResult {
+    /// The change in the coinbase balance.
+    pub coinbase_diff: U256,
+    /// The amount sent to the coinbase directly.
+    pub eth_sent_to_coinbase: U256,
+    /// The sender of the transaction.
+    pub from_address: Address,
+    /// The gas fees paid by the transaction.
+    pub gas_fees: U256,
+    /// The effective gas price of the transaction.
+    pub gas_price: U256,
+    /// The gas used by the transaction.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub gas_used: u64,
+    /// The recipient of the transaction.
+    pub to_address: Option<Address>,
+    /// The hash of the transaction.
+    pub tx_hash: TxHash,
+    /// The output of the transaction, if it succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Bytes>,
+    /// The revert reason, if the transaction reverted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert: Option<String>,
+    /// The error, if the transaction failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The version of the MEV-Share API.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProtocolVersion {
+    /// Version `v0.1`.
+    #[default]
+    #[serde(rename = "v0.1")]
+    V0_1,
+    /// Version `beta-1`.
+    #[serde(rename = "beta-1")]
+    Beta1,
+}
+
+/// A bundle for `mev_sendBundle` and `mev_simBundle`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleRequest {
+    /// The version of the MEV-Share API.
+    #[serde(rename = "version")]
+    pub protocol_version: ProtocolVersion,
+    /// The blocks the bundle can be included in.
+    pub inclusion: Inclusion,
+    /// The transactions and nested bundles of the bundle.
+    #[serde(rename = "body")]
+    pub bundle_body: Vec<BundleItem>,
+    /// Requirements for the bundle to be included.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validity: Option<Validity>,
+    /// Preferences on what data should be shared about the bundle and its transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub privacy: Option<Privacy>,
+}
+
+/// The range of blocks a MEV-Share bundle can be included in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Inclusion {
+    /// The first block the bundle is valid for.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub block: u64,
+    /// The last block the bundle is valid for.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u64_opt_via_ruint"
+    )]
+    pub max_block: Option<u64>,
+}
+
+impl Inclusion {
+    /// Creates an inclusion range for the given block only.
+    pub const fn at_block(block: u64) -> Self {
+        Self { block, max_block: None }
+    }
+}
+
+/// An item of a MEV-Share bundle body.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BundleItem {
+    /// The hash of a transaction from the MEV-Share event stream.
+    #[serde(rename_all = "camelCase")]
+    Hash {
+        /// The hash of the transaction.
+        hash: TxHash,
+    },
+    /// A signed transaction.
+    #[serde(rename_all = "camelCase")]
+    Tx {
+        /// The signed transaction.
+        tx: Bytes,
+        /// Whether the transaction is allowed to revert.
+        can_revert: bool,
+    },
+    /// A nested bundle.
+    Bundle {
+        /// The nested bundle.
+        bundle: Box<SendBundleRequest>,
+    },
+}
+
+/// Requirements for a MEV-Share bundle to be included.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Validity {
+    /// The minimum refund the bundle must pay to the originators of its transactions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refund: Vec<Refund>,
+    /// How the refund of the bundle should be split between addresses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub refund_config: Vec<RefundConfig>,
+}
+
+/// A refund of a MEV-Share bundle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Refund {
+    /// The index of the transaction in the bundle body.
+    pub body_idx: u64,
+    /// The minimum percentage of the profit to refund.
+    pub percent: u64,
+}
+
+/// A refund recipient of a MEV-Share bundle.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundConfig {
+    /// The address to refund.
+    pub address: Address,
+    /// The percentage of the refund to send to the address.
+    pub percent: u64,
+}
+
+/// Preferences on what data should be shared about a MEV-Share bundle.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Privacy {
+    /// The data of the bundle to share with searchers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hints: Option<Vec<PrivacyHint>>,
+    /// The builders allowed to receive the bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builders: Option<Vec<String>>,
+}
+
+/// A piece of data of a MEV-Share bundle that may be shared with searchers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PrivacyHint {
+    /// The calldata of the transactions.
+    Calldata,
+    /// The recipient addresses of the transactions.
+    ContractAddress,
+    /// The logs emitted by the transactions.
+    Logs,
+    /// The function selectors of the transactions.
+    FunctionSelector,
+    /// The hash of the bundle.
+    Hash,
+    /// The hashes of the transactions.
+    TxHash,
+}
+
+/// Response of `mev_sendBundle`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendBundleResponse {
+    /// The hash of the bundle.
+    pub bundle_hash: B256,
+}
+
+/// Overrides for the block environment of `mev_simBundle`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBundleOverrides {
+    /// The block whose state the simulation is based on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_block: Option<BlockId>,
+    /// The number of the simulated block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u64_opt_via_ruint"
+    )]
+    pub block_number: Option<u64>,
+    /// The coinbase of the simulated block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+    /// The timestamp of the simulated block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u64_opt_via_ruint"
+    )]
+    pub timestamp: Option<u64>,
+    /// The gas limit of the simulated block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u64_opt_via_ruint"
+    )]
+    pub gas_limit: Option<u64>,
+    /// The base fee of the simulated block.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u128_opt_via_ruint"
+    )]
+    pub base_fee: Option<u128>,
+    /// The simulation timeout, in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+/// Response of `mev_simBundle`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBundleResponse {
+    /// Whether the simulation succeeded.
+    pub success: bool,
+    /// The error, if the simulation failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The block whose state the simulation was based on.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub state_block: u64,
+    /// The gas price of the bundle relative to the MEV it extracts.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub mev_gas_price: u64,
+    /// The profit of the bundle.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub profit: u64,
+    /// The value that can be refunded to the originators of the transactions.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub refundable_value: u64,
+    /// The gas used by the bundle.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub gas_used: u64,
+    /// The logs emitted by the bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logs: Option<Vec<SimBundleLogs>>,
+}
+
+/// Logs emitted by an item of a simulated MEV-Share bundle.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBundleLogs {
+    /// The logs of a transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_logs: Option<Vec<Log>>,
+    /// The logs of a nested bundle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bundle_logs: Option<Vec<SimBundleLogs>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_send_bundle_request() {
+        let json = r#"{"version":"v0.1","inclusion":{"block":"0x1","maxBlock":"0x3"},"body":[{"hash":"0xd4c2c9eb4ded9eb6a3f3e10ab7b9b2d8bdbe6c2ab7ebd31465b37e2eedaf1ddb"},{"tx":"0x02f86b","canRevert":false}],"privacy":{"hints":["calldata","tx_hash"]}}"#;
+        let request: SendBundleRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(request.inclusion, Inclusion { block: 1, max_block: Some(3) });
+        assert!(matches!(request.bundle_body[0], BundleItem::Hash { .. }));
+        assert!(matches!(request.bundle_body[1], BundleItem::Tx { can_revert: false, .. }));
+        assert_eq!(serde_json::to_string(&request).unwrap(), json);
+    }
+
+    #[test]
+    fn serde_eth_send_bundle() {
+        let json = r#"{"txs":["0x02f86b"],"blockNumber":"0xb63dcd","minTimestamp":"0x0"}"#;
+        let bundle: EthSendBundle = serde_json::from_str(json).unwrap();
+        assert_eq!(bundle.block_number, 0xb63dcd);
+        assert_eq!(serde_json::to_string(&bundle).unwrap(), json);
+    }
+
+    #[test]
+    fn deserialize_call_bundle_response() {
+        let json = r#"{
+            "bundleGasPrice": "476190476193",
+            "bundleHash": "0x73b1e258c7a42fd0230b2fd05529c5d4b6fcb66c227783f8bece8aeacdd1db2e",
+            "coinbaseDiff": "20000000000126000",
+            "ethSentToCoinbase": "20000000000000000",
+            "gasFees": "126000",
+            "results": [
+                {
+                    "coinbaseDiff": "10000000000063000",
+                    "ethSentToCoinbase": "10000000000000000",
+                    "fromAddress": "0x02a727155aef8609c9f7f2179b2a1f560b39f5a0",
+                    "gasFees": "63000",
+                    "gasPrice": "476190476193",
+                    "gasUsed": 21000,
+                    "toAddress": "0x73625f59cadc5009cb458b751b3e7b6b48c06f2c",
+                    "txHash": "0x669b4704a7d993a946cdd6e2f95233f308ce0c4649d2e04944e8299efcaa098a",
+                    "value": "0x"
+                }
+            ],
+            "stateBlockNumber": 5221585,
+            "totalGasUsed": 42000
+        }"#;
+        let response: EthCallBundleResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.bundle_gas_price, U256::from(476190476193u64));
+        assert_eq!(response.results[0].gas_used, 21000);
+        assert_eq!(response.state_block_number, 5221585);
+    }
+}
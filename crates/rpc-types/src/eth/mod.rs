@@ -2,16 +2,28 @@
 
 mod account;
 pub mod admin;
+#[cfg(feature = "arbitrum")]
+pub mod arbitrum;
 mod block;
+#[cfg(feature = "bsc")]
+pub mod bsc;
 mod call;
+pub mod erc4337;
 pub mod error;
 mod fee;
 mod filter;
 mod index;
+pub mod interner;
 mod log;
+pub mod mev;
 pub mod other;
+#[cfg(feature = "polygon")]
+pub mod polygon;
 pub mod pubsub;
 pub mod raw_log;
+#[cfg(feature = "schemars")]
+pub mod schema;
+pub mod simulate;
 pub mod state;
 mod syncing;
 pub mod transaction;
@@ -22,8 +34,8 @@ pub use account::*;
 pub use admin::NodeInfo;
 pub use alloy_eips::eip4895::Withdrawal;
 pub use block::*;
-pub use call::{Bundle, EthCallResponse, StateContext};
-pub use fee::{FeeHistory, TxGasAndReward};
+pub use call::{Bundle, EthCallResponse, StateContext, TransactionIndex};
+pub use fee::{FeeHistory, FeeHistoryBlock, FeeHistoryError, TxGasAndReward};
 pub use filter::*;
 pub use index::Index;
 pub use log::*;
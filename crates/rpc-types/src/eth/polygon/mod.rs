@@ -0,0 +1,91 @@
+//! Polygon PoS specific types.
+//!
+//! Bor, the execution client of Polygon PoS, appends a state-sync transaction to the blocks
+//! ending a sprint, to apply the deposits and messages bridged from Ethereum. These transactions
+//! are not signed, and have a zero sender and gas price. Older versions of Bor return them as
+//! legacy transactions, while newer versions use the dedicated [`PolygonTxType::StateSync`]
+//! type, which has no Ethereum receipt envelope.
+
+use alloy_eips::eip2718::Eip2718Error;
+use std::fmt;
+
+mod receipt;
+pub use receipt::PolygonTransactionReceipt;
+
+mod request;
+pub use request::PolygonTransactionRequest;
+
+mod transaction;
+pub use transaction::PolygonTransaction;
+
+/// Polygon PoS `TransactionType` flags.
+///
+/// EIP-4844 blob transactions are not supported.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PolygonTxType {
+    /// Legacy transaction type.
+    Legacy = 0,
+    /// EIP-2930 transaction type.
+    Eip2930 = 1,
+    /// EIP-1559 transaction type.
+    Eip1559 = 2,
+    /// State-sync transaction, applying the messages bridged from Ethereum.
+    StateSync = 0x7f,
+}
+
+impl PolygonTxType {
+    /// Returns true if transactions of this type are created by Bor instead of being signed by a
+    /// user.
+    pub const fn is_system(&self) -> bool {
+        matches!(self, Self::StateSync)
+    }
+}
+
+impl From<PolygonTxType> for u8 {
+    fn from(value: PolygonTxType) -> Self {
+        value as u8
+    }
+}
+
+impl fmt::Display for PolygonTxType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Legacy => write!(f, "Legacy"),
+            Self::Eip2930 => write!(f, "EIP-2930"),
+            Self::Eip1559 => write!(f, "EIP-1559"),
+            Self::StateSync => write!(f, "StateSync"),
+        }
+    }
+}
+
+impl TryFrom<u8> for PolygonTxType {
+    type Error = Eip2718Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::Legacy,
+            1 => Self::Eip2930,
+            2 => Self::Eip1559,
+            0x7f => Self::StateSync,
+            _ => return Err(Eip2718Error::UnexpectedType(value)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tx_type_roundtrip() {
+        for ty in 0..=u8::MAX {
+            if let Ok(tx_type) = PolygonTxType::try_from(ty) {
+                assert_eq!(u8::from(tx_type), ty);
+            }
+        }
+        assert!(PolygonTxType::StateSync.is_system());
+        assert!(!PolygonTxType::Legacy.is_system());
+        assert!(PolygonTxType::try_from(3).is_err());
+    }
+}
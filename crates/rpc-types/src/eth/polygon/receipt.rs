@@ -0,0 +1,63 @@
+use super::PolygonTxType;
+use crate::{Log, TransactionReceipt};
+use alloy_consensus::AnyReceiptEnvelope;
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Transaction receipt returned by Bor nodes.
+///
+/// The receipt is generic over [`AnyReceiptEnvelope`], since receipts of
+/// [`PolygonTxType::StateSync`] transactions have no Ethereum envelope.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolygonTransactionReceipt {
+    /// The Ethereum receipt fields.
+    #[serde(flatten)]
+    pub inner: TransactionReceipt<AnyReceiptEnvelope<Log>>,
+}
+
+impl PolygonTransactionReceipt {
+    /// Returns the [`PolygonTxType`] of the transaction, or `None` if it is unknown.
+    pub fn tx_type(&self) -> Option<PolygonTxType> {
+        self.inner.inner.r#type.try_into().ok()
+    }
+
+    /// Returns true if this is the receipt of a state-sync transaction.
+    ///
+    /// See [`PolygonTransaction::is_state_sync`](super::PolygonTransaction::is_state_sync).
+    pub fn is_state_sync(&self) -> bool {
+        self.tx_type().is_some_and(|ty| ty.is_system()) || self.inner.from == Address::ZERO
+    }
+}
+
+impl Deref for PolygonTransactionReceipt {
+    type Target = TransactionReceipt<AnyReceiptEnvelope<Log>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_state_sync_receipt() {
+        let receipt = r#"{"blockHash":"0x8b2a3c5a1f1e85a0a3b1c9d3e4f5061728394a5b6c7d8e9fa0b1c2d3e4f50617","blockNumber":"0x3b9aca0","contractAddress":null,"cumulativeGasUsed":"0x0","effectiveGasPrice":"0x0","from":"0x0000000000000000000000000000000000000000","gasUsed":"0x0","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","status":"0x1","to":"0x0000000000000000000000000000000000000000","transactionHash":"0x3f2a9b8c7d6e5f4031a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708","transactionIndex":"0x0","type":"0x7f"}"#;
+
+        // The Ethereum receipt envelope has no state-sync variant.
+        assert!(serde_json::from_str::<TransactionReceipt>(receipt).is_err());
+
+        let receipt = serde_json::from_str::<PolygonTransactionReceipt>(receipt).unwrap();
+        assert_eq!(receipt.tx_type(), Some(PolygonTxType::StateSync));
+        assert!(receipt.is_state_sync());
+        assert!(receipt.inner.inner.status());
+
+        let serialized = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(
+            serde_json::from_value::<PolygonTransactionReceipt>(serialized).unwrap(),
+            receipt
+        );
+    }
+}
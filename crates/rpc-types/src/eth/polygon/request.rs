@@ -0,0 +1,48 @@
+use crate::TransactionRequest;
+use alloy_consensus::{TxEnvelope, TypedTransaction};
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Transaction request for Polygon.
+///
+/// Users can only submit the Ethereum transaction types, so this has the same fields as
+/// [`TransactionRequest`]. It is a distinct type so that it can be built with the Polygon
+/// transaction types.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PolygonTransactionRequest {
+    /// The Ethereum transaction request fields.
+    #[serde(flatten)]
+    pub inner: TransactionRequest,
+}
+
+impl Deref for PolygonTransactionRequest {
+    type Target = TransactionRequest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for PolygonTransactionRequest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl From<TransactionRequest> for PolygonTransactionRequest {
+    fn from(inner: TransactionRequest) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<TypedTransaction> for PolygonTransactionRequest {
+    fn from(tx: TypedTransaction) -> Self {
+        Self { inner: tx.into() }
+    }
+}
+
+impl From<TxEnvelope> for PolygonTransactionRequest {
+    fn from(envelope: TxEnvelope) -> Self {
+        Self { inner: envelope.into() }
+    }
+}
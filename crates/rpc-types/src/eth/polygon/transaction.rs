@@ -0,0 +1,75 @@
+use super::PolygonTxType;
+use crate::Transaction;
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Transaction object returned by Bor nodes.
+///
+/// State-sync transactions are returned along with the transactions of users, with a zero sender,
+/// gas and gas price, and either the legacy or the [`PolygonTxType::StateSync`] type.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolygonTransaction {
+    /// The Ethereum transaction fields.
+    #[serde(flatten)]
+    pub inner: Transaction,
+}
+
+impl PolygonTransaction {
+    /// Returns the [`PolygonTxType`] of the transaction, or `None` if it is unknown.
+    pub fn tx_type(&self) -> Option<PolygonTxType> {
+        self.inner.transaction_type.unwrap_or_default().try_into().ok()
+    }
+
+    /// Returns true if this is a state-sync transaction, created by Bor instead of being signed
+    /// by a user.
+    ///
+    /// State-sync transactions returned as legacy transactions are recognized by their zero
+    /// sender, which no user can sign for.
+    pub fn is_state_sync(&self) -> bool {
+        self.tx_type().is_some_and(|ty| ty.is_system()) || self.inner.from == Address::ZERO
+    }
+}
+
+impl Deref for PolygonTransaction {
+    type Target = Transaction;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for PolygonTransaction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl From<Transaction> for PolygonTransaction {
+    fn from(inner: Transaction) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_state_sync() {
+        let rpc_tx = r#"{"blockHash":"0x8b2a3c5a1f1e85a0a3b1c9d3e4f5061728394a5b6c7d8e9fa0b1c2d3e4f50617","blockNumber":"0x3b9aca0","from":"0x0000000000000000000000000000000000000000","gas":"0x0","gasPrice":"0x0","hash":"0x3f2a9b8c7d6e5f4031a2b3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e6f708","input":"0x","nonce":"0x0","to":"0x0000000000000000000000000000000000000000","transactionIndex":"0x0","value":"0x0","type":"0x0","v":"0x0","r":"0x0","s":"0x0"}"#;
+
+        let tx = serde_json::from_str::<PolygonTransaction>(rpc_tx).unwrap();
+        assert_eq!(tx.tx_type(), Some(PolygonTxType::Legacy));
+        assert!(tx.is_state_sync());
+        assert_eq!(tx.gas_price, Some(0));
+
+        let typed = rpc_tx.replace(r#""type":"0x0""#, r#""type":"0x7f""#);
+        let tx = serde_json::from_str::<PolygonTransaction>(&typed).unwrap();
+        assert_eq!(tx.tx_type(), Some(PolygonTxType::StateSync));
+        assert!(tx.is_state_sync());
+
+        let serialized = serde_json::to_value(&tx).unwrap();
+        assert_eq!(serde_json::from_value::<PolygonTransaction>(serialized).unwrap(), tx);
+    }
+}
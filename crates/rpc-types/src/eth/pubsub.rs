@@ -1,10 +1,10 @@
 //! Ethereum types for pub-sub
 
 use crate::{
-    eth::{Filter, Transaction},
+    eth::{Filter, SyncInfo, SyncStatus, Transaction},
     Log, RichHeader,
 };
-use alloy_primitives::B256;
+use alloy_primitives::{B256, U256};
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Subscription result.
@@ -24,7 +24,12 @@ pub enum SubscriptionResult {
 }
 
 /// Response type for a SyncStatus subscription.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Clients disagree on the shape of the notification: some send a flat [`SyncStatusMetadata`]
+/// object, geth nests the progress under a `status` key, and all of them send a bare `false` once
+/// syncing is done. Deserialization accepts each of these, with block numbers either as integers
+/// or hex quantities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 #[serde(untagged)]
 pub enum PubSubSyncStatus {
     /// If not currently syncing, this should always be `false`.
@@ -33,6 +38,74 @@ pub enum PubSubSyncStatus {
     Detailed(SyncStatusMetadata),
 }
 
+impl PubSubSyncStatus {
+    /// Returns `true` if the node is syncing.
+    pub const fn is_syncing(&self) -> bool {
+        match self {
+            Self::Simple(syncing) => *syncing,
+            Self::Detailed(metadata) => metadata.syncing,
+        }
+    }
+
+    /// Returns the sync progress, if the notification carries it.
+    pub const fn metadata(&self) -> Option<&SyncStatusMetadata> {
+        match self {
+            Self::Simple(_) => None,
+            Self::Detailed(metadata) => Some(metadata),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PubSubSyncStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        /// The progress nested by geth, with Go's default field names.
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Progress {
+            #[serde(
+                alias = "StartingBlock",
+                deserialize_with = "alloy_serde::num::u64_via_ruint::deserialize"
+            )]
+            starting_block: u64,
+            #[serde(
+                alias = "CurrentBlock",
+                deserialize_with = "alloy_serde::num::u64_via_ruint::deserialize"
+            )]
+            current_block: u64,
+            #[serde(
+                default,
+                alias = "HighestBlock",
+                deserialize_with = "alloy_serde::num::u64_opt_via_ruint::deserialize"
+            )]
+            highest_block: Option<u64>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Simple(bool),
+            Detailed(SyncStatusMetadata),
+            Nested { syncing: bool, status: Option<Progress> },
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Simple(syncing) | Repr::Nested { syncing, status: None } => Self::Simple(syncing),
+            Repr::Detailed(metadata) => Self::Detailed(metadata),
+            Repr::Nested { syncing, status: Some(progress) } => {
+                Self::Detailed(SyncStatusMetadata {
+                    syncing,
+                    starting_block: progress.starting_block,
+                    current_block: progress.current_block,
+                    highest_block: progress.highest_block,
+                })
+            }
+        })
+    }
+}
+
 /// Sync status metadata.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -40,14 +113,41 @@ pub struct SyncStatusMetadata {
     /// Whether the node is currently syncing.
     pub syncing: bool,
     /// The starting block.
+    #[serde(deserialize_with = "alloy_serde::num::u64_via_ruint::deserialize")]
     pub starting_block: u64,
     /// The current block.
+    #[serde(deserialize_with = "alloy_serde::num::u64_via_ruint::deserialize")]
     pub current_block: u64,
     /// The highest block.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "alloy_serde::num::u64_opt_via_ruint::deserialize"
+    )]
     pub highest_block: Option<u64>,
 }
 
+impl From<PubSubSyncStatus> for SyncStatus {
+    /// Converts a notification to the status returned by `eth_syncing`.
+    ///
+    /// A bare `true` carries no progress, and converts to a default [`SyncInfo`]. A missing
+    /// highest block defaults to the current block.
+    fn from(status: PubSubSyncStatus) -> Self {
+        match status {
+            PubSubSyncStatus::Simple(false) => Self::None,
+            PubSubSyncStatus::Simple(true) => Self::Info(SyncInfo::default()),
+            PubSubSyncStatus::Detailed(metadata) if !metadata.syncing => Self::None,
+            PubSubSyncStatus::Detailed(metadata) => Self::Info(SyncInfo {
+                starting_block: U256::from(metadata.starting_block),
+                current_block: U256::from(metadata.current_block),
+                highest_block: U256::from(metadata.highest_block.unwrap_or(metadata.current_block)),
+                warp_chunks_amount: None,
+                warp_chunks_processed: None,
+            }),
+        }
+    }
+}
+
 impl Serialize for SubscriptionResult {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -169,4 +269,57 @@ mod tests {
         let s: Params = serde_json::from_str("null").unwrap();
         assert_eq!(s, Params::None);
     }
+
+    #[test]
+    fn sync_status_serde() {
+        let done: PubSubSyncStatus = serde_json::from_str("false").unwrap();
+        assert_eq!(done, PubSubSyncStatus::Simple(false));
+        assert!(!done.is_syncing());
+        let done: PubSubSyncStatus = serde_json::from_str(r#"{"syncing":false}"#).unwrap();
+        assert_eq!(done, PubSubSyncStatus::Simple(false));
+
+        let expected = PubSubSyncStatus::Detailed(SyncStatusMetadata {
+            syncing: true,
+            starting_block: 0,
+            current_block: 4096,
+            highest_block: Some(8192),
+        });
+        let flat = r#"{"syncing":true,"startingBlock":0,"currentBlock":4096,"highestBlock":8192}"#;
+        assert_eq!(serde_json::from_str::<PubSubSyncStatus>(flat).unwrap(), expected);
+        assert_eq!(serde_json::to_string(&expected).unwrap(), flat);
+        let hex = r#"{"syncing":true,"startingBlock":"0x0","currentBlock":"0x1000","highestBlock":"0x2000"}"#;
+        assert_eq!(serde_json::from_str::<PubSubSyncStatus>(hex).unwrap(), expected);
+
+        let geth = r#"{"syncing":true,"status":{"StartingBlock":0,"CurrentBlock":4096,"HighestBlock":8192,"PulledStates":0,"KnownStates":0}}"#;
+        let status: PubSubSyncStatus = serde_json::from_str(geth).unwrap();
+        assert_eq!(status, expected);
+        assert!(status.is_syncing());
+        assert_eq!(status.metadata().unwrap().current_block, 4096);
+
+        let result: SubscriptionResult = serde_json::from_str(geth).unwrap();
+        assert_eq!(result, SubscriptionResult::SyncState(expected));
+    }
+
+    #[test]
+    fn sync_status_conversion() {
+        let status = SyncStatus::from(PubSubSyncStatus::Detailed(SyncStatusMetadata {
+            syncing: true,
+            starting_block: 1,
+            current_block: 4096,
+            highest_block: None,
+        }));
+        let info = status.info().unwrap();
+        assert_eq!(info.starting_block, U256::from(1));
+        assert_eq!(info.highest_block, U256::from(4096));
+        assert!(!SyncStatus::from(PubSubSyncStatus::Simple(false)).is_syncing());
+
+        // `eth_syncing` returns either `false` or the progress object.
+        let status: SyncStatus = serde_json::from_str(
+            r#"{"startingBlock":"0x0","currentBlock":"0x1000","highestBlock":"0x2000"}"#,
+        )
+        .unwrap();
+        assert!(status.is_syncing());
+        assert_eq!(serde_json::from_str::<SyncStatus>("false").unwrap(), SyncStatus::None);
+        assert!(serde_json::from_str::<SyncStatus>("true").is_err());
+    }
 }
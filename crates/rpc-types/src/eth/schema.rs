@@ -0,0 +1,470 @@
+//! [JSON Schema](https://json-schema.org) definitions of the RPC types.
+//!
+//! The schemas describe the JSON-RPC encoding of the types, as produced by their serde
+//! implementations: quantities are `0x`-prefixed hex strings without leading zeroes, binary data
+//! are `0x`-prefixed hex strings, and optional fields may be omitted or `null`. The required
+//! properties are the ones needed to deserialize a value, and objects may have other properties,
+//! as nodes of other networks add their own.
+//!
+//! Any type implementing [`JsonSchema`] can be turned into a standalone schema with
+//! [`schemars::schema_for!`], and [`definitions`] collects the schemas of the main types of the
+//! `eth` namespace, e.g. to validate the responses of another implementation:
+//!
+//! ```
+//! use alloy_rpc_types::{schema, Block};
+//!
+//! let schema = schemars::schema_for!(Block);
+//! assert_eq!(schema.get("title").unwrap(), "Block");
+//!
+//! let definitions = schema::definitions();
+//! assert!(definitions.contains_key("TransactionReceipt"));
+//! ```
+
+use crate::{
+    Block, BlockTransactions, Header, Log, Signature, Transaction, TransactionReceipt,
+    TransactionRequest,
+};
+use alloy_consensus::ReceiptEnvelope;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+
+/// Returns the schemas of the main `eth` types, keyed by name, along with the schemas they
+/// reference.
+pub fn definitions() -> Map<String, Value> {
+    let mut generator = SchemaGenerator::default();
+    generator.subschema_for::<Block>();
+    generator.subschema_for::<Log>();
+    generator.subschema_for::<TransactionReceipt>();
+    generator.subschema_for::<TransactionRequest>();
+    generator.take_definitions(true)
+}
+
+/// A hex encoded quantity.
+fn quantity() -> Schema {
+    json_schema!({ "type": "string", "pattern": "^0x(0|[1-9a-fA-F][0-9a-fA-F]*)$" })
+}
+
+/// Hex encoded binary data of any length.
+fn data() -> Schema {
+    json_schema!({ "type": "string", "pattern": "^0x([0-9a-fA-F]{2})*$" })
+}
+
+/// Hex encoded binary data of `len` bytes.
+fn fixed_bytes(len: usize) -> Schema {
+    json_schema!({ "type": "string", "pattern": format!("^0x[0-9a-fA-F]{{{}}}$", len * 2) })
+}
+
+fn address() -> Schema {
+    fixed_bytes(20)
+}
+
+fn hash() -> Schema {
+    fixed_bytes(32)
+}
+
+fn bloom() -> Schema {
+    fixed_bytes(256)
+}
+
+/// `schema`, or `null`.
+fn nullable(schema: Schema) -> Schema {
+    json_schema!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn array(items: Schema) -> Schema {
+    json_schema!({ "type": "array", "items": items })
+}
+
+/// An object with the given properties, of which `required` must be present.
+fn object(
+    properties: impl IntoIterator<Item = (&'static str, Schema)>,
+    required: &[&str],
+) -> Schema {
+    let properties: Map<String, Value> =
+        properties.into_iter().map(|(name, schema)| (name.to_string(), schema.into())).collect();
+    json_schema!({ "type": "object", "properties": properties, "required": required })
+}
+
+/// Merges the properties and required properties of `schemas` into a single object, as done by
+/// `#[serde(flatten)]`.
+fn merge(schemas: impl IntoIterator<Item = Schema>) -> Schema {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for schema in schemas {
+        let mut schema = schema.to_value();
+        if let Some(Value::Object(props)) = schema.get_mut("properties").map(Value::take) {
+            properties.extend(props);
+        }
+        if let Some(Value::Array(names)) = schema.get_mut("required").map(Value::take) {
+            required.extend(names);
+        }
+    }
+    json_schema!({ "type": "object", "properties": properties, "required": required })
+}
+
+fn access_list() -> Schema {
+    array(object(
+        [("address", address()), ("storageKeys", array(hash()))],
+        &["address", "storageKeys"],
+    ))
+}
+
+impl JsonSchema for Header {
+    fn schema_name() -> Cow<'static, str> {
+        "Header".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        object(
+            [
+                ("hash", nullable(hash())),
+                ("parentHash", hash()),
+                ("sha3Uncles", hash()),
+                ("miner", address()),
+                ("stateRoot", hash()),
+                ("transactionsRoot", hash()),
+                ("receiptsRoot", hash()),
+                ("logsBloom", bloom()),
+                ("difficulty", quantity()),
+                ("number", nullable(quantity())),
+                ("gasLimit", quantity()),
+                ("gasUsed", quantity()),
+                ("timestamp", quantity()),
+                ("totalDifficulty", nullable(quantity())),
+                ("extraData", data()),
+                ("mixHash", nullable(hash())),
+                ("nonce", nullable(fixed_bytes(8))),
+                ("baseFeePerGas", nullable(quantity())),
+                ("withdrawalsRoot", nullable(hash())),
+                ("blobGasUsed", nullable(quantity())),
+                ("excessBlobGas", nullable(quantity())),
+                ("parentBeaconBlockRoot", nullable(hash())),
+                ("requestsRoot", nullable(hash())),
+                ("requestsHash", nullable(hash())),
+            ],
+            &[
+                "parentHash",
+                "sha3Uncles",
+                "miner",
+                "stateRoot",
+                "transactionsRoot",
+                "receiptsRoot",
+                "logsBloom",
+                "difficulty",
+                "extraData",
+            ],
+        )
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for BlockTransactions<T> {
+    fn schema_name() -> Cow<'static, str> {
+        format!("BlockTransactions_{}", T::schema_name()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "The hashes of the transactions, or the full transactions.",
+            "anyOf": [array(hash()), array(generator.subschema_for::<T>())],
+        })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for Block<T> {
+    fn schema_name() -> Cow<'static, str> {
+        match T::schema_name() {
+            name if name == Transaction::schema_name() => "Block".into(),
+            name => format!("Block_{name}").into(),
+        }
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let withdrawal = object(
+            [
+                ("index", quantity()),
+                ("validatorIndex", quantity()),
+                ("address", address()),
+                ("amount", quantity()),
+            ],
+            &["index", "validatorIndex", "address", "amount"],
+        );
+        let request = json_schema!({
+            "anyOf": [
+                object(
+                    [
+                        ("pubkey", fixed_bytes(48)),
+                        ("withdrawalCredentials", hash()),
+                        ("amount", quantity()),
+                        ("signature", fixed_bytes(96)),
+                        ("index", quantity()),
+                    ],
+                    &["pubkey", "withdrawalCredentials", "amount", "signature", "index"],
+                ),
+                object(
+                    [
+                        ("sourceAddress", address()),
+                        ("validatorPublicKey", fixed_bytes(48)),
+                        ("amount", quantity()),
+                    ],
+                    &["sourceAddress", "validatorPublicKey", "amount"],
+                ),
+                object(
+                    [
+                        ("sourceAddress", address()),
+                        ("sourcePubkey", fixed_bytes(48)),
+                        ("targetPubkey", fixed_bytes(48)),
+                    ],
+                    &["sourceAddress", "sourcePubkey", "targetPubkey"],
+                ),
+            ],
+        });
+        merge([
+            Header::json_schema(generator),
+            object(
+                [
+                    ("uncles", array(hash())),
+                    ("transactions", generator.subschema_for::<BlockTransactions<T>>()),
+                    ("size", nullable(quantity())),
+                    ("withdrawals", nullable(array(withdrawal))),
+                    ("requests", nullable(array(request))),
+                ],
+                &[],
+            ),
+        ])
+    }
+}
+
+impl JsonSchema for Signature {
+    fn schema_name() -> Cow<'static, str> {
+        "Signature".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        object(
+            [
+                ("r", quantity()),
+                ("s", quantity()),
+                ("v", quantity()),
+                ("yParity", json_schema!({ "enum": ["0x0", "0x1"] })),
+            ],
+            &["r", "s", "v"],
+        )
+    }
+}
+
+impl JsonSchema for Transaction {
+    fn schema_name() -> Cow<'static, str> {
+        "Transaction".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let transaction = object(
+            [
+                ("hash", hash()),
+                ("nonce", quantity()),
+                ("blockHash", nullable(hash())),
+                ("blockNumber", nullable(quantity())),
+                ("transactionIndex", nullable(quantity())),
+                ("from", address()),
+                ("to", nullable(address())),
+                ("value", quantity()),
+                ("gasPrice", nullable(quantity())),
+                ("gas", quantity()),
+                ("maxFeePerGas", nullable(quantity())),
+                ("maxPriorityFeePerGas", nullable(quantity())),
+                ("maxFeePerBlobGas", nullable(quantity())),
+                ("input", data()),
+                ("chainId", nullable(quantity())),
+                ("blobVersionedHashes", nullable(array(hash()))),
+                ("accessList", nullable(access_list())),
+                ("type", nullable(quantity())),
+            ],
+            &["hash", "nonce", "from", "value", "gas", "input"],
+        );
+        // The signature is optional as a whole, so none of its fields is required.
+        let mut signature = Signature::json_schema(generator);
+        signature.remove("required");
+        merge([transaction, signature])
+    }
+}
+
+impl JsonSchema for Log {
+    fn schema_name() -> Cow<'static, str> {
+        "Log".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        object(
+            [
+                ("address", address()),
+                ("topics", array(hash())),
+                ("data", data()),
+                ("blockHash", nullable(hash())),
+                ("blockNumber", nullable(quantity())),
+                ("blockTimestamp", nullable(quantity())),
+                ("transactionHash", nullable(hash())),
+                ("transactionIndex", nullable(quantity())),
+                ("logIndex", nullable(quantity())),
+                ("removed", json_schema!({ "type": "boolean" })),
+            ],
+            &["address", "topics", "data", "blockNumber", "transactionIndex", "logIndex"],
+        )
+    }
+}
+
+impl JsonSchema for TransactionReceipt<ReceiptEnvelope<Log>> {
+    fn schema_name() -> Cow<'static, str> {
+        "TransactionReceipt".into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        let envelope = object(
+            [
+                ("type", json_schema!({ "enum": ["0x0", "0x1", "0x2", "0x3"] })),
+                ("status", json_schema!({ "enum": ["0x0", "0x1"] })),
+                ("cumulativeGasUsed", quantity()),
+                ("logs", array(generator.subschema_for::<Log>())),
+                ("logsBloom", bloom()),
+            ],
+            &["type", "status", "cumulativeGasUsed", "logs", "logsBloom"],
+        );
+        let receipt = object(
+            [
+                ("transactionHash", hash()),
+                ("transactionIndex", nullable(quantity())),
+                ("blockHash", nullable(hash())),
+                ("blockNumber", nullable(quantity())),
+                ("gasUsed", quantity()),
+                ("effectiveGasPrice", quantity()),
+                ("blobGasUsed", nullable(quantity())),
+                ("blobGasPrice", nullable(quantity())),
+                ("from", address()),
+                ("to", nullable(address())),
+                ("contractAddress", nullable(address())),
+                ("root", nullable(hash())),
+            ],
+            &["transactionHash", "gasUsed", "effectiveGasPrice", "from"],
+        );
+        merge([envelope, receipt])
+    }
+}
+
+impl JsonSchema for TransactionRequest {
+    fn schema_name() -> Cow<'static, str> {
+        "TransactionRequest".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        let sidecar = object(
+            [
+                ("blobs", array(fixed_bytes(alloy_eips::eip4844::BYTES_PER_BLOB))),
+                ("commitments", array(fixed_bytes(48))),
+                ("proofs", array(fixed_bytes(48))),
+            ],
+            &["blobs", "commitments", "proofs"],
+        );
+        object(
+            [
+                ("from", nullable(address())),
+                // an empty `to` creates a contract
+                ("to", nullable(json_schema!({ "anyOf": [address(), data()] }))),
+                ("gasPrice", nullable(quantity())),
+                ("maxFeePerGas", nullable(quantity())),
+                ("maxPriorityFeePerGas", nullable(quantity())),
+                ("maxFeePerBlobGas", nullable(quantity())),
+                ("gas", nullable(quantity())),
+                ("value", nullable(quantity())),
+                ("input", nullable(data())),
+                ("data", nullable(data())),
+                ("nonce", nullable(quantity())),
+                ("chainId", nullable(quantity())),
+                ("accessList", nullable(access_list())),
+                ("type", nullable(quantity())),
+                ("blobVersionedHashes", nullable(array(hash()))),
+                ("sidecar", nullable(sidecar)),
+            ],
+            &[],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Receipt, ReceiptWithBloom};
+    use alloy_primitives::{Address, Bytes, LogData, B256, U256};
+    use serde::Serialize;
+
+    /// Asserts that `value` has all required properties of the schema of `T`, and no others.
+    fn assert_matches<T: JsonSchema + Serialize>(value: &T) {
+        let schema = schemars::schema_for!(T);
+        let json = serde_json::to_value(value).unwrap();
+        let json = json.as_object().unwrap();
+        let properties = schema.get("properties").unwrap().as_object().unwrap();
+        for name in json.keys() {
+            assert!(properties.contains_key(name), "{} is missing {name}", T::schema_name());
+        }
+        for name in schema.get("required").unwrap().as_array().unwrap() {
+            let name = name.as_str().unwrap();
+            assert!(json.contains_key(name), "{} requires {name}", T::schema_name());
+        }
+    }
+
+    #[test]
+    fn schemas_match_serialization() {
+        let tx = Transaction {
+            gas_price: Some(1),
+            signature: Some(Signature::default()),
+            chain_id: Some(1),
+            access_list: Some(Default::default()),
+            transaction_type: Some(1),
+            ..Default::default()
+        };
+        assert_matches(&tx);
+        assert_matches(&Block {
+            transactions: vec![tx].into(),
+            withdrawals: Some(Vec::new()),
+            ..Default::default()
+        });
+
+        let log = Log {
+            inner: alloy_primitives::Log {
+                address: Address::ZERO,
+                data: LogData::new_unchecked(vec![B256::ZERO], Bytes::new()),
+            },
+            ..Default::default()
+        };
+        assert_matches(&log);
+        assert_matches(&TransactionReceipt {
+            inner: ReceiptEnvelope::Eip1559(ReceiptWithBloom {
+                receipt: Receipt { status: true.into(), cumulative_gas_used: 1, logs: vec![log] },
+                logs_bloom: Default::default(),
+            }),
+            transaction_hash: B256::ZERO,
+            transaction_index: None,
+            block_hash: None,
+            block_number: None,
+            gas_used: 1,
+            effective_gas_price: 1,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::ZERO,
+            to: None,
+            contract_address: None,
+        });
+        assert_matches(
+            &TransactionRequest::default().value(U256::from(1)).input(Bytes::new().into()),
+        );
+    }
+
+    #[test]
+    fn collects_definitions() {
+        let definitions = definitions();
+        for name in
+            ["Block", "BlockTransactions_Transaction", "Transaction", "Log", "TransactionRequest"]
+        {
+            assert!(definitions.contains_key(name), "missing {name}");
+        }
+    }
+}
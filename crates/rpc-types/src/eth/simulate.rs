@@ -0,0 +1,231 @@
+//! Types for the `eth_simulateV1` RPC method.
+//!
+//! See the [execution-apis specification](https://github.com/ethereum/execution-apis/pull/484).
+
+use crate::{request::TransactionRequest, state::StateOverride, Block, BlockOverrides, Log};
+use alloy_primitives::Bytes;
+use serde::{Deserialize, Serialize};
+
+/// A block to simulate: a list of calls executed on top of the given overrides.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimBlock {
+    /// Overrides of the simulated block's header fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// State overrides applied before executing the calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls to execute, in order.
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+impl SimBlock {
+    /// Sets the block overrides.
+    pub fn with_block_overrides(mut self, overrides: BlockOverrides) -> Self {
+        self.block_overrides = Some(overrides);
+        self
+    }
+
+    /// Sets the state overrides.
+    pub fn with_state_overrides(mut self, overrides: StateOverride) -> Self {
+        self.state_overrides = Some(overrides);
+        self
+    }
+
+    /// Appends a call to the block.
+    pub fn call(mut self, call: TransactionRequest) -> Self {
+        self.calls.push(call);
+        self
+    }
+
+    /// Appends several calls to the block.
+    pub fn extend_calls(mut self, calls: impl IntoIterator<Item = TransactionRequest>) -> Self {
+        self.calls.extend(calls);
+        self
+    }
+}
+
+/// The payload of an `eth_simulateV1` request.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, in order. Each block builds on the state of the previous one.
+    #[serde(default)]
+    pub block_state_calls: Vec<SimBlock>,
+    /// Whether to add an ERC-20 style `Transfer` log for every ether transfer, emitted from
+    /// `0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee`.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether to run the full transaction validation checks, such as nonce and balance checks.
+    #[serde(default)]
+    pub validation: bool,
+    /// Whether to return the full transactions of the simulated blocks instead of their hashes.
+    #[serde(default)]
+    pub return_full_transactions: bool,
+}
+
+impl SimulatePayload {
+    /// Appends a block to simulate.
+    pub fn block(mut self, block: SimBlock) -> Self {
+        self.block_state_calls.push(block);
+        self
+    }
+
+    /// Appends several blocks to simulate.
+    pub fn extend_blocks(mut self, blocks: impl IntoIterator<Item = SimBlock>) -> Self {
+        self.block_state_calls.extend(blocks);
+        self
+    }
+
+    /// Enables ether transfer logs.
+    pub const fn with_trace_transfers(mut self) -> Self {
+        self.trace_transfers = true;
+        self
+    }
+
+    /// Enables full transaction validation.
+    pub const fn with_validation(mut self) -> Self {
+        self.validation = true;
+        self
+    }
+
+    /// Returns full transactions in the simulated blocks.
+    pub const fn with_full_transactions(mut self) -> Self {
+        self.return_full_transactions = true;
+        self
+    }
+}
+
+/// A simulated block, along with the results of its calls.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedBlock {
+    /// The simulated block.
+    #[serde(flatten)]
+    pub inner: Block,
+    /// The results of the calls, in the order they were executed.
+    pub calls: Vec<SimCallResult>,
+}
+
+/// The result of a simulated call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimCallResult {
+    /// The data returned by the call, or the revert data if it failed.
+    pub return_data: Bytes,
+    /// The logs emitted by the call, including transfer logs if enabled.
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    /// The gas used by the call.
+    #[serde(with = "alloy_serde::num::u64_via_ruint")]
+    pub gas_used: u64,
+    /// Whether the call succeeded.
+    #[serde(with = "alloy_serde::quantity_bool")]
+    pub status: bool,
+    /// The error of the call, if it failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+impl SimCallResult {
+    /// Returns the return data if the call succeeded, otherwise returns the error.
+    pub fn ensure_ok(self) -> Result<Bytes, SimulateError> {
+        if self.status {
+            Ok(self.return_data)
+        } else {
+            Err(self.error.unwrap_or_else(|| SimulateError {
+                code: 0,
+                message: "execution failed".to_string(),
+                data: Some(self.return_data),
+            }))
+        }
+    }
+}
+
+/// The error of a failed simulated call.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateError {
+    /// The error code, e.g. `3` for a reverted call or `-32015` for a VM error.
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+    /// The revert data, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+}
+
+impl std::fmt::Display for SimulateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for SimulateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, U256};
+
+    #[test]
+    fn serde_payload() {
+        let mut state = StateOverride::default();
+        state.insert(
+            address!("c000000000000000000000000000000000000000"),
+            crate::state::AccountOverride { balance: Some(U256::from(1000)), ..Default::default() },
+        );
+        let payload = SimulatePayload::default()
+            .block(
+                SimBlock::default()
+                    .with_state_overrides(state)
+                    .with_block_overrides(BlockOverrides {
+                        number: Some(U256::from(10)),
+                        ..Default::default()
+                    })
+                    .call(TransactionRequest::default()),
+            )
+            .with_trace_transfers();
+
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["traceTransfers"], true);
+        assert_eq!(json["validation"], false);
+        let block = &json["blockStateCalls"][0];
+        assert_eq!(block["blockOverrides"]["number"], "0xa");
+        assert_eq!(
+            block["stateOverrides"]["0xc000000000000000000000000000000000000000"]["balance"],
+            "0x3e8"
+        );
+        assert_eq!(block["calls"].as_array().unwrap().len(), 1);
+
+        let decoded: SimulatePayload = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, payload);
+    }
+
+    #[test]
+    fn deserialize_call_results() {
+        let s = r#"[
+            {
+                "returnData": "0x",
+                "logs": [],
+                "gasUsed": "0x5208",
+                "status": "0x1"
+            },
+            {
+                "returnData": "0x08c379a0",
+                "logs": [],
+                "gasUsed": "0x6a4b",
+                "status": "0x0",
+                "error": { "code": 3, "message": "execution reverted" }
+            }
+        ]"#;
+        let results: Vec<SimCallResult> = serde_json::from_str(s).unwrap();
+        assert!(results[0].status);
+        assert_eq!(results[0].gas_used, 21000);
+        assert_eq!(results[0].clone().ensure_ok().unwrap(), Bytes::new());
+
+        let error = results[1].clone().ensure_ok().unwrap_err();
+        assert_eq!(error.code, 3);
+        assert_eq!(error.message, "execution reverted");
+    }
+}
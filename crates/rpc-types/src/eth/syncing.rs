@@ -82,8 +82,12 @@ pub struct PeerEthProtocolInfo {
     /// Negotiated ethereum protocol version
     pub version: u32,
     /// Peer total difficulty if known
+    ///
+    /// Nodes stop reporting this, and the head, after the merge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub difficulty: Option<U256>,
     /// SHA3 of peer best block hash
+    #[serde(default)]
     pub head: String,
 }
 
@@ -93,6 +97,7 @@ pub struct PipProtocolInfo {
     /// Negotiated PIP protocol version
     pub version: u32,
     /// Peer total difficulty
+    #[serde(default)]
     pub difficulty: U256,
     /// SHA3 of peer best block hash
     pub head: String,
@@ -107,6 +112,21 @@ pub enum SyncStatus {
     None,
 }
 
+impl SyncStatus {
+    /// Returns `true` if the node is syncing.
+    pub const fn is_syncing(&self) -> bool {
+        matches!(self, Self::Info(_))
+    }
+
+    /// Returns the sync progress, if the node is syncing.
+    pub const fn info(&self) -> Option<&SyncInfo> {
+        match self {
+            Self::Info(info) => Some(info),
+            Self::None => None,
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for SyncStatus {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
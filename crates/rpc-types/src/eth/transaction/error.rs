@@ -45,9 +45,15 @@ pub enum ConversionError {
     /// Missing `blobVersionedHashes` field for EIP-4844 transaction.
     #[error("missing `blobVersionedHashes` field for EIP-4844 transaction")]
     MissingBlobVersionedHashes,
+    /// Missing `sourceHash` field for deposit transaction.
+    #[error("missing `sourceHash` field for deposit transaction")]
+    MissingSourceHash,
     /// Missing full transactions required for block decoding
     #[error("missing full transactions required for block decoding")]
     MissingFullTransactions,
+    /// Missing uncle headers required for block decoding
+    #[error("missing uncle headers required for block decoding")]
+    MissingUncleHeaders,
     /// Base fee per gas integer conversion error
     #[error("base fee per gas integer conversion error: {0}")]
     BaseFeePerGasConversion(TryFromIntError),
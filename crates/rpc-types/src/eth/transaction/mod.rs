@@ -8,7 +8,9 @@ use alloy_primitives::{Address, Bytes, TxKind, B256, U256};
 use serde::{Deserialize, Serialize};
 
 pub use alloy_consensus::BlobTransactionSidecar;
-pub use alloy_eips::eip2930::{AccessList, AccessListItem, AccessListWithGasUsed};
+pub use alloy_eips::eip2930::{
+    AccessList, AccessListItem, AccessListResult, AccessListWithGasUsed,
+};
 
 mod common;
 pub use common::TransactionInfo;
@@ -21,7 +23,7 @@ pub use optimism::OptimismTransactionReceiptFields;
 
 mod receipt;
 pub use alloy_consensus::{AnyReceiptEnvelope, Receipt, ReceiptEnvelope, ReceiptWithBloom};
-pub use receipt::{AnyTransactionReceipt, TransactionReceipt};
+pub use receipt::{AnyTransactionReceipt, ProvenReceipt, TransactionReceipt};
 
 pub mod request;
 pub use request::{TransactionInput, TransactionRequest};
@@ -129,12 +131,68 @@ pub struct Transaction {
     pub other: OtherFields,
 }
 
+/// Type flag of [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) transactions.
+const EIP7702_TX_TYPE_ID: u8 = 4;
+
+/// Type flag of OP-stack deposit transactions.
+const DEPOSIT_TX_TYPE_ID: u8 = 0x7e;
+
 impl Transaction {
     /// Returns true if the transaction is a legacy or 2930 transaction.
     pub const fn is_legacy_gas(&self) -> bool {
         self.gas_price.is_none()
     }
 
+    /// Returns the [`TxType`] of the transaction, or `None` if it is not an Ethereum transaction
+    /// type, e.g. an EIP-7702 or OP-stack deposit transaction.
+    ///
+    /// Transactions without a `type` field are legacy transactions.
+    pub fn tx_type(&self) -> Option<TxType> {
+        self.type_flag().try_into().ok()
+    }
+
+    /// Returns true if the transaction is an EIP-1559 transaction.
+    pub fn is_eip1559(&self) -> bool {
+        self.tx_type() == Some(TxType::Eip1559)
+    }
+
+    /// Returns true if the transaction is an EIP-4844 blob transaction.
+    pub fn is_eip4844(&self) -> bool {
+        self.tx_type() == Some(TxType::Eip4844)
+    }
+
+    /// Returns true if the transaction is an EIP-7702 transaction.
+    pub fn is_eip7702(&self) -> bool {
+        self.type_flag() == EIP7702_TX_TYPE_ID
+    }
+
+    /// Returns true if the transaction is an OP-stack deposit transaction.
+    pub fn is_deposit(&self) -> bool {
+        self.type_flag() == DEPOSIT_TX_TYPE_ID
+    }
+
+    /// Returns the number of blobs of the transaction, which is zero for non-blob transactions.
+    pub fn blob_count(&self) -> usize {
+        self.blob_versioned_hashes.as_ref().map_or(0, Vec::len)
+    }
+
+    /// Returns the number of authorizations of an EIP-7702 transaction, and zero for other
+    /// transactions.
+    ///
+    /// The authorization list is not a field of this type, so it is read from the
+    /// [`other`](Self::other) fields.
+    pub fn authorization_count(&self) -> usize {
+        self.other
+            .get("authorizationList")
+            .and_then(serde_json::Value::as_array)
+            .map_or(0, Vec::len)
+    }
+
+    /// Returns the EIP-2718 type flag, where a missing `type` field means a legacy transaction.
+    fn type_flag(&self) -> u8 {
+        self.transaction_type.unwrap_or_default()
+    }
+
     /// Converts [Transaction] into [TransactionRequest].
     ///
     /// During this conversion data for [TransactionRequest::sidecar] is not populated as it is not
@@ -420,4 +478,32 @@ mod tests {
         assert!(request.gas_price.is_none());
         assert!(request.max_fee_per_gas.is_some());
     }
+
+    #[test]
+    fn tx_type_helpers() {
+        let legacy = Transaction::default();
+        assert_eq!(legacy.tx_type(), Some(TxType::Legacy));
+        assert!(!legacy.is_eip1559());
+        assert_eq!(legacy.blob_count(), 0);
+
+        let blob = Transaction {
+            transaction_type: Some(3),
+            blob_versioned_hashes: Some(vec![B256::with_last_byte(1), B256::with_last_byte(2)]),
+            ..Default::default()
+        };
+        assert!(blob.is_eip4844());
+        assert_eq!(blob.blob_count(), 2);
+
+        let eip7702: Transaction = serde_json::from_str(
+            r#"{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","nonce":"0x0","blockHash":null,"blockNumber":null,"transactionIndex":null,"from":"0x0000000000000000000000000000000000000006","to":null,"value":"0x0","gas":"0x0","input":"0x","type":"0x4","authorizationList":[{"chainId":"0x1","address":"0x0000000000000000000000000000000000000007","nonce":"0x0","yParity":"0x0","r":"0x1","s":"0x1"}]}"#,
+        )
+        .unwrap();
+        assert!(eip7702.is_eip7702());
+        assert_eq!(eip7702.tx_type(), None);
+        assert_eq!(eip7702.authorization_count(), 1);
+
+        let deposit = Transaction { transaction_type: Some(0x7e), ..Default::default() };
+        assert!(deposit.is_deposit());
+        assert_eq!(deposit.authorization_count(), 0);
+    }
 }
@@ -4,6 +4,21 @@ use crate::other::OtherFields;
 use alloy_primitives::{B256, U128, U64};
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "optimism")]
+mod receipt;
+#[cfg(feature = "optimism")]
+pub use receipt::OptimismTransactionReceipt;
+
+#[cfg(feature = "optimism")]
+mod request;
+#[cfg(feature = "optimism")]
+pub use request::OptimismTransactionRequest;
+
+#[cfg(feature = "optimism")]
+mod transaction;
+#[cfg(feature = "optimism")]
+pub use transaction::OptimismTransaction;
+
 /// Optimism specific transaction fields
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct OptimismTransactionFields {
@@ -0,0 +1,84 @@
+use super::l1_fee_scalar_serde;
+use crate::{Log, TransactionReceipt};
+use alloy_consensus::optimism::OpReceiptEnvelope;
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Transaction receipt returned by OP-stack nodes, with the L1 data fee charged to the
+/// transaction.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimismTransactionReceipt {
+    /// The Ethereum receipt fields, over an [`OpReceiptEnvelope`].
+    #[serde(flatten)]
+    pub inner: TransactionReceipt<OpReceiptEnvelope<Log>>,
+    /// L1 fee for the transaction
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u128_opt_via_ruint"
+    )]
+    pub l1_fee: Option<u128>,
+    /// L1 fee scalar for the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "l1_fee_scalar_serde")]
+    pub l1_fee_scalar: Option<f64>,
+    /// L1 gas price for the transaction
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u128_opt_via_ruint"
+    )]
+    pub l1_gas_price: Option<u128>,
+    /// L1 gas used for the transaction
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u128_opt_via_ruint"
+    )]
+    pub l1_gas_used: Option<u128>,
+}
+
+impl Deref for OptimismTransactionReceipt {
+    type Target = TransactionReceipt<OpReceiptEnvelope<Log>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxReceipt;
+
+    #[test]
+    fn deserialize_deposit_receipt() {
+        let receipt = r#"{"blockHash":"0x9d86bb313ebeedf4f9f82bf8a19b426be656a365648a7c089b618771311db9f9","blockNumber":"0x798ad0b","contractAddress":null,"cumulativeGasUsed":"0xab04","depositNonce":"0x7d764","depositReceiptVersion":"0x1","effectiveGasPrice":"0x0","from":"0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001","gasUsed":"0xab04","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","status":"0x1","to":"0x4200000000000000000000000000000000000015","transactionHash":"0x3e8c4f58d1d1e7b767d1d8d5f2c44ed10fe1c216e5e3a4457c0fe980b9f4f047","transactionIndex":"0x0","type":"0x7e"}"#;
+
+        let receipt = serde_json::from_str::<OptimismTransactionReceipt>(receipt).unwrap();
+        let deposit = receipt.inner.inner.as_deposit_receipt().unwrap();
+        assert_eq!(deposit.deposit_nonce, Some(0x7d764));
+        assert_eq!(deposit.deposit_receipt_version, Some(1));
+        assert_eq!(receipt.inner.inner.cumulative_gas_used(), 0xab04);
+        assert!(receipt.inner.inner.status());
+        assert_eq!(receipt.l1_fee, None);
+
+        let serialized = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(serialized["depositNonce"], "0x7d764");
+        assert_eq!(
+            serde_json::from_value::<OptimismTransactionReceipt>(serialized).unwrap(),
+            receipt
+        );
+    }
+
+    #[test]
+    fn deserialize_l1_fees() {
+        let receipt = r#"{"blockHash":"0x2b95c4e6fa4039a63e1a1a2c0e2de4ce7dc4120ab3faac2bcc0ea50a0c13d5f8","blockNumber":"0x798ad0b","contractAddress":null,"cumulativeGasUsed":"0x12ca8","effectiveGasPrice":"0xf4857","from":"0x2a8ab4ae3b9ee11c8e3aadad8bc6b2cd6d8ac9cb","gasUsed":"0x5208","l1Fee":"0x1d2bb8d9b35","l1FeeScalar":"0.684","l1GasPrice":"0x5f9b2bbf3","l1GasUsed":"0x640","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","status":"0x1","to":"0x2a8ab4ae3b9ee11c8e3aadad8bc6b2cd6d8ac9cb","transactionHash":"0x7e02a78d1a3f1f0b1e0c0f8d7192a3bde2c9fa6ae52d96bb8e1da6a46cded21b","transactionIndex":"0x1","type":"0x2"}"#;
+
+        let receipt = serde_json::from_str::<OptimismTransactionReceipt>(receipt).unwrap();
+        assert!(receipt.inner.inner.as_deposit_receipt().is_none());
+        assert_eq!(receipt.l1_fee, Some(0x1d2bb8d9b35));
+        assert_eq!(receipt.l1_fee_scalar, Some(0.684));
+        assert_eq!(receipt.l1_gas_used, Some(0x640));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::TransactionRequest;
+use alloy_consensus::{
+    optimism::{OpTxEnvelope, OpTxType, OpTypedTransaction, TxDeposit},
+    TxEnvelope, TxType,
+};
+use alloy_primitives::{TxKind, B256};
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Transaction request for OP-stack networks, which can also describe a
+/// [deposit](TxDeposit) transaction.
+///
+/// The request is built as a deposit if [`source_hash`](Self::source_hash) is set. EIP-4844
+/// transactions are not supported on L2, so the blob fields of the inner request are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimismTransactionRequest {
+    /// The Ethereum transaction request fields.
+    #[serde(flatten)]
+    pub inner: TransactionRequest,
+    /// Hash that uniquely identifies the source of the deposit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_hash: Option<B256>,
+    /// The ETH value to mint on L2.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u128_opt_via_ruint"
+    )]
+    pub mint: Option<u128>,
+    /// Whether the transaction is a system transaction, exempt from the L2 gas limit.
+    #[serde(default, rename = "isSystemTx", skip_serializing_if = "Option::is_none")]
+    pub is_system_tx: Option<bool>,
+}
+
+impl OptimismTransactionRequest {
+    /// Sets the source hash, turning the request into a deposit.
+    pub const fn source_hash(mut self, source_hash: B256) -> Self {
+        self.source_hash = Some(source_hash);
+        self
+    }
+
+    /// Sets the ETH value to mint on L2.
+    pub const fn mint(mut self, mint: u128) -> Self {
+        self.mint = Some(mint);
+        self
+    }
+
+    /// Sets whether the deposit is a system transaction.
+    pub const fn is_system_tx(mut self, is_system_tx: bool) -> Self {
+        self.is_system_tx = Some(is_system_tx);
+        self
+    }
+
+    /// Check this builder's preferred type, based on the fields that are set.
+    ///
+    /// This is a deposit if the source hash is set, or the preferred type of the inner request
+    /// otherwise, with EIP-1559 in place of EIP-4844.
+    pub const fn preferred_type(&self) -> OpTxType {
+        if self.source_hash.is_some() {
+            return OpTxType::Deposit;
+        }
+        match self.inner.preferred_type() {
+            TxType::Legacy => OpTxType::Legacy,
+            TxType::Eip2930 => OpTxType::Eip2930,
+            TxType::Eip1559 | TxType::Eip4844 => OpTxType::Eip1559,
+        }
+    }
+
+    /// Check if all necessary keys are present to build a deposit transaction,
+    /// returning a list of keys that are missing.
+    pub fn complete_deposit(&self) -> Result<(), Vec<&'static str>> {
+        let mut missing = Vec::new();
+        if self.source_hash.is_none() {
+            missing.push("source_hash");
+        }
+        if self.inner.from.is_none() {
+            missing.push("from");
+        }
+        if self.inner.gas.is_none() {
+            missing.push("gas_limit");
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+
+    /// Check if all necessary keys are present to build a transaction of the given type,
+    /// returning a list of keys that are missing.
+    pub fn complete_type(&self, ty: OpTxType) -> Result<(), Vec<&'static str>> {
+        match ty {
+            OpTxType::Legacy => self.inner.complete_legacy(),
+            OpTxType::Eip2930 => self.inner.complete_2930(),
+            OpTxType::Eip1559 => self.inner.complete_1559(),
+            OpTxType::Deposit => self.complete_deposit(),
+        }
+    }
+
+    /// Check if all necessary keys are present to build a transaction.
+    ///
+    /// # Returns
+    ///
+    /// - Ok(type) if all necessary keys are present to build the preferred type.
+    /// - Err((type, missing)) if some keys are missing to build the preferred type.
+    pub fn missing_keys(&self) -> Result<OpTxType, (OpTxType, Vec<&'static str>)> {
+        let pref = self.preferred_type();
+        match self.complete_type(pref) {
+            Ok(()) => Ok(pref),
+            Err(missing) => Err((pref, missing)),
+        }
+    }
+
+    /// Return the tx type this request can be built as. Computed by checking
+    /// the preferred type, and then checking for completeness.
+    pub fn buildable_type(&self) -> Option<OpTxType> {
+        self.missing_keys().ok()
+    }
+
+    /// Trim field conflicts, based on the preferred type
+    ///
+    /// See [`TransactionRequest::trim_conflicting_keys`].
+    pub fn trim_conflicting_keys(&mut self) {
+        self.inner.sidecar = None;
+        self.inner.max_fee_per_blob_gas = None;
+        self.inner.blob_versioned_hashes = None;
+        self.inner.trim_conflicting_keys();
+        if self.source_hash.is_some() {
+            self.inner.nonce = None;
+            self.inner.gas_price = None;
+            self.inner.max_fee_per_gas = None;
+            self.inner.max_priority_fee_per_gas = None;
+            self.inner.access_list = None;
+        } else {
+            self.mint = None;
+            self.is_system_tx = None;
+        }
+    }
+
+    /// Build an [`OpTypedTransaction`]
+    #[allow(clippy::result_large_err)]
+    pub fn build_typed_tx(mut self) -> Result<OpTypedTransaction, Self> {
+        match self.buildable_type() {
+            None => Err(self),
+            Some(OpTxType::Deposit) => Ok(TxDeposit {
+                source_hash: self.source_hash.expect("checked in complete_deposit"),
+                from: self.inner.from.expect("checked in complete_deposit"),
+                to: self.inner.to.unwrap_or(TxKind::Create),
+                mint: self.mint,
+                value: self.inner.value.unwrap_or_default(),
+                gas_limit: self.inner.gas.expect("checked in complete_deposit"),
+                is_system_transaction: self.is_system_tx.unwrap_or_default(),
+                input: self.inner.input.into_input().unwrap_or_default(),
+            }
+            .into()),
+            Some(_) => {
+                self.inner.sidecar = None;
+                self.inner.max_fee_per_blob_gas = None;
+                let tx = self.inner.build_typed_tx().expect("checked by buildable_type");
+                Ok(tx.try_into().expect("not an EIP-4844 transaction"))
+            }
+        }
+    }
+}
+
+impl Deref for OptimismTransactionRequest {
+    type Target = TransactionRequest;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for OptimismTransactionRequest {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl From<TransactionRequest> for OptimismTransactionRequest {
+    fn from(inner: TransactionRequest) -> Self {
+        Self { inner, ..Default::default() }
+    }
+}
+
+impl From<TxDeposit> for OptimismTransactionRequest {
+    fn from(tx: TxDeposit) -> Self {
+        Self {
+            inner: TransactionRequest {
+                from: Some(tx.from),
+                to: Some(tx.to),
+                value: Some(tx.value),
+                gas: Some(tx.gas_limit),
+                input: tx.input.into(),
+                transaction_type: Some(OpTxType::Deposit.into()),
+                ..Default::default()
+            },
+            source_hash: Some(tx.source_hash),
+            mint: tx.mint,
+            is_system_tx: Some(tx.is_system_transaction),
+        }
+    }
+}
+
+impl From<OpTypedTransaction> for OptimismTransactionRequest {
+    fn from(tx: OpTypedTransaction) -> Self {
+        match tx {
+            OpTypedTransaction::Legacy(tx) => Self { inner: tx.into(), ..Default::default() },
+            OpTypedTransaction::Eip2930(tx) => Self { inner: tx.into(), ..Default::default() },
+            OpTypedTransaction::Eip1559(tx) => Self { inner: tx.into(), ..Default::default() },
+            OpTypedTransaction::Deposit(tx) => tx.into(),
+        }
+    }
+}
+
+impl From<OpTxEnvelope> for OptimismTransactionRequest {
+    fn from(envelope: OpTxEnvelope) -> Self {
+        match envelope {
+            OpTxEnvelope::Legacy(tx) => {
+                Self { inner: TxEnvelope::from(tx).into(), ..Default::default() }
+            }
+            OpTxEnvelope::Eip2930(tx) => {
+                Self { inner: TxEnvelope::from(tx).into(), ..Default::default() }
+            }
+            OpTxEnvelope::Eip1559(tx) => {
+                Self { inner: TxEnvelope::from(tx).into(), ..Default::default() }
+            }
+            OpTxEnvelope::Deposit(tx) => tx.into(),
+            _ => Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, U256};
+
+    #[test]
+    fn build_deposit() {
+        let request = OptimismTransactionRequest::from(TransactionRequest {
+            from: Some(address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001")),
+            to: Some(address!("4200000000000000000000000000000000000015").into()),
+            gas: Some(1_000_000),
+            ..Default::default()
+        })
+        .source_hash(b256!("0000000000000000000000000000000000000000000000000000000000000001"))
+        .mint(100);
+        assert_eq!(request.missing_keys(), Ok(OpTxType::Deposit));
+
+        let OpTypedTransaction::Deposit(tx) = request.clone().build_typed_tx().unwrap() else {
+            panic!("expected a deposit")
+        };
+        assert_eq!(tx.mint, Some(100));
+        assert_eq!(tx.value, U256::ZERO);
+        assert_eq!(
+            OptimismTransactionRequest::from(tx).build_typed_tx().unwrap().tx_type(),
+            OpTxType::Deposit
+        );
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["mint"], "0x64");
+        assert_eq!(serde_json::from_value::<OptimismTransactionRequest>(json).unwrap(), request);
+    }
+
+    #[test]
+    fn incomplete_deposit() {
+        let request = OptimismTransactionRequest::default().source_hash(B256::ZERO);
+        let (ty, missing) = request.missing_keys().unwrap_err();
+        assert_eq!(ty, OpTxType::Deposit);
+        assert_eq!(missing, ["from", "gas_limit"]);
+    }
+
+    #[test]
+    fn ignore_blob_fields() {
+        let request = OptimismTransactionRequest::from(TransactionRequest {
+            to: Some(TxKind::Call(Default::default())),
+            nonce: Some(0),
+            gas: Some(21_000),
+            max_fee_per_gas: Some(2),
+            max_priority_fee_per_gas: Some(1),
+            max_fee_per_blob_gas: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(request.preferred_type(), OpTxType::Eip1559);
+        assert_eq!(request.build_typed_tx().unwrap().tx_type(), OpTxType::Eip1559);
+    }
+}
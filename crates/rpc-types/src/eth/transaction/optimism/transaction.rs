@@ -0,0 +1,115 @@
+use crate::{ConversionError, Transaction};
+use alloy_consensus::{
+    optimism::{OpTxEnvelope, OpTxType, TxDeposit},
+    TxEnvelope,
+};
+use alloy_primitives::{TxKind, B256};
+use serde::{Deserialize, Serialize};
+use std::ops::{Deref, DerefMut};
+
+/// Transaction object returned by OP-stack nodes.
+///
+/// The fields of [deposit](TxDeposit) transactions are captured here, instead of in
+/// [`Transaction::other`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OptimismTransaction {
+    /// The Ethereum transaction fields.
+    #[serde(flatten)]
+    pub inner: Transaction,
+    /// Hash that uniquely identifies the source of the deposit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_hash: Option<B256>,
+    /// The ETH value to mint on L2.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "alloy_serde::num::u128_opt_via_ruint"
+    )]
+    pub mint: Option<u128>,
+    /// Whether the transaction is a system transaction, exempt from the L2 gas limit.
+    #[serde(default, rename = "isSystemTx", skip_serializing_if = "Option::is_none")]
+    pub is_system_tx: Option<bool>,
+}
+
+impl OptimismTransaction {
+    /// Returns true if this is a deposit transaction.
+    pub fn is_deposit(&self) -> bool {
+        self.inner.transaction_type == Some(OpTxType::Deposit.into())
+    }
+}
+
+impl Deref for OptimismTransaction {
+    type Target = Transaction;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for OptimismTransaction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl TryFrom<OptimismTransaction> for TxDeposit {
+    type Error = ConversionError;
+
+    fn try_from(tx: OptimismTransaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            source_hash: tx.source_hash.ok_or(ConversionError::MissingSourceHash)?,
+            from: tx.inner.from,
+            to: tx.inner.to.map_or(TxKind::Create, TxKind::Call),
+            mint: tx.mint,
+            value: tx.inner.value,
+            gas_limit: tx.inner.gas,
+            is_system_transaction: tx.is_system_tx.unwrap_or_default(),
+            input: tx.inner.input,
+        })
+    }
+}
+
+impl TryFrom<OptimismTransaction> for OpTxEnvelope {
+    type Error = ConversionError;
+
+    fn try_from(tx: OptimismTransaction) -> Result<Self, Self::Error> {
+        if tx.is_deposit() {
+            return Ok(Self::Deposit(tx.try_into()?));
+        }
+        Ok(TxEnvelope::try_from(tx.inner)?.try_into()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn deserialize_deposit() {
+        // cast rpc eth_getTransactionByHash
+        // 0x3e8c4f58d1d1e7b767d1d8d5f2c44ed10fe1c216e5e3a4457c0fe980b9f4f047 --rpc-url optimism
+        let rpc_tx = r#"{"blockHash":"0x9d86bb313ebeedf4f9f82bf8a19b426be656a365648a7c089b618771311db9f9","blockNumber":"0x798ad0b","from":"0xdeaddeaddeaddeaddeaddeaddeaddeaddead0001","gas":"0xf4240","gasPrice":"0x0","hash":"0x3e8c4f58d1d1e7b767d1d8d5f2c44ed10fe1c216e5e3a4457c0fe980b9f4f047","input":"0x440a5e20000008dd00101c1200000000000000040000000065c0c5a70000000000d1842c0000000000000000000000000000000000000000000000000000000000000002","mint":"0x0","nonce":"0x7d764","r":"0x0","s":"0x0","sourceHash":"0xc84b5fcb4f5f8a2b96e5bbd9d5c09a8d9e6047a527ca6288e8df71b3f4ec6b68","to":"0x4200000000000000000000000000000000000015","transactionIndex":"0x0","type":"0x7e","v":"0x0","value":"0x0"}"#;
+
+        let tx = serde_json::from_str::<OptimismTransaction>(rpc_tx).unwrap();
+        assert!(tx.is_deposit());
+        assert!(tx.inner.other.is_empty());
+        assert_eq!(tx.mint, Some(0));
+        assert_eq!(
+            tx.source_hash,
+            Some(b256!("c84b5fcb4f5f8a2b96e5bbd9d5c09a8d9e6047a527ca6288e8df71b3f4ec6b68"))
+        );
+
+        let OpTxEnvelope::Deposit(deposit) = OpTxEnvelope::try_from(tx.clone()).unwrap() else {
+            panic!("expected a deposit")
+        };
+        assert_eq!(deposit.from, address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001"));
+        assert_eq!(deposit.gas_limit, 1_000_000);
+        assert!(!deposit.is_system_transaction);
+
+        let serialized = serde_json::to_value(&tx).unwrap();
+        assert_eq!(serialized["sourceHash"], serde_json::to_value(tx.source_hash).unwrap());
+        assert_eq!(serde_json::from_value::<OptimismTransaction>(serialized).unwrap(), tx);
+    }
+}
@@ -1,8 +1,10 @@
 #![allow(unknown_lints, non_local_definitions)]
 
 use crate::{Log, WithOtherFields};
-use alloy_consensus::{AnyReceiptEnvelope, ReceiptEnvelope, TxType};
-use alloy_primitives::{Address, B256};
+use alloy_consensus::{
+    trie::verify_ordered_trie_proof, AnyReceiptEnvelope, Eip658Value, ReceiptEnvelope, TxType,
+};
+use alloy_primitives::{Address, Bytes, B256};
 use serde::{Deserialize, Serialize};
 
 /// Transaction receipt
@@ -60,11 +62,6 @@ pub struct TransactionReceipt<T = ReceiptEnvelope<Log>> {
     pub to: Option<Address>,
     /// Contract address created, or None if not a deployment.
     pub contract_address: Option<Address>,
-    /// The post-transaction stateroot (pre Byzantium)
-    ///
-    /// EIP98 makes this optional field, if it's missing then skip serializing it
-    #[serde(skip_serializing_if = "Option::is_none", rename = "root")]
-    pub state_root: Option<B256>,
 }
 
 impl AsRef<ReceiptEnvelope<Log>> for TransactionReceipt {
@@ -75,13 +72,33 @@ impl AsRef<ReceiptEnvelope<Log>> for TransactionReceipt {
 
 impl TransactionReceipt {
     /// Returns the status of the transaction.
+    ///
+    /// Pre-Byzantium receipts are assumed to be successful, see
+    /// [`Eip658Value::coerce_status`].
     pub const fn status(&self) -> bool {
+        match self.status_or_post_state() {
+            Some(status) => status.coerce_status(),
+            None => false,
+        }
+    }
+
+    /// Returns the status of the transaction, or the state root after it before the Byzantium
+    /// hardfork.
+    pub const fn status_or_post_state(&self) -> Option<Eip658Value> {
         match &self.inner {
             ReceiptEnvelope::Eip1559(receipt)
             | ReceiptEnvelope::Eip2930(receipt)
             | ReceiptEnvelope::Eip4844(receipt)
-            | ReceiptEnvelope::Legacy(receipt) => receipt.receipt.status,
-            _ => false,
+            | ReceiptEnvelope::Legacy(receipt) => Some(receipt.receipt.status),
+            _ => None,
+        }
+    }
+
+    /// Returns the state root after the transaction, if this is a pre-Byzantium receipt.
+    pub const fn state_root(&self) -> Option<B256> {
+        match self.status_or_post_state() {
+            Some(status) => status.as_post_state(),
+            None => None,
         }
     }
 
@@ -121,7 +138,6 @@ impl<T> TransactionReceipt<T> {
             from: self.from,
             to: self.to,
             contract_address: self.contract_address,
-            state_root: self.state_root,
         }
     }
 }
@@ -129,6 +145,42 @@ impl<T> TransactionReceipt<T> {
 /// Alias for a catch-all receipt type.
 pub type AnyTransactionReceipt = WithOtherFields<TransactionReceipt<AnyReceiptEnvelope<Log>>>;
 
+/// A receipt along with the proof of its inclusion in the receipts root of its block, e.g. to
+/// prove a cross-chain message emitted by the transaction.
+///
+/// The proof is built from all the receipts of the block, as the trie nodes on the path from the
+/// receipts root to the receipt.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvenReceipt<R = TransactionReceipt> {
+    /// The receipt.
+    pub receipt: R,
+    /// The consensus receipt, in its EIP-2718 encoding, as committed to in the receipts root.
+    pub encoded_receipt: Bytes,
+    /// Hash of the block the transaction was included in.
+    pub block_hash: B256,
+    /// Index of the transaction within the block.
+    #[serde(with = "alloy_serde::u64_via_ruint")]
+    pub transaction_index: u64,
+    /// Receipts root of the block.
+    pub receipts_root: B256,
+    /// The trie nodes on the path from the receipts root to the receipt, starting with the root.
+    pub proof: Vec<Bytes>,
+}
+
+impl<R> ProvenReceipt<R> {
+    /// Verifies that the proof proves the encoded receipt to be at its index under the receipts
+    /// root.
+    ///
+    /// This does not check the receipts root against the header of the block, nor the encoded
+    /// receipt against [`receipt`](Self::receipt).
+    pub fn verify(&self) -> bool {
+        usize::try_from(self.transaction_index).is_ok_and(|index| {
+            verify_ordered_trie_proof(self.receipts_root, index, &self.encoded_receipt, &self.proof)
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -146,6 +198,21 @@ mod test {
             TransactionReceipt::arbitrary(&mut arbitrary::Unstructured::new(&bytes)).unwrap();
     }
 
+    #[test]
+    fn pre_byzantium_receipt() {
+        let json_str = r#"{"blockHash":"0x4e3a3754410177e6937ef1f84bba68ea139e8d1a2258c5f85db9f1cd715a1bdd","blockNumber":"0xb443","contractAddress":null,"cumulativeGasUsed":"0x5208","from":"0xa1e4380a3b1f749673e270229993ee55f35663b4","gasUsed":"0x5208","effectiveGasPrice":"0x2d79883d2000","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","root":"0x96a8e009d2b88b1483e6941e6812e32263b05683fac202abc622a3e31aed1957","to":"0x5df9b87991262f6ba471f09758cde1c0fc1de734","transactionHash":"0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060","transactionIndex":"0x0","type":"0x0"}"#;
+
+        let receipt: TransactionReceipt = serde_json::from_str(json_str).unwrap();
+        let root = b256!("96a8e009d2b88b1483e6941e6812e32263b05683fac202abc622a3e31aed1957");
+        assert_eq!(receipt.state_root(), Some(root));
+        assert!(receipt.status());
+
+        let json = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(json["root"], root.to_string());
+        assert!(json.get("status").is_none());
+        assert_eq!(serde_json::from_value::<TransactionReceipt>(json).unwrap(), receipt);
+    }
+
     #[test]
     fn test_sanity() {
         let json_str = r#"{"transactionHash":"0x21f6554c28453a01e7276c1db2fc1695bb512b170818bfa98fa8136433100616","blockHash":"0x4acbdefb861ef4adedb135ca52865f6743451bfbfa35db78076f881a40401a5e","blockNumber":"0x129f4b9","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000200000000000000000040000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000400000800000000000000000000000000000000004000000000000000000800000000100000020000000000000000000080000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000010000000000000000000000000000","gasUsed":"0xbde1","contractAddress":null,"cumulativeGasUsed":"0xa42aec","transactionIndex":"0x7f","from":"0x9a53bfba35269414f3b2d20b52ca01b15932c7b2","to":"0xdac17f958d2ee523a2206206994597c13d831ec7","type":"0x2","effectiveGasPrice":"0xfb0f6e8c9","logs":[{"blockHash":"0x4acbdefb861ef4adedb135ca52865f6743451bfbfa35db78076f881a40401a5e","address":"0xdac17f958d2ee523a2206206994597c13d831ec7","logIndex":"0x118","data":"0x00000000000000000000000000000000000000000052b7d2dcc80cd2e4000000","removed":false,"topics":["0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925","0x0000000000000000000000009a53bfba35269414f3b2d20b52ca01b15932c7b2","0x00000000000000000000000039e5dbb9d2fead31234d7c647d6ce77d85826f76"],"blockNumber":"0x129f4b9","transactionIndex":"0x7f","transactionHash":"0x21f6554c28453a01e7276c1db2fc1695bb512b170818bfa98fa8136433100616"}],"status":"0x1"}"#;
@@ -162,7 +229,11 @@ mod test {
         assert!(matches!(
             receipt.inner,
             ReceiptEnvelope::Eip1559(ReceiptWithBloom {
-                receipt: Receipt { status: true, cumulative_gas_used: EXPECTED_CGU, .. },
+                receipt: Receipt {
+                    status: Eip658Value::Eip658(true),
+                    cumulative_gas_used: EXPECTED_CGU,
+                    ..
+                },
                 logs_bloom: EXPECTED_BLOOM
             })
         ));
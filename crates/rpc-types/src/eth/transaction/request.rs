@@ -75,7 +75,9 @@ pub struct TransactionRequest {
     )]
     pub chain_id: Option<ChainId>,
     /// An EIP-2930 access list, which lowers cost for accessing accounts and storages in the list. See [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) for more information.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ///
+    /// An empty access list is omitted like a missing one, as some servers reject it.
+    #[serde(default, skip_serializing_if = "is_none_or_empty")]
     pub access_list: Option<AccessList>,
     /// The EIP-2718 transaction type. See [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718) for more information.
     #[serde(
@@ -93,6 +95,11 @@ pub struct TransactionRequest {
     pub sidecar: Option<BlobTransactionSidecar>,
 }
 
+/// Returns `true` if the access list is missing or empty.
+fn is_none_or_empty(access_list: &Option<AccessList>) -> bool {
+    access_list.as_ref().map_or(true, |list| list.0.is_empty())
+}
+
 impl TransactionRequest {
     /// Sets the `from` field in the call to the provided address
     #[inline]
@@ -395,15 +402,20 @@ impl TransactionRequest {
     /// type.
     pub fn missing_keys(&self) -> Result<TxType, (TxType, Vec<&'static str>)> {
         let pref = self.preferred_type();
-        if let Err(missing) = match pref {
+        match self.complete_type(pref) {
+            Ok(()) => Ok(pref),
+            Err(missing) => Err((pref, missing)),
+        }
+    }
+
+    /// Check if all necessary keys are present to build a transaction of the
+    /// given type, returning a list of keys that are missing.
+    pub fn complete_type(&self, ty: TxType) -> Result<(), Vec<&'static str>> {
+        match ty {
             TxType::Legacy => self.complete_legacy(),
             TxType::Eip2930 => self.complete_2930(),
             TxType::Eip1559 => self.complete_1559(),
             TxType::Eip4844 => self.complete_4844(),
-        } {
-            Err((pref, missing))
-        } else {
-            Ok(pref)
         }
     }
 
@@ -413,7 +425,8 @@ impl TransactionRequest {
         let mut missing = self.check_reqd_fields();
         self.check_1559_fields(&mut missing);
 
-        if self.to.is_none() {
+        // Blob transactions cannot create contracts.
+        if self.to == Some(TxKind::Create) {
             missing.push("to");
         }
 
@@ -477,14 +490,7 @@ impl TransactionRequest {
     /// Return the tx type this request can be built as. Computed by checking
     /// the preferred type, and then checking for completeness.
     pub fn buildable_type(&self) -> Option<TxType> {
-        let pref = self.preferred_type();
-        match pref {
-            TxType::Legacy => self.complete_legacy().ok(),
-            TxType::Eip2930 => self.complete_2930().ok(),
-            TxType::Eip1559 => self.complete_1559().ok(),
-            TxType::Eip4844 => self.complete_4844().ok(),
-        }?;
-        Some(pref)
+        self.missing_keys().ok()
     }
 
     /// Build an [`TypedTransaction`]
@@ -860,4 +866,51 @@ mod tests {
         let serialized = serde_json::to_string(&tx).unwrap();
         assert_eq!(serialized, "{}");
     }
+
+    #[test]
+    fn serde_skips_empty_fields() {
+        let tx = TransactionRequest {
+            to: Some(TxKind::Call(Address::ZERO)),
+            access_list: Some(AccessList::default()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_value(&tx).unwrap(),
+            serde_json::json!({ "to": "0x0000000000000000000000000000000000000000" })
+        );
+
+        let tx = tx.access_list(AccessList(vec![Default::default()])).nonce(1);
+        assert_eq!(
+            serde_json::to_value(&tx).unwrap(),
+            serde_json::json!({
+                "to": "0x0000000000000000000000000000000000000000",
+                "nonce": "0x1",
+                "accessList": [{
+                    "address": "0x0000000000000000000000000000000000000000",
+                    "storageKeys": []
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn missing_keys() {
+        let mut tx = TransactionRequest::default().to(Address::ZERO).max_fee_per_gas(1);
+        assert_eq!(tx.preferred_type(), TxType::Eip1559);
+        assert_eq!(
+            tx.missing_keys(),
+            Err((TxType::Eip1559, vec!["nonce", "gas_limit", "max_priority_fee_per_gas"]))
+        );
+        assert_eq!(tx.complete_legacy(), Err(vec!["nonce", "gas_limit", "gas_price"]));
+
+        tx = tx.nonce(0).gas_limit(21_000).max_priority_fee_per_gas(1);
+        assert_eq!(tx.buildable_type(), Some(TxType::Eip1559));
+
+        // Blob transactions must be sent to an address.
+        tx.sidecar = Some(BlobTransactionSidecar::default());
+        tx.max_fee_per_blob_gas = Some(1);
+        assert_eq!(tx.complete_type(TxType::Eip4844), Ok(()));
+        tx.to = Some(TxKind::Create);
+        assert_eq!(tx.missing_keys(), Err((TxType::Eip4844, vec!["to"])));
+    }
 }
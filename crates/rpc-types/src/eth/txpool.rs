@@ -129,6 +129,26 @@ impl TxpoolContent {
             queued: self.queued.remove(sender).unwrap_or_default(),
         }
     }
+
+    /// Returns the pending transaction of the given sender with the given nonce, if any.
+    pub fn get_pending(&self, sender: &Address, nonce: u64) -> Option<&Transaction> {
+        self.pending.get(sender)?.get(&nonce.to_string())
+    }
+
+    /// Returns the queued transaction of the given sender with the given nonce, if any.
+    pub fn get_queued(&self, sender: &Address, nonce: u64) -> Option<&Transaction> {
+        self.queued.get(sender)?.get(&nonce.to_string())
+    }
+
+    /// Returns an iterator over all pending transactions.
+    pub fn pending_transactions(&self) -> impl Iterator<Item = &Transaction> {
+        self.pending.values().flat_map(BTreeMap::values)
+    }
+
+    /// Returns an iterator over all queued transactions.
+    pub fn queued_transactions(&self) -> impl Iterator<Item = &Transaction> {
+        self.queued.values().flat_map(BTreeMap::values)
+    }
 }
 
 /// Transaction Pool Content From
@@ -144,6 +164,18 @@ pub struct TxpoolContentFrom {
     pub queued: BTreeMap<String, Transaction>,
 }
 
+impl TxpoolContentFrom {
+    /// Returns the pending transaction with the given nonce, if any.
+    pub fn get_pending(&self, nonce: u64) -> Option<&Transaction> {
+        self.pending.get(&nonce.to_string())
+    }
+
+    /// Returns the queued transaction with the given nonce, if any.
+    pub fn get_queued(&self, nonce: u64) -> Option<&Transaction> {
+        self.queued.get(&nonce.to_string())
+    }
+}
+
 /// Transaction Pool Inspect
 ///
 /// The inspect inspection property can be queried to list a textual summary
@@ -359,6 +391,30 @@ mod tests {
         assert_eq!(deserialized, serde_json::from_str::<TxpoolContent>(&serialized).unwrap());
     }
 
+    #[test]
+    fn txpool_content_by_nonce() {
+        let tx = |nonce: u64| (nonce.to_string(), Transaction { nonce, ..Default::default() });
+        let sender = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        let mut content = TxpoolContent {
+            pending: BTreeMap::from([
+                (sender, BTreeMap::from([tx(0)])),
+                (other, BTreeMap::from([tx(3), tx(4)])),
+            ]),
+            queued: BTreeMap::from([(sender, BTreeMap::from([tx(1), tx(4)]))]),
+        };
+        assert_eq!(content.pending_transactions().count(), 3);
+        assert_eq!(content.queued_transactions().count(), 2);
+        assert_eq!(content.get_queued(&sender, 4).unwrap().nonce, 4);
+        assert!(content.get_pending(&sender, 4).is_none());
+        assert_eq!(content.get_pending(&other, 4).unwrap().nonce, 4);
+
+        let from = content.remove_from(&sender);
+        assert_eq!(from.get_pending(0).unwrap().nonce, 0);
+        assert_eq!(from.get_queued(1).unwrap().nonce, 1);
+        assert!(from.get_queued(2).is_none());
+    }
+
     #[test]
     fn serde_txpool_inspect() {
         let txpool_inspect_json = r#"
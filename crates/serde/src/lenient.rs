@@ -0,0 +1,154 @@
+//! Lenient deserialization, for RPC providers returning values which do not conform to the
+//! Ethereum JSON-RPC specification.
+//!
+//! With the `lenient` feature:
+//! - the quantity helpers of [`num`](crate::num) also accept hex quantities without the `0x`
+//!   prefix, and `null` in place of lists of quantities;
+//! - [`null_as_default`] accepts `null` in place of lists, or other values with a default.
+//!
+//! Without the feature, or with formats which are not [human readable], these helpers deserialize
+//! values as usual. Values are always serialized canonically.
+//!
+//! Quantities without the `0x` prefix are read as hex. Those made of decimal digits only, like
+//! `"10"`, could be decimal numbers as well, and are rejected unless both readings agree, as for
+//! `"7"`.
+//!
+//! [human readable]: serde::Deserializer::is_human_readable
+
+use alloy_primitives::Uint;
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes `null` as the default value of `T`, with the `lenient` feature.
+///
+/// This is meant for lists which some providers return as `null` when empty:
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Receipt {
+///     #[serde(default, deserialize_with = "alloy_serde::lenient::null_as_default")]
+///     logs: Vec<u64>,
+/// }
+///
+/// let receipt: Result<Receipt, _> = serde_json::from_str(r#"{"logs":null}"#);
+/// assert_eq!(receipt.is_ok(), cfg!(feature = "lenient"));
+/// ```
+pub fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Default,
+{
+    #[cfg(feature = "lenient")]
+    if deserializer.is_human_readable() {
+        return Option::<T>::deserialize(deserializer).map(Option::unwrap_or_default);
+    }
+    T::deserialize(deserializer)
+}
+
+/// A quantity, deserialized leniently with the `lenient` feature, and as a [`Uint`] otherwise.
+pub(crate) struct Quantity<const BITS: usize, const LIMBS: usize>(pub(crate) Uint<BITS, LIMBS>);
+
+impl<'de, const BITS: usize, const LIMBS: usize> Deserialize<'de> for Quantity<BITS, LIMBS> {
+    #[cfg(not(feature = "lenient"))]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Uint::deserialize(deserializer).map(Self)
+    }
+
+    #[cfg(feature = "lenient")]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor::QuantityVisitor)
+        } else {
+            Uint::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[cfg(feature = "lenient")]
+mod visitor {
+    use super::Quantity;
+    use alloy_primitives::Uint;
+    use core::fmt;
+    use serde::de::{Error, Unexpected, Visitor};
+
+    pub(super) struct QuantityVisitor<const BITS: usize, const LIMBS: usize>;
+
+    impl<const BITS: usize, const LIMBS: usize> Visitor<'_> for QuantityVisitor<BITS, LIMBS> {
+        type Value = Quantity<BITS, LIMBS>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(formatter, "a {BITS} bits quantity")
+        }
+
+        fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+            Uint::try_from(v)
+                .map(Quantity)
+                .map_err(|_| E::invalid_value(Unexpected::Unsigned(v), &self))
+        }
+
+        fn visit_u128<E: Error>(self, v: u128) -> Result<Self::Value, E> {
+            Uint::try_from(v).map(Quantity).map_err(E::custom)
+        }
+
+        fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+            let parsed = match v.strip_prefix("0x").or_else(|| v.strip_prefix("0X")) {
+                Some(hex) => Uint::from_str_radix(hex, 16),
+                // Decimal digits only, read as hex if the value is the same in decimal.
+                None if v.bytes().all(|b| b.is_ascii_digit()) => {
+                    match (Uint::from_str_radix(v, 16), Uint::from_str_radix(v, 10)) {
+                        (Ok(hex), Ok(decimal)) if hex == decimal => Ok(hex),
+                        _ => return Err(E::invalid_value(Unexpected::Str(v), &"a 0x prefix")),
+                    }
+                }
+                None => Uint::from_str_radix(v, 16),
+            };
+            parsed.map(Quantity).map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "lenient"))]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn lenient_quantities() {
+        #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+        struct Value {
+            #[serde(with = "crate::num::u64_via_ruint")]
+            number: u64,
+            #[serde(default, with = "crate::num::u128_vec_via_ruint")]
+            fees: Vec<u128>,
+            #[serde(default, deserialize_with = "null_as_default")]
+            logs: Vec<u64>,
+        }
+
+        let value = Value { number: 0x1a, fees: vec![], logs: vec![] };
+        for json in [
+            r#"{"number":"0x1a","fees":[],"logs":[]}"#,
+            r#"{"number":"1a","fees":null,"logs":null}"#,
+            r#"{"number":26}"#,
+        ] {
+            assert_eq!(serde_json::from_str::<Value>(json).unwrap(), value, "{json}");
+        }
+
+        let value = Value { number: 10, fees: vec![1, 0x20], logs: vec![3] };
+        let json = r#"{"number":"0xa","fees":["1","0x20"],"logs":[3]}"#;
+        assert_eq!(serde_json::from_str::<Value>(json).unwrap(), value);
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"number":"0xa","fees":["0x1","0x20"],"logs":[3]}"#
+        );
+
+        assert!(serde_json::from_str::<Value>(r#"{"number":"0x1g"}"#).is_err());
+        // Either 10 or 16.
+        assert!(serde_json::from_str::<Value>(r#"{"number":"10"}"#).is_err());
+        assert!(serde_json::from_str::<Value>(r#"{"number":"26"}"#).is_err());
+        assert!(serde_json::from_str::<Value>(r#"{"number":"0x10000000000000000"}"#).is_err());
+    }
+}
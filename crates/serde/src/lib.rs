@@ -24,6 +24,8 @@ extern crate alloc;
 mod bool;
 pub use self::bool::*;
 
+pub mod lenient;
+
 /// Helpers for dealing with numbers.
 pub mod num;
 pub use self::num::*;
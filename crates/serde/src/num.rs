@@ -2,6 +2,7 @@
 
 /// serde functions for handling `u8` via [U8](alloy_primitives::U8)
 pub mod u8_via_ruint {
+    use crate::lenient::Quantity;
     use alloy_primitives::U8;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -10,7 +11,7 @@ pub mod u8_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        U8::deserialize(deserializer).map(|val| val.to())
+        Quantity::<8, 1>::deserialize(deserializer).map(|val| val.0.to())
     }
 
     /// Serializes u64 as hex string
@@ -21,6 +22,7 @@ pub mod u8_via_ruint {
 
 /// serde functions for handling `Option<u8>` via [U8](alloy_primitives::U8)
 pub mod u8_opt_via_ruint {
+    use crate::lenient::Quantity;
     use alloy_primitives::U8;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -37,12 +39,13 @@ pub mod u8_opt_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        Ok(U8::deserialize(deserializer).map_or(None, |v| Some(u8::from_be_bytes(v.to_be_bytes()))))
+        Ok(Quantity::<8, 1>::deserialize(deserializer).map_or(None, |v| Some(v.0.to())))
     }
 }
 
 /// serde functions for handling `u64` via [U64](alloy_primitives::U64)
 pub mod u64_via_ruint {
+    use crate::lenient::Quantity;
     use alloy_primitives::U64;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -51,7 +54,7 @@ pub mod u64_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        U64::deserialize(deserializer).map(|val| val.to())
+        Quantity::<64, 1>::deserialize(deserializer).map(|val| val.0.to())
     }
 
     /// Serializes u64 as hex string
@@ -62,6 +65,7 @@ pub mod u64_via_ruint {
 
 /// serde functions for handling `Option<u64>` via [U64](alloy_primitives::U64)
 pub mod u64_opt_via_ruint {
+    use crate::lenient::Quantity;
     use alloy_primitives::U64;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -78,13 +82,13 @@ pub mod u64_opt_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        Ok(U64::deserialize(deserializer)
-            .map_or(None, |v| Some(u64::from_be_bytes(v.to_be_bytes()))))
+        Ok(Quantity::<64, 1>::deserialize(deserializer).map_or(None, |v| Some(v.0.to())))
     }
 }
 
 /// serde functions for handling primitive `u128` via [U128](alloy_primitives::U128)
 pub mod u128_via_ruint {
+    use crate::lenient::Quantity;
     use alloy_primitives::U128;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -94,7 +98,7 @@ pub mod u128_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        U128::deserialize(deserializer).map(|val| val.to())
+        Quantity::<128, 2>::deserialize(deserializer).map(|val| val.0.to())
     }
 
     /// Serializes u128 as hex string
@@ -105,6 +109,7 @@ pub mod u128_via_ruint {
 
 /// serde functions for handling primitive optional `u128` via [U128](alloy_primitives::U128)
 pub mod u128_opt_via_ruint {
+    use crate::lenient::Quantity;
     use alloy_primitives::U128;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -114,8 +119,8 @@ pub mod u128_opt_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        match Option::<U128>::deserialize(deserializer)? {
-            Some(val) => Ok(Some(val.to())),
+        match Option::<Quantity<128, 2>>::deserialize(deserializer)? {
+            Some(val) => Ok(Some(val.0.to())),
             None => Ok(None),
         }
     }
@@ -131,10 +136,11 @@ pub mod u128_opt_via_ruint {
 
 /// serde functions for handling `Vec<u128>` via [U128](alloy_primitives::U128)
 pub mod u128_vec_via_ruint {
+    use crate::lenient::{null_as_default, Quantity};
     #[cfg(not(feature = "std"))]
     use alloc::vec::Vec;
     use alloy_primitives::U128;
-    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use serde::{Deserializer, Serialize, Serializer};
 
     /// Deserializes an `u128` accepting a hex quantity string with optional 0x prefix or
     /// a number
@@ -142,8 +148,8 @@ pub mod u128_vec_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        let vec = Vec::<U128>::deserialize(deserializer)?;
-        Ok(vec.into_iter().map(|val| val.to()).collect())
+        let vec: Vec<Quantity<128, 2>> = null_as_default(deserializer)?;
+        Ok(vec.into_iter().map(|val| val.0.to()).collect())
     }
 
     /// Serializes u128 as hex string
@@ -155,6 +161,7 @@ pub mod u128_vec_via_ruint {
 
 /// serde functions for handling `Vec<Vec<u128>>` via [U128](alloy_primitives::U128)
 pub mod u128_vec_vec_opt_via_ruint {
+    use crate::lenient::Quantity;
     use alloy_primitives::U128;
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
@@ -167,9 +174,9 @@ pub mod u128_vec_vec_opt_via_ruint {
     where
         D: Deserializer<'de>,
     {
-        match Option::<Vec<Vec<U128>>>::deserialize(deserializer)? {
+        match Option::<Vec<Vec<Quantity<128, 2>>>>::deserialize(deserializer)? {
             Some(vec) => Ok(Some(
-                vec.into_iter().map(|v| v.into_iter().map(|val| val.to()).collect()).collect(),
+                vec.into_iter().map(|v| v.into_iter().map(|val| val.0.to()).collect()).collect(),
             )),
             None => Ok(None),
         }
@@ -233,9 +240,11 @@ mod tests {
         assert_eq!(val, deserialized);
 
         let s = "{\"inner\":\"1000\"}".to_string();
-        let deserialized: Value = serde_json::from_str(&s).unwrap();
-
-        assert_eq!(val, deserialized);
+        // Either 1000 or 0x1000 with the `lenient` feature.
+        #[cfg(feature = "lenient")]
+        assert!(serde_json::from_str::<Value>(&s).is_err());
+        #[cfg(not(feature = "lenient"))]
+        assert_eq!(val, serde_json::from_str(&s).unwrap());
     }
 
     #[test]
@@ -254,9 +263,11 @@ mod tests {
         assert_eq!(val, deserialized);
 
         let s = "{\"inner\":\"1000\"}".to_string();
-        let deserialized: Value = serde_json::from_str(&s).unwrap();
-
-        assert_eq!(val, deserialized);
+        // Either 1000 or 0x1000 with the `lenient` feature.
+        #[cfg(feature = "lenient")]
+        assert!(serde_json::from_str::<Value>(&s).is_err());
+        #[cfg(not(feature = "lenient"))]
+        assert_eq!(val, serde_json::from_str(&s).unwrap());
 
         let val = Value { inner: None };
         let s = serde_json::to_string(&val).unwrap();
@@ -1,7 +1,8 @@
 //! Json U256 serde helpers.
 
+use alloc::string::ToString;
 use alloy_primitives::U256;
-use serde::{de::Error, Deserialize, Deserializer};
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
 use serde_json::Value;
 
 /// Supports parsing the TTD as an `Option<u64>`, or `Option<f64>` specifically for the mainnet TTD
@@ -14,6 +15,21 @@ where
     value.map(|value| ttd_from_value::<'de, D>(value)).transpose()
 }
 
+/// Serializes the TTD as a JSON number, like geth does, falling back to a string for values that
+/// do not fit in a `u128`.
+pub fn serialize_json_ttd_opt<S>(value: &Option<U256>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match value {
+        None => serializer.serialize_none(),
+        Some(value) => match u128::try_from(*value) {
+            Ok(value) => serializer.serialize_u128(value),
+            Err(_) => serializer.serialize_str(&value.to_string()),
+        },
+    }
+}
+
 /// Converts the given [serde_json::Value] into a `U256` value for TTD deserialization.
 fn ttd_from_value<'de, D>(val: Value) -> Result<U256, D::Error>
 where
@@ -113,4 +129,16 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn serialize_ttd() {
+        #[derive(Serialize)]
+        struct Ttd(#[serde(serialize_with = "super::serialize_json_ttd_opt")] Option<U256>);
+
+        let ttds =
+            [Ttd(None), Ttd(Some(U256::ZERO)), Ttd(Some(U256::from(5875u128 * 10u128.pow(19))))];
+        assert_eq!(serde_json::to_string(&ttds).unwrap(), "[null,0,58750000000000000000000]");
+        let ttd = Ttd(Some(U256::MAX));
+        assert_eq!(serde_json::to_string(&ttd).unwrap(), format!("\"{}\"", U256::MAX));
+    }
 }
@@ -42,6 +42,9 @@ pub use yubihsm;
 #[cfg(feature = "mnemonic")]
 pub use coins_bip39;
 
+#[cfg(feature = "keystore")]
+pub use eth_keystore;
+
 /// A wallet instantiated with a locally stored private key
 pub type LocalWallet = Wallet<k256::ecdsa::SigningKey>;
 
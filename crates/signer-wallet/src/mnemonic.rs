@@ -9,7 +9,10 @@ use coins_bip32::path::DerivationPath;
 use coins_bip39::{Mnemonic, Wordlist};
 use k256::ecdsa::SigningKey;
 use rand::Rng;
-use std::{marker::PhantomData, path::PathBuf};
+use std::{
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
 const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
@@ -19,9 +22,9 @@ const DEFAULT_DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[must_use = "builders do nothing unless `build` is called"]
 pub struct MnemonicBuilder<W: Wordlist> {
-    /// The mnemonic phrase can be supplied to the builder as a string. A builder that has a valid
-    /// phrase should `build` the wallet.
-    phrase: Option<String>,
+    /// The mnemonic phrase can be supplied to the builder as a string or a path to the file that
+    /// contains the phrase. A builder that has a valid phrase should `build` the wallet.
+    phrase: Option<PathOrString>,
     /// The mnemonic builder can also be asked to generate a new random wallet by providing the
     /// number of words in the phrase. By default this is set to 12.
     word_count: usize,
@@ -49,6 +52,25 @@ pub enum MnemonicBuilderError {
     UnexpectedPhraseFound,
 }
 
+/// A mnemonic phrase, or the path to a file containing one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PathOrString {
+    /// Path to a file containing the phrase.
+    Path(PathBuf),
+    /// The phrase itself.
+    String(String),
+}
+
+impl PathOrString {
+    /// Returns the phrase, reading it from disk if a path was provided.
+    fn read(&self) -> Result<String, std::io::Error> {
+        match self {
+            Self::Path(path) => Ok(std::fs::read_to_string(path)?.trim().to_string()),
+            Self::String(phrase) => Ok(phrase.clone()),
+        }
+    }
+}
+
 impl<W: Wordlist> Default for MnemonicBuilder<W> {
     fn default() -> Self {
         Self {
@@ -80,7 +102,19 @@ impl<W: Wordlist> MnemonicBuilder<W> {
     /// # }
     /// ```
     pub fn phrase<P: Into<String>>(mut self, phrase: P) -> Self {
-        self.phrase = Some(phrase.into());
+        let phrase = phrase.into();
+        self.phrase = Some(if Path::new(&phrase).is_file() {
+            PathOrString::Path(phrase.into())
+        } else {
+            PathOrString::String(phrase)
+        });
+        self
+    }
+
+    /// Sets the path to the file that contains the mnemonic phrase. The file is read when `build`
+    /// is called.
+    pub fn phrase_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.phrase = Some(PathOrString::Path(path.into()));
         self
     }
 
@@ -131,7 +165,7 @@ impl<W: Wordlist> MnemonicBuilder<W> {
     /// the phrase field to be set.
     pub fn build(&self) -> Result<LocalWallet, WalletError> {
         let mnemonic = match &self.phrase {
-            Some(phrase) => Mnemonic::<W>::new_from_phrase(phrase)?,
+            Some(phrase) => Mnemonic::<W>::new_from_phrase(&phrase.read()?)?,
             None => return Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
         };
         self.mnemonic_to_wallet(&mnemonic)
@@ -249,4 +283,36 @@ mod tests {
 
         dir.close().unwrap();
     }
+
+    #[test]
+    fn mnemonic_from_path() {
+        let dir = tempdir().unwrap();
+
+        let wallet1 = MnemonicBuilder::<English>::default()
+            .derivation_path(TEST_DERIVATION_PATH)
+            .unwrap()
+            .write_to(dir.as_ref())
+            .build_random()
+            .unwrap();
+
+        // Load the phrase directly from the file it was written to.
+        let phrase_path = dir.as_ref().join(wallet1.address.to_string());
+        let wallet2 = MnemonicBuilder::<English>::default()
+            .phrase(phrase_path.to_str().unwrap())
+            .derivation_path(TEST_DERIVATION_PATH)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(wallet1.address, wallet2.address);
+
+        let wallet3 = MnemonicBuilder::<English>::default()
+            .phrase_path(phrase_path)
+            .derivation_path(TEST_DERIVATION_PATH)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(wallet1.address, wallet3.address);
+
+        dir.close().unwrap();
+    }
 }
@@ -108,7 +108,12 @@ impl Wallet<SigningKey> {
         Ok((Self::from_slice(&secret)?, uuid))
     }
 
-    /// Decrypts an encrypted JSON from the provided path to construct a Wallet instance
+    /// Decrypts an encrypted JSON from the provided path to construct a Wallet instance.
+    ///
+    /// Both the scrypt and PBKDF2 key derivation functions of the [Web3 Secret Storage]
+    /// definition are supported, so keystores created by geth or clef can be loaded directly.
+    ///
+    /// [Web3 Secret Storage]: https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/
     #[inline]
     pub fn decrypt_keystore<P, S>(keypath: P, password: S) -> Result<Self, WalletError>
     where
@@ -245,6 +250,45 @@ mod tests {
         test_encrypted_json_keystore(key, &uuid, dir.path());
     }
 
+    #[test]
+    #[cfg(feature = "keystore")]
+    fn decrypt_pbkdf2_keystore() {
+        // test vector from the Web3 Secret Storage definition:
+        // https://ethereum.org/en/developers/docs/data-structures-and-encoding/web3-secret-storage/#PBKDF2-SHA-256
+        let keystore = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+                "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 262144,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("key-pbkdf2.json");
+        std::fs::write(&path, keystore).unwrap();
+
+        let wallet = Wallet::<SigningKey>::decrypt_keystore(&path, "testpassword").unwrap();
+        assert_eq!(
+            wallet.to_bytes(),
+            b256!("7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d")
+        );
+
+        let err = Wallet::<SigningKey>::decrypt_keystore(&path, "wrongpassword").unwrap_err();
+        assert!(matches!(
+            err,
+            WalletError::EthKeystoreError(eth_keystore::KeystoreError::MacMismatch)
+        ));
+    }
+
     #[test]
     fn signs_msg() {
         let message = "Some data";
@@ -264,6 +308,31 @@ mod tests {
         assert_eq!(recovered2, address);
     }
 
+    #[test]
+    fn signs_eip191_versions() {
+        use alloy_signer::eip191::{
+            Eip191Message, IntendedValidatorMessage, PersonalMessage, StructuredDataMessage,
+        };
+
+        let key = Wallet::<SigningKey>::random_with(&mut rand::thread_rng());
+        let data = B256::repeat_byte(0x42);
+        let validator = IntendedValidatorMessage::new(alloy_primitives::Address::ZERO, data);
+        let structured = StructuredDataMessage::new(data, data);
+        let personal = PersonalMessage::new(data);
+
+        let signature = key.sign_eip191_sync(&validator).unwrap();
+        assert_eq!(validator.recover_address(&signature).unwrap(), key.address);
+        let signature = key.sign_eip191_sync(&structured).unwrap();
+        assert_eq!(structured.recover_address(&signature).unwrap(), key.address);
+
+        // The same data is signed in a different domain by each version.
+        let signature = key.sign_eip191_sync(&personal).unwrap();
+        assert_eq!(signature, key.sign_message_sync(data.as_slice()).unwrap());
+        assert_eq!(personal.recover_address(&signature).unwrap(), key.address);
+        assert_ne!(validator.recover_address(&signature).unwrap(), key.address);
+        assert_ne!(structured.recover_address(&signature).unwrap(), key.address);
+    }
+
     #[test]
     #[cfg(feature = "eip712")]
     fn typed_data() {
@@ -294,7 +363,7 @@ mod tests {
         let foo_bar = FooBar {
             foo: I256::try_from(10u64).unwrap(),
             bar: U256::from(20u64),
-            fizz: b"fizz".to_vec(),
+            fizz: b"fizz".into(),
             buzz: keccak256("buzz"),
             far: "space".into(),
             out: Address::ZERO,
@@ -314,6 +383,65 @@ mod tests {
         assert_eq!(wallet.sign_hash_sync(&dynamic_hash).unwrap(), sig_dynamic);
     }
 
+    #[test]
+    #[cfg(feature = "eip712")]
+    fn typed_data_json() {
+        use alloy_dyn_abi::eip712::TypedData;
+        use alloy_primitives::keccak256;
+
+        // `eth_signTypedData_v4` payload from the EIP-712 reference implementation:
+        // https://github.com/ethereum/EIPs/blob/master/assets/eip-712/Example.js
+        let json = r#"{
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }"#;
+        let typed_data: TypedData = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            typed_data.eip712_signing_hash().unwrap(),
+            b256!("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2")
+        );
+
+        let wallet = Wallet::from_bytes(&keccak256("cow")).unwrap();
+        assert_eq!(wallet.address, address!("CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"));
+        let sig = wallet.sign_dynamic_typed_data_sync(&typed_data).unwrap();
+        assert_eq!(sig.v().y_parity_byte(), 1);
+        assert_eq!(
+            B256::from(sig.r()),
+            b256!("4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d")
+        );
+        assert_eq!(
+            B256::from(sig.s()),
+            b256!("07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562")
+        );
+    }
+
     #[test]
     fn key_to_address() {
         let wallet: Wallet<SigningKey> =
@@ -0,0 +1,327 @@
+use alloy_consensus::SignableTransaction;
+use alloy_primitives::{hex, keccak256, utils::eip191_message, Address, ChainId, B256};
+use alloy_signer::{
+    sign_transaction_with_chain_id, Result, Signature, Signer, UnsupportedSignerOperation,
+};
+use async_trait::async_trait;
+use k256::ecdsa::{self, VerifyingKey};
+use serde::Serialize;
+use std::fmt;
+use thiserror::Error;
+use url::Url;
+
+#[cfg(feature = "eip712")]
+use alloy_dyn_abi::TypedData;
+#[cfg(feature = "eip712")]
+use alloy_sol_types::{Eip712Domain, SolStruct};
+
+/// [Web3Signer] Ethereum signer.
+///
+/// The signer sends signing requests for one of the keys of a Web3Signer instance to its `eth1`
+/// REST API. Web3Signer hashes the data it signs, so the signer sends the preimage of every
+/// signing hash: the signing payload of transactions, the [EIP-191] prefixed message, and the
+/// [EIP-712] encoding of typed data. Signing a bare hash is thus not supported, and
+/// [`sign_hash`](Signer::sign_hash) always returns an error.
+///
+/// The key is selected by address among the public keys of the instance, which are retrieved on
+/// instantiation. This means that the new function is `async` and must be called within some
+/// runtime.
+///
+/// TLS, including client certificates for mutual TLS, is configured on the [`reqwest::Client`]
+/// used by the signer. Client certificates require the `rustls-tls` feature, which provides
+/// [`Identity::from_pem`](reqwest::Identity), or the `native-tls` feature, which provides
+/// PKCS#12 identities.
+///
+/// # Examples
+///
+/// ```no_run
+/// use alloy_signer::Signer;
+/// use alloy_signer_web3signer::{reqwest, Web3Signer};
+///
+/// # async fn test() -> Result<(), Box<dyn std::error::Error>> {
+/// let url = "https://web3signer.internal:9000".parse()?;
+/// let signer = Web3Signer::new(reqwest::Client::new(), url, None, Some(1)).await?;
+///
+/// let message = vec![0, 1, 2, 3];
+/// let sig = signer.sign_message(&message).await?;
+/// assert_eq!(sig.recover_address_from_msg(message)?, signer.address());
+/// # Ok(())
+/// # }
+/// ```
+///
+#[cfg_attr(feature = "rustls-tls", doc = "With a client certificate for mutual TLS:")]
+#[cfg_attr(feature = "rustls-tls", doc = "")]
+#[cfg_attr(
+    feature = "rustls-tls",
+    doc = r#"```no_run
+use alloy_signer_web3signer::{reqwest, Web3Signer};
+
+# async fn test() -> Result<(), Box<dyn std::error::Error>> {
+// The PEM file holds both the private key and the certificate chain.
+let identity = reqwest::Identity::from_pem(&std::fs::read("client.pem")?)?;
+let client = reqwest::Client::builder().use_rustls_tls().identity(identity).build()?;
+
+let url = "https://web3signer.internal:9000".parse()?;
+let signer = Web3Signer::new(client, url, None, Some(1)).await?;
+# Ok(())
+# }
+```"#
+)]
+#[cfg_attr(feature = "rustls-tls", doc = "")]
+/// [Web3Signer]: https://docs.web3signer.consensys.io/
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+#[derive(Clone)]
+pub struct Web3Signer {
+    client: reqwest::Client,
+    url: Url,
+    identifier: String,
+    chain_id: Option<ChainId>,
+    address: Address,
+}
+
+impl fmt::Debug for Web3Signer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Web3Signer")
+            .field("url", &self.url.as_str())
+            .field("identifier", &self.identifier)
+            .field("chain_id", &self.chain_id)
+            .field("address", &self.address)
+            .finish()
+    }
+}
+
+/// Errors thrown by [`Web3Signer`].
+#[derive(Debug, Error)]
+pub enum Web3SignerError {
+    /// Thrown on a request error, including error responses of Web3Signer.
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    /// Thrown when the URL of a request cannot be built.
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+    /// Thrown when a public key or signature is not valid hex.
+    #[error(transparent)]
+    Hex(#[from] hex::FromHexError),
+    /// [`ecdsa`] error.
+    #[error(transparent)]
+    K256(#[from] ecdsa::Error),
+    /// Thrown when a signature cannot be parsed.
+    #[error(transparent)]
+    Signature(#[from] alloy_primitives::SignatureError),
+    /// Thrown when Web3Signer holds no key for the requested address.
+    #[error("web3signer holds no key for {0}")]
+    KeyNotFound(Address),
+    /// Thrown when Web3Signer holds no key at all.
+    #[error("web3signer holds no keys")]
+    NoKeys,
+    /// Thrown when a signature returned by Web3Signer was not made by the key of the signer.
+    #[error("signature was made by {0} instead of the key of the signer")]
+    WrongSigner(Address),
+}
+
+#[derive(Serialize)]
+struct SignRequest<'a> {
+    data: &'a str,
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl alloy_network::TxSigner<Signature> for Web3Signer {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    #[inline]
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> Result<Signature> {
+        sign_transaction_with_chain_id!(self, tx, self.sign_data(&tx.encoded_for_signing()).await)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Signer for Web3Signer {
+    #[inline]
+    async fn sign_hash(&self, _hash: &B256) -> Result<Signature> {
+        Err(alloy_signer::Error::UnsupportedOperation(UnsupportedSignerOperation::SignHash))
+    }
+
+    #[inline]
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature> {
+        self.sign_data(&eip191_message(message)).await.map_err(alloy_signer::Error::other)
+    }
+
+    #[cfg(feature = "eip712")]
+    #[inline]
+    async fn sign_typed_data<T: SolStruct + Send + Sync>(
+        &self,
+        payload: &T,
+        domain: &Eip712Domain,
+    ) -> Result<Signature> {
+        self.sign_typed_data_(&payload.eip712_hash_struct(), domain)
+            .await
+            .map_err(alloy_signer::Error::other)
+    }
+
+    #[cfg(feature = "eip712")]
+    #[inline]
+    async fn sign_dynamic_typed_data(&self, payload: &TypedData) -> Result<Signature> {
+        self.sign_typed_data_(&payload.hash_struct()?, &payload.domain)
+            .await
+            .map_err(alloy_signer::Error::other)
+    }
+
+    #[inline]
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    #[inline]
+    fn chain_id(&self) -> Option<ChainId> {
+        self.chain_id
+    }
+
+    #[inline]
+    fn set_chain_id(&mut self, chain_id: Option<ChainId>) {
+        self.chain_id = chain_id;
+    }
+}
+
+impl Web3Signer {
+    /// Instantiate a new signer for the key of `address` held by the Web3Signer instance at
+    /// `url`, or for its first key if `address` is `None`.
+    #[instrument(skip(client), err)]
+    pub async fn new(
+        client: reqwest::Client,
+        url: Url,
+        address: Option<Address>,
+        chain_id: Option<ChainId>,
+    ) -> Result<Self, Web3SignerError> {
+        let keys: Vec<String> = client
+            .get(endpoint(&url, "publicKeys")?)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let mut found = None;
+        for identifier in keys {
+            let key_address = public_key_address(&identifier)?;
+            if address.map_or(true, |address| address == key_address) {
+                found = Some((identifier, key_address));
+                break;
+            }
+        }
+        let (identifier, address) = found.ok_or(match address {
+            Some(address) => Web3SignerError::KeyNotFound(address),
+            None => Web3SignerError::NoKeys,
+        })?;
+
+        debug!(%identifier, %address, "instantiated Web3Signer signer");
+        Ok(Self { client, url, identifier, chain_id, address })
+    }
+
+    /// Returns the identifier of the key of the signer, which is its public key.
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// Signs the keccak256 hash of `data` with the key of the signer.
+    ///
+    /// The signature is checked against the address of the signer.
+    #[instrument(err, skip(data), fields(data = %hex::encode(data)))]
+    pub async fn sign_data(&self, data: &[u8]) -> Result<Signature, Web3SignerError> {
+        let url = endpoint(&self.url, &format!("sign/{}", self.identifier))?;
+        let request = SignRequest { data: &hex::encode_prefixed(data) };
+        let response = self.client.post(url).json(&request).send().await?;
+        let signature = decode_signature(response.error_for_status()?.text().await?.trim())?;
+
+        let signer = signature.recover_address_from_prehash(&keccak256(data))?;
+        if signer != self.address {
+            return Err(Web3SignerError::WrongSigner(signer));
+        }
+        Ok(signature)
+    }
+
+    /// Signs the EIP-712 encoding of the given struct hash and domain.
+    #[cfg(feature = "eip712")]
+    async fn sign_typed_data_(
+        &self,
+        hash_struct: &B256,
+        domain: &Eip712Domain,
+    ) -> Result<Signature, Web3SignerError> {
+        let data = [&[0x19, 0x01], domain.separator().as_slice(), hash_struct.as_slice()].concat();
+        self.sign_data(&data).await
+    }
+}
+
+/// Returns the URL of an endpoint of the `eth1` API of the Web3Signer instance at `url`.
+fn endpoint(url: &Url, path: &str) -> Result<Url, url::ParseError> {
+    let base = url.as_str().trim_end_matches('/');
+    Url::parse(&format!("{base}/api/v1/eth1/{path}"))
+}
+
+/// Returns the address of a public key as listed by Web3Signer, which omits the SEC1 prefix of
+/// uncompressed keys.
+fn public_key_address(public_key: &str) -> Result<Address, Web3SignerError> {
+    let mut bytes = hex::decode(public_key)?;
+    if bytes.len() == 64 {
+        bytes.insert(0, 0x04);
+    }
+    let key = VerifyingKey::from_sec1_bytes(&bytes)?;
+    Ok(alloy_signer::utils::public_key_to_address(&key))
+}
+
+/// Decodes a signature returned by Web3Signer: `r`, `s` and `v`, where `v` is 27 or 28.
+fn decode_signature(signature: &str) -> Result<Signature, Web3SignerError> {
+    let bytes = hex::decode(signature)?;
+    Ok(Signature::try_from(bytes.as_slice())?.with_parity_bool())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey;
+
+    #[test]
+    fn decodes_responses() {
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let public_key =
+            hex::encode_prefixed(&key.verifying_key().to_encoded_point(false).as_bytes()[1..]);
+        let address = alloy_signer::utils::public_key_to_address(key.verifying_key());
+        assert_eq!(public_key_address(&public_key).unwrap(), address);
+
+        let hash = keccak256(eip191_message(b"hello"));
+        let (sig, recid) = key.sign_prehash_recoverable(hash.as_slice()).unwrap();
+        let mut bytes = sig.to_bytes().to_vec();
+        bytes.push(27 + recid.to_byte());
+        let signature = decode_signature(&hex::encode_prefixed(bytes)).unwrap();
+        assert_eq!(signature.recover_address_from_msg(b"hello").unwrap(), address);
+    }
+
+    #[test]
+    fn endpoints() {
+        let url = "https://web3signer:9000/".parse().unwrap();
+        assert_eq!(
+            endpoint(&url, "publicKeys").unwrap().as_str(),
+            "https://web3signer:9000/api/v1/eth1/publicKeys"
+        );
+    }
+
+    #[tokio::test]
+    async fn sign_message() {
+        let Ok(url) = std::env::var("WEB3SIGNER_URL") else {
+            return;
+        };
+        let client = reqwest::Client::new();
+        let signer = Web3Signer::new(client, url.parse().unwrap(), None, None).await.unwrap();
+
+        let message = vec![0, 1, 2, 3];
+        let sig = signer.sign_message(&message).await.unwrap();
+        assert_eq!(sig.recover_address_from_msg(message).unwrap(), signer.address());
+    }
+}
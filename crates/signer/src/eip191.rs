@@ -0,0 +1,196 @@
+//! The versions of signed data defined by [EIP-191].
+//!
+//! Signed data is encoded as `0x19 <version> <version specific data> <data>`, so that it can never
+//! be a valid RLP transaction, and so that the data signed for one version cannot be replayed as
+//! data of another. Each version has its own type here, so that the data of custom protocols is
+//! always signed in the domain it was built for:
+//! - [`IntendedValidatorMessage`], version `0x00`, for data meant to be validated by a contract;
+//! - [`StructuredDataMessage`], version `0x01`, for [EIP-712] typed data;
+//! - [`PersonalMessage`], version `0x45`, for the messages of `personal_sign`, as signed by
+//!   [`Signer::sign_message`](crate::Signer::sign_message).
+//!
+//! [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+
+use alloy_primitives::{
+    keccak256, utils::eip191_message, Address, Bytes, Signature, SignatureError, B256,
+};
+
+#[cfg(feature = "eip712")]
+use alloy_sol_types::{Eip712Domain, SolStruct};
+
+/// The byte prefixing all [EIP-191] signed data.
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+pub const EIP191_PREFIX: u8 = 0x19;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Data signed under one of the versions of [EIP-191].
+///
+/// This trait is sealed, and only implemented by the types of the versions defined by the EIP.
+///
+/// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+pub trait Eip191Message: private::Sealed {
+    /// The version byte of the data.
+    const VERSION: u8;
+
+    /// Returns the encoded data to sign, starting with [`EIP191_PREFIX`] and the version byte.
+    fn encode_eip191(&self) -> Vec<u8>;
+
+    /// Returns the hash of the [encoded data](Self::encode_eip191), which is the hash signed.
+    fn eip191_signing_hash(&self) -> B256 {
+        keccak256(self.encode_eip191())
+    }
+
+    /// Recovers the address that signed the data with `signature`.
+    fn recover_address(&self, signature: &Signature) -> Result<Address, SignatureError> {
+        signature.recover_address_from_prehash(&self.eip191_signing_hash())
+    }
+}
+
+/// Version `0x00` data, meant to be validated by the contract at `validator`.
+///
+/// The data is encoded after the address of the validator, such that a signature for a
+/// contract cannot be replayed on another one, e.g. for multisig wallets.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntendedValidatorMessage {
+    /// The address of the contract validating the signature.
+    pub validator: Address,
+    /// The data signed.
+    pub data: Bytes,
+}
+
+impl IntendedValidatorMessage {
+    /// Creates new data for the given validator.
+    pub fn new(validator: Address, data: impl Into<Bytes>) -> Self {
+        Self { validator, data: data.into() }
+    }
+}
+
+impl private::Sealed for IntendedValidatorMessage {}
+
+impl Eip191Message for IntendedValidatorMessage {
+    const VERSION: u8 = 0x00;
+
+    fn encode_eip191(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(2 + Address::len_bytes() + self.data.len());
+        out.extend_from_slice(&[EIP191_PREFIX, Self::VERSION]);
+        out.extend_from_slice(self.validator.as_slice());
+        out.extend_from_slice(&self.data);
+        out
+    }
+}
+
+/// Version `0x01` data, the [EIP-712] hash of a struct in a domain.
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StructuredDataMessage {
+    /// The hash of the domain, see [`Eip712Domain::separator`].
+    ///
+    /// [`Eip712Domain::separator`]: alloy_sol_types::Eip712Domain::separator
+    pub domain_separator: B256,
+    /// The `hashStruct` of the message.
+    pub struct_hash: B256,
+}
+
+impl StructuredDataMessage {
+    /// Creates new data from the domain separator and the struct hash.
+    pub const fn new(domain_separator: B256, struct_hash: B256) -> Self {
+        Self { domain_separator, struct_hash }
+    }
+
+    /// Creates the data of `payload` in `domain`.
+    ///
+    /// This has the same signing hash as [`SolStruct::eip712_signing_hash`].
+    #[cfg(feature = "eip712")]
+    pub fn from_struct<T: SolStruct>(payload: &T, domain: &Eip712Domain) -> Self {
+        Self::new(domain.separator(), payload.eip712_hash_struct())
+    }
+}
+
+impl private::Sealed for StructuredDataMessage {}
+
+impl Eip191Message for StructuredDataMessage {
+    const VERSION: u8 = 0x01;
+
+    fn encode_eip191(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(66);
+        out.extend_from_slice(&[EIP191_PREFIX, Self::VERSION]);
+        out.extend_from_slice(self.domain_separator.as_slice());
+        out.extend_from_slice(self.struct_hash.as_slice());
+        out
+    }
+}
+
+/// Version `0x45` data, a message prefixed with `Ethereum Signed Message:\n` and its length, as
+/// signed by `personal_sign`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PersonalMessage(pub Bytes);
+
+impl PersonalMessage {
+    /// Creates a new personal message.
+    pub fn new(message: impl Into<Bytes>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl private::Sealed for PersonalMessage {}
+
+impl Eip191Message for PersonalMessage {
+    const VERSION: u8 = 0x45;
+
+    fn encode_eip191(&self) -> Vec<u8> {
+        eip191_message(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, eip191_hash_message};
+
+    #[test]
+    fn encodes_versions() {
+        let validator = address!("d8dA6BF26964aF9D7eEd9e03E53415D37aA96045");
+        let message = IntendedValidatorMessage::new(validator, &b"data"[..]);
+        let encoded = message.encode_eip191();
+        assert_eq!(encoded[..2], [0x19, 0x00]);
+        assert_eq!(encoded[2..22], validator[..]);
+        assert_eq!(encoded[22..], *b"data");
+
+        let message = StructuredDataMessage::new(B256::repeat_byte(1), B256::repeat_byte(2));
+        let encoded = message.encode_eip191();
+        assert_eq!(encoded[..2], [0x19, 0x01]);
+        assert_eq!(encoded.len(), 66);
+
+        let message = PersonalMessage::new(&b"Hello World"[..]);
+        assert_eq!(message.encode_eip191()[..2], [0x19, b'E']);
+        assert_eq!(message.eip191_signing_hash(), eip191_hash_message("Hello World"));
+        assert_eq!(
+            message.eip191_signing_hash(),
+            b256!("a1de988600a42c4b4ab089b619297c17d53cffae5d5120d82d8a92d0bb3b78f2")
+        );
+    }
+
+    #[cfg(feature = "eip712")]
+    #[test]
+    fn structured_data_hash() {
+        alloy_sol_types::sol! {
+            struct Mail {
+                address to;
+                string contents;
+            }
+        }
+
+        let mail = Mail { to: Address::repeat_byte(1), contents: "Hello".into() };
+        let domain = alloy_sol_types::eip712_domain! { name: "Mail", version: "1", chain_id: 1, };
+        assert_eq!(
+            StructuredDataMessage::from_struct(&mail, &domain).eip191_signing_hash(),
+            mail.eip712_signing_hash(&domain)
+        );
+    }
+}
@@ -15,12 +15,17 @@
 #![deny(unused_must_use, rust_2018_idioms)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod eip191;
+
 mod error;
 pub use error::{Error, Result, UnsupportedSignerOperation};
 
 mod signer;
 pub use signer::{Signer, SignerSync};
 
+#[cfg(feature = "eip712")]
+pub mod typed_data;
+
 pub mod utils;
 
 pub use alloy_primitives::Signature;
@@ -1,4 +1,4 @@
-use crate::Result;
+use crate::{eip191::Eip191Message, Result};
 use alloy_primitives::{eip191_hash_message, Address, ChainId, Signature, B256};
 use async_trait::async_trait;
 use auto_impl::auto_impl;
@@ -27,12 +27,26 @@ pub trait Signer<Sig = Signature> {
 
     /// Signs the hash of the provided message after prefixing it, as specified in [EIP-191].
     ///
+    /// This is version `0x45` of EIP-191, see [`PersonalMessage`](crate::eip191::PersonalMessage).
+    ///
     /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
     #[inline]
     async fn sign_message(&self, message: &[u8]) -> Result<Sig> {
         self.sign_hash(&eip191_hash_message(message)).await
     }
 
+    /// Signs data of any version of [EIP-191].
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    #[inline]
+    #[auto_impl(keep_default_for(&mut, Box))]
+    async fn sign_eip191<M: Eip191Message + Send + Sync>(&self, message: &M) -> Result<Sig>
+    where
+        Self: Sized,
+    {
+        self.sign_hash(&message.eip191_signing_hash()).await
+    }
+
     /// Encodes and signs the typed data according to [EIP-712].
     ///
     /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
@@ -98,12 +112,26 @@ pub trait SignerSync<Sig = Signature> {
 
     /// Signs the hash of the provided message after prefixing it, as specified in [EIP-191].
     ///
+    /// This is version `0x45` of EIP-191, see [`PersonalMessage`](crate::eip191::PersonalMessage).
+    ///
     /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
     #[inline]
     fn sign_message_sync(&self, message: &[u8]) -> Result<Sig> {
         self.sign_hash_sync(&eip191_hash_message(message))
     }
 
+    /// Signs data of any version of [EIP-191].
+    ///
+    /// [EIP-191]: https://eips.ethereum.org/EIPS/eip-191
+    #[inline]
+    #[auto_impl(keep_default_for(&, &mut, Box, Rc, Arc))]
+    fn sign_eip191_sync<M: Eip191Message>(&self, message: &M) -> Result<Sig>
+    where
+        Self: Sized,
+    {
+        self.sign_hash_sync(&message.eip191_signing_hash())
+    }
+
     /// Encodes and signs the typed data according to [EIP-712].
     ///
     /// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
@@ -0,0 +1,300 @@
+//! [EIP-3009] transfers with authorization, such as the gasless transfers of USD Coin.
+//!
+//! The holder of the tokens signs an authorization, which anyone can then submit to the token
+//! contract, paying the gas of the transfer. Authorizations are identified by a random nonce
+//! rather than a sequential one, so that several of them can be pending at once, and are only
+//! valid between two timestamps.
+//!
+//! # Examples
+//!
+//! ```
+//! use alloy_primitives::{address, Address, U256};
+//! use alloy_signer::{
+//!     typed_data::{eip3009::AuthorizationBuilder, token_domain},
+//!     SignerSync,
+//! };
+//! use std::time::Duration;
+//!
+//! # fn test() -> Result<(), Box<dyn std::error::Error>> {
+//! let signer = alloy_signer_wallet::LocalWallet::random();
+//! let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+//! let domain = token_domain("USD Coin", "2", 1, usdc);
+//!
+//! let authorization = AuthorizationBuilder::new(signer.address(), Address::ZERO, U256::from(1_000_000))
+//!     .valid_for(Duration::from_secs(3600))
+//!     .transfer();
+//! let signature = signer.sign_typed_data_sync(&authorization, &domain)?;
+//!
+//! // The calldata of the transfer, to be sent to the token contract by anyone.
+//! let call = authorization.call(&signature);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [EIP-3009]: https://eips.ethereum.org/EIPS/eip-3009
+
+use alloy_primitives::{keccak256, Address, Signature, B256, U256};
+use alloy_sol_types::sol;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+sol! {
+    /// Authorization of a transfer, which anyone can submit to the token contract.
+    #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct TransferWithAuthorization {
+        address from;
+        address to;
+        uint256 value;
+        uint256 validAfter;
+        uint256 validBefore;
+        bytes32 nonce;
+    }
+
+    /// Authorization of a transfer, which only the recipient can submit to the token contract.
+    ///
+    /// This prevents front-running the submission when the recipient is a contract that must act
+    /// on the transfer.
+    #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct ReceiveWithAuthorization {
+        address from;
+        address to;
+        uint256 value;
+        uint256 validAfter;
+        uint256 validBefore;
+        bytes32 nonce;
+    }
+
+    /// Cancellation of an authorization that has not been used yet.
+    #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct CancelAuthorization {
+        address authorizer;
+        bytes32 nonce;
+    }
+
+    /// The functions of EIP-3009 tokens.
+    #[allow(missing_docs)]
+    interface IERC3009 {
+        function authorizationState(address authorizer, bytes32 nonce) external view returns (bool);
+        function transferWithAuthorization(address from, address to, uint256 value, uint256 validAfter, uint256 validBefore, bytes32 nonce, uint8 v, bytes32 r, bytes32 s) external;
+        function receiveWithAuthorization(address from, address to, uint256 value, uint256 validAfter, uint256 validBefore, bytes32 nonce, uint8 v, bytes32 r, bytes32 s) external;
+        function cancelAuthorization(address authorizer, bytes32 nonce, uint8 v, bytes32 r, bytes32 s) external;
+    }
+}
+
+/// Returns a random authorization nonce.
+pub fn random_nonce() -> B256 {
+    B256::random()
+}
+
+/// Returns a deterministic authorization nonce derived from the given seed, such as the ID of a
+/// payment, so that retrying the payment cannot transfer twice.
+pub fn nonce_from_seed(seed: impl AsRef<[u8]>) -> B256 {
+    keccak256(seed)
+}
+
+/// Returns the `v`, `r` and `s` arguments of the EIP-3009 functions for a signature.
+fn vrs(signature: &Signature) -> (u8, B256, B256) {
+    (27 + signature.v().y_parity_byte(), signature.r().into(), signature.s().into())
+}
+
+/// Builder of [`TransferWithAuthorization`] and [`ReceiveWithAuthorization`].
+///
+/// The authorization is valid from now on and never expires, and its nonce is random, unless
+/// configured otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AuthorizationBuilder {
+    from: Address,
+    to: Address,
+    value: U256,
+    valid_after: u64,
+    valid_before: u64,
+    nonce: B256,
+}
+
+impl AuthorizationBuilder {
+    /// Creates a builder of the authorization to transfer `value` tokens from `from` to `to`.
+    pub fn new(from: Address, to: Address, value: U256) -> Self {
+        Self { from, to, value, valid_after: 0, valid_before: u64::MAX, nonce: random_nonce() }
+    }
+
+    /// Sets the unix timestamp after which the authorization is valid, in seconds.
+    pub const fn valid_after(mut self, timestamp: u64) -> Self {
+        self.valid_after = timestamp;
+        self
+    }
+
+    /// Sets the unix timestamp before which the authorization is valid, in seconds.
+    pub const fn valid_before(mut self, timestamp: u64) -> Self {
+        self.valid_before = timestamp;
+        self
+    }
+
+    /// Sets the authorization to expire after the given duration from now.
+    pub fn valid_for(self, duration: Duration) -> Self {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        self.valid_before(now.saturating_add(duration).as_secs())
+    }
+
+    /// Sets the nonce of the authorization.
+    pub const fn nonce(mut self, nonce: B256) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    /// Builds an authorization that anyone can submit.
+    pub fn transfer(self) -> TransferWithAuthorization {
+        TransferWithAuthorization {
+            from: self.from,
+            to: self.to,
+            value: self.value,
+            validAfter: U256::from(self.valid_after),
+            validBefore: U256::from(self.valid_before),
+            nonce: self.nonce,
+        }
+    }
+
+    /// Builds an authorization that only the recipient can submit.
+    pub fn receive(self) -> ReceiveWithAuthorization {
+        ReceiveWithAuthorization {
+            from: self.from,
+            to: self.to,
+            value: self.value,
+            validAfter: U256::from(self.valid_after),
+            validBefore: U256::from(self.valid_before),
+            nonce: self.nonce,
+        }
+    }
+
+    /// Builds the cancellation of the authorization.
+    pub const fn cancel(self) -> CancelAuthorization {
+        CancelAuthorization { authorizer: self.from, nonce: self.nonce }
+    }
+}
+
+/// Returns whether a block of the given timestamp can include an authorization valid after and
+/// before the given timestamps, which are both exclusive.
+fn is_valid_at(valid_after: U256, valid_before: U256, timestamp: u64) -> bool {
+    let timestamp = U256::from(timestamp);
+    valid_after < timestamp && timestamp < valid_before
+}
+
+impl TransferWithAuthorization {
+    /// Returns whether a block of the given timestamp can include the authorization.
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        is_valid_at(self.validAfter, self.validBefore, timestamp)
+    }
+
+    /// Returns the call of the token contract submitting the authorization with its signature.
+    pub fn call(&self, signature: &Signature) -> IERC3009::transferWithAuthorizationCall {
+        let (v, r, s) = vrs(signature);
+        IERC3009::transferWithAuthorizationCall {
+            from: self.from,
+            to: self.to,
+            value: self.value,
+            validAfter: self.validAfter,
+            validBefore: self.validBefore,
+            nonce: self.nonce,
+            v,
+            r,
+            s,
+        }
+    }
+}
+
+impl ReceiveWithAuthorization {
+    /// Returns whether a block of the given timestamp can include the authorization.
+    pub fn is_valid_at(&self, timestamp: u64) -> bool {
+        is_valid_at(self.validAfter, self.validBefore, timestamp)
+    }
+
+    /// Returns the call of the token contract submitting the authorization with its signature.
+    pub fn call(&self, signature: &Signature) -> IERC3009::receiveWithAuthorizationCall {
+        let (v, r, s) = vrs(signature);
+        IERC3009::receiveWithAuthorizationCall {
+            from: self.from,
+            to: self.to,
+            value: self.value,
+            validAfter: self.validAfter,
+            validBefore: self.validBefore,
+            nonce: self.nonce,
+            v,
+            r,
+            s,
+        }
+    }
+}
+
+impl CancelAuthorization {
+    /// Returns the call of the token contract submitting the cancellation with its signature.
+    pub fn call(&self, signature: &Signature) -> IERC3009::cancelAuthorizationCall {
+        let (v, r, s) = vrs(signature);
+        IERC3009::cancelAuthorizationCall {
+            authorizer: self.authorizer,
+            nonce: self.nonce,
+            v,
+            r,
+            s,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{typed_data::token_domain, utils::secret_key_to_address};
+    use alloy_primitives::address;
+    use alloy_sol_types::{SolCall, SolStruct};
+    use k256::ecdsa::SigningKey;
+
+    #[test]
+    fn type_hashes() {
+        // The type hashes of the specification.
+        let builder = AuthorizationBuilder::new(Address::ZERO, Address::ZERO, U256::ZERO);
+        assert_eq!(
+            builder.transfer().eip712_type_hash(),
+            keccak256("TransferWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)")
+        );
+        assert_eq!(
+            builder.receive().eip712_type_hash(),
+            keccak256("ReceiveWithAuthorization(address from,address to,uint256 value,uint256 validAfter,uint256 validBefore,bytes32 nonce)")
+        );
+        assert_eq!(
+            builder.cancel().eip712_type_hash(),
+            keccak256("CancelAuthorization(address authorizer,bytes32 nonce)")
+        );
+    }
+
+    #[test]
+    fn sign_transfer_authorization() {
+        let key = SigningKey::from_slice(&[1; 32]).unwrap();
+        let address = secret_key_to_address(&key);
+        let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let domain = token_domain("USD Coin", "2", 1, usdc);
+
+        let nonce = nonce_from_seed("payment-1");
+        let authorization =
+            AuthorizationBuilder::new(address, Address::repeat_byte(1), U256::from(5))
+                .valid_after(100)
+                .valid_before(200)
+                .nonce(nonce)
+                .transfer();
+        assert!(!authorization.is_valid_at(100));
+        assert!(authorization.is_valid_at(101));
+        assert!(!authorization.is_valid_at(200));
+
+        let hash = authorization.eip712_signing_hash(&domain);
+        let signature = Signature::from(key.sign_prehash_recoverable(hash.as_slice()).unwrap());
+        assert_eq!(signature.recover_address_from_prehash(&hash).unwrap(), address);
+
+        let call = authorization.call(&signature);
+        assert_eq!((call.nonce, call.validBefore), (nonce, U256::from(200)));
+        assert!(call.v == 27 || call.v == 28);
+        let decoded =
+            IERC3009::transferWithAuthorizationCall::abi_decode(&call.abi_encode(), true).unwrap();
+        assert_eq!(decoded.r, B256::from(signature.r()));
+
+        assert_ne!(random_nonce(), random_nonce());
+    }
+}
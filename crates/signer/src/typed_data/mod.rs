@@ -0,0 +1,29 @@
+//! [EIP-712] typed data of common token standards, to be signed with
+//! [`Signer::sign_typed_data`](crate::Signer::sign_typed_data).
+//!
+//! [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+
+use alloy_primitives::{Address, ChainId, U256};
+use alloy_sol_types::Eip712Domain;
+
+pub mod eip3009;
+
+/// Returns the [EIP-712] domain of a token with the given name and version, as defined by
+/// EIP-2612 and EIP-3009 tokens. USD Coin, for instance, uses the name `USD Coin` and the version
+/// `2`.
+///
+/// [EIP-712]: https://eips.ethereum.org/EIPS/eip-712
+pub fn token_domain(
+    name: impl Into<String>,
+    version: impl Into<String>,
+    chain_id: ChainId,
+    verifying_contract: Address,
+) -> Eip712Domain {
+    Eip712Domain::new(
+        Some(name.into().into()),
+        Some(version.into().into()),
+        Some(U256::from(chain_id)),
+        Some(verifying_contract),
+        None,
+    )
+}
@@ -0,0 +1,198 @@
+use alloy_transport::dual_stack::DualStack;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::{
+    collections::HashMap,
+    fmt,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// A DNS resolver rotating among the addresses of a hostname.
+///
+/// Each new connection starts with the address following the one the previous connection started
+/// with, and falls back to the next ones if it cannot connect, so that an unreachable address of
+/// a flaky anycast or DNS setup does not fail every connection. Setting a connect timeout with
+/// [`ConnectionConfig::with_connect_timeout`] bounds the time lost on such an address.
+///
+/// Addresses are resolved by the system by default, or by a custom resolver, and can be pinned
+/// for some hostnames.
+///
+/// The IPv4 and IPv6 addresses of a host can also be ordered or filtered with a [`DualStack`]
+/// configuration, for all hosts or for some of them.
+///
+/// ```
+/// use alloy_transport_http::{ConnectionConfig, Http, RotatingResolver};
+/// use std::time::Duration;
+///
+/// let resolver = RotatingResolver::new().with_static_addrs(
+///     "rpc.example.com",
+///     ["10.0.0.1:443".parse().unwrap(), "10.0.0.2:443".parse().unwrap()],
+/// );
+/// let config = ConnectionConfig::new()
+///     .with_dns_resolver(resolver)
+///     .with_connect_timeout(Duration::from_secs(2));
+/// let http = Http::with_config("https://rpc.example.com".parse().unwrap(), config);
+/// ```
+///
+/// [`ConnectionConfig::with_connect_timeout`]: crate::ConnectionConfig::with_connect_timeout
+#[derive(Clone, Default)]
+pub struct RotatingResolver {
+    inner: Option<Arc<dyn Resolve>>,
+    static_addrs: HashMap<String, Vec<SocketAddr>>,
+    dual_stack: Option<DualStack>,
+    host_dual_stack: HashMap<String, DualStack>,
+    next: Arc<AtomicUsize>,
+}
+
+impl fmt::Debug for RotatingResolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RotatingResolver")
+            .field("custom", &self.inner.is_some())
+            .field("static_addrs", &self.static_addrs)
+            .field("dual_stack", &self.dual_stack)
+            .field("host_dual_stack", &self.host_dual_stack)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RotatingResolver {
+    /// Create a new resolver using the resolver of the system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new resolver rotating among the addresses returned by `resolver`.
+    pub fn with_resolver<R: Resolve + 'static>(resolver: R) -> Self {
+        Self { inner: Some(Arc::new(resolver)), ..Default::default() }
+    }
+
+    /// Pin `host` to the given addresses, instead of resolving it.
+    ///
+    /// If the port of an address is `0`, the port of the URL, or the default port of its scheme,
+    /// is used.
+    pub fn with_static_addrs(
+        mut self,
+        host: &str,
+        addrs: impl IntoIterator<Item = SocketAddr>,
+    ) -> Self {
+        self.static_addrs.insert(host.to_ascii_lowercase(), addrs.into_iter().collect());
+        self
+    }
+
+    /// Order the IPv4 and IPv6 addresses of the hosts according to `config`.
+    ///
+    /// The addresses of the preferred version are returned first. The HTTP client races the
+    /// addresses of the other version against them after 300ms, regardless of the fallback
+    /// delay of `config`; without racing, only the preferred addresses are returned, unless the
+    /// host has none.
+    pub const fn with_dual_stack(mut self, config: DualStack) -> Self {
+        self.dual_stack = Some(config);
+        self
+    }
+
+    /// Order the IPv4 and IPv6 addresses of `host` according to `config`, instead of the
+    /// configuration set with [`with_dual_stack`](Self::with_dual_stack).
+    pub fn with_host_dual_stack(mut self, host: &str, config: DualStack) -> Self {
+        self.host_dual_stack.insert(host.to_ascii_lowercase(), config);
+        self
+    }
+
+    /// Makes the next connection start with the following address, e.g. after a request failed.
+    pub fn rotate(&self) {
+        self.next.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the addresses of `host`, starting with the next address in rotation, and ordered
+    /// by IP version if configured.
+    fn rotated(&self, host: &str, mut addrs: Vec<SocketAddr>) -> Addrs {
+        if !addrs.is_empty() {
+            let start = self.next.fetch_add(1, Ordering::Relaxed) % addrs.len();
+            addrs.rotate_left(start);
+        }
+        if let Some(config) = self.host_dual_stack.get(host).or(self.dual_stack.as_ref()) {
+            let (mut preferred, fallback) = config.split(addrs);
+            if config.racing() || preferred.is_empty() {
+                preferred.extend(fallback);
+            }
+            addrs = preferred;
+        }
+        Box::new(addrs.into_iter())
+    }
+}
+
+impl Resolve for RotatingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let this = self.clone();
+        Box::pin(async move {
+            let host = name.as_str().to_owned();
+            let addrs = if let Some(addrs) = this.static_addrs.get(name.as_str()) {
+                addrs.clone()
+            } else if let Some(inner) = &this.inner {
+                inner.resolve(name).await?.collect()
+            } else {
+                tokio::net::lookup_host((name.as_str(), 0)).await?.collect()
+            };
+            Ok(this.rotated(&host, addrs))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn rotates_addresses() {
+        let addrs: [SocketAddr; 3] = [
+            "10.0.0.1:0".parse().unwrap(),
+            "10.0.0.2:0".parse().unwrap(),
+            "10.0.0.3:0".parse().unwrap(),
+        ];
+        let resolver = RotatingResolver::new().with_static_addrs("RPC.example.com", addrs);
+        let resolve = |resolver: &RotatingResolver| {
+            let resolving = resolver.resolve("rpc.example.com".parse().unwrap());
+            async move { resolving.await.unwrap().collect::<Vec<_>>() }
+        };
+
+        assert_eq!(resolve(&resolver).await, addrs);
+        assert_eq!(resolve(&resolver).await, [addrs[1], addrs[2], addrs[0]]);
+        resolver.rotate();
+        assert_eq!(resolve(&resolver).await, addrs);
+
+        // Other hosts are resolved by the custom resolver.
+        let custom = RotatingResolver::with_resolver(resolver);
+        assert_eq!(resolve(&custom).await, [addrs[1], addrs[2], addrs[0]]);
+    }
+
+    #[tokio::test]
+    async fn orders_ip_versions() {
+        use alloy_transport::dual_stack::IpPreference;
+
+        let v4: SocketAddr = "10.0.0.1:0".parse().unwrap();
+        let v6: SocketAddr = "[2001:db8::1]:0".parse().unwrap();
+        let resolve = |resolver: RotatingResolver, host: &str| {
+            let resolving = resolver.resolve(host.parse().unwrap());
+            async move { resolving.await.unwrap().collect::<Vec<_>>() }
+        };
+        let prefer_v4 = DualStack::new().with_ip_preference(IpPreference::PreferIpv4);
+        let resolver = RotatingResolver::new()
+            .with_static_addrs("a.example.com", [v6, v4])
+            .with_static_addrs("b.example.com", [v6])
+            .with_dual_stack(prefer_v4)
+            .with_host_dual_stack(
+                "c.example.com",
+                DualStack::new().with_ip_preference(IpPreference::Ipv6Only),
+            )
+            .with_static_addrs("c.example.com", [v4, v6]);
+
+        assert_eq!(resolve(resolver.clone(), "a.example.com").await, [v4, v6]);
+        assert_eq!(resolve(resolver.clone(), "c.example.com").await, [v6]);
+        // Without racing, the other version is only used when there are no preferred addresses.
+        let resolver = resolver.with_dual_stack(prefer_v4.with_racing(false));
+        assert_eq!(resolve(resolver.clone(), "a.example.com").await, [v4]);
+        assert_eq!(resolve(resolver, "b.example.com").await, [v6]);
+    }
+}
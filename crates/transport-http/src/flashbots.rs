@@ -0,0 +1,127 @@
+use crate::{
+    reqwest_transport::{send, with_request_headers},
+    Http,
+};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_primitives::{hex, keccak256};
+use alloy_signer::Signer;
+use alloy_transport::{TransportError, TransportErrorKind, TransportFut, TransportResult};
+use reqwest::Client;
+use std::{sync::Arc, task};
+use tower::Service;
+use tracing::{debug_span, Instrument};
+use url::Url;
+
+/// The header carrying the signature of a Flashbots relay request.
+pub const FLASHBOTS_SIGNATURE_HEADER: &str = "X-Flashbots-Signature";
+
+/// An [`Http`] transport using [`reqwest`] that authenticates every request to a Flashbots relay.
+///
+/// Each request carries the [`FLASHBOTS_SIGNATURE_HEADER`] header, set to
+/// `<address>:<signature>`, where the signature is an EIP-191 signature of the hex-encoded
+/// Keccak-256 hash of the request body. The signer only identifies the searcher to the relay for
+/// reputation purposes, and should not hold any funds.
+#[derive(Debug)]
+pub struct FlashbotsHttp<S> {
+    http: Http<Client>,
+    signer: Arc<S>,
+}
+
+impl<S> Clone for FlashbotsHttp<S> {
+    fn clone(&self) -> Self {
+        Self { http: self.http.clone(), signer: self.signer.clone() }
+    }
+}
+
+impl<S> FlashbotsHttp<S> {
+    /// Create a new transport for the given relay URL, signing requests with `signer`.
+    pub fn new(url: Url, signer: S) -> Self {
+        Self::with_http(Http::new(url), signer)
+    }
+
+    /// Create a new transport from an existing [`Http`] transport.
+    pub fn with_http(http: Http<Client>, signer: S) -> Self {
+        Self { http, signer: Arc::new(signer) }
+    }
+
+    /// Get a reference to the underlying [`Http`] transport.
+    pub const fn http(&self) -> &Http<Client> {
+        &self.http
+    }
+
+    /// Get a reference to the signer.
+    pub fn signer(&self) -> &S {
+        &self.signer
+    }
+}
+
+impl<S: Signer + Send + Sync + 'static> FlashbotsHttp<S> {
+    fn request_flashbots(&self, req: RequestPacket) -> TransportFut<'static> {
+        let Self { http, signer } = self.clone();
+        let span: tracing::Span = debug_span!("FlashbotsTransport", url = %http.url);
+        Box::pin(
+            async move {
+                let body = serde_json::to_vec(&req).map_err(TransportError::ser_err)?;
+                let header = flashbots_signature(&*signer, &body).await?;
+                send(
+                    with_request_headers(http.client.post(http.url), &req)
+                        .header(reqwest::header::CONTENT_TYPE, "application/json")
+                        .header(FLASHBOTS_SIGNATURE_HEADER, header)
+                        .body(body),
+                )
+                .await
+            }
+            .instrument(span),
+        )
+    }
+}
+
+/// Compute the value of the [`FLASHBOTS_SIGNATURE_HEADER`] header for the given request body.
+pub async fn flashbots_signature<S: Signer + Sync + ?Sized>(
+    signer: &S,
+    body: &[u8],
+) -> TransportResult<String> {
+    let message = hex::encode_prefixed(keccak256(body));
+    let signature =
+        signer.sign_message(message.as_bytes()).await.map_err(TransportErrorKind::custom)?;
+    Ok(format!("{}:{}", signer.address(), hex::encode_prefixed(signature.as_bytes())))
+}
+
+impl<S: Signer + Send + Sync + 'static> Service<RequestPacket> for FlashbotsHttp<S> {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    #[inline]
+    fn poll_ready(&mut self, _cx: &mut task::Context<'_>) -> task::Poll<Result<(), Self::Error>> {
+        // reqwest always returns ok
+        task::Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        self.request_flashbots(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_signer_wallet::LocalWallet;
+
+    #[tokio::test]
+    async fn signature_header() {
+        let wallet: LocalWallet =
+            "0000000000000000000000000000000000000000000000000000000000000001".parse().unwrap();
+        let body = br#"{"jsonrpc":"2.0","id":1,"method":"eth_sendBundle","params":[]}"#;
+        let header = flashbots_signature(&wallet, body).await.unwrap();
+
+        let (address, signature) = header.split_once(':').unwrap();
+        assert_eq!(address, wallet.address().to_string());
+        let signature =
+            alloy_primitives::Signature::try_from(hex::decode(signature).unwrap().as_slice())
+                .unwrap();
+        let message = hex::encode_prefixed(keccak256(body));
+        assert_eq!(signature.recover_address_from_msg(message).unwrap(), wallet.address());
+    }
+}
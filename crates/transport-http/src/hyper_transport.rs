@@ -32,16 +32,20 @@ where
         Box::pin(
             async move {
                 debug!(count = req.len(), "sending request packet to server");
+                let headers = crate::request_headers(&req).collect::<Vec<_>>();
                 let ser = req.serialize().map_err(TransportError::ser_err)?;
                 // convert the Box<RawValue> into a hyper request<B>
                 let body = Full::from(Bytes::from(<Box<[u8]>>::from(<Box<str>>::from(ser))));
-                let req = hyper::Request::builder()
+                let builder = hyper::Request::builder()
                     .method(hyper::Method::POST)
                     .uri(this.url.as_str())
                     .header(
                         header::CONTENT_TYPE,
                         header::HeaderValue::from_static("application/json"),
-                    )
+                    );
+                let req = headers
+                    .into_iter()
+                    .fold(builder, |builder, (name, value)| builder.header(name, value))
                     .body(body)
                     .expect("request parts are valid");
 
@@ -64,10 +68,10 @@ where
                 trace!(body = %String::from_utf8_lossy(&body), "response body");
 
                 if status != hyper::StatusCode::OK {
-                    return Err(TransportErrorKind::custom_str(&format!(
-                        "HTTP error {status} with body: {}",
-                        String::from_utf8_lossy(&body)
-                    )));
+                    return Err(TransportErrorKind::http_error(
+                        status.as_u16(),
+                        String::from_utf8_lossy(&body).into_owned(),
+                    ));
                 }
 
                 // Deser a Box<RawValue> from the body. If deser fails, return
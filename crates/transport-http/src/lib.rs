@@ -24,6 +24,20 @@ pub use reqwest_transport::*;
 #[cfg(feature = "reqwest")]
 pub use reqwest;
 
+#[cfg(all(not(target_arch = "wasm32"), feature = "reqwest"))]
+mod dns;
+#[cfg(all(not(target_arch = "wasm32"), feature = "reqwest"))]
+pub use dns::RotatingResolver;
+
+// Only enables the `js` feature, to source randomness from the browser.
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+use getrandom as _;
+
+#[cfg(feature = "flashbots")]
+mod flashbots;
+#[cfg(feature = "flashbots")]
+pub use flashbots::{flashbots_signature, FlashbotsHttp, FLASHBOTS_SIGNATURE_HEADER};
+
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
 mod hyper_transport;
 #[cfg(all(not(target_arch = "wasm32"), feature = "hyper"))]
@@ -38,6 +52,11 @@ pub use hyper_util;
 use alloy_transport::utils::guess_local_url;
 use url::Url;
 
+#[cfg(all(not(feature = "reqwest"), feature = "hyper"))]
+use hyper::header::HeaderValue;
+#[cfg(feature = "reqwest")]
+use reqwest::header::HeaderValue;
+
 /// An Http transport.
 ///
 /// The user must provide an internal http client and a URL to which to
@@ -90,3 +109,31 @@ impl<T> Http<T> {
         self.url.as_ref()
     }
 }
+
+/// Returns the headers to send along with the request packet: the W3C trace context and the
+/// `Authorization` header set on the requests, if any. The `Authorization` header is marked as
+/// sensitive.
+///
+/// Header values that are not printable ASCII are skipped.
+#[cfg(any(feature = "reqwest", feature = "hyper"))]
+fn request_headers(
+    req: &alloy_json_rpc::RequestPacket,
+) -> impl Iterator<Item = (&'static str, HeaderValue)> {
+    let context = req.trace_context();
+    let traceparent =
+        context.map(|context| (alloy_json_rpc::TRACEPARENT_HEADER, context.to_string(), false));
+    let tracestate = context
+        .and_then(|context| context.state.clone())
+        .map(|state| (alloy_json_rpc::TRACESTATE_HEADER, state, false));
+    let authorization = req.authorization().map(|auth| ("authorization", auth.to_string(), true));
+    traceparent.into_iter().chain(tracestate).chain(authorization).filter_map(
+        |(name, value, sensitive)| {
+            if !value.bytes().all(|b| (0x20..0x7f).contains(&b)) {
+                return None;
+            }
+            let mut value = HeaderValue::from_str(&value).ok()?;
+            value.set_sensitive(sensitive);
+            Some((name, value))
+        },
+    )
+}
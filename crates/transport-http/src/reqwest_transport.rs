@@ -1,7 +1,10 @@
 use crate::Http;
 use alloy_json_rpc::{RequestPacket, ResponsePacket};
-use alloy_transport::{TransportError, TransportErrorKind, TransportFut};
-use std::task;
+use alloy_transport::{
+    Authorization, TransportError, TransportErrorKind, TransportFut, TransportResult,
+};
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use std::{task, time::Duration};
 use tower::Service;
 use tracing::{debug, debug_span, trace, Instrument};
 use url::Url;
@@ -12,53 +15,260 @@ pub use reqwest::Client;
 /// An [`Http`] transport using [`reqwest`].
 pub type ReqwestTransport = Http<Client>;
 
+/// Connection settings of the [`reqwest`] client of an [`Http`] transport.
+///
+/// Settings left unset keep the defaults of [`reqwest`]. High-throughput applications usually
+/// benefit from keeping more idle connections alive, to avoid setting up a new connection for
+/// each concurrent request. The connection pool and TCP settings are ignored on wasm, where the
+/// browser manages the connections.
+///
+/// ```
+/// use alloy_transport_http::{ConnectionConfig, Http};
+/// use std::time::Duration;
+///
+/// let config = ConnectionConfig::new()
+///     .with_pool_max_idle_per_host(64)
+///     .with_pool_idle_timeout(Duration::from_secs(300))
+///     .with_tcp_nodelay(true);
+/// let http = Http::with_config("http://localhost:8545".parse().unwrap(), config);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionConfig {
+    headers: HeaderMap,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+    tcp_nodelay: Option<bool>,
+    connect_timeout: Option<Duration>,
+    #[cfg(not(target_arch = "wasm32"))]
+    dns_resolver: Option<crate::RotatingResolver>,
+    #[cfg(feature = "reqwest-http2")]
+    http2_adaptive_window: Option<bool>,
+    #[cfg(feature = "reqwest-http2")]
+    http2_prior_knowledge: bool,
+}
+
+impl ConnectionConfig {
+    /// Create a new configuration, keeping the defaults of [`reqwest`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send `headers` with every request, e.g. API keys or a `User-Agent`.
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Set how long idle connections are kept alive in the pool before being closed.
+    pub const fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the maximum number of idle connections kept alive for each host.
+    pub const fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the interval of the TCP keep-alive probes.
+    pub const fn with_tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Set whether to disable Nagle's algorithm, sending small requests without delay.
+    pub const fn with_tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.tcp_nodelay = Some(nodelay);
+        self
+    }
+
+    /// Set the time after which connecting to an address is abandoned, in favor of the next
+    /// address of the host, if any.
+    pub const fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Resolve hostnames with `resolver`, e.g. to pin them to static addresses, or to rotate
+    /// among their addresses.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_dns_resolver(mut self, resolver: crate::RotatingResolver) -> Self {
+        self.dns_resolver = Some(resolver);
+        self
+    }
+
+    /// Order the IPv4 and IPv6 addresses of the hosts according to `config`, e.g. to avoid a
+    /// degraded IPv6 network. See [`RotatingResolver::with_dual_stack`].
+    ///
+    /// This sets the configuration of the resolver set with
+    /// [`with_dns_resolver`](Self::with_dns_resolver), or of a new [`RotatingResolver`].
+    ///
+    /// [`RotatingResolver`]: crate::RotatingResolver
+    /// [`RotatingResolver::with_dual_stack`]: crate::RotatingResolver::with_dual_stack
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_dual_stack(mut self, config: alloy_transport::dual_stack::DualStack) -> Self {
+        let resolver = self.dns_resolver.take().unwrap_or_default();
+        self.dns_resolver = Some(resolver.with_dual_stack(config));
+        self
+    }
+
+    /// Set whether to use the adaptive flow control of HTTP/2, which lets a single connection
+    /// carry many concurrent requests.
+    #[cfg(feature = "reqwest-http2")]
+    pub const fn with_http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = Some(enabled);
+        self
+    }
+
+    /// Only use HTTP/2, multiplexing the requests over a single connection, without negotiating
+    /// it first. The server must support HTTP/2.
+    #[cfg(feature = "reqwest-http2")]
+    pub const fn with_http2_prior_knowledge(mut self) -> Self {
+        self.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Apply the settings to a client builder, e.g. one also configured with a proxy.
+    pub fn apply(self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        let builder = builder.default_headers(self.headers);
+        #[cfg(not(target_arch = "wasm32"))]
+        let builder = {
+            let mut builder = builder;
+            if let Some(interval) = self.tcp_keepalive {
+                builder = builder.tcp_keepalive(interval);
+            }
+            if let Some(timeout) = self.pool_idle_timeout {
+                builder = builder.pool_idle_timeout(timeout);
+            }
+            if let Some(max) = self.pool_max_idle_per_host {
+                builder = builder.pool_max_idle_per_host(max);
+            }
+            if let Some(nodelay) = self.tcp_nodelay {
+                builder = builder.tcp_nodelay(nodelay);
+            }
+            if let Some(timeout) = self.connect_timeout {
+                builder = builder.connect_timeout(timeout);
+            }
+            if let Some(resolver) = self.dns_resolver {
+                builder = builder.dns_resolver(std::sync::Arc::new(resolver));
+            }
+            #[cfg(feature = "reqwest-http2")]
+            if let Some(enabled) = self.http2_adaptive_window {
+                builder = builder.http2_adaptive_window(enabled);
+            }
+            #[cfg(feature = "reqwest-http2")]
+            if self.http2_prior_knowledge {
+                builder = builder.http2_prior_knowledge();
+            }
+            builder
+        };
+        builder
+    }
+
+    /// Build a client with the settings.
+    pub fn build_client(self) -> reqwest::Result<Client> {
+        self.apply(Client::builder()).build()
+    }
+}
+
 impl Http<Client> {
-    /// Create a new [`Http`] transport.
+    /// Create a new [`Http`] transport, with a client of its own.
+    ///
+    /// To share a connection pool between several transports, create them with clones of the same
+    /// [`Client`] with [`Http::with_client`]:
+    ///
+    /// ```
+    /// use alloy_transport_http::{Client, Http};
+    ///
+    /// let client = Client::new();
+    /// let mainnet = Http::with_client(client.clone(), "https://eth.llamarpc.com".parse().unwrap());
+    /// let sepolia = Http::with_client(client, "https://rpc.sepolia.org".parse().unwrap());
+    /// ```
     pub fn new(url: Url) -> Self {
         Self { client: Default::default(), url }
     }
 
+    /// Create a new [`Http`] transport with a client using the given connection settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client cannot be built, like [`Client::new`].
+    pub fn with_config(url: Url, config: ConnectionConfig) -> Self {
+        let client = config.build_client().expect("failed to build the reqwest client");
+        Self { client, url }
+    }
+
+    /// Create a new [`Http`] transport sending `headers` with every request, e.g. API keys, a
+    /// `User-Agent` or a `Cookie`.
+    ///
+    /// To configure the client further, e.g. with a proxy or custom TLS settings, build it with
+    /// [`Client::builder`] and use [`Http::with_client`] instead.
+    ///
+    /// Returns an error if the client cannot be built.
+    pub fn with_headers(url: Url, headers: HeaderMap) -> TransportResult<Self> {
+        let client = ConnectionConfig::new()
+            .with_headers(headers)
+            .build_client()
+            .map_err(TransportErrorKind::custom)?;
+        Ok(Self { client, url })
+    }
+
+    /// Create a new [`Http`] transport authenticating every request with `auth`, in the
+    /// `Authorization` header.
+    ///
+    /// Returns an error if `auth` is not a valid header value, or if the client cannot be built.
+    pub fn with_auth(url: Url, auth: Authorization) -> TransportResult<Self> {
+        let mut value =
+            HeaderValue::from_str(&auth.header_value()).map_err(TransportErrorKind::custom)?;
+        value.set_sensitive(true);
+        Self::with_headers(url, HeaderMap::from_iter([(AUTHORIZATION, value)]))
+    }
+
     /// Make a request.
     fn request_reqwest(&self, req: RequestPacket) -> TransportFut<'static> {
-        let this = self.clone();
         let span: tracing::Span = debug_span!("ReqwestTransport", url = %self.url);
-        Box::pin(
-            async move {
-                let resp = this
-                    .client
-                    .post(this.url)
-                    .json(&req)
-                    .send()
-                    .await
-                    .map_err(TransportErrorKind::custom)?;
-                let status = resp.status();
-
-                debug!(%status, "received response from server");
-
-                // Unpack data from the response body. We do this regardless of
-                // the status code, as we want to return the error in the body
-                // if there is one.
-                let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
-
-                debug!(bytes = body.len(), "retrieved response body. Use `trace` for full body");
-                trace!(body = %String::from_utf8_lossy(&body), "response body");
-
-                if status != reqwest::StatusCode::OK {
-                    return Err(TransportErrorKind::custom_str(&format!(
-                        "HTTP error {status} with body: {}",
-                        String::from_utf8_lossy(&body)
-                    )));
-                }
-
-                // Deser a Box<RawValue> from the body. If deser fails, return
-                // the body as a string in the error. The conversion to String
-                // is lossy and may not cover all the bytes in the body.
-                serde_json::from_slice(&body)
-                    .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&body)))
-            }
-            .instrument(span),
-        )
+        let builder = with_request_headers(self.client.post(self.url.clone()), &req).json(&req);
+        Box::pin(send(builder).instrument(span))
+    }
+}
+
+/// Add the headers of the request packet, e.g. its trace context, if any.
+pub(crate) fn with_request_headers(
+    builder: reqwest::RequestBuilder,
+    req: &RequestPacket,
+) -> reqwest::RequestBuilder {
+    crate::request_headers(req).fold(builder, |builder, (name, value)| builder.header(name, value))
+}
+
+/// Send a request and deserialize the response packet.
+pub(crate) async fn send(req: reqwest::RequestBuilder) -> TransportResult<ResponsePacket> {
+    let resp = req.send().await.map_err(TransportErrorKind::custom)?;
+    let status = resp.status();
+
+    debug!(%status, "received response from server");
+
+    // Unpack data from the response body. We do this regardless of
+    // the status code, as we want to return the error in the body
+    // if there is one.
+    let body = resp.bytes().await.map_err(TransportErrorKind::custom)?;
+
+    debug!(bytes = body.len(), "retrieved response body. Use `trace` for full body");
+    trace!(body = %String::from_utf8_lossy(&body), "response body");
+
+    if status != reqwest::StatusCode::OK {
+        return Err(TransportErrorKind::http_error(
+            status.as_u16(),
+            String::from_utf8_lossy(&body).into_owned(),
+        ));
     }
+
+    // Deser a Box<RawValue> from the body. If deser fails, return
+    // the body as a string in the error. The conversion to String
+    // is lossy and may not cover all the bytes in the body.
+    serde_json::from_slice(&body)
+        .map_err(|err| TransportError::deser_err(err, String::from_utf8_lossy(&body)))
 }
 
 impl Service<RequestPacket> for Http<reqwest::Client> {
@@ -94,3 +304,128 @@ impl Service<RequestPacket> for &Http<reqwest::Client> {
         self.request_reqwest(req)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request};
+    use reqwest::header::{COOKIE, USER_AGENT};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Serves a single request, returning its headers.
+    async fn serve_once(listener: TcpListener) -> HeaderMap {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        while !request.ends_with(b"\r\n\r\n") {
+            request.push(stream.read_u8().await.unwrap());
+        }
+        let body = r#"{"jsonrpc":"2.0","id":0,"result":"0x1"}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{body}",
+            body.len()
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        let request = String::from_utf8(request).unwrap();
+        request
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_once(": "))
+            .map(|(name, value)| (name.parse().unwrap(), value.parse().unwrap()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn sends_headers() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        let server = tokio::spawn(serve_once(listener));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_static("secret"));
+        headers.insert(USER_AGENT, HeaderValue::from_static("indexer/1.0"));
+        headers.insert(COOKIE, HeaderValue::from_static("session=abc"));
+        let mut http = Http::with_headers(url, headers).unwrap();
+
+        let req = Request::new("eth_blockNumber", Id::Number(0), ()).serialize().unwrap();
+        http.call(RequestPacket::Single(req)).await.unwrap();
+
+        let headers = server.await.unwrap();
+        assert_eq!(headers["x-api-key"], "secret");
+        assert_eq!(headers[USER_AGENT], "indexer/1.0");
+        assert_eq!(headers[COOKIE], "session=abc");
+    }
+
+    #[tokio::test]
+    async fn sends_with_config() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        let server = tokio::spawn(serve_once(listener));
+
+        let config = ConnectionConfig::new()
+            .with_headers(HeaderMap::from_iter([(USER_AGENT, HeaderValue::from_static("a/1"))]))
+            .with_pool_max_idle_per_host(64)
+            .with_pool_idle_timeout(Duration::from_secs(300))
+            .with_tcp_keepalive(Duration::from_secs(30))
+            .with_tcp_nodelay(true);
+        let mut http = Http::with_config(url, config);
+        let req = Request::new("eth_blockNumber", Id::Number(0), ()).serialize().unwrap();
+        http.call(RequestPacket::Single(req)).await.unwrap();
+
+        assert_eq!(server.await.unwrap()[USER_AGENT], "a/1");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_address() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_once(listener));
+
+        // Nothing listens on the first address. The port of the URL overrides the resolved ones.
+        let dead = "127.0.0.2:0".parse().unwrap();
+        let resolver =
+            crate::RotatingResolver::new().with_static_addrs("rpc.example.com", [dead, live]);
+        let config = ConnectionConfig::new()
+            .with_dns_resolver(resolver)
+            .with_connect_timeout(Duration::from_secs(1));
+        let url = format!("http://rpc.example.com:{}", live.port()).parse().unwrap();
+        let mut http = Http::with_config(url, config);
+        let req = Request::new("eth_blockNumber", Id::Number(0), ()).serialize().unwrap();
+        http.call(RequestPacket::Single(req)).await.unwrap();
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn sends_auth() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap()).parse().unwrap();
+        let server = tokio::spawn(serve_once(listener));
+
+        let mut http = Http::with_auth(url, Authorization::basic("alice", "pass")).unwrap();
+        let req = Request::new("eth_blockNumber", Id::Number(0), ()).serialize().unwrap();
+        http.call(RequestPacket::Single(req)).await.unwrap();
+
+        assert_eq!(server.await.unwrap()[AUTHORIZATION], "Basic YWxpY2U6cGFzcw==");
+    }
+
+    #[test]
+    fn request_auth_is_sensitive() {
+        let mut req =
+            Request::new("engine_exchangeCapabilities", Id::Number(0), ()).serialize().unwrap();
+        req.set_authorization(Some("Bearer token".into()));
+        assert!(!format!("{req:?}").contains("token"));
+        let headers: Vec<_> = crate::request_headers(&RequestPacket::Single(req)).collect();
+        let (name, value) = &headers[0];
+        assert_eq!((*name, value.to_str().unwrap()), ("authorization", "Bearer token"));
+        assert!(value.is_sensitive());
+        assert_eq!(format!("{value:?}"), "Sensitive");
+    }
+
+    #[test]
+    fn rejects_invalid_auth() {
+        let url = "http://localhost:8545".parse().unwrap();
+        assert!(Http::with_auth(url, Authorization::bearer("bad\ntoken")).is_err());
+    }
+}
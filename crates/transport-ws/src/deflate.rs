@@ -0,0 +1,678 @@
+//! `permessage-deflate` compression, as specified in [RFC 7692].
+//!
+//! `tungstenite` does not support WebSocket extensions, and rejects frames with reserved bits set.
+//! [`DeflateStream`] sits between the network stream and `tungstenite`: it passes the handshake
+//! through, reads the negotiated parameters from the handshake response, and from then on
+//! decompresses incoming messages and compresses outgoing ones, so that `tungstenite` only ever
+//! sees uncompressed frames.
+//!
+//! [RFC 7692]: https://datatracker.ietf.org/doc/html/rfc7692
+
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+use std::{
+    io::{self, Cursor},
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_tungstenite::tungstenite::protocol::frame::{
+    coding::{Data, OpCode},
+    FrameHeader,
+};
+
+/// The name of the extension, as negotiated in the `Sec-WebSocket-Extensions` header.
+const EXTENSION: &str = "permessage-deflate";
+
+/// The trailer that a sync flush appends to a deflate stream, which is stripped from the payload.
+const TRAILER: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// The largest message accepted, matching `tungstenite`'s default frame size limit, as messages
+/// are passed on to it in a single frame.
+const MAX_MESSAGE_SIZE: usize = 16 << 20;
+
+/// The size of the write buffer above which writes wait for it to be flushed.
+const MAX_WRITE_BUFFER: usize = 1 << 20;
+
+/// Configuration of `permessage-deflate` compression.
+///
+/// Window sizes are given as the base-2 logarithm of the LZ77 sliding window size, between 9 and
+/// 15. Smaller windows use less memory on both ends, at the cost of a worse compression ratio.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeflateConfig {
+    /// The window size used to compress outgoing messages. The server may ask for a smaller one.
+    client_max_window_bits: u8,
+    /// The window size the server is asked to use for incoming messages.
+    server_max_window_bits: u8,
+    /// Whether to reset the compression context after each outgoing message.
+    client_no_context_takeover: bool,
+    /// Whether to ask the server to reset its compression context after each message.
+    server_no_context_takeover: bool,
+    /// The compression level of outgoing messages.
+    level: u32,
+}
+
+impl Default for DeflateConfig {
+    fn default() -> Self {
+        Self {
+            client_max_window_bits: 15,
+            server_max_window_bits: 15,
+            client_no_context_takeover: false,
+            server_no_context_takeover: false,
+            level: Compression::fast().level(),
+        }
+    }
+}
+
+impl DeflateConfig {
+    /// Sets the window size used to compress outgoing messages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not between 9 and 15.
+    pub fn client_max_window_bits(mut self, bits: u8) -> Self {
+        assert!((9..=15).contains(&bits), "window bits must be between 9 and 15");
+        self.client_max_window_bits = bits;
+        self
+    }
+
+    /// Sets the window size the server is asked to use.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not between 9 and 15.
+    pub fn server_max_window_bits(mut self, bits: u8) -> Self {
+        assert!((9..=15).contains(&bits), "window bits must be between 9 and 15");
+        self.server_max_window_bits = bits;
+        self
+    }
+
+    /// Resets the compression context after each outgoing message.
+    pub const fn client_no_context_takeover(mut self) -> Self {
+        self.client_no_context_takeover = true;
+        self
+    }
+
+    /// Asks the server to reset its compression context after each message.
+    pub const fn server_no_context_takeover(mut self) -> Self {
+        self.server_no_context_takeover = true;
+        self
+    }
+
+    /// Sets the compression level of outgoing messages, from 0 to 9.
+    pub fn level(mut self, level: u32) -> Self {
+        self.level = level.min(9);
+        self
+    }
+
+    /// Returns the extension offer, the value of the `Sec-WebSocket-Extensions` request header.
+    pub fn offer(&self) -> String {
+        let mut offer = EXTENSION.to_string();
+        if self.client_max_window_bits < 15 {
+            offer.push_str(&format!("; client_max_window_bits={}", self.client_max_window_bits));
+        } else {
+            offer.push_str("; client_max_window_bits");
+        }
+        if self.server_max_window_bits < 15 {
+            offer.push_str(&format!("; server_max_window_bits={}", self.server_max_window_bits));
+        }
+        if self.client_no_context_takeover {
+            offer.push_str("; client_no_context_takeover");
+        }
+        if self.server_no_context_takeover {
+            offer.push_str("; server_no_context_takeover");
+        }
+        offer
+    }
+
+    /// Parses the `Sec-WebSocket-Extensions` response header, returning the negotiated
+    /// parameters, or `None` if the server declined compression.
+    ///
+    /// Parameters that can't be honored are an error, failing the connection as required by the
+    /// RFC.
+    fn negotiate(&self, response: Option<&str>) -> io::Result<Option<Negotiated>> {
+        let Some(response) = response.map(str::trim).filter(|response| !response.is_empty()) else {
+            return Ok(None);
+        };
+        if response.contains(',') {
+            return Err(invalid("more than one extension accepted"));
+        }
+
+        let mut params = response.split(';').map(str::trim);
+        if params.next() != Some(EXTENSION) {
+            return Err(invalid(format!("unexpected extension: {response}")));
+        }
+        let mut negotiated = Negotiated {
+            client_window_bits: self.client_max_window_bits,
+            client_no_context_takeover: self.client_no_context_takeover,
+            server_no_context_takeover: self.server_no_context_takeover,
+        };
+        for param in params {
+            let (name, value) = match param.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (param, None),
+            };
+            let bits = || {
+                value
+                    .and_then(|value| value.parse::<u8>().ok())
+                    .filter(|bits| (8..=15).contains(bits))
+                    .ok_or_else(|| invalid(format!("invalid extension parameter: {param}")))
+            };
+            match name {
+                "client_max_window_bits" => {
+                    // zlib does not support 8-bit windows for raw deflate streams, and the server
+                    // may not be able to decompress messages using a larger window.
+                    let bits = bits()?;
+                    if bits < 9 {
+                        return Err(invalid("8-bit client windows are not supported"));
+                    }
+                    negotiated.client_window_bits = bits.min(self.client_max_window_bits)
+                }
+                "server_max_window_bits" => {
+                    bits()?;
+                }
+                "client_no_context_takeover" => negotiated.client_no_context_takeover = true,
+                "server_no_context_takeover" => negotiated.server_no_context_takeover = true,
+                _ => return Err(invalid(format!("unknown extension parameter: {param}"))),
+            }
+        }
+        Ok(Some(negotiated))
+    }
+}
+
+/// The parameters agreed on with the server.
+#[derive(Clone, Copy, Debug)]
+struct Negotiated {
+    client_window_bits: u8,
+    client_no_context_takeover: bool,
+    server_no_context_takeover: bool,
+}
+
+/// The compression state of a connection.
+struct Codec {
+    negotiated: Negotiated,
+    compress: Compress,
+    decompress: Decompress,
+}
+
+impl std::fmt::Debug for Codec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Codec").field("negotiated", &self.negotiated).finish_non_exhaustive()
+    }
+}
+
+impl Codec {
+    fn new(negotiated: Negotiated, level: u32) -> Self {
+        Self {
+            negotiated,
+            compress: Compress::new_with_window_bits(
+                Compression::new(level),
+                false,
+                negotiated.client_window_bits,
+            ),
+            // A full window can decompress streams compressed with any window size.
+            decompress: Decompress::new_with_window_bits(false, 15),
+        }
+    }
+
+    fn compress(&mut self, data: &[u8]) -> io::Result<Vec<u8>> {
+        let mut out = Vec::with_capacity(data.len() / 2 + 64);
+        let start = self.compress.total_in();
+        loop {
+            let consumed = (self.compress.total_in() - start) as usize;
+            out.reserve(64);
+            self.compress
+                .compress_vec(&data[consumed..], &mut out, FlushCompress::Sync)
+                .map_err(io::Error::other)?;
+            // The flush is complete once all input is consumed without filling the output.
+            if (self.compress.total_in() - start) as usize == data.len()
+                && out.len() < out.capacity()
+            {
+                break;
+            }
+        }
+        if out.ends_with(&TRAILER) {
+            out.truncate(out.len() - TRAILER.len());
+        }
+        if self.negotiated.client_no_context_takeover {
+            self.compress.reset();
+        }
+        Ok(out)
+    }
+
+    fn decompress(&mut self, mut data: Vec<u8>) -> io::Result<Vec<u8>> {
+        data.extend_from_slice(&TRAILER);
+        let mut out = Vec::with_capacity(data.len() * 4);
+        let start = self.decompress.total_in();
+        loop {
+            let consumed = (self.decompress.total_in() - start) as usize;
+            out.reserve(out.len().max(1024));
+            let status = self
+                .decompress
+                .decompress_vec(&data[consumed..], &mut out, FlushDecompress::Sync)
+                .map_err(io::Error::other)?;
+            if out.len() > MAX_MESSAGE_SIZE {
+                return Err(invalid("decompressed message too large"));
+            }
+            let done = (self.decompress.total_in() - start) as usize == data.len();
+            if status == Status::StreamEnd || (done && out.len() < out.capacity()) {
+                break;
+            }
+        }
+        if self.negotiated.server_no_context_takeover {
+            self.decompress.reset(false);
+        }
+        Ok(out)
+    }
+}
+
+/// A stream compressing and decompressing WebSocket messages with `permessage-deflate`.
+///
+/// The stream must be created before the handshake. If the server declines compression, frames
+/// are passed through unchanged.
+#[derive(Debug)]
+pub(crate) struct DeflateStream<S> {
+    inner: S,
+    config: DeflateConfig,
+    /// The handshake response read so far, until it is complete.
+    handshake: Option<Vec<u8>>,
+    codec: Option<Codec>,
+    /// Bytes read from the inner stream, not yet processed.
+    read_buf: Vec<u8>,
+    /// Processed bytes, ready to be read.
+    ready: Vec<u8>,
+    /// The header and payload so far of an incoming compressed message.
+    incoming: Option<(FrameHeader, Vec<u8>)>,
+    /// Bytes written to the stream, not yet processed.
+    write_buf: Vec<u8>,
+    /// Processed bytes, waiting to be written to the inner stream.
+    outgoing: Vec<u8>,
+}
+
+impl<S> DeflateStream<S> {
+    /// Wraps a stream, before the WebSocket handshake.
+    pub(crate) const fn new(inner: S, config: DeflateConfig) -> Self {
+        Self {
+            inner,
+            config,
+            handshake: Some(Vec::new()),
+            codec: None,
+            read_buf: Vec::new(),
+            ready: Vec::new(),
+            incoming: None,
+            write_buf: Vec::new(),
+            outgoing: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the server accepted compression.
+    pub(crate) const fn is_negotiated(&self) -> bool {
+        self.codec.is_some()
+    }
+
+    /// Processes the bytes read from the inner stream.
+    fn process_read(&mut self) -> io::Result<()> {
+        if let Some(handshake) = &mut self.handshake {
+            let start = handshake.len().saturating_sub(3);
+            handshake.extend_from_slice(&self.read_buf);
+            let Some(end) = handshake[start..].windows(4).position(|w| w == b"\r\n\r\n") else {
+                self.ready.append(&mut self.read_buf);
+                return Ok(());
+            };
+            // Pass the rest of the response through, and keep any frames that follow it.
+            let end = start + end + 4;
+            let rest = self.read_buf.split_off(self.read_buf.len() - (handshake.len() - end));
+            self.ready.append(&mut self.read_buf);
+            self.read_buf = rest;
+
+            let response = String::from_utf8_lossy(&handshake[..end]);
+            let extensions = response.lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.trim().eq_ignore_ascii_case("sec-websocket-extensions").then_some(value)
+            });
+            let negotiated = self.config.negotiate(extensions)?;
+            debug!(?negotiated, "negotiated websocket compression");
+            self.codec = negotiated.map(|negotiated| Codec::new(negotiated, self.config.level));
+            self.handshake = None;
+        }
+
+        let Some(codec) = &mut self.codec else {
+            self.ready.append(&mut self.read_buf);
+            return Ok(());
+        };
+        while let Some((header, start, end)) = next_frame(&self.read_buf)? {
+            let frame: Vec<u8> = self.read_buf.drain(..end).collect();
+            let mut payload = frame[start..].to_vec();
+            if let Some(mask) = header.mask {
+                apply_mask(&mut payload, mask);
+            }
+
+            match (header.opcode, &mut self.incoming) {
+                (OpCode::Data(Data::Text | Data::Binary), incoming) if header.rsv1 => {
+                    if incoming.is_some() {
+                        return Err(invalid("new message before the previous one ended"));
+                    }
+                    *incoming = Some((header, payload));
+                }
+                (OpCode::Data(Data::Continue), Some((first, data))) => {
+                    data.extend_from_slice(&payload);
+                    first.is_final = header.is_final;
+                    if data.len() > MAX_MESSAGE_SIZE {
+                        return Err(invalid("compressed message too large"));
+                    }
+                }
+                // Control frames and uncompressed messages are passed through.
+                _ => {
+                    self.ready.extend_from_slice(&frame);
+                    continue;
+                }
+            }
+
+            if self.incoming.as_ref().is_some_and(|(header, _)| header.is_final) {
+                let (mut header, payload) = self.incoming.take().expect("checked");
+                let payload = codec.decompress(payload)?;
+                header.rsv1 = false;
+                header.mask = None;
+                header.format(payload.len() as u64, &mut self.ready).map_err(io::Error::other)?;
+                self.ready.extend_from_slice(&payload);
+            }
+        }
+        Ok(())
+    }
+
+    /// Processes the bytes written to the stream.
+    fn process_write(&mut self) -> io::Result<()> {
+        let Some(codec) = &mut self.codec else {
+            self.outgoing.append(&mut self.write_buf);
+            return Ok(());
+        };
+        while let Some((mut header, start, end)) = next_frame(&self.write_buf)? {
+            // Only whole messages are compressed. Fragmented messages and control frames are
+            // passed through.
+            let whole = matches!(header.opcode, OpCode::Data(Data::Text | Data::Binary));
+            if !whole || !header.is_final {
+                self.outgoing.extend(self.write_buf.drain(..end));
+                continue;
+            }
+
+            let mut payload = self.write_buf[start..end].to_vec();
+            self.write_buf.drain(..end);
+            if let Some(mask) = header.mask {
+                apply_mask(&mut payload, mask);
+            }
+            let mut payload = codec.compress(&payload)?;
+            if let Some(mask) = header.mask {
+                apply_mask(&mut payload, mask);
+            }
+            header.rsv1 = true;
+            header.format(payload.len() as u64, &mut self.outgoing).map_err(io::Error::other)?;
+            self.outgoing.extend_from_slice(&payload);
+        }
+        Ok(())
+    }
+}
+
+impl<S: AsyncWrite + Unpin> DeflateStream<S> {
+    /// Writes the processed bytes to the inner stream.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.outgoing.is_empty() {
+            let n = ready!(Pin::new(&mut self.inner).poll_write(cx, &self.outgoing))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            self.outgoing.drain(..n);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for DeflateStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            if !this.ready.is_empty() {
+                let n = this.ready.len().min(buf.remaining());
+                buf.put_slice(&this.ready[..n]);
+                this.ready.drain(..n);
+                return Poll::Ready(Ok(()));
+            }
+
+            let mut chunk = [0u8; 8192];
+            let mut chunk = ReadBuf::new(&mut chunk);
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut chunk))?;
+            if chunk.filled().is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+            this.read_buf.extend_from_slice(chunk.filled());
+            this.process_read()?;
+        }
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for DeflateStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.outgoing.len() > MAX_WRITE_BUFFER {
+            ready!(this.poll_drain(cx))?;
+        }
+        this.write_buf.extend_from_slice(buf);
+        this.process_write()?;
+        // Whatever can't be written now is written on flush.
+        if let Poll::Ready(Err(err)) = this.poll_drain(cx) {
+            return Poll::Ready(Err(err));
+        }
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+/// Parses the frame at the start of `buf`, returning its header and the range of its payload, or
+/// `None` if the frame is incomplete.
+fn next_frame(buf: &[u8]) -> io::Result<Option<(FrameHeader, usize, usize)>> {
+    let mut cursor = Cursor::new(buf);
+    let Some((header, len)) = FrameHeader::parse(&mut cursor).map_err(io::Error::other)? else {
+        return Ok(None);
+    };
+    let start = cursor.position() as usize;
+    let end = usize::try_from(len)
+        .ok()
+        .and_then(|len| start.checked_add(len))
+        .ok_or_else(|| invalid("frame too large"))?;
+    Ok((end <= buf.len()).then_some((header, start, end)))
+}
+
+fn apply_mask(payload: &mut [u8], mask: [u8; 4]) {
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+}
+
+fn invalid(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::{SinkExt, StreamExt};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_tungstenite::tungstenite::{
+        client::IntoClientRequest, handshake::derive_accept_key, protocol::frame::coding::Control,
+        Message,
+    };
+
+    #[test]
+    fn offer() {
+        assert_eq!(DeflateConfig::default().offer(), "permessage-deflate; client_max_window_bits");
+        let config = DeflateConfig::default()
+            .client_max_window_bits(10)
+            .server_max_window_bits(12)
+            .client_no_context_takeover()
+            .server_no_context_takeover();
+        assert_eq!(
+            config.offer(),
+            "permessage-deflate; client_max_window_bits=10; server_max_window_bits=12; \
+             client_no_context_takeover; server_no_context_takeover"
+        );
+    }
+
+    #[test]
+    fn negotiate() {
+        let config = DeflateConfig::default().client_max_window_bits(12);
+        assert!(config.negotiate(None).unwrap().is_none());
+        assert!(config.negotiate(Some(" ")).unwrap().is_none());
+
+        let negotiated = config
+            .negotiate(Some(
+                "permessage-deflate; client_max_window_bits=10; server_no_context_takeover",
+            ))
+            .unwrap()
+            .unwrap();
+        assert_eq!(negotiated.client_window_bits, 10);
+        assert!(!negotiated.client_no_context_takeover);
+        assert!(negotiated.server_no_context_takeover);
+
+        let negotiated = config.negotiate(Some("permessage-deflate")).unwrap().unwrap();
+        assert_eq!(negotiated.client_window_bits, 12);
+
+        assert!(config.negotiate(Some("x-webkit-deflate-frame")).is_err());
+        assert!(config.negotiate(Some("permessage-deflate; foo")).is_err());
+        assert!(config.negotiate(Some("permessage-deflate; client_max_window_bits=8")).is_err());
+        assert!(config.negotiate(Some("permessage-deflate; client_max_window_bits=16")).is_err());
+        assert!(config.negotiate(Some("permessage-deflate, permessage-deflate")).is_err());
+    }
+
+    #[test]
+    fn codec_roundtrip() {
+        for no_context_takeover in [false, true] {
+            let negotiated = Negotiated {
+                client_window_bits: 9,
+                client_no_context_takeover: no_context_takeover,
+                server_no_context_takeover: no_context_takeover,
+            };
+            let mut codec = Codec::new(negotiated, 6);
+            let message = br#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#.repeat(100);
+            for _ in 0..3 {
+                let compressed = codec.compress(&message).unwrap();
+                assert!(compressed.len() < message.len() / 10);
+                assert!(!compressed.ends_with(&TRAILER));
+                assert_eq!(codec.decompress(compressed).unwrap(), message);
+            }
+            let empty = codec.compress(&[]).unwrap();
+            assert!(codec.decompress(empty).unwrap().is_empty());
+        }
+    }
+
+    /// Reads a single frame from `stream`, returning its header and unmasked payload.
+    async fn read_frame(stream: &mut (impl AsyncRead + Unpin)) -> (FrameHeader, Vec<u8>) {
+        let mut buf = Vec::new();
+        loop {
+            if let Some((header, start, end)) = next_frame(&buf).unwrap() {
+                let mut payload = buf[start..end].to_vec();
+                if let Some(mask) = header.mask {
+                    apply_mask(&mut payload, mask);
+                }
+                return (header, payload);
+            }
+            let mut byte = [0];
+            stream.read_exact(&mut byte).await.unwrap();
+            buf.push(byte[0]);
+        }
+    }
+
+    #[tokio::test]
+    async fn compressed_messages() {
+        let (client, mut server) = tokio::io::duplex(1 << 16);
+        let config = DeflateConfig::default();
+        let server = tokio::spawn(async move {
+            let mut request = Vec::new();
+            while !request.ends_with(b"\r\n\r\n") {
+                request.push(server.read_u8().await.unwrap());
+            }
+            let request = String::from_utf8(request).unwrap();
+            let header = |name: &str| {
+                request.lines().find_map(|line| {
+                    let (key, value) = line.split_once(": ")?;
+                    key.eq_ignore_ascii_case(name).then_some(value)
+                })
+            };
+            assert_eq!(header("sec-websocket-extensions"), Some(config.offer().as_str()));
+            let key = header("sec-websocket-key").unwrap();
+            let response = format!(
+                "HTTP/1.1 101 Switching Protocols\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\
+                 Sec-WebSocket-Accept: {}\r\n\
+                 Sec-WebSocket-Extensions: permessage-deflate; server_no_context_takeover\r\n\r\n",
+                derive_accept_key(key.as_bytes())
+            );
+            server.write_all(response.as_bytes()).await.unwrap();
+
+            let negotiated = Negotiated {
+                client_window_bits: 15,
+                client_no_context_takeover: false,
+                server_no_context_takeover: true,
+            };
+            let mut codec = Codec::new(negotiated, 6);
+
+            // A compressed message, in two fragments with a ping in between.
+            let payload = codec.compress(b"hello from the server").unwrap();
+            let (first, second) = payload.split_at(payload.len() / 2);
+            let mut frames = Vec::new();
+            let mut header = FrameHeader { is_final: false, rsv1: true, ..Default::default() };
+            header.opcode = OpCode::Data(Data::Text);
+            header.format(first.len() as u64, &mut frames).unwrap();
+            frames.extend_from_slice(first);
+            FrameHeader { opcode: OpCode::Control(Control::Ping), ..Default::default() }
+                .format(0, &mut frames)
+                .unwrap();
+            header = FrameHeader { opcode: OpCode::Data(Data::Continue), ..Default::default() };
+            header.format(second.len() as u64, &mut frames).unwrap();
+            frames.extend_from_slice(second);
+            server.write_all(&frames).await.unwrap();
+
+            let (header, payload) = read_frame(&mut server).await;
+            assert_eq!(header.opcode, OpCode::Control(Control::Pong));
+            assert!(!header.rsv1);
+            assert!(payload.is_empty());
+
+            let (header, payload) = read_frame(&mut server).await;
+            assert_eq!(header.opcode, OpCode::Data(Data::Text));
+            assert!(header.rsv1 && header.is_final);
+            assert_eq!(codec.decompress(payload).unwrap(), b"hello from the client");
+        });
+
+        let request = "ws://localhost".into_client_request().unwrap();
+        let mut request = request;
+        request.headers_mut().insert("Sec-WebSocket-Extensions", config.offer().parse().unwrap());
+        let (mut socket, _) =
+            tokio_tungstenite::client_async(request, DeflateStream::new(client, config))
+                .await
+                .unwrap();
+        assert!(socket.get_ref().is_negotiated());
+
+        let message = socket.next().await.unwrap().unwrap();
+        assert_eq!(message, Message::Ping(vec![]));
+        let message = socket.next().await.unwrap().unwrap();
+        assert_eq!(message, Message::Text("hello from the server".into()));
+        socket.send(Message::Text("hello from the client".into())).await.unwrap();
+
+        server.await.unwrap();
+    }
+}
@@ -20,10 +20,15 @@ extern crate tracing;
 
 use alloy_pubsub::ConnectionInterface;
 
+#[cfg(not(target_arch = "wasm32"))]
+mod deflate;
+#[cfg(not(target_arch = "wasm32"))]
+pub use deflate::DeflateConfig;
+
 #[cfg(not(target_arch = "wasm32"))]
 mod native;
 #[cfg(not(target_arch = "wasm32"))]
-pub use native::WsConnect;
+pub use native::{ConfiguredWsConnect, WsConnect};
 
 #[cfg(target_arch = "wasm32")]
 mod wasm;
@@ -1,17 +1,28 @@
-use crate::WsBackend;
+use crate::{
+    deflate::{DeflateConfig, DeflateStream},
+    WsBackend,
+};
 use alloy_pubsub::PubSubConnect;
-use alloy_transport::{utils::Spawnable, Authorization, TransportErrorKind, TransportResult};
-use futures::{SinkExt, StreamExt};
+use alloy_transport::{
+    dual_stack::DualStack, utils::Spawnable, Authorization, TransportErrorKind, TransportResult,
+};
+use futures::{
+    future::{self, Either},
+    SinkExt, StreamExt,
+};
 use serde_json::value::RawValue;
-use std::time::Duration;
-use tokio::time::sleep;
+use std::{io, net::SocketAddr, pin::pin, sync::Arc, time::Duration};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+    time::sleep,
+};
+use tokio_rustls::{client::TlsStream, TlsConnector};
 use tokio_tungstenite::{
-    tungstenite::{self, client::IntoClientRequest, Message},
-    MaybeTlsStream, WebSocketStream,
+    tungstenite::{self, client::IntoClientRequest, error::UrlError, Message},
+    WebSocketStream,
 };
 
-type TungsteniteStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
-
 const KEEPALIVE: u64 = 10;
 
 /// Simple connection details for a websocket connection.
@@ -34,13 +45,25 @@ impl WsConnect {
     pub fn with_auth<S: Into<String>>(url: S, auth: Option<Authorization>) -> Self {
         Self { url: url.into(), auth }
     }
+
+    /// Offers `permessage-deflate` compression to the server, see
+    /// [`ConfiguredWsConnect::with_compression`].
+    pub fn with_compression(self, config: DeflateConfig) -> ConfiguredWsConnect {
+        ConfiguredWsConnect::from(self).with_compression(config)
+    }
+
+    /// Connects to the IPv4 and IPv6 addresses of the host according to `config`, see
+    /// [`ConfiguredWsConnect::with_dual_stack`].
+    pub fn with_dual_stack(self, config: DualStack) -> ConfiguredWsConnect {
+        ConfiguredWsConnect::from(self).with_dual_stack(config)
+    }
 }
 
 impl IntoClientRequest for WsConnect {
     fn into_client_request(self) -> tungstenite::Result<tungstenite::handshake::client::Request> {
         let mut request: http::Request<()> = self.url.into_client_request()?;
         if let Some(auth) = self.auth {
-            let mut auth_value = http::HeaderValue::from_str(&auth.to_string())?;
+            let mut auth_value = http::HeaderValue::from_str(&auth.header_value())?;
             auth_value.set_sensitive(true);
 
             request.headers_mut().insert(http::header::AUTHORIZATION, auth_value);
@@ -70,7 +93,203 @@ impl PubSubConnect for WsConnect {
     }
 }
 
-impl WsBackend<TungsteniteStream> {
+/// Connection details for a websocket connection, with connection options.
+///
+/// Created with [`WsConnect::with_compression`] or [`WsConnect::with_dual_stack`].
+#[derive(Clone, Debug)]
+pub struct ConfiguredWsConnect {
+    connect: WsConnect,
+    compression: Option<DeflateConfig>,
+    dual_stack: Option<DualStack>,
+}
+
+impl From<WsConnect> for ConfiguredWsConnect {
+    fn from(connect: WsConnect) -> Self {
+        Self { connect, compression: None, dual_stack: None }
+    }
+}
+
+impl ConfiguredWsConnect {
+    /// Returns the connection details.
+    pub const fn connect(&self) -> &WsConnect {
+        &self.connect
+    }
+
+    /// Offers `permessage-deflate` compression to the server.
+    ///
+    /// Compression is only used if the server accepts it, otherwise messages are sent
+    /// uncompressed. On `wss` URLs, the TLS session is set up with `rustls` and the Mozilla root
+    /// certificates, as `tokio-tungstenite` does without compression.
+    pub const fn with_compression(mut self, config: DeflateConfig) -> Self {
+        self.compression = Some(config);
+        self
+    }
+
+    /// Connects to the IPv4 and IPv6 addresses of the host according to `config`, e.g. racing
+    /// them, or avoiding a degraded IPv6 network.
+    pub const fn with_dual_stack(mut self, config: DualStack) -> Self {
+        self.dual_stack = Some(config);
+        self
+    }
+}
+
+impl PubSubConnect for ConfiguredWsConnect {
+    fn is_local(&self) -> bool {
+        self.connect.is_local()
+    }
+
+    async fn connect(&self) -> TransportResult<alloy_pubsub::ConnectionHandle> {
+        let request = self.connect.clone().into_client_request();
+        let mut req = request.map_err(TransportErrorKind::custom)?;
+        if let Some(compression) = self.compression {
+            let offer = http::HeaderValue::from_str(&compression.offer())
+                .map_err(TransportErrorKind::custom)?;
+            req.headers_mut().insert(http::header::SEC_WEBSOCKET_EXTENSIONS, offer);
+        }
+
+        let stream =
+            connect_tcp(&req, self.dual_stack).await.map_err(TransportErrorKind::custom)?;
+        let (handle, interface) = alloy_pubsub::ConnectionHandle::new();
+        match (self.compression, req.uri().scheme_str()) {
+            (Some(compression), Some("wss")) => {
+                let stream = connect_tls(&req, stream).await.map_err(TransportErrorKind::custom)?;
+                let socket =
+                    handshake_deflate(req, DeflateStream::new(stream, compression)).await?;
+                WsBackend { socket, interface }.spawn();
+            }
+            (Some(compression), _) => {
+                let socket =
+                    handshake_deflate(req, DeflateStream::new(stream, compression)).await?;
+                WsBackend { socket, interface }.spawn();
+            }
+            (None, _) => {
+                let (socket, _) = tokio_tungstenite::client_async_tls(req, stream)
+                    .await
+                    .map_err(TransportErrorKind::custom)?;
+                WsBackend { socket, interface }.spawn();
+            }
+        }
+
+        Ok(handle)
+    }
+}
+
+/// Performs the WebSocket handshake over a stream offering compression.
+async fn handshake_deflate<S>(
+    request: tungstenite::handshake::client::Request,
+    stream: DeflateStream<S>,
+) -> TransportResult<WebSocketStream<DeflateStream<S>>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (socket, _) = tokio_tungstenite::client_async(request, stream)
+        .await
+        .map_err(TransportErrorKind::custom)?;
+    if !socket.get_ref().is_negotiated() {
+        debug!("server declined websocket compression");
+    }
+    Ok(socket)
+}
+
+/// Sets up the TLS session of a `wss` request over `stream`.
+///
+/// This mirrors what [`tokio_tungstenite::client_async_tls`] does internally with the
+/// `rustls-tls-webpki-roots` feature, so that the TLS stream can be wrapped before the handshake.
+async fn connect_tls(
+    request: &tungstenite::handshake::client::Request,
+    stream: TcpStream,
+) -> io::Result<TlsStream<TcpStream>> {
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+            anchor.subject,
+            anchor.spki,
+            anchor.name_constraints,
+        )
+    }));
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    let host = request.uri().host().unwrap_or_default();
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let server_name = rustls::ServerName::try_from(host)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    TlsConnector::from(Arc::new(config)).connect(server_name, stream).await
+}
+
+/// Opens the TCP connection for `request`, connecting to the addresses of the host according to
+/// `dual_stack`.
+///
+/// This mirrors what [`tokio_tungstenite::connect_async`] does internally, so that the stream
+/// can be wrapped before the handshake.
+async fn connect_tcp(
+    request: &tungstenite::handshake::client::Request,
+    dual_stack: Option<DualStack>,
+) -> tungstenite::Result<TcpStream> {
+    let uri = request.uri();
+    let host = uri.host().ok_or(tungstenite::Error::Url(UrlError::NoHostName))?;
+    // IPv6 addresses are resolved without the surrounding brackets
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let port = match (uri.port_u16(), uri.scheme_str()) {
+        (Some(port), _) => port,
+        (None, Some("wss")) => 443,
+        (None, Some("ws")) => 80,
+        _ => return Err(tungstenite::Error::Url(UrlError::UnsupportedUrlScheme)),
+    };
+
+    Ok(match dual_stack {
+        Some(config) => {
+            connect_dual_stack(tokio::net::lookup_host((host, port)).await?, config).await?
+        }
+        None => TcpStream::connect((host, port)).await?,
+    })
+}
+
+/// Connects to the first reachable of `addrs`, ordered by IP version and raced according to
+/// `config`.
+async fn connect_dual_stack(
+    addrs: impl IntoIterator<Item = SocketAddr>,
+    config: DualStack,
+) -> io::Result<TcpStream> {
+    let (preferred, fallback) = config.split(addrs);
+    if !config.racing() || fallback.is_empty() {
+        return connect_sequential(preferred.into_iter().chain(fallback)).await;
+    }
+
+    // Race the fallback addresses once the delay elapsed, or as soon as the preferred ones failed.
+    let mut preferred = pin!(connect_sequential(preferred));
+    match future::select(preferred.as_mut(), pin!(sleep(config.fallback_delay()))).await {
+        Either::Left((Ok(socket), _)) => return Ok(socket),
+        Either::Left((Err(_), _)) => return connect_sequential(fallback).await,
+        Either::Right(_) => {}
+    }
+    match future::select(preferred, pin!(connect_sequential(fallback))).await {
+        Either::Left((Ok(socket), _)) | Either::Right((Ok(socket), _)) => Ok(socket),
+        Either::Left((Err(_), other)) => other.await,
+        Either::Right((Err(_), other)) => other.await,
+    }
+}
+
+/// Connects to the first reachable of `addrs`, trying them in order.
+async fn connect_sequential(addrs: impl IntoIterator<Item = SocketAddr>) -> io::Result<TcpStream> {
+    let mut last_err = None;
+    for addr in addrs {
+        match TcpStream::connect(addr).await {
+            Ok(socket) => return Ok(socket),
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(io::ErrorKind::AddrNotAvailable, "no address to connect to")
+    }))
+}
+
+impl<S> WsBackend<WebSocketStream<S>>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
     /// Handle a message from the server.
     #[allow(clippy::result_unit_err)]
     pub fn handle(&mut self, msg: Message) -> Result<(), ()> {
@@ -175,3 +394,28 @@ impl WsBackend<TungsteniteStream> {
         fut.spawn_task()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_transport::dual_stack::IpPreference;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn connects_dual_stack() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live = listener.local_addr().unwrap();
+        // A port of the IPv6 loopback nothing listens on, if IPv6 is available at all.
+        let dead: SocketAddr = "[::1]:1".parse().unwrap();
+        let addrs = [live, dead];
+
+        let prefer_v6 = DualStack::new().with_ip_preference(IpPreference::PreferIpv6);
+        for config in [prefer_v6, prefer_v6.with_racing(false)] {
+            let socket = connect_dual_stack(addrs, config).await.unwrap();
+            assert_eq!(socket.peer_addr().unwrap(), live);
+        }
+
+        let v6_only = DualStack::new().with_ip_preference(IpPreference::Ipv6Only);
+        assert!(connect_dual_stack(addrs, v6_only).await.is_err());
+    }
+}
@@ -0,0 +1,314 @@
+//! Batch splitting.
+//!
+//! RPC providers and gateways commonly reject batches with too many requests, or request bodies
+//! above some size. A [`BatchLimitLayer`] splits the batches exceeding the configured
+//! [`BatchLimits`] into several batches sent by the inner transport, and merges their responses
+//! into a single batch response, ordered like the requests.
+//!
+//! A request whose own size exceeds the byte limit cannot be split, and is sent alone. At most
+//! [`BatchLimits::max_concurrent_batches`] of the batches split from a single batch are in flight
+//! at once.
+
+use crate::{TransportError, TransportFut};
+use alloy_json_rpc::{Id, RequestPacket, ResponsePacket, SerializedRequest};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use std::{
+    collections::HashMap,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service, ServiceExt};
+
+/// The default maximum number of split batches in flight at once.
+pub const DEFAULT_MAX_CONCURRENT_BATCHES: usize = 4;
+
+/// The maximum number of requests, and of serialized bytes, of the batches sent over a
+/// transport.
+///
+/// No limit is set by default, and at most [`DEFAULT_MAX_CONCURRENT_BATCHES`] split batches are
+/// sent concurrently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BatchLimits {
+    max_requests: Option<usize>,
+    max_bytes: Option<usize>,
+    max_concurrent_batches: usize,
+}
+
+impl Default for BatchLimits {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchLimits {
+    /// Create new limits, without any limit set.
+    pub const fn new() -> Self {
+        Self {
+            max_requests: None,
+            max_bytes: None,
+            max_concurrent_batches: DEFAULT_MAX_CONCURRENT_BATCHES,
+        }
+    }
+
+    /// Sets the maximum number of requests of a batch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_requests` is zero.
+    pub const fn with_max_requests(mut self, max_requests: usize) -> Self {
+        assert!(max_requests > 0, "a batch must contain at least one request");
+        self.max_requests = Some(max_requests);
+        self
+    }
+
+    /// Sets the maximum size of a batch, as the sum of the sizes of its serialized requests.
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Sets the maximum number of batches split from a single batch that are sent concurrently.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_concurrent_batches` is zero.
+    pub const fn with_max_concurrent_batches(mut self, max_concurrent_batches: usize) -> Self {
+        assert!(max_concurrent_batches > 0, "at least one batch must be sent at a time");
+        self.max_concurrent_batches = max_concurrent_batches;
+        self
+    }
+
+    /// Returns the maximum number of requests of a batch, if any.
+    pub const fn max_requests(&self) -> Option<usize> {
+        self.max_requests
+    }
+
+    /// Returns the maximum size of a batch, if any.
+    pub const fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Returns the maximum number of split batches sent concurrently.
+    pub const fn max_concurrent_batches(&self) -> usize {
+        self.max_concurrent_batches
+    }
+
+    /// Splits `requests` into consecutive batches within the limits, keeping their order.
+    ///
+    /// Every batch contains at least one request, even if it exceeds the byte limit on its own.
+    pub fn split(&self, requests: Vec<SerializedRequest>) -> Vec<Vec<SerializedRequest>> {
+        let mut batches = Vec::new();
+        let mut batch = Vec::new();
+        let mut bytes = 0;
+        for request in requests {
+            let size = request.serialized().get().len();
+            let full = self.max_requests.is_some_and(|max| batch.len() >= max)
+                || self.max_bytes.is_some_and(|max| bytes + size > max);
+            if full && !batch.is_empty() {
+                batches.push(std::mem::take(&mut batch));
+                bytes = 0;
+            }
+            bytes += size;
+            batch.push(request);
+        }
+        if !batch.is_empty() {
+            batches.push(batch);
+        }
+        batches
+    }
+}
+
+/// A [`Layer`] splitting the batches exceeding some [`BatchLimits`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BatchLimitLayer {
+    limits: BatchLimits,
+}
+
+impl BatchLimitLayer {
+    /// Create a new layer with the given limits.
+    pub const fn new(limits: BatchLimits) -> Self {
+        Self { limits }
+    }
+
+    /// Returns the limits of the layer.
+    pub const fn limits(&self) -> &BatchLimits {
+        &self.limits
+    }
+}
+
+impl<S> Layer<S> for BatchLimitLayer {
+    type Service = BatchLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BatchLimitService { inner, limits: self.limits }
+    }
+}
+
+/// A transport splitting the batches exceeding some [`BatchLimits`], created by a
+/// [`BatchLimitLayer`].
+#[derive(Clone, Debug)]
+pub struct BatchLimitService<S> {
+    inner: S,
+    limits: BatchLimits,
+}
+
+impl<S> Service<RequestPacket> for BatchLimitService<S>
+where
+    S: Service<
+            RequestPacket,
+            Response = ResponsePacket,
+            Error = TransportError,
+            Future = TransportFut<'static>,
+        > + Clone
+        + Send
+        + 'static,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let requests = match req {
+            RequestPacket::Batch(requests) if requests.len() > 1 => requests,
+            req => return self.inner.call(req),
+        };
+        let mut batches = self.limits.split(requests);
+        if batches.len() == 1 {
+            return self.inner.call(RequestPacket::Batch(batches.remove(0)));
+        }
+
+        // The position of each request, to order the responses like the requests.
+        let positions: HashMap<Id, usize> =
+            batches.iter().flatten().enumerate().map(|(i, req)| (req.id().clone(), i)).collect();
+        // Each batch is sent by its own clone of the inner service, once that clone is ready.
+        let inner = self.inner.clone();
+        let concurrency = self.limits.max_concurrent_batches;
+
+        Box::pin(async move {
+            let resps: Vec<_> = stream::iter(batches)
+                .map(move |batch| inner.clone().oneshot(RequestPacket::Batch(batch)))
+                .buffered(concurrency)
+                .try_collect()
+                .await?;
+            let mut responses: Vec<_> = resps
+                .into_iter()
+                .flat_map(|resp| match resp {
+                    ResponsePacket::Single(resp) => vec![resp],
+                    ResponsePacket::Batch(resps) => resps,
+                })
+                .collect();
+            // Responses to unknown IDs are kept last, in the order they were received.
+            responses.sort_by_key(|resp| positions.get(&resp.id).copied().unwrap_or(usize::MAX));
+            Ok(ResponsePacket::Batch(responses))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Request, Response, ResponsePayload};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn splits_within_limits() {
+        let request = |id, params: &str| {
+            Request::new("eth_call", Id::Number(id), params.to_owned()).serialize().unwrap()
+        };
+        let ids = |batches: Vec<Vec<SerializedRequest>>| {
+            batches
+                .iter()
+                .map(|batch| batch.iter().map(|req| req.id().as_number().unwrap()).collect())
+                .collect::<Vec<Vec<_>>>()
+        };
+        let size = request(0, "").serialized().get().len();
+        let requests = || (1..=5).map(|id| request(id, "")).collect::<Vec<_>>();
+
+        let limits = BatchLimits::new().with_max_requests(2);
+        assert_eq!(ids(limits.split(requests())), [vec![1, 2], vec![3, 4], vec![5]]);
+        let limits = BatchLimits::new().with_max_bytes(size * 3);
+        assert_eq!(ids(limits.split(requests())), [vec![1, 2, 3], vec![4, 5]]);
+        assert_eq!(ids(BatchLimits::new().split(requests())), [vec![1, 2, 3, 4, 5]]);
+
+        // Oversized requests are sent alone.
+        let limits = BatchLimits::new().with_max_bytes(size * 2);
+        let requests = vec![request(1, ""), request(2, &"f".repeat(size)), request(3, "")];
+        assert_eq!(ids(limits.split(requests)), [vec![1], vec![2], vec![3]]);
+    }
+
+    #[tokio::test]
+    async fn merges_responses_in_order() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let inner_sent = sent.clone();
+        // Responds to the requests of each batch in reverse order.
+        let inner = tower::service_fn(move |req: RequestPacket| -> TransportFut<'static> {
+            let RequestPacket::Batch(reqs) = req else { unreachable!() };
+            inner_sent.lock().unwrap().push(reqs.len());
+            let resps = reqs
+                .iter()
+                .rev()
+                .map(|req| Response {
+                    id: req.id().clone(),
+                    payload: ResponsePayload::Success(req.params().unwrap().to_owned()),
+                })
+                .collect();
+            Box::pin(async move { Ok(ResponsePacket::Batch(resps)) })
+        });
+        let mut service =
+            BatchLimitLayer::new(BatchLimits::new().with_max_requests(2)).layer(inner);
+
+        let batch = (1..=5u64)
+            .map(|id| Request::new("eth_call", Id::Number(id), (id,)).serialize().unwrap())
+            .collect();
+        let ResponsePacket::Batch(resps) = service.call(RequestPacket::Batch(batch)).await.unwrap()
+        else {
+            unreachable!()
+        };
+        let ids = resps.iter().map(|resp| resp.id.as_number().unwrap()).collect::<Vec<_>>();
+        assert_eq!(ids, [1, 2, 3, 4, 5]);
+        assert_eq!(*sent.lock().unwrap(), [2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn caps_concurrent_batches() {
+        let in_flight = Arc::new(Mutex::new((0, 0)));
+        let inner_in_flight = in_flight.clone();
+        let inner = tower::service_fn(move |req: RequestPacket| -> TransportFut<'static> {
+            let RequestPacket::Batch(reqs) = req else { unreachable!() };
+            let in_flight = inner_in_flight.clone();
+            Box::pin(async move {
+                {
+                    let (current, max) = &mut *in_flight.lock().unwrap();
+                    *current += 1;
+                    *max = (*max).max(*current);
+                }
+                tokio::task::yield_now().await;
+                in_flight.lock().unwrap().0 -= 1;
+                let resps = reqs
+                    .iter()
+                    .map(|req| Response {
+                        id: req.id().clone(),
+                        payload: ResponsePayload::Success(req.params().unwrap().to_owned()),
+                    })
+                    .collect();
+                Ok(ResponsePacket::Batch(resps))
+            })
+        });
+        let limits = BatchLimits::new().with_max_requests(1).with_max_concurrent_batches(2);
+        let mut service = BatchLimitLayer::new(limits).layer(inner);
+
+        let batch = (1..=6u64)
+            .map(|id| Request::new("eth_call", Id::Number(id), (id,)).serialize().unwrap())
+            .collect();
+        service.ready().await.unwrap();
+        let ResponsePacket::Batch(resps) = service.call(RequestPacket::Batch(batch)).await.unwrap()
+        else {
+            unreachable!()
+        };
+        assert_eq!(resps.len(), 6);
+        assert_eq!(*in_flight.lock().unwrap(), (0, 2));
+    }
+}
@@ -43,6 +43,28 @@ impl Authorization {
     pub fn bearer(token: impl Into<String>) -> Self {
         Self::Bearer(token.into())
     }
+
+    /// Instantiate a new bearer auth with a JWT issued now, as expected by the authenticated
+    /// Engine API.
+    ///
+    /// The token is only valid for about a minute, so this is suited to authenticating a
+    /// connection once, e.g. a websocket handshake. Use a [`JwtLayer`](crate::JwtLayer) to
+    /// authenticate each request instead.
+    #[cfg(feature = "jwt-auth")]
+    pub fn jwt(secret: &alloy_rpc_types_engine::JwtSecret) -> Result<Self, crate::TransportError> {
+        crate::jwt::issue_token(secret).map(Self::Bearer)
+    }
+
+    /// Returns the value of the `Authorization` header, including the credentials.
+    ///
+    /// The [`Display`](fmt::Display) implementation only prints the scheme, so that credentials
+    /// do not end up in logs.
+    pub fn header_value(&self) -> String {
+        match self {
+            Self::Basic(auth) => format!("Basic {auth}"),
+            Self::Bearer(token) => format!("Bearer {token}"),
+        }
+    }
 }
 
 impl fmt::Display for Authorization {
@@ -0,0 +1,350 @@
+//! Request and compute unit accounting.
+//!
+//! RPC providers usually bill by compute units, a per-method weight on each request. A
+//! [`CostLayer`] records the requests sent through a transport, per method, in a [`CostTracker`].
+//! Trackers are labeled, so that usage can be attributed to a specific provider, API key or
+//! application feature by using one tracker for each.
+
+use crate::{TransportError, TransportFut};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, Mutex, RwLock},
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// The compute units charged for each method.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CostTable {
+    default: u64,
+    methods: HashMap<Cow<'static, str>, u64>,
+}
+
+impl Default for CostTable {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl CostTable {
+    /// Create a new table charging `default` compute units for every method.
+    pub fn new(default: u64) -> Self {
+        Self { default, methods: HashMap::new() }
+    }
+
+    /// Set the compute units charged for `method`.
+    pub fn with_method(mut self, method: impl Into<Cow<'static, str>>, units: u64) -> Self {
+        self.methods.insert(method.into(), units);
+        self
+    }
+
+    /// Returns the compute units charged for `method`.
+    pub fn compute_units(&self, method: &str) -> u64 {
+        self.methods.get(method).copied().unwrap_or(self.default)
+    }
+}
+
+/// The usage of a single method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MethodUsage {
+    /// The number of requests sent.
+    pub requests: u64,
+    /// The number of requests that failed at the transport level.
+    pub failures: u64,
+    /// The compute units charged.
+    pub compute_units: u64,
+}
+
+impl std::ops::AddAssign for MethodUsage {
+    fn add_assign(&mut self, rhs: Self) {
+        self.requests += rhs.requests;
+        self.failures += rhs.failures;
+        self.compute_units += rhs.compute_units;
+    }
+}
+
+/// A snapshot of the usage recorded by a [`CostTracker`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CostReport {
+    /// The label of the tracker.
+    pub label: String,
+    /// The usage of each method.
+    pub methods: BTreeMap<String, MethodUsage>,
+}
+
+impl CostReport {
+    /// Returns the usage summed over all methods.
+    pub fn total(&self) -> MethodUsage {
+        let mut total = MethodUsage::default();
+        for usage in self.methods.values() {
+            total += *usage;
+        }
+        total
+    }
+}
+
+/// A single request, as passed to the hooks of a [`CostTracker`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CostRecord<'a> {
+    /// The label of the tracker.
+    pub label: &'a str,
+    /// The method of the request.
+    pub method: &'a str,
+    /// The compute units charged for the request.
+    pub compute_units: u64,
+}
+
+type Hook = Arc<dyn Fn(&CostRecord<'_>) + Send + Sync>;
+
+struct Inner {
+    label: String,
+    table: CostTable,
+    usage: Mutex<HashMap<String, MethodUsage>>,
+    hooks: RwLock<Vec<Hook>>,
+}
+
+/// Records the requests and compute units used through one or more [`CostLayer`]s.
+///
+/// Cloning a tracker returns a handle to the same records.
+///
+/// ```
+/// use alloy_transport::cost::{CostTable, CostTracker};
+///
+/// let table = CostTable::new(10).with_method("eth_getLogs", 75);
+/// let tracker = CostTracker::new("indexer", table);
+/// tracker.add_hook(|record| println!("{}: {} {}", record.label, record.method, record.compute_units));
+///
+/// // Add `tracker.layer()` to the client, e.g. with `ClientBuilder::layer`, then later:
+/// let report = tracker.take_report();
+/// println!("{} compute units", report.total().compute_units);
+/// ```
+#[derive(Clone)]
+pub struct CostTracker {
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for CostTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CostTracker")
+            .field("label", &self.inner.label)
+            .field("table", &self.inner.table)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CostTracker {
+    /// Create a new tracker with the given label and cost table.
+    pub fn new(label: impl Into<String>, table: CostTable) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                label: label.into(),
+                table,
+                usage: Mutex::new(HashMap::new()),
+                hooks: RwLock::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Returns the label of the tracker.
+    pub fn label(&self) -> &str {
+        &self.inner.label
+    }
+
+    /// Returns the cost table of the tracker.
+    pub fn table(&self) -> &CostTable {
+        &self.inner.table
+    }
+
+    /// Returns a [`CostLayer`] recording into this tracker.
+    pub fn layer(&self) -> CostLayer {
+        CostLayer { tracker: self.clone() }
+    }
+
+    /// Add a hook called for every request, e.g. to export usage to a metrics system.
+    pub fn add_hook(&self, hook: impl Fn(&CostRecord<'_>) + Send + Sync + 'static) {
+        self.inner.hooks.write().unwrap().push(Arc::new(hook));
+    }
+
+    /// Returns the usage recorded so far.
+    pub fn report(&self) -> CostReport {
+        let usage = self.inner.usage.lock().unwrap();
+        self.make_report(usage.iter().map(|(method, usage)| (method.clone(), *usage)).collect())
+    }
+
+    /// Returns the usage recorded so far, and resets it.
+    pub fn take_report(&self) -> CostReport {
+        let usage = std::mem::take(&mut *self.inner.usage.lock().unwrap());
+        self.make_report(usage.into_iter().collect())
+    }
+
+    fn make_report(&self, methods: BTreeMap<String, MethodUsage>) -> CostReport {
+        CostReport { label: self.inner.label.clone(), methods }
+    }
+
+    fn record_requests<'a>(&self, methods: impl Iterator<Item = &'a str>) {
+        let mut usage = self.inner.usage.lock().unwrap();
+        let records: Vec<_> = methods
+            .map(|method| {
+                let compute_units = self.inner.table.compute_units(method);
+                let entry = usage.entry(method.to_string()).or_default();
+                entry.requests += 1;
+                entry.compute_units += compute_units;
+                CostRecord { label: &self.inner.label, method, compute_units }
+            })
+            .collect();
+        // Hooks are called without the lock held, so that they may read the usage, and so that a
+        // panicking hook does not poison it.
+        drop(usage);
+
+        let hooks = self.inner.hooks.read().unwrap().clone();
+        for record in &records {
+            for hook in &hooks {
+                hook(record);
+            }
+        }
+    }
+
+    fn record_failures(&self, methods: &[String]) {
+        let mut usage = self.inner.usage.lock().unwrap();
+        for method in methods {
+            usage.entry(method.clone()).or_default().failures += 1;
+        }
+    }
+}
+
+/// A [`Layer`] recording requests into a [`CostTracker`].
+#[derive(Clone, Debug)]
+pub struct CostLayer {
+    tracker: CostTracker,
+}
+
+impl CostLayer {
+    /// Create a new layer recording into `tracker`.
+    pub const fn new(tracker: CostTracker) -> Self {
+        Self { tracker }
+    }
+}
+
+impl<S> Layer<S> for CostLayer {
+    type Service = CostService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CostService { inner, tracker: self.tracker.clone() }
+    }
+}
+
+/// A transport recording requests into a [`CostTracker`], created by a [`CostLayer`].
+#[derive(Clone, Debug)]
+pub struct CostService<S> {
+    inner: S,
+    tracker: CostTracker,
+}
+
+impl<S> CostService<S> {
+    /// Returns a reference to the tracker.
+    pub const fn tracker(&self) -> &CostTracker {
+        &self.tracker
+    }
+}
+
+impl<S> Service<RequestPacket> for CostService<S>
+where
+    S: Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    >,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let methods: Vec<String> = match &req {
+            RequestPacket::Single(single) => vec![single.method().to_string()],
+            RequestPacket::Batch(batch) => batch.iter().map(|r| r.method().to_string()).collect(),
+        };
+        self.tracker.record_requests(methods.iter().map(String::as_str));
+
+        let tracker = self.tracker.clone();
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let res = fut.await;
+            if res.is_err() {
+                tracker.record_failures(&methods);
+            }
+            res
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransportErrorKind;
+    use alloy_json_rpc::{Id, Request};
+    use futures_util::FutureExt;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn request(method: &'static str) -> alloy_json_rpc::SerializedRequest {
+        Request::new(method, Id::Number(0), ()).serialize().unwrap()
+    }
+
+    #[test]
+    fn records_usage() {
+        let table = CostTable::new(10).with_method("eth_getLogs", 75);
+        let tracker = CostTracker::new("indexer", table);
+        let hooked = Arc::new(AtomicU64::new(0));
+        let hooked2 = hooked.clone();
+        tracker.add_hook(move |record| {
+            assert_eq!(record.label, "indexer");
+            hooked2.fetch_add(record.compute_units, Ordering::Relaxed);
+        });
+
+        let failing = tower::service_fn(|_: RequestPacket| -> TransportFut<'static> {
+            Box::pin(async { Err(TransportErrorKind::backend_gone()) })
+        });
+        let mut service = tracker.layer().layer(failing);
+
+        let single = RequestPacket::Single(request("eth_blockNumber"));
+        assert!(service.call(single).now_or_never().unwrap().is_err());
+        let batch: RequestPacket =
+            [request("eth_getLogs"), request("eth_getLogs")].into_iter().collect();
+        assert!(service.call(batch).now_or_never().unwrap().is_err());
+
+        let report = tracker.report();
+        assert_eq!(report.label, "indexer");
+        assert_eq!(
+            report.methods["eth_getLogs"],
+            MethodUsage { requests: 2, failures: 2, compute_units: 150 }
+        );
+        assert_eq!(report.total(), MethodUsage { requests: 3, failures: 3, compute_units: 160 });
+        assert_eq!(hooked.load(Ordering::Relaxed), 160);
+
+        assert_eq!(tracker.take_report(), report);
+        assert!(tracker.report().methods.is_empty());
+    }
+
+    #[test]
+    fn hooks_can_read_usage() {
+        let tracker = CostTracker::new("indexer", CostTable::default());
+        let handle = tracker.clone();
+        tracker.add_hook(move |_| {
+            handle.report();
+        });
+        let failing = tower::service_fn(|_: RequestPacket| -> TransportFut<'static> {
+            Box::pin(async { Err(TransportErrorKind::backend_gone()) })
+        });
+        let mut service = tracker.layer().layer(failing);
+        let _ = service.call(RequestPacket::Single(request("eth_chainId"))).now_or_never();
+        assert_eq!(tracker.report().total().requests, 1);
+    }
+}
@@ -0,0 +1,154 @@
+//! IPv4 and IPv6 connection settings.
+//!
+//! Hosts with both IPv4 and IPv6 addresses can be reached over either stack, but one of them is
+//! sometimes degraded, e.g. IPv6 is announced but not routed. A [`DualStack`] configuration sets
+//! which addresses are tried first, or at all, and whether the other stack is raced against the
+//! preferred one after a delay, as described by "Happy Eyeballs" ([RFC 8305]).
+//!
+//! [RFC 8305]: https://www.rfc-editor.org/rfc/rfc8305
+
+use std::{net::SocketAddr, time::Duration};
+
+/// The default delay after which the addresses of the other stack are raced against the
+/// preferred ones.
+pub const DEFAULT_FALLBACK_DELAY: Duration = Duration::from_millis(300);
+
+/// The IP version of the addresses to connect to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum IpPreference {
+    /// Prefer the version of the first address returned by the resolver.
+    #[default]
+    Any,
+    /// Prefer IPv4 addresses, falling back to IPv6 ones.
+    PreferIpv4,
+    /// Prefer IPv6 addresses, falling back to IPv4 ones.
+    PreferIpv6,
+    /// Only connect to IPv4 addresses.
+    Ipv4Only,
+    /// Only connect to IPv6 addresses.
+    Ipv6Only,
+}
+
+impl IpPreference {
+    /// Returns true if `addr` may be connected to.
+    pub const fn allows(&self, addr: &SocketAddr) -> bool {
+        match self {
+            Self::Any | Self::PreferIpv4 | Self::PreferIpv6 => true,
+            Self::Ipv4Only => addr.is_ipv4(),
+            Self::Ipv6Only => addr.is_ipv6(),
+        }
+    }
+
+    /// Returns whether IPv6 addresses are preferred, if a version is preferred.
+    const fn prefers_ipv6(&self) -> Option<bool> {
+        match self {
+            Self::Any => None,
+            Self::PreferIpv4 | Self::Ipv4Only => Some(false),
+            Self::PreferIpv6 | Self::Ipv6Only => Some(true),
+        }
+    }
+}
+
+/// How to connect to hosts with both IPv4 and IPv6 addresses.
+///
+/// By default, the addresses of the version of the first resolved address are tried first, and
+/// the addresses of the other version are raced against them after [`DEFAULT_FALLBACK_DELAY`].
+///
+/// ```
+/// use alloy_transport::dual_stack::{DualStack, IpPreference};
+///
+/// // The IPv6 network of the host is unreliable.
+/// let config = DualStack::new().with_ip_preference(IpPreference::PreferIpv4).with_racing(false);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DualStack {
+    preference: IpPreference,
+    racing: bool,
+    fallback_delay: Duration,
+}
+
+impl Default for DualStack {
+    fn default() -> Self {
+        Self { preference: IpPreference::Any, racing: true, fallback_delay: DEFAULT_FALLBACK_DELAY }
+    }
+}
+
+impl DualStack {
+    /// Create a new configuration with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the IP version of the addresses to connect to.
+    pub const fn with_ip_preference(mut self, preference: IpPreference) -> Self {
+        self.preference = preference;
+        self
+    }
+
+    /// Sets whether the addresses of the other version are raced against the preferred ones.
+    ///
+    /// Without racing, they are only tried once all the preferred addresses failed.
+    pub const fn with_racing(mut self, racing: bool) -> Self {
+        self.racing = racing;
+        self
+    }
+
+    /// Sets the delay after which the addresses of the other version are raced against the
+    /// preferred ones.
+    pub const fn with_fallback_delay(mut self, delay: Duration) -> Self {
+        self.fallback_delay = delay;
+        self
+    }
+
+    /// Returns the IP version of the addresses to connect to.
+    pub const fn ip_preference(&self) -> IpPreference {
+        self.preference
+    }
+
+    /// Returns whether the addresses of the other version are raced against the preferred ones.
+    pub const fn racing(&self) -> bool {
+        self.racing
+    }
+
+    /// Returns the delay after which the addresses of the other version are raced against the
+    /// preferred ones.
+    pub const fn fallback_delay(&self) -> Duration {
+        self.fallback_delay
+    }
+
+    /// Splits resolved addresses into the preferred addresses, and the fallback addresses of the
+    /// other version, keeping their order and dropping the disallowed ones.
+    pub fn split(
+        &self,
+        addrs: impl IntoIterator<Item = SocketAddr>,
+    ) -> (Vec<SocketAddr>, Vec<SocketAddr>) {
+        let mut addrs = addrs.into_iter().filter(|addr| self.preference.allows(addr)).peekable();
+        let Some(ipv6) =
+            self.preference.prefers_ipv6().or_else(|| addrs.peek().map(SocketAddr::is_ipv6))
+        else {
+            return Default::default();
+        };
+        addrs.partition(|addr| addr.is_ipv6() == ipv6)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_addresses() {
+        let v4: SocketAddr = "10.0.0.1:443".parse().unwrap();
+        let v4b: SocketAddr = "10.0.0.2:443".parse().unwrap();
+        let v6: SocketAddr = "[2001:db8::1]:443".parse().unwrap();
+        let addrs = [v6, v4, v4b];
+        let split = |preference| DualStack::new().with_ip_preference(preference).split(addrs);
+
+        assert_eq!(split(IpPreference::Any), (vec![v6], vec![v4, v4b]));
+        assert_eq!(split(IpPreference::PreferIpv4), (vec![v4, v4b], vec![v6]));
+        assert_eq!(split(IpPreference::PreferIpv6), (vec![v6], vec![v4, v4b]));
+        assert_eq!(split(IpPreference::Ipv4Only), (vec![v4, v4b], vec![]));
+        assert_eq!(split(IpPreference::Ipv6Only), (vec![v6], vec![]));
+        assert_eq!(DualStack::new().split([]), (vec![], vec![]));
+    }
+}
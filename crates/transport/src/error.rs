@@ -1,6 +1,6 @@
-use alloy_json_rpc::{Id, RpcError, RpcResult};
+use alloy_json_rpc::{Id, RetryableError, RpcError, RpcResult};
 use serde_json::value::RawValue;
-use std::{error::Error as StdError, fmt::Debug};
+use std::{error::Error as StdError, fmt::Debug, time::Duration};
 use thiserror::Error;
 
 /// A transport error is an [`RpcError`] containing a [`TransportErrorKind`].
@@ -31,6 +31,32 @@ pub enum TransportErrorKind {
     #[error("subscriptions are not available on this provider")]
     PubsubUnavailable,
 
+    /// The endpoint is connected to a different chain than expected.
+    #[error("expected chain ID {expected}, but the endpoint reports chain ID {actual}")]
+    ChainIdMismatch {
+        /// The expected chain ID.
+        expected: u64,
+        /// The chain ID reported by the endpoint.
+        actual: u64,
+    },
+
+    /// The request did not complete in time.
+    #[error("request timed out after {0:?}")]
+    Timeout(Duration),
+
+    /// The deadline of the request passed before it completed.
+    #[error("request deadline exceeded")]
+    DeadlineExceeded,
+
+    /// The server responded with an HTTP status other than `200 OK`.
+    #[error("HTTP error {status} with body: {body}")]
+    HttpError {
+        /// The HTTP status code.
+        status: u16,
+        /// The body of the response, decoded lossily.
+        body: String,
+    },
+
     /// Custom error.
     #[error("{0}")]
     Custom(#[source] Box<dyn StdError + Send + Sync + 'static>),
@@ -67,4 +93,62 @@ impl TransportErrorKind {
     pub const fn pubsub_unavailable() -> TransportError {
         RpcError::Transport(Self::PubsubUnavailable)
     }
+
+    /// Instantiate a new `TransportError::Timeout`.
+    pub const fn timeout(duration: Duration) -> TransportError {
+        RpcError::Transport(Self::Timeout(duration))
+    }
+
+    /// Instantiate a new `TransportError::DeadlineExceeded`.
+    pub const fn deadline_exceeded() -> TransportError {
+        RpcError::Transport(Self::DeadlineExceeded)
+    }
+
+    /// Instantiate a new `TransportError::HttpError`.
+    pub const fn http_error(status: u16, body: String) -> TransportError {
+        RpcError::Transport(Self::HttpError { status, body })
+    }
+
+    /// Instantiate a new `TransportError::ChainIdMismatch`.
+    pub const fn chain_id_mismatch(expected: u64, actual: u64) -> TransportError {
+        RpcError::Transport(Self::ChainIdMismatch { expected, actual })
+    }
+
+    /// Check that the chain ID reported by an endpoint is the expected one, failing with
+    /// `TransportError::ChainIdMismatch` otherwise.
+    pub const fn check_chain_id(expected: u64, actual: u64) -> Result<(), TransportError> {
+        if expected != actual {
+            return Err(Self::chain_id_mismatch(expected, actual));
+        }
+        Ok(())
+    }
+}
+
+impl RetryableError for TransportErrorKind {
+    /// Missing batch responses, timeouts, and HTTP errors for rate limiting or unavailable
+    /// servers are retryable.
+    fn is_retryable(&self) -> bool {
+        match self {
+            Self::MissingBatchResponse(_) | Self::Timeout(_) => true,
+            Self::HttpError { status, .. } => matches!(status, 429 | 502 | 503 | 504),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retryable() {
+        assert!(TransportErrorKind::timeout(Duration::from_secs(1)).is_retryable());
+        assert!(TransportErrorKind::http_error(429, "Too Many Requests".into()).is_retryable());
+        assert!(!TransportErrorKind::http_error(401, String::new()).is_retryable());
+        assert!(!TransportErrorKind::deadline_exceeded().is_retryable());
+        assert_eq!(
+            TransportErrorKind::http_error(503, "unavailable".into()).to_string(),
+            "HTTP error 503 with body: unavailable"
+        );
+    }
 }
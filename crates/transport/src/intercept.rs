@@ -0,0 +1,248 @@
+//! Request and response interceptors.
+//!
+//! An [`Interceptor`] is a middleware seeing every request before it is sent, and every response
+//! before it is returned to the caller. It can rewrite them, or fail them, which makes it suited
+//! for request rewriting, response validation, audit logging or signing. A [`TransportLayer`]
+//! adds an interceptor to a transport, like any other [`Layer`], e.g. with `ClientBuilder::layer`.
+//!
+//! Interceptors see each request of a batch individually. The method name of a request is
+//! available with [`SerializedRequest::method`], and its serialized params with
+//! [`SerializedRequest::params`]. Middlewares needing to await, or to see the batches, can
+//! implement [`Service`] directly instead.
+
+use crate::{TransportError, TransportFut, TransportResult};
+use alloy_json_rpc::{Id, RequestPacket, Response, ResponsePacket, SerializedRequest};
+use std::{
+    fmt,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// A middleware seeing the requests and responses going through a [`TransportLayer`].
+///
+/// Both methods do nothing by default.
+///
+/// ```
+/// use alloy_json_rpc::{Response, SerializedRequest};
+/// use alloy_transport::{intercept::Interceptor, TransportErrorKind, TransportResult};
+///
+/// /// Logs the requests, and rejects the transactions sent from the client.
+/// struct Audit;
+///
+/// impl Interceptor for Audit {
+///     fn on_request(&self, request: &mut SerializedRequest) -> TransportResult<()> {
+///         if request.method() == "eth_sendTransaction" {
+///             return Err(TransportErrorKind::custom_str("unsigned transactions are not allowed"));
+///         }
+///         println!("{} {:?}", request.method(), request.params().map(|params| params.get()));
+///         Ok(())
+///     }
+///
+///     fn on_response(&self, method: &str, response: &mut Response) -> TransportResult<()> {
+///         println!("{method} {}", if response.is_success() { "ok" } else { "failed" });
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait Interceptor: Send + Sync + 'static {
+    /// Called with each request before it is sent.
+    ///
+    /// The request can be modified in place, e.g. with [`SerializedRequest::set_params`] or
+    /// [`SerializedRequest::set_authorization`]. Returning an error fails the request, or the
+    /// whole batch containing it, without sending it.
+    fn on_request(&self, request: &mut SerializedRequest) -> TransportResult<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called with each response, and the method of its request, before it is returned.
+    ///
+    /// The response can be modified in place. Returning an error fails the request, or the
+    /// whole batch containing it.
+    fn on_response(&self, method: &str, response: &mut Response) -> TransportResult<()> {
+        let _ = (method, response);
+        Ok(())
+    }
+}
+
+/// A [`Layer`] adding an [`Interceptor`] to a transport.
+pub struct TransportLayer<I> {
+    interceptor: Arc<I>,
+}
+
+impl<I> TransportLayer<I> {
+    /// Create a new layer with the given interceptor.
+    pub fn new(interceptor: I) -> Self {
+        Self { interceptor: Arc::new(interceptor) }
+    }
+
+    /// Returns the interceptor of the layer.
+    pub fn interceptor(&self) -> &I {
+        &self.interceptor
+    }
+}
+
+impl<I> Clone for TransportLayer<I> {
+    fn clone(&self) -> Self {
+        Self { interceptor: self.interceptor.clone() }
+    }
+}
+
+impl<I> fmt::Debug for TransportLayer<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransportLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S, I> Layer<S> for TransportLayer<I> {
+    type Service = InterceptService<S, I>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        InterceptService { inner, interceptor: self.interceptor.clone() }
+    }
+}
+
+/// A transport passing its requests and responses to an [`Interceptor`], created by a
+/// [`TransportLayer`].
+pub struct InterceptService<S, I> {
+    inner: S,
+    interceptor: Arc<I>,
+}
+
+impl<S: Clone, I> Clone for InterceptService<S, I> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), interceptor: self.interceptor.clone() }
+    }
+}
+
+impl<S: fmt::Debug, I> fmt::Debug for InterceptService<S, I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InterceptService").field("inner", &self.inner).finish_non_exhaustive()
+    }
+}
+
+impl<S, I> InterceptService<S, I>
+where
+    I: Interceptor,
+{
+    /// Passes the requests to the interceptor, returning their IDs and methods.
+    fn intercept_requests(&self, req: &mut RequestPacket) -> TransportResult<Vec<(Id, String)>> {
+        let requests = match req {
+            RequestPacket::Single(req) => std::slice::from_mut(req),
+            RequestPacket::Batch(reqs) => reqs.as_mut_slice(),
+        };
+        requests
+            .iter_mut()
+            .map(|request| {
+                self.interceptor.on_request(request)?;
+                Ok((request.id().clone(), request.method().to_owned()))
+            })
+            .collect()
+    }
+}
+
+impl<S, I> Service<RequestPacket> for InterceptService<S, I>
+where
+    S: Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    >,
+    I: Interceptor,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: RequestPacket) -> Self::Future {
+        let methods = match self.intercept_requests(&mut req) {
+            Ok(methods) => methods,
+            Err(err) => return Box::pin(async move { Err(err) }),
+        };
+        let interceptor = self.interceptor.clone();
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let mut resp = fut.await?;
+            let responses = match &mut resp {
+                ResponsePacket::Single(resp) => std::slice::from_mut(resp),
+                ResponsePacket::Batch(resps) => resps.as_mut_slice(),
+            };
+            for response in responses {
+                if let Some((_, method)) = methods.iter().find(|(id, _)| *id == response.id) {
+                    interceptor.on_response(method, response)?;
+                }
+            }
+            Ok(resp)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransportErrorKind;
+    use alloy_json_rpc::{Request, ResponsePayload};
+    use serde_json::value::RawValue;
+
+    /// Doubles the block numbers requested, and hides the balances returned.
+    struct Rewrite;
+
+    impl Interceptor for Rewrite {
+        fn on_request(&self, request: &mut SerializedRequest) -> TransportResult<()> {
+            if request.method() == "eth_sendRawTransaction" {
+                return Err(TransportErrorKind::custom_str("read only"));
+            }
+            let params: (u64,) = serde_json::from_str(request.params().unwrap().get()).unwrap();
+            request.set_params((params.0 * 2,)).map_err(TransportError::ser_err)
+        }
+
+        fn on_response(&self, method: &str, response: &mut Response) -> TransportResult<()> {
+            if method == "eth_getBalance" {
+                let balance = RawValue::from_string("0".into()).map_err(TransportError::ser_err)?;
+                response.payload = ResponsePayload::Success(balance);
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn intercepts_requests_and_responses() {
+        // Echoes the params of each request.
+        let inner = tower::service_fn(|req: RequestPacket| -> TransportFut<'static> {
+            let RequestPacket::Batch(reqs) = req else { unreachable!() };
+            let resps = reqs
+                .iter()
+                .map(|req| Response {
+                    id: req.id().clone(),
+                    payload: ResponsePayload::Success(req.params().unwrap().to_owned()),
+                })
+                .collect();
+            Box::pin(async move { Ok(ResponsePacket::Batch(resps)) })
+        });
+        let mut service = TransportLayer::new(Rewrite).layer(inner);
+        let request = |method, id| Request::new(method, Id::Number(id), (21u64,)).serialize();
+
+        let batch = vec![
+            request("eth_getBlockByNumber", 1).unwrap(),
+            request("eth_getBalance", 2).unwrap(),
+        ];
+        let ResponsePacket::Batch(resps) = service.call(RequestPacket::Batch(batch)).await.unwrap()
+        else {
+            unreachable!()
+        };
+        let results = resps
+            .iter()
+            .map(|resp| resp.payload.as_success().unwrap().get().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(results, ["[42]", "0"]);
+
+        let batch = vec![request("eth_sendRawTransaction", 3).unwrap()];
+        assert!(service.call(RequestPacket::Batch(batch)).await.is_err());
+    }
+}
@@ -0,0 +1,110 @@
+//! JWT authentication, as required by the authenticated Engine API port of execution clients.
+
+use crate::{Authorization, TransportError, TransportErrorKind, TransportFut};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_rpc_types_engine::{Claims, JwtSecret};
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// Issues a token with the current time as its `iat` claim.
+pub(crate) fn issue_token(secret: &JwtSecret) -> Result<String, TransportError> {
+    secret.encode(&Claims::default()).map_err(TransportErrorKind::custom)
+}
+
+/// A [`Layer`] authenticating each request with a freshly issued HS256 JWT.
+///
+/// Execution clients only accept tokens issued within the last minute, so a token is issued for
+/// every request packet, and sent as an `Authorization: Bearer` header by the HTTP transports.
+///
+/// ```no_run
+/// use alloy_rpc_types_engine::JwtSecret;
+/// use alloy_transport::JwtLayer;
+///
+/// let secret = JwtSecret::from_hex(std::fs::read_to_string("jwt.hex").unwrap()).unwrap();
+/// let layer = JwtLayer::new(secret);
+/// // Add `layer` to the client, e.g. with `ClientBuilder::layer`, before connecting to the
+/// // node's authenticated port.
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct JwtLayer {
+    secret: JwtSecret,
+}
+
+impl JwtLayer {
+    /// Create a new layer signing tokens with `secret`.
+    pub const fn new(secret: JwtSecret) -> Self {
+        Self { secret }
+    }
+}
+
+impl<S> Layer<S> for JwtLayer {
+    type Service = JwtService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        JwtService { inner, secret: self.secret }
+    }
+}
+
+/// A transport authenticating each request with a JWT, created by a [`JwtLayer`].
+#[derive(Clone, Debug)]
+pub struct JwtService<S> {
+    inner: S,
+    secret: JwtSecret,
+}
+
+impl<S> Service<RequestPacket> for JwtService<S>
+where
+    S: Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    >,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: RequestPacket) -> Self::Future {
+        match Authorization::jwt(&self.secret) {
+            Ok(auth) => {
+                req.set_authorization(Some(auth.header_value()));
+                self.inner.call(req)
+            }
+            Err(err) => Box::pin(async move { Err(err) }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request};
+    use futures_util::FutureExt;
+
+    #[test]
+    fn authenticates_requests() {
+        let secret = JwtSecret::random();
+        let echo = tower::service_fn(move |req: RequestPacket| -> TransportFut<'static> {
+            let token = req.authorization().unwrap().strip_prefix("Bearer ").unwrap();
+            secret.validate(token).unwrap();
+            Box::pin(async { Ok(ResponsePacket::Batch(vec![])) })
+        });
+        let mut service = JwtLayer::new(secret).layer(echo);
+
+        let request = Request::new("engine_exchangeCapabilities", Id::Number(0), ());
+        let batch: RequestPacket = [request.clone(), request]
+            .into_iter()
+            .map(|request| request.serialize().unwrap())
+            .collect();
+        service.call(batch).now_or_never().unwrap().unwrap();
+
+        let other = JwtSecret::random();
+        let token = Authorization::jwt(&secret).unwrap().header_value();
+        assert!(other.validate(token.strip_prefix("Bearer ").unwrap()).is_err());
+    }
+}
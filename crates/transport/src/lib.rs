@@ -32,9 +32,24 @@ pub use error::{TransportError, TransportResult};
 mod r#trait;
 pub use r#trait::Transport;
 
-pub use alloy_json_rpc::{RpcError, RpcResult};
+pub use alloy_json_rpc::{ErrorKind, RetryableError, RpcError, RpcResult};
 pub use futures_utils_wasm::{impl_future, BoxFuture};
 
+pub mod batch;
+
+pub mod cost;
+
+pub mod dual_stack;
+
+pub mod intercept;
+
+pub mod timeout;
+
+#[cfg(feature = "jwt-auth")]
+mod jwt;
+#[cfg(feature = "jwt-auth")]
+pub use jwt::{JwtLayer, JwtService};
+
 /// Misc. utilities for building transports.
 pub mod utils;
 
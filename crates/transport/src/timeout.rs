@@ -0,0 +1,179 @@
+//! Request timeouts.
+//!
+//! A [`TimeoutLayer`] abandons the requests that do not complete within a default duration,
+//! unless they set their own timeout with [`RequestMeta::set_timeout`], e.g. with
+//! `RpcCall::timeout`. This lets slow methods, like block traces, be given more time than
+//! regular ones.
+//!
+//! A request that times out, or whose future is dropped, is canceled: the future of the
+//! transport is dropped, which aborts the in-flight HTTP request instead of leaving it running
+//! in the background.
+//!
+//! Requests can also carry a deadline, set with [`RequestMeta::set_deadline`], e.g. with
+//! `RpcCall::deadline`, bounding the total time spent on them across retries. The
+//! [`RequestPacket::deadline`] of a request lets layers retrying or failing over requests
+//! respect it, and [`with_deadline`] abandons a request once it passed.
+//!
+//! [`RequestMeta::set_timeout`]: alloy_json_rpc::RequestMeta::set_timeout
+//! [`RequestMeta::set_deadline`]: alloy_json_rpc::RequestMeta::set_deadline
+
+use crate::{TransportError, TransportErrorKind, TransportFut};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use futures_util::future::{self, Either};
+use std::{
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::time::sleep;
+#[cfg(target_arch = "wasm32")]
+use wasmtimer::{std::Instant, tokio::sleep};
+
+/// Returns a future failing with [`TransportErrorKind::Timeout`] if `fut` does not complete
+/// within `duration`, in which case `fut` is dropped.
+pub fn with_timeout(fut: TransportFut<'_>, duration: Duration) -> TransportFut<'_> {
+    Box::pin(async move {
+        let timer = Box::pin(sleep(duration));
+        match future::select(fut, timer).await {
+            Either::Left((res, _)) => res,
+            Either::Right(_) => Err(TransportErrorKind::timeout(duration)),
+        }
+    })
+}
+
+/// Returns a future failing with [`TransportErrorKind::DeadlineExceeded`] if `fut` does not
+/// complete before `deadline`, in which case `fut` is dropped.
+pub fn with_deadline(fut: TransportFut<'_>, deadline: Instant) -> TransportFut<'_> {
+    Box::pin(async move {
+        // `saturating_duration_since` is not available in the browser.
+        let now = Instant::now();
+        let timer = Box::pin(sleep(if deadline > now { deadline - now } else { Duration::ZERO }));
+        match future::select(fut, timer).await {
+            Either::Left((res, _)) => res,
+            Either::Right(_) => Err(TransportErrorKind::deadline_exceeded()),
+        }
+    })
+}
+
+/// A [`Layer`] abandoning the requests that do not complete in time.
+///
+/// ```
+/// use alloy_transport::timeout::TimeoutLayer;
+/// use std::time::Duration;
+///
+/// // Add the layer to the client, e.g. with `ClientBuilder::layer`.
+/// let layer = TimeoutLayer::new(Duration::from_secs(10));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeoutLayer {
+    default: Duration,
+}
+
+impl TimeoutLayer {
+    /// Create a new layer abandoning the requests without a timeout of their own after
+    /// `default`.
+    pub const fn new(default: Duration) -> Self {
+        Self { default }
+    }
+
+    /// Returns the timeout of the requests without a timeout of their own.
+    pub const fn default_timeout(&self) -> Duration {
+        self.default
+    }
+}
+
+impl<S> Layer<S> for TimeoutLayer {
+    type Service = TimeoutService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimeoutService { inner, default: self.default }
+    }
+}
+
+/// A transport abandoning the requests that do not complete in time, created by a
+/// [`TimeoutLayer`].
+#[derive(Clone, Debug)]
+pub struct TimeoutService<S> {
+    inner: S,
+    default: Duration,
+}
+
+impl<S> Service<RequestPacket> for TimeoutService<S>
+where
+    S: Service<
+        RequestPacket,
+        Response = ResponsePacket,
+        Error = TransportError,
+        Future = TransportFut<'static>,
+    >,
+{
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let duration = req.timeout().unwrap_or(self.default);
+        with_timeout(self.inner.call(req), duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_json_rpc::{Id, Request, Response, ResponsePayload};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    /// Sets the flag when dropped.
+    struct DropGuard(Arc<AtomicBool>);
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn request_timeouts() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let flag = dropped.clone();
+        // A transport taking 5 seconds to answer.
+        let inner = tower::service_fn(move |req: RequestPacket| -> TransportFut<'static> {
+            let guard = DropGuard(flag.clone());
+            Box::pin(async move {
+                let _guard = guard;
+                sleep(Duration::from_secs(5)).await;
+                let RequestPacket::Single(req) = req else { unreachable!() };
+                let payload =
+                    ResponsePayload::Success(serde_json::value::to_raw_value(&1).unwrap());
+                Ok(ResponsePacket::Single(Response { id: req.id().clone(), payload }))
+            })
+        });
+        let mut service = TimeoutLayer::new(Duration::from_secs(1)).layer(inner);
+        let request = |timeout| {
+            let mut req = Request::new("debug_traceBlockByNumber", Id::Number(1), ());
+            req.meta.set_timeout(timeout);
+            RequestPacket::Single(req.serialize().unwrap())
+        };
+
+        let err = service.call(request(None)).await.unwrap_err();
+        assert!(
+            matches!(err.as_transport_err(), Some(TransportErrorKind::Timeout(d)) if d.as_secs() == 1)
+        );
+        // The in-flight request is dropped rather than left running.
+        assert!(dropped.load(Ordering::Relaxed));
+
+        // A longer timeout of the request overrides the default.
+        service.call(request(Some(Duration::from_secs(10)))).await.unwrap();
+    }
+}